@@ -1,10 +1,20 @@
 use crate::err::Error;
 use crate::key::change;
 use crate::kvs::Transaction;
+#[cfg(feature = "sql2")]
+use crate::kvs::{Key, Val};
+#[cfg(feature = "sql2")]
+use crate::sql::{statements::DefineTableStatement, ChangeFeed};
 use crate::vs;
 use crate::vs::Versionstamp;
+#[cfg(feature = "sql2")]
+use std::collections::HashMap;
 use std::str;
 
+// The number of change feed entries which are read at once when enforcing the size limits
+#[cfg(feature = "sql2")]
+const LIMITS_BATCH_SIZE: u32 = 1000;
+
 // gc_all_at deletes all change feed entries that become stale at the given timestamp.
 #[allow(unused)]
 pub async fn gc_all_at(tx: &mut Transaction, ts: u64, limit: Option<u32>) -> Result<(), Error> {
@@ -33,6 +43,8 @@ pub async fn gc_ns(
 		};
 		let tbs = tx.all_tb(ns, db.name.as_str()).await?;
 		let tbs = tbs.as_ref();
+		#[cfg(feature = "sql2")]
+		gc_tb_limits(tx, ns, db.name.as_str(), db.changefeed.as_ref(), tbs).await?;
 		let max_tb_cf_expiry = tbs.iter().fold(0, |acc, tb| match &tb.changefeed {
 			None => acc,
 			Some(cf) => {
@@ -50,13 +62,122 @@ pub async fn gc_ns(
 		let watermark_ts = ts - cf_expiry;
 		let watermark_vs =
 			tx.get_versionstamp_from_timestamp(watermark_ts, ns, db.name.as_str(), true).await?;
-		if let Some(watermark_vs) = watermark_vs {
+		#[cfg_attr(not(feature = "sql2"), allow(unused_mut))]
+		if let Some(mut watermark_vs) = watermark_vs {
+			// Keep the changes which have not been read by a consumer yet
+			#[cfg(feature = "sql2")]
+			for tb in tbs {
+				let cns = tx.all_tb_consumers(ns, db.name.as_str(), &tb.name).await?;
+				for cn in cns.iter() {
+					let offset =
+						tx.get_tb_consumer_offset(ns, db.name.as_str(), &tb.name, &cn.name).await?;
+					watermark_vs = watermark_vs.min(super::reader::consumer_start(offset)?);
+				}
+			}
 			gc_db(tx, ns, db.name.as_str(), watermark_vs, limit).await?;
 		}
 	}
 	Ok(())
 }
 
+// gc_tb_limits deletes the oldest change feed entries of the tables which exceed the size limits
+// of their change feed. Tables without a change feed of their own inherit the database change feed.
+// The changes which have not been read by a consumer of the table yet are always kept.
+#[cfg(feature = "sql2")]
+pub async fn gc_tb_limits(
+	tx: &mut Transaction,
+	ns: &str,
+	db: &str,
+	db_cf: Option<&ChangeFeed>,
+	tbs: &[DefineTableStatement],
+) -> Result<(), Error> {
+	// Get the limits of each table
+	let limits: HashMap<&str, &ChangeFeed> = tbs
+		.iter()
+		.filter_map(|tb| {
+			let cf = tb.changefeed.as_ref().or(db_cf)?;
+			(cf.max_changes.is_some() || cf.max_bytes.is_some()).then_some((tb.name.as_str(), cf))
+		})
+		.collect();
+	if limits.is_empty() {
+		return Ok(());
+	}
+	// Get the first change of each table which has not been read by a consumer
+	let mut unread: HashMap<&str, Versionstamp> = HashMap::new();
+	for tb in limits.keys() {
+		for cn in tx.all_tb_consumers(ns, db, tb).await?.iter() {
+			let offset = tx.get_tb_consumer_offset(ns, db, tb, &cn.name).await?;
+			let start = super::reader::consumer_start(offset)?;
+			unread.entry(tb).and_modify(|vs| *vs = (*vs).min(start)).or_insert(start);
+		}
+	}
+	// Count the entries of each table
+	let end = change::suffix(ns, db);
+	let mut beg = change::prefix(ns, db);
+	let mut used: HashMap<String, (u64, u64)> = HashMap::new();
+	loop {
+		let res = next_batch(tx, &mut beg, &end).await?;
+		for (k, v) in res.iter() {
+			let dec = change::Cf::decode(k)?;
+			if limits.contains_key(dec.tb) {
+				let (changes, bytes) = used.entry(dec.tb.to_owned()).or_default();
+				*changes += 1;
+				*bytes += (k.len() + v.len()) as u64;
+			}
+		}
+		if res.len() < LIMITS_BATCH_SIZE as usize {
+			break;
+		}
+	}
+	// Only keep the tables which exceed their limits
+	let exceeds = |tb: &str, (changes, bytes): (u64, u64)| {
+		let cf = limits[tb];
+		cf.max_changes.is_some_and(|max| changes > max)
+			|| cf.max_bytes.is_some_and(|max| bytes > max)
+	};
+	used.retain(|tb, v| exceeds(tb, *v));
+	// Delete the oldest entries until the tables fit
+	let mut beg = change::prefix(ns, db);
+	while !used.is_empty() {
+		let res = next_batch(tx, &mut beg, &end).await?;
+		for (k, v) in res.iter() {
+			let dec = change::Cf::decode(k)?;
+			let Some((changes, bytes)) = used.get_mut(dec.tb) else {
+				continue;
+			};
+			if unread.get(dec.tb).is_some_and(|vs| dec.vs >= *vs) {
+				used.remove(dec.tb);
+				continue;
+			}
+			tx.del(k.clone()).await?;
+			*changes -= 1;
+			*bytes -= (k.len() + v.len()) as u64;
+			if !exceeds(dec.tb, (*changes, *bytes)) {
+				used.remove(dec.tb);
+			}
+		}
+		if res.len() < LIMITS_BATCH_SIZE as usize {
+			break;
+		}
+	}
+	Ok(())
+}
+
+// next_batch reads the next batch of change feed entries, and moves the range past them.
+#[cfg(feature = "sql2")]
+pub(crate) async fn next_batch(
+	tx: &mut Transaction,
+	beg: &mut Vec<u8>,
+	end: &[u8],
+) -> Result<Vec<(Key, Val)>, Error> {
+	let res = tx.getr(beg.clone()..end.to_vec(), LIMITS_BATCH_SIZE).await?;
+	if let Some((k, _)) = res.last() {
+		*beg = k.clone();
+		beg.push(0x00);
+	}
+	Ok(res)
+}
+
 // gc_db deletes all change feed entries in the given database that are older than the given watermark.
 pub async fn gc_db(
 	tx: &mut Transaction,
@@ -1,6 +1,7 @@
 pub(crate) mod gc;
 pub(crate) mod mutations;
 pub(crate) mod reader;
+pub mod sync;
 pub(crate) mod writer;
 
 pub use self::gc::*;
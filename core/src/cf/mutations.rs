@@ -12,13 +12,20 @@ use std::fmt::{self, Display, Formatter};
 
 // Mutation is a single mutation to a table.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub enum TableMutation {
 	// Although the Value is supposed to contain a field "id" of Thing,
 	// we do include it in the first field for convenience.
 	Set(Thing, Value),
 	Del(Thing),
 	Def(DefineTableStatement),
+	// The same as Set, but also carrying the value of the record before the change.
+	// This is recorded when the changefeed was defined with INCLUDE ORIGINAL.
+	#[revision(start = 2)]
+	SetWithOriginal(Thing, Value, Value),
+	// The same as Del, but also carrying the value of the record before it was deleted.
+	#[revision(start = 2)]
+	DelWithOriginal(Thing, Value),
 }
 
 impl From<DefineTableStatement> for Value {
@@ -65,22 +72,30 @@ pub struct ChangeSet(pub [u8; 10], pub DatabaseMutation);
 
 impl TableMutation {
 	pub fn into_value(self) -> Value {
-		let (k, v) = match self {
-			TableMutation::Set(_t, v) => ("update".to_string(), v),
-			TableMutation::Del(t) => {
-				let mut h = BTreeMap::<String, Value>::new();
-				h.insert("id".to_string(), Value::Thing(t));
-				let o = Object::from(h);
-				("delete".to_string(), Value::Object(o))
+		let (k, v, original) = match self {
+			TableMutation::Set(_t, v) => ("update".to_string(), v, None),
+			TableMutation::SetWithOriginal(_t, v, o) => ("update".to_string(), v, Some(o)),
+			TableMutation::Del(t) => ("delete".to_string(), Self::deleted(t), None),
+			TableMutation::DelWithOriginal(t, o) => {
+				("delete".to_string(), Self::deleted(t), Some(o))
 			}
-			TableMutation::Def(t) => ("define_table".to_string(), Value::from(t)),
+			TableMutation::Def(t) => ("define_table".to_string(), Value::from(t), None),
 		};
 
 		let mut h = BTreeMap::<String, Value>::new();
 		h.insert(k, v);
+		if let Some(o) = original {
+			h.insert("original".to_string(), o);
+		}
 		let o = crate::sql::object::Object::from(h);
 		Value::Object(o)
 	}
+
+	fn deleted(t: Thing) -> Value {
+		let mut h = BTreeMap::<String, Value>::new();
+		h.insert("id".to_string(), Value::Thing(t));
+		Value::Object(Object::from(h))
+	}
 }
 
 impl DatabaseMutation {
@@ -110,7 +125,11 @@ impl Display for TableMutation {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
 			TableMutation::Set(id, v) => write!(f, "SET {} {}", id, v),
+			TableMutation::SetWithOriginal(id, v, o) => {
+				write!(f, "SET {} {} ORIGINAL {}", id, v, o)
+			}
 			TableMutation::Del(id) => write!(f, "DEL {}", id),
+			TableMutation::DelWithOriginal(id, o) => write!(f, "DEL {} ORIGINAL {}", id, o),
 			TableMutation::Def(t) => write!(f, "{}", t),
 		}
 	}
@@ -194,4 +213,35 @@ mod tests {
 			r#"{"changes":[{"update":{"id":"mytb:tobie","note":"surreal"}},{"delete":{"id":"mytb:tobie"}},{"define_table":{"name":"mytb"}}],"versionstamp":1}"#
 		);
 	}
+
+	#[test]
+	fn serialization_with_original() {
+		use super::*;
+		use std::collections::HashMap;
+		let id = Thing::from(("mytb".to_string(), "tobie".to_string()));
+		let before = Value::Object(Object::from(HashMap::from([
+			("id", Value::from(id.clone())),
+			("note", Value::from("surreal")),
+		])));
+		let after = Value::Object(Object::from(HashMap::from([
+			("id", Value::from(id.clone())),
+			("note", Value::from("surrealdb")),
+		])));
+		let cs = ChangeSet(
+			[0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+			DatabaseMutation(vec![TableMutations(
+				"mytb".to_string(),
+				vec![
+					TableMutation::SetWithOriginal(id.clone(), after.clone(), before),
+					TableMutation::DelWithOriginal(id, after),
+				],
+			)]),
+		);
+		let v = cs.into_value().into_json();
+		let s = serde_json::to_string(&v).unwrap();
+		assert_eq!(
+			s,
+			r#"{"changes":[{"original":{"id":"mytb:tobie","note":"surreal"},"update":{"id":"mytb:tobie","note":"surrealdb"}},{"delete":{"id":"mytb:tobie"},"original":{"id":"mytb:tobie","note":"surrealdb"}}],"versionstamp":1}"#
+		);
+	}
 }
@@ -35,6 +35,17 @@ pub async fn read(
 				}
 			}
 		}
+		#[cfg(feature = "sql2")]
+		ShowSince::Consumer(cn) => {
+			let tb = tb.ok_or_else(|| {
+				Error::Internal("a consumer can only read the changes of a table".to_string())
+			})?;
+			// Check the consumer exists
+			tx.get_tb_consumer(ns, db, tb, &cn).await?;
+			// Start right after the last committed offset
+			let offset = tx.get_tb_consumer_offset(ns, db, tb, &cn).await?;
+			change::prefix_ts(ns, db, consumer_start(offset)?)
+		}
 	};
 	let end = change::suffix(ns, db);
 
@@ -95,3 +106,96 @@ pub async fn read(
 
 	Ok(r)
 }
+
+// Returns the number of change sets, and the number of bytes they use, for each table of a database.
+#[cfg(feature = "sql2")]
+pub async fn usage(
+	tx: &mut Transaction,
+	ns: &str,
+	db: &str,
+) -> Result<std::collections::BTreeMap<String, (u64, u64)>, Error> {
+	let mut beg = change::prefix(ns, db);
+	let end = change::suffix(ns, db);
+	let mut res = std::collections::BTreeMap::new();
+	// Read the change sets in batches, so that large changefeeds are not loaded at once
+	loop {
+		let batch = super::gc::next_batch(tx, &mut beg, &end).await?;
+		if batch.is_empty() {
+			break;
+		}
+		for (k, v) in batch {
+			let dec = change::Cf::decode(&k)?;
+			let (changes, bytes) = res.entry(dec.tb.to_owned()).or_insert((0, 0));
+			*changes += 1;
+			*bytes += (k.len() + v.len()) as u64;
+		}
+	}
+	Ok(res)
+}
+
+// Returns the first versionstamp a changefeed consumer still has to read.
+// The committed offset is the last versionstamp as returned by SHOW CHANGES.
+#[cfg(feature = "sql2")]
+pub(crate) fn consumer_start(offset: Option<u64>) -> Result<vs::Versionstamp, Error> {
+	match offset {
+		Some(v) => Ok(vs::try_u128_to_versionstamp(v as u128 + 1)?),
+		None => Ok([0; 10]),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::cf::{ChangeSet, DatabaseMutation, TableMutation, TableMutations};
+	use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+	use crate::sql::statements::show::ShowSince;
+	use crate::sql::thing::Thing;
+	use crate::sql::value::Value;
+	use crate::vs;
+	use revision::{revisioned, Revisioned};
+
+	// The table mutations as they were stored before
+	// the original values of records could be recorded.
+	#[revisioned(revision = 1)]
+	enum TableMutationV1 {
+		Set(Thing, Value),
+		Del(Thing),
+	}
+
+	#[revisioned(revision = 1)]
+	struct TableMutationsV1(String, Vec<TableMutationV1>);
+
+	#[tokio::test]
+	async fn read_changes_in_previous_format() {
+		let (ns, db, tb) = ("myns", "mydb", "mytb");
+		let id = Thing::from((tb.to_string(), "A".to_string()));
+		let old = TableMutationsV1(
+			tb.to_string(),
+			vec![
+				TableMutationV1::Set(id.clone(), Value::from("a")),
+				TableMutationV1::Del(id.clone()),
+			],
+		);
+		let mut val = Vec::new();
+		old.serialize_revisioned(&mut val).unwrap();
+
+		let ds = Datastore::new("memory").await.unwrap();
+		let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.set(crate::key::change::new(ns, db, 1, tb), val).await.unwrap();
+		tx.commit().await.unwrap();
+
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		let r = super::read(&mut tx, ns, db, Some(tb), ShowSince::Versionstamp(0), Some(10))
+			.await
+			.unwrap();
+		tx.cancel().await.unwrap();
+
+		let want = vec![ChangeSet(
+			vs::u64_to_versionstamp(1),
+			DatabaseMutation(vec![TableMutations(
+				tb.to_string(),
+				vec![TableMutation::Set(id.clone(), Value::from("a")), TableMutation::Del(id)],
+			)]),
+		)];
+		assert_eq!(r, want);
+	}
+}
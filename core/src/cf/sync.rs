@@ -0,0 +1,539 @@
+//! Replicates the records of a database between datastores, so that a replica,
+//! like an embedded datastore in an application, can be used while offline and
+//! reconciled with the server later.
+//!
+//! A replica pulls the changes made to a database since a versionstamp, which
+//! are read from the changefeed, so only tables with a changefeed replicate.
+//! It pushes the changes made to its own records in batches. A pushed change
+//! conflicts when the record was changed after the versionstamp the replica
+//! last pulled from, and the conflict is resolved for each record, either by
+//! keeping the last written change, or with a user-defined function. Only
+//! owners can push changes which replicate another datastore, as they are not
+//! recorded in the changefeed.
+//!
+//! The time at which a change was written is only recorded to the precision of
+//! the datastore tick, which saves the time of the latest versionstamp. A change
+//! is taken to be written at the first tick after it, or when it is read if no
+//! tick has run since, so that a pushed change only replaces a change on the
+//! server when it was certainly written after it.
+//!
+//! Changes which are already applied are skipped, so that a change does not
+//! echo back and forth between a replica and the server.
+use crate::cf::{ChangeSet, DatabaseMutation, TableMutation, TableMutations};
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::statements::show::ShowSince;
+use crate::sql::statements::{DeleteStatement, SelectStatement, UpdateStatement};
+use crate::sql::{
+	Base, Data, Datetime, Field, Fields, Function, Number, Object, Output, Thing, Value, Values,
+};
+use crate::vs::{self, Versionstamp};
+use chrono::{TimeZone, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The number of changes to the tables which are read at once to find conflicts
+const CONFLICTS_BATCH_SIZE: u32 = 1000;
+
+/// The number of tick timestamps which are read at once to find when changes were written
+const TIMESTAMPS_BATCH_SIZE: u32 = 1000;
+
+/// A change to a record of a database
+///
+/// A change carries the time at which it was written, if known. A pushed change
+/// without a time is taken to be written when it is pushed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+	/// The record was created or updated, with its new content
+	Update(Thing, Value, Option<Datetime>),
+	/// The record was deleted
+	Delete(Thing, Option<Datetime>),
+}
+
+/// The changes made to the records of a database since a versionstamp
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Changes {
+	/// The versionstamp to pull the next changes from
+	pub versionstamp: u64,
+	/// The changes, in the order in which they were made
+	pub changes: Vec<Change>,
+}
+
+/// How a pushed change is resolved when the record was changed since it was pulled
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Resolution {
+	/// The pushed change replaces the record if it was written at the same time
+	/// as, or after, the change to the record, and is discarded otherwise
+	#[default]
+	LastWriterWins,
+	/// A `DEFINE FUNCTION` is called with the current and the pushed record,
+	/// and the record is replaced with its result, or deleted if it is NONE
+	Function(String),
+}
+
+/// A batch of changes which a replica pushes to a datastore
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Push {
+	/// The versionstamp the replica last pulled from, without which no
+	/// conflicts are detected
+	pub since: Option<u64>,
+	/// The changes made by the replica
+	pub changes: Vec<Change>,
+	/// How conflicting changes are resolved
+	pub resolution: Resolution,
+	/// Whether these are changes pulled from another datastore, which are
+	/// not recorded in the changefeed, so that they are not pushed back.
+	/// Only owners can replicate changes.
+	pub replicate: bool,
+}
+
+impl Change {
+	/// The record which was changed
+	pub fn id(&self) -> &Thing {
+		match self {
+			Self::Update(id, _, _) => id,
+			Self::Delete(id, _) => id,
+		}
+	}
+
+	/// The time at which the change was written, if known
+	pub fn written(&self) -> Option<&Datetime> {
+		match self {
+			Self::Update(_, _, at) => at.as_ref(),
+			Self::Delete(_, at) => at.as_ref(),
+		}
+	}
+}
+
+impl From<Change> for Value {
+	fn from(v: Change) -> Self {
+		let mut h = BTreeMap::<String, Value>::new();
+		let at = match v {
+			Change::Update(_, v, at) => {
+				h.insert("update".to_string(), v);
+				at
+			}
+			Change::Delete(id, at) => {
+				h.insert(
+					"delete".to_string(),
+					Object::from(map! { "id".to_string() => Value::from(id) }).into(),
+				);
+				at
+			}
+		};
+		if let Some(at) = at {
+			h.insert("at".to_string(), Value::from(at));
+		}
+		Value::Object(Object::from(h))
+	}
+}
+
+impl TryFrom<Value> for Change {
+	type Error = Error;
+	fn try_from(v: Value) -> Result<Self, Self::Error> {
+		let Value::Object(mut v) = v else {
+			return Err(Error::InvalidChanges(format!("expected a change, found {v}")));
+		};
+		let at = datetime(v.remove("at"))?;
+		match (v.remove("update"), v.remove("delete")) {
+			(Some(Value::Object(mut v)), None) => {
+				let id = thing(v.remove("id"))?;
+				v.insert("id".to_string(), id.clone().into());
+				Ok(Self::Update(id, v.into(), at))
+			}
+			(None, Some(Value::Object(mut v))) => Ok(Self::Delete(thing(v.remove("id"))?, at)),
+			_ => Err(Error::InvalidChanges(
+				"expected a change with either an `update` or a `delete` object".to_string(),
+			)),
+		}
+	}
+}
+
+impl From<Changes> for Value {
+	fn from(v: Changes) -> Self {
+		Value::Object(Object::from(map! {
+			"versionstamp".to_string() => Value::from(v.versionstamp),
+			"changes".to_string() => Value::Array(v.changes.into_iter().map(Value::from).collect()),
+		}))
+	}
+}
+
+impl TryFrom<Value> for Changes {
+	type Error = Error;
+	fn try_from(v: Value) -> Result<Self, Self::Error> {
+		let Value::Object(mut v) = v else {
+			return Err(Error::InvalidChanges(format!("expected the changes, found {v}")));
+		};
+		Ok(Self {
+			versionstamp: match v.remove("versionstamp") {
+				Some(Value::Number(Number::Int(n))) if n >= 0 => n as u64,
+				v => {
+					return Err(Error::InvalidChanges(format!(
+						"expected a versionstamp, found {}",
+						v.unwrap_or_default()
+					)))
+				}
+			},
+			changes: changes(v.remove("changes"))?,
+		})
+	}
+}
+
+impl From<Changes> for Vec<Change> {
+	fn from(v: Changes) -> Self {
+		v.changes
+	}
+}
+
+impl From<Push> for Value {
+	fn from(v: Push) -> Self {
+		let mut h = map! {
+			"changes".to_string() => Value::Array(v.changes.into_iter().map(Value::from).collect()),
+			"replicate".to_string() => Value::from(v.replicate),
+		};
+		if let Some(since) = v.since {
+			h.insert("since".to_string(), Value::from(since));
+		}
+		if let Resolution::Function(name) = v.resolution {
+			h.insert("resolve".to_string(), Value::from(name));
+		}
+		Value::Object(Object::from(h))
+	}
+}
+
+impl TryFrom<Value> for Push {
+	type Error = Error;
+	fn try_from(v: Value) -> Result<Self, Self::Error> {
+		let Value::Object(mut v) = v else {
+			return Err(Error::InvalidChanges(format!("expected the changes to push, found {v}")));
+		};
+		Ok(Self {
+			since: match v.remove("since") {
+				None | Some(Value::None) | Some(Value::Null) => None,
+				Some(Value::Number(Number::Int(n))) if n >= 0 => Some(n as u64),
+				Some(v) => {
+					return Err(Error::InvalidChanges(format!(
+						"expected a versionstamp, found {v}"
+					)))
+				}
+			},
+			changes: changes(v.remove("changes"))?,
+			resolution: match v.remove("resolve") {
+				None | Some(Value::None) | Some(Value::Null) => Resolution::LastWriterWins,
+				Some(Value::Strand(s)) => {
+					Resolution::Function(s.0.trim_start_matches("fn::").to_string())
+				}
+				Some(v) => {
+					return Err(Error::InvalidChanges(format!(
+						"expected the name of a function to resolve conflicts, found {v}"
+					)))
+				}
+			},
+			replicate: v.remove("replicate").is_some_and(|v| v.is_true()),
+		})
+	}
+}
+
+/// Parses a list of changes
+fn changes(v: Option<Value>) -> Result<Vec<Change>, Error> {
+	match v {
+		Some(Value::Array(v)) => v.into_iter().map(Change::try_from).collect(),
+		v => Err(Error::InvalidChanges(format!(
+			"expected a list of changes, found {}",
+			v.unwrap_or_default()
+		))),
+	}
+}
+
+/// Parses the id of a changed record, which JSON clients send as a string
+fn thing(v: Option<Value>) -> Result<Thing, Error> {
+	match v {
+		Some(Value::Thing(v)) => Ok(v),
+		Some(Value::Strand(v)) => crate::syn::thing(&v)
+			.map_err(|_| Error::InvalidChanges(format!("expected a record id, found '{v}'"))),
+		v => Err(Error::InvalidChanges(format!(
+			"expected a record id, found {}",
+			v.unwrap_or_default()
+		))),
+	}
+}
+
+/// Parses the time at which a change was written, which JSON clients send as a string
+fn datetime(v: Option<Value>) -> Result<Option<Datetime>, Error> {
+	match v {
+		None | Some(Value::None) | Some(Value::Null) => Ok(None),
+		Some(Value::Datetime(v)) => Ok(Some(v)),
+		Some(Value::Strand(v)) => Datetime::try_from(v.as_str())
+			.map(Some)
+			.map_err(|_| Error::InvalidChanges(format!("expected a datetime, found '{v}'"))),
+		Some(v) => Err(Error::InvalidChanges(format!("expected a datetime, found {v}"))),
+	}
+}
+
+/// Returns the time at which the changes with each of the sorted versionstamps were
+/// written, which is the time of the first datastore tick after each versionstamp.
+/// The changes made since the last tick are taken to be written now.
+async fn written(
+	run: &mut crate::kvs::Transaction,
+	ns: &str,
+	db: &str,
+	vss: &[Versionstamp],
+) -> Result<Vec<Datetime>, Error> {
+	let now = Datetime::default();
+	let mut res = Vec::with_capacity(vss.len());
+	let mut beg = crate::key::database::ts::prefix(ns, db);
+	let end = crate::key::database::ts::suffix(ns, db);
+	// The timestamps of the ticks are read in batches, in the order of their versionstamps
+	while res.len() < vss.len() {
+		let batch = run.getr(beg.clone()..end.clone(), TIMESTAMPS_BATCH_SIZE).await?;
+		let Some((k, _)) = batch.last() else {
+			break;
+		};
+		beg = k.clone();
+		beg.push(0x00);
+		for (k, v) in batch.iter() {
+			let ts = crate::key::database::ts::Ts::decode(k)?.ts;
+			let vs = Versionstamp::try_from(v.as_slice())
+				.map_err(|_| Error::Internal("versionstamp is not 10 bytes".to_string()))?;
+			while res.len() < vss.len() && vss[res.len()] < vs {
+				res.push(match Utc.timestamp_opt(ts as i64, 0).single() {
+					Some(v) => v.into(),
+					None => now.clone(),
+				});
+			}
+		}
+	}
+	res.resize(vss.len(), now);
+	Ok(res)
+}
+
+/// Reads the change sets of the current database since a versionstamp, along with
+/// whether there are more change sets to read
+///
+/// The limit is the number of changes to the tables to read. A change set which is cut
+/// off by the limit is left to be read next, and when the first change set does not fit
+/// within the limit, it is read again with a larger limit, so that it is never truncated.
+async fn read(
+	run: &mut crate::kvs::Transaction,
+	ns: &str,
+	db: &str,
+	since: u64,
+	mut limit: u32,
+) -> Result<(Vec<ChangeSet>, bool), Error> {
+	loop {
+		let start = ShowSince::Versionstamp(since);
+		let mut sets = super::read(run, ns, db, None, start, Some(limit)).await?;
+		let read = sets.iter().map(|ChangeSet(_, DatabaseMutation(v))| v.len()).sum::<usize>();
+		if read < limit as usize {
+			return Ok((sets, false));
+		}
+		if sets.len() > 1 {
+			sets.pop();
+			return Ok((sets, true));
+		}
+		limit = limit.saturating_mul(2).max(1);
+	}
+}
+
+/// Reads the changes made to the records of the current database since a versionstamp
+///
+/// The limit is the maximum number of changes to the tables to read. The changes
+/// made at a versionstamp are always returned in full, even when they are more
+/// than the limit.
+pub(crate) async fn pull(
+	opt: &Options,
+	txn: &Transaction,
+	since: u64,
+	limit: Option<u32>,
+) -> Result<Changes, Error> {
+	// Allowed to run?
+	opt.is_allowed(Action::View, ResourceKind::Table, &Base::Db)?;
+	// Read the changefeed of the database
+	let limit = limit.unwrap_or(100);
+	let mut run = txn.lock().await;
+	let (sets, _) = read(&mut run, opt.ns(), opt.db(), since, limit).await?;
+	// Process the change sets
+	let vss: Vec<Versionstamp> = sets.iter().map(|ChangeSet(vs, _)| *vs).collect();
+	let times = written(&mut run, opt.ns(), opt.db(), &vss).await?;
+	let mut res = Changes {
+		versionstamp: since,
+		changes: Vec::new(),
+	};
+	for (ChangeSet(vs, DatabaseMutation(tbs)), at) in sets.into_iter().zip(times) {
+		res.versionstamp = vs::try_to_u64_be(vs)? + 1;
+		for TableMutations(_, muts) in tbs {
+			for m in muts {
+				let at = Some(at.clone());
+				res.changes.push(match m {
+					TableMutation::Set(id, v) => Change::Update(id, v, at),
+					TableMutation::SetWithOriginal(id, v, _) => Change::Update(id, v, at),
+					TableMutation::Del(id) => Change::Delete(id, at),
+					TableMutation::DelWithOriginal(id, _) => Change::Delete(id, at),
+					TableMutation::Def(_) => continue,
+				});
+			}
+		}
+	}
+	Ok(res)
+}
+
+/// Applies a batch of changes to the records of the current database, returning the
+/// records with conflicting changes
+pub(crate) async fn push(
+	ctx: &Context<'_>,
+	opt: &Options,
+	txn: &Transaction,
+	push: Push,
+) -> Result<Vec<Thing>, Error> {
+	// Only owners can apply changes which are not recorded in the changefeed
+	if push.replicate {
+		opt.is_allowed(Action::Edit, ResourceKind::Any, &Base::Db)?;
+	}
+	// Find the pushed records which were changed since the replica last pulled
+	let ids: HashSet<String> = push.changes.iter().map(|c| c.id().to_string()).collect();
+	let mut changed = HashMap::new();
+	if let Some(mut since) = push.since {
+		let mut run = txn.lock().await;
+		loop {
+			let (sets, more) =
+				read(&mut run, opt.ns(), opt.db(), since, CONFLICTS_BATCH_SIZE).await?;
+			let Some(ChangeSet(last, _)) = sets.last() else {
+				break;
+			};
+			let next = match more {
+				true => Some(vs::try_to_u64_be(*last)? + 1),
+				false => None,
+			};
+			for ChangeSet(vs, DatabaseMutation(tbs)) in sets {
+				for TableMutations(_, muts) in tbs {
+					for m in muts {
+						let id = match m {
+							TableMutation::Set(id, _)
+							| TableMutation::SetWithOriginal(id, _, _)
+							| TableMutation::Del(id)
+							| TableMutation::DelWithOriginal(id, _) => id.to_string(),
+							TableMutation::Def(_) => continue,
+						};
+						if ids.contains(&id) {
+							changed.insert(id, vs);
+						}
+					}
+				}
+			}
+			match next {
+				Some(next) => since = next,
+				None => break,
+			}
+		}
+	}
+	// Find when the records were last changed, to keep the last written changes
+	let changed: HashMap<String, Datetime> = match push.resolution {
+		Resolution::LastWriterWins if !changed.is_empty() => {
+			let mut changed: Vec<(String, Versionstamp)> = changed.into_iter().collect();
+			changed.sort_unstable_by_key(|(_, vs)| *vs);
+			let vss: Vec<Versionstamp> = changed.iter().map(|(_, vs)| *vs).collect();
+			let times = written(&mut *txn.lock().await, opt.ns(), opt.db(), &vss).await?;
+			changed.into_iter().map(|(id, _)| id).zip(times).collect()
+		}
+		_ => changed.into_keys().map(|id| (id, Datetime::default())).collect(),
+	};
+	// Apply the changes in order
+	let now = Datetime::default();
+	let mut conflicts = Vec::new();
+	for change in push.changes {
+		let (id, val, at) = match change {
+			Change::Update(id, val, at) => (id, val, at),
+			Change::Delete(id, at) => (id, Value::None, at),
+		};
+		// Fetch the current record
+		let cur = SelectStatement {
+			expr: Fields(vec![Field::All], false),
+			what: Values(vec![Value::from(id.clone())]),
+			..SelectStatement::default()
+		}
+		.compute(ctx, opt, txn, None)
+		.await?
+		.first();
+		// Skip changes which are already applied
+		if cur == val {
+			continue;
+		}
+		// Resolve a conflicting change
+		let val = match (changed.get(&id.to_string()), &push.resolution) {
+			(None, _) => val,
+			(Some(current), Resolution::LastWriterWins) => {
+				conflicts.push(id.clone());
+				// Keep the current record if it was written after the pushed change
+				if at.as_ref().unwrap_or(&now) < current {
+					continue;
+				}
+				val
+			}
+			(Some(_), Resolution::Function(name)) => {
+				conflicts.push(id.clone());
+				let fnc = Function::Custom(name.clone(), vec![cur, val]);
+				Value::Function(Box::new(fnc)).compute(ctx, opt, txn, None).await?
+			}
+		};
+		// Store or delete the record
+		let what = Values(vec![Value::from(id)]);
+		if val.is_none_or_null() {
+			DeleteStatement {
+				what,
+				output: Some(Output::None),
+				..DeleteStatement::default()
+			}
+			.compute(ctx, opt, txn, None)
+			.await?;
+		} else {
+			UpdateStatement {
+				what,
+				data: Some(Data::ContentExpression(val)),
+				output: Some(Output::None),
+				..UpdateStatement::default()
+			}
+			.compute(ctx, opt, txn, None)
+			.await?;
+		}
+	}
+	Ok(conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::cf::{ChangeSet, DatabaseMutation, TableMutation, TableMutations};
+	use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+	use crate::sql::{Thing, Value};
+	use crate::vs;
+	use revision::Revisioned;
+
+	#[tokio::test]
+	async fn read_does_not_truncate_change_sets() {
+		let (ns, db) = ("myns", "mydb");
+		let ds = Datastore::new("memory").await.unwrap();
+		// Some datastores record the changes to all tables of a transaction at one versionstamp
+		let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+		for (ts, tb) in [(1, "a"), (1, "b"), (1, "c"), (2, "a")] {
+			let id = Thing::from((tb.to_string(), ts.to_string()));
+			let muts = TableMutations(tb.to_string(), vec![TableMutation::Set(id, Value::None)]);
+			let mut val = Vec::new();
+			muts.serialize_revisioned(&mut val).unwrap();
+			tx.set(crate::key::change::new(ns, db, ts, tb), val).await.unwrap();
+		}
+		tx.commit().await.unwrap();
+		// The first change set is read in full, even though it is larger than the limit
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		let (sets, more) = super::read(&mut tx, ns, db, 0, 2).await.unwrap();
+		assert!(more);
+		assert_eq!(sets.len(), 1);
+		let ChangeSet(v, DatabaseMutation(tbs)) = &sets[0];
+		assert_eq!(*v, vs::u64_to_versionstamp(1));
+		assert_eq!(tbs.len(), 3);
+		// The next change set is read from the versionstamp after it
+		let (sets, more) = super::read(&mut tx, ns, db, 2, 2).await.unwrap();
+		assert!(!more);
+		assert_eq!(sets.len(), 1);
+		tx.cancel().await.unwrap();
+	}
+}
@@ -72,6 +72,24 @@ impl Writer {
 		}
 	}
 
+	#[cfg(feature = "sql2")]
+	pub(crate) fn update_with_original(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		id: Thing,
+		previous: Cow<'_, Value>,
+		current: Cow<'_, Value>,
+	) {
+		let m = if current.is_some() {
+			TableMutation::SetWithOriginal(id, current.into_owned(), previous.into_owned())
+		} else {
+			TableMutation::DelWithOriginal(id, previous.into_owned())
+		};
+		self.buf.push(ns.to_string(), db.to_string(), tb.to_string(), m);
+	}
+
 	pub(crate) fn define_table(&mut self, ns: &str, db: &str, tb: &str, dt: &DefineTableStatement) {
 		self.buf.push(
 			ns.to_string(),
@@ -137,6 +155,12 @@ mod tests {
 			name: crate::sql::Ident(db.to_string()),
 			changefeed: Some(ChangeFeed {
 				expiry: Duration::from_secs(10),
+				#[cfg(feature = "sql2")]
+				store_original: false,
+				#[cfg(feature = "sql2")]
+				max_changes: None,
+				#[cfg(feature = "sql2")]
+				max_bytes: None,
 			}),
 			..Default::default()
 		};
@@ -144,6 +168,12 @@ mod tests {
 			name: tb.into(),
 			changefeed: Some(ChangeFeed {
 				expiry: Duration::from_secs(10),
+				#[cfg(feature = "sql2")]
+				store_original: false,
+				#[cfg(feature = "sql2")]
+				max_changes: None,
+				#[cfg(feature = "sql2")]
+				max_bytes: None,
 			}),
 			..Default::default()
 		};
@@ -36,6 +36,45 @@ pub const SERVER_NAME: &str = "SurrealDB";
 /// Datastore processor batch size for scan operations
 pub const PROCESSOR_BATCH_SIZE: u32 = 50;
 
+/// The number of records indexed in each transaction when an index is built concurrently
+pub static INDEX_BUILD_BATCH_SIZE: Lazy<u32> = Lazy::new(|| {
+	option_env!("SURREAL_INDEX_BUILD_BATCH_SIZE").and_then(|s| s.parse::<u32>().ok()).unwrap_or(250)
+});
+
+/// The maximum number of batches of records indexed on each datastore tick, across all the indexes being built
+pub static INDEX_BUILD_BATCHES_PER_TICK: Lazy<u32> = Lazy::new(|| {
+	option_env!("SURREAL_INDEX_BUILD_BATCHES_PER_TICK")
+		.and_then(|s| s.parse::<u32>().ok())
+		.unwrap_or(10)
+});
+
+/// The maximum number of webhook payloads delivered on each datastore tick
+pub static WEBHOOK_BATCH_SIZE: Lazy<u32> = Lazy::new(|| {
+	option_env!("SURREAL_WEBHOOK_BATCH_SIZE").and_then(|s| s.parse::<u32>().ok()).unwrap_or(100)
+});
+
+/// The maximum number of webhook payloads which are delivered concurrently
+pub static WEBHOOK_CONCURRENCY: Lazy<usize> = Lazy::new(|| {
+	option_env!("SURREAL_WEBHOOK_CONCURRENCY").and_then(|s| s.parse::<usize>().ok()).unwrap_or(10)
+});
+
+/// The number of seconds after which no new webhook deliveries are started on each run
+pub static WEBHOOK_DELIVERY_BUDGET: Lazy<u64> = Lazy::new(|| {
+	option_env!("SURREAL_WEBHOOK_DELIVERY_BUDGET").and_then(|s| s.parse::<u64>().ok()).unwrap_or(30)
+});
+
+/// The number of seconds to wait for a webhook endpoint to respond
+pub static WEBHOOK_TIMEOUT: Lazy<u64> = Lazy::new(|| {
+	option_env!("SURREAL_WEBHOOK_TIMEOUT").and_then(|s| s.parse::<u64>().ok()).unwrap_or(10)
+});
+
+/// The number of seconds for which a refresh token can be used
+pub static REFRESH_TOKEN_EXPIRY: Lazy<i64> = Lazy::new(|| {
+	option_env!("SURREAL_REFRESH_TOKEN_EXPIRY")
+		.and_then(|s| s.parse::<i64>().ok())
+		.unwrap_or(30 * 24 * 60 * 60)
+});
+
 /// Forward all signup/signin query errors to a client trying authenticate to a scope. Do not use in production.
 pub static INSECURE_FORWARD_SCOPE_ERRORS: Lazy<bool> = Lazy::new(|| {
 	option_env!("SURREAL_INSECURE_FORWARD_SCOPE_ERRORS")
@@ -5,9 +5,11 @@ use crate::dbs::capabilities::FuncTarget;
 use crate::dbs::capabilities::NetTarget;
 use crate::dbs::{Capabilities, Notification};
 use crate::err::Error;
+use crate::iam::password::PasswordPolicy;
 use crate::idx::planner::executor::QueryExecutor;
 use crate::idx::planner::{IterationStage, QueryPlanner};
 use crate::idx::trees::store::IndexStores;
+use crate::kvs::keyring::Keyring;
 use crate::sql::value::Value;
 use channel::Sender;
 use std::borrow::Cow;
@@ -53,6 +55,10 @@ pub struct Context<'a> {
 	index_stores: IndexStores,
 	// Capabilities
 	capabilities: Arc<Capabilities>,
+	// The rules for the passwords of system users
+	password_policy: Arc<PasswordPolicy>,
+	// The keys which encrypt the values of fields
+	keyring: Arc<Keyring>,
 }
 
 impl<'a> Default for Context<'a> {
@@ -89,6 +95,8 @@ impl<'a> Context<'a> {
 			iteration_stage: None,
 			capabilities: Arc::new(capabilities),
 			index_stores,
+			password_policy: Arc::new(PasswordPolicy::default()),
+			keyring: Arc::new(Keyring::default()),
 		};
 		if let Some(timeout) = time_out {
 			ctx.add_timeout(timeout)?;
@@ -108,6 +116,8 @@ impl<'a> Context<'a> {
 			iteration_stage: None,
 			capabilities: Arc::new(Capabilities::default()),
 			index_stores: IndexStores::default(),
+			password_policy: Arc::new(PasswordPolicy::default()),
+			keyring: Arc::new(Keyring::default()),
 		}
 	}
 
@@ -124,6 +134,8 @@ impl<'a> Context<'a> {
 			iteration_stage: parent.iteration_stage.clone(),
 			capabilities: parent.capabilities.clone(),
 			index_stores: parent.index_stores.clone(),
+			password_policy: parent.password_policy.clone(),
+			keyring: parent.keyring.clone(),
 		}
 	}
 
@@ -280,6 +292,28 @@ impl<'a> Context<'a> {
 		self.capabilities.clone()
 	}
 
+	/// Set the rules for the passwords of system users
+	pub(crate) fn add_password_policy(&mut self, policy: Arc<PasswordPolicy>) {
+		self.password_policy = policy;
+	}
+
+	/// Get the rules for the passwords of system users
+	#[allow(dead_code)]
+	pub(crate) fn get_password_policy(&self) -> &PasswordPolicy {
+		&self.password_policy
+	}
+
+	/// Set the keys which encrypt the values of fields
+	pub(crate) fn add_keyring(&mut self, keyring: Arc<Keyring>) {
+		self.keyring = keyring;
+	}
+
+	/// Get the keys which encrypt the values of fields
+	#[allow(dead_code)]
+	pub(crate) fn get_keyring(&self) -> &Keyring {
+		&self.keyring
+	}
+
 	/// Check if scripting is allowed
 	#[allow(dead_code)]
 	pub fn check_allowed_scripting(&self) -> Result<(), Error> {
@@ -7,9 +7,11 @@ use crate::dbs::Transaction;
 use crate::err::Error;
 use crate::iam::Action;
 use crate::iam::ResourceKind;
+use crate::kvs::audit::{self, AuditClass, AuditLog, Event};
 use crate::kvs::TransactionType;
 use crate::kvs::{Datastore, LockType::*, TransactionType::*};
 use crate::sql::paths::DB;
+use crate::sql::paths::IP;
 use crate::sql::paths::NS;
 use crate::sql::query::Query;
 use crate::sql::statement::Statement;
@@ -164,6 +166,21 @@ impl<'a> Executor<'a> {
 		});
 	}
 
+	/// Record the outcome of a statement in the audit log
+	fn audit(
+		ctx: &Context<'_>,
+		opt: &Options,
+		(log, class, detail): (&AuditLog, AuditClass, String),
+		res: &Result<Value, Error>,
+	) {
+		// Get the client address from the session
+		let ip = match ctx.value("session").map(|v| v.pick(IP.as_ref())) {
+			Some(Value::Strand(v)) => Some(v.0),
+			_ => None,
+		};
+		log.record(Event::new(class, &opt.auth, ip, detail), res);
+	}
+
 	async fn set_ns(&self, ctx: &mut Context<'_>, opt: &mut Options, ns: &str) {
 		let mut session = ctx.value("session").unwrap_or(&Value::None).clone();
 		session.put(NS.as_ref(), ns.to_owned().into());
@@ -209,6 +226,10 @@ impl<'a> Executor<'a> {
 			let is_stm_kill = matches!(stm, Statement::Kill(_));
 			// Check if this is a RETURN statement
 			let is_stm_output = matches!(stm, Statement::Output(_));
+			// Check if this statement is recorded in the audit log
+			let audit = audit::classify(&stm).and_then(|class| {
+				self.kvs.audit_log(class).map(|log| (log, class, audit::describe(&stm)))
+			});
 			// Process a single statement
 			let res = match stm {
 				// Specify runtime options
@@ -360,9 +381,16 @@ impl<'a> Executor<'a> {
 										// Clear live query notification details
 										self.clear(&ctx, recv.clone()).await;
 										// The commit failed
-										Err(Error::QueryNotExecutedDetail {
-											message: e.to_string(),
-										})
+										match e {
+											// A violation of a deferred unique index is reported
+											// as it is for an index checked when writing
+											e @ Error::IndexExists {
+												..
+											} => Err(e),
+											e => Err(Error::QueryNotExecutedDetail {
+												message: e.to_string(),
+											}),
+										}
 									} else {
 										// Flush the live query change notifications
 										self.flush(&ctx, recv.clone()).await;
@@ -381,6 +409,10 @@ impl<'a> Executor<'a> {
 					}
 				},
 			};
+			// Record the statement in the audit log
+			if let Some(audit) = audit {
+				Self::audit(&ctx, &opt, audit, &res);
+			}
 			// Produce the response
 			let res = Response {
 				// Get the statement end time
@@ -5,11 +5,14 @@ use std::fmt::{self, Debug, Display};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub enum Action {
 	Create,
 	Update,
 	Delete,
+	/// The LIVE query was ended by the database, rather than by the client
+	#[revision(start = 2)]
+	Killed,
 }
 
 impl Display for Action {
@@ -18,19 +21,40 @@ impl Display for Action {
 			Action::Create => write!(f, "CREATE"),
 			Action::Update => write!(f, "UPDATE"),
 			Action::Delete => write!(f, "DELETE"),
+			Action::Killed => write!(f, "KILLED"),
 		}
 	}
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub struct Notification {
 	/// The id of the LIVE query to which this notification belongs
 	pub id: Uuid,
-	/// The CREATE / UPDATE / DELETE action which caused this notification
+	/// The CREATE / UPDATE / DELETE / KILLED action which caused this notification
 	pub action: Action,
-	/// The resulting notification content, usually the altered record content
+	/// The id of the record which was altered, or NONE when the LIVE query was killed
+	#[revision(start = 2)]
+	pub record: Value,
+	/// The resulting notification content, usually the altered record content,
+	/// or the reason why the LIVE query was killed
 	pub result: Value,
+	/// The record content before it was altered, when requested with `WITH BEFORE`
+	#[revision(start = 2)]
+	pub before: Value,
+}
+
+impl Notification {
+	/// Creates a notification informing the client that a LIVE query has been killed
+	pub fn killed(id: Uuid, reason: impl Into<String>) -> Self {
+		Notification {
+			id,
+			action: Action::Killed,
+			record: Value::None,
+			result: Value::from(reason.into()),
+			before: Value::None,
+		}
+	}
 }
 
 impl Display for Notification {
@@ -38,7 +62,32 @@ impl Display for Notification {
 		let obj: Object = map! {
 			"id".to_string() => self.id.to_string().into(),
 			"action".to_string() => self.action.to_string().into(),
+			"record".to_string() => self.record.clone(),
 			"result".to_string() => self.result.clone(),
+			"before".to_string() => self.before.clone(),
+		}
+		.into();
+		write!(f, "{}", obj)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[revisioned(revision = 1)]
+pub struct ChangeNotification {
+	/// The id of the changefeed subscription to which this notification belongs
+	pub id: Uuid,
+	/// The versionstamp at which these changes were committed
+	pub versionstamp: u128,
+	/// The changes which were committed at this versionstamp
+	pub changes: Value,
+}
+
+impl Display for ChangeNotification {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let obj: Object = map! {
+			"id".to_string() => self.id.to_string().into(),
+			"versionstamp".to_string() => self.versionstamp.into(),
+			"changes".to_string() => self.changes.clone(),
 		}
 		.into();
 		write!(f, "{}", obj)
@@ -477,6 +477,18 @@ impl Options {
 		self.auth.is_allowed(action, &res).map_err(Error::IamError)
 	}
 
+	/// Whether or not to check table policies
+	///
+	/// Unlike table permissions, policies apply to every actor, whatever its roles.
+	pub fn check_policies(&self) -> bool {
+		// If permissions are disabled, don't check policies
+		if !self.perms {
+			return false;
+		}
+		// If auth is disabled and actor is anonymous, don't check policies
+		!(!self.auth_enabled && self.auth.is_anon())
+	}
+
 	/// Whether or not to check table permissions
 	///
 	/// TODO: This method is called a lot during data operations, so we decided to bypass the system's authorization mechanism.
@@ -494,9 +506,11 @@ impl Options {
 
 		// Is the actor allowed to view?
 		let can_view =
-			[Role::Viewer, Role::Editor, Role::Owner].iter().any(|r| self.auth.has_role(r));
+			[Role::Viewer, Role::Editor, Role::Owner].iter().any(|r| self.auth.has_role(r))
+				|| self.auth.has_privilege(&Action::View, &ResourceKind::Document);
 		// Is the actor allowed to edit?
-		let can_edit = [Role::Editor, Role::Owner].iter().any(|r| self.auth.has_role(r));
+		let can_edit = [Role::Editor, Role::Owner].iter().any(|r| self.auth.has_role(r))
+			|| self.auth.has_privilege(&Action::Edit, &ResourceKind::Document);
 		// Is the target database in the actor's level?
 		let db_in_actor_level = self.auth.is_root()
 			|| self.auth.is_ns() && self.auth.level().ns().unwrap() == self.ns()
@@ -507,11 +521,11 @@ impl Options {
 		// Is the actor allowed to do the action on the selected database?
 		let is_allowed = match action {
 			Action::View => {
-				// Today all users have at least View permissions, so if the target database belongs to the user's level, don't check permissions
+				// Predefined roles, and custom roles which grant records, can view the target database if it belongs to the user's level
 				can_view && db_in_actor_level
 			}
 			Action::Edit => {
-				// Editor and Owner roles are allowed to edit, but only if the target database belongs to the user's level
+				// Editor and Owner roles, and custom roles which grant editing records, are allowed to edit, but only if the target database belongs to the user's level
 				can_edit && db_in_actor_level
 			}
 		};
@@ -87,6 +87,7 @@ impl Session {
 			"ip".to_string() => self.ip.to_owned().into(),
 			"ns".to_string() => self.ns.to_owned().into(),
 			"or".to_string() => self.or.to_owned().into(),
+			"ro".to_string() => self.au.roles().iter().map(|r| r.to_string()).collect::<Vec<_>>().into(),
 			"sc".to_string() => self.sc.to_owned().into(),
 			"sd".to_string() => self.sd.to_owned().into(),
 			"tk".to_string() => self.tk.to_owned().into(),
@@ -1,9 +1,13 @@
 use crate::ctx::Context;
 use crate::dbs::Statement;
 use crate::dbs::{Options, Transaction};
+#[cfg(feature = "sql2")]
+use crate::doc::CursorDoc;
 use crate::doc::Document;
 use crate::err::Error;
 use crate::sql::permission::Permission;
+#[cfg(feature = "sql2")]
+use crate::sql::PermissionKind;
 
 impl<'a> Document<'a> {
 	pub async fn allow(
@@ -32,7 +36,7 @@ impl<'a> Document<'a> {
 				// Process the table permissions
 				match perms {
 					Permission::None => return Err(Error::Ignore),
-					Permission::Full => return Ok(()),
+					Permission::Full => (),
 					Permission::Specific(e) => {
 						// Disable permissions
 						let opt = &opt.new_with_perms(false);
@@ -43,6 +47,46 @@ impl<'a> Document<'a> {
 					}
 				}
 			}
+			// Process the table policies
+			#[cfg(feature = "sql2")]
+			{
+				let kind = if stm.is_delete() {
+					PermissionKind::Delete
+				} else if stm.is_select() {
+					PermissionKind::Select
+				} else if self.is_new() {
+					PermissionKind::Create
+				} else {
+					PermissionKind::Update
+				};
+				self.allow_policies(ctx, opt, txn, kind, &self.current).await?;
+			}
+		}
+		// Carry on
+		Ok(())
+	}
+	/// Check that every POLICY for this table, which applies
+	/// to this type of statement, allows access to the record
+	#[cfg(feature = "sql2")]
+	pub(super) async fn allow_policies(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		kind: PermissionKind,
+		doc: &CursorDoc<'_>,
+	) -> Result<(), Error> {
+		// Should we run policy checks?
+		if opt.check_policies() {
+			// Disable permissions
+			let opt = &opt.new_with_perms(false);
+			// Loop through all policy statements
+			for pl in self.pl(opt, txn).await?.iter().filter(|pl| pl.applies(kind)) {
+				// Process the WHEN clause
+				if !pl.when.compute(ctx, opt, txn, Some(doc)).await?.is_truthy() {
+					return Err(Error::Ignore);
+				}
+			}
 		}
 		// Carry on
 		Ok(())
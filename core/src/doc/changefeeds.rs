@@ -8,7 +8,7 @@ use crate::err::Error;
 impl<'a> Document<'a> {
 	pub async fn changefeeds(
 		&self,
-		_ctx: &Context<'_>,
+		ctx: &Context<'_>,
 		opt: &Options,
 		txn: &Transaction,
 		_stm: &Statement<'_>,
@@ -19,19 +19,32 @@ impl<'a> Document<'a> {
 		}
 		//
 		let tb = self.tb(opt, txn).await?;
-		// Clone transaction
-		let run = txn.clone();
-		// Claim transaction
-		let mut run = run.lock().await;
-		// Get the database and the table for the record
-		let db = run.add_and_cache_db(opt.ns(), opt.db(), opt.strict).await?;
+		// Get the database for the record
+		let db = txn.lock().await.add_and_cache_db(opt.ns(), opt.db(), opt.strict).await?;
 		// Check if changefeeds are enabled
 		if db.changefeed.is_some() || tb.changefeed.is_some() {
 			// Get the arguments
-			let tb = tb.name.as_str();
 			let id = self.id.as_ref().unwrap();
+			// Record the encrypted fields as they are stored
+			let current = self.encrypted(ctx, opt, txn).await?;
+			// Check if the previous value should be stored too, as tables inherit the database changefeed
+			#[cfg(feature = "sql2")]
+			if tb.changefeed.as_ref().or(db.changefeed.as_ref()).is_some_and(|cf| cf.store_original)
+			{
+				let initial = self.encrypt(ctx, opt, txn, self.initial.doc.as_ref()).await?;
+				// Create the changefeed entry with the original value
+				txn.lock().await.record_change_with_original(
+					opt.ns(),
+					opt.db(),
+					tb.name.as_str(),
+					id,
+					initial,
+					current,
+				);
+				return Ok(());
+			}
 			// Create the changefeed entry
-			run.record_change(opt.ns(), opt.db(), tb, id, self.current.doc.clone());
+			txn.lock().await.record_change(opt.ns(), opt.db(), tb.name.as_str(), id, current);
 		}
 		// Carry on
 		Ok(())
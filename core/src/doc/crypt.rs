@@ -0,0 +1,90 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::Document;
+use crate::err::Error;
+#[cfg(feature = "sql2")]
+use crate::kvs::keyring::Field;
+use crate::sql::value::Value;
+use std::borrow::Cow;
+
+impl<'a> Document<'a> {
+	/// Decrypts the values of the encrypted fields of this document
+	#[cfg_attr(not(feature = "sql2"), allow(unused_variables))]
+	pub async fn decrypt(
+		&mut self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<(), Error> {
+		// Fields are only encrypted with the `sql2` feature
+		#[cfg(feature = "sql2")]
+		{
+			// Check if the record exists
+			if self.id.is_none() || self.initial.doc.is_none() {
+				return Ok(());
+			}
+			// Check if the table has any encrypted fields
+			let fds = self.fd(opt, txn).await?;
+			if !fds.iter().any(|fd| fd.encrypted.is_some()) {
+				return Ok(());
+			}
+			// Decrypt the stored values
+			let mut val = self.initial.doc.as_ref().clone();
+			for fd in fds.iter() {
+				if let Some(key) = &fd.encrypted {
+					let field = Field::new(opt.ns(), opt.db(), fd, self.id);
+					for (k, v) in self.initial.doc.walk(&fd.name).into_iter() {
+						if !v.is_none() {
+							val.put(&k, ctx.get_keyring().decrypt(key, &field, &v)?);
+						}
+					}
+				}
+			}
+			self.current.doc = Cow::Owned(val.clone());
+			self.initial.doc = Cow::Owned(val);
+		}
+		// Carry on
+		Ok(())
+	}
+	/// Encrypts the values of the encrypted fields of this document, as they are stored
+	pub async fn encrypted(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Cow<'_, Value>, Error> {
+		self.encrypt(ctx, opt, txn, self.current.doc.as_ref()).await
+	}
+	/// Encrypts the values of the encrypted fields of a version of this document
+	#[cfg_attr(not(feature = "sql2"), allow(unused_variables))]
+	pub async fn encrypt<'v>(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: &'v Value,
+	) -> Result<Cow<'v, Value>, Error> {
+		// Fields are only encrypted with the `sql2` feature
+		#[cfg(feature = "sql2")]
+		{
+			let fds = self.fd(opt, txn).await?;
+			if fds.iter().any(|fd| fd.encrypted.is_some()) {
+				let mut val = doc.clone();
+				for fd in fds.iter() {
+					if let Some(key) = &fd.encrypted {
+						let field = Field::new(opt.ns(), opt.db(), fd, self.id);
+						for (k, v) in doc.walk(&fd.name).into_iter() {
+							if !v.is_none() {
+								let v =
+									ctx.get_keyring().encrypt(key, &field, &v, fd.deterministic)?;
+								val.put(&k, v);
+							}
+						}
+					}
+				}
+				return Ok(Cow::Owned(val));
+			}
+		}
+		Ok(Cow::Borrowed(doc))
+	}
+}
@@ -9,7 +9,11 @@ use crate::idx::planner::executor::IteratorRef;
 use crate::sql::statements::define::DefineEventStatement;
 use crate::sql::statements::define::DefineFieldStatement;
 use crate::sql::statements::define::DefineIndexStatement;
+#[cfg(feature = "sql2")]
+use crate::sql::statements::define::DefinePolicyStatement;
 use crate::sql::statements::define::DefineTableStatement;
+#[cfg(feature = "sql2")]
+use crate::sql::statements::define::DefineWebhookStatement;
 use crate::sql::statements::live::LiveStatement;
 use crate::sql::thing::Thing;
 use crate::sql::value::Value;
@@ -163,6 +167,30 @@ impl<'a> Document<'a> {
 		// Get the event definitions
 		txn.clone().lock().await.all_tb_events(opt.ns(), opt.db(), &id.tb).await
 	}
+	/// Get the webhooks for this document
+	#[cfg(feature = "sql2")]
+	pub async fn wh(
+		&self,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Arc<[DefineWebhookStatement]>, Error> {
+		// Get the record id
+		let id = self.id.as_ref().unwrap();
+		// Get the webhook definitions
+		txn.clone().lock().await.all_tb_webhooks(opt.ns(), opt.db(), &id.tb).await
+	}
+	/// Get the policies for this document
+	#[cfg(feature = "sql2")]
+	pub async fn pl(
+		&self,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Arc<[DefinePolicyStatement]>, Error> {
+		// Get the record id
+		let id = self.id.as_ref().unwrap();
+		// Get the policy definitions
+		txn.clone().lock().await.all_tb_policies(opt.ns(), opt.db(), &id.tb).await
+	}
 	/// Get the fields for this document
 	pub async fn fd(
 		&self,
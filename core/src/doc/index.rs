@@ -7,9 +7,13 @@ use crate::idx::ft::FtIndex;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::IndexKeyBase;
 use crate::key;
+#[cfg(feature = "sql2")]
+use crate::kvs::keyring::Field;
 use crate::kvs::TransactionType;
 use crate::sql::array::Array;
 use crate::sql::index::{Index, MTreeParams, SearchParams};
+#[cfg(feature = "sql2")]
+use crate::sql::statements::DefineFieldStatement;
 use crate::sql::statements::DefineIndexStatement;
 use crate::sql::{Part, Thing, Value};
 
@@ -35,6 +39,9 @@ impl<'a> Document<'a> {
 		}
 		// Get the record id
 		let rid = self.id.as_ref().unwrap();
+		// Get the field definitions
+		#[cfg(feature = "sql2")]
+		let fds = self.fd(opt, txn).await?;
 		// Loop through all index statements
 		for ix in self.ix(opt, txn).await?.iter() {
 			// Calculate old values
@@ -43,6 +50,10 @@ impl<'a> Document<'a> {
 			// Calculate new values
 			let n = build_opt_values(ctx, opt, txn, ix, &self.current).await?;
 
+			// Encrypt the values of encrypted fields
+			#[cfg(feature = "sql2")]
+			let (o, n) = (encrypt_values(ctx, opt, ix, &fds, o)?, encrypt_values(ctx, opt, ix, &fds, n)?);
+
 			// Update the index entries
 			if opt.force || o != n {
 				// Store all the variable and parameters required by the index operation
@@ -52,6 +63,8 @@ impl<'a> Document<'a> {
 				match &ix.index {
 					Index::Uniq => ic.index_unique(txn).await?,
 					Index::Idx => ic.index_non_unique(txn).await?,
+					#[cfg(feature = "sql2")]
+					Index::UniqDeferred => ic.index_unique_deferred(txn).await?,
 					Index::Search(p) => ic.index_full_text(ctx, txn, p).await?,
 					Index::MTree(p) => ic.index_mtree(ctx, txn, p).await?,
 				};
@@ -76,6 +89,13 @@ async fn build_opt_values(
 	if !doc.doc.is_some() {
 		return Ok(None);
 	}
+	// Partial indexes ignore the records which don't match the condition
+	#[cfg(feature = "sql2")]
+	if let Some(cond) = &ix.cond {
+		if !cond.compute(ctx, opt, txn, Some(doc)).await?.is_truthy() {
+			return Ok(None);
+		}
+	}
 	let mut o = Vec::with_capacity(ix.cols.len());
 	for i in ix.cols.iter() {
 		let v = i.compute(ctx, opt, txn, Some(doc)).await?;
@@ -84,6 +104,31 @@ async fn build_opt_values(
 	Ok(Some(o))
 }
 
+/// Encrypt the values of the encrypted fields which are indexed, so that the index
+/// only contains the deterministically encrypted values, and never the values themselves
+#[cfg(feature = "sql2")]
+fn encrypt_values(
+	ctx: &Context<'_>,
+	opt: &Options,
+	ix: &DefineIndexStatement,
+	fds: &[DefineFieldStatement],
+	vals: Option<Vec<Value>>,
+) -> Result<Option<Vec<Value>>, Error> {
+	let Some(mut vals) = vals else {
+		return Ok(None);
+	};
+	for (v, col) in vals.iter_mut().zip(ix.cols.iter()) {
+		if let Some(fd) = fds.iter().find(|fd| fd.encrypted.is_some() && fd.name.eq(col)) {
+			fd.check_index(ix)?;
+			if let (Some(key), false) = (&fd.encrypted, v.is_none()) {
+				let field = Field::new(opt.ns(), opt.db(), fd, None);
+				*v = ctx.get_keyring().encrypt(key, &field, v, true)?;
+			}
+		}
+	}
+	Ok(Some(vals))
+}
+
 /// Extract from the given document, the values required by the index and put then in an array.
 /// Eg. IF the index is composed of the columns `name` and `instrument`
 /// Given this doc: { "id": 1, "instrument":"piano", "name":"Tobie" }
@@ -316,14 +361,77 @@ impl<'a> IndexOperation<'a> {
 		Ok(())
 	}
 
+	#[cfg(feature = "sql2")]
+	async fn index_unique_deferred(&mut self, txn: &Transaction) -> Result<(), Error> {
+		let mut run = txn.lock().await;
+		// Delete the old index data
+		if let Some(o) = self.o.take() {
+			let i = Indexable::new(o, self.ix);
+			for o in i {
+				let key = self.get_non_unique_index_key(&o);
+				match run.delc(key, Some(self.rid)).await {
+					Err(Error::TxConditionNotMet) => Ok(()),
+					Err(e) => Err(e),
+					Ok(v) => Ok(v),
+				}?;
+				// Release the value, if this record holds it
+				let key = self.get_unique_claim_key(&o);
+				match run.delc(key, Some(self.rid)).await {
+					Err(Error::TxConditionNotMet) => Ok(()),
+					Err(e) => Err(e),
+					Ok(v) => Ok(v),
+				}?
+			}
+		}
+		// Create the new index data, which is checked for uniqueness on commit
+		if let Some(n) = self.n.take() {
+			let i = Indexable::new(n, self.ix);
+			for n in i {
+				if !n.is_all_none_or_null() {
+					let key = self.get_non_unique_index_key(&n);
+					run.set(key.clone(), self.rid).await?;
+					let (ns, db, tb, ix) =
+						(self.opt.ns(), self.opt.db(), &self.ix.what, &self.ix.name);
+					let beg = crate::key::index::Index::prefix_ids_beg(ns, db, tb, ix, &n);
+					let end = crate::key::index::Index::prefix_ids_end(ns, db, tb, ix, &n);
+					run.defer_unique_check(
+						key.into(),
+						self.get_unique_claim_key(&n).into(),
+						beg..end,
+						ix,
+						Self::index_value(&n),
+						self.rid,
+					);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// The key holding the record which claimed a value of a deferred unique index
+	#[cfg(feature = "sql2")]
+	fn get_unique_claim_key(&self, v: &'a Array) -> key::index::uq::Uq {
+		crate::key::index::uq::Uq::new(
+			self.opt.ns(),
+			self.opt.db(),
+			&self.ix.what,
+			&self.ix.name,
+			v,
+		)
+	}
+
+	fn index_value(n: &Array) -> String {
+		match n.len() {
+			1 => n.first().unwrap().to_string(),
+			_ => n.to_string(),
+		}
+	}
+
 	fn err_index_exists(&self, rid: Thing, n: Array) -> Result<(), Error> {
 		Err(Error::IndexExists {
 			thing: rid,
 			index: self.ix.name.to_string(),
-			value: match n.len() {
-				1 => n.first().unwrap().to_string(),
-				_ => n.to_string(),
-			},
+			value: Self::index_value(&n),
 		})
 	}
 
@@ -72,6 +72,9 @@ impl<'a> Document<'a> {
 		self.changefeeds(ctx, opt, txn, stm).await?;
 		// Run event queries
 		self.event(ctx, opt, txn, stm).await?;
+		// Queue any webhooks
+		#[cfg(feature = "sql2")]
+		self.webhooks(ctx, opt, txn, stm).await?;
 		// Yield document
 		self.pluck(ctx, opt, txn, stm).await
 	}
@@ -107,6 +110,9 @@ impl<'a> Document<'a> {
 		self.changefeeds(ctx, opt, txn, stm).await?;
 		// Run event queries
 		self.event(ctx, opt, txn, stm).await?;
+		// Queue any webhooks
+		#[cfg(feature = "sql2")]
+		self.webhooks(ctx, opt, txn, stm).await?;
 		// Yield document
 		self.pluck(ctx, opt, txn, stm).await
 	}
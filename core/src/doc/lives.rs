@@ -2,6 +2,8 @@ use crate::ctx::Context;
 use crate::dbs::Notification;
 use crate::dbs::Options;
 use crate::dbs::Statement;
+#[cfg(feature = "sql2")]
+use crate::dbs::Workable;
 use crate::dbs::{Action, Transaction};
 use crate::doc::CursorDoc;
 use crate::doc::Document;
@@ -10,15 +12,27 @@ use crate::sql::paths::META;
 use crate::sql::paths::SC;
 use crate::sql::paths::SD;
 use crate::sql::paths::TK;
+#[cfg(feature = "sql2")]
+use crate::sql::paths::{ID, IN, OUT};
 use crate::sql::permission::Permission;
+use crate::sql::statements::LiveStatement;
+#[cfg(feature = "sql2")]
+use crate::sql::statements::SelectStatement;
+#[cfg(feature = "sql2")]
+use crate::sql::PermissionKind;
 use crate::sql::Value;
+#[cfg(feature = "sql2")]
+use crate::sql::{
+	Cond, Expression, Fetchs, Field, Fields, Index, Kind, Operator, Part, Thing, Values,
+};
+use channel::Sender;
 use std::ops::Deref;
 use std::sync::Arc;
 
 impl<'a> Document<'a> {
 	pub async fn lives(
 		&self,
-		_ctx: &Context<'_>,
+		#[cfg_attr(not(feature = "sql2"), allow(unused_variables))] ctx: &Context<'_>,
 		opt: &Options,
 		txn: &Transaction,
 		stm: &Statement<'_>,
@@ -29,126 +43,349 @@ impl<'a> Document<'a> {
 		}
 		// Check if we can send notifications
 		if let Some(chn) = &opt.sender {
+			// Get the event action
+			let act = if stm.is_delete() {
+				Action::Delete
+			} else if self.is_new() {
+				Action::Create
+			} else {
+				Action::Update
+			};
 			// Loop through all index statements
 			for lv in self.lv(opt, txn).await?.iter() {
-				// Create a new statement
-				let lq = Statement::from(lv);
-				// Get the event action
-				let met = if stm.is_delete() {
-					Value::from("DELETE")
-				} else if self.is_new() {
-					Value::from("CREATE")
-				} else {
-					Value::from("UPDATE")
-				};
-				// Check if this is a delete statement
-				let doc = match stm.is_delete() {
-					true => &self.initial,
-					false => &self.current,
-				};
-				// Ensure that a session exists on the LIVE query
-				let sess = match lv.session.as_ref() {
-					Some(v) => v,
-					None => continue,
-				};
-				// Ensure that auth info exists on the LIVE query
-				let auth = match lv.auth.clone() {
-					Some(v) => v,
-					None => continue,
-				};
-				// We need to create a new context which we will
-				// use for processing this LIVE query statement.
-				// This ensures that we are using the session
-				// of the user who created the LIVE query.
-				let mut lqctx = Context::background();
-				lqctx.add_value("auth", sess.pick(SD.as_ref()));
-				lqctx.add_value("scope", sess.pick(SC.as_ref()));
-				lqctx.add_value("token", sess.pick(TK.as_ref()));
-				lqctx.add_value("session", sess);
-				// We need to create a new options which we will
-				// use for processing this LIVE query statement.
-				// This ensures that we are using the auth data
-				// of the user who created the LIVE query.
-				let lqopt = opt.new_with_perms(true).with_auth(Arc::from(auth));
-				// Add $before, $after, $value, and $event params
-				// to this LIVE query so that user can use these
-				// within field projections and WHERE clauses.
-				lqctx.add_value("event", met);
-				lqctx.add_value("value", self.current.doc.deref());
-				lqctx.add_value("after", self.current.doc.deref());
-				lqctx.add_value("before", self.initial.doc.deref());
-				// First of all, let's check to see if the WHERE
-				// clause of the LIVE query is matched by this
-				// document. If it is then we can continue.
-				match self.lq_check(&lqctx, &lqopt, txn, &lq, doc).await {
-					Err(Error::Ignore) => continue,
-					Err(e) => return Err(e),
-					Ok(_) => (),
+				self.lq_notify(opt, txn, chn, lv, &act).await?;
+			}
+			// Notify any LIVE queries on related records
+			#[cfg(feature = "sql2")]
+			self.lives_related(ctx, opt, txn, chn).await?;
+		}
+		// Carry on
+		Ok(())
+	}
+	/// Process a LIVE query for this document, and send a notification
+	async fn lq_notify(
+		&self,
+		opt: &Options,
+		txn: &Transaction,
+		chn: &Sender<Notification>,
+		lv: &LiveStatement,
+		act: &Action,
+	) -> Result<(), Error> {
+		// Create a new statement
+		let lq = Statement::from(lv);
+		// Get the event action
+		let met = Value::from(act.to_string());
+		// Check if this is a delete statement
+		let doc = match act {
+			Action::Delete => &self.initial,
+			_ => &self.current,
+		};
+		// Ensure that a session exists on the LIVE query
+		let sess = match lv.session.as_ref() {
+			Some(v) => v,
+			None => return Ok(()),
+		};
+		// Ensure that auth info exists on the LIVE query
+		let auth = match lv.auth.clone() {
+			Some(v) => v,
+			None => return Ok(()),
+		};
+		// We need to create a new context which we will
+		// use for processing this LIVE query statement.
+		// This ensures that we are using the session
+		// of the user who created the LIVE query.
+		let mut lqctx = Context::background();
+		lqctx.add_value("auth", sess.pick(SD.as_ref()));
+		lqctx.add_value("scope", sess.pick(SC.as_ref()));
+		lqctx.add_value("token", sess.pick(TK.as_ref()));
+		lqctx.add_value("session", sess);
+		// We need to create a new options which we will
+		// use for processing this LIVE query statement.
+		// This ensures that we are using the auth data
+		// of the user who created the LIVE query.
+		let lqopt = opt.new_with_perms(true).with_auth(Arc::from(auth));
+		// Add $before, $after, $value, and $event params
+		// to this LIVE query so that user can use these
+		// within field projections and WHERE clauses.
+		lqctx.add_value("event", met);
+		lqctx.add_value("value", self.current.doc.deref());
+		lqctx.add_value("after", self.current.doc.deref());
+		lqctx.add_value("before", self.initial.doc.deref());
+		// Before anything else, let's check that the user
+		// who created this LIVE query is still allowed to
+		// view this table. If the permissions have since
+		// been revoked, then the LIVE query is killed.
+		if self.lq_revoked(&lqopt, txn, &lq).await? {
+			// Get the table of this record
+			let tb = self.tb(opt, txn).await?;
+			// Claim transaction
+			let mut run = txn.lock().await;
+			// Delete the node live query
+			run.del_ndlq(lv.node.0, lv.id.0, opt.ns(), opt.db()).await?;
+			// Delete the table live query
+			run.del_tblq(opt.ns(), opt.db(), &tb.name, lv.id.0).await?;
+			// Delete the database live query
+			#[cfg(feature = "sql2")]
+			run.del_dblq(opt.ns(), opt.db(), lv.id.0).await?;
+			// Send a KILLED notification
+			if opt.id()? == lv.node.0 {
+				chn.send(Notification::killed(
+					lv.id,
+					"The permissions for this live query have been revoked",
+				))
+				.await?;
+			} else {
+				// TODO: Send to storage
+			}
+			return Ok(());
+		}
+		// First of all, let's check to see if the WHERE
+		// clause of the LIVE query is matched by this
+		// document. If it is then we can continue.
+		match self.lq_check(&lqctx, &lqopt, txn, &lq, doc).await {
+			Err(Error::Ignore) => return Ok(()),
+			Err(e) => return Err(e),
+			Ok(_) => (),
+		}
+		// Secondly, let's check to see if any PERMISSIONS
+		// clause for this table allows this document to
+		// be viewed by the user who created this LIVE
+		// query. If it does, then we can continue.
+		match self.lq_allow(&lqctx, &lqopt, txn, &lq, doc).await {
+			Err(Error::Ignore) => return Ok(()),
+			Err(e) => return Err(e),
+			Ok(_) => (),
+		}
+		// Ensure futures are run
+		let lqfut: &Options = &lqopt.new_with_futures(true);
+		// Get the id of the altered record
+		let record = self.id.map(|id| Value::from(id.clone())).unwrap_or_default();
+		// Get the record content before any changes,
+		// if this was requested by the LIVE query
+		#[cfg(not(feature = "sql2"))]
+		let before = Value::None;
+		#[cfg(feature = "sql2")]
+		let before = match lv.before && *act != Action::Create {
+			true => self.lq_initial(&lqctx, lqfut, txn).await?,
+			false => Value::None,
+		};
+		// Finally, let's check what type of statement
+		// caused this LIVE query to run, and get the
+		// relevant output based on the statement.
+		#[cfg_attr(not(feature = "sql2"), allow(unused_mut))]
+		let mut result = match act {
+			// Output the full document before any changes were applied
+			Action::Delete => self.lq_initial(&lqctx, lqfut, txn).await?,
+			// Output the LIVE query projection of the document
+			_ => self.pluck(&lqctx, &lqopt, txn, &lq).await?,
+		};
+		// Fetch any records specified in the FETCH clause
+		#[cfg(feature = "sql2")]
+		if let Some(fetchs) = &lv.fetch {
+			for fetch in fetchs.iter() {
+				result.fetch(&lqctx, lqfut, txn, fetch).await?;
+			}
+		}
+		// Send the notification
+		if opt.id()? == lv.node.0 {
+			chn.send(Notification {
+				id: lv.id,
+				action: act.clone(),
+				record,
+				result,
+				before,
+			})
+			.await?;
+		} else {
+			// TODO: Send to storage
+		}
+		// Carry on
+		Ok(())
+	}
+	/// Notify any LIVE queries on other tables whose output depends on this record,
+	/// either through graph edges linking to their records, or through their FETCH
+	/// clause fetching this record.
+	#[cfg(feature = "sql2")]
+	async fn lives_related(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		chn: &Sender<Notification>,
+	) -> Result<(), Error> {
+		// Get the record id
+		let rid = self.id.as_ref().unwrap();
+		// Get the records which this edge links, if any
+		let mut linked: Vec<Thing> = vec![];
+		for doc in [&self.initial, &self.current] {
+			for path in [IN.as_ref(), OUT.as_ref()] {
+				if let Value::Thing(v) = doc.doc.pick(path) {
+					if &v != *rid && !linked.contains(&v) {
+						linked.push(v);
+					}
 				}
-				// Secondly, let's check to see if any PERMISSIONS
-				// clause for this table allows this document to
-				// be viewed by the user who created this LIVE
-				// query. If it does, then we can continue.
-				match self.lq_allow(&lqctx, &lqopt, txn, &lq, doc).await {
-					Err(Error::Ignore) => continue,
-					Err(e) => return Err(e),
-					Ok(_) => (),
+			}
+		}
+		// Notify any LIVE queries which traverse the altered edge
+		for v in linked.iter() {
+			let lvs = txn.lock().await.all_tb_lives(opt.ns(), opt.db(), &v.tb).await?;
+			for lv in lvs.iter().filter(|lv| lq_traverses_graph(lv)) {
+				self.lq_refire(ctx, opt, txn, chn, lv, v).await?;
+			}
+		}
+		// Notify any LIVE queries which fetch this record
+		let lvs = txn.lock().await.all_db_lives(opt.ns(), opt.db()).await?;
+		for (tb, lv) in lvs.iter() {
+			// Changes to records in the LIVE query table are notified directly
+			if *tb == rid.tb {
+				continue;
+			}
+			// Find the records which fetch this record
+			if let Some(fetchs) = &lv.fetch {
+				for v in self.lq_fetched_by(ctx, opt, txn, tb, fetchs).await? {
+					self.lq_refire(ctx, opt, txn, chn, lv, &v).await?;
 				}
-				// Finally, let's check what type of statement
-				// caused this LIVE query to run, and send the
-				// relevant notification based on the statement.
-				if stm.is_delete() {
-					// Send a DELETE notification
-					if opt.id()? == lv.node.0 {
-						chn.send(Notification {
-							id: lv.id,
-							action: Action::Delete,
-							result: {
-								// Ensure futures are run
-								let lqopt: &Options = &lqopt.new_with_futures(true);
-								// Output the full document before any changes were applied
-								let mut value =
-									doc.doc.compute(&lqctx, lqopt, txn, Some(doc)).await?;
-								// Remove metadata fields on output
-								value.del(&lqctx, lqopt, txn, &*META).await?;
-								// Output result
-								value
-							},
-						})
-						.await?;
-					} else {
-						// TODO: Send to storage
-					}
-				} else if self.is_new() {
-					// Send a CREATE notification
-					if opt.id()? == lv.node.0 {
-						chn.send(Notification {
-							id: lv.id,
-							action: Action::Create,
-							result: self.pluck(&lqctx, &lqopt, txn, &lq).await?,
-						})
-						.await?;
-					} else {
-						// TODO: Send to storage
-					}
-				} else {
-					// Send a UPDATE notification
-					if opt.id()? == lv.node.0 {
-						chn.send(Notification {
-							id: lv.id,
-							action: Action::Update,
-							result: self.pluck(&lqctx, &lqopt, txn, &lq).await?,
-						})
-						.await?;
-					} else {
-						// TODO: Send to storage
-					}
-				};
 			}
 		}
 		// Carry on
 		Ok(())
 	}
+	/// Find the records in a table whose FETCH clause fetches this record. Only the
+	/// fetched fields which are indexed on their own are looked up, so that a write
+	/// never scans the whole table of a LIVE query. Fields which are defined with a
+	/// type which can not link to the table of this record are skipped.
+	#[cfg(feature = "sql2")]
+	async fn lq_fetched_by(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		tb: &str,
+		fetchs: &Fetchs,
+	) -> Result<Vec<Thing>, Error> {
+		// Get the record id
+		let rid = self.id.unwrap();
+		// Get the fields and indexes of the LIVE query table
+		let (fds, ixs) = {
+			let mut run = txn.lock().await;
+			let fds = run.all_tb_fields(opt.ns(), opt.db(), tb).await?;
+			let ixs = run.all_tb_indexes(opt.ns(), opt.db(), tb).await?;
+			(fds, ixs)
+		};
+		// Don't run permissions
+		let opt = &opt.new_with_perms(false);
+		let mut res: Vec<Thing> = vec![];
+		for f in fetchs.iter() {
+			// Check if the field can link to this record
+			let kind = fds.iter().find(|fd| fd.name == f.0).and_then(|fd| fd.kind.as_ref());
+			if kind.is_some_and(|k| !kind_links(k, &rid.tb)) {
+				continue;
+			}
+			// Check if the field is indexed
+			let indexed = ixs.iter().any(|ix| {
+				ix.cond.is_none()
+					&& matches!(ix.index, Index::Idx | Index::Uniq | Index::UniqDeferred)
+					&& ix.cols.len() == 1
+					&& ix.cols[0] == f.0
+			});
+			if !indexed {
+				trace!("Not notifying LIVE queries on `{tb}` fetching `{}` without an index", f.0);
+				continue;
+			}
+			// Match a fetched record link, or an array of record links
+			let ops = match kind {
+				Some(Kind::Record(_)) => &[Operator::Equal][..],
+				Some(Kind::Array(..) | Kind::Set(..)) => &[Operator::Contain][..],
+				_ => &[Operator::Equal, Operator::Contain][..],
+			};
+			for op in ops {
+				// Select the ids of the matching records, with the index
+				let stm = SelectStatement {
+					expr: Fields(
+						vec![Field::Single {
+							expr: Value::Idiom(ID.as_ref().into()),
+							alias: None,
+						}],
+						true,
+					),
+					what: Values(vec![Value::Table(tb.into())]),
+					cond: Some(Cond(Value::from(Expression::Binary {
+						l: Value::Idiom(f.0.clone()),
+						o: op.clone(),
+						r: Value::from(rid.clone()),
+					}))),
+					..SelectStatement::default()
+				};
+				if let Value::Array(v) = stm.compute(ctx, opt, txn, None).await? {
+					for v in v.into_iter() {
+						if let Value::Thing(v) = v {
+							if !res.contains(&v) {
+								res.push(v);
+							}
+						}
+					}
+				}
+			}
+		}
+		Ok(res)
+	}
+	/// Send an UPDATE notification for a record whose related records have changed
+	#[cfg(feature = "sql2")]
+	async fn lq_refire(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		chn: &Sender<Notification>,
+		lv: &LiveStatement,
+		rid: &Thing,
+	) -> Result<(), Error> {
+		// The record itself is unchanged, so a DIFF would be empty
+		if lv.expr.is_empty() {
+			return Ok(());
+		}
+		// Fetch the current record content
+		let key = crate::key::thing::new(opt.ns(), opt.db(), &rid.tb, &rid.id);
+		let val = match txn.lock().await.get(key).await? {
+			Some(v) => Value::from(v),
+			None => return Ok(()),
+		};
+		// Process the LIVE query for the record
+		let mut doc = Document::new(None, Some(rid), None, &val, Workable::Normal);
+		doc.decrypt(ctx, opt, txn).await?;
+		doc.lq_notify(opt, txn, chn, lv, &Action::Update).await
+	}
+	/// Output the full document before any changes were applied
+	async fn lq_initial(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Value, Error> {
+		// Compute the document before any changes
+		let mut value = self.initial.doc.compute(ctx, opt, txn, Some(&self.initial)).await?;
+		// Remove metadata fields on output
+		value.del(ctx, opt, txn, &*META).await?;
+		// Output result
+		Ok(value)
+	}
+	/// Check if the PERMISSIONS for a LIVE query have been revoked
+	async fn lq_revoked(
+		&self,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+	) -> Result<bool, Error> {
+		// Should we run permissions checks?
+		if opt.check_perms(stm.into()) {
+			// Get the table
+			let tb = self.tb(opt, txn).await?;
+			// The table can no longer be viewed at all
+			return Ok(tb.permissions.select == Permission::None);
+		}
+		// Carry on
+		Ok(false)
+	}
 	/// Check the WHERE clause for a LIVE query
 	async fn lq_check(
 		&self,
@@ -185,7 +422,7 @@ impl<'a> Document<'a> {
 			// Process the table permissions
 			match &tb.permissions.select {
 				Permission::None => return Err(Error::Ignore),
-				Permission::Full => return Ok(()),
+				Permission::Full => (),
 				Permission::Specific(e) => {
 					// Disable permissions
 					let opt = &opt.new_with_perms(false);
@@ -196,7 +433,68 @@ impl<'a> Document<'a> {
 				}
 			}
 		}
+		// Process the table policies
+		#[cfg(feature = "sql2")]
+		self.allow_policies(ctx, opt, txn, PermissionKind::Select, doc).await?;
 		// Carry on
 		Ok(())
 	}
 }
+
+/// Check if a value of a kind can be, or can contain, a link to a record of a table
+#[cfg(feature = "sql2")]
+fn kind_links(kind: &Kind, tb: &str) -> bool {
+	match kind {
+		Kind::Any => true,
+		Kind::Record(tbs) => tbs.is_empty() || tbs.iter().any(|t| t.0 == tb),
+		Kind::Option(k) | Kind::Set(k, _) | Kind::Array(k, _) => kind_links(k, tb),
+		Kind::Either(ks) => ks.iter().any(|k| kind_links(k, tb)),
+		_ => false,
+	}
+}
+
+/// Check if a LIVE query traverses the graph, in which case its output
+/// can change when an edge linking one of its records is altered
+#[cfg(feature = "sql2")]
+fn lq_traverses_graph(lv: &LiveStatement) -> bool {
+	lv.expr.iter().any(|f| match f {
+		Field::Single {
+			expr,
+			..
+		} => has_graph(expr),
+		Field::All => false,
+	}) || lv.cond.as_ref().is_some_and(|c| has_graph(&c.0))
+		|| lv
+			.fetch
+			.as_ref()
+			.is_some_and(|f| f.iter().any(|f| has_graph(&Value::Idiom(f.0.clone()))))
+}
+
+/// Check if a value contains any graph traversals
+#[cfg(feature = "sql2")]
+fn has_graph(v: &Value) -> bool {
+	match v {
+		Value::Idiom(v) => v.iter().any(|p| match p {
+			Part::Graph(_) => true,
+			Part::Where(v) | Part::Value(v) | Part::Start(v) => has_graph(v),
+			Part::Method(_, v) => v.iter().any(has_graph),
+			_ => false,
+		}),
+		Value::Edges(_) | Value::Subquery(_) => true,
+		Value::Expression(v) => match v.as_ref() {
+			Expression::Unary {
+				v,
+				..
+			} => has_graph(v),
+			Expression::Binary {
+				l,
+				r,
+				..
+			} => has_graph(l) || has_graph(r),
+		},
+		Value::Function(v) => v.args().iter().any(has_graph),
+		Value::Array(v) => v.iter().any(has_graph),
+		Value::Object(v) => v.values().any(has_graph),
+		_ => false,
+	}
+}
@@ -24,6 +24,7 @@ mod alter; // Modifies and updates the fields in this document
 mod changefeeds; // Processes any change feeds relevant for this document
 mod check; // Checks whether the WHERE clauses matches this document
 mod clean; // Ensures records adhere to the table schema
+mod crypt; // Encrypts and decrypts the encrypted fields of this document
 mod edges; // Attempts to store the edge data for this document
 mod empty; // Checks whether the specified document actually exists
 mod erase; // Removes all content and field data for this document
@@ -37,3 +38,5 @@ mod purge; // Deletes this document, and any edges or indexes
 mod reset; // Resets internal fields which were set for this document
 mod store; // Writes the document content to the storage engine
 mod table; // Processes any foreign tables relevant for this document
+#[cfg(feature = "sql2")]
+mod webhook; // Queues any webhooks relevant for this document
@@ -26,6 +26,8 @@ impl<'a> Document<'a> {
 			};
 			// Setup a new document
 			let mut doc = Document::new(pro.ir, pro.rid.as_ref(), pro.doc_id, &ins.0, ins.1);
+			// Decrypt the encrypted fields
+			doc.decrypt(ctx, opt, txn).await?;
 			// Process the statement
 			let res = match stm {
 				Statement::Select(_) => doc.select(ctx, opt, txn, stm).await,
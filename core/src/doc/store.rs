@@ -8,7 +8,7 @@ use crate::key::key_req::KeyRequirements;
 impl<'a> Document<'a> {
 	pub async fn store(
 		&self,
-		_ctx: &Context<'_>,
+		ctx: &Context<'_>,
 		opt: &Options,
 		txn: &Transaction,
 		stm: &Statement<'_>,
@@ -21,6 +21,8 @@ impl<'a> Document<'a> {
 		if self.tb(opt, txn).await?.drop {
 			return Ok(());
 		}
+		// Encrypt the encrypted fields
+		let val = self.encrypted(ctx, opt, txn).await?;
 		// Claim transaction
 		let mut run = txn.lock().await;
 		// Get the record id
@@ -30,7 +32,7 @@ impl<'a> Document<'a> {
 		//
 		match stm {
 			// This is a CREATE statement so try to insert the key
-			Statement::Create(_) => match run.put(key.key_category(), key, self).await {
+			Statement::Create(_) => match run.put(key.key_category(), key, val.as_ref()).await {
 				// The key already exists, so return an error
 				Err(Error::TxKeyAlreadyExistsCategory(_)) => Err(Error::RecordExists {
 					thing: rid.to_string(),
@@ -41,7 +43,7 @@ impl<'a> Document<'a> {
 				Ok(v) => Ok(v),
 			},
 			// This is not a CREATE statement, so update the key
-			_ => run.set(key, self).await,
+			_ => run.set(key, val.as_ref()).await,
 		}?;
 		// Carry on
 		Ok(())
@@ -39,6 +39,9 @@ impl<'a> Document<'a> {
 		self.changefeeds(ctx, opt, txn, stm).await?;
 		// Run event queries
 		self.event(ctx, opt, txn, stm).await?;
+		// Queue any webhooks
+		#[cfg(feature = "sql2")]
+		self.webhooks(ctx, opt, txn, stm).await?;
 		// Yield document
 		self.pluck(ctx, opt, txn, stm).await
 	}
@@ -0,0 +1,78 @@
+use crate::ctx::Context;
+use crate::dbs::Statement;
+use crate::dbs::{Options, Transaction};
+use crate::doc::Document;
+use crate::err::Error;
+use crate::kvs::webhook::Delivery;
+use crate::sql::value::Value;
+use std::ops::Deref;
+use uuid::Uuid;
+
+impl<'a> Document<'a> {
+	pub async fn webhooks(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check events
+		if !opt.events {
+			return Ok(());
+		}
+		// Check if forced
+		if !opt.force && !self.changed() {
+			return Ok(());
+		}
+		// Don't run permissions
+		let opt = &opt.new_with_perms(false);
+		// Get the record id
+		let rid = self.id.as_ref().unwrap();
+		// Loop through all webhook statements
+		for wh in self.wh(opt, txn).await?.iter() {
+			// Get the event action
+			let met = if stm.is_delete() {
+				Value::from("DELETE")
+			} else if self.is_new() {
+				Value::from("CREATE")
+			} else {
+				Value::from("UPDATE")
+			};
+			// Configure the context
+			let mut ctx = Context::new(ctx);
+			ctx.add_value("event", met.clone());
+			ctx.add_value("value", self.current.doc.deref());
+			ctx.add_value("after", self.current.doc.deref());
+			ctx.add_value("before", self.initial.doc.deref());
+			// Process conditional clause
+			let val = wh.when.compute(&ctx, opt, txn, Some(&self.current)).await?;
+			// Queue the payload if value is truthy
+			if val.is_truthy() {
+				// The encrypted fields are sent as they are stored
+				let before = self.encrypt(&ctx, opt, txn, self.initial.doc.as_ref()).await?;
+				let after = self.encrypted(&ctx, opt, txn).await?;
+				let payload = Value::from(map! {
+					"webhook".to_string() => Value::from(wh.name.as_str()),
+					"table".to_string() => Value::from(rid.tb.as_str()),
+					"event".to_string() => met,
+					"id".to_string() => Value::from((*rid).clone()),
+					"before".to_string() => before.into_owned(),
+					"after".to_string() => after.into_owned(),
+				});
+				let msg = Delivery {
+					ns: opt.ns().to_owned(),
+					db: opt.db().to_owned(),
+					tb: rid.tb.clone(),
+					wh: wh.name.to_raw(),
+					payload,
+					..Default::default()
+				};
+				// The payload is committed along with this change, and is due immediately
+				let key = crate::key::root::wq::new(0, Uuid::now_v7());
+				txn.lock().await.set(key, msg).await?;
+			}
+		}
+		// Carry on
+		Ok(())
+	}
+}
@@ -391,9 +391,81 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested changefeed consumer does not exist
+	#[error("The consumer '{value}' does not exist")]
+	CnNotFound {
+		value: String,
+	},
+
+	/// The requested webhook does not exist
+	#[error("The webhook '{value}' does not exist")]
+	WhNotFound {
+		value: String,
+	},
+
+	/// The requested policy does not exist
+	#[error("The policy '{value}' does not exist")]
+	PlNotFound {
+		value: String,
+	},
+
 	#[error("Unsupported distance: {0}")]
 	UnsupportedDistance(Distance),
 
+	/// The requested root API key does not exist
+	#[error("The root API key '{value}' does not exist")]
+	ApiKeyRootNotFound {
+		value: String,
+	},
+
+	/// The requested namespace API key does not exist
+	#[error("The API key '{value}' does not exist in the namespace '{ns}'")]
+	ApiKeyNsNotFound {
+		value: String,
+		ns: String,
+	},
+
+	/// The requested database API key does not exist
+	#[error("The API key '{value}' does not exist in the database '{db}'")]
+	ApiKeyDbNotFound {
+		value: String,
+		ns: String,
+		db: String,
+	},
+
+	/// The hash of an imported API key is already used by another key
+	#[error("The hash of the API key '{value}' is already used by another key")]
+	ApiKeyHashExists {
+		value: String,
+	},
+
+	/// A predefined role can not be redefined
+	#[error("The role '{value}' is a predefined role")]
+	RoleReserved {
+		value: String,
+	},
+
+	/// The requested root role does not exist
+	#[error("The root role '{value}' does not exist")]
+	RoleRootNotFound {
+		value: String,
+	},
+
+	/// The requested namespace role does not exist
+	#[error("The role '{value}' does not exist in the namespace '{ns}'")]
+	RoleNsNotFound {
+		value: String,
+		ns: String,
+	},
+
+	/// The requested database role does not exist
+	#[error("The role '{value}' does not exist in the database '{db}'")]
+	RoleDbNotFound {
+		value: String,
+		ns: String,
+		db: String,
+	},
+
 	/// The requested root user does not exist
 	#[error("The root user '{value}' does not exist")]
 	UserRootNotFound {
@@ -643,6 +715,13 @@ pub enum Error {
 	#[error("A value can't be analyzed: {0}")]
 	AnalyzerError(String),
 
+	/// The analyzer definition is not valid
+	#[error("The analyzer '{name}' is not valid: {message}")]
+	InvalidAnalyzer {
+		name: String,
+		message: String,
+	},
+
 	/// Represents an error when trying to highlight a value
 	#[error("A value can't be highlighted: {0}")]
 	HighlightError(String),
@@ -752,6 +831,30 @@ pub enum Error {
 	#[error("The password did not verify")]
 	InvalidPass,
 
+	/// The user has made too many signin attempts
+	#[error("Too many signin attempts, try again later")]
+	SigninThrottled,
+
+	/// The user is locked out after too many failed signin attempts
+	#[error("The user is locked out after too many failed signin attempts, try again later")]
+	SigninLocked,
+
+	/// The password of the user has expired
+	#[error("The password has expired, and needs to be changed")]
+	PasswordExpired,
+
+	/// The password is shorter than the password policy allows
+	#[error("The password needs to be at least {min} characters long")]
+	PasswordTooShort {
+		min: u32,
+	},
+
+	/// The password contains fewer classes of characters than the password policy allows
+	#[error("The password needs to contain at least {min} of lowercase letters, uppercase letters, digits and symbols")]
+	PasswordTooSimple {
+		min: u8,
+	},
+
 	/// There was an error with authentication
 	#[error("There was a problem with authentication")]
 	InvalidAuth,
@@ -779,6 +882,50 @@ pub enum Error {
 	/// The db is running without an available storage engine
 	#[error("The db is running without an available storage engine")]
 	MissingStorageEngine,
+
+	/// The audit log could not be read or written
+	#[error("Invalid audit log: {0}")]
+	InvalidAuditLog(String),
+
+	/// The keyfile could not be read or parsed
+	#[error("Invalid keyring: {0}")]
+	InvalidKeyring(String),
+
+	/// The key which encrypts a field does not exist in the keyring
+	#[error("The encryption key '{value}' does not exist")]
+	EncryptionKeyNotFound {
+		value: String,
+	},
+
+	/// A value could not be decrypted with the key which encrypts the field
+	#[error("Unable to decrypt a value with the encryption key '{value}'")]
+	DecryptionFailed {
+		value: String,
+	},
+
+	/// A condition can not be checked against the values of an encrypted field
+	#[error("The encrypted field '{value}' can only be compared for equality with a deterministic index in a WHERE clause")]
+	EncryptedFieldCondition {
+		value: String,
+	},
+
+	/// An index can not be defined on a field with randomized encryption
+	#[error("The field '{value}' can only be indexed when it is encrypted deterministically")]
+	EncryptedFieldIndex {
+		value: String,
+	},
+
+	/// The file with the key which encrypts the datastore could not be read or parsed
+	#[error("Invalid datastore encryption key: {0}")]
+	InvalidEncryptionKey(String),
+
+	/// A key or value of the datastore could not be decrypted
+	#[error("Unable to decrypt the datastore, which is not encrypted, or is encrypted with a different key")]
+	DatastoreDecryptionFailed,
+
+	/// The changes pushed to or pulled from a datastore could not be parsed
+	#[error("Invalid changes to synchronize: {0}")]
+	InvalidChanges(String),
 }
 
 impl From<Error> for String {
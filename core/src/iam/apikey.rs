@@ -0,0 +1,237 @@
+//! Verifies API keys, which are defined with `DEFINE KEY`.
+//!
+//! An API key is a random secret which is generated when the key is defined,
+//! and which is only returned at that time. Only a hash of the secret is stored,
+//! along with an index from the hash to the definition of the key, so that the
+//! secret can be used as a bearer credential in place of a token. A key grants
+//! the roles of its definition on the level on which it is defined, until it
+//! expires or until its definition is removed.
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::iam::{roles, Actor, Auth, Level, Role};
+use crate::kvs::{Datastore, LockType::*, Transaction, TransactionType::*};
+use crate::sql::{statements::DefineKeyStatement, Datetime};
+use chrono::{Duration, Utc};
+use derive::Store;
+use once_cell::sync::Lazy;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// The prefix which distinguishes an API key from a token
+pub const PREFIX: &str = "sk-";
+
+/// The number of random characters in an API key
+const KEY_LENGTH: usize = 40;
+
+/// How often the time at which a key was last used is recorded
+static USED_INTERVAL: Lazy<Duration> = Lazy::new(|| Duration::minutes(1));
+
+/// The location of an API key, stored by the hash of its secret
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[revisioned(revision = 1)]
+pub(crate) struct Index {
+	pub(crate) ns: Option<String>,
+	pub(crate) db: Option<String>,
+	pub(crate) name: String,
+	/// The time at which the key was last used
+	pub(crate) used: Option<Datetime>,
+}
+
+impl Index {
+	pub(crate) fn new(level: Level, name: &str) -> Self {
+		Self {
+			ns: level.ns().map(str::to_owned),
+			db: level.db().map(str::to_owned),
+			name: name.to_owned(),
+			used: None,
+		}
+	}
+
+	/// Checks if this is the location of the key with this name on this level
+	fn is(&self, level: &Level, name: &str) -> bool {
+		self.ns.as_deref() == level.ns() && self.db.as_deref() == level.db() && self.name == name
+	}
+
+	/// Returns the level on which the key is defined
+	fn level(&self) -> Level {
+		match (&self.ns, &self.db) {
+			(Some(ns), Some(db)) => Level::Database(ns.to_owned(), db.to_owned()),
+			(Some(ns), None) => Level::Namespace(ns.to_owned()),
+			_ => Level::Root,
+		}
+	}
+}
+
+/// Generates the secret of a new API key
+pub(crate) fn generate() -> String {
+	let key: String =
+		rand::thread_rng().sample_iter(&Alphanumeric).take(KEY_LENGTH).map(char::from).collect();
+	format!("{PREFIX}{key}")
+}
+
+/// Hashes the secret of an API key, which is random so does not need to be salted
+pub(crate) fn hash(key: &str) -> String {
+	hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Indexes an API key by the hash of its secret, unless the hash is used by another key
+pub(crate) async fn claim(
+	tx: &mut Transaction,
+	hash: &str,
+	level: &Level,
+	name: &str,
+) -> Result<(), Error> {
+	let key = crate::key::root::ak::new(hash);
+	match tx.get(key.clone()).await?.map(Index::from) {
+		Some(ix) if ix.is(level, name) => Ok(()),
+		Some(_) => Err(Error::ApiKeyHashExists {
+			value: name.to_owned(),
+		}),
+		None => tx.set(key, Index::new(level.clone(), name)).await,
+	}
+}
+
+/// Removes the index of the secret of an API key, unless it locates another key
+pub(crate) async fn release(
+	tx: &mut Transaction,
+	hash: &str,
+	level: &Level,
+	name: &str,
+) -> Result<(), Error> {
+	let key = crate::key::root::ak::new(hash);
+	match tx.get(key.clone()).await?.map(Index::from) {
+		Some(ix) if ix.is(level, name) => tx.del(key).await,
+		_ => Ok(()),
+	}
+}
+
+/// Returns the time at which an API key was last used
+pub(crate) async fn used(tx: &mut Transaction, hash: &str) -> Result<Option<Datetime>, Error> {
+	Ok(tx.get(crate::key::root::ak::new(hash)).await?.and_then(|v| Index::from(v).used))
+}
+
+/// Authenticates the session with an API key
+pub async fn verify(kvs: &Datastore, session: &mut Session, key: &str) -> Result<(), Error> {
+	// Log the authentication type
+	trace!("Attempting API key authentication");
+	let hash = hash(key);
+	// Create a new readonly transaction
+	let mut tx = kvs.transaction(Read, Optimistic).await?;
+	let res = lookup(&mut tx, &hash).await;
+	let (ix, de) = match res {
+		Ok(v) => v,
+		Err(e) => {
+			tx.cancel().await?;
+			return Err(e);
+		}
+	};
+	// Check that the custom roles are still defined
+	let level = ix.level();
+	let roles: Vec<Role> = de.roles.iter().map(Role::from).collect();
+	let res = roles::check(&mut tx, &level, &roles).await;
+	tx.cancel().await?;
+	res?;
+	// Record the use of the key, which does not need to succeed
+	if let Err(e) = touch(kvs, &hash, ix).await {
+		warn!("Failed to record the use of API key `{}`: {}", de.name, e);
+	}
+	// Set the session
+	session.tk = None;
+	session.ns = level.ns().map(str::to_owned);
+	session.db = level.db().map(str::to_owned);
+	session.au = Arc::new(Auth::new(Actor::new(de.name.to_raw(), roles, level)));
+	// Log the success
+	debug!("Authenticated to {} with API key `{}`", session.au.level(), de.name);
+	Ok(())
+}
+
+/// Finds the definition of an API key by the hash of its secret
+async fn lookup(tx: &mut Transaction, hash: &str) -> Result<(Index, DefineKeyStatement), Error> {
+	let ix: Index = match tx.get(crate::key::root::ak::new(hash)).await? {
+		Some(v) => v.into(),
+		None => {
+			trace!("The API key does not exist, or was revoked");
+			return Err(Error::InvalidAuth);
+		}
+	};
+	let res = match (&ix.ns, &ix.db) {
+		(Some(ns), Some(db)) => tx.get_db_key(ns, db, &ix.name).await,
+		(Some(ns), None) => tx.get_ns_key(ns, &ix.name).await,
+		_ => tx.get_root_key(&ix.name).await,
+	};
+	let de = res.map_err(|e| {
+		trace!("The definition of the API key could not be found: {e}");
+		Error::InvalidAuth
+	})?;
+	// The key may have been redefined with a new secret
+	if de.hash != hash {
+		trace!("The API key was replaced by a new secret");
+		return Err(Error::InvalidAuth);
+	}
+	// Check if the key has expired
+	if de.expires.as_ref().is_some_and(|v| v.0 < Utc::now()) {
+		trace!("The API key `{}` has expired", de.name);
+		return Err(Error::InvalidAuth);
+	}
+	Ok((ix, de))
+}
+
+/// Records the time at which an API key was used, at most once every interval
+async fn touch(kvs: &Datastore, hash: &str, mut ix: Index) -> Result<(), Error> {
+	let now = Utc::now();
+	if ix.used.as_ref().is_some_and(|v| now.signed_duration_since(v.0) < *USED_INTERVAL) {
+		return Ok(());
+	}
+	let old = ix.clone();
+	ix.used = Some(now.into());
+	let mut tx = kvs.transaction(Write, Optimistic).await?;
+	// The key may have been removed or redefined since it was verified
+	match tx.putc(crate::key::root::ak::new(hash), ix, Some(old)).await {
+		Ok(_) => tx.commit().await,
+		Err(Error::TxConditionNotMet) => tx.cancel().await,
+		Err(e) => {
+			tx.cancel().await?;
+			Err(e)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn keys_are_random() {
+		let key = generate();
+		assert!(key.starts_with(PREFIX));
+		assert_eq!(key.len(), PREFIX.len() + KEY_LENGTH);
+		assert_ne!(key, generate());
+		assert_eq!(hash(&key), hash(&key));
+		assert_ne!(hash(&key), hash(&generate()));
+	}
+
+	#[test]
+	fn index_level() {
+		let ix = Index::new(Level::Database("ns".into(), "db".into()), "svc");
+		assert_eq!(ix.level(), Level::Database("ns".into(), "db".into()));
+		let ix = Index::new(Level::Namespace("ns".into()), "svc");
+		assert_eq!(ix.level(), Level::Namespace("ns".into()));
+		let ix = Index::new(Level::Root, "svc");
+		assert_eq!(ix.level(), Level::Root);
+	}
+
+	#[test]
+	fn index_is() {
+		let level = Level::Database("ns".into(), "db".into());
+		let ix = Index::new(level.clone(), "svc");
+		assert!(ix.is(&level, "svc"));
+		assert!(!ix.is(&level, "other"));
+		assert!(!ix.is(&Level::Database("ns".into(), "other".into()), "svc"));
+		assert!(!ix.is(&Level::Namespace("ns".into()), "svc"));
+		assert!(!ix.is(&Level::Root, "svc"));
+	}
+}
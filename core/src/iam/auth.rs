@@ -2,7 +2,7 @@ use crate::sql::statements::{DefineTokenStatement, DefineUserStatement};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 
-use super::{is_allowed, Action, Actor, Error, Level, Resource, Role};
+use super::{is_allowed, Action, Actor, Error, Level, Privilege, Resource, ResourceKind, Role};
 
 /// Specifies the current authentication for the datastore execution context.
 #[derive(Clone, Default, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
@@ -86,6 +86,21 @@ impl Auth {
 	pub fn has_role(&self, role: &Role) -> bool {
 		self.actor.has_role(role)
 	}
+
+	/// Checks if a custom role of the current actor grants an action on a type of resource
+	pub fn has_privilege(&self, action: &Action, kind: &ResourceKind) -> bool {
+		self.actor.has_privilege(action, kind)
+	}
+
+	/// Returns the roles of the current actor
+	pub fn roles(&self) -> &[Role] {
+		self.actor.roles()
+	}
+
+	/// Returns this authentication with the privileges granted by custom roles
+	pub fn with_privileges(&self, privileges: Vec<Privilege>) -> Self {
+		Self::new(self.actor.clone().with_privileges(privileges))
+	}
 }
 
 impl std::convert::From<(&DefineUserStatement, Level)> for Auth {
@@ -0,0 +1,279 @@
+//! Authenticates the client certificates of mutual TLS connections.
+//!
+//! A verified client certificate identifies a system user whose definition
+//! has a `CERTIFICATE` clause matching the subject of the certificate, or one
+//! of its subject alternative names. A client certificate can instead sign in
+//! to a scope with a `CERTIFICATE` clause, which selects the record of the
+//! certificate from the `$subject` and `$san` of the certificate.
+#[cfg(feature = "sql2")]
+use crate::{
+	cnf::INSECURE_FORWARD_SCOPE_ERRORS,
+	dbs::Session,
+	err::Error,
+	iam::{Actor, Auth, Level},
+	kvs::{Datastore, LockType::*, TransactionType::*},
+	sql::{statements::DefineUserStatement, Value},
+};
+#[cfg(feature = "sql2")]
+use std::{collections::BTreeMap, sync::Arc};
+
+/// The identities of a verified client certificate
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Certificate {
+	/// The distinguished name of the subject, such as `CN=api,O=mesh`, with
+	/// its attribute values escaped as described in RFC 4514
+	pub subject: String,
+	/// The DNS names, URIs, email addresses, and IP addresses of the subject
+	pub san: Vec<String>,
+}
+
+#[cfg(feature = "sql2")]
+impl Certificate {
+	/// Checks if the `CERTIFICATE` clause of a user identifies this certificate
+	pub(crate) fn matches(&self, rule: &str) -> bool {
+		match (parse(rule), parse(&self.subject)) {
+			(Some(rule), Some(subject)) if rule == subject => true,
+			_ => self.san.iter().any(|v| v == rule),
+		}
+	}
+}
+
+/// The attribute types and values of a relative distinguished name
+#[cfg(feature = "sql2")]
+type Rdn = Vec<(String, String)>;
+
+/// Parses a distinguished name, as described in RFC 4514, into its sequence
+/// of relative distinguished names, ignoring the whitespace between them
+#[cfg(feature = "sql2")]
+fn parse(name: &str) -> Option<Vec<Rdn>> {
+	let mut rdns = Vec::new();
+	let mut rdn = Vec::new();
+	let mut chars = name.chars();
+	loop {
+		// Parse the attribute type
+		let mut key = String::new();
+		loop {
+			match chars.next()? {
+				'=' => break,
+				c => key.push(c),
+			}
+		}
+		let key = key.trim();
+		if key.is_empty() {
+			return None;
+		}
+		// Parse the attribute value, until the next unescaped separator
+		let mut val = Vec::new();
+		let mut end = 0;
+		let sep = loop {
+			match chars.next() {
+				None => break None,
+				Some(c @ (',' | '+')) => break Some(c),
+				Some('\\') => {
+					match chars.next()? {
+						c if c.is_ascii_hexdigit() => {
+							let hex = [c, chars.next().filter(char::is_ascii_hexdigit)?];
+							let hex: String = hex.iter().collect();
+							val.push(u8::from_str_radix(&hex, 16).ok()?);
+						}
+						c => {
+							let mut buf = [0; 4];
+							val.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+						}
+					}
+					end = val.len();
+				}
+				// Ignore the unescaped leading whitespace
+				Some(' ') if val.is_empty() => {}
+				Some(c) => {
+					let mut buf = [0; 4];
+					val.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+					if c != ' ' {
+						end = val.len();
+					}
+				}
+			}
+		};
+		// Remove the unescaped trailing whitespace
+		val.truncate(end);
+		rdn.push((key.to_ascii_uppercase(), String::from_utf8(val).ok()?));
+		match sep {
+			Some('+') => continue,
+			Some(_) => rdns.push(std::mem::take(&mut rdn)),
+			None => {
+				rdns.push(rdn);
+				break;
+			}
+		}
+	}
+	// The attributes of a multi-valued name are unordered
+	for rdn in rdns.iter_mut() {
+		rdn.sort();
+	}
+	Some(rdns)
+}
+
+/// Authenticates the session with a client certificate
+#[cfg(feature = "sql2")]
+pub async fn verify(
+	kvs: &Datastore,
+	session: &mut Session,
+	cert: &Certificate,
+	ns: Option<&str>,
+	db: Option<&str>,
+	sc: Option<&str>,
+) -> Result<(), Error> {
+	// Log the authentication type
+	trace!("Attempting client certificate authentication");
+	match (ns, db, sc) {
+		(Some(ns), Some(db), Some(sc)) => scope(kvs, session, cert, ns, db, sc).await,
+		(Some(ns), Some(db), None) => {
+			let level = Level::Database(ns.to_owned(), db.to_owned());
+			user(kvs, session, cert, level).await
+		}
+		(Some(ns), None, None) => user(kvs, session, cert, Level::Namespace(ns.to_owned())).await,
+		(None, None, None) => user(kvs, session, cert, Level::Root).await,
+		_ => Err(Error::InvalidAuth),
+	}
+}
+
+/// Finds the system user which is identified by the certificate
+#[cfg(feature = "sql2")]
+async fn user(
+	kvs: &Datastore,
+	session: &mut Session,
+	cert: &Certificate,
+	level: Level,
+) -> Result<(), Error> {
+	// Create a new readonly transaction
+	let mut tx = kvs.transaction(Read, Optimistic).await?;
+	let res = match (level.ns(), level.db()) {
+		(Some(ns), Some(db)) => tx.all_db_users(ns, db).await,
+		(Some(ns), None) => tx.all_ns_users(ns).await,
+		_ => tx.all_root_users().await,
+	};
+	tx.cancel().await?;
+	let us: Option<DefineUserStatement> =
+		res?.iter().find(|us| us.certificate.as_ref().is_some_and(|v| cert.matches(v))).cloned();
+	match us {
+		Some(us) => {
+			debug!("Authenticated as {} user '{}' with a client certificate", level, us.name);
+			session.au = Arc::new((&us, level).into());
+			Ok(())
+		}
+		None => {
+			trace!("No user is identified by the client certificate '{}'", cert.subject);
+			Err(Error::InvalidAuth)
+		}
+	}
+}
+
+/// Selects the scope record of the certificate
+#[cfg(feature = "sql2")]
+async fn scope(
+	kvs: &Datastore,
+	session: &mut Session,
+	cert: &Certificate,
+	ns: &str,
+	db: &str,
+	sc: &str,
+) -> Result<(), Error> {
+	// Create a new readonly transaction
+	let mut tx = kvs.transaction(Read, Optimistic).await?;
+	// Fetch the specified scope from storage
+	let scope = tx.get_sc(ns, db, sc).await;
+	// Ensure that the transaction is cancelled
+	tx.cancel().await?;
+	let Ok(sv) = scope else {
+		return Err(Error::NoScopeFound);
+	};
+	// Check that the scope allows client certificates
+	let Some(val) = sv.certificate else {
+		trace!("The scope `{}` does not allow client certificates", sc);
+		return Err(Error::InvalidAuth);
+	};
+	// Setup the query params
+	let vars = BTreeMap::from([
+		("subject".to_owned(), Value::from(cert.subject.as_str())),
+		("san".to_owned(), cert.san.iter().map(String::as_str).map(Value::from).collect()),
+	]);
+	// Setup the system session for finding the certificate record
+	let mut sess = Session::editor().with_ns(ns).with_db(db);
+	sess.ip = session.ip.clone();
+	sess.or = session.or.clone();
+	// Compute the value with the params
+	let rid = match kvs.evaluate(val, &sess, Some(vars)).await {
+		Ok(val) => match val.record() {
+			Some(rid) => rid,
+			_ => return Err(Error::NoRecordFound),
+		},
+		Err(e) => match e {
+			Error::Thrown(_) => return Err(e),
+			e if *INSECURE_FORWARD_SCOPE_ERRORS => return Err(e),
+			_ => return Err(Error::SigninQueryFailed),
+		},
+	};
+	// Log the authenticated scope info
+	debug!("Authenticated to scope `{}` as `{}` with a client certificate", sc, rid);
+	// Set the authentication on the session
+	session.tk = None;
+	session.ns = Some(ns.to_owned());
+	session.db = Some(db.to_owned());
+	session.sc = Some(sc.to_owned());
+	session.sd = Some(Value::from(rid.to_owned()));
+	session.au = Arc::new(Auth::new(Actor::new(
+		rid.to_string(),
+		Default::default(),
+		Level::Scope(ns.to_owned(), db.to_owned(), sc.to_owned()),
+	)));
+	Ok(())
+}
+
+#[cfg(all(test, feature = "sql2"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn certificate_matches() {
+		let cert = Certificate {
+			subject: "CN=api,O=mesh".to_owned(),
+			san: vec!["spiffe://mesh/ns/prod/sa/api".to_owned(), "api.prod".to_owned()],
+		};
+		assert!(cert.matches("CN=api,O=mesh"));
+		assert!(cert.matches("CN=api, O=mesh"));
+		assert!(cert.matches("spiffe://mesh/ns/prod/sa/api"));
+		assert!(cert.matches("api.prod"));
+		assert!(!cert.matches("CN=api"));
+		assert!(!cert.matches("CN=web,O=mesh"));
+		assert!(!cert.matches("spiffe://mesh/ns/prod/sa"));
+	}
+
+	#[test]
+	fn certificate_matches_escaped_values() {
+		// A value which contains a separator can not impersonate another subject
+		let cert = Certificate {
+			subject: "CN=api\\,O=mesh".to_owned(),
+			san: vec![],
+		};
+		assert!(!cert.matches("CN=api,O=mesh"));
+		assert!(cert.matches("CN=api\\,O=mesh"));
+		assert!(cert.matches("cn=api\\2cO=mesh"));
+		// Escaped whitespace is part of the value
+		let cert = Certificate {
+			subject: "CN=\\ api\\ ,O=mesh".to_owned(),
+			san: vec![],
+		};
+		assert!(cert.matches("CN=\\ api\\ , O=mesh"));
+		assert!(!cert.matches("CN=api,O=mesh"));
+		// Multi-valued names match regardless of the order of their attributes
+		let cert = Certificate {
+			subject: "CN=api+UID=1,O=mesh".to_owned(),
+			san: vec![],
+		};
+		assert!(cert.matches("UID=1 + CN=api, O=mesh"));
+		assert!(!cert.matches("CN=api,UID=1,O=mesh"));
+		// Malformed names never match
+		assert!(!cert.matches("CN=api+UID=1,O"));
+		assert!(!cert.matches("CN=api\\"));
+	}
+}
@@ -1,11 +1,16 @@
 use std::str::FromStr;
 
 use cedar_policy::{Entity, EntityId, EntityTypeName, EntityUid};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
 
 use crate::dbs::Statement;
+use crate::iam::Error;
 
-// TODO(sgirones): For now keep it simple. In the future, we will allow for custom roles and policies using a more exhaustive list of actions and resources.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd)]
+// TODO(sgirones): For now keep it simple. In the future, we will allow for custom policies using a more exhaustive list of actions and resources.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[revisioned(revision = 1)]
 pub enum Action {
 	View,
 	Edit,
@@ -20,6 +25,17 @@ impl std::fmt::Display for Action {
 	}
 }
 
+impl FromStr for Action {
+	type Err = Error;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_ascii_lowercase().as_str() {
+			"view" => Ok(Self::View),
+			"edit" => Ok(Self::Edit),
+			_ => Err(Error::InvalidAction(s.to_string())),
+		}
+	}
+}
+
 impl Action {
 	pub fn id(&self) -> String {
 		self.to_string()
@@ -0,0 +1,96 @@
+use cedar_policy::RestrictedExpression;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+
+use super::{Action, ResourceKind};
+use crate::iam::Error;
+
+/// A privilege granted by a custom role, allowing an action on a type of resource.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[revisioned(revision = 1)]
+pub struct Privilege {
+	pub action: Action,
+	pub kind: ResourceKind,
+}
+
+impl Privilege {
+	pub fn new(action: Action, kind: ResourceKind) -> Self {
+		Self {
+			action,
+			kind,
+		}
+	}
+
+	/// Checks if this privilege allows the action on the type of resource.
+	/// Being allowed to edit a type of resource implies being allowed to view it.
+	pub fn allows(&self, action: &Action, kind: &ResourceKind) -> bool {
+		&self.kind == kind && (&self.action == action || self.action == Action::Edit)
+	}
+
+	/// The name of a type of resource, as used in `DEFINE ROLE` statements
+	pub fn kind_name(kind: &ResourceKind) -> &'static str {
+		match kind {
+			ResourceKind::Any => "ANY",
+			ResourceKind::Namespace => "NAMESPACE",
+			ResourceKind::Database => "DATABASE",
+			ResourceKind::Scope => "SCOPE",
+			ResourceKind::Table => "TABLE",
+			ResourceKind::Document => "RECORD",
+			ResourceKind::Option => "OPTION",
+			ResourceKind::Function => "FUNCTION",
+			ResourceKind::Analyzer => "ANALYZER",
+			ResourceKind::Parameter => "PARAM",
+			ResourceKind::Model => "MODEL",
+			ResourceKind::Event => "EVENT",
+			ResourceKind::Field => "FIELD",
+			ResourceKind::Index => "INDEX",
+			ResourceKind::Consumer => "CONSUMER",
+			ResourceKind::Webhook => "WEBHOOK",
+			ResourceKind::Role => "ROLE",
+			ResourceKind::Policy => "POLICY",
+			ResourceKind::Actor => "USER",
+		}
+	}
+
+	/// Parses the name of a type of resource, as used in `DEFINE ROLE` statements
+	pub fn parse_kind(name: &str) -> Result<ResourceKind, Error> {
+		match name.to_ascii_uppercase().as_str() {
+			"ANY" => Ok(ResourceKind::Any),
+			"NAMESPACE" | "NS" => Ok(ResourceKind::Namespace),
+			"DATABASE" | "DB" => Ok(ResourceKind::Database),
+			"SCOPE" => Ok(ResourceKind::Scope),
+			"TABLE" => Ok(ResourceKind::Table),
+			"RECORD" => Ok(ResourceKind::Document),
+			"OPTION" => Ok(ResourceKind::Option),
+			"FUNCTION" => Ok(ResourceKind::Function),
+			"ANALYZER" => Ok(ResourceKind::Analyzer),
+			"PARAM" => Ok(ResourceKind::Parameter),
+			"MODEL" => Ok(ResourceKind::Model),
+			"EVENT" => Ok(ResourceKind::Event),
+			"FIELD" => Ok(ResourceKind::Field),
+			"INDEX" => Ok(ResourceKind::Index),
+			"CONSUMER" => Ok(ResourceKind::Consumer),
+			"WEBHOOK" => Ok(ResourceKind::Webhook),
+			"ROLE" => Ok(ResourceKind::Role),
+			"POLICY" => Ok(ResourceKind::Policy),
+			"USER" => Ok(ResourceKind::Actor),
+			_ => Err(Error::InvalidResource(name.to_string())),
+		}
+	}
+}
+
+impl std::fmt::Display for Privilege {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} ON {}", self.action.to_string().to_uppercase(), Self::kind_name(&self.kind))
+	}
+}
+
+impl std::convert::From<&Privilege> for RestrictedExpression {
+	fn from(privilege: &Privilege) -> Self {
+		RestrictedExpression::new_record([
+			("action".to_string(), RestrictedExpression::new_string(privilege.action.id())),
+			("type".to_string(), RestrictedExpression::new_string(privilege.kind.to_string())),
+		])
+	}
+}
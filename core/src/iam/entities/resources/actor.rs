@@ -7,7 +7,7 @@ use cedar_policy::{Entity, EntityId, EntityTypeName, EntityUid, RestrictedExpres
 use serde::{Deserialize, Serialize};
 
 use super::{Level, Resource, ResourceKind};
-use crate::iam::Role;
+use crate::iam::{Action, Privilege, Role};
 use crate::sql::statements::{DefineTokenStatement, DefineUserStatement};
 
 //
@@ -15,10 +15,13 @@ use crate::sql::statements::{DefineTokenStatement, DefineUserStatement};
 //
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub struct Actor {
 	res: Resource,
 	roles: Vec<Role>,
+	/// The privileges granted by the custom roles of the actor
+	#[revision(start = 2)]
+	privileges: Vec<Privilege>,
 }
 
 impl Default for Actor {
@@ -26,6 +29,7 @@ impl Default for Actor {
 		Self {
 			res: ResourceKind::Actor.on_level(Level::No),
 			roles: Vec::new(),
+			privileges: Vec::new(),
 		}
 	}
 }
@@ -52,20 +56,38 @@ impl Actor {
 		Self {
 			res: Resource::new(id, super::ResourceKind::Actor, level),
 			roles,
+			privileges: Vec::new(),
 		}
 	}
 
+	/// Sets the privileges granted by the custom roles of the actor.
+	pub fn with_privileges(mut self, privileges: Vec<Privilege>) -> Self {
+		self.privileges = privileges;
+		self
+	}
+
 	/// Checks if the actor has the given role.
 	pub fn has_role(&self, role: &Role) -> bool {
 		self.roles.contains(role)
 	}
 
+	/// Returns the roles of the actor.
+	pub fn roles(&self) -> &[Role] {
+		&self.roles
+	}
+
+	/// Checks if any custom role of the actor grants the action on the type of resource.
+	pub fn has_privilege(&self, action: &Action, kind: &ResourceKind) -> bool {
+		self.privileges.iter().any(|p| p.allows(action, kind))
+	}
+
 	// Cedar policy helpers
 	pub fn cedar_attrs(&self) -> HashMap<String, RestrictedExpression> {
 		[
 			("type", self.kind().into()),
 			("level", self.level().into()),
 			("roles", RestrictedExpression::new_set(self.roles.iter().map(|r| r.into()))),
+			("privileges", RestrictedExpression::new_set(self.privileges.iter().map(|p| p.into()))),
 		]
 		.into_iter()
 		.map(|(x, v)| (x.into(), v))
@@ -66,7 +66,7 @@ impl Level {
 		}
 	}
 
-	fn parent(&self) -> Option<Level> {
+	pub(crate) fn parent(&self) -> Option<Level> {
 		match self {
 			Level::No => None,
 			Level::Root => None,
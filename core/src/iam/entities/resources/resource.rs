@@ -28,9 +28,13 @@ pub enum ResourceKind {
 	Event,
 	Field,
 	Index,
+	Consumer,
+	Webhook,
 
 	// IAM
 	Actor,
+	Role,
+	Policy,
 }
 
 impl std::fmt::Display for ResourceKind {
@@ -50,7 +54,11 @@ impl std::fmt::Display for ResourceKind {
 			ResourceKind::Event => write!(f, "Event"),
 			ResourceKind::Field => write!(f, "Field"),
 			ResourceKind::Index => write!(f, "Index"),
+			ResourceKind::Consumer => write!(f, "Consumer"),
+			ResourceKind::Webhook => write!(f, "Webhook"),
 			ResourceKind::Actor => write!(f, "Actor"),
+			ResourceKind::Role => write!(f, "Role"),
+			ResourceKind::Policy => write!(f, "Policy"),
 		}
 	}
 }
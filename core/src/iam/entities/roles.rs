@@ -5,15 +5,18 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-// In the future, we will allow for custom roles. For now, provide predefined roles.
+// Predefined roles, along with custom roles which are defined with `DEFINE ROLE`.
 #[derive(Hash, Clone, Default, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub enum Role {
 	#[default]
 	Viewer,
 	Editor,
 	Owner,
+	/// A custom role, granting the privileges of its definition
+	#[revision(start = 2)]
+	Custom(String),
 }
 
 impl std::fmt::Display for Role {
@@ -22,6 +25,7 @@ impl std::fmt::Display for Role {
 			Self::Viewer => write!(f, "Viewer"),
 			Self::Editor => write!(f, "Editor"),
 			Self::Owner => write!(f, "Owner"),
+			Self::Custom(name) => write!(f, "{name}"),
 		}
 	}
 }
@@ -38,9 +42,19 @@ impl FromStr for Role {
 	}
 }
 
+impl Role {
+	/// Returns the name of the role if it is a custom role
+	pub fn custom(&self) -> Option<&str> {
+		match self {
+			Self::Custom(name) => Some(name),
+			_ => None,
+		}
+	}
+}
+
 impl std::convert::From<&Ident> for Role {
 	fn from(id: &Ident) -> Self {
-		Role::from_str(id).unwrap()
+		Role::from_str(id).unwrap_or_else(|_| Role::Custom(id.to_raw()))
 	}
 }
 
@@ -14,6 +14,14 @@ pub static DEFAULT_CEDAR_SCHEMA: Lazy<serde_json::Value> = Lazy::new(|| {
 							"level" : { "type": "Entity", "name": "Level", "required": true },
 						}
 					},
+					// Represents a privilege granted by a custom role
+					"Privilege": {
+						"type": "Record",
+						"attributes": {
+							"action": { "type": "String", "required": true },
+							"type": { "type": "String", "required": true },
+						}
+					},
 				},
 				"entityTypes": {
 					// Represents the Root, Namespace, Database and Scope levels
@@ -56,6 +64,7 @@ pub static DEFAULT_CEDAR_SCHEMA: Lazy<serde_json::Value> = Lazy::new(|| {
 								"type": { "type": "String", "required": true },
 								"level" : { "type": "Entity", "name": "Level", "required": true },
 								"roles": { "type": "Set", "element": { "type": "Entity", "name": "Role" }, "required": true},
+								"privileges": { "type": "Set", "element": { "type": "Privilege" }, "required": true},
 							},
 						},
 						"memberOfTypes": ["Level"],
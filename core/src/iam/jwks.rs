@@ -11,9 +11,9 @@ use sha2::{Digest, Sha256};
 use std::str::FromStr;
 
 #[cfg(test)]
-static CACHE_EXPIRATION: Lazy<chrono::Duration> = Lazy::new(|| Duration::seconds(1));
+pub(super) static CACHE_EXPIRATION: Lazy<chrono::Duration> = Lazy::new(|| Duration::seconds(1));
 #[cfg(not(test))]
-static CACHE_EXPIRATION: Lazy<chrono::Duration> =
+pub(super) static CACHE_EXPIRATION: Lazy<chrono::Duration> =
 	Lazy::new(|| match std::env::var("SURREAL_JWKS_CACHE_EXPIRATION_SECONDS") {
 		Ok(seconds_str) => {
 			let seconds = seconds_str.parse::<u64>().expect(
@@ -43,7 +43,7 @@ static CACHE_COOLDOWN: Lazy<chrono::Duration> =
 	});
 
 #[cfg(not(target_arch = "wasm32"))]
-static REMOTE_TIMEOUT: Lazy<chrono::Duration> =
+pub(super) static REMOTE_TIMEOUT: Lazy<chrono::Duration> =
 	Lazy::new(|| match std::env::var("SURREAL_JWKS_REMOTE_TIMEOUT_MILLISECONDS") {
 		Ok(milliseconds_str) => {
 			let milliseconds = milliseconds_str
@@ -166,7 +166,7 @@ async fn find_jwk_from_url(kvs: &Datastore, url: &str, kid: &str) -> Result<Jwk,
 }
 
 // Returns an error if network access to the address from a given URL string is not allowed
-fn check_capabilities_url(kvs: &Datastore, url: &str) -> Result<(), Error> {
+pub(super) fn check_capabilities_url(kvs: &Datastore, url: &str) -> Result<(), Error> {
 	let url_parsed = match Url::parse(url) {
 		Ok(url) => url,
 		Err(_) => {
@@ -2,16 +2,25 @@ use cedar_policy::Context;
 pub use entities::Level;
 use thiserror::Error;
 
+#[cfg(feature = "sql2")]
+pub mod apikey;
 pub mod auth;
 pub mod base;
+pub mod certificate;
 pub mod check;
 pub mod clear;
 pub mod entities;
 #[cfg(feature = "jwks")]
 pub mod jwks;
+#[cfg(feature = "jwks")]
+pub mod oidc;
+pub mod password;
 pub mod policies;
+pub mod refresh;
+pub(crate) mod roles;
 pub mod signin;
 pub mod signup;
+pub mod throttle;
 pub mod token;
 pub mod verify;
 
@@ -23,6 +32,12 @@ pub enum Error {
 	#[error("Invalid role '{0}'")]
 	InvalidRole(String),
 
+	#[error("Invalid action '{0}'")]
+	InvalidAction(String),
+
+	#[error("Invalid resource '{0}'")]
+	InvalidResource(String),
+
 	#[error("Not enough permissions to perform this action")]
 	NotAllowed {
 		actor: String,
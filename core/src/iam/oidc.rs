@@ -0,0 +1,253 @@
+//! Verifies the tokens of OpenID Connect providers, which are defined with `TYPE OIDC`.
+//!
+//! The keys of a provider are found through its discovery document, which is
+//! served at `{issuer}/.well-known/openid-configuration`. The tokens of these
+//! providers do not name the token definition which they are signed for, so the
+//! definitions are found by the issuer of the token, and the claims of the token
+//! are mapped onto the session with the CLAIMS clause of the definition.
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::iam::{jwks, Level};
+use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+use crate::sql::{statements::DefineTokenStatement, Algorithm, Value};
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{DecodingKey, Validation};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+
+/// A token definition for an OpenID Connect provider
+pub(super) struct Provider {
+	/// The level on which the token is defined
+	pub(super) level: Level,
+	/// The definition of the token
+	pub(super) de: DefineTokenStatement,
+}
+
+impl Provider {
+	/// Finds the claim of a token which is mapped onto a claim of the session
+	fn claim<'a>(&self, name: &str, claims: &'a JsonValue) -> Option<&'a JsonValue> {
+		let path = match self.de.claims.as_ref()?.get(name)? {
+			Value::Strand(v) => v.as_str(),
+			_ => return None,
+		};
+		// Claims which are named with a URL contain dots, so are matched first
+		claims.get(path).or_else(|| path.split('.').try_fold(claims, |v, part| v.get(part)))
+	}
+
+	/// Checks that the mapped namespace, database, and scope are those of the definition
+	pub(super) fn matches(&self, claims: &JsonValue) -> bool {
+		let mapped = |name| self.de.claims.as_ref().is_some_and(|v| v.contains_key(name));
+		[("ns", self.level.ns()), ("db", self.level.db()), ("sc", self.level.scope())]
+			.into_iter()
+			.filter(|(name, level)| level.is_some() && mapped(*name))
+			.all(|(name, level)| self.claim(name, claims).and_then(JsonValue::as_str) == level)
+	}
+
+	/// The record id which is mapped from a token
+	pub(super) fn id<'a>(&self, claims: &'a JsonValue) -> Option<&'a str> {
+		self.claim("id", claims)?.as_str()
+	}
+
+	/// The roles which are mapped from a token
+	pub(super) fn roles(&self, claims: &JsonValue) -> Option<Vec<String>> {
+		match self.claim("roles", claims)? {
+			JsonValue::String(v) => Some(vec![v.to_owned()]),
+			JsonValue::Array(v) => {
+				Some(v.iter().filter_map(JsonValue::as_str).map(str::to_owned).collect())
+			}
+			_ => None,
+		}
+	}
+}
+
+/// Finds the token definitions for an issuer, within the namespace and database of the session
+///
+/// The definitions are only looked up on the levels which the session has selected, so that
+/// a token from an unknown issuer can not cause every namespace and database to be scanned.
+pub(super) async fn providers(
+	kvs: &Datastore,
+	session: &Session,
+	iss: &str,
+) -> Result<Vec<Provider>, Error> {
+	let is_provider = |de: &&DefineTokenStatement| de.kind == Algorithm::Oidc && de.code == iss;
+	let mut out = Vec::new();
+	// A namespace is required to find a provider
+	let Some(ns) = &session.ns else {
+		trace!("No namespace is selected to find the provider for issuer `{}`", iss);
+		return Ok(out);
+	};
+	// Create a new readonly transaction
+	let mut tx = kvs.transaction(Read, Optimistic).await?;
+	for de in tx.all_ns_tokens(ns).await?.iter().filter(is_provider) {
+		out.push(Provider {
+			level: Level::Namespace(ns.to_owned()),
+			de: de.to_owned(),
+		});
+	}
+	if let Some(db) = &session.db {
+		for de in tx.all_db_tokens(ns, db).await?.iter().filter(is_provider) {
+			out.push(Provider {
+				level: Level::Database(ns.to_owned(), db.to_owned()),
+				de: de.to_owned(),
+			});
+		}
+		for sc in tx.all_sc(ns, db).await?.iter() {
+			for de in tx.all_sc_tokens(ns, db, &sc.name).await?.iter().filter(is_provider) {
+				out.push(Provider {
+					level: Level::Scope(ns.to_owned(), db.to_owned(), sc.name.to_raw()),
+					de: de.to_owned(),
+				});
+			}
+		}
+	}
+	tx.cancel().await?;
+	Ok(out)
+}
+
+// Generates a verification configuration for the tokens of an OpenID Connect provider
+// Finds the JWKS location of the provider from its discovery document
+// Source: https://openid.net/specs/openid-connect-discovery-1_0.html
+pub(super) async fn config(
+	kvs: &Datastore,
+	kid: &str,
+	de: &DefineTokenStatement,
+) -> Result<(DecodingKey, Validation), Error> {
+	let discovery = discover(kvs, &de.code).await?;
+	let (key, mut validation) = jwks::config(kvs, kid, &discovery.jwks_uri).await?;
+	// The token must be issued by the provider, for the audience of the definition
+	validation.set_issuer(&[&de.code]);
+	if let Some(aud) = &de.audience {
+		validation.set_audience(&[aud.as_str()]);
+	}
+	Ok((key, validation))
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Discovery {
+	issuer: String,
+	jwks_uri: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiscoveryCache {
+	discovery: Discovery,
+	time: DateTime<Utc>,
+}
+
+// Fetches the discovery document of a provider either from local cache or remote location
+async fn discover(kvs: &Datastore, issuer: &str) -> Result<Discovery, Error> {
+	let path = cache_path_from_issuer(issuer);
+	if let Ok(bytes) = crate::obs::get(&path).await {
+		if let Ok(cache) = serde_json::from_slice::<DiscoveryCache>(&bytes) {
+			// Check that the cached discovery document has not expired yet
+			if Utc::now().signed_duration_since(cache.time) < *jwks::CACHE_EXPIRATION {
+				trace!("Successfully fetched OIDC discovery document from local cache");
+				return Ok(cache.discovery);
+			}
+		}
+	}
+	let url = format!("{}/.well-known/openid-configuration", issuer.trim_end_matches('/'));
+	// Check that the datastore capabilities allow connections to the URL host
+	if let Err(err) = jwks::check_capabilities_url(kvs, &url) {
+		warn!("Network access to OIDC discovery location is not allowed: '{}'", err);
+		return Err(Error::InvalidAuth); // Return opaque error
+	}
+	let client = Client::new();
+	#[cfg(not(target_arch = "wasm32"))]
+	let res = client.get(&url).timeout((*jwks::REMOTE_TIMEOUT).to_std().unwrap()).send().await?;
+	#[cfg(target_arch = "wasm32")]
+	let res = client.get(&url).send().await?;
+	if !res.status().is_success() {
+		warn!(
+			"Unsuccessful HTTP status code received when fetching OIDC discovery document: '{:?}'",
+			res.status()
+		);
+		return Err(Error::InvalidAuth); // Return opaque error
+	}
+	let discovery = match serde_json::from_slice::<Discovery>(&res.bytes().await?) {
+		Ok(discovery) => discovery,
+		Err(err) => {
+			warn!("Failed to parse malformed OIDC discovery document: '{}'", err);
+			return Err(Error::InvalidAuth); // Return opaque error
+		}
+	};
+	// The issuer of the discovery document must be the one which was requested
+	// Source: https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderConfigurationValidation
+	if discovery.issuer != issuer {
+		warn!("The issuer of the OIDC discovery document does not match: '{}'", discovery.issuer);
+		return Err(Error::InvalidAuth); // Return opaque error
+	}
+	// Cache the discovery document by its issuer
+	let cache = DiscoveryCache {
+		discovery: discovery.clone(),
+		time: Utc::now(),
+	};
+	match serde_json::to_vec(&cache) {
+		Ok(data) => match crate::obs::put(&path, data).await {
+			Ok(_) => trace!("Successfully stored OIDC discovery document in local cache"),
+			Err(err) => warn!("Failed to store OIDC discovery document in local cache: '{}'", err),
+		},
+		Err(err) => warn!("Failed to cache malformed OIDC discovery document: '{}'", err),
+	}
+	Ok(discovery)
+}
+
+// Generates a unique cache path for a given issuer
+fn cache_path_from_issuer(issuer: &str) -> String {
+	let mut hasher = Sha256::new();
+	hasher.update(issuer);
+	let result = hasher.finalize();
+
+	format!("oidc/{:x}.json", result)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::{Base, Object};
+	use serde_json::json;
+
+	fn provider(level: Level, claims: Value) -> Provider {
+		let Value::Object(claims) = claims else {
+			unreachable!()
+		};
+		Provider {
+			level,
+			de: DefineTokenStatement {
+				base: Base::Db,
+				kind: Algorithm::Oidc,
+				claims: Some(claims),
+				..Default::default()
+			},
+		}
+	}
+
+	#[test]
+	fn claims_are_mapped() {
+		let claims = Object::from(map! {
+			"ns".to_string() => Value::from("tenant"),
+			"db".to_string() => Value::from("https://example.com/db"),
+			"id".to_string() => Value::from("surreal.record"),
+			"roles".to_string() => Value::from("realm_access.roles"),
+		});
+		let pv = provider(Level::Database("one".into(), "app".into()), claims.into());
+		let token = json!({
+			"tenant": "one",
+			"https://example.com/db": "app",
+			"surreal": { "record": "user:tobie" },
+			"realm_access": { "roles": ["Editor", "Viewer"] },
+		});
+		assert!(pv.matches(&token));
+		assert_eq!(pv.id(&token), Some("user:tobie"));
+		assert_eq!(pv.roles(&token), Some(vec!["Editor".to_string(), "Viewer".to_string()]));
+		// The mapped namespace must be the one of the definition
+		let other = json!({ "tenant": "two", "https://example.com/db": "app" });
+		assert!(!pv.matches(&other));
+		// Claims which are not mapped are not checked
+		let pv = provider(Level::Database("one".into(), "app".into()), Object::default().into());
+		assert!(pv.matches(&other));
+		assert_eq!(pv.roles(&other), None);
+	}
+}
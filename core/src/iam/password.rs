@@ -0,0 +1,128 @@
+//! Checks the passwords of system users against the complexity and expiry rules of a datastore.
+//!
+//! Passwords are hashed when a `DEFINE USER` statement is parsed, so the statement
+//! only carries the strength of the password, which is checked when the statement
+//! is run, and which is never stored.
+use crate::err::Error;
+use crate::sql::Datetime;
+use chrono::Utc;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The rules for the passwords of system users
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PasswordPolicy {
+	/// The minimum number of characters in a password
+	pub min_length: u32,
+	/// The minimum number of classes of characters (lowercase, uppercase, digits and symbols) in a password
+	pub min_classes: u8,
+	/// The period of time after which a password needs to be changed
+	pub expiry: Option<Duration>,
+}
+
+// The passwords of system users are only checked with the `sql2` feature
+#[cfg_attr(not(feature = "sql2"), allow(dead_code))]
+impl PasswordPolicy {
+	/// Checks that a new password is complex enough
+	pub(crate) fn check(&self, strength: &PasswordStrength) -> Result<(), Error> {
+		if strength.length < self.min_length {
+			return Err(Error::PasswordTooShort {
+				min: self.min_length,
+			});
+		}
+		if strength.classes < self.min_classes {
+			return Err(Error::PasswordTooSimple {
+				min: self.min_classes,
+			});
+		}
+		Ok(())
+	}
+
+	/// Checks that a password, which was set at the given time, has not expired
+	pub(crate) fn check_expiry(&self, changed: Option<&Datetime>) -> Result<(), Error> {
+		match (self.expiry, changed) {
+			(Some(expiry), Some(changed)) => {
+				let age = Utc::now().signed_duration_since(changed.0);
+				match age.to_std().is_ok_and(|age| age > expiry) {
+					true => Err(Error::PasswordExpired),
+					false => Ok(()),
+				}
+			}
+			// Passwords which were set before the time was recorded do not expire
+			_ => Ok(()),
+		}
+	}
+}
+
+/// The complexity of a password
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[revisioned(revision = 1)]
+pub struct PasswordStrength {
+	/// The number of characters in the password
+	pub length: u32,
+	/// The number of classes of characters in the password
+	pub classes: u8,
+}
+
+impl From<&str> for PasswordStrength {
+	fn from(pass: &str) -> Self {
+		let classes: [fn(char) -> bool; 4] =
+			[char::is_lowercase, char::is_uppercase, char::is_numeric, |c| !c.is_alphanumeric()];
+		Self {
+			length: pass.chars().count() as u32,
+			classes: classes.iter().filter(|f| pass.chars().any(**f)).count() as u8,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn password_strength() {
+		assert_eq!(
+			PasswordStrength::from("secret"),
+			PasswordStrength {
+				length: 6,
+				classes: 1
+			}
+		);
+		assert_eq!(
+			PasswordStrength::from("Secr3t!!"),
+			PasswordStrength {
+				length: 8,
+				classes: 4
+			}
+		);
+	}
+
+	#[test]
+	fn password_rules() {
+		let policy = PasswordPolicy {
+			min_length: 8,
+			min_classes: 3,
+			expiry: Some(Duration::from_secs(60)),
+		};
+		assert!(matches!(
+			policy.check(&"Secr3t".into()),
+			Err(Error::PasswordTooShort {
+				min: 8
+			})
+		));
+		assert!(matches!(
+			policy.check(&"secretpassword".into()),
+			Err(Error::PasswordTooSimple {
+				min: 3
+			})
+		));
+		assert!(policy.check(&"Secr3tpassword".into()).is_ok());
+		// Check the expiry of passwords
+		let old = Datetime(Utc::now() - chrono::Duration::minutes(2));
+		assert!(matches!(policy.check_expiry(Some(&old)), Err(Error::PasswordExpired)));
+		assert!(policy.check_expiry(Some(&Datetime::default())).is_ok());
+		assert!(policy.check_expiry(None).is_ok());
+	}
+}
@@ -55,7 +55,7 @@ fn _is_allowed(
 mod tests {
 	use cedar_policy::{ValidationMode, ValidationResult, Validator};
 
-	use crate::iam::{default_schema, entities::Level, ResourceKind, Role};
+	use crate::iam::{default_schema, entities::Level, Privilege, ResourceKind, Role};
 
 	use super::*;
 
@@ -93,4 +93,36 @@ mod tests {
 		let (allowed, _) = is_allowed(&actor, &Action::View, &res, Context::empty());
 		assert!(!allowed);
 	}
+
+	#[test]
+	fn test_is_allowed_with_privileges() {
+		let actor = Actor::new("test".into(), vec![Role::Custom("ops".into())], Level::Root)
+			.with_privileges(vec![
+				Privilege::new(Action::View, ResourceKind::Any),
+				Privilege::new(Action::Edit, ResourceKind::Index),
+			]);
+
+		// Granted privileges apply on the same level hierarchy or below
+		let res = ResourceKind::Any.on_db("test", "test");
+		let (allowed, _) = is_allowed(&actor, &Action::View, &res, Context::empty());
+		assert!(allowed);
+		let (allowed, _) = is_allowed(&actor, &Action::Edit, &res, Context::empty());
+		assert!(!allowed);
+
+		// Being allowed to edit a type of resource implies being allowed to view it
+		let res = ResourceKind::Index.on_db("test", "test");
+		let (allowed, _) = is_allowed(&actor, &Action::View, &res, Context::empty());
+		assert!(allowed);
+		let (allowed, _) = is_allowed(&actor, &Action::Edit, &res, Context::empty());
+		assert!(allowed);
+
+		// Other types of resources are not granted
+		for kind in [ResourceKind::Document, ResourceKind::Actor] {
+			let res = kind.on_db("test", "test");
+			let (allowed, _) = is_allowed(&actor, &Action::View, &res, Context::empty());
+			assert!(!allowed);
+			let (allowed, _) = is_allowed(&actor, &Action::Edit, &res, Context::empty());
+			assert!(!allowed);
+		}
+	}
 }
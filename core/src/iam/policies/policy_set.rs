@@ -27,6 +27,27 @@ pub static POLICY_SET: Lazy<PolicySet> = Lazy::new(|| {
         ["Namespace", "Database", "Scope", "Table", "Document", "Option", "Function", "Analyzer", "Parameter", "Event", "Field", "Index"].contains(resource.type)
     };
 
+    // Custom roles can view the types of resources they were granted, on the same level hierarchy or below
+    permit(
+        principal,
+        action == Action::"View",
+        resource
+    ) when {
+        resource.level in principal.level &&
+        (principal.privileges.contains({ "action": "View", "type": resource.type }) ||
+         principal.privileges.contains({ "action": "Edit", "type": resource.type }))
+    };
+
+    // Custom roles can edit the types of resources they were granted, on the same level hierarchy or below
+    permit(
+        principal,
+        action == Action::"Edit",
+        resource
+    ) when {
+        resource.level in principal.level &&
+        principal.privileges.contains({ "action": "Edit", "type": resource.type })
+    };
+
     // Owner role can edit all resources on the same level hierarchy or below
     permit(
         principal,
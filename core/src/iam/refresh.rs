@@ -0,0 +1,525 @@
+//! Issues, rotates and revokes refresh tokens.
+//!
+//! A refresh token is an opaque random string which is returned alongside the
+//! access token on signin, when requested. Only a hash of the token is stored,
+//! and each token can be used exactly once, as a new refresh token is issued
+//! every time it is exchanged for a new access token.
+//!
+//! The sessions of a user, or of every user of a scope or level, can be revoked.
+//! Any access or refresh token which was issued up until the time of revocation
+//! is then rejected, and the user needs to signin again.
+//!
+//! Refresh tokens which expire without being used are removed periodically.
+use crate::cnf::{REFRESH_TOKEN_EXPIRY, SERVER_NAME};
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::iam::token::{Claims, HEADER};
+use crate::iam::{Action, Actor, Auth, Level, ResourceKind};
+use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+use crate::sql::{Object, Value};
+use crate::syn;
+use chrono::{Duration, Utc};
+use derive::Store;
+use jsonwebtoken::{encode, EncodingKey};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// The number of random characters in a refresh token
+const TOKEN_LENGTH: usize = 48;
+
+/// The number of refresh tokens which are checked for expiry at once
+const GC_BATCH_SIZE: u32 = 1000;
+
+/// The tokens returned when signing in, or when refreshing a session
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Tokens {
+	/// The access token
+	pub token: Option<String>,
+	/// The refresh token, if one was requested
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub refresh: Option<String>,
+}
+
+impl From<Tokens> for Value {
+	/// Only the access token is returned, unless a refresh token was issued
+	fn from(v: Tokens) -> Value {
+		match v.refresh {
+			Some(refresh) => {
+				let mut out = Object::default();
+				out.insert("token".to_string(), v.token.into());
+				out.insert("refresh".to_string(), refresh.into());
+				out.into()
+			}
+			None => v.token.into(),
+		}
+	}
+}
+
+/// A stored refresh token
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[revisioned(revision = 2)]
+pub(crate) struct RefreshToken {
+	pub(crate) ns: Option<String>,
+	pub(crate) db: Option<String>,
+	pub(crate) sc: Option<String>,
+	/// The name of the system user, or the record id of the scope user
+	pub(crate) id: String,
+	#[revision(end = 2, convert_fn = "convert_iat")]
+	pub(crate) iat: i64,
+	pub(crate) exp: i64,
+	/// The time at which the token was issued, in milliseconds
+	#[revision(start = 2)]
+	pub(crate) iat_ms: i64,
+}
+
+impl RefreshToken {
+	fn convert_iat(&mut self, _revision: u16, value: i64) -> Result<(), revision::Error> {
+		self.iat_ms = value.saturating_mul(1000);
+		Ok(())
+	}
+}
+
+/// The time at which the sessions of a subject were revoked
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[revisioned(revision = 2)]
+pub(crate) struct Revocation {
+	#[revision(end = 2, convert_fn = "convert_at")]
+	pub(crate) at: i64,
+	/// The time of the revocation, in milliseconds
+	#[revision(start = 2)]
+	pub(crate) at_ms: i64,
+}
+
+impl Revocation {
+	fn convert_at(&mut self, _revision: u16, value: i64) -> Result<(), revision::Error> {
+		// Revoke the tokens issued at any time within that second
+		self.at_ms = value.saturating_mul(1000).saturating_add(999);
+		Ok(())
+	}
+}
+
+/// The user, or the set of users, whose tokens are revoked
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Subject {
+	ns: Option<String>,
+	db: Option<String>,
+	sc: Option<String>,
+	/// An empty id selects every user of the scope or level
+	id: String,
+}
+
+impl Subject {
+	/// Returns the user who is authenticated on the session
+	fn from_session(session: &Session) -> Result<Self, Error> {
+		let au = &session.au;
+		let (ns, db, sc, id) = match au.level() {
+			Level::Root => (None, None, None, au.id().to_owned()),
+			Level::Namespace(ns) => (Some(ns), None, None, au.id().to_owned()),
+			Level::Database(ns, db) => (Some(ns), Some(db), None, au.id().to_owned()),
+			Level::Scope(ns, db, sc) => match &session.sd {
+				Some(Value::Thing(rid)) => (Some(ns), Some(db), Some(sc), rid.to_raw()),
+				_ => return Err(Error::InvalidAuth),
+			},
+			Level::No => return Err(Error::InvalidAuth),
+		};
+		Ok(Self {
+			ns: ns.cloned(),
+			db: db.cloned(),
+			sc: sc.cloned(),
+			id,
+		})
+	}
+
+	/// Parses the subject of a revocation request
+	fn from_object(vars: &Object) -> Result<Self, Error> {
+		let get = |k: &str| {
+			vars.get(&k.to_uppercase()).or_else(|| vars.get(k)).map(|v| match v {
+				Value::Thing(v) => v.to_raw(),
+				v => v.to_raw_string(),
+			})
+		};
+		let sub = Self {
+			ns: get("ns"),
+			db: get("db"),
+			sc: get("sc"),
+			id: get("id").unwrap_or_default(),
+		};
+		// A scope lives in a database, which lives in a namespace
+		match (&sub.ns, &sub.db, &sub.sc) {
+			(_, None, Some(_)) | (None, Some(_), _) => Err(Error::NoSigninTarget),
+			_ => Ok(sub),
+		}
+	}
+
+	fn key<'a>(&'a self, id: &'a str) -> crate::key::root::rv::Rv<'a> {
+		crate::key::root::rv::new(
+			self.ns.as_deref().unwrap_or_default(),
+			self.db.as_deref().unwrap_or_default(),
+			self.sc.as_deref().unwrap_or_default(),
+			id,
+		)
+	}
+
+	/// Returns the level on which the users of the subject are defined
+	fn level(&self) -> Level {
+		match (&self.ns, &self.db) {
+			(Some(ns), Some(db)) => Level::Database(ns.to_owned(), db.to_owned()),
+			(Some(ns), None) => Level::Namespace(ns.to_owned()),
+			_ => Level::Root,
+		}
+	}
+}
+
+fn hash(token: &str) -> String {
+	hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Issues a refresh token for the user who is authenticated on the session
+pub async fn issue(kvs: &Datastore, session: &Session) -> Result<String, Error> {
+	let sub = Subject::from_session(session)?;
+	let token: String =
+		rand::thread_rng().sample_iter(&Alphanumeric).take(TOKEN_LENGTH).map(char::from).collect();
+	let now = Utc::now();
+	let val = RefreshToken {
+		ns: sub.ns,
+		db: sub.db,
+		sc: sub.sc,
+		id: sub.id,
+		iat_ms: now.timestamp_millis(),
+		exp: now.timestamp().saturating_add(*REFRESH_TOKEN_EXPIRY),
+		..Default::default()
+	};
+	let mut tx = kvs.transaction(Write, Optimistic).await?;
+	tx.set(crate::key::root::rt::new(&hash(&token)), val).await?;
+	tx.commit().await?;
+	Ok(token)
+}
+
+/// Exchanges a refresh token for a new access token and a new refresh token,
+/// and authenticates the session as the user to whom the token was issued
+pub async fn refresh(kvs: &Datastore, session: &mut Session, token: &str) -> Result<Tokens, Error> {
+	// Remove the token, so that it can only be used once
+	let hash = hash(token);
+	let mut tx = kvs.transaction(Write, Optimistic).await?;
+	let key = crate::key::root::rt::new(&hash);
+	let rt: RefreshToken = match tx.get(key.clone()).await? {
+		Some(v) => v.into(),
+		None => {
+			tx.cancel().await?;
+			trace!("The refresh token does not exist, or was already used");
+			return Err(Error::InvalidAuth);
+		}
+	};
+	tx.del(key).await?;
+	tx.commit().await?;
+	// Check if the token can still be used
+	if rt.exp < Utc::now().timestamp() {
+		trace!("The refresh token has expired");
+		return Err(Error::InvalidAuth);
+	}
+	verify(
+		kvs,
+		rt.ns.as_deref(),
+		rt.db.as_deref(),
+		rt.sc.as_deref(),
+		Some(&rt.id),
+		Some(rt.iat_ms),
+	)
+	.await?;
+	// Issue the new tokens
+	let token = authenticate(kvs, session, &rt).await?;
+	let refresh = issue(kvs, session).await?;
+	Ok(Tokens {
+		token: Some(token),
+		refresh: Some(refresh),
+	})
+}
+
+/// Creates a new access token for the user to whom a refresh token was issued
+async fn authenticate(
+	kvs: &Datastore,
+	session: &mut Session,
+	rt: &RefreshToken,
+) -> Result<String, Error> {
+	let mut tx = kvs.transaction(Read, Optimistic).await?;
+	let now = Utc::now();
+	let mut val = Claims {
+		iss: Some(SERVER_NAME.to_owned()),
+		iat: Some(now.timestamp()),
+		iat_ms: Some(now.timestamp_millis()),
+		nbf: Some(now.timestamp()),
+		exp: Some((now + Duration::hours(1)).timestamp()),
+		ns: rt.ns.clone(),
+		db: rt.db.clone(),
+		sc: rt.sc.clone(),
+		id: Some(rt.id.clone()),
+		..Claims::default()
+	};
+	// The user may have been removed since the token was issued
+	let res = match (&rt.ns, &rt.db, &rt.sc) {
+		(Some(ns), Some(db), Some(sc)) => match tx.get_sc(ns, db, sc).await {
+			Ok(sv) => {
+				if let Some(v) = sv.session {
+					val.exp = Some((now + Duration::from_std(v.0).unwrap()).timestamp());
+				}
+				let rid = syn::thing(&rt.id)?;
+				let au = Auth::new(Actor::new(
+					rid.to_string(),
+					Default::default(),
+					Level::Scope(ns.to_owned(), db.to_owned(), sc.to_owned()),
+				));
+				session.sd = Some(Value::from(rid));
+				Ok((sv.code, au))
+			}
+			Err(e) => Err(e),
+		},
+		(Some(ns), Some(db), None) => tx
+			.get_db_user(ns, db, &rt.id)
+			.await
+			.map(|u| (u.code.clone(), (&u, Level::Database(ns.to_owned(), db.to_owned())).into())),
+		(Some(ns), None, None) => tx
+			.get_ns_user(ns, &rt.id)
+			.await
+			.map(|u| (u.code.clone(), (&u, Level::Namespace(ns.to_owned())).into())),
+		(None, None, None) => {
+			tx.get_root_user(&rt.id).await.map(|u| (u.code.clone(), (&u, Level::Root).into()))
+		}
+		_ => Err(Error::InvalidAuth),
+	};
+	tx.cancel().await?;
+	let (code, au) = res.map_err(|e| {
+		trace!("The user of the refresh token could not be found: {e}");
+		Error::InvalidAuth
+	})?;
+	// Create the authentication token
+	let key = EncodingKey::from_secret(code.as_ref());
+	let enc = encode(&HEADER, &val, &key).map_err(|_| Error::TokenMakingFailed)?;
+	// Set the authentication on the session
+	session.tk = Some(val.into());
+	session.ns = rt.ns.clone();
+	session.db = rt.db.clone();
+	session.sc = rt.sc.clone();
+	session.au = Arc::new(au);
+	Ok(enc)
+}
+
+/// Revokes every access and refresh token which was issued to a user, or to every
+/// user of a scope or level when no user is specified. Without a subject, the tokens
+/// of the user who is authenticated on the session are revoked.
+pub async fn revoke(
+	kvs: &Datastore,
+	session: &Session,
+	subject: Option<Object>,
+) -> Result<(), Error> {
+	let sub = match subject {
+		Some(v) => {
+			let sub = Subject::from_object(&v)?;
+			// Users can always revoke their own sessions
+			if Subject::from_session(session).ok().as_ref() != Some(&sub) {
+				session.au.is_allowed(Action::Edit, &ResourceKind::Actor.on_level(sub.level()))?;
+			}
+			sub
+		}
+		None => Subject::from_session(session)?,
+	};
+	let val = Revocation {
+		at_ms: Utc::now().timestamp_millis(),
+		..Default::default()
+	};
+	let mut tx = kvs.transaction(Write, Optimistic).await?;
+	tx.set(sub.key(&sub.id), val).await?;
+	tx.commit().await?;
+	Ok(())
+}
+
+/// Checks that a token which was issued at the given time, in milliseconds, was not revoked since
+pub(crate) async fn verify(
+	kvs: &Datastore,
+	ns: Option<&str>,
+	db: Option<&str>,
+	sc: Option<&str>,
+	id: Option<&str>,
+	iat_ms: Option<i64>,
+) -> Result<(), Error> {
+	let sub = Subject {
+		ns: ns.map(str::to_owned),
+		db: db.map(str::to_owned),
+		sc: sc.map(str::to_owned),
+		id: id.unwrap_or_default().to_owned(),
+	};
+	let mut tx = kvs.transaction(Read, Optimistic).await?;
+	// Check the revocations of the whole scope or level, and of the user
+	let all = tx.get(sub.key("")).await;
+	let one = match sub.id.is_empty() {
+		true => Ok(None),
+		false => tx.get(sub.key(&sub.id)).await,
+	};
+	tx.cancel().await?;
+	// Tokens without an issue time are revoked along with all others
+	let iat_ms = iat_ms.unwrap_or(i64::MIN);
+	for v in [all?, one?].into_iter().flatten() {
+		// Tokens issued within the millisecond of the revocation are revoked too
+		if Revocation::from(v).at_ms >= iat_ms {
+			trace!("The authentication token was revoked");
+			return Err(Error::InvalidAuth);
+		}
+	}
+	Ok(())
+}
+
+/// Removes the refresh tokens which have expired
+pub(crate) async fn garbage_collect(kvs: &Datastore, now: i64) -> Result<(), Error> {
+	let mut beg = crate::key::root::rt::prefix();
+	let end = crate::key::root::rt::suffix();
+	loop {
+		let mut tx = kvs.transaction(Write, Optimistic).await?;
+		let res = match tx.getr(beg.clone()..end.clone(), GC_BATCH_SIZE).await {
+			Ok(v) => v,
+			Err(e) => {
+				tx.cancel().await?;
+				return Err(e);
+			}
+		};
+		for (k, v) in res.iter() {
+			if RefreshToken::from(v.clone()).exp < now {
+				tx.del(k.clone()).await?;
+			}
+		}
+		tx.commit().await?;
+		// Continue after the last entry of the batch
+		match res.last() {
+			Some((k, _)) if res.len() as u32 == GC_BATCH_SIZE => {
+				beg = k.clone();
+				beg.push(0x00);
+			}
+			_ => return Ok(()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::iam::Role;
+
+	async fn signin(ds: &Datastore) -> Session {
+		ds.execute("DEFINE USER user ON ROOT PASSWORD 'pass' ROLES OWNER", &Session::owner(), None)
+			.await
+			.unwrap();
+		let mut sess = Session::default();
+		crate::iam::signin::root(ds, &mut sess, "user".to_string(), "pass".to_string())
+			.await
+			.unwrap();
+		sess
+	}
+
+	#[tokio::test]
+	async fn refresh_rotates_tokens() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = signin(&ds).await;
+		let token = issue(&ds, &sess).await.unwrap();
+		assert_eq!(token.len(), TOKEN_LENGTH);
+		// The refresh token authenticates a new session
+		let mut sess = Session::default();
+		let res = refresh(&ds, &mut sess, &token).await.unwrap();
+		assert!(res.token.is_some());
+		assert!(res.refresh.is_some());
+		assert_ne!(res.refresh.as_deref(), Some(token.as_str()));
+		assert_eq!(sess.au.id(), "user");
+		assert!(sess.au.is_root());
+		assert!(sess.au.has_role(&Role::Owner));
+		// The refresh token can only be used once
+		let mut sess = Session::default();
+		assert!(matches!(refresh(&ds, &mut sess, &token).await, Err(Error::InvalidAuth)));
+		assert!(sess.au.is_anon());
+		// The new refresh token can be used instead
+		refresh(&ds, &mut sess, res.refresh.as_deref().unwrap()).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn expired_tokens_are_removed() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = signin(&ds).await;
+		let token = issue(&ds, &sess).await.unwrap();
+		let hash = hash(&token);
+		let key = crate::key::root::rt::new(&hash);
+		// The token is kept until it expires
+		let now = Utc::now().timestamp();
+		garbage_collect(&ds, now).await.unwrap();
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(tx.exi(key.clone()).await.unwrap());
+		tx.cancel().await.unwrap();
+		// And removed once it has expired
+		garbage_collect(&ds, now + *REFRESH_TOKEN_EXPIRY + 1).await.unwrap();
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(!tx.exi(key).await.unwrap());
+		tx.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn revoke_rejects_earlier_tokens() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = signin(&ds).await;
+		let token = issue(&ds, &sess).await.unwrap();
+		let iat = Utc::now().timestamp_millis();
+		verify(&ds, None, None, None, Some("user"), Some(iat)).await.unwrap();
+		revoke(&ds, &sess, None).await.unwrap();
+		// Both the access tokens and the refresh tokens are rejected
+		let res = verify(&ds, None, None, None, Some("user"), Some(iat)).await;
+		assert!(matches!(res, Err(Error::InvalidAuth)));
+		let mut tmp = Session::default();
+		assert!(matches!(refresh(&ds, &mut tmp, &token).await, Err(Error::InvalidAuth)));
+		// Other users are not affected
+		verify(&ds, None, None, None, Some("other"), Some(iat)).await.unwrap();
+		// Tokens issued after the revocation are accepted
+		let iat = Utc::now().timestamp_millis() + 1;
+		verify(&ds, None, None, None, Some("user"), Some(iat)).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn revoke_accepts_signin_within_the_same_second() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = signin(&ds).await;
+		let iat = Utc::now().timestamp().saturating_mul(1000);
+		revoke(&ds, &sess, None).await.unwrap();
+		tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+		// A new signin right after the revocation is accepted
+		let mut sess = Session::default();
+		let token =
+			crate::iam::signin::root(&ds, &mut sess, "user".to_string(), "pass".to_string())
+				.await
+				.unwrap()
+				.unwrap();
+		let mut sess = Session::default();
+		crate::iam::verify::token(&ds, &mut sess, &token).await.unwrap();
+		// Tokens which only have an issue time in seconds are revoked within the whole second
+		let res = verify(&ds, None, None, None, Some("user"), Some(iat)).await;
+		assert!(matches!(res, Err(Error::InvalidAuth)));
+	}
+
+	#[tokio::test]
+	async fn revoke_scope() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = signin(&ds).await;
+		let mut subject = Object::default();
+		subject.insert("ns".to_string(), "test".into());
+		subject.insert("db".to_string(), "test".into());
+		subject.insert("sc".to_string(), "user".into());
+		revoke(&ds, &sess, Some(subject.clone())).await.unwrap();
+		let iat = Utc::now().timestamp_millis();
+		let res =
+			verify(&ds, Some("test"), Some("test"), Some("user"), Some("user:one"), Some(iat));
+		assert!(matches!(res.await, Err(Error::InvalidAuth)));
+		// Tokens of other scopes are not affected
+		verify(&ds, Some("test"), Some("test"), Some("other"), Some("user:one"), Some(iat))
+			.await
+			.unwrap();
+		// Scope users can not revoke the sessions of others
+		let sess = Session::for_scope("test", "test", "user", Value::None);
+		assert!(matches!(revoke(&ds, &sess, Some(subject)).await, Err(Error::IamError(_))));
+	}
+}
@@ -0,0 +1,98 @@
+//! Resolves the privileges which are granted by custom roles.
+//!
+//! Custom roles are defined with `DEFINE ROLE` on the root, namespace or
+//! database level. A user, or a token with a `roles` claim, can be assigned
+//! any custom role which is defined on its own level or on a level above it.
+//! The privileges of the custom roles of an actor are looked up whenever a
+//! query is run, so that changes to a role apply to existing sessions.
+use crate::err::Error;
+use crate::iam::{Auth, Error as IamError, Level, Privilege, Role};
+use crate::kvs::Transaction;
+use std::sync::Arc;
+
+/// Returns the authentication with the privileges of its custom roles.
+pub(crate) async fn resolve(tx: &mut Transaction, au: &Arc<Auth>) -> Result<Arc<Auth>, Error> {
+	let mut privileges = Vec::new();
+	for role in au.roles().iter().filter_map(Role::custom) {
+		// A role which has since been removed does not grant anything
+		if let Some(v) = lookup(tx, au.level(), role).await? {
+			privileges.extend(v);
+		}
+	}
+	Ok(Arc::new(au.with_privileges(privileges)))
+}
+
+/// Checks that custom roles are defined on the level, or on a level above it.
+pub(crate) async fn check(
+	tx: &mut Transaction,
+	level: &Level,
+	roles: &[Role],
+) -> Result<(), Error> {
+	for role in roles.iter().filter_map(Role::custom) {
+		if lookup(tx, level, role).await?.is_none() {
+			return Err(Error::IamError(IamError::InvalidRole(role.to_owned())));
+		}
+	}
+	Ok(())
+}
+
+/// Returns the roles without the custom roles which are not defined on the level, or on a level above it.
+pub(crate) async fn defined(
+	tx: &mut Transaction,
+	level: &Level,
+	roles: Vec<Role>,
+) -> Result<Vec<Role>, Error> {
+	let mut res = Vec::with_capacity(roles.len());
+	for role in roles {
+		if let Some(name) = role.custom() {
+			if lookup(tx, level, name).await?.is_none() {
+				trace!("Ignoring role `{name}`, which is not defined on {level}");
+				continue;
+			}
+		}
+		res.push(role);
+	}
+	Ok(res)
+}
+
+/// Looks up the privileges of a custom role, from the level up to the root level.
+#[cfg(feature = "sql2")]
+async fn lookup(
+	tx: &mut Transaction,
+	level: &Level,
+	role: &str,
+) -> Result<Option<Vec<Privilege>>, Error> {
+	let mut level = Some(level.to_owned());
+	while let Some(current) = level {
+		let res = match &current {
+			Level::Database(ns, db) => tx.get_db_role(ns, db, role).await,
+			Level::Namespace(ns) => tx.get_ns_role(ns, role).await,
+			Level::Root => tx.get_root_role(role).await,
+			_ => return Ok(None),
+		};
+		match res {
+			Ok(v) => return Ok(Some(v.privileges)),
+			Err(Error::RoleDbNotFound {
+				..
+			})
+			| Err(Error::RoleNsNotFound {
+				..
+			})
+			| Err(Error::RoleRootNotFound {
+				..
+			}) => level = current.parent(),
+			Err(e) => return Err(e),
+		}
+	}
+	Ok(None)
+}
+
+/// Custom roles can only be defined with the newer SQL syntax.
+#[cfg(not(feature = "sql2"))]
+async fn lookup(
+	_tx: &mut Transaction,
+	_level: &Level,
+	_role: &str,
+) -> Result<Option<Vec<Privilege>>, Error> {
+	Ok(None)
+}
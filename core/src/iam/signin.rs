@@ -3,8 +3,11 @@ use super::{Actor, Level};
 use crate::cnf::{INSECURE_FORWARD_SCOPE_ERRORS, SERVER_NAME};
 use crate::dbs::Session;
 use crate::err::Error;
+use crate::iam::refresh::{self, Tokens};
+use crate::iam::throttle::{self, Principal};
 use crate::iam::token::{Claims, HEADER};
 use crate::iam::Auth;
+use crate::kvs::audit::{AuditClass, Event, Target};
 use crate::kvs::{Datastore, LockType::*, TransactionType::*};
 use crate::sql::Object;
 use crate::sql::Value;
@@ -17,21 +20,24 @@ pub async fn signin(
 	session: &mut Session,
 	vars: Object,
 ) -> Result<Option<String>, Error> {
+	// Get the target for the audit log
+	let target = kvs.audit_log(AuditClass::Signin).map(|_| Target::from_vars(&vars));
 	// Parse the specified variables
 	let ns = vars.get("NS").or_else(|| vars.get("ns"));
 	let db = vars.get("DB").or_else(|| vars.get("db"));
 	let sc = vars.get("SC").or_else(|| vars.get("sc"));
 
 	// Check if the parameters exist
-	match (ns, db, sc) {
+	let res = match (ns, db, sc) {
 		// SCOPE signin
 		(Some(ns), Some(db), Some(sc)) => {
 			// Process the provided values
 			let ns = ns.to_raw_string();
 			let db = db.to_raw_string();
 			let sc = sc.to_raw_string();
-			// Attempt to signin to specified scope
-			super::signin::sc(kvs, session, ns, db, sc, vars).await
+			// Attempt to signin to specified scope, unless the user is throttled or locked out
+			let who = Principal::for_scope(kvs, &ns, &db, &sc, &vars).await?;
+			throttle::attempt(kvs, &who, super::signin::sc(kvs, session, ns, db, sc, vars)).await
 		}
 		// DB signin
 		(Some(ns), Some(db), None) => {
@@ -91,7 +97,30 @@ pub async fn signin(
 			}
 		}
 		_ => Err(Error::NoSigninTarget),
+	};
+	// Record the outcome in the audit log
+	if let (Some(log), Some(target)) = (kvs.audit_log(AuditClass::Signin), target) {
+		log.record(Event::for_attempt(AuditClass::Signin, session, target, &res), &res);
 	}
+	res
+}
+
+/// Signs in like [`signin`], and also issues a refresh token when the `refresh` variable is true
+pub async fn signin_with_refresh(
+	kvs: &Datastore,
+	session: &mut Session,
+	vars: Object,
+) -> Result<Tokens, Error> {
+	let issue = vars.get("refresh").is_some_and(Value::is_true);
+	let token = signin(kvs, session, vars).await?;
+	let refresh = match issue {
+		true => Some(refresh::issue(kvs, session).await?),
+		false => None,
+	};
+	Ok(Tokens {
+		token,
+		refresh,
+	})
 }
 
 pub async fn sc(
@@ -132,6 +161,7 @@ pub async fn sc(
 								let val = Claims {
 									iss: Some(SERVER_NAME.to_owned()),
 									iat: Some(Utc::now().timestamp()),
+									iat_ms: Some(Utc::now().timestamp_millis()),
 									nbf: Some(Utc::now().timestamp()),
 									exp: Some(
 										match sv.session {
@@ -211,6 +241,7 @@ pub async fn db(
 			let val = Claims {
 				iss: Some(SERVER_NAME.to_owned()),
 				iat: Some(Utc::now().timestamp()),
+				iat_ms: Some(Utc::now().timestamp_millis()),
 				nbf: Some(Utc::now().timestamp()),
 				exp: Some((Utc::now() + Duration::hours(1)).timestamp()),
 				ns: Some(ns.to_owned()),
@@ -234,6 +265,9 @@ pub async fn db(
 				_ => Err(Error::TokenMakingFailed),
 			}
 		}
+		// The user is throttled, locked out, or needs to change the password
+		Err(e @ (Error::SigninThrottled | Error::SigninLocked | Error::PasswordExpired)) => Err(e),
+		// The password did not verify
 		_ => Err(Error::InvalidAuth),
 	}
 }
@@ -262,6 +296,7 @@ pub async fn ns(
 			let val = Claims {
 				iss: Some(SERVER_NAME.to_owned()),
 				iat: Some(Utc::now().timestamp()),
+				iat_ms: Some(Utc::now().timestamp_millis()),
 				nbf: Some(Utc::now().timestamp()),
 				exp: Some((Utc::now() + Duration::hours(1)).timestamp()),
 				ns: Some(ns.to_owned()),
@@ -283,6 +318,8 @@ pub async fn ns(
 				_ => Err(Error::TokenMakingFailed),
 			}
 		}
+		// The user is throttled, locked out, or needs to change the password
+		Err(e @ (Error::SigninThrottled | Error::SigninLocked | Error::PasswordExpired)) => Err(e),
 		// The password did not verify
 		_ => Err(Error::InvalidAuth),
 	}
@@ -311,6 +348,7 @@ pub async fn root(
 			let val = Claims {
 				iss: Some(SERVER_NAME.to_owned()),
 				iat: Some(Utc::now().timestamp()),
+				iat_ms: Some(Utc::now().timestamp_millis()),
 				nbf: Some(Utc::now().timestamp()),
 				exp: Some((Utc::now() + Duration::hours(1)).timestamp()),
 				id: Some(user),
@@ -330,6 +368,8 @@ pub async fn root(
 				_ => Err(Error::TokenMakingFailed),
 			}
 		}
+		// The user is throttled, locked out, or needs to change the password
+		Err(e @ (Error::SigninThrottled | Error::SigninLocked | Error::PasswordExpired)) => Err(e),
 		// The password did not verify
 		_ => Err(Error::InvalidAuth),
 	}
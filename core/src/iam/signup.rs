@@ -4,6 +4,7 @@ use crate::err::Error;
 use crate::iam::token::{Claims, HEADER};
 use crate::iam::Auth;
 use crate::iam::{Actor, Level};
+use crate::kvs::audit::{AuditClass, Event, Target};
 use crate::kvs::{Datastore, LockType::*, TransactionType::*};
 use crate::sql::Object;
 use crate::sql::Value;
@@ -16,12 +17,14 @@ pub async fn signup(
 	session: &mut Session,
 	vars: Object,
 ) -> Result<Option<String>, Error> {
+	// Get the target for the audit log
+	let target = kvs.audit_log(AuditClass::Signup).map(|_| Target::from_vars(&vars));
 	// Parse the specified variables
 	let ns = vars.get("NS").or_else(|| vars.get("ns"));
 	let db = vars.get("DB").or_else(|| vars.get("db"));
 	let sc = vars.get("SC").or_else(|| vars.get("sc"));
 	// Check if the parameters exist
-	match (ns, db, sc) {
+	let res = match (ns, db, sc) {
 		(Some(ns), Some(db), Some(sc)) => {
 			// Process the provided values
 			let ns = ns.to_raw_string();
@@ -31,7 +34,12 @@ pub async fn signup(
 			super::signup::sc(kvs, session, ns, db, sc, vars).await
 		}
 		_ => Err(Error::InvalidSignup),
+	};
+	// Record the outcome in the audit log
+	if let (Some(log), Some(target)) = (kvs.audit_log(AuditClass::Signup), target) {
+		log.record(Event::for_attempt(AuditClass::Signup, session, target, &res), &res);
 	}
+	res
 }
 
 pub async fn sc(
@@ -72,6 +80,7 @@ pub async fn sc(
 								let val = Claims {
 									iss: Some(SERVER_NAME.to_owned()),
 									iat: Some(Utc::now().timestamp()),
+									iat_ms: Some(Utc::now().timestamp_millis()),
 									nbf: Some(Utc::now().timestamp()),
 									exp: Some(
 										match sv.session {
@@ -0,0 +1,326 @@
+//! Throttles signin attempts, and locks out users after repeated failures.
+//!
+//! The recent signin attempts of each user are stored in the key space, so
+//! that the limits apply across every node which shares the datastore. A user
+//! is identified by the level on which it is defined, and by its name. The
+//! users of a scope are identified by the signin variable which identifies
+//! the user, as the record of the user is only known once signin succeeds.
+//! The attempts of a user are removed once they no longer limit the user.
+use crate::err::Error;
+use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+use crate::sql::{Object, Param};
+use chrono::Utc;
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+
+/// The number of signin attempt entries which are checked for expiry at once
+const GC_BATCH_SIZE: u32 = 1000;
+
+/// The limits on the signin attempts of each user
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LoginPolicy {
+	/// The number of signin attempts which a user can make within the window, or 0 for no limit
+	pub max_attempts: u32,
+	/// The period of time over which signin attempts are counted
+	pub window: Duration,
+	/// The number of consecutive failed attempts after which a user is locked out, or 0 for never
+	pub max_failures: u32,
+	/// The period of time for which a user is locked out
+	pub lockout: Duration,
+}
+
+impl Default for LoginPolicy {
+	fn default() -> Self {
+		Self {
+			max_attempts: 0,
+			window: Duration::from_secs(60),
+			max_failures: 0,
+			lockout: Duration::from_secs(15 * 60),
+		}
+	}
+}
+
+impl LoginPolicy {
+	fn is_enabled(&self) -> bool {
+		self.max_attempts > 0 || self.max_failures > 0
+	}
+}
+
+/// The recent signin attempts of a user
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[revisioned(revision = 2)]
+pub(crate) struct Attempts {
+	/// The time at which the current window started
+	pub(crate) since: i64,
+	/// The number of attempts within the current window
+	pub(crate) count: u32,
+	/// The number of consecutive failed attempts
+	pub(crate) failures: u32,
+	/// The time until which the user is locked out
+	pub(crate) locked: i64,
+	/// The time of the latest failed attempt
+	#[revision(start = 2)]
+	pub(crate) failed: i64,
+}
+
+impl Attempts {
+	/// Consecutive failed attempts are forgotten after the lockout period
+	fn forget_failures(&mut self, policy: &LoginPolicy, now: i64) {
+		if now - self.failed >= policy.lockout.as_secs() as i64 {
+			self.failures = 0;
+		}
+	}
+
+	/// Checks if the attempts no longer limit the user
+	fn is_expired(&self, policy: &LoginPolicy, now: i64) -> bool {
+		self.locked <= now
+			&& now - self.since >= policy.window.as_secs() as i64
+			&& (self.failures == 0 || now - self.failed >= policy.lockout.as_secs() as i64)
+	}
+}
+
+/// The user who is attempting to signin
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Principal {
+	ns: String,
+	db: String,
+	sc: String,
+	id: String,
+}
+
+impl Principal {
+	/// Identifies a system user by its level and name
+	pub(crate) fn new(ns: Option<&str>, db: Option<&str>, id: &str) -> Self {
+		Self {
+			ns: ns.unwrap_or_default().to_owned(),
+			db: db.unwrap_or_default().to_owned(),
+			sc: String::new(),
+			id: id.to_owned(),
+		}
+	}
+
+	/// Identifies a scope user by the signin variable which identifies the user
+	pub(crate) async fn for_scope(
+		kvs: &Datastore,
+		ns: &str,
+		db: &str,
+		sc: &str,
+		vars: &Object,
+	) -> Result<Self, Error> {
+		let mut tx = kvs.transaction(Read, Optimistic).await?;
+		let sv = tx.get_sc(ns, db, sc).await;
+		tx.cancel().await?;
+		let signin = sv.ok().and_then(|sv| sv.signin.as_ref().map(ToString::to_string));
+		Ok(Self::for_signin(ns, db, sc, &signin.unwrap_or_default(), vars))
+	}
+
+	/// Identifies a scope user by the first variable, other than a password, which the
+	/// signin query refers to. Any other variables are ignored, so that they can not
+	/// be varied to avoid the limits on the attempts of the user.
+	fn for_signin(ns: &str, db: &str, sc: &str, signin: &str, vars: &Object) -> Self {
+		let id = vars
+			.iter()
+			.filter(|(k, _)| !k.to_ascii_lowercase().contains("pass"))
+			.filter_map(|(k, v)| position(signin, k).map(|i| (i, v)))
+			.min_by_key(|(i, _)| *i)
+			.map(|(_, v)| v.to_string())
+			.unwrap_or_default();
+		Self {
+			ns: ns.to_owned(),
+			db: db.to_owned(),
+			sc: sc.to_owned(),
+			id,
+		}
+	}
+
+	fn key(&self) -> crate::key::root::at::At<'_> {
+		crate::key::root::at::new(&self.ns, &self.db, &self.sc, &self.id)
+	}
+}
+
+/// Finds the first reference to a variable in a query
+fn position(query: &str, name: &str) -> Option<usize> {
+	let param = Param::from(name).to_string();
+	query
+		.match_indices(&param)
+		.map(|(i, _)| i)
+		.find(|i| !query[i + param.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+}
+
+/// Makes a signin attempt, unless the user has made too many attempts or is locked out,
+/// and records whether the attempt succeeded
+pub(crate) async fn attempt<T>(
+	kvs: &Datastore,
+	who: &Principal,
+	signin: impl Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+	// Check if signin attempts are limited
+	let policy = kvs.login_policy();
+	if !policy.is_enabled() {
+		return signin.await;
+	}
+	let now = Utc::now().timestamp();
+	let key = who.key();
+	// Count the attempt
+	let mut tx = kvs.transaction(Write, Optimistic).await?;
+	let mut at: Attempts = tx.get(key.clone()).await?.map(Into::into).unwrap_or_default();
+	if at.locked > now {
+		tx.cancel().await?;
+		trace!("The user is locked out after too many failed signin attempts");
+		return Err(Error::SigninLocked);
+	}
+	if now - at.since >= policy.window.as_secs() as i64 {
+		at.since = now;
+		at.count = 0;
+	}
+	at.forget_failures(policy, now);
+	at.count += 1;
+	if policy.max_attempts > 0 && at.count > policy.max_attempts {
+		tx.cancel().await?;
+		trace!("The user has made too many signin attempts");
+		return Err(Error::SigninThrottled);
+	}
+	tx.set(key.clone(), at).await?;
+	tx.commit().await?;
+	// Make the attempt
+	let res = signin.await;
+	// Record the outcome of the attempt
+	let mut tx = kvs.transaction(Write, Optimistic).await?;
+	let mut at: Attempts = tx.get(key.clone()).await?.map(Into::into).unwrap_or_default();
+	at.forget_failures(policy, now);
+	match res {
+		Ok(_) => at.failures = 0,
+		Err(_) => {
+			at.failures += 1;
+			at.failed = now;
+			if policy.max_failures > 0 && at.failures >= policy.max_failures {
+				debug!("Locking out a user after {} failed signin attempts", at.failures);
+				at.locked = now + policy.lockout.as_secs() as i64;
+				at.failures = 0;
+			}
+		}
+	}
+	tx.set(key, at).await?;
+	tx.commit().await?;
+	res
+}
+
+/// Removes the signin attempts which no longer limit their users
+pub(crate) async fn garbage_collect(kvs: &Datastore, now: i64) -> Result<(), Error> {
+	let policy = kvs.login_policy();
+	let mut beg = crate::key::root::at::prefix();
+	let end = crate::key::root::at::suffix();
+	loop {
+		let mut tx = kvs.transaction(Write, Optimistic).await?;
+		let res = match tx.getr(beg.clone()..end.clone(), GC_BATCH_SIZE).await {
+			Ok(v) => v,
+			Err(e) => {
+				tx.cancel().await?;
+				return Err(e);
+			}
+		};
+		for (k, v) in res.iter() {
+			if Attempts::from(v.clone()).is_expired(policy, now) {
+				tx.del(k.clone()).await?;
+			}
+		}
+		tx.commit().await?;
+		// Continue after the last entry of the batch
+		match res.last() {
+			Some((k, _)) if res.len() as u32 == GC_BATCH_SIZE => {
+				beg = k.clone();
+				beg.push(0x00);
+			}
+			_ => return Ok(()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	async fn fail(kvs: &Datastore, who: &Principal) -> Result<(), Error> {
+		attempt(kvs, who, async { Err::<(), _>(Error::InvalidPass) }).await
+	}
+
+	async fn succeed(kvs: &Datastore, who: &Principal) -> Result<(), Error> {
+		attempt(kvs, who, async { Ok(()) }).await
+	}
+
+	#[tokio::test]
+	async fn users_are_locked_out() {
+		let kvs = Datastore::new("memory").await.unwrap().with_login_policy(LoginPolicy {
+			max_failures: 3,
+			..Default::default()
+		});
+		let who = Principal::new(Some("test"), None, "user");
+		// A successful attempt resets the failures
+		assert!(matches!(fail(&kvs, &who).await, Err(Error::InvalidPass)));
+		assert!(matches!(fail(&kvs, &who).await, Err(Error::InvalidPass)));
+		assert!(succeed(&kvs, &who).await.is_ok());
+		// The user is locked out after consecutive failures
+		for _ in 0..3 {
+			assert!(matches!(fail(&kvs, &who).await, Err(Error::InvalidPass)));
+		}
+		assert!(matches!(succeed(&kvs, &who).await, Err(Error::SigninLocked)));
+		// Other users are not locked out
+		let other = Principal::new(Some("test"), Some("test"), "user");
+		assert!(succeed(&kvs, &other).await.is_ok());
+	}
+
+	#[tokio::test]
+	async fn attempts_are_throttled() {
+		let kvs = Datastore::new("memory").await.unwrap().with_login_policy(LoginPolicy {
+			max_attempts: 2,
+			..Default::default()
+		});
+		let who = Principal::new(None, None, "root");
+		assert!(succeed(&kvs, &who).await.is_ok());
+		assert!(matches!(fail(&kvs, &who).await, Err(Error::InvalidPass)));
+		assert!(matches!(succeed(&kvs, &who).await, Err(Error::SigninThrottled)));
+	}
+
+	#[tokio::test]
+	async fn failures_are_forgotten() {
+		let kvs = Datastore::new("memory").await.unwrap().with_login_policy(LoginPolicy {
+			max_failures: 3,
+			..Default::default()
+		});
+		let who = Principal::new(Some("test"), None, "user");
+		assert!(matches!(fail(&kvs, &who).await, Err(Error::InvalidPass)));
+		// The attempts are kept while they limit the user
+		let now = Utc::now().timestamp();
+		garbage_collect(&kvs, now).await.unwrap();
+		let mut tx = kvs.transaction(Read, Optimistic).await.unwrap();
+		assert!(tx.exi(who.key()).await.unwrap());
+		tx.cancel().await.unwrap();
+		// And removed once the failures are forgotten
+		garbage_collect(&kvs, now + 15 * 60).await.unwrap();
+		let mut tx = kvs.transaction(Read, Optimistic).await.unwrap();
+		assert!(!tx.exi(who.key()).await.unwrap());
+		tx.cancel().await.unwrap();
+	}
+
+	#[test]
+	fn scope_users_are_identified_by_signin_variable() {
+		let signin = "SELECT * FROM user WHERE email = $email \
+			AND crypto::argon2::compare(pass, $password)";
+		let vars = |pass: &str, other: &str| {
+			let mut vars = Object::default();
+			vars.insert("NS".into(), "test".into());
+			vars.insert("email".into(), "info@surrealdb.com".into());
+			vars.insert("emails".into(), other.into());
+			vars.insert("other".into(), other.into());
+			vars.insert("password".into(), pass.into());
+			vars
+		};
+		let one = Principal::for_signin("test", "test", "user", signin, &vars("one", "a"));
+		let two = Principal::for_signin("test", "test", "user", signin, &vars("two", "b"));
+		assert_eq!(one, two);
+		assert_eq!(one.id, "'info@surrealdb.com'");
+	}
+}
@@ -12,6 +12,9 @@ pub static HEADER: Lazy<Header> = Lazy::new(|| Header::new(Algorithm::HS512));
 pub struct Claims {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub iat: Option<i64>,
+	/// The time at which the token was issued, in milliseconds
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub iat_ms: Option<i64>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub nbf: Option<i64>,
 	#[serde(skip_serializing_if = "Option::is_none")]
@@ -78,6 +81,10 @@ impl From<Claims> for Value {
 		if let Some(iat) = v.iat {
 			out.insert("iat".to_string(), iat.into());
 		}
+		// Add iat_ms field if set
+		if let Some(iat_ms) = v.iat_ms {
+			out.insert("iat_ms".to_string(), iat_ms.into());
+		}
 		// Add nbf field if set
 		if let Some(nbf) = v.nbf {
 			out.insert("nbf".to_string(), nbf.into());
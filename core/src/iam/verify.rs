@@ -1,16 +1,20 @@
 use crate::dbs::Session;
 use crate::err::Error;
+use crate::iam::throttle::{self, Principal};
+#[cfg(feature = "sql2")]
+use crate::iam::{apikey, certificate::Certificate};
 #[cfg(feature = "jwks")]
-use crate::iam::jwks;
-use crate::iam::{token::Claims, Actor, Auth, Level, Role};
+use crate::iam::{jwks, oidc};
+use crate::iam::{refresh, roles, token::Claims, Actor, Auth, Level, Role};
+use crate::kvs::audit::{AuditClass, Event, Target};
 use crate::kvs::{Datastore, LockType::*, TransactionType::*};
-use crate::sql::{statements::DefineUserStatement, Algorithm, Value};
+use crate::sql::{statements::DefineUserStatement, Algorithm, Ident, Value};
 use crate::syn;
 use argon2::{Argon2, PasswordHash, PasswordVerifier};
 use chrono::Utc;
 use jsonwebtoken::{decode, DecodingKey, Header, Validation};
 use once_cell::sync::Lazy;
-use std::str::{self, FromStr};
+use std::str;
 use std::sync::Arc;
 
 async fn config(
@@ -97,6 +101,8 @@ fn config_alg(algo: Algorithm, code: String) -> Result<(DecodingKey, Validation)
 		)),
 		#[cfg(feature = "sql2")]
 		Algorithm::Jwks => Err(Error::InvalidAuth), // We should never get here
+		#[cfg(feature = "sql2")]
+		Algorithm::Oidc => Err(Error::InvalidAuth), // Verified without a token claim
 	}
 }
 
@@ -122,7 +128,7 @@ pub async fn basic(
 	trace!("Attempting basic authentication");
 
 	// Check if the parameters exist
-	match (ns, db) {
+	let res = match (ns, db) {
 		// DB signin
 		(Some(ns), Some(db)) => match verify_db_creds(kvs, ns, db, user, pass).await {
 			Ok(u) => {
@@ -151,7 +157,13 @@ pub async fn basic(
 			Err(err) => Err(err),
 		},
 		(None, Some(_)) => Err(Error::InvalidAuth),
+	};
+	// Record any failure in the audit log
+	if let (Err(_), Some(log)) = (&res, kvs.audit_log(AuditClass::Signin)) {
+		let target = Target::new(ns, db, None, Some(user));
+		log.record(Event::for_attempt(AuditClass::Signin, session, target, &res), &res);
 	}
+	res
 }
 
 // TODO(gguillemas): Remove this method once the legacy authentication is deprecated in v2.0.0
@@ -164,7 +176,9 @@ pub async fn basic_legacy(
 	// Log the authentication type
 	trace!("Attempting legacy basic authentication");
 
-	match verify_creds_legacy(kvs, session.ns.as_ref(), session.db.as_ref(), user, pass).await {
+	let res = match verify_creds_legacy(kvs, session.ns.as_ref(), session.db.as_ref(), user, pass)
+		.await
+	{
 		Ok((au, _)) if au.is_root() => {
 			debug!("Authenticated as root user '{}'", user);
 			session.au = Arc::new(au);
@@ -182,10 +196,59 @@ pub async fn basic_legacy(
 		}
 		Ok(_) => Err(Error::InvalidAuth),
 		Err(e) => Err(e),
+	};
+	// Record any failure in the audit log
+	if let (Err(_), Some(log)) = (&res, kvs.audit_log(AuditClass::Signin)) {
+		let target = Target::new(session.ns.as_deref(), session.db.as_deref(), None, Some(user));
+		log.record(Event::for_attempt(AuditClass::Signin, session, target, &res), &res);
 	}
+	res
 }
 
 pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Result<(), Error> {
+	// API keys are accepted wherever a token is
+	#[cfg(feature = "sql2")]
+	let res = match token.starts_with(apikey::PREFIX) {
+		true => apikey::verify(kvs, session, token).await,
+		false => verify_token(kvs, session, token).await,
+	};
+	#[cfg(not(feature = "sql2"))]
+	let res = verify_token(kvs, session, token).await;
+	// Record any failure in the audit log
+	if let (Err(_), Some(log)) = (&res, kvs.audit_log(AuditClass::Token)) {
+		// The claims of the token can not be trusted
+		let claims = decode::<Claims>(token, &KEY, &DUD).map(|v| v.claims).unwrap_or_default();
+		let target = Target::new(
+			claims.ns.as_deref(),
+			claims.db.as_deref(),
+			claims.sc.as_deref(),
+			claims.id.as_deref(),
+		);
+		log.record(Event::for_attempt(AuditClass::Token, session, target, &res), &res);
+	}
+	res
+}
+
+/// Authenticates the session with the verified client certificate of a connection
+#[cfg(feature = "sql2")]
+pub async fn certificate(
+	kvs: &Datastore,
+	session: &mut Session,
+	cert: &Certificate,
+	ns: Option<&str>,
+	db: Option<&str>,
+	sc: Option<&str>,
+) -> Result<(), Error> {
+	let res = super::certificate::verify(kvs, session, cert, ns, db, sc).await;
+	// Record any failure in the audit log
+	if let (Err(_), Some(log)) = (&res, kvs.audit_log(AuditClass::Signin)) {
+		let target = Target::new(ns, db, sc, Some(&cert.subject));
+		log.record(Event::for_attempt(AuditClass::Signin, session, target, &res), &res);
+	}
+	res
+}
+
+async fn verify_token(kvs: &Datastore, session: &mut Session, token: &str) -> Result<(), Error> {
 	// Log the authentication type
 	trace!("Attempting token authentication");
 	// Decode the token without verifying
@@ -206,6 +269,29 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			return Err(Error::InvalidAuth);
 		}
 	}
+	// Check if the sessions of the user have been revoked
+	let claims = &token_data.claims;
+	refresh::verify(
+		kvs,
+		claims.ns.as_deref(),
+		claims.db.as_deref(),
+		claims.sc.as_deref(),
+		claims.id.as_deref(),
+		// Tokens which only have an issue time in seconds are revoked within the whole second
+		claims.iat_ms.or(claims.iat.map(|v| v.saturating_mul(1000))),
+	)
+	.await?;
+	// Check if this is a token from an OpenID Connect provider
+	#[cfg(feature = "jwks")]
+	if let (None, Some(iss)) = (&claims.tk, &claims.iss) {
+		if iss != crate::cnf::SERVER_NAME {
+			let providers = oidc::providers(kvs, session, iss).await?;
+			if !providers.is_empty() {
+				return verify_oidc_token(kvs, session, token, token_data.header, value, providers)
+					.await;
+			}
+		}
+	}
 	// Check the token authentication claims
 	match token_data.claims {
 		// Check if this is scope token authentication
@@ -303,13 +389,10 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 				// If no role is provided, grant the viewer role
 				None => vec![Role::Viewer],
 				// If roles are provided, parse them
-				Some(roles) => roles
-					.iter()
-					.map(|r| -> Result<Role, Error> {
-						Role::from_str(r.as_str()).map_err(Error::IamError)
-					})
-					.collect::<Result<Vec<_>, _>>()?,
+				Some(roles) => roles.iter().map(|r| Role::from(&Ident::from(r.as_str()))).collect(),
 			};
+			// Check that the custom roles are defined
+			roles::check(&mut tx, &Level::Database(ns.to_owned(), db.to_owned()), &roles).await?;
 			// Log the success
 			debug!("Authenticated to database `{}` with token `{}`", db, tk);
 			// Set the session
@@ -376,13 +459,10 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 				// If no role is provided, grant the viewer role
 				None => vec![Role::Viewer],
 				// If roles are provided, parse them
-				Some(roles) => roles
-					.iter()
-					.map(|r| -> Result<Role, Error> {
-						Role::from_str(r.as_str()).map_err(Error::IamError)
-					})
-					.collect::<Result<Vec<_>, _>>()?,
+				Some(roles) => roles.iter().map(|r| Role::from(&Ident::from(r.as_str()))).collect(),
 			};
+			// Check that the custom roles are defined
+			roles::check(&mut tx, &Level::Namespace(ns.to_owned()), &roles).await?;
 			// Log the success
 			trace!("Authenticated to namespace `{}` with token `{}`", ns, tk);
 			// Set the session
@@ -455,11 +535,115 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 	}
 }
 
+#[cfg(feature = "jwks")]
+async fn verify_oidc_token(
+	kvs: &Datastore,
+	session: &mut Session,
+	token: &str,
+	header: Header,
+	value: Value,
+	providers: Vec<oidc::Provider>,
+) -> Result<(), Error> {
+	// The key identifier header must be present
+	let Some(kid) = header.kid else {
+		return Err(Error::MissingTokenHeader("kid".to_string()));
+	};
+	// Several definitions can share a provider, for different audiences or tenants
+	for provider in providers {
+		let name = &provider.de.name;
+		// Log the decoded authentication claims
+		trace!("Authenticating to {} with token `{}`", provider.level, name);
+		// Obtain the configuration with which to verify the token
+		let cf = match oidc::config(kvs, &kid, &provider.de).await {
+			Ok(v) => v,
+			Err(e) => {
+				warn!("Unable to obtain the configuration for token `{}`: {}", name, e);
+				continue;
+			}
+		};
+		// Verify the token
+		let claims = match decode::<serde_json::Value>(token, &cf.0, &cf.1) {
+			Ok(v) => v.claims,
+			Err(e) => {
+				trace!("The token is not valid for token `{}`: {}", name, e);
+				continue;
+			}
+		};
+		// Check that the token was issued for the level of the definition
+		if !provider.matches(&claims) {
+			trace!("The mapped claims of the token do not match token `{}`", name);
+			continue;
+		}
+		// Parse the roles
+		let roles = match provider.roles(&claims) {
+			// If no role is provided, grant the viewer role
+			None => vec![Role::Viewer],
+			// If roles are provided, parse them
+			Some(roles) => roles.iter().map(|r| Role::from(&Ident::from(r.as_str()))).collect(),
+		};
+		// Parse the record id
+		let id = match provider.id(&claims) {
+			Some(id) => syn::thing(id)?.into(),
+			None => Value::None,
+		};
+		// Keep only the roles which are defined, ignoring any other roles of the provider
+		let roles = match &provider.level {
+			Level::Scope(..) => roles,
+			level => {
+				let mut tx = kvs.transaction(Read, Optimistic).await?;
+				let res = roles::defined(&mut tx, level, roles).await;
+				tx.cancel().await?;
+				res?
+			}
+		};
+		// Set the session
+		session.tk = Some(value);
+		session.au = Arc::new(match provider.level {
+			Level::Scope(ns, db, sc) => {
+				session.sd = Some(id);
+				session.ns = Some(ns.to_owned());
+				session.db = Some(db.to_owned());
+				session.sc = Some(sc.to_owned());
+				Auth::new(Actor::new(
+					name.to_string(),
+					Default::default(),
+					Level::Scope(ns, db, sc),
+				))
+			}
+			Level::Database(ns, db) => {
+				let level = Level::Database(ns.to_owned(), db.to_owned());
+				session.ns = Some(ns);
+				session.db = Some(db);
+				Auth::new(Actor::new(name.to_string(), roles, level))
+			}
+			level => {
+				session.ns = level.ns().map(str::to_owned);
+				Auth::new(Actor::new(name.to_string(), roles, level))
+			}
+		});
+		// Log the success
+		debug!("Authenticated to {} with token `{}`", session.au.level(), name);
+		return Ok(());
+	}
+	// There was an auth error
+	Err(Error::InvalidAuth)
+}
+
 pub async fn verify_root_creds(
 	ds: &Datastore,
 	user: &str,
 	pass: &str,
 ) -> Result<DefineUserStatement, Error> {
+	// Verify the credentials, unless the user is throttled or locked out
+	let user = throttle::attempt(ds, &Principal::new(None, None, user), root_creds(ds, user, pass))
+		.await?;
+	// Check that the password of the user has not expired
+	check_expiry(ds, &user)?;
+	// Return the verified user object
+	Ok(user)
+}
+
+async fn root_creds(ds: &Datastore, user: &str, pass: &str) -> Result<DefineUserStatement, Error> {
 	// Create a new readonly transaction
 	let mut tx = ds.transaction(Read, Optimistic).await?;
 	// Fetch the specified user from storage
@@ -478,6 +662,22 @@ pub async fn verify_ns_creds(
 	ns: &str,
 	user: &str,
 	pass: &str,
+) -> Result<DefineUserStatement, Error> {
+	// Verify the credentials, unless the user is throttled or locked out
+	let user =
+		throttle::attempt(ds, &Principal::new(Some(ns), None, user), ns_creds(ds, ns, user, pass))
+			.await?;
+	// Check that the password of the user has not expired
+	check_expiry(ds, &user)?;
+	// Return the verified user object
+	Ok(user)
+}
+
+async fn ns_creds(
+	ds: &Datastore,
+	ns: &str,
+	user: &str,
+	pass: &str,
 ) -> Result<DefineUserStatement, Error> {
 	// Create a new readonly transaction
 	let mut tx = ds.transaction(Read, Optimistic).await?;
@@ -498,6 +698,26 @@ pub async fn verify_db_creds(
 	db: &str,
 	user: &str,
 	pass: &str,
+) -> Result<DefineUserStatement, Error> {
+	// Verify the credentials, unless the user is throttled or locked out
+	let user = throttle::attempt(
+		ds,
+		&Principal::new(Some(ns), Some(db), user),
+		db_creds(ds, ns, db, user, pass),
+	)
+	.await?;
+	// Check that the password of the user has not expired
+	check_expiry(ds, &user)?;
+	// Return the verified user object
+	Ok(user)
+}
+
+async fn db_creds(
+	ds: &Datastore,
+	ns: &str,
+	db: &str,
+	user: &str,
+	pass: &str,
 ) -> Result<DefineUserStatement, Error> {
 	// Create a new readonly transaction
 	let mut tx = ds.transaction(Read, Optimistic).await?;
@@ -512,6 +732,12 @@ pub async fn verify_db_creds(
 	Ok(user)
 }
 
+fn check_expiry(_ds: &Datastore, _user: &DefineUserStatement) -> Result<(), Error> {
+	#[cfg(feature = "sql2")]
+	_ds.password_policy().check_expiry(_user.changed.as_ref())?;
+	Ok(())
+}
+
 fn verify_pass(pass: &str, hash: &str) -> Result<(), Error> {
 	// Compute the hash and verify the password
 	let hash = PasswordHash::new(hash).unwrap();
@@ -534,34 +760,48 @@ pub async fn verify_creds_legacy(
 		return Err(Error::InvalidAuth);
 	}
 
+	// Verify the credentials, unless the user is throttled or locked out
+	let (au, user) = creds_legacy(ds, ns, db, user, pass).await.map_err(|e| match e {
+		Error::SigninThrottled | Error::SigninLocked => e,
+		_ => Error::InvalidAuth,
+	})?;
+	// Check that the password of the user has not expired
+	check_expiry(ds, &user)?;
+	Ok((au, user))
+}
+
+// Each level is throttled with the same key as when signing in to that level directly,
+// so that the limits of a root user can not be avoided by varying the namespace or database
+async fn creds_legacy(
+	ds: &Datastore,
+	ns: Option<&String>,
+	db: Option<&String>,
+	user: &str,
+	pass: &str,
+) -> Result<(Auth, DefineUserStatement), Error> {
 	// Try to authenticate as a ROOT user
-	match verify_root_creds(ds, user, pass).await {
-		Ok(u) => Ok(((&u, Level::Root).into(), u)),
-		Err(_) => {
-			// Try to authenticate as a NS user
-			match ns {
-				Some(ns) => {
-					match verify_ns_creds(ds, ns, user, pass).await {
-						Ok(u) => Ok(((&u, Level::Namespace(ns.to_owned())).into(), u)),
-						Err(_) => {
-							// Try to authenticate as a DB user
-							match db {
-								Some(db) => match verify_db_creds(ds, ns, db, user, pass).await {
-									Ok(u) => Ok((
-										(&u, Level::Database(ns.to_owned(), db.to_owned())).into(),
-										u,
-									)),
-									Err(_) => Err(Error::InvalidAuth),
-								},
-								None => Err(Error::InvalidAuth),
-							}
-						}
-					}
-				}
-				None => Err(Error::InvalidAuth),
-			}
-		}
+	let root = Principal::new(None, None, user);
+	let res = throttle::attempt(ds, &root, root_creds(ds, user, pass)).await;
+	let Some(ns) = ns else {
+		return res.map(|u| ((&u, Level::Root).into(), u));
+	};
+	if let Ok(u) = res {
+		return Ok(((&u, Level::Root).into(), u));
+	}
+	// Try to authenticate as a NS user
+	let who = Principal::new(Some(ns), None, user);
+	let res = throttle::attempt(ds, &who, ns_creds(ds, ns, user, pass)).await;
+	let Some(db) = db else {
+		return res.map(|u| ((&u, Level::Namespace(ns.to_owned())).into(), u));
+	};
+	if let Ok(u) = res {
+		return Ok(((&u, Level::Namespace(ns.to_owned())).into(), u));
 	}
+	// Try to authenticate as a DB user
+	let who = Principal::new(Some(ns), Some(db), user);
+	throttle::attempt(ds, &who, db_creds(ds, ns, db, user, pass))
+		.await
+		.map(|u| ((&u, Level::Database(ns.to_owned(), db.to_owned())).into(), u))
 }
 
 #[cfg(test)]
@@ -965,6 +1205,38 @@ mod tests {
 			assert!(sess.au.has_role(&Role::Owner), "Auth user expected to have Owner role");
 		}
 
+		//
+		// Test with custom roles defined
+		//
+		#[cfg(feature = "sql2")]
+		{
+			// Prepare the claims object
+			let mut claims = claims.clone();
+			claims.roles = Some(vec!["ops".to_string()]);
+			// Create the token
+			let enc = encode(&HEADER, &claims, &key).unwrap();
+			// Signin with the token before the role is defined
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+
+			// Signin with the token once the role is defined on a level above
+			let owner = Session::owner().with_ns("test");
+			ds.execute("DEFINE ROLE ops ON NAMESPACE GRANT VIEW ON ANY", &owner, None)
+				.await
+				.unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_ok(), "Failed to signin with token: {:?}", res);
+			assert!(
+				sess.au.has_role(&Role::Custom("ops".to_string())),
+				"Auth user expected to have ops role"
+			);
+			assert!(!sess.au.has_role(&Role::Viewer), "Auth user expected to not have Viewer role");
+		}
+
 		//
 		// Test with invalid signature
 		//
@@ -1365,6 +1637,7 @@ mod tests {
 		use base64_lib::{engine::general_purpose::STANDARD_NO_PAD, Engine};
 		use jsonwebtoken::jwk::{Jwk, JwkSet};
 		use rand::{distributions::Alphanumeric, Rng};
+		use std::str::FromStr;
 		use wiremock::matchers::{method, path};
 		use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -1494,6 +1767,214 @@ mod tests {
 		}
 	}
 
+	#[cfg(feature = "jwks")]
+	#[tokio::test]
+	async fn test_token_oidc() {
+		use crate::dbs::capabilities::{Capabilities, NetTarget, Targets};
+		use crate::sql::Thing;
+		use base64_lib::{engine::general_purpose::STANDARD_NO_PAD, Engine};
+		use jsonwebtoken::jwk::{Jwk, JwkSet};
+		use rand::{distributions::Alphanumeric, Rng};
+		use serde_json::json;
+		use std::str::FromStr;
+		use wiremock::matchers::{method, path};
+		use wiremock::{Mock, MockServer, ResponseTemplate};
+
+		// Use unique path to prevent accidental cache reuse
+		let realm: String =
+			rand::thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+
+		// Key identifier used in both JWT and JWK
+		let kid = "test_kid";
+		// Secret used to both sign and verify with HMAC
+		let secret = "jwt_secret";
+
+		// JWKS object with single JWK object providing the HS512 secret used to verify
+		let jwks = JwkSet {
+			keys: vec![Jwk {
+				common: jsonwebtoken::jwk::CommonParameters {
+					public_key_use: None,
+					key_operations: None,
+					algorithm: Some(jsonwebtoken::Algorithm::HS512),
+					key_id: Some(kid.to_string()),
+					x509_url: None,
+					x509_chain: None,
+					x509_sha1_fingerprint: None,
+					x509_sha256_fingerprint: None,
+				},
+				algorithm: jsonwebtoken::jwk::AlgorithmParameters::OctetKey(
+					jsonwebtoken::jwk::OctetKeyParameters {
+						key_type: jsonwebtoken::jwk::OctetKeyType::Octet,
+						value: STANDARD_NO_PAD.encode(&secret),
+					},
+				),
+			}],
+		};
+
+		// The mock issuer serves a discovery document which points to its JWKS object
+		let mock_server = MockServer::start().await;
+		let issuer = format!("{}/{realm}", mock_server.uri());
+		let discovery = json!({
+			"issuer": issuer,
+			"jwks_uri": format!("{issuer}/jwks.json"),
+		});
+		Mock::given(method("GET"))
+			.and(path(format!("/{realm}/.well-known/openid-configuration")))
+			.respond_with(ResponseTemplate::new(200).set_body_json(discovery))
+			.expect(1)
+			.mount(&mock_server)
+			.await;
+		Mock::given(method("GET"))
+			.and(path(format!("/{realm}/jwks.json")))
+			.respond_with(ResponseTemplate::new(200).set_body_json(jwks))
+			.expect(1)
+			.mount(&mock_server)
+			.await;
+
+		// We allow requests to the local server serving the discovery document
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(
+			Capabilities::default().with_network_targets(Targets::<NetTarget>::Some(
+				[NetTarget::from_str("127.0.0.1").unwrap()].into(),
+			)),
+		);
+
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = format!(
+			"
+			DEFINE TOKEN admins ON DATABASE TYPE OIDC ISSUER '{issuer}' AUDIENCE 'surrealdb'
+				CLAIMS {{ db: 'tenant', roles: 'realm_access.roles' }};
+			DEFINE SCOPE account;
+			DEFINE TOKEN users ON SCOPE account TYPE OIDC ISSUER '{issuer}' AUDIENCE 'app'
+				CLAIMS {{ id: 'https://surrealdb.com/record' }};
+			DEFINE ROLE reviewer ON DATABASE GRANT VIEW ON ANY;
+			"
+		);
+		for res in ds.execute(&sql, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+
+		// Use custom JWT header that includes the key identifier
+		let header_with_kid = jsonwebtoken::Header {
+			kid: Some(kid.to_string()),
+			alg: jsonwebtoken::Algorithm::HS512,
+			..jsonwebtoken::Header::default()
+		};
+		let key = EncodingKey::from_secret(secret.as_ref());
+		let claims = json!({
+			"iss": issuer,
+			"aud": ["surrealdb", "account"],
+			"sub": "f3a2c7e0",
+			"iat": Utc::now().timestamp(),
+			"exp": (Utc::now() + Duration::hours(1)).timestamp(),
+			"tenant": "test",
+			"realm_access": { "roles": ["Editor"] },
+		});
+
+		//
+		// Test with the claims mapped onto the database and roles
+		//
+		{
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			let mut sess = Session::default().with_ns("test").with_db("test");
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_ok(), "Failed to signin with token: {:?}", res);
+			assert_eq!(sess.ns, Some("test".to_string()));
+			assert_eq!(sess.db, Some("test".to_string()));
+			assert_eq!(sess.au.id(), "admins");
+			assert!(sess.au.is_db());
+			assert!(sess.au.has_role(&Role::Editor), "Auth user expected to have Editor role");
+			assert!(!sess.au.has_role(&Role::Owner), "Auth user expected to not have Owner role");
+			// The custom claims are available in the session
+			let tk = sess.tk.unwrap();
+			assert_eq!(tk.pick(&["tenant".into()]), Value::from("test"));
+		}
+
+		//
+		// Test with roles of the provider which are not defined
+		//
+		{
+			let mut claims = claims.clone();
+			claims["realm_access"]["roles"] = json!(["Editor", "reviewer", "offline_access"]);
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			let mut sess = Session::default().with_ns("test").with_db("test");
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_ok(), "Failed to signin with token: {:?}", res);
+			assert!(sess.au.has_role(&Role::Editor), "Auth user expected to have Editor role");
+			assert!(sess.au.has_role(&Role::Custom("reviewer".to_string())));
+			assert!(!sess.au.has_role(&Role::Custom("offline_access".to_string())));
+		}
+
+		//
+		// Test without a namespace and database selected on the session
+		//
+		{
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+		}
+
+		//
+		// Test with a tenant which does not match the database
+		//
+		{
+			let mut claims = claims.clone();
+			claims["tenant"] = json!("other");
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			let mut sess = Session::default().with_ns("test").with_db("test");
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+		}
+
+		//
+		// Test with the audience of another definition, and the record id mapped
+		//
+		{
+			let mut claims = claims.clone();
+			claims["aud"] = json!("app");
+			claims["https://surrealdb.com/record"] = json!("user:f3a2c7e0");
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			let mut sess = Session::default().with_ns("test").with_db("test");
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_ok(), "Failed to signin with token: {:?}", res);
+			assert_eq!(sess.sc, Some("account".to_string()));
+			assert_eq!(sess.au.id(), "users");
+			assert!(sess.au.is_scope());
+			assert_eq!(sess.sd, Some(Value::from(Thing::from(("user", "f3a2c7e0")))));
+		}
+
+		//
+		// Test with an audience which is not defined
+		//
+		{
+			let mut claims = claims.clone();
+			claims["aud"] = json!("other");
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			let mut sess = Session::default().with_ns("test").with_db("test");
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+		}
+
+		//
+		// Test with an issuer which is not defined
+		//
+		{
+			let mut claims = claims.clone();
+			claims["iss"] = json!("https://example.com");
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			let mut sess = Session::default().with_ns("test").with_db("test");
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+		}
+	}
+
 	#[test]
 	fn test_verify_pass() {
 		let salt = SaltString::generate(&mut rand::thread_rng());
@@ -0,0 +1,97 @@
+//! Tracks the state of the indexes defined with `DEFINE INDEX ... CONCURRENTLY`.
+//!
+//! Such an index is backfilled in batches by the datastore, each batch in its own
+//! transaction, while new writes keep the index up to date. The query planner
+//! does not use the index before the build is complete.
+use crate::err::Error;
+use crate::idx::VersionedSerdeState;
+use crate::kvs::Transaction;
+use crate::sql::statements::DefineIndexStatement;
+use crate::sql::{Id, Object, Value};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[revisioned(revision = 1)]
+pub(crate) enum BuildStatus {
+	#[default]
+	Building,
+	Ready,
+	Failed(String),
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[revisioned(revision = 1)]
+pub(crate) struct BuildState {
+	pub(crate) status: BuildStatus,
+	/// The number of records which have been indexed so far
+	pub(crate) processed: u64,
+	/// The id of the last record which has been indexed
+	pub(crate) cursor: Option<Id>,
+}
+
+impl VersionedSerdeState for BuildState {}
+
+impl BuildState {
+	pub(crate) async fn get(
+		tx: &mut Transaction,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		ix: &str,
+	) -> Result<Option<Self>, Error> {
+		let key = crate::key::index::ib::new(ns, db, tb, ix);
+		match tx.get(key).await? {
+			Some(val) => Ok(Some(Self::try_from_val(val)?)),
+			None => Ok(None),
+		}
+	}
+
+	pub(crate) async fn set(
+		&self,
+		tx: &mut Transaction,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		ix: &str,
+	) -> Result<(), Error> {
+		let key = crate::key::index::ib::new(ns, db, tb, ix);
+		tx.set(key, self.try_to_val()?).await
+	}
+
+	/// Describes the build, as returned by `INFO FOR INDEX`
+	pub(crate) fn to_value(&self) -> Value {
+		let mut res = Object::default();
+		let status = match &self.status {
+			BuildStatus::Building => "building",
+			BuildStatus::Ready => "ready",
+			BuildStatus::Failed(e) => {
+				res.insert("error".to_owned(), Value::from(e.as_str()));
+				"failed"
+			}
+		};
+		res.insert("status".to_owned(), Value::from(status));
+		res.insert("count".to_owned(), Value::from(self.processed));
+		Value::from(res)
+	}
+}
+
+/// Checks if the given index is ready to be used by the query planner.
+pub(crate) async fn is_ready(
+	tx: &mut Transaction,
+	ns: &str,
+	db: &str,
+	ix: &DefineIndexStatement,
+) -> Result<bool, Error> {
+	if !ix.concurrently {
+		return Ok(true);
+	}
+	let state = BuildState::get(tx, ns, db, &ix.what, &ix.name).await?;
+	Ok(matches!(
+		state,
+		Some(BuildState {
+			status: BuildStatus::Ready,
+			..
+		})
+	))
+}
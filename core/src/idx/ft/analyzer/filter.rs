@@ -1,9 +1,18 @@
+use crate::dbs::{Options, Transaction};
 use crate::err::Error;
 use crate::idx::ft::analyzer::tokenizer::Tokens;
 use crate::sql::filter::Filter as SqlFilter;
+#[cfg(feature = "sql2")]
+use crate::sql::filter::{Stopwords, Synonyms};
 use crate::sql::language::Language;
+#[cfg(feature = "sql2")]
+use crate::sql::{Object, Table, Value};
 use deunicode::deunicode;
 use rust_stemmers::{Algorithm, Stemmer};
+#[cfg(feature = "sql2")]
+use std::cmp::Reverse;
+#[cfg(feature = "sql2")]
+use std::collections::{HashMap, HashSet};
 
 pub(super) enum Filter {
 	Stemmer(Stemmer),
@@ -12,6 +21,16 @@ pub(super) enum Filter {
 	EdgeNgram(u16, u16),
 	Lowercase,
 	Uppercase,
+	#[cfg(feature = "sql2")]
+	Stopwords(HashSet<String>),
+	#[cfg(feature = "sql2")]
+	Synonyms(HashMap<String, Vec<String>>),
+	/// The synonyms are loaded from the table when the filter is applied
+	#[cfg(feature = "sql2")]
+	SynonymsTable(Table),
+	/// The mapping entries are sorted by decreasing length of the source
+	#[cfg(feature = "sql2")]
+	Mapping(Vec<(String, String)>),
 }
 
 impl From<SqlFilter> for Filter {
@@ -44,6 +63,28 @@ impl From<SqlFilter> for Filter {
 				Filter::Stemmer(a)
 			}
 			SqlFilter::Uppercase => Filter::Uppercase,
+			#[cfg(feature = "sql2")]
+			SqlFilter::Stopwords(sw) => {
+				let words = match sw {
+					Stopwords::Language(l) => {
+						l.stopwords().unwrap_or_default().iter().map(|w| w.to_lowercase()).collect()
+					}
+					Stopwords::Custom(l) => l.into_iter().map(|w| w.0.to_lowercase()).collect(),
+				};
+				Filter::Stopwords(words)
+			}
+			#[cfg(feature = "sql2")]
+			SqlFilter::Synonyms(Synonyms::Table(tb)) => Filter::SynonymsTable(tb),
+			#[cfg(feature = "sql2")]
+			SqlFilter::Synonyms(Synonyms::Map(map)) => {
+				let mut synonyms = HashMap::with_capacity(map.len());
+				for (term, val) in map.0 {
+					synonyms.insert(term.to_lowercase(), Self::synonyms_from_value(val));
+				}
+				Filter::Synonyms(synonyms)
+			}
+			#[cfg(feature = "sql2")]
+			SqlFilter::Mapping(map) => Filter::Mapping(Self::mapping_from_object(map)),
 		}
 	}
 }
@@ -61,15 +102,82 @@ impl Filter {
 		}
 	}
 
-	pub(super) fn apply_filters(mut t: Tokens, f: &Option<Vec<Filter>>) -> Result<Tokens, Error> {
+	#[allow(unused_variables)]
+	pub(super) async fn apply_filters(
+		mut t: Tokens,
+		f: &Option<Vec<Filter>>,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Tokens, Error> {
 		if let Some(f) = f {
 			for f in f {
+				#[cfg(feature = "sql2")]
+				if let Filter::SynonymsTable(tb) = f {
+					let f = Self::load_synonyms(opt, txn, tb, &t).await?;
+					t = t.filter(&f)?;
+					continue;
+				}
 				t = t.filter(f)?;
 			}
 		}
 		Ok(t)
 	}
 
+	/// Loads the synonyms of the current tokens from the records of the given table.
+	/// Only the records matching a token are read, with the token in lowercase as the ID.
+	#[cfg(feature = "sql2")]
+	async fn load_synonyms(
+		opt: &Options,
+		txn: &Transaction,
+		tb: &Table,
+		t: &Tokens,
+	) -> Result<Filter, Error> {
+		let mut terms = HashSet::new();
+		for tk in t.list() {
+			terms.insert(t.get_token_string(tk)?.to_lowercase());
+		}
+		let mut synonyms = HashMap::with_capacity(terms.len());
+		let mut run = txn.lock().await;
+		for term in terms {
+			let key = crate::key::thing::new(opt.ns(), opt.db(), tb, &term.as_str().into());
+			if let Some(val) = run.get(key).await? {
+				let val = Value::from(val).pick(&[crate::sql::Part::from("synonyms")]);
+				synonyms.insert(term, Self::synonyms_from_value(val));
+			}
+		}
+		Ok(Filter::Synonyms(synonyms))
+	}
+
+	#[cfg(feature = "sql2")]
+	fn synonyms_from_value(val: Value) -> Vec<String> {
+		match val {
+			Value::Strand(s) => vec![s.0],
+			Value::Array(a) => {
+				a.0.into_iter()
+					.filter_map(|v| match v {
+						Value::Strand(s) => Some(s.0),
+						_ => None,
+					})
+					.collect()
+			}
+			_ => vec![],
+		}
+	}
+
+	#[cfg(feature = "sql2")]
+	fn mapping_from_object(map: Object) -> Vec<(String, String)> {
+		let mut mapping: Vec<(String, String)> = map
+			.0
+			.into_iter()
+			.filter_map(|(k, v)| match v {
+				Value::Strand(s) if !k.is_empty() => Some((k, s.0)),
+				_ => None,
+			})
+			.collect();
+		mapping.sort_by_key(|(a, _)| Reverse(a.len()));
+		mapping
+	}
+
 	pub(super) fn apply_filter(&self, c: &str) -> FilterResult {
 		match self {
 			Filter::Ascii => Self::deunicode(c),
@@ -78,6 +186,14 @@ impl Filter {
 			Filter::Ngram(min, max) => Self::ngram(c, *min, *max),
 			Filter::Stemmer(s) => Self::stem(s, c),
 			Filter::Uppercase => Self::uppercase(c),
+			#[cfg(feature = "sql2")]
+			Filter::Stopwords(w) => Self::stopwords(w, c),
+			#[cfg(feature = "sql2")]
+			Filter::Synonyms(s) => Self::synonyms(s, c),
+			#[cfg(feature = "sql2")]
+			Filter::SynonymsTable(_) => FilterResult::Term(Term::Unchanged),
+			#[cfg(feature = "sql2")]
+			Filter::Mapping(m) => Self::mapping(m, c),
 		}
 	}
 
@@ -112,6 +228,56 @@ impl Filter {
 		Self::check_term(c, s.stem(&c.to_lowercase()).into())
 	}
 
+	#[cfg(feature = "sql2")]
+	#[inline]
+	fn stopwords(w: &HashSet<String>, c: &str) -> FilterResult {
+		if w.contains(&c.to_lowercase()) {
+			FilterResult::Ignore
+		} else {
+			FilterResult::Term(Term::Unchanged)
+		}
+	}
+
+	#[cfg(feature = "sql2")]
+	#[inline]
+	fn synonyms(s: &HashMap<String, Vec<String>>, c: &str) -> FilterResult {
+		if let Some(s) = s.get(&c.to_lowercase()) {
+			let mut terms = Vec::with_capacity(s.len() + 1);
+			terms.push(Term::Unchanged);
+			for s in s {
+				if s.ne(c) {
+					terms.push(Term::NewTerm(s.to_owned()));
+				}
+			}
+			FilterResult::Terms(terms)
+		} else {
+			FilterResult::Term(Term::Unchanged)
+		}
+	}
+
+	#[cfg(feature = "sql2")]
+	#[inline]
+	fn mapping(m: &[(String, String)], c: &str) -> FilterResult {
+		let mut s = String::with_capacity(c.len());
+		let mut rest = c;
+		'outer: while !rest.is_empty() {
+			// The entries are sorted by length, so the longest match wins
+			for (from, to) in m {
+				if let Some(r) = rest.strip_prefix(from.as_str()) {
+					s.push_str(to);
+					rest = r;
+					continue 'outer;
+				}
+			}
+			let mut chars = rest.chars();
+			if let Some(ch) = chars.next() {
+				s.push(ch);
+			}
+			rest = chars.as_str();
+		}
+		Self::check_term(c, s)
+	}
+
 	#[inline]
 	fn ngram(c: &str, min: u16, max: u16) -> FilterResult {
 		let min = min as usize;
@@ -716,4 +882,54 @@ mod tests {
 		)
 		.await;
 	}
+
+	#[cfg(feature = "sql2")]
+	#[tokio::test]
+	async fn test_stopwords_language() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,class FILTERS lowercase,stopwords(english);",
+			"The quick brown fox jumps over the lazy dog",
+			&["quick", "brown", "fox", "jumps", "lazy", "dog"],
+		)
+		.await;
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,class FILTERS stopwords(fr);",
+			"Le chien dort dans son panier",
+			&["chien", "dort", "panier"],
+		)
+		.await;
+	}
+
+	#[cfg(feature = "sql2")]
+	#[tokio::test]
+	async fn test_stopwords_custom() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS stopwords(['ticket', 'RE:']);",
+			"RE: Ticket printer is broken",
+			&["printer", "is", "broken"],
+		)
+		.await;
+	}
+
+	#[cfg(feature = "sql2")]
+	#[tokio::test]
+	async fn test_synonyms() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS lowercase,synonyms({ car: ['auto', 'automobile'], tv: 'television' });",
+			"My car and my TV",
+			&["my", "car", "auto", "automobile", "and", "my", "tv", "television"],
+		)
+		.await;
+	}
+
+	#[cfg(feature = "sql2")]
+	#[tokio::test]
+	async fn test_mapping() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS lowercase,mapping({ 'ß': 'ss', 'æ': 'ae', 'ae': 'ä', 'e': 'é' });",
+			"Straße Æsir maestro",
+			&["strassé", "aesir", "mästro"],
+		)
+		.await;
+	}
 }
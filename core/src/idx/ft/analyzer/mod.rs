@@ -214,7 +214,7 @@ impl Analyzer {
 		if let Some(t) = &self.tokenizers {
 			if !input.is_empty() {
 				let t = Tokenizer::tokenize(t, input);
-				return Filter::apply_filters(t, &self.filters);
+				return Filter::apply_filters(t, &self.filters, opt, txn).await;
 			}
 		}
 		Ok(Tokens::new(input))
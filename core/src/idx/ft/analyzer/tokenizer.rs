@@ -4,6 +4,8 @@ use crate::idx::ft::analyzer::filter::{Filter, FilterResult, Term};
 use crate::idx::ft::offsets::{Offset, Position};
 use crate::sql::tokenizer::Tokenizer as SqlTokenizer;
 use crate::sql::Value;
+#[cfg(feature = "sql2")]
+use unicode_segmentation::UnicodeSegmentation;
 
 pub(super) struct Tokens {
 	/// The input string
@@ -169,11 +171,16 @@ impl Token {
 
 pub(super) struct Tokenizer {
 	splitters: Vec<Splitter>,
+	/// True if the input is first segmented on Unicode word boundaries
+	#[cfg(feature = "sql2")]
+	words: bool,
 }
 
 impl Tokenizer {
 	pub(in crate::idx::ft) fn new(t: &[SqlTokenizer]) -> Self {
 		Self {
+			#[cfg(feature = "sql2")]
+			words: t.contains(&SqlTokenizer::Unicode),
 			splitters: t.iter().map(|t| t.into()).collect(),
 		}
 	}
@@ -194,15 +201,41 @@ impl Tokenizer {
 
 	pub(super) fn tokenize(t: &[SqlTokenizer], i: String) -> Tokens {
 		let mut w = Tokenizer::new(t);
-		let mut last_char_pos = 0;
-		let mut last_byte_pos = 0;
-		let mut current_char_pos = 0;
-		let mut current_byte_pos = 0;
 		let mut t = Vec::new();
+		#[cfg(feature = "sql2")]
+		if w.words {
+			// Each word segment is then split by the other tokenizers
+			let mut char_pos = 0;
+			for (byte_pos, segment) in i.split_word_bound_indices() {
+				let char_len = segment.chars().count() as Position;
+				if segment.chars().any(char::is_alphanumeric) {
+					w.split(segment, char_pos, byte_pos as Position, &mut t);
+				}
+				char_pos += char_len;
+			}
+			return Tokens {
+				i,
+				t,
+			};
+		}
+		w.split(&i, 0, 0, &mut t);
+		Tokens {
+			i,
+			t,
+		}
+	}
+
+	/// Splits the segment into tokens. The given char and byte
+	/// positions are the positions of the segment in the input.
+	fn split(&mut self, i: &str, char_pos: Position, byte_pos: Position, t: &mut Vec<Token>) {
+		let mut last_char_pos = char_pos;
+		let mut last_byte_pos = byte_pos;
+		let mut current_char_pos = char_pos;
+		let mut current_byte_pos = byte_pos;
 		for c in i.chars() {
 			let char_len = c.len_utf8() as Position;
 			let is_valid = Self::is_valid(c);
-			let should_split = w.should_split(c);
+			let should_split = self.should_split(c);
 			if should_split || !is_valid {
 				// The last pos may be more advanced due to the is_valid process
 				if last_char_pos < current_char_pos {
@@ -229,9 +262,9 @@ impl Tokenizer {
 				bytes: (last_byte_pos, current_byte_pos),
 			});
 		}
-		Tokens {
-			i,
-			t,
+		// The splitters must not carry a state from one segment to the next one
+		for s in &mut self.splitters {
+			s.state = 0;
 		}
 	}
 }
@@ -257,6 +290,9 @@ impl Splitter {
 			SqlTokenizer::Camel => self.camel_state(c),
 			SqlTokenizer::Class => self.class_state(c),
 			SqlTokenizer::Punct => self.punct_state(c),
+			// Word boundaries are handled before the splitters are applied
+			#[cfg(feature = "sql2")]
+			SqlTokenizer::Unicode => false,
 		}
 	}
 
@@ -384,4 +420,29 @@ static LANGUAGE: &str = "Rust";"#,
 		)
 		.await;
 	}
+
+	#[cfg(feature = "sql2")]
+	#[tokio::test]
+	async fn test_tokenize_unicode() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS unicode FILTERS lowercase",
+			"The quick (“brown”) fox can’t jump 32.3 feet, right? 東京は日本の首都です",
+			&[
+				"the", "quick", "brown", "fox", "can", "t", "jump", "32.3", "feet", "right", "東",
+				"京", "は", "日", "本", "の", "首", "都", "で", "す",
+			],
+		)
+		.await;
+	}
+
+	#[cfg(feature = "sql2")]
+	#[tokio::test]
+	async fn test_tokenize_unicode_camel() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS unicode,camel FILTERS lowercase",
+			"Hello MyRectangle, привет мир",
+			&["hello", "my", "rectangle", "привет", "мир"],
+		)
+		.await;
+	}
 }
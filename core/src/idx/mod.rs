@@ -1,3 +1,5 @@
+#[cfg(feature = "sql2")]
+pub(crate) mod builder;
 pub mod docids;
 pub(crate) mod ft;
 pub(crate) mod planner;
@@ -133,7 +133,7 @@ impl InnerQueryExecutor {
 						if let IndexOperator::Knn(a, k) = io.op() {
 							let mut tx = txn.lock().await;
 							let entry = if let Some(mt) = mt_map.get(&ix_ref) {
-								MtEntry::new(&mut tx, mt, a.clone(), *k).await?
+								MtEntry::new(&mut tx, opt, idx_def, mt, a, *k).await?
 							} else {
 								let ikb = IndexKeyBase::new(opt, idx_def);
 								let mt = MTreeIndex::new(
@@ -144,7 +144,7 @@ impl InnerQueryExecutor {
 									TransactionType::Read,
 								)
 								.await?;
-								let entry = MtEntry::new(&mut tx, &mt, a.clone(), *k).await?;
+								let entry = MtEntry::new(&mut tx, opt, idx_def, &mt, a, *k).await?;
 								mt_map.insert(ix_ref, mt);
 								entry
 							};
@@ -270,6 +270,9 @@ impl QueryExecutor {
 						match ix.index {
 							Index::Idx => Ok(Self::new_index_iterator(opt, ix, io.clone())),
 							Index::Uniq => Ok(Self::new_unique_index_iterator(opt, ix, io.clone())),
+							// Deferred unique indexes are stored as non unique indexes
+							#[cfg(feature = "sql2")]
+							Index::UniqDeferred => Ok(Self::new_index_iterator(opt, ix, io.clone())),
 							Index::Search {
 								..
 							} => self.new_search_index_iterator(it_ref, io.clone()).await,
@@ -323,6 +326,12 @@ impl QueryExecutor {
 						opt, ix, from, to,
 					)))
 				}
+				#[cfg(feature = "sql2")]
+				Index::UniqDeferred => {
+					return Some(ThingIterator::IndexRange(IndexRangeThingIterator::new(
+						opt, ix, from, to,
+					)))
+				}
 				_ => {}
 			}
 		}
@@ -537,13 +546,23 @@ pub(super) struct MtEntry {
 }
 
 impl MtEntry {
+	#[cfg_attr(not(feature = "sql2"), allow(unused_variables))]
 	async fn new(
 		tx: &mut kvs::Transaction,
+		opt: &Options,
+		ix: &DefineIndexStatement,
 		mt: &MTreeIndex,
-		a: Array,
+		a: &Array,
 		k: u32,
 	) -> Result<Self, Error> {
-		let res = mt.knn_search(tx, a, k as usize).await?;
+		let res = mt.knn_search(tx, a.clone(), k as usize).await?;
+		// Approximated vectors are re-ranked with the vectors stored in the records
+		#[cfg(feature = "sql2")]
+		let res = if mt.is_lossy() {
+			mt.rerank(tx, opt.ns(), opt.db(), &ix.cols[0], a, k as usize, res).await?
+		} else {
+			res
+		};
 		Ok(Self {
 			res,
 			doc_ids: mt.doc_ids(),
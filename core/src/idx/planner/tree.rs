@@ -1,9 +1,15 @@
 use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::err::Error;
+#[cfg(feature = "sql2")]
+use crate::idx::builder;
 use crate::idx::planner::executor::KnnExpressions;
 use crate::idx::planner::plan::{IndexOperator, IndexOption};
+#[cfg(feature = "sql2")]
+use crate::kvs::keyring::Field;
 use crate::sql::index::{Distance, Index};
+#[cfg(feature = "sql2")]
+use crate::sql::statements::DefineFieldStatement;
 use crate::sql::statements::DefineIndexStatement;
 use crate::sql::{
 	Array, Cond, Expression, Idiom, Number, Operator, Part, Subquery, Table, Value, With,
@@ -25,7 +31,7 @@ impl Tree {
 		cond: &'a Option<Cond>,
 		with: &'a Option<With>,
 	) -> Result<Option<(Node, IndexesMap, Vec<IndexRef>, KnnExpressions)>, Error> {
-		let mut b = TreeBuilder::new(ctx, opt, txn, table, with);
+		let mut b = TreeBuilder::new(ctx, opt, txn, table, cond, with);
 		if let Some(cond) = cond {
 			let node = b.eval_value(&cond.0).await?;
 			Ok(Some((node, b.index_map, b.with_indexes, b.knn_expressions)))
@@ -40,8 +46,12 @@ struct TreeBuilder<'a> {
 	opt: &'a Options,
 	txn: &'a Transaction,
 	table: &'a Table,
+	#[cfg_attr(not(feature = "sql2"), allow(dead_code))]
+	cond: &'a Option<Cond>,
 	with: &'a Option<With>,
 	indexes: Option<Arc<[DefineIndexStatement]>>,
+	#[cfg(feature = "sql2")]
+	fields: Option<Arc<[DefineFieldStatement]>>,
 	resolved_expressions: HashMap<Arc<Expression>, ResolvedExpression>,
 	resolved_idioms: HashMap<Arc<Idiom>, Arc<Idiom>>,
 	idioms_indexes: HashMap<Arc<Idiom>, Option<Arc<Vec<IndexRef>>>>,
@@ -56,6 +66,7 @@ impl<'a> TreeBuilder<'a> {
 		opt: &'a Options,
 		txn: &'a Transaction,
 		table: &'a Table,
+		cond: &'a Option<Cond>,
 		with: &'a Option<With>,
 	) -> Self {
 		let with_indexes = match with {
@@ -67,8 +78,11 @@ impl<'a> TreeBuilder<'a> {
 			opt,
 			txn,
 			table,
+			cond,
 			with,
 			indexes: None,
+			#[cfg(feature = "sql2")]
+			fields: None,
 			resolved_expressions: Default::default(),
 			resolved_idioms: Default::default(),
 			idioms_indexes: Default::default(),
@@ -79,18 +93,68 @@ impl<'a> TreeBuilder<'a> {
 	}
 	async fn lazy_cache_indexes(&mut self) -> Result<(), Error> {
 		if self.indexes.is_none() {
-			let indexes = self
-				.txn
-				.clone()
-				.lock()
-				.await
-				.all_tb_indexes(self.opt.ns(), self.opt.db(), &self.table.0)
-				.await?;
+			let mut run = self.txn.lock().await;
+			let indexes = run.all_tb_indexes(self.opt.ns(), self.opt.db(), &self.table.0).await?;
+			// Ignore the indexes which are still being built,
+			// and the partial indexes whose condition is not part of the query condition
+			#[cfg(feature = "sql2")]
+			let indexes = if indexes.iter().any(|ix| ix.concurrently || ix.cond.is_some()) {
+				let conds = self.cond.as_ref().map(|c| conjunctions(&c.0)).unwrap_or_default();
+				let mut usable = Vec::with_capacity(indexes.len());
+				for ix in indexes.iter() {
+					if let Some(c) = &ix.cond {
+						if !conjunctions(&c.0).iter().all(|v| conds.contains(v)) {
+							continue;
+						}
+					}
+					if builder::is_ready(&mut run, self.opt.ns(), self.opt.db(), ix).await? {
+						usable.push(ix.clone());
+					}
+				}
+				usable.into()
+			} else {
+				indexes
+			};
 			self.indexes = Some(indexes);
+			// The encrypted fields can only be resolved by an index
+			#[cfg(feature = "sql2")]
+			{
+				self.fields =
+					Some(run.all_tb_fields(self.opt.ns(), self.opt.db(), &self.table.0).await?);
+			}
 		}
 		Ok(())
 	}
 
+	/// Find the definition of an encrypted field
+	#[cfg(feature = "sql2")]
+	fn encrypted_field(&self, i: &Idiom) -> Option<&DefineFieldStatement> {
+		self.fields.as_ref()?.iter().find(|fd| fd.encrypted.is_some() && fd.name.eq(i))
+	}
+
+	/// The values of an encrypted field can only be found with a deterministic index,
+	/// by encrypting the value which the field is compared with. Returns `None` if the
+	/// index can not be used, while older versions of the key remain in the keyring.
+	#[cfg(feature = "sql2")]
+	fn encrypt_operand(&self, id: &Idiom, op: &Operator, n: &Node) -> Result<Option<Node>, Error> {
+		let Some(fd) = self.encrypted_field(id) else {
+			return Ok(Some(n.clone()));
+		};
+		match (op, n.is_computed(), &fd.encrypted) {
+			(Operator::Equal | Operator::Exact, Some(v), Some(key)) => {
+				let field = Field::new(self.opt.ns(), self.opt.db(), fd, None);
+				Ok(self
+					.ctx
+					.get_keyring()
+					.encrypt_lookup(key, &field, v)?
+					.map(|v| Node::Computed(Arc::new(v))))
+			}
+			_ => Err(Error::EncryptedFieldCondition {
+				value: id.to_string(),
+			}),
+		}
+	}
+
 	#[cfg_attr(not(target_arch = "wasm32"), async_recursion)]
 	#[cfg_attr(target_arch = "wasm32", async_recursion(?Send))]
 	async fn eval_value(&mut self, v: &Value) -> Result<Node, Error> {
@@ -106,6 +170,8 @@ impl<'a> TreeBuilder<'a> {
 				let v = p.compute(self.ctx, self.opt, self.txn, None).await?;
 				self.eval_value(&v).await
 			}
+			#[cfg(feature = "sql2")]
+			Value::Function(_) => self.eval_function(v).await,
 			_ => Ok(Node::Unsupported(format!("Unsupported value: {}", v))),
 		}
 	}
@@ -146,9 +212,28 @@ impl<'a> TreeBuilder<'a> {
 			return Ok(Node::IndexedField(i.clone(), irs));
 		}
 
+		// Encrypted fields need to be resolved by a deterministic index
+		#[cfg(feature = "sql2")]
+		if self.encrypted_field(&i).is_some() {
+			return Err(Error::EncryptedFieldCondition {
+				value: i.to_string(),
+			});
+		}
+
 		Ok(Node::NonIndexedField(i))
 	}
 
+	/// Expression indexes are defined on an idiom starting with the indexed expression
+	#[cfg(feature = "sql2")]
+	async fn eval_function(&mut self, v: &Value) -> Result<Node, Error> {
+		self.lazy_cache_indexes().await?;
+		let i = Arc::new(Idiom(vec![Part::Start(v.to_owned())]));
+		if let Some(irs) = self.resolve_indexes(&i) {
+			return Ok(Node::IndexedField(i, irs));
+		}
+		Ok(Node::Unsupported(format!("Unsupported value: {}", v)))
+	}
+
 	fn resolve_indexes(&mut self, i: &Arc<Idiom>) -> Option<Arc<Vec<IndexRef>>> {
 		let mut res = None;
 		if let Some(indexes) = &self.indexes {
@@ -235,11 +320,19 @@ impl<'a> TreeBuilder<'a> {
 		e: &Arc<Expression>,
 		p: IdiomPosition,
 	) -> Result<Option<IndexOption>, Error> {
+		// Encrypt the value which an encrypted field is compared with
+		#[cfg(feature = "sql2")]
+		let Some(n) = &self.encrypt_operand(&id, op, n)?
+		else {
+			return Ok(None);
+		};
 		for ir in irs {
 			if let Some(ix) = self.index_map.definitions.get(*ir as usize) {
 				let op = match &ix.index {
 					Index::Idx => Self::eval_index_operator(op, n, p),
 					Index::Uniq => Self::eval_index_operator(op, n, p),
+					#[cfg(feature = "sql2")]
+					Index::UniqDeferred => Self::eval_index_operator(op, n, p),
 					Index::Search {
 						..
 					} => Self::eval_matches_operator(op, n),
@@ -282,6 +375,8 @@ impl<'a> TreeBuilder<'a> {
 						None | Some(Distance::Euclidean) | Some(Distance::Manhattan) => {
 							return Ok(Some(IndexOperator::Knn(a.clone(), *k)))
 						}
+						#[cfg(feature = "sql2")]
+						Some(Distance::Hamming) => return Ok(Some(IndexOperator::Knn(a.clone(), *k))),
 						_ => {}
 					}
 				}
@@ -339,6 +434,30 @@ impl<'a> TreeBuilder<'a> {
 	}
 }
 
+/// Splits a condition into the expressions joined by AND
+#[cfg(feature = "sql2")]
+fn conjunctions(v: &Value) -> Vec<&Value> {
+	match v {
+		Value::Expression(e) => match e.as_ref() {
+			Expression::Binary {
+				l,
+				o: Operator::And,
+				r,
+			} => {
+				let mut c = conjunctions(l);
+				c.extend(conjunctions(r));
+				c
+			}
+			_ => vec![v],
+		},
+		Value::Subquery(s) => match s.as_ref() {
+			Subquery::Value(v) => conjunctions(v),
+			_ => vec![v],
+		},
+		_ => vec![v],
+	}
+}
+
 pub(super) type IndexRef = u16;
 /// For each expression a possible index option
 #[derive(Default)]
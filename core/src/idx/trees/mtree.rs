@@ -23,12 +23,23 @@ use crate::idx::trees::store::{
 use crate::idx::trees::vector::{SharedVector, Vector};
 use crate::idx::{IndexKeyBase, VersionedSerdeState};
 use crate::kvs::{Key, Transaction, TransactionType, Val};
+#[cfg(feature = "sql2")]
+use crate::sql::index::Quantization;
 use crate::sql::index::{Distance, MTreeParams, VectorType};
 use crate::sql::{Array, Object, Thing, Value};
+#[cfg(feature = "sql2")]
+use crate::sql::{Idiom, Number};
+/// The number of candidates collected for each requested neighbour,
+/// when the indexed vectors are only an approximation of the stored ones.
+#[cfg(feature = "sql2")]
+const RERANK_OVERSAMPLING: usize = 4;
+
 pub(crate) struct MTreeIndex {
 	state_key: Key,
 	dim: usize,
 	vector_type: VectorType,
+	#[cfg(feature = "sql2")]
+	quantization: Option<Quantization>,
 	store: MTreeStore,
 	doc_ids: Arc<RwLock<DocIds>>,
 	mtree: Arc<RwLock<MTree>>,
@@ -64,6 +75,8 @@ impl MTreeIndex {
 			state_key,
 			dim: p.dimension as usize,
 			vector_type: p.vector_type,
+			#[cfg(feature = "sql2")]
+			quantization: p.quantization,
 			doc_ids,
 			mtree,
 			store,
@@ -96,11 +109,74 @@ impl MTreeIndex {
 	) -> Result<VecDeque<DocId>, Error> {
 		// Extract the vector
 		let vector = self.check_vector_array(a)?;
+		// Collect more candidates if they have to be re-ranked
+		#[cfg(feature = "sql2")]
+		let k = if self.is_lossy() {
+			k * RERANK_OVERSAMPLING
+		} else {
+			k
+		};
 		// Lock the store
 		let res = self.mtree.read().await.knn_search(tx, &self.store, &vector, k).await?;
 		Ok(res.docs)
 	}
 
+	/// Checks if the indexed vectors are only an approximation of the vectors stored in the records
+	#[cfg(feature = "sql2")]
+	pub(crate) fn is_lossy(&self) -> bool {
+		match self.vector_type {
+			VectorType::Binary => false,
+			VectorType::F16 => true,
+			_ => self.quantization.is_some(),
+		}
+	}
+
+	/// Sorts the candidates with the exact distance to the vector stored in each record,
+	/// and keeps the k nearest ones.
+	#[cfg(feature = "sql2")]
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) async fn rerank(
+		&self,
+		tx: &mut Transaction,
+		ns: &str,
+		db: &str,
+		col: &Idiom,
+		a: &Array,
+		k: usize,
+		docs: VecDeque<DocId>,
+	) -> Result<VecDeque<DocId>, Error> {
+		let obj = Self::to_numbers(&a.0)?;
+		let distance = self.mtree.read().await.distance.clone();
+		let doc_ids = self.doc_ids.read().await;
+		let mut ranked = Vec::with_capacity(docs.len());
+		for doc_id in docs {
+			if let Some(key) = doc_ids.get_doc_key(tx, doc_id).await? {
+				let rid: Thing = key.into();
+				let key = crate::key::thing::new(ns, db, &rid.tb, &rid.id);
+				if let Some(val) = tx.get(key).await? {
+					let val: Value = val.into();
+					if let Value::Array(v) = val.pick(col) {
+						let v = Self::to_numbers(&v.0)?;
+						ranked.push((distance.compute(&v, &obj)?, doc_id));
+					}
+				}
+			}
+		}
+		ranked.sort_by(|(a, _), (b, _)| a.cmp(b));
+		Ok(ranked.into_iter().take(k).map(|(_, doc_id)| doc_id).collect())
+	}
+
+	#[cfg(feature = "sql2")]
+	fn to_numbers(values: &[Value]) -> Result<Vec<Number>, Error> {
+		values
+			.iter()
+			.map(|v| match v {
+				Value::Number(n) => Ok(n.clone()),
+				_ => Err(Error::InvalidVectorValue(v.clone().to_raw_string())),
+			})
+			.collect()
+	}
+
 	fn check_vector_array(&self, a: Array) -> Result<SharedVector, Error> {
 		if a.0.len() != self.dim {
 			return Err(Error::InvalidVectorDimension {
@@ -108,7 +184,7 @@ impl MTreeIndex {
 				expected: self.dim,
 			});
 		}
-		let mut vec = Vector::new(self.vector_type, a.len());
+		let mut vec = self.new_vector();
 		for v in a.0 {
 			if let Value::Number(n) = v {
 				vec.add(n);
@@ -122,8 +198,16 @@ impl MTreeIndex {
 		Ok(vec.into())
 	}
 
+	fn new_vector(&self) -> Vector {
+		#[cfg(feature = "sql2")]
+		if self.quantization.is_some() && self.vector_type != VectorType::Binary {
+			return Vector::U8(Vec::with_capacity(self.dim));
+		}
+		Vector::new(self.vector_type, self.dim)
+	}
+
 	fn extract_vector(&self, v: Value) -> Result<Vector, Error> {
-		let mut vec = Vector::new(self.vector_type, self.dim);
+		let mut vec = self.new_vector();
 		Self::check_vector_value(v, &mut vec)?;
 		if vec.len() != self.dim {
 			return Err(Error::InvalidVectorDimension {
@@ -819,6 +903,8 @@ impl MTree {
 			Distance::Euclidean => v1.euclidean_distance(v2)?,
 			Distance::Manhattan => v1.manhattan_distance(v2)?,
 			Distance::Minkowski(order) => v1.minkowski_distance(v2, order)?,
+			#[cfg(feature = "sql2")]
+			Distance::Hamming => v1.hamming_distance(v2)?,
 			_ => return Err(Error::UnsupportedDistance(self.distance.clone())),
 		};
 		if dist.is_finite() {
@@ -249,6 +249,15 @@ impl IndexStores {
 		.await
 	}
 
+	/// Clears the caches of an index which is defined again, as its data is rebuilt
+	pub(crate) async fn index_defined(
+		&self,
+		opt: &Options,
+		ix: &DefineIndexStatement,
+	) -> Result<(), Error> {
+		self.remove_index(opt, ix).await
+	}
+
 	pub(crate) async fn namespace_removed(
 		&self,
 		opt: &Options,
@@ -1,6 +1,7 @@
 use crate::err::Error;
 use crate::sql::index::VectorType;
 use crate::sql::Number;
+use half::f16;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
@@ -8,13 +9,22 @@ use std::sync::Arc;
 
 /// In the context of a Symmetric MTree index, the term object refers to a vector, representing the indexed item.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub enum Vector {
 	F64(Vec<f64>),
 	F32(Vec<f32>),
 	I64(Vec<i64>),
 	I32(Vec<i32>),
 	I16(Vec<i16>),
+	/// Half precision floats, stored as their IEEE 754 bit representation
+	#[revision(start = 2)]
+	F16(Vec<u16>),
+	/// Bit-packed components, along with the number of components
+	#[revision(start = 2)]
+	Binary(Vec<u8>, u16),
+	/// Scalar quantized components, mapped from the [-1, 1] range to a byte
+	#[revision(start = 2)]
+	U8(Vec<u8>),
 }
 
 /// For vectors, as we want to support very large vectors, we want to avoid copy or clone.
@@ -32,6 +42,9 @@ impl PartialEq for Vector {
 			(I64(v), I64(v_o)) => v == v_o,
 			(I32(v), I32(v_o)) => v == v_o,
 			(I16(v), I16(v_o)) => v == v_o,
+			(F16(v), F16(v_o)) => v == v_o,
+			(Binary(v, l), Binary(v_o, l_o)) => l == l_o && v == v_o,
+			(U8(v), U8(v_o)) => v == v_o,
 			_ => false,
 		}
 	}
@@ -54,6 +67,9 @@ impl Ord for Vector {
 			(I64(v), I64(v_o)) => v.cmp(v_o),
 			(I32(v), I32(v_o)) => v.cmp(v_o),
 			(I16(v), I16(v_o)) => v.cmp(v_o),
+			(F16(v), F16(v_o)) => v.cmp(v_o),
+			(Binary(v, l), Binary(v_o, l_o)) => l.cmp(l_o).then_with(|| v.cmp(v_o)),
+			(U8(v), U8(v_o)) => v.cmp(v_o),
 			(F64(_), _) => Ordering::Less,
 			(_, F64(_)) => Ordering::Greater,
 			(F32(_), _) => Ordering::Less,
@@ -62,6 +78,12 @@ impl Ord for Vector {
 			(_, I64(_)) => Ordering::Greater,
 			(I32(_), _) => Ordering::Less,
 			(_, I32(_)) => Ordering::Greater,
+			(I16(_), _) => Ordering::Less,
+			(_, I16(_)) => Ordering::Greater,
+			(F16(_), _) => Ordering::Less,
+			(_, F16(_)) => Ordering::Greater,
+			(Binary(..), _) => Ordering::Less,
+			(_, Binary(..)) => Ordering::Greater,
 		}
 	}
 }
@@ -74,6 +96,10 @@ impl Vector {
 			VectorType::I64 => Self::I64(Vec::with_capacity(l)),
 			VectorType::I32 => Self::I32(Vec::with_capacity(l)),
 			VectorType::I16 => Self::I16(Vec::with_capacity(l)),
+			#[cfg(feature = "sql2")]
+			VectorType::F16 => Self::F16(Vec::with_capacity(l)),
+			#[cfg(feature = "sql2")]
+			VectorType::Binary => Self::Binary(Vec::with_capacity((l + 7) / 8), 0),
 		}
 	}
 
@@ -84,6 +110,18 @@ impl Vector {
 			Vector::I64(v) => v.push(n.to_int()),
 			Vector::I32(v) => v.push(n.to_int() as i32),
 			Vector::I16(v) => v.push(n.to_int() as i16),
+			Vector::F16(v) => v.push(f16::from_f64(n.to_float()).to_bits()),
+			Vector::Binary(v, l) => {
+				let i = *l as usize;
+				if i % 8 == 0 {
+					v.push(0);
+				}
+				if n.to_float() > 0.0 {
+					v[i / 8] |= 1 << (i % 8);
+				}
+				*l += 1;
+			}
+			Vector::U8(v) => v.push(((n.to_float().clamp(-1.0, 1.0) + 1.0) * 127.5).round() as u8),
 		};
 	}
 
@@ -94,6 +132,9 @@ impl Vector {
 			Vector::I64(v) => v.len(),
 			Vector::I32(v) => v.len(),
 			Vector::I16(v) => v.len(),
+			Vector::F16(v) => v.len(),
+			Vector::Binary(_, l) => *l as usize,
+			Vector::U8(v) => v.len(),
 		}
 	}
 
@@ -128,6 +169,19 @@ impl Vector {
 			(Vector::I16(a), Vector::I16(b)) => {
 				Ok((a.iter().zip(b.iter()).map(|(a, b)| (a - b).pow(2)).sum::<i16>() as f64).sqrt())
 			}
+			(Vector::F16(a), Vector::F16(b)) => Ok(a
+				.iter()
+				.zip(b.iter())
+				.map(|(a, b)| (f16_to_f64(*a) - f16_to_f64(*b)).powi(2))
+				.sum::<f64>()
+				.sqrt()),
+			(Vector::Binary(a, _), Vector::Binary(b, _)) => Ok((hamming(a, b) as f64).sqrt()),
+			(Vector::U8(a), Vector::U8(b)) => Ok(a
+				.iter()
+				.zip(b.iter())
+				.map(|(a, b)| (*a as f64 - *b as f64).powi(2))
+				.sum::<f64>()
+				.sqrt()),
 			_ => Err(Error::Unreachable("Vector::euclidean_distance")),
 		}
 	}
@@ -150,6 +204,15 @@ impl Vector {
 			(Vector::I16(a), Vector::I16(b)) => {
 				Ok(a.iter().zip(b.iter()).map(|(a, b)| (a - b).abs()).sum::<i16>() as f64)
 			}
+			(Vector::F16(a), Vector::F16(b)) => Ok(a
+				.iter()
+				.zip(b.iter())
+				.map(|(a, b)| (f16_to_f64(*a) - f16_to_f64(*b)).abs())
+				.sum::<f64>()),
+			(Vector::Binary(a, _), Vector::Binary(b, _)) => Ok(hamming(a, b) as f64),
+			(Vector::U8(a), Vector::U8(b)) => {
+				Ok(a.iter().zip(b.iter()).map(|(a, b)| (*a as f64 - *b as f64).abs()).sum::<f64>())
+			}
 			_ => Err(Error::Unreachable("Vector::manhattan_distance")),
 		}
 	}
@@ -181,8 +244,59 @@ impl Vector {
 				.zip(b.iter())
 				.map(|(a, b)| (a - b).abs().pow(order.to_int() as u32))
 				.sum::<i16>() as f64,
+			(Vector::F16(a), Vector::F16(b)) => a
+				.iter()
+				.zip(b.iter())
+				.map(|(a, b)| (f16_to_f64(*a) - f16_to_f64(*b)).abs().powf(order.to_float()))
+				.sum::<f64>(),
+			(Vector::Binary(a, _), Vector::Binary(b, _)) => hamming(a, b) as f64,
+			(Vector::U8(a), Vector::U8(b)) => a
+				.iter()
+				.zip(b.iter())
+				.map(|(a, b)| (*a as f64 - *b as f64).abs().powf(order.to_float()))
+				.sum::<f64>(),
 			_ => return Err(Error::Unreachable("Vector::minkowski_distance")),
 		};
 		Ok(dist.powf(1.0 / order.to_float()))
 	}
+
+	#[cfg(feature = "sql2")]
+	pub(super) fn hamming_distance(&self, other: &Self) -> Result<f64, Error> {
+		Self::check_same_dimension("vector::distance::hamming", self, other)?;
+		let dist = match (self, other) {
+			(Vector::F64(a), Vector::F64(b)) => {
+				a.iter().zip(b.iter()).filter(|(a, b)| a != b).count()
+			}
+			(Vector::F32(a), Vector::F32(b)) => {
+				a.iter().zip(b.iter()).filter(|(a, b)| a != b).count()
+			}
+			(Vector::I64(a), Vector::I64(b)) => {
+				a.iter().zip(b.iter()).filter(|(a, b)| a != b).count()
+			}
+			(Vector::I32(a), Vector::I32(b)) => {
+				a.iter().zip(b.iter()).filter(|(a, b)| a != b).count()
+			}
+			(Vector::I16(a), Vector::I16(b)) => {
+				a.iter().zip(b.iter()).filter(|(a, b)| a != b).count()
+			}
+			(Vector::F16(a), Vector::F16(b)) => {
+				a.iter().zip(b.iter()).filter(|(a, b)| f16_to_f64(**a) != f16_to_f64(**b)).count()
+			}
+			(Vector::Binary(a, _), Vector::Binary(b, _)) => hamming(a, b) as usize,
+			(Vector::U8(a), Vector::U8(b)) => {
+				a.iter().zip(b.iter()).filter(|(a, b)| a != b).count()
+			}
+			_ => return Err(Error::Unreachable("Vector::hamming_distance")),
+		};
+		Ok(dist as f64)
+	}
+}
+
+fn f16_to_f64(v: u16) -> f64 {
+	f16::from_bits(v).to_f64()
+}
+
+/// The number of bits which differ between two bit-packed vectors
+fn hamming(a: &[u8], b: &[u8]) -> u32 {
+	a.iter().zip(b.iter()).map(|(a, b)| (a ^ b).count_ones()).sum()
 }
@@ -0,0 +1,85 @@
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+pub struct Ky<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub key: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, key: &'a str) -> Ky<'a> {
+	Ky::new(ns, db, key)
+}
+
+pub fn prefix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'k', b'y', 0x00]);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'k', b'y', 0xff]);
+	k
+}
+
+impl KeyRequirements for Ky<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::DatabaseApiKey
+	}
+}
+
+impl<'a> Ky<'a> {
+	pub fn new(ns: &'a str, db: &'a str, key: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'k',
+			_e: b'y',
+			key,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Ky::new(
+			"testns",
+			"testdb",
+			"testkey",
+		);
+		let enc = Ky::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00!kytestkey\x00");
+		let dec = Ky::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb");
+		assert_eq!(val, b"/*testns\0*testdb\0!ky\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns", "testdb");
+		assert_eq!(val, b"/*testns\0*testdb\0!ky\xff");
+	}
+}
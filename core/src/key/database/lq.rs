@@ -0,0 +1,99 @@
+//! Stores a LIVE SELECT query which depends on records in other tables
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Lq is used to find the live queries in a database which need to be notified
+/// when records in a table other than their own are changed, i.e. when a record
+/// which is fetched by the live query is altered.
+///
+/// The value of the lq is the table of the live query.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+pub struct Lq<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	#[serde(with = "uuid::serde::compact")]
+	pub lq: Uuid,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, lq: Uuid) -> Lq<'a> {
+	Lq::new(ns, db, lq)
+}
+
+pub fn prefix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'l', b'q', 0x00]);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'l', b'q']);
+	k.extend_from_slice(Uuid::max().as_ref());
+	// We need the extra byte here because `getr()` only supports half-open ranges
+	// so it wouldn't match max UUIDs because it doesn't check for equal matches
+	// on the upper bound. Adding an extra byte to bring max into range as well.
+	k.push(0x00);
+	k
+}
+
+impl KeyRequirements for Lq<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::DatabaseLiveQuery
+	}
+}
+
+impl<'a> Lq<'a> {
+	pub fn new(ns: &'a str, db: &'a str, lq: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'l',
+			_e: b'q',
+			lq,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let live_query_id = Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+		let val = Lq::new("testns", "testdb", live_query_id);
+		let enc = Lq::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\x00*testdb\x00!lq\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+
+		let dec = Lq::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn prefix() {
+		let val = super::prefix("testns", "testdb");
+		assert_eq!(val, b"/*testns\x00*testdb\x00!lq\x00")
+	}
+
+	#[test]
+	fn suffix() {
+		let val = super::suffix("testns", "testdb");
+		assert_eq!(val, b"/*testns\x00*testdb\x00!lq\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00")
+	}
+}
@@ -1,8 +1,11 @@
 pub mod all;
 pub mod az;
 pub mod fc;
+pub mod ky;
+pub mod lq;
 pub mod ml;
 pub mod pa;
+pub mod rl;
 pub mod sc;
 pub mod tb;
 pub mod ti;
@@ -5,18 +5,34 @@ pub enum KeyCategory {
 	/// This category is reserved for cases when we do not know the category
 	/// It should be caught and re-populated with the correct category where appropriate
 	Unknown,
+	/// crate::key::root::ak                 /!ak{hash}
+	ApiKeyIndex,
 	/// crate::key::root::all                /
 	Root,
+	/// crate::key::root::at                 /!at{ns}{db}{sc}{id}
+	SigninAttempts,
 	/// crate::key::root::hb                 /!hb{ts}/{nd}
 	Heartbeat,
+	/// crate::key::root::ib                 /!ib{ns}{db}{tb}{ix}
+	IndexBuild,
+	/// crate::key::root::ky                 /!ky{ky}
+	ApiKey,
 	/// crate::key::root::nd                 /!nd{nd}
 	Node,
 	/// crate::key::root::ni                 /!ni
 	NamespaceIdentifier,
 	/// crate::key::root::ns                 /!ns{ns}
 	Namespace,
+	/// crate::key::root::rl                 /!rl{rl}
+	Role,
+	/// crate::key::root::rt                 /!rt{hash}
+	RefreshToken,
+	/// crate::key::root::rv                 /!rv{ns}{db}{sc}{id}
+	Revocation,
 	/// crate::key::root::us                 /!us{us}
 	User,
+	/// crate::key::root::wq                 /!wq{ts}{id}
+	WebhookQueue,
 	///
 	/// crate::key::node::all                /${nd}
 	NodeRoot,
@@ -29,8 +45,12 @@ pub enum KeyCategory {
 	DatabaseAlias,
 	/// crate::key::namespace::di            /+{ns id}!di
 	DatabaseIdentifier,
+	/// crate::key::namespace::ky            /*{ns}!ky{ky}
+	NamespaceApiKey,
 	/// crate::key::namespace::lg            /*{ns}!lg{lg}
 	DatabaseLogAlias,
+	/// crate::key::namespace::rl            /*{ns}!rl{rl}
+	NamespaceRole,
 	/// crate::key::namespace::tk            /*{ns}!tk{tk}
 	NamespaceToken,
 	/// crate::key::namespace::us            /*{ns}!us{us}
@@ -42,12 +62,18 @@ pub enum KeyCategory {
 	DatabaseAnalyzer,
 	/// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 	DatabaseFunction,
+	/// crate::key::database::ky             /*{ns}*{db}!ky{ky}
+	DatabaseApiKey,
 	/// crate::key::database::lg             /*{ns}*{db}!lg{lg}
 	DatabaseLog,
+	/// crate::key::database::lq             /*{ns}*{db}!lq{lq}
+	DatabaseLiveQuery,
 	/// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
 	DatabaseModel,
 	/// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 	DatabaseParameter,
+	/// crate::key::database::rl             /*{ns}*{db}!rl{rl}
+	DatabaseRole,
 	/// crate::key::database::sc             /*{ns}*{db}!sc{sc}
 	DatabaseScope,
 	/// crate::key::database::tb             /*{ns}*{db}!tb{tb}
@@ -70,6 +96,10 @@ pub enum KeyCategory {
 	///
 	/// crate::key::table::all               /*{ns}*{db}*{tb}
 	TableRoot,
+	/// crate::key::table::cn                /*{ns}*{db}*{tb}!cn{cn}
+	TableConsumer,
+	/// crate::key::table::co                /*{ns}*{db}*{tb}!co{cn}
+	TableConsumerOffset,
 	/// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
 	TableEvent,
 	/// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
@@ -80,6 +110,12 @@ pub enum KeyCategory {
 	IndexDefinition,
 	/// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
 	TableLiveQuery,
+	/// crate::key::table::pl                /*{ns}*{db}*{tb}!pl{pl}
+	TablePolicy,
+	/// crate::key::table::wd                /*{ns}*{db}*{tb}!wd{wh}{id}
+	TableWebhookDeadLetter,
+	/// crate::key::table::wh                /*{ns}*{db}*{tb}!wh{wh}
+	TableWebhook,
 	///
 	/// crate::key::index::all               /*{ns}*{db}*{tb}+{ix}
 	IndexRoot,
@@ -105,6 +141,8 @@ pub enum KeyCategory {
 	IndexBTreeNodeTerms,
 	/// crate::key::index::bu                /*{ns}*{db}*{tb}+{ix}!bu{id}
 	IndexTerms,
+	/// crate::key::index::ib                /*{ns}*{db}*{tb}+{ix}!ib
+	IndexBuildState,
 	/// crate::key::index                    /*{ns}*{db}*{tb}+{ix}*{fd}{id}
 	Index,
 	///
@@ -122,26 +160,39 @@ impl Display for KeyCategory {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		let name = match self {
 			KeyCategory::Unknown => "Unknown",
+			KeyCategory::ApiKeyIndex => "ApiKeyIndex",
 			KeyCategory::Root => "Root",
+			KeyCategory::SigninAttempts => "SigninAttempts",
 			KeyCategory::Heartbeat => "Heartbeat",
+			KeyCategory::IndexBuild => "IndexBuild",
+			KeyCategory::ApiKey => "ApiKey",
 			KeyCategory::Node => "Node",
 			KeyCategory::NamespaceIdentifier => "NamespaceIdentifier",
 			KeyCategory::Namespace => "Namespace",
+			KeyCategory::Role => "Role",
+			KeyCategory::RefreshToken => "RefreshToken",
+			KeyCategory::Revocation => "Revocation",
 			KeyCategory::User => "User",
+			KeyCategory::WebhookQueue => "WebhookQueue",
 			KeyCategory::NodeRoot => "NodeRoot",
 			KeyCategory::NodeLiveQuery => "NodeLiveQuery",
 			KeyCategory::NamespaceRoot => "NamespaceRoot",
 			KeyCategory::DatabaseAlias => "DatabaseAlias",
 			KeyCategory::DatabaseIdentifier => "DatabaseIdentifier",
+			KeyCategory::NamespaceApiKey => "NamespaceApiKey",
 			KeyCategory::DatabaseLogAlias => "DatabaseLogAlias",
+			KeyCategory::NamespaceRole => "NamespaceRole",
 			KeyCategory::NamespaceToken => "NamespaceToken",
 			KeyCategory::NamespaceUser => "NamespaceUser",
 			KeyCategory::DatabaseRoot => "DatabaseRoot",
 			KeyCategory::DatabaseAnalyzer => "DatabaseAnalyzer",
 			KeyCategory::DatabaseFunction => "DatabaseFunction",
+			KeyCategory::DatabaseApiKey => "DatabaseApiKey",
 			KeyCategory::DatabaseLog => "DatabaseLog",
+			KeyCategory::DatabaseLiveQuery => "DatabaseLiveQuery",
 			KeyCategory::DatabaseModel => "DatabaseModel",
 			KeyCategory::DatabaseParameter => "DatabaseParameter",
+			KeyCategory::DatabaseRole => "DatabaseRole",
 			KeyCategory::DatabaseScope => "DatabaseScope",
 			KeyCategory::DatabaseTable => "DatabaseTable",
 			KeyCategory::DatabaseTableIdentifier => "DatabaseTableIdentifier",
@@ -152,11 +203,16 @@ impl Display for KeyCategory {
 			KeyCategory::ScopeRoot => "ScopeRoot",
 			KeyCategory::ScopeToken => "ScopeToken",
 			KeyCategory::TableRoot => "TableRoot",
+			KeyCategory::TableConsumer => "TableConsumer",
+			KeyCategory::TableConsumerOffset => "TableConsumerOffset",
 			KeyCategory::TableEvent => "TableEvent",
 			KeyCategory::TableField => "TableField",
 			KeyCategory::TableView => "TableView",
 			KeyCategory::IndexDefinition => "IndexDefinition",
 			KeyCategory::TableLiveQuery => "TableLiveQuery",
+			KeyCategory::TablePolicy => "TablePolicy",
+			KeyCategory::TableWebhookDeadLetter => "TableWebhookDeadLetter",
+			KeyCategory::TableWebhook => "TableWebhook",
 			KeyCategory::IndexRoot => "IndexRoot",
 			KeyCategory::IndexTermDocList => "IndexTermDocList",
 			KeyCategory::IndexBTreeNode => "IndexBTreeNode",
@@ -169,6 +225,7 @@ impl Display for KeyCategory {
 			KeyCategory::IndexFullTextState => "IndexFullTextState",
 			KeyCategory::IndexBTreeNodeTerms => "IndexBTreeNodeTerms",
 			KeyCategory::IndexTerms => "IndexTerms",
+			KeyCategory::IndexBuildState => "IndexBuildState",
 			KeyCategory::Index => "Index",
 			KeyCategory::ChangeFeed => "ChangeFeed",
 			KeyCategory::Thing => "Thing",
@@ -11,6 +11,8 @@ pub mod bp;
 pub mod bs;
 pub mod bt;
 pub mod bu;
+pub mod ib;
+pub mod uq;
 pub mod vm;
 
 use crate::key::error::KeyCategory;
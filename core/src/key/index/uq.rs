@@ -0,0 +1,69 @@
+//! Stores the record holding a value of a deferred unique index
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use crate::sql::array::Array;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+pub struct Uq<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	pub ix: &'a str,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub fd: Cow<'a, Array>,
+}
+
+impl KeyRequirements for Uq<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::Index
+	}
+}
+
+impl<'a> Uq<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, ix: &'a str, fd: &'a Array) -> Self {
+		Uq {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'u',
+			_g: b'q',
+			fd: Cow::Borrowed(fd),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let fd = vec!["testfd1", "testfd2"].into();
+		let val = Uq::new("testns", "testdb", "testtb", "testix", &fd);
+		let enc = Uq::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\0*testdb\0*testtb\0+testix\0!uq\0\0\0\x04testfd1\0\0\0\0\x04testfd2\0\x01"
+		);
+
+		let dec = Uq::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -1,11 +1,19 @@
 //! How the keys are structured in the key value store
 ///
+/// crate::key::root::ak                 /!ak{hash}
 /// crate::key::root::all                /
+/// crate::key::root::at                 /!at{ns}{db}{sc}{id}
 /// crate::key::root::hb                 /!hb{ts}/{nd}
+/// crate::key::root::ib                 /!ib{ns}{db}{tb}{ix}
+/// crate::key::root::ky                 /!ky{ky}
 /// crate::key::root::nd                 /!nd{nd}
 /// crate::key::root::ni                 /!ni
 /// crate::key::root::ns                 /!ns{ns}
+/// crate::key::root::rl                 /!rl{rl}
+/// crate::key::root::rt                 /!rt{hash}
+/// crate::key::root::rv                 /!rv{ns}{db}{sc}{id}
 /// crate::key::root::us                 /!us{us}
+/// crate::key::root::wq                 /!wq{ts}{id}
 ///
 /// crate::key::node::all                /${nd}
 /// crate::key::node::lq                 /${nd}!lq{lq}{ns}{db}
@@ -13,15 +21,20 @@
 /// crate::key::namespace::all           /*{ns}
 /// crate::key::namespace::db            /*{ns}!db{db}
 /// crate::key::namespace::di            /+{ns id}!di
+/// crate::key::namespace::ky            /*{ns}!ky{ky}
 /// crate::key::namespace::lg            /*{ns}!lg{lg}
+/// crate::key::namespace::rl            /*{ns}!rl{rl}
 /// crate::key::namespace::tk            /*{ns}!tk{tk}
 /// crate::key::namespace::us            /*{ns}!us{us}
 ///
 /// crate::key::database::all            /*{ns}*{db}
 /// crate::key::database::az             /*{ns}*{db}!az{az}
 /// crate::key::database::fc             /*{ns}*{db}!fn{fc}
+/// crate::key::database::ky             /*{ns}*{db}!ky{ky}
 /// crate::key::database::lg             /*{ns}*{db}!lg{lg}
+/// crate::key::database::lq             /*{ns}*{db}!lq{lq}
 /// crate::key::database::pa             /*{ns}*{db}!pa{pa}
+/// crate::key::database::rl             /*{ns}*{db}!rl{rl}
 /// crate::key::database::sc             /*{ns}*{db}!sc{sc}
 /// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 /// crate::key::database::ti             /+{ns id}*{db id}!ti
@@ -34,11 +47,16 @@
 /// crate::key::scope::tk                /*{ns}*{db}±{sc}!tk{tk}
 ///
 /// crate::key::table::all               /*{ns}*{db}*{tb}
+/// crate::key::table::cn                /*{ns}*{db}*{tb}!cn{cn}
+/// crate::key::table::co                /*{ns}*{db}*{tb}!co{cn}
 /// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
 /// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
 /// crate::key::table::ft                /*{ns}*{db}*{tb}!ft{ft}
 /// crate::key::table::ix                /*{ns}*{db}*{tb}!ix{ix}
 /// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
+/// crate::key::table::pl                /*{ns}*{db}*{tb}!pl{pl}
+/// crate::key::table::wd                /*{ns}*{db}*{tb}!wd{wh}{id}
+/// crate::key::table::wh                /*{ns}*{db}*{tb}!wh{wh}
 ///
 /// crate::key::index::all               /*{ns}*{db}*{tb}+{ix}
 /// crate::key::index::bc                /*{ns}*{db}*{tb}+{ix}!bc{id}
@@ -52,6 +70,8 @@
 /// crate::key::index::bs                /*{ns}*{db}*{tb}+{ix}!bs
 /// crate::key::index::bt                /*{ns}*{db}*{tb}+{ix}!bt{id}
 /// crate::key::index::bu                /*{ns}*{db}*{tb}+{ix}!bu{id}
+/// crate::key::index::ib                /*{ns}*{db}*{tb}+{ix}!ib
+/// crate::key::index::uq                /*{ns}*{db}*{tb}+{ix}!uq{fd}
 /// crate::key::index                    /*{ns}*{db}*{tb}+{ix}*{fd}{id}
 ///
 /// crate::key::change                   /*{ns}*{db}#{ts}
@@ -0,0 +1,80 @@
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+pub struct Ky<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	_c: u8,
+	_d: u8,
+	pub key: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, key: &'a str) -> Ky<'a> {
+	Ky::new(ns, key)
+}
+
+pub fn prefix(ns: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'k', b'y', 0x00]);
+	k
+}
+
+pub fn suffix(ns: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'k', b'y', 0xff]);
+	k
+}
+
+impl KeyRequirements for Ky<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::NamespaceApiKey
+	}
+}
+
+impl<'a> Ky<'a> {
+	pub fn new(ns: &'a str, key: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'!',
+			_c: b'k',
+			_d: b'y',
+			key,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Ky::new(
+			"testns",
+			"testkey",
+		);
+		let enc = Ky::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00!kytestkey\x00");
+		let dec = Ky::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns");
+		assert_eq!(val, b"/*testns\0!ky\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns");
+		assert_eq!(val, b"/*testns\0!ky\xff");
+	}
+}
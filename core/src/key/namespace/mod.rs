@@ -1,5 +1,7 @@
 pub mod all;
 pub mod db;
 pub mod di;
+pub mod ky;
+pub mod rl;
 pub mod tk;
 pub mod us;
@@ -0,0 +1,80 @@
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+pub struct Rl<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	_c: u8,
+	_d: u8,
+	pub role: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, role: &'a str) -> Rl<'a> {
+	Rl::new(ns, role)
+}
+
+pub fn prefix(ns: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'r', b'l', 0x00]);
+	k
+}
+
+pub fn suffix(ns: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'r', b'l', 0xff]);
+	k
+}
+
+impl KeyRequirements for Rl<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::NamespaceRole
+	}
+}
+
+impl<'a> Rl<'a> {
+	pub fn new(ns: &'a str, role: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'!',
+			_c: b'r',
+			_d: b'l',
+			role,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Rl::new(
+			"testns",
+			"testrole",
+		);
+		let enc = Rl::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00!rltestrole\x00");
+		let dec = Rl::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns");
+		assert_eq!(val, b"/*testns\0!rl\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns");
+		assert_eq!(val, b"/*testns\0!rl\xff");
+	}
+}
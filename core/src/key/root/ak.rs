@@ -0,0 +1,50 @@
+//! Stores the location of an API key, keyed by the hash of the key
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+pub struct Ak<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub hash: &'a str,
+}
+
+pub fn new(hash: &str) -> Ak<'_> {
+	Ak::new(hash)
+}
+
+impl KeyRequirements for Ak<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::ApiKeyIndex
+	}
+}
+
+impl<'a> Ak<'a> {
+	pub fn new(hash: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'a',
+			_c: b'k',
+			hash,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Ak::new("testhash");
+		let enc = Ak::encode(&val).unwrap();
+		assert_eq!(enc, b"/!aktesthash\x00");
+		let dec = Ak::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
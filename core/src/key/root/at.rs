@@ -0,0 +1,68 @@
+//! Stores the recent signin attempts of a user, for throttling and locking out the user
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+pub struct At<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub ns: &'a str,
+	pub db: &'a str,
+	pub sc: &'a str,
+	pub id: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, sc: &'a str, id: &'a str) -> At<'a> {
+	At::new(ns, db, sc, id)
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(&[b'!', b'a', b't', 0x00]);
+	k
+}
+
+pub fn suffix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(&[b'!', b'a', b't', 0xff]);
+	k
+}
+
+impl KeyRequirements for At<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::SigninAttempts
+	}
+}
+
+impl<'a> At<'a> {
+	pub fn new(ns: &'a str, db: &'a str, sc: &'a str, id: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'a',
+			_c: b't',
+			ns,
+			db,
+			sc,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = At::new("testns", "testdb", "testsc", "testid");
+		let enc = At::encode(&val).unwrap();
+		assert_eq!(enc, b"/!attestns\x00testdb\x00testsc\x00testid\x00");
+		let dec = At::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
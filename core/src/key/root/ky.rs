@@ -0,0 +1,73 @@
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+pub struct Ky<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub key: &'a str,
+}
+
+pub fn new(key: &str) -> Ky<'_> {
+	Ky::new(key)
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(&[b'!', b'k', b'y', 0x00]);
+	k
+}
+
+pub fn suffix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(&[b'!', b'k', b'y', 0xff]);
+	k
+}
+
+impl KeyRequirements for Ky<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::ApiKey
+	}
+}
+
+impl<'a> Ky<'a> {
+	pub fn new(key: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'k',
+			_c: b'y',
+			key,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Ky::new("testkey");
+		let enc = Ky::encode(&val).unwrap();
+		assert_eq!(enc, b"/!kytestkey\x00");
+		let dec = Ky::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix();
+		assert_eq!(val, b"/!ky\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix();
+		assert_eq!(val, b"/!ky\xff");
+	}
+}
@@ -1,6 +1,14 @@
+pub mod ak;
 pub mod all;
+pub mod at;
 pub mod hb;
+pub mod ib;
+pub mod ky;
 pub mod nd;
 pub mod ni;
 pub mod ns;
+pub mod rl;
+pub mod rt;
+pub mod rv;
 pub mod us;
+pub mod wq;
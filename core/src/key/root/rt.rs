@@ -0,0 +1,62 @@
+//! Stores a refresh token, keyed by the hash of the token
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+pub struct Rt<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub hash: &'a str,
+}
+
+pub fn new(hash: &str) -> Rt<'_> {
+	Rt::new(hash)
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(&[b'!', b'r', b't', 0x00]);
+	k
+}
+
+pub fn suffix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(&[b'!', b'r', b't', 0xff]);
+	k
+}
+
+impl KeyRequirements for Rt<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::RefreshToken
+	}
+}
+
+impl<'a> Rt<'a> {
+	pub fn new(hash: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'r',
+			_c: b't',
+			hash,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Rt::new("testhash");
+		let enc = Rt::encode(&val).unwrap();
+		assert_eq!(enc, b"/!rttesthash\x00");
+		let dec = Rt::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -0,0 +1,56 @@
+//! Stores the time at which the sessions of a user, or of a whole level, were revoked
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+pub struct Rv<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub ns: &'a str,
+	pub db: &'a str,
+	pub sc: &'a str,
+	pub id: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, sc: &'a str, id: &'a str) -> Rv<'a> {
+	Rv::new(ns, db, sc, id)
+}
+
+impl KeyRequirements for Rv<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::Revocation
+	}
+}
+
+impl<'a> Rv<'a> {
+	pub fn new(ns: &'a str, db: &'a str, sc: &'a str, id: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'r',
+			_c: b'v',
+			ns,
+			db,
+			sc,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Rv::new("testns", "testdb", "testsc", "testid");
+		let enc = Rv::encode(&val).unwrap();
+		assert_eq!(enc, b"/!rvtestns\x00testdb\x00testsc\x00testid\x00");
+		let dec = Rv::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -0,0 +1,83 @@
+//! Stores a pending webhook delivery, ordered by the time of the next attempt
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+pub struct Wq {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub ts: u64,
+	#[serde(with = "uuid::serde::compact")]
+	pub id: Uuid,
+}
+
+pub fn new(ts: u64, id: Uuid) -> Wq {
+	Wq::new(ts, id)
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(&[b'!', b'w', b'q', 0x00]);
+	k
+}
+
+/// Returns the end of the range of deliveries which are due at the given time
+pub fn suffix(ts: u64) -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(&[b'!', b'w', b'q']);
+	k.extend_from_slice(&ts.saturating_add(1).to_be_bytes());
+	k
+}
+
+impl KeyRequirements for Wq {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::WebhookQueue
+	}
+}
+
+impl Wq {
+	pub fn new(ts: u64, id: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'w',
+			_c: b'q',
+			ts,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Wq::new(
+			123,
+			Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]),
+		);
+		let enc = Wq::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/!wq\x00\x00\x00\x00\x00\x00\x00\x7b\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+
+		let dec = Wq::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix(123);
+		assert_eq!(val, b"/!wq\x00\x00\x00\x00\x00\x00\x00\x7c");
+		let enc = super::new(123, uuid::Uuid::max()).encode().unwrap();
+		assert!(enc < val);
+	}
+}
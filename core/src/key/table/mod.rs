@@ -1,6 +1,11 @@
 pub mod all;
+pub mod cn;
+pub mod co;
 pub mod ev;
 pub mod fd;
 pub mod ft;
 pub mod ix;
 pub mod lq;
+pub mod pl;
+pub mod wd;
+pub mod wh;
@@ -0,0 +1,132 @@
+//! Stores a webhook delivery which failed after all of its retries
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+pub struct Wd<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub wh: &'a str,
+	#[serde(with = "uuid::serde::compact")]
+	pub id: Uuid,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+struct Prefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub wh: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, wh: &'a str, id: Uuid) -> Wd<'a> {
+	Wd::new(ns, db, tb, wh, id)
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str, wh: &str) -> Vec<u8> {
+	Prefix::new(ns, db, tb, wh).encode().unwrap()
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str, wh: &str) -> Vec<u8> {
+	let mut k = Prefix::new(ns, db, tb, wh).encode().unwrap();
+	// Replace the terminator of the webhook name
+	*k.last_mut().unwrap() = 0x01;
+	k
+}
+
+impl KeyRequirements for Wd<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::TableWebhookDeadLetter
+	}
+}
+
+impl<'a> Wd<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, wh: &'a str, id: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'w',
+			_f: b'd',
+			wh,
+			id,
+		}
+	}
+}
+
+impl<'a> Prefix<'a> {
+	fn new(ns: &'a str, db: &'a str, tb: &'a str, wh: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'w',
+			_f: b'd',
+			wh,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Wd::new(
+			"testns",
+			"testdb",
+			"testtb",
+			"testwh",
+			Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]),
+		);
+		let enc = Wd::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\x00*testdb\x00*testtb\x00!wdtestwh\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+
+		let dec = Wd::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb", "testtb", "testwh");
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!wdtestwh\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns", "testdb", "testtb", "testwh");
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!wdtestwh\x01");
+	}
+}
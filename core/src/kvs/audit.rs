@@ -0,0 +1,477 @@
+//! Records authentication and data-definition events in an append-only audit log.
+//!
+//! Each record in the log is a single line of JSON, which contains the hash of
+//! the previous record, and the hash of its own contents. Any change to a record,
+//! or the removal of a record from anywhere but the end of the log, breaks the
+//! chain of hashes, which can be checked with [`verify`]. The log is opened in
+//! append mode, and an existing log is continued from its last record.
+//!
+//! Statements are recorded when they are run. A statement which is run within a
+//! transaction is recorded even if the transaction is later cancelled.
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::iam::{Auth, Level};
+use crate::sql::statements::{DefineStatement, RemoveStatement};
+use crate::sql::{Object, Statement, Value};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// The hash which precedes the first record in a log
+const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// The text which replaces secrets in recorded statements
+const REDACTED: &str = "[REDACTED]";
+
+/// The classes of events which can be recorded in the audit log
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditClass {
+	/// The outcome of signing in, with credentials or with basic authentication
+	Signin,
+	/// The outcome of signing up to a scope
+	Signup,
+	/// A failure to authenticate with a token
+	Token,
+	/// A `DEFINE` or `REMOVE` statement for anything but users and roles
+	Ddl,
+	/// A `DEFINE` or `REMOVE` statement for users and roles
+	User,
+}
+
+impl AuditClass {
+	/// All of the classes of events
+	pub const ALL: [AuditClass; 5] = [
+		AuditClass::Signin,
+		AuditClass::Signup,
+		AuditClass::Token,
+		AuditClass::Ddl,
+		AuditClass::User,
+	];
+}
+
+impl Display for AuditClass {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			AuditClass::Signin => f.write_str("signin"),
+			AuditClass::Signup => f.write_str("signup"),
+			AuditClass::Token => f.write_str("token"),
+			AuditClass::Ddl => f.write_str("ddl"),
+			AuditClass::User => f.write_str("user"),
+		}
+	}
+}
+
+impl FromStr for AuditClass {
+	type Err = Error;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_ascii_lowercase().as_str() {
+			"signin" => Ok(AuditClass::Signin),
+			"signup" => Ok(AuditClass::Signup),
+			"token" => Ok(AuditClass::Token),
+			"ddl" => Ok(AuditClass::Ddl),
+			"user" => Ok(AuditClass::User),
+			_ => Err(Error::InvalidAuditLog(format!("unknown class of events '{s}'"))),
+		}
+	}
+}
+
+/// Whether an audited event succeeded
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+	Success,
+	Failure,
+}
+
+/// The contents of a record, which are covered by its hash
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Entry {
+	seq: u64,
+	time: String,
+	class: AuditClass,
+	outcome: Outcome,
+	actor: String,
+	level: String,
+	roles: Vec<String>,
+	ip: Option<String>,
+	detail: String,
+	error: Option<String>,
+	prev: String,
+}
+
+impl Entry {
+	fn hash(&self) -> Result<String, Error> {
+		let json =
+			serde_json::to_string(self).map_err(|e| Error::InvalidAuditLog(e.to_string()))?;
+		Ok(hex::encode(Sha256::digest(json.as_bytes())))
+	}
+}
+
+/// A single line of the audit log
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Record {
+	#[serde(flatten)]
+	entry: Entry,
+	hash: String,
+}
+
+/// An event which is about to be recorded
+pub(crate) struct Event {
+	pub(crate) class: AuditClass,
+	pub(crate) actor: String,
+	pub(crate) level: String,
+	pub(crate) roles: Vec<String>,
+	pub(crate) ip: Option<String>,
+	pub(crate) detail: String,
+}
+
+impl Event {
+	/// Creates an event which was caused by an authenticated actor
+	pub(crate) fn new(class: AuditClass, auth: &Auth, ip: Option<String>, detail: String) -> Self {
+		Self {
+			class,
+			actor: auth.id().to_owned(),
+			level: auth.level().to_string(),
+			roles: auth.roles().iter().map(ToString::to_string).collect(),
+			ip,
+			detail,
+		}
+	}
+	/// Creates an event which was caused by the actor of a session
+	pub(crate) fn for_session(class: AuditClass, sess: &Session, detail: String) -> Self {
+		Self::new(class, &sess.au, sess.ip.clone(), detail)
+	}
+	/// Creates an event for an attempt to authenticate, which was made with a session
+	pub(crate) fn for_attempt<T>(
+		class: AuditClass,
+		sess: &Session,
+		target: Target,
+		res: &Result<T, Error>,
+	) -> Self {
+		let detail = target.level.to_string();
+		match res {
+			// The session is now authenticated as the actor
+			Ok(_) => Self::for_session(class, sess, detail),
+			// The actor is the identity which was claimed
+			Err(_) => Self {
+				class,
+				actor: target.user.unwrap_or_default(),
+				level: detail.clone(),
+				roles: vec![],
+				ip: sess.ip.clone(),
+				detail,
+			},
+		}
+	}
+}
+
+/// The identity which an actor attempted to authenticate as
+pub(crate) struct Target {
+	level: Level,
+	user: Option<String>,
+}
+
+impl Target {
+	pub(crate) fn new(
+		ns: Option<&str>,
+		db: Option<&str>,
+		sc: Option<&str>,
+		user: Option<&str>,
+	) -> Self {
+		let level = match (ns, db, sc) {
+			(Some(ns), Some(db), Some(sc)) => Level::Scope(ns.into(), db.into(), sc.into()),
+			(Some(ns), Some(db), None) => Level::Database(ns.into(), db.into()),
+			(Some(ns), None, None) => Level::Namespace(ns.into()),
+			(None, None, None) => Level::Root,
+			_ => Level::No,
+		};
+		Self {
+			level,
+			user: user.map(str::to_owned),
+		}
+	}
+	/// Gets the target from the variables which were used to sign in or sign up
+	pub(crate) fn from_vars(vars: &Object) -> Self {
+		let get = |k: &str| {
+			vars.get(&k.to_ascii_uppercase()).or_else(|| vars.get(k)).map(Value::to_raw_string)
+		};
+		let (ns, db, sc) = (get("ns"), get("db"), get("sc"));
+		let user = vars.get("user").map(Value::to_raw_string);
+		Self::new(ns.as_deref(), db.as_deref(), sc.as_deref(), user.as_deref())
+	}
+}
+
+struct Writer {
+	file: File,
+	seq: u64,
+	hash: String,
+}
+
+/// An append-only, hash-chained audit log
+pub struct AuditLog {
+	classes: HashSet<AuditClass>,
+	writer: Mutex<Writer>,
+}
+
+impl AuditLog {
+	/// Opens the audit log at the path, recording the given classes of events
+	pub fn open<P: AsRef<Path>>(
+		path: P,
+		classes: impl IntoIterator<Item = AuditClass>,
+	) -> Result<Self, Error> {
+		let mut file = OpenOptions::new().read(true).append(true).create(true).open(path)?;
+		// Continue the chain from the last record
+		let (seq, hash) = match last_line(&mut file)? {
+			Some(line) => {
+				let rec: Record = serde_json::from_str(&line)
+					.map_err(|e| Error::InvalidAuditLog(e.to_string()))?;
+				(rec.entry.seq, rec.hash)
+			}
+			None => (0, GENESIS.to_owned()),
+		};
+		Ok(Self {
+			classes: classes.into_iter().collect(),
+			writer: Mutex::new(Writer {
+				file,
+				seq,
+				hash,
+			}),
+		})
+	}
+
+	/// Checks if a class of events is recorded
+	pub fn records(&self, class: AuditClass) -> bool {
+		self.classes.contains(&class)
+	}
+
+	/// Records the outcome of an event, if its class is recorded
+	pub(crate) fn record<T>(&self, event: Event, res: &Result<T, Error>) {
+		if !self.records(event.class) {
+			return;
+		}
+		if let Err(e) = self.append(event, res) {
+			error!("Failed to write to the audit log: {e}");
+		}
+	}
+
+	fn append<T>(&self, event: Event, res: &Result<T, Error>) -> Result<(), Error> {
+		let mut writer = self.writer.lock().map_err(|e| Error::InvalidAuditLog(e.to_string()))?;
+		let entry = Entry {
+			seq: writer.seq + 1,
+			time: Utc::now().to_rfc3339(),
+			class: event.class,
+			outcome: match res {
+				Ok(_) => Outcome::Success,
+				Err(_) => Outcome::Failure,
+			},
+			actor: event.actor,
+			level: event.level,
+			roles: event.roles,
+			ip: event.ip,
+			detail: event.detail,
+			error: res.as_ref().err().map(ToString::to_string),
+			prev: writer.hash.clone(),
+		};
+		let hash = entry.hash()?;
+		let rec = Record {
+			entry,
+			hash,
+		};
+		let mut line =
+			serde_json::to_string(&rec).map_err(|e| Error::InvalidAuditLog(e.to_string()))?;
+		line.push('\n');
+		writer.file.write_all(line.as_bytes())?;
+		writer.file.flush()?;
+		writer.seq = rec.entry.seq;
+		writer.hash = rec.hash;
+		Ok(())
+	}
+}
+
+/// Checks the chain of hashes of the audit log at the path, returning the number of records
+pub fn verify<P: AsRef<Path>>(path: P) -> Result<u64, Error> {
+	let file = File::open(path)?;
+	let mut prev = GENESIS.to_owned();
+	let mut seq = 0;
+	for line in BufReader::new(file).lines() {
+		let line = line?;
+		if line.is_empty() {
+			continue;
+		}
+		let rec: Record = serde_json::from_str(&line)
+			.map_err(|e| Error::InvalidAuditLog(format!("record {} is malformed: {e}", seq + 1)))?;
+		if rec.entry.seq != seq + 1 {
+			return Err(Error::InvalidAuditLog(format!("record {} is missing", seq + 1)));
+		}
+		if rec.entry.prev != prev || rec.entry.hash()? != rec.hash {
+			return Err(Error::InvalidAuditLog(format!("record {} has been altered", seq + 1)));
+		}
+		prev = rec.hash;
+		seq = rec.entry.seq;
+	}
+	Ok(seq)
+}
+
+/// Reads the last line of a file, without reading the whole file
+fn last_line(file: &mut File) -> Result<Option<String>, Error> {
+	let len = file.seek(SeekFrom::End(0))?;
+	let mut size = 4096u64;
+	loop {
+		let start = len.saturating_sub(size);
+		file.seek(SeekFrom::Start(start))?;
+		let mut buf = Vec::new();
+		file.take(len - start).read_to_end(&mut buf)?;
+		let text = String::from_utf8_lossy(&buf);
+		let text = text.trim_end_matches('\n');
+		match text.rfind('\n') {
+			Some(i) => return Ok(Some(text[i + 1..].to_owned())),
+			None if start == 0 => {
+				return Ok(match text.is_empty() {
+					true => None,
+					false => Some(text.to_owned()),
+				})
+			}
+			None => size *= 2,
+		}
+	}
+}
+
+/// Gets the class of events of a statement, if it is audited
+pub(crate) fn classify(stm: &Statement) -> Option<AuditClass> {
+	match stm {
+		Statement::Define(DefineStatement::User(_)) => Some(AuditClass::User),
+		#[cfg(feature = "sql2")]
+		Statement::Define(DefineStatement::Role(_)) => Some(AuditClass::User),
+		#[cfg(feature = "sql2")]
+		Statement::Define(DefineStatement::Key(_)) => Some(AuditClass::User),
+		Statement::Define(_) => Some(AuditClass::Ddl),
+		Statement::Remove(RemoveStatement::User(_)) => Some(AuditClass::User),
+		#[cfg(feature = "sql2")]
+		Statement::Remove(RemoveStatement::Role(_)) => Some(AuditClass::User),
+		#[cfg(feature = "sql2")]
+		Statement::Remove(RemoveStatement::Key(_)) => Some(AuditClass::User),
+		Statement::Remove(_) => Some(AuditClass::Ddl),
+		_ => None,
+	}
+}
+
+/// Describes a statement, without any of its secrets
+pub(crate) fn describe(stm: &Statement) -> String {
+	match stm {
+		Statement::Define(DefineStatement::User(v)) => {
+			let mut v = v.clone();
+			v.hash = REDACTED.to_owned();
+			v.code = REDACTED.to_owned();
+			v.to_string()
+		}
+		Statement::Define(DefineStatement::Token(v)) => {
+			let mut v = v.clone();
+			v.code = REDACTED.to_owned();
+			v.to_string()
+		}
+		#[cfg(feature = "sql2")]
+		Statement::Define(DefineStatement::Key(v)) if !v.hash.is_empty() => {
+			let mut v = v.clone();
+			v.hash = REDACTED.to_owned();
+			v.to_string()
+		}
+		v => v.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use temp_dir::TempDir;
+
+	fn event(detail: &str) -> Event {
+		Event::new(AuditClass::Ddl, &Auth::default(), None, detail.to_owned())
+	}
+
+	#[test]
+	fn records_are_chained() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.child("audit.log");
+		// Write some records
+		let log = AuditLog::open(&path, AuditClass::ALL).unwrap();
+		log.record(event("DEFINE TABLE person"), &Ok::<_, Error>(()));
+		log.record(event("REMOVE TABLE person"), &Err::<(), _>(Error::QueryNotExecuted));
+		drop(log);
+		assert_eq!(verify(&path).unwrap(), 2);
+		// Continue the chain of an existing log
+		let log = AuditLog::open(&path, AuditClass::ALL).unwrap();
+		log.record(event("DEFINE TABLE person"), &Ok::<_, Error>(()));
+		drop(log);
+		assert_eq!(verify(&path).unwrap(), 3);
+		// Check the contents of a record
+		let text = std::fs::read_to_string(&path).unwrap();
+		let rec: Record = serde_json::from_str(text.lines().nth(1).unwrap()).unwrap();
+		assert_eq!(rec.entry.seq, 2);
+		assert_eq!(rec.entry.outcome, Outcome::Failure);
+		assert_eq!(rec.entry.detail, "REMOVE TABLE person");
+		assert!(rec.entry.error.is_some());
+	}
+
+	#[test]
+	fn classes_are_filtered() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.child("audit.log");
+		let log = AuditLog::open(&path, [AuditClass::Signin]).unwrap();
+		log.record(event("DEFINE TABLE person"), &Ok::<_, Error>(()));
+		drop(log);
+		assert_eq!(verify(&path).unwrap(), 0);
+	}
+
+	#[test]
+	fn tampering_is_detected() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.child("audit.log");
+		let log = AuditLog::open(&path, AuditClass::ALL).unwrap();
+		for _ in 0..3 {
+			log.record(event("DEFINE TABLE person"), &Ok::<_, Error>(()));
+		}
+		drop(log);
+		let text = std::fs::read_to_string(&path).unwrap();
+		// Alter a record
+		std::fs::write(&path, text.replacen("DEFINE TABLE person", "DEFINE TABLE other", 1))
+			.unwrap();
+		assert!(matches!(verify(&path), Err(Error::InvalidAuditLog(_))));
+		// Remove a record
+		let lines: Vec<&str> = text.lines().collect();
+		std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+		assert!(matches!(verify(&path), Err(Error::InvalidAuditLog(_))));
+	}
+
+	#[test]
+	fn secrets_are_redacted() {
+		let stm = crate::syn::parse(
+			"DEFINE USER test ON ROOT PASSWORD 'secret'; DEFINE TOKEN test ON DATABASE TYPE HS512 VALUE 'secret'",
+		)
+		.unwrap();
+		for stm in stm.iter() {
+			let text = describe(stm);
+			assert!(!text.contains("secret"), "{text}");
+			assert!(text.contains(REDACTED), "{text}");
+		}
+	}
+
+	#[cfg(feature = "sql2")]
+	#[test]
+	fn webhook_secrets_are_redacted() {
+		let stm = crate::syn::parse(
+			"DEFINE WEBHOOK test ON person URL 'http://localhost' SECRET 'secret'",
+		)
+		.unwrap();
+		let text = describe(&stm[0]);
+		assert!(!text.contains("secret"), "{text}");
+		assert!(text.contains(REDACTED), "{text}");
+	}
+}
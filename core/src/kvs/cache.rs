@@ -1,18 +1,28 @@
 use crate::idg::u32::U32;
 use crate::kvs::kv::Key;
 use crate::sql::statements::DefineAnalyzerStatement;
+#[cfg(feature = "sql2")]
+use crate::sql::statements::DefineConsumerStatement;
 use crate::sql::statements::DefineDatabaseStatement;
 use crate::sql::statements::DefineEventStatement;
 use crate::sql::statements::DefineFieldStatement;
 use crate::sql::statements::DefineFunctionStatement;
 use crate::sql::statements::DefineIndexStatement;
+#[cfg(feature = "sql2")]
+use crate::sql::statements::DefineKeyStatement;
 use crate::sql::statements::DefineModelStatement;
 use crate::sql::statements::DefineNamespaceStatement;
 use crate::sql::statements::DefineParamStatement;
+#[cfg(feature = "sql2")]
+use crate::sql::statements::DefinePolicyStatement;
+#[cfg(feature = "sql2")]
+use crate::sql::statements::DefineRoleStatement;
 use crate::sql::statements::DefineScopeStatement;
 use crate::sql::statements::DefineTableStatement;
 use crate::sql::statements::DefineTokenStatement;
 use crate::sql::statements::DefineUserStatement;
+#[cfg(feature = "sql2")]
+use crate::sql::statements::DefineWebhookStatement;
 use crate::sql::statements::LiveStatement;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -29,7 +39,15 @@ pub enum Entry {
 	Tb(Arc<DefineTableStatement>),
 	// Multi definitions
 	Azs(Arc<[DefineAnalyzerStatement]>),
+	#[cfg(feature = "sql2")]
+	Cns(Arc<[DefineConsumerStatement]>),
 	Dbs(Arc<[DefineDatabaseStatement]>),
+	#[cfg(feature = "sql2")]
+	Dls(Arc<[(String, LiveStatement)]>),
+	#[cfg(feature = "sql2")]
+	Dks(Arc<[DefineKeyStatement]>),
+	#[cfg(feature = "sql2")]
+	Drs(Arc<[DefineRoleStatement]>),
 	Dts(Arc<[DefineTokenStatement]>),
 	Dus(Arc<[DefineUserStatement]>),
 	Evs(Arc<[DefineEventStatement]>),
@@ -39,13 +57,21 @@ pub enum Entry {
 	Ixs(Arc<[DefineIndexStatement]>),
 	Lvs(Arc<[LiveStatement]>),
 	Mls(Arc<[DefineModelStatement]>),
+	#[cfg(feature = "sql2")]
+	Nks(Arc<[DefineKeyStatement]>),
+	#[cfg(feature = "sql2")]
+	Nrs(Arc<[DefineRoleStatement]>),
 	Nss(Arc<[DefineNamespaceStatement]>),
 	Nts(Arc<[DefineTokenStatement]>),
 	Nus(Arc<[DefineUserStatement]>),
 	Pas(Arc<[DefineParamStatement]>),
+	#[cfg(feature = "sql2")]
+	Pls(Arc<[DefinePolicyStatement]>),
 	Scs(Arc<[DefineScopeStatement]>),
 	Sts(Arc<[DefineTokenStatement]>),
 	Tbs(Arc<[DefineTableStatement]>),
+	#[cfg(feature = "sql2")]
+	Whs(Arc<[DefineWebhookStatement]>),
 	// Sequences
 	Seq(U32),
 }
@@ -0,0 +1,319 @@
+//! Encrypts the keys and values of a datastore, whichever storage engine it uses.
+//!
+//! Values are encrypted with AES-256-GCM and a random nonce. An encrypted
+//! value starts with a header, so that a datastore which is not encrypted,
+//! or which is encrypted with a different key, is detected when it is read.
+//! The plaintext key of each value is authenticated along with it, so that a
+//! value which is copied or moved to a different key can not be read either.
+//! Change feed keys are authenticated without their versionstamp, as some
+//! storage engines only write the versionstamp into the key on commit.
+//! The versionstamps of each database are maintained by the storage engines
+//! themselves, so these values are always stored unencrypted.
+//!
+//! Keys can optionally be encrypted too. Range scans depend on the order of
+//! the keys, so keys are encrypted deterministically, with an order-preserving
+//! encryption: each byte of a key is mapped to two bytes, with a random but
+//! increasing mapping, which is derived from the encryption key and from all
+//! of the bytes which precede it. This hides the contents of the keys, but it
+//! reveals their order, their length, and which keys share a common prefix,
+//! and it doubles the size of each key.
+//!
+//! The encryption key is loaded from a file, which contains a base64 encoded
+//! 256-bit key. Separate keys for the keys and the values are derived from it.
+use super::{Key, Val};
+use crate::err::Error;
+use crate::key::change::{self, Cf};
+use crate::key::database::vs::Vs;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::aes::cipher::BlockEncrypt;
+use aes_gcm::aes::{Aes256, Block};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64_lib::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+use std::borrow::Cow;
+use std::fmt::{self, Debug};
+use std::path::Path;
+
+/// The bytes which start every encrypted value
+const HEADER: &[u8; 4] = b"sdbe";
+
+/// The length of the nonce of an encrypted value
+const NONCE_LEN: usize = 12;
+
+/// The cipher which encrypts the keys and values of a datastore
+#[derive(Clone)]
+pub struct Cipher {
+	values: Aes256Gcm,
+	keys: Aes256,
+	encrypt_keys: bool,
+}
+
+impl Debug for Cipher {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		// Never print the keys themselves
+		f.debug_struct("Cipher").field("encrypt_keys", &self.encrypt_keys).finish()
+	}
+}
+
+impl Cipher {
+	/// Creates a cipher which encrypts the values of a datastore
+	pub fn new(key: [u8; 32]) -> Self {
+		Self {
+			values: Aes256Gcm::new(&subkey(&key, b"values").into()),
+			keys: Aes256::new(&subkey(&key, b"keys").into()),
+			encrypt_keys: false,
+		}
+	}
+
+	/// Specify whether the keys of the datastore are encrypted too
+	pub fn with_key_encryption(mut self, enabled: bool) -> Self {
+		self.encrypt_keys = enabled;
+		self
+	}
+
+	/// Checks if the keys of the datastore are encrypted
+	pub fn encrypts_keys(&self) -> bool {
+		self.encrypt_keys
+	}
+
+	/// Loads the encryption key from a file
+	pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+		let path = path.as_ref();
+		let text = std::fs::read_to_string(path)
+			.map_err(|e| Error::InvalidEncryptionKey(format!("{}: {e}", path.display())))?;
+		let key = STANDARD
+			.decode(text.trim())
+			.ok()
+			.and_then(|v| <[u8; 32]>::try_from(v).ok())
+			.ok_or_else(|| {
+				Error::InvalidEncryptionKey(format!(
+					"{} does not contain a base64 encoded 256-bit key",
+					path.display()
+				))
+			})?;
+		Ok(Self::new(key))
+	}
+
+	/// Encrypts a key, preserving its order
+	pub(crate) fn encrypt_key(&self, key: Key) -> Key {
+		if !self.encrypt_keys {
+			return key;
+		}
+		let mut out = Vec::with_capacity(key.len() * 2);
+		let mut state = self.initial();
+		for b in key {
+			out.extend_from_slice(&self.codes(&state)[b as usize].to_be_bytes());
+			state = self.next(&state, b);
+		}
+		out
+	}
+
+	/// Decrypts a key which was encrypted with [`Cipher::encrypt_key`]
+	pub(crate) fn decrypt_key(&self, key: Key) -> Result<Key, Error> {
+		if !self.encrypt_keys {
+			return Ok(key);
+		}
+		if key.len() % 2 != 0 {
+			return Err(Error::DatastoreDecryptionFailed);
+		}
+		let mut out = Vec::with_capacity(key.len() / 2);
+		let mut state = self.initial();
+		for code in key.chunks_exact(2) {
+			let code = u16::from_be_bytes([code[0], code[1]]);
+			let b = self
+				.codes(&state)
+				.binary_search(&code)
+				.map_err(|_| Error::DatastoreDecryptionFailed)? as u8;
+			out.push(b);
+			state = self.next(&state, b);
+		}
+		Ok(out)
+	}
+
+	/// Encrypts the value of a key
+	pub(crate) fn encrypt_val(&self, key: &[u8], val: Val) -> Result<Val, Error> {
+		if is_versionstamp(key) {
+			return Ok(val);
+		}
+		let mut nonce = [0u8; NONCE_LEN];
+		rand::thread_rng().fill_bytes(&mut nonce);
+		let payload = Payload {
+			msg: &val,
+			aad: &associated_data(key),
+		};
+		let data = self
+			.values
+			.encrypt(Nonce::from_slice(&nonce), payload)
+			.map_err(|_| Error::Unreachable("Cipher::encrypt_val"))?;
+		let mut out = Vec::with_capacity(HEADER.len() + NONCE_LEN + data.len());
+		out.extend_from_slice(HEADER);
+		out.extend_from_slice(&nonce);
+		out.extend_from_slice(&data);
+		Ok(out)
+	}
+
+	/// Decrypts the value of a key which was encrypted with [`Cipher::encrypt_val`]
+	pub(crate) fn decrypt_val(&self, key: &[u8], val: Val) -> Result<Val, Error> {
+		if is_versionstamp(key) {
+			return Ok(val);
+		}
+		match val.strip_prefix(HEADER) {
+			Some(v) if v.len() >= NONCE_LEN => {
+				let (nonce, data) = v.split_at(NONCE_LEN);
+				let payload = Payload {
+					msg: data,
+					aad: &associated_data(key),
+				};
+				self.values
+					.decrypt(Nonce::from_slice(nonce), payload)
+					.map_err(|_| Error::DatastoreDecryptionFailed)
+			}
+			_ => Err(Error::DatastoreDecryptionFailed),
+		}
+	}
+
+	/// The state of the key encryption before the first byte of a key
+	fn initial(&self) -> Block {
+		let mut block = Block::default();
+		self.keys.encrypt_block(&mut block);
+		block
+	}
+
+	/// The state of the key encryption after a byte of a key
+	fn next(&self, state: &Block, b: u8) -> Block {
+		let mut block = *state;
+		block[0] ^= b;
+		block[15] ^= 0x10;
+		self.keys.encrypt_block(&mut block);
+		block
+	}
+
+	/// The increasing codes which each byte is mapped to, in a state of the key encryption
+	fn codes(&self, state: &Block) -> [u16; 256] {
+		let mut codes = [0u16; 256];
+		let mut last = None;
+		for i in 0..16u8 {
+			let mut block = *state;
+			block[15] ^= i;
+			self.keys.encrypt_block(&mut block);
+			for (j, r) in block.iter().enumerate() {
+				// Every code is at least one more than the previous code, and at most
+				// 255 more, so that the largest code is less than 256 * 255
+				let step = (*r % 255) as u16;
+				let code = match last {
+					Some(last) => last + 1 + step,
+					None => step,
+				};
+				codes[i as usize * 16 + j] = code;
+				last = Some(code);
+			}
+		}
+		codes
+	}
+}
+
+/// Derives a key for a specific purpose from the encryption key
+fn subkey(key: &[u8; 32], purpose: &[u8]) -> [u8; 32] {
+	let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts any key length");
+	mac.update(purpose);
+	mac.finalize().into_bytes().into()
+}
+
+/// The data which is authenticated along with the value of a key
+///
+/// This is the key itself, except for change feed keys, which leave out
+/// their versionstamp, so that they can be encrypted before it is known.
+fn associated_data(key: &[u8]) -> Cow<'_, [u8]> {
+	match Cf::decode(key) {
+		Ok(cf) if cf.encode().is_ok_and(|v| v == key) => {
+			let mut aad = change::versionstamped_key_prefix(cf.ns, cf.db);
+			aad.extend_from_slice(&change::versionstamped_key_suffix(cf.tb));
+			Cow::Owned(aad)
+		}
+		_ => Cow::Borrowed(key),
+	}
+}
+
+/// Checks if a key stores the versionstamp of a database, which the storage engines maintain
+fn is_versionstamp(key: &[u8]) -> bool {
+	key.ends_with(b"!vs")
+		&& Vs::decode(key).ok().and_then(|v| v.encode().ok()).is_some_and(|v| v == key)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn encrypt_values() {
+		let cipher = Cipher::new([1; 32]);
+		let key = b"/*test\0*test\0*person\0*tobie".to_vec();
+		let enc = cipher.encrypt_val(&key, b"value".to_vec()).unwrap();
+		assert!(enc.starts_with(HEADER));
+		assert_ne!(enc, cipher.encrypt_val(&key, b"value".to_vec()).unwrap());
+		assert_eq!(cipher.decrypt_val(&key, enc.clone()).unwrap(), b"value");
+		// Values can only be decrypted with the key which encrypted them
+		let other = Cipher::new([2; 32]);
+		assert!(matches!(other.decrypt_val(&key, enc), Err(Error::DatastoreDecryptionFailed)));
+		// Values which are not encrypted can not be read
+		let val = b"value".to_vec();
+		assert!(matches!(cipher.decrypt_val(&key, val), Err(Error::DatastoreDecryptionFailed)));
+		// Values can only be decrypted with the key they were stored under
+		let enc = cipher.encrypt_val(&key, b"value".to_vec()).unwrap();
+		let moved = b"/*test\0*test\0*person\0*jaime".to_vec();
+		assert!(matches!(cipher.decrypt_val(&moved, enc), Err(Error::DatastoreDecryptionFailed)));
+		// Versionstamps are stored unencrypted
+		let key: Key = Vs::new("test", "test").into();
+		assert_eq!(cipher.encrypt_val(&key, vec![0; 10]).unwrap(), vec![0; 10]);
+	}
+
+	#[test]
+	fn encrypt_change_feed_values() {
+		let cipher = Cipher::new([1; 32]);
+		// Change feed values are encrypted before their versionstamp is known
+		let mut key = change::versionstamped_key_prefix("test", "test");
+		key.extend_from_slice(&change::versionstamped_key_suffix("person"));
+		let enc = cipher.encrypt_val(&key, b"value".to_vec()).unwrap();
+		let key: Key = change::new("test", "test", 12345, "person").into();
+		assert_eq!(cipher.decrypt_val(&key, enc).unwrap(), b"value");
+		// They are still bound to their database and table
+		let enc = cipher.encrypt_val(&key, b"value".to_vec()).unwrap();
+		let key: Key = change::new("test", "test", 12345, "other").into();
+		assert!(matches!(cipher.decrypt_val(&key, enc), Err(Error::DatastoreDecryptionFailed)));
+	}
+
+	#[test]
+	fn encrypt_keys() {
+		let cipher = Cipher::new([1; 32]).with_key_encryption(true);
+		let key = b"/*test\0*test\0*person\0*tobie".to_vec();
+		let enc = cipher.encrypt_key(key.clone());
+		assert_eq!(enc.len(), key.len() * 2);
+		assert_ne!(enc[..key.len()], key[..]);
+		assert_eq!(enc, cipher.encrypt_key(key.clone()));
+		assert_eq!(cipher.decrypt_key(enc).unwrap(), key);
+		// Keys are only encrypted when enabled
+		let cipher = Cipher::new([1; 32]);
+		assert_eq!(cipher.encrypt_key(key.clone()), key);
+	}
+
+	#[test]
+	fn encrypt_keys_in_order() {
+		let cipher = Cipher::new([1; 32]).with_key_encryption(true);
+		let mut keys: Vec<Key> =
+			vec![vec![], vec![0x00], vec![0xff], vec![0x00, 0xff], vec![0xff, 0x00]];
+		for _ in 0..200 {
+			let mut key = vec![0u8; (rand::random::<u8>() % 8) as usize];
+			rand::thread_rng().fill_bytes(&mut key);
+			keys.push(key);
+		}
+		keys.sort();
+		keys.dedup();
+		let enc: Vec<Key> = keys.iter().map(|k| cipher.encrypt_key(k.clone())).collect();
+		assert!(enc.windows(2).all(|w| w[0] < w[1]));
+		for (key, enc) in keys.into_iter().zip(enc) {
+			assert_eq!(cipher.decrypt_key(enc).unwrap(), key);
+		}
+	}
+}
@@ -1,5 +1,11 @@
 use super::tx::Transaction;
 use crate::cf;
+#[cfg(feature = "sql2")]
+use crate::cnf::INDEX_BUILD_BATCHES_PER_TICK;
+#[cfg(feature = "sql2")]
+use crate::cnf::INDEX_BUILD_BATCH_SIZE;
+#[cfg(feature = "sql2")]
+use crate::cnf::{WEBHOOK_BATCH_SIZE, WEBHOOK_CONCURRENCY, WEBHOOK_DELIVERY_BUDGET};
 use crate::ctx::Context;
 #[cfg(feature = "jwks")]
 use crate::dbs::capabilities::NetTarget;
@@ -8,27 +14,48 @@ use crate::dbs::{
 	Variables,
 };
 use crate::err::Error;
+use crate::iam::password::PasswordPolicy;
+use crate::iam::throttle::LoginPolicy;
 use crate::iam::{Action, Auth, Error as IamError, Resource, Role};
+#[cfg(feature = "sql2")]
+use crate::idx::builder::{BuildState, BuildStatus};
 use crate::idx::trees::store::IndexStores;
 use crate::key::root::hb::Hb;
+use crate::kvs::audit::{AuditClass, AuditLog};
+use crate::kvs::cipher::Cipher;
 use crate::kvs::clock::SizedClock;
 #[allow(unused_imports)]
 use crate::kvs::clock::SystemClock;
-use crate::kvs::{LockType, LockType::*, TransactionType, TransactionType::*};
-use crate::sql::{self, statements::DefineUserStatement, Base, Query, Uuid, Value};
+use crate::kvs::keyring::Keyring;
+use crate::kvs::sync;
+#[cfg(feature = "sql2")]
+use crate::kvs::webhook::{self, Delivery};
+#[cfg(feature = "sql2")]
+use crate::kvs::{Key, Val};
+use crate::kvs::{LockType, LockType::*, ScanPage, TransactionType, TransactionType::*};
+use crate::sql::{self, statements::DefineUserStatement, Base, Query, Thing, Uuid, Value};
 use crate::syn;
 use crate::vs::Oracle;
 use channel::{Receiver, Sender};
+#[cfg(feature = "sql2")]
+use futures::{future, stream, StreamExt};
 use futures::{lock::Mutex, Future};
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
+#[cfg(feature = "sql2")]
+use std::sync::atomic;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::instrument;
 use tracing::trace;
+#[cfg(feature = "sql2")]
+use tracing::warn;
+#[cfg(feature = "sql2")]
+use trice::Instant;
 #[cfg(target_arch = "wasm32")]
 use wasmtimer::std::{SystemTime, UNIX_EPOCH};
 
@@ -114,6 +141,29 @@ pub struct Datastore {
 	clock: Arc<SizedClock>,
 	// The index store cache
 	index_stores: IndexStores,
+	// The audit log for authentication and definition events
+	audit_log: Option<Arc<AuditLog>>,
+	// The limits on the signin attempts of each user
+	login_policy: LoginPolicy,
+	// The rules for the passwords of system users
+	password_policy: Arc<PasswordPolicy>,
+	// The keys which encrypt the values of fields
+	keyring: Arc<Keyring>,
+	// The cipher which encrypts the keys and values of the datastore
+	cipher: Option<Arc<Cipher>>,
+	// Whether webhook payloads are currently being delivered
+	delivering: Arc<AtomicBool>,
+}
+
+/// Marks webhook deliveries as finished, even if the delivery run is dropped
+#[cfg(feature = "sql2")]
+struct Delivering<'a>(&'a AtomicBool);
+
+#[cfg(feature = "sql2")]
+impl Drop for Delivering<'_> {
+	fn drop(&mut self) {
+		self.0.store(false, atomic::Ordering::Release);
+	}
 }
 
 /// We always want to be circulating the live query information
@@ -397,6 +447,12 @@ impl Datastore {
 			versionstamp_oracle: Arc::new(Mutex::new(Oracle::systime_counter())),
 			clock,
 			index_stores: IndexStores::default(),
+			audit_log: None,
+			login_policy: LoginPolicy::default(),
+			password_policy: Arc::new(PasswordPolicy::default()),
+			keyring: Arc::new(Keyring::default()),
+			cipher: None,
+			delivering: Arc::new(AtomicBool::new(false)),
 		})
 	}
 
@@ -449,6 +505,52 @@ impl Datastore {
 		self
 	}
 
+	/// Set an audit log for authentication and definition events
+	pub fn with_audit_log(mut self, log: AuditLog) -> Self {
+		self.audit_log = Some(Arc::new(log));
+		self
+	}
+
+	/// Get the audit log, if this class of events is recorded
+	pub(crate) fn audit_log(&self, class: AuditClass) -> Option<&AuditLog> {
+		self.audit_log.as_deref().filter(|log| log.records(class))
+	}
+
+	/// Set the limits on the signin attempts of each user
+	pub fn with_login_policy(mut self, policy: LoginPolicy) -> Self {
+		self.login_policy = policy;
+		self
+	}
+
+	/// Set the rules for the passwords of system users
+	pub fn with_password_policy(mut self, policy: PasswordPolicy) -> Self {
+		self.password_policy = Arc::new(policy);
+		self
+	}
+
+	/// Set the keys which encrypt the values of fields
+	pub fn with_keyring(mut self, keyring: Keyring) -> Self {
+		self.keyring = Arc::new(keyring);
+		self
+	}
+
+	/// Set the cipher which encrypts the keys and values of this Datastore
+	pub fn with_encryption(mut self, cipher: Cipher) -> Self {
+		self.cipher = Some(Arc::new(cipher));
+		self
+	}
+
+	/// Get the limits on the signin attempts of each user
+	pub(crate) fn login_policy(&self) -> &LoginPolicy {
+		&self.login_policy
+	}
+
+	/// Get the rules for the passwords of system users
+	#[allow(dead_code)]
+	pub(crate) fn password_policy(&self) -> &PasswordPolicy {
+		&self.password_policy
+	}
+
 	pub fn index_store(&self) -> &IndexStores {
 		&self.index_stores
 	}
@@ -566,6 +668,8 @@ impl Datastore {
 			}
 		}
 
+		// The live queries which are removed, and whose clients should be told
+		let killed: Vec<Uuid> = filtered.iter().map(|lq| lq.lq).collect();
 		let mut tx = self.transaction(Write, Optimistic).await?;
 		let val = self.remove_archived(&mut tx, filtered).await;
 		let resolve_err = match val {
@@ -588,6 +692,18 @@ impl Datastore {
 			error!("Error bootstrapping sweep phase: {:?}", err);
 			return Err(Error::Tx(format!("Error bootstrapping sweep phase: {:?}", err)));
 		}
+		// Let any listening clients know that their live queries have ended
+		if let Some(channel) = &self.notification_channel {
+			for id in killed {
+				let notification = Notification::killed(
+					id,
+					"The node which was processing this live query is no longer available",
+				);
+				if let Err(e) = channel.0.try_send(notification) {
+					warn!("Unable to send killed live query notification: {:?}", e);
+				}
+			}
+		}
 		Ok(())
 	}
 
@@ -857,6 +973,10 @@ impl Datastore {
 	pub async fn tick_at(&self, ts: u64) -> Result<(), Error> {
 		self.save_timestamp_for_versionstamp(ts).await?;
 		self.garbage_collect_stale_change_feeds(ts).await?;
+		crate::iam::throttle::garbage_collect(self, ts as i64).await?;
+		crate::iam::refresh::garbage_collect(self, ts as i64).await?;
+		#[cfg(feature = "sql2")]
+		self.build_pending_indexes().await?;
 		// TODO Add LQ GC
 		// TODO Add Node GC?
 		Ok(())
@@ -925,6 +1045,247 @@ impl Datastore {
 		Ok(())
 	}
 
+	// build_pending_indexes backfills the indexes which are defined with `CONCURRENTLY`.
+	// Every batch of records is indexed in its own transaction, so writes are never blocked,
+	// and only a limited number of batches are indexed on each tick, so that the other
+	// maintenance tasks are not delayed while a large table is indexed.
+	#[cfg(feature = "sql2")]
+	pub(crate) async fn build_pending_indexes(&self) -> Result<(), Error> {
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let beg = crate::key::root::ib::prefix();
+		let end = crate::key::root::ib::suffix();
+		let pending = tx.scan(beg..end, NON_PAGED_BATCH_SIZE).await?;
+		tx.cancel().await?;
+		let mut batches = *INDEX_BUILD_BATCHES_PER_TICK;
+		for (k, _) in pending {
+			let ib = crate::key::root::ib::Ib::decode(&k)?;
+			while batches > 0 {
+				batches -= 1;
+				if !self.build_index_batch(ib.ns, ib.db, ib.tb, ib.ix).await? {
+					break;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	// build_index_batch indexes the next batch of records, and returns whether there are more to process.
+	#[cfg(feature = "sql2")]
+	async fn build_index_batch(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		ix: &str,
+	) -> Result<bool, Error> {
+		let txn = self.transaction(Write, Optimistic).await?.enclose();
+		let res = self.build_index_batch_impl(&txn, ns, db, tb, ix).await;
+		let mut tx = txn.lock().await;
+		match res {
+			Ok(more) => match tx.commit().await {
+				Ok(_) => Ok(more),
+				Err(e) => {
+					// The batch conflicted with another transaction, so retry on the next tick
+					trace!("Building index {ix} on {ns}:{db}:{tb} will be retried: {e}");
+					Ok(false)
+				}
+			},
+			Err(e @ (Error::Tx(_) | Error::TxFailure)) => {
+				tx.cancel().await?;
+				trace!("Building index {ix} on {ns}:{db}:{tb} will be retried: {e}");
+				Ok(false)
+			}
+			Err(e) => {
+				tx.cancel().await?;
+				// Record the failure, and stop building the index
+				let mut tx = self.transaction(Write, Optimistic).await?;
+				let mut state = BuildState::get(&mut tx, ns, db, tb, ix).await?.unwrap_or_default();
+				state.status = BuildStatus::Failed(e.to_string());
+				state.set(&mut tx, ns, db, tb, ix).await?;
+				tx.del(crate::key::root::ib::new(ns, db, tb, ix)).await?;
+				tx.commit().await?;
+				Ok(false)
+			}
+		}
+	}
+
+	#[cfg(feature = "sql2")]
+	async fn build_index_batch_impl(
+		&self,
+		txn: &crate::dbs::Transaction,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		ix: &str,
+	) -> Result<bool, Error> {
+		let mut run = txn.lock().await;
+		let pending = crate::key::root::ib::new(ns, db, tb, ix);
+		// Check that the index is still waiting to be built
+		let state = match run.get_and_cache_tb_index(ns, db, tb, ix).await {
+			Ok(def) if def.concurrently => BuildState::get(&mut run, ns, db, tb, ix).await?,
+			Ok(_)
+			| Err(Error::IxNotFound {
+				..
+			}) => None,
+			Err(e) => return Err(e),
+		};
+		let mut state = match state {
+			Some(state) if state.status == BuildStatus::Building => state,
+			_ => {
+				run.del(pending).await?;
+				return Ok(false);
+			}
+		};
+		// Fetch the records following the last indexed one
+		let beg = match &state.cursor {
+			Some(id) => {
+				let mut k: Vec<u8> = crate::key::thing::new(ns, db, tb, id).into();
+				k.push(0x00);
+				k
+			}
+			None => crate::key::thing::prefix(ns, db, tb),
+		};
+		let end = crate::key::thing::suffix(ns, db, tb);
+		let batch = run.scan(beg..end, *INDEX_BUILD_BATCH_SIZE).await?;
+		let mut what = Vec::with_capacity(batch.len());
+		for (k, _) in batch.iter() {
+			let key = crate::key::thing::Thing::decode(k)?;
+			what.push(Value::from(sql::Thing::from((key.tb, key.id))));
+		}
+		// Mark the index as ready once all the records are indexed
+		let Some(Value::Thing(last)) = what.last() else {
+			state.status = BuildStatus::Ready;
+			state.set(&mut run, ns, db, tb, ix).await?;
+			run.del(pending).await?;
+			return Ok(false);
+		};
+		state.cursor = Some(last.id.clone());
+		state.processed += what.len() as u64;
+		state.set(&mut run, ns, db, tb, ix).await?;
+		drop(run);
+		// Index the batch, as a synchronous build would
+		let opt = Options::default()
+			.with_id(self.id.0)
+			.with_ns(Some(ns.into()))
+			.with_db(Some(db.into()))
+			.with_auth(Arc::new(Auth::for_root(Role::Owner)))
+			.with_force(true)
+			.with_fields(false)
+			.with_events(false)
+			.with_tables(false);
+		let mut ctx =
+			Context::from_ds(None, self.capabilities.clone(), self.index_stores.clone())?;
+		ctx.add_keyring(self.keyring.clone());
+		let stm = sql::statements::UpdateStatement {
+			what: sql::Values(what),
+			..Default::default()
+		};
+		stm.compute(&ctx, &opt, txn, None).await?;
+		Ok(true)
+	}
+
+	// deliver_webhooks delivers the webhook payloads which are due now.
+	// This is called every TICK_INTERVAL, separately from tick, so that slow endpoints do not hold back other maintenance tasks.
+	#[cfg(feature = "sql2")]
+	pub async fn deliver_webhooks(&self) -> Result<(), Error> {
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).map_err(|e| {
+			Error::Internal(format!("Clock may have gone backwards: {:?}", e.duration()))
+		})?;
+		self.process_webhooks(now.as_secs()).await
+	}
+
+	// process_webhooks delivers the webhook payloads which are due at the given timestamp.
+	// Failed deliveries are retried with a backoff, and are dead-lettered once all retries are exhausted.
+	// Only one run delivers payloads at a time, and no new deliveries are started once the run exceeds its budget.
+	#[cfg(feature = "sql2")]
+	pub async fn process_webhooks(&self, ts: u64) -> Result<(), Error> {
+		// Skip this run if the previous one is still delivering
+		if self.delivering.swap(true, atomic::Ordering::AcqRel) {
+			trace!("Skipping webhook deliveries, as a previous run is still in progress");
+			return Ok(());
+		}
+		let _running = Delivering(&self.delivering);
+		// Fetch the payloads which are due
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let beg = crate::key::root::wq::prefix();
+		let end = crate::key::root::wq::suffix(ts);
+		let due = tx.scan(beg..end, *WEBHOOK_BATCH_SIZE).await;
+		tx.cancel().await?;
+		// Deliver the payloads concurrently, within the time budget
+		let started = Instant::now();
+		let budget = Duration::from_secs(*WEBHOOK_DELIVERY_BUDGET);
+		stream::iter(due?)
+			.take_while(|_| future::ready(started.elapsed() < budget))
+			.map(|(k, v)| self.process_webhook(ts, k, v))
+			.buffer_unordered(*WEBHOOK_CONCURRENCY)
+			.for_each(|res| {
+				if let Err(e) = res {
+					warn!("Processing webhook delivery failed: {e}");
+				}
+				future::ready(())
+			})
+			.await;
+		Ok(())
+	}
+
+	// process_webhook delivers a single webhook payload, and then removes, requeues, or dead-letters it.
+	#[cfg(feature = "sql2")]
+	async fn process_webhook(&self, ts: u64, k: Key, v: Val) -> Result<(), Error> {
+		let key = crate::key::root::wq::Wq::decode(&k)?;
+		let mut msg = Delivery::from(v);
+		// Fetch the current webhook definition
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let wh = tx.get_tb_webhook(&msg.ns, &msg.db, &msg.tb, &msg.wh).await;
+		tx.cancel().await?;
+		let wh = match wh {
+			Ok(wh) => Some(wh),
+			// The webhook has since been removed
+			Err(Error::WhNotFound {
+				..
+			}) => None,
+			Err(e) => return Err(e),
+		};
+		// Attempt to deliver the payload, without holding a transaction open
+		let res = match &wh {
+			Some(wh) => webhook::deliver(&self.capabilities, wh, key.id, &msg.payload).await,
+			None => Ok(()),
+		};
+		let mut tx = self.transaction(Write, Optimistic).await?;
+		let res = async {
+			tx.del(k).await?;
+			if let (Some(wh), Err(e)) = (wh, res) {
+				trace!(
+					"Delivering webhook {} on {}:{}:{} failed: {e}",
+					msg.wh,
+					msg.ns,
+					msg.db,
+					msg.tb
+				);
+				msg.attempts += 1;
+				msg.error = Some(e.to_string());
+				if msg.attempts > wh.retry {
+					let dead =
+						crate::key::table::wd::new(&msg.ns, &msg.db, &msg.tb, &msg.wh, key.id);
+					tx.set(dead, &msg).await?;
+				} else {
+					let next = ts.saturating_add(webhook::backoff(msg.attempts));
+					tx.set(crate::key::root::wq::new(next, key.id), &msg).await?;
+				}
+			}
+			Ok::<(), Error>(())
+		}
+		.await;
+		if let Err(e) = res {
+			tx.cancel().await?;
+			return Err(e);
+		}
+		if let Err(e) = tx.commit().await {
+			// Another node may have processed the same delivery
+			trace!("Processing webhook delivery {} will be retried: {e}", key.id);
+		}
+		Ok(())
+	}
+
 	// Creates a heartbeat entry for the member indicating to the cluster
 	// that the node is alive.
 	// This is the preferred way of creating heartbeats inside the database, so try to use this.
@@ -1027,8 +1388,11 @@ impl Datastore {
 			inner,
 			cache: super::cache::Cache::default(),
 			cf: cf::Writer::new(),
+			#[cfg(feature = "sql2")]
+			uniques: Vec::new(),
 			vso: self.versionstamp_oracle.clone(),
 			clock: self.clock.clone(),
+			cipher: self.cipher.clone(),
 		})
 	}
 
@@ -1101,7 +1465,7 @@ impl Datastore {
 			.with_ns(sess.ns())
 			.with_db(sess.db())
 			.with_live(sess.live())
-			.with_auth(sess.au.clone())
+			.with_auth(self.session_auth(sess).await?)
 			.with_strict(self.strict)
 			.with_auth_enabled(self.auth_enabled);
 		// Create a new query executor
@@ -1116,6 +1480,9 @@ impl Datastore {
 		if let Some(channel) = &self.notification_channel {
 			ctx.add_notifications(Some(&channel.0));
 		}
+		// Set the password rules
+		ctx.add_password_policy(self.password_policy.clone());
+		ctx.add_keyring(self.keyring.clone());
 		// Start an execution context
 		let ctx = sess.context(ctx);
 		// Store the query variables
@@ -1165,7 +1532,7 @@ impl Datastore {
 			.with_ns(sess.ns())
 			.with_db(sess.db())
 			.with_live(sess.live())
-			.with_auth(sess.au.clone())
+			.with_auth(self.session_auth(sess).await?)
 			.with_strict(self.strict)
 			.with_auth_enabled(self.auth_enabled);
 		// Create a default context
@@ -1180,6 +1547,9 @@ impl Datastore {
 		if let Some(channel) = &self.notification_channel {
 			ctx.add_notifications(Some(&channel.0));
 		}
+		// Set the password rules
+		ctx.add_password_policy(self.password_policy.clone());
+		ctx.add_keyring(self.keyring.clone());
 		// Start an execution context
 		let ctx = sess.context(ctx);
 		// Store the query variables
@@ -1234,7 +1604,7 @@ impl Datastore {
 			.with_ns(sess.ns())
 			.with_db(sess.db())
 			.with_live(sess.live())
-			.with_auth(sess.au.clone())
+			.with_auth(self.session_auth(sess).await?)
 			.with_strict(self.strict)
 			.with_auth_enabled(self.auth_enabled);
 		// Create a default context
@@ -1249,6 +1619,9 @@ impl Datastore {
 		if let Some(channel) = &self.notification_channel {
 			ctx.add_notifications(Some(&channel.0));
 		}
+		// Set the password rules
+		ctx.add_password_policy(self.password_policy.clone());
+		ctx.add_keyring(self.keyring.clone());
 		// Start an execution context
 		let ctx = sess.context(ctx);
 		// Store the query variables
@@ -1319,6 +1692,152 @@ impl Datastore {
 		})
 	}
 
+	/// Copies every key and value of this datastore into another datastore,
+	/// returning the number of keys which were copied. This encrypts an
+	/// existing datastore when the other datastore is encrypted, or changes
+	/// the key of an encrypted datastore. Nothing should write to either
+	/// datastore while it is being copied.
+	#[instrument(level = "debug", skip(self, into))]
+	pub async fn copy_into(&self, into: &Datastore) -> Result<u64, Error> {
+		let mut count = 0;
+		let mut next_page = Some(ScanPage::from(vec![]..vec![0xff]));
+		while let Some(page) = next_page {
+			// Read the next batch of keys
+			let mut src = self.transaction(Read, Optimistic).await?;
+			let res = src.scan_paged(page, 1000).await;
+			src.cancel().await?;
+			let res = res?;
+			next_page = res.next_page;
+			// Write the batch of keys into the other datastore
+			let mut dst = into.transaction(Write, Optimistic).await?;
+			for (k, v) in res.values {
+				if let Err(e) = dst.set(k, v).await {
+					dst.cancel().await?;
+					return Err(e);
+				}
+				count += 1;
+			}
+			dst.commit().await?;
+		}
+		Ok(count)
+	}
+
+	/// Reads the changes made to the records of a database since a versionstamp,
+	/// which a replica of the database pulls to catch up with it
+	#[instrument(level = "debug", skip(self, sess))]
+	pub async fn sync_pull(
+		&self,
+		sess: &Session,
+		since: u64,
+		limit: Option<u32>,
+	) -> Result<sync::Changes, Error> {
+		// Check if anonymous actors can pull changes when auth is enabled
+		if self.auth_enabled && sess.au.is_anon() && !self.capabilities.allows_guest_access() {
+			return Err(IamError::NotAllowed {
+				actor: "anonymous".to_string(),
+				action: "pull".to_string(),
+				resource: "changes".to_string(),
+			}
+			.into());
+		}
+		// Ensure a NS and DB are set
+		let _ = crate::iam::check::check_ns_db(sess)?;
+		// Create a new query options
+		let opt = Options::default()
+			.with_id(self.id.0)
+			.with_ns(sess.ns())
+			.with_db(sess.db())
+			.with_auth(self.session_auth(sess).await?)
+			.with_strict(self.strict)
+			.with_auth_enabled(self.auth_enabled);
+		// Start a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?.enclose();
+		// Read the changes
+		let res = sync::pull(&opt, &txn, since, limit).await;
+		// Cancel the transaction
+		txn.lock().await.cancel().await?;
+		// Return result
+		res
+	}
+
+	/// Applies a batch of changes which a replica of a database pushes,
+	/// returning the records with conflicting changes
+	#[instrument(level = "debug", skip_all)]
+	pub async fn sync_push(&self, sess: &Session, push: sync::Push) -> Result<Vec<Thing>, Error> {
+		// Check if anonymous actors can push changes when auth is enabled
+		if self.auth_enabled && sess.au.is_anon() && !self.capabilities.allows_guest_access() {
+			return Err(IamError::NotAllowed {
+				actor: "anonymous".to_string(),
+				action: "push".to_string(),
+				resource: "changes".to_string(),
+			}
+			.into());
+		}
+		// Ensure a NS and DB are set
+		let _ = crate::iam::check::check_ns_db(sess)?;
+		// Create a new query options
+		let opt = Options::default()
+			.with_id(self.id.0)
+			.with_ns(sess.ns())
+			.with_db(sess.db())
+			.with_live(sess.live())
+			.with_auth(self.session_auth(sess).await?)
+			.with_strict(self.strict)
+			.with_auth_enabled(self.auth_enabled);
+		// Create a default context
+		let mut ctx = Context::default();
+		// Set context capabilities
+		ctx.add_capabilities(self.capabilities.clone());
+		// Set the global query timeout
+		if let Some(timeout) = self.query_timeout {
+			ctx.add_timeout(timeout)?;
+		}
+		// Setup the notification channel
+		if let Some(channel) = &self.notification_channel {
+			ctx.add_notifications(Some(&channel.0));
+		}
+		// Set the password rules
+		ctx.add_password_policy(self.password_policy.clone());
+		ctx.add_keyring(self.keyring.clone());
+		// Start an execution context
+		let ctx = sess.context(ctx);
+		// Start a new transaction
+		let txn = self.transaction(Write, Optimistic).await?.enclose();
+		// Apply the changes
+		let replicate = push.replicate;
+		let res = sync::push(&ctx, &opt, &txn, push).await;
+		// Store any data
+		let mut run = txn.lock().await;
+		// Record the changes, unless they come from another datastore
+		let res = match (res, replicate) {
+			(Ok(v), false) => run.complete_changes(false).await.map(|_| v),
+			(res, _) => res,
+		};
+		match res.is_ok() {
+			// If the changes were applied, then commit
+			true => run.commit().await?,
+			// Cancel if the changes could not be applied
+			false => run.cancel().await?,
+		};
+		// Return result
+		res
+	}
+
+	/// Returns the authentication of a session, with the privileges granted by its custom roles
+	async fn session_auth(&self, sess: &Session) -> Result<Arc<Auth>, Error> {
+		// Most sessions only have predefined roles
+		if sess.au.roles().iter().all(|r| r.custom().is_none()) {
+			return Ok(sess.au.clone());
+		}
+		// Create a new readonly transaction
+		let mut txn = self.transaction(Read, Optimistic).await?;
+		// Lookup the privileges of the custom roles
+		let res = crate::iam::roles::resolve(&mut txn, &sess.au).await;
+		// Cancel the transaction
+		txn.cancel().await?;
+		res
+	}
+
 	/// Checks the required permissions level for this session
 	#[instrument(level = "debug", skip(self, sess))]
 	pub fn check(&self, sess: &Session, action: Action, resource: Resource) -> Result<(), Error> {
@@ -0,0 +1,439 @@
+//! Encrypts the values of fields with the named keys of a keyring.
+//!
+//! Each key of a keyring has one or more versions, and values are always
+//! encrypted with the latest version of a key. An encrypted value is stored
+//! as bytes, which start with a header, and the version of the key which
+//! encrypted the value, so that values which were encrypted with an older
+//! version of a key can still be decrypted after the key has been rotated.
+//!
+//! Values are encrypted with AES-256-GCM. A randomized encryption uses a
+//! random nonce, so that encrypting the same value twice gives different
+//! results. A deterministic encryption derives the nonce from the value
+//! itself, so that equal values can be found in an index without being
+//! decrypted, at the cost of revealing which records have equal values.
+//!
+//! The namespace, database, table, and field of a value are authenticated
+//! along with the value, as is the record of a randomly encrypted value, so
+//! that an encrypted value can not be copied into another field or record.
+//! A value is only ever treated as encrypted once it has been authenticated,
+//! so bytes which merely look like an encrypted value are encrypted too.
+//!
+//! A keyring is loaded from a JSON keyfile, which maps the name of each key
+//! to its versions, and each version to a base64 encoded 256-bit key:
+//!
+//! ```json
+//! { "pii": { "1": "Q2hhbmdlIG1lIHRvIGEgcmFuZG9tIDMyIGJ5dGUga2V5IQ==" } }
+//! ```
+use crate::err::Error;
+use crate::sql::{statements::DefineFieldStatement, Bytes, Idiom, Thing, Value};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64_lib::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+use std::path::Path;
+
+/// The bytes which start every encrypted value
+const HEADER: &[u8; 4] = b"senc";
+
+/// The length of the nonce of an encrypted value
+const NONCE_LEN: usize = 12;
+
+/// The length of the header, the mode, the key version, and the nonce of an encrypted value
+const PREFIX_LEN: usize = HEADER.len() + 1 + 4 + NONCE_LEN;
+
+/// The field of a record in which a value is encrypted
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Field<'a> {
+	ns: &'a str,
+	db: &'a str,
+	tb: &'a str,
+	fd: &'a Idiom,
+	/// The record, which deterministically encrypted values are not bound to
+	id: Option<&'a Thing>,
+}
+
+#[cfg_attr(not(feature = "sql2"), allow(dead_code))]
+impl<'a> Field<'a> {
+	pub(crate) fn new(
+		ns: &'a str,
+		db: &'a str,
+		fd: &'a DefineFieldStatement,
+		id: Option<&'a Thing>,
+	) -> Self {
+		Self {
+			ns,
+			db,
+			tb: &fd.what,
+			fd: &fd.name,
+			id,
+		}
+	}
+
+	/// The additional data which is authenticated along with a value
+	fn aad(&self, name: &str, deterministic: bool) -> Result<Vec<u8>, Error> {
+		let id = match deterministic {
+			true => None,
+			false => Some(self.id.ok_or(Error::Unreachable("Field::aad"))?.to_string()),
+		};
+		let fd = self.fd.to_string();
+		let mut aad = vec![deterministic as u8];
+		for part in [name, self.ns, self.db, self.tb, &fd].into_iter().chain(id.as_deref()) {
+			aad.extend_from_slice(&(part.len() as u32).to_be_bytes());
+			aad.extend_from_slice(part.as_bytes());
+		}
+		Ok(aad)
+	}
+}
+
+/// The parts of an encrypted value
+struct Envelope<'a> {
+	deterministic: bool,
+	version: u32,
+	nonce: &'a [u8],
+	data: &'a [u8],
+}
+
+/// The named and versioned keys which encrypt the values of fields
+#[derive(Clone, Default)]
+pub struct Keyring {
+	keys: BTreeMap<String, BTreeMap<u32, [u8; 32]>>,
+}
+
+impl Debug for Keyring {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		// Never print the keys themselves
+		let keys: BTreeMap<_, Vec<_>> =
+			self.keys.iter().map(|(k, v)| (k, v.keys().collect())).collect();
+		f.debug_struct("Keyring").field("keys", &keys).finish()
+	}
+}
+
+// Fields are only encrypted with the `sql2` feature
+#[cfg_attr(not(feature = "sql2"), allow(dead_code))]
+impl Keyring {
+	/// Creates an empty keyring
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a version of a key to the keyring
+	pub fn with_key(mut self, name: &str, version: u32, key: [u8; 32]) -> Self {
+		self.keys.entry(name.to_owned()).or_default().insert(version, key);
+		self
+	}
+
+	/// Loads a keyring from a JSON keyfile
+	pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+		let path = path.as_ref();
+		let json = std::fs::read_to_string(path)
+			.map_err(|e| Error::InvalidKeyring(format!("{}: {e}", path.display())))?;
+		json.parse()
+	}
+
+	/// Checks if the keyring has no keys
+	pub fn is_empty(&self) -> bool {
+		self.keys.is_empty()
+	}
+
+	/// Checks that a key exists in the keyring
+	pub(crate) fn check(&self, name: &str) -> Result<(), Error> {
+		self.current(name).map(|_| ())
+	}
+
+	/// Gets the latest version of a key
+	fn current(&self, name: &str) -> Result<(u32, &[u8; 32]), Error> {
+		self.keys.get(name).and_then(|v| v.last_key_value()).map(|(v, k)| (*v, k)).ok_or_else(
+			|| Error::EncryptionKeyNotFound {
+				value: name.to_owned(),
+			},
+		)
+	}
+
+	/// Encrypts a value with the latest version of a key. A value which is
+	/// already encrypted for this field, such as an imported value, is
+	/// returned unchanged.
+	pub(crate) fn encrypt(
+		&self,
+		name: &str,
+		field: &Field,
+		val: &Value,
+		deterministic: bool,
+	) -> Result<Value, Error> {
+		if let Some(env) = parts(val) {
+			if env.deterministic == deterministic && self.unseal(name, field, &env).is_ok() {
+				return Ok(val.clone());
+			}
+		}
+		let (version, key) = self.current(name)?;
+		seal(name, version, key, field, val, deterministic)
+	}
+
+	/// Encrypts a value deterministically, so that it can be looked up in an index.
+	/// While older versions of the key remain, the same value could have been
+	/// encrypted with any of them, so the value can not be looked up.
+	pub(crate) fn encrypt_lookup(
+		&self,
+		name: &str,
+		field: &Field,
+		val: &Value,
+	) -> Result<Option<Value>, Error> {
+		match self.keys.get(name).map(BTreeMap::len) {
+			Some(1) => self.encrypt(name, field, val, true).map(Some),
+			Some(_) => Ok(None),
+			None => Err(Error::EncryptionKeyNotFound {
+				value: name.to_owned(),
+			}),
+		}
+	}
+
+	/// Decrypts a value which was encrypted for this field with any version of a key.
+	/// Values which are not encrypted are returned unchanged.
+	pub(crate) fn decrypt(&self, name: &str, field: &Field, val: &Value) -> Result<Value, Error> {
+		match parts(val) {
+			Some(env) => self.unseal(name, field, &env).map(Value::from),
+			None => Ok(val.clone()),
+		}
+	}
+
+	/// Authenticates and decrypts the parts of an encrypted value
+	fn unseal(&self, name: &str, field: &Field, env: &Envelope) -> Result<Vec<u8>, Error> {
+		let key = self.keys.get(name).and_then(|v| v.get(&env.version)).ok_or_else(|| {
+			Error::EncryptionKeyNotFound {
+				value: format!("{name}@{}", env.version),
+			}
+		})?;
+		let aad = field.aad(name, env.deterministic)?;
+		let payload = Payload {
+			msg: env.data,
+			aad: &aad,
+		};
+		Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
+			.decrypt(Nonce::from_slice(env.nonce), payload)
+			.map_err(|_| Error::DecryptionFailed {
+				value: name.to_owned(),
+			})
+	}
+}
+
+impl std::str::FromStr for Keyring {
+	type Err = Error;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let file: BTreeMap<String, BTreeMap<String, String>> =
+			serde_json::from_str(s).map_err(|e| Error::InvalidKeyring(e.to_string()))?;
+		let mut keyring = Keyring::new();
+		for (name, versions) in file {
+			for (version, key) in versions {
+				let version = version.parse::<u32>().map_err(|_| {
+					Error::InvalidKeyring(format!("invalid version '{version}' of key '{name}'"))
+				})?;
+				let key = STANDARD
+					.decode(key)
+					.ok()
+					.and_then(|v| <[u8; 32]>::try_from(v).ok())
+					.ok_or_else(|| {
+						Error::InvalidKeyring(format!(
+							"version {version} of key '{name}' is not a base64 encoded 256-bit key"
+						))
+					})?;
+				keyring = keyring.with_key(&name, version, key);
+			}
+		}
+		Ok(keyring)
+	}
+}
+
+/// Encrypts a value with a version of a key
+fn seal(
+	name: &str,
+	version: u32,
+	key: &[u8; 32],
+	field: &Field,
+	val: &Value,
+	deterministic: bool,
+) -> Result<Value, Error> {
+	let data: Vec<u8> = val.into();
+	let aad = field.aad(name, deterministic)?;
+	let mut nonce = [0u8; NONCE_LEN];
+	match deterministic {
+		true => {
+			// Derive the nonce from the field and the value, with a key derived from the encryption key
+			let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key)
+				.map_err(|_| Error::Unreachable("Keyring::seal"))?;
+			mac.update(b"nonce");
+			let sub = mac.finalize().into_bytes();
+			let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&sub)
+				.map_err(|_| Error::Unreachable("Keyring::seal"))?;
+			mac.update(&aad);
+			mac.update(&data);
+			nonce.copy_from_slice(&mac.finalize().into_bytes()[..NONCE_LEN]);
+		}
+		false => rand::thread_rng().fill_bytes(&mut nonce),
+	}
+	let payload = Payload {
+		msg: &data,
+		aad: &aad,
+	};
+	let data = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
+		.encrypt(Nonce::from_slice(&nonce), payload)
+		.map_err(|_| Error::Unreachable("Keyring::seal"))?;
+	let mut out = Vec::with_capacity(PREFIX_LEN + data.len());
+	out.extend_from_slice(HEADER);
+	out.push(deterministic as u8);
+	out.extend_from_slice(&version.to_be_bytes());
+	out.extend_from_slice(&nonce);
+	out.extend_from_slice(&data);
+	Ok(Value::Bytes(Bytes::from(out)))
+}
+
+/// Splits a value which looks like an encrypted value into its parts,
+/// which are only known to be an encrypted value once authenticated
+fn parts(val: &Value) -> Option<Envelope<'_>> {
+	match val {
+		Value::Bytes(v) if v.len() > PREFIX_LEN && v.starts_with(HEADER) => Some(Envelope {
+			deterministic: match v[4] {
+				0 => false,
+				1 => true,
+				_ => return None,
+			},
+			version: u32::from_be_bytes(v[5..9].try_into().ok()?),
+			nonce: &v[9..PREFIX_LEN],
+			data: &v[PREFIX_LEN..],
+		}),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::syn::Parse;
+
+	fn field(name: &str) -> DefineFieldStatement {
+		DefineFieldStatement {
+			name: Idiom::parse(name),
+			what: "person".into(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn encrypt_and_decrypt() {
+		let keyring = Keyring::new().with_key("pii", 1, [1; 32]);
+		let (fd, id) = (field("ssn"), Thing::from(("person", "tobie")));
+		let f = Field::new("test", "test", &fd, Some(&id));
+		let val = Value::parse("{ ssn: '123-45-6789' }");
+		let enc = keyring.encrypt("pii", &f, &val, false).unwrap();
+		assert!(enc.is_bytes());
+		assert_ne!(enc, keyring.encrypt("pii", &f, &val, false).unwrap());
+		assert_eq!(keyring.decrypt("pii", &f, &enc).unwrap(), val);
+		// An encrypted value is stored unchanged
+		assert_eq!(keyring.encrypt("pii", &f, &enc, false).unwrap(), enc);
+		// A value can only be decrypted with the key which encrypted it
+		let other = Keyring::new().with_key("pii", 1, [2; 32]);
+		assert!(matches!(other.decrypt("pii", &f, &enc), Err(Error::DecryptionFailed { .. })));
+		assert!(matches!(
+			keyring.decrypt("ops", &f, &enc),
+			Err(Error::EncryptionKeyNotFound { .. })
+		));
+		// Values which are not encrypted are not changed
+		assert_eq!(keyring.decrypt("pii", &f, &val).unwrap(), val);
+	}
+
+	#[test]
+	fn encrypt_bound_to_field() {
+		let keyring = Keyring::new().with_key("pii", 1, [1; 32]);
+		let (ssn, tax) = (field("ssn"), field("tax"));
+		let (tobie, jaime) = (Thing::from(("person", "tobie")), Thing::from(("person", "jaime")));
+		let f = Field::new("test", "test", &ssn, Some(&tobie));
+		let enc = keyring.encrypt("pii", &f, &Value::from("secret"), false).unwrap();
+		// A value can not be copied into another record, field, or database
+		for other in [
+			Field::new("test", "test", &ssn, Some(&jaime)),
+			Field::new("test", "test", &tax, Some(&tobie)),
+			Field::new("test", "other", &ssn, Some(&tobie)),
+		] {
+			assert!(matches!(
+				keyring.decrypt("pii", &other, &enc),
+				Err(Error::DecryptionFailed { .. })
+			));
+			// So it is encrypted again when it is stored
+			let tmp = keyring.encrypt("pii", &other, &enc, false).unwrap();
+			assert_ne!(tmp, enc);
+			assert_eq!(keyring.decrypt("pii", &other, &tmp).unwrap(), enc);
+		}
+		// A deterministic value is not bound to a record
+		let enc = keyring.encrypt("pii", &f, &Value::from("secret"), true).unwrap();
+		let other = Field::new("test", "test", &ssn, Some(&jaime));
+		assert_eq!(keyring.decrypt("pii", &other, &enc).unwrap(), Value::from("secret"));
+		let other = Field::new("test", "test", &ssn, None);
+		assert_eq!(
+			keyring.encrypt_lookup("pii", &other, &Value::from("secret")).unwrap(),
+			Some(enc)
+		);
+	}
+
+	#[test]
+	fn encrypt_bytes_like_encrypted_value() {
+		let keyring = Keyring::new().with_key("pii", 1, [1; 32]);
+		let (fd, id) = (field("ssn"), Thing::from(("person", "tobie")));
+		let f = Field::new("test", "test", &fd, Some(&id));
+		let mut val = b"senc".to_vec();
+		val.extend_from_slice(&[0; 32]);
+		let val = Value::Bytes(Bytes::from(val));
+		let enc = keyring.encrypt("pii", &f, &val, false).unwrap();
+		assert_ne!(enc, val);
+		assert_eq!(keyring.decrypt("pii", &f, &enc).unwrap(), val);
+	}
+
+	#[test]
+	fn encrypt_deterministic() {
+		let keyring = Keyring::new().with_key("pii", 1, [1; 32]);
+		let fd = field("ssn");
+		let f = Field::new("test", "test", &fd, None);
+		let val = Value::from("123-45-6789");
+		let enc = keyring.encrypt("pii", &f, &val, true).unwrap();
+		assert_eq!(enc, keyring.encrypt("pii", &f, &val, true).unwrap());
+		assert_ne!(enc, keyring.encrypt("pii", &f, &Value::from("987-65-4321"), true).unwrap());
+		assert_eq!(keyring.decrypt("pii", &f, &enc).unwrap(), val);
+		// Equal values in different fields are encrypted differently
+		let fd = field("tax");
+		let other = Field::new("test", "test", &fd, None);
+		assert_ne!(enc, keyring.encrypt("pii", &other, &val, true).unwrap());
+	}
+
+	#[test]
+	fn rotate_key() {
+		let fd = field("ssn");
+		let f = Field::new("test", "test", &fd, None);
+		let old = Keyring::new().with_key("pii", 1, [1; 32]);
+		let enc = old.encrypt("pii", &f, &Value::from("secret"), true).unwrap();
+		let new = old.clone().with_key("pii", 2, [2; 32]);
+		assert_eq!(new.decrypt("pii", &f, &enc).unwrap(), Value::from("secret"));
+		assert_eq!(
+			old.encrypt_lookup("pii", &f, &Value::from("secret")).unwrap(),
+			Some(enc.clone())
+		);
+		assert_eq!(new.encrypt_lookup("pii", &f, &Value::from("secret")).unwrap(), None);
+		assert_ne!(new.encrypt("pii", &f, &Value::from("secret"), true).unwrap(), enc);
+	}
+
+	#[test]
+	fn parse_keyfile() {
+		let key = STANDARD.encode([7u8; 32]);
+		let keyring: Keyring =
+			format!(r#"{{ "pii": {{ "1": "{key}", "3": "{key}" }} }}"#).parse().unwrap();
+		assert_eq!(keyring.current("pii").unwrap().0, 3);
+		assert!(matches!(
+			r#"{ "pii": { "1": "c2hvcnQ=" } }"#.parse::<Keyring>(),
+			Err(Error::InvalidKeyring(_))
+		));
+		assert!(matches!(
+			format!(r#"{{ "pii": {{ "latest": "{key}" }} }}"#).parse::<Keyring>(),
+			Err(Error::InvalidKeyring(_))
+		));
+	}
+}
@@ -13,16 +13,21 @@
 //! - `tikv`: [TiKV](https://github.com/tikv/tikv) a distributed, and transactional key-value database
 //! - `dynamodb`: [DynamoDb](https://aws.amazon.com/dynamodb/) a Serverless, NoSQL, fully managed database with single-digit millisecond performance at any scale
 //! - `mem`: in-memory database
+pub mod audit;
 mod cache;
+pub mod cipher;
 mod ds;
 mod fdb;
 mod indxdb;
+pub mod keyring;
 mod kv;
 mod mem;
 mod rocksdb;
 mod speedb;
 mod tikv;
 mod tx;
+#[cfg(feature = "sql2")]
+pub(crate) mod webhook;
 mod dynamodb;
 
 mod clock;
@@ -41,3 +46,4 @@ mod tests;
 pub use self::ds::*;
 pub use self::kv::*;
 pub use self::tx::*;
+pub use crate::cf::sync;
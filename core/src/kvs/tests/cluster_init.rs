@@ -91,6 +91,8 @@ async fn expired_nodes_get_live_queries_archived() {
 		what: Table(sql::Table::from(table)),
 		cond: None,
 		fetch: None,
+		#[cfg(feature = "sql2")]
+		before: false,
 		archived: Some(crate::sql::uuid::Uuid::from(old_node)),
 		session: Some(Value::None),
 		auth: Some(Auth::for_root(Role::Owner)),
@@ -173,6 +175,8 @@ async fn single_live_queries_are_garbage_collected() {
 		what: Table(sql::Table::from(table)),
 		cond: None,
 		fetch: None,
+		#[cfg(feature = "sql2")]
+		before: false,
 		archived: None,
 		session: Some(Value::None),
 		auth: Some(Auth::for_root(Role::Owner)),
@@ -190,6 +194,8 @@ async fn single_live_queries_are_garbage_collected() {
 		what: Table(sql::Table::from(table)),
 		cond: None,
 		fetch: None,
+		#[cfg(feature = "sql2")]
+		before: false,
 		archived: None,
 		session: Some(Value::None),
 		auth: Some(Auth::for_root(Role::Owner)),
@@ -260,6 +266,8 @@ async fn bootstrap_does_not_error_on_missing_live_queries() {
 		what: Table(sql::Table::from(table)),
 		cond: None,
 		fetch: None,
+		#[cfg(feature = "sql2")]
+		before: false,
 		archived: None,
 		session: Some(Value::None),
 		auth: Some(Auth::for_root(Role::Owner)),
@@ -63,6 +63,52 @@ mod mem {
 	include!("tbnt.rs");
 }
 
+#[cfg(feature = "kv-mem")]
+mod encrypted {
+
+	use crate::kvs::cipher::Cipher;
+	use crate::kvs::tests::{ClockType, Kvs};
+	use crate::kvs::Datastore;
+	use crate::kvs::LockType;
+	use crate::kvs::Transaction;
+	use crate::kvs::TransactionType;
+	use serial_test::serial;
+
+	async fn new_ds(node_id: Uuid, clock_override: ClockType) -> (Datastore, Kvs) {
+		(
+			Datastore::new_full("memory", Some(clock_override))
+				.await
+				.unwrap()
+				.with_node_id(crate::sql::Uuid::from(node_id))
+				.with_encryption(Cipher::new([1; 32]).with_key_encryption(true)),
+			Kvs::Mem,
+		)
+	}
+
+	async fn new_tx(write: TransactionType, lock: LockType) -> Transaction {
+		// Shared node id for one-off transactions
+		// We should delete this, node IDs should be known.
+		let new_tx_uuid = Uuid::parse_str("0d1f3c1e-5e0a-4c7b-9d4e-7c2f2a6b8e51").unwrap();
+		let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+		new_ds(new_tx_uuid, clock).await.0.transaction(write, lock).await.unwrap()
+	}
+
+	include!("cluster_init.rs");
+	include!("hb.rs");
+	include!("helper.rs");
+	include!("lq.rs");
+	include!("nq.rs");
+	include!("raw.rs");
+	include!("snapshot.rs");
+	include!("tb.rs");
+	include!("multireader.rs");
+	include!("timestamp_to_versionstamp.rs");
+	include!("nd.rs");
+	include!("ndlq.rs");
+	include!("tblq.rs");
+	include!("tbnt.rs");
+}
+
 #[cfg(feature = "kv-rocksdb")]
 mod rocksdb {
 
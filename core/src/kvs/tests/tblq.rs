@@ -26,6 +26,8 @@ async fn write_scan_tblq() {
 			what: Default::default(),
 			cond: None,
 			fetch: None,
+			#[cfg(feature = "sql2")]
+			before: false,
 			archived: None,
 			session: Some(Value::None),
 			auth: None,
@@ -1,3 +1,4 @@
+use super::cipher::Cipher;
 use super::kv::Add;
 use super::kv::Convert;
 use super::Key;
@@ -27,18 +28,28 @@ use channel::Sender;
 use futures::lock::Mutex;
 use sql::permission::Permissions;
 use sql::statements::DefineAnalyzerStatement;
+#[cfg(feature = "sql2")]
+use sql::statements::DefineConsumerStatement;
 use sql::statements::DefineDatabaseStatement;
 use sql::statements::DefineEventStatement;
 use sql::statements::DefineFieldStatement;
 use sql::statements::DefineFunctionStatement;
 use sql::statements::DefineIndexStatement;
+#[cfg(feature = "sql2")]
+use sql::statements::DefineKeyStatement;
 use sql::statements::DefineModelStatement;
 use sql::statements::DefineNamespaceStatement;
 use sql::statements::DefineParamStatement;
+#[cfg(feature = "sql2")]
+use sql::statements::DefinePolicyStatement;
+#[cfg(feature = "sql2")]
+use sql::statements::DefineRoleStatement;
 use sql::statements::DefineScopeStatement;
 use sql::statements::DefineTableStatement;
 use sql::statements::DefineTokenStatement;
 use sql::statements::DefineUserStatement;
+#[cfg(feature = "sql2")]
+use sql::statements::DefineWebhookStatement;
 use sql::statements::LiveStatement;
 use std::borrow::Cow;
 use std::fmt;
@@ -78,14 +89,31 @@ where
 	pub values: Vec<(Key, Val)>,
 }
 
+/// A uniqueness check on a deferred unique index, which runs when the transaction is committed.
+#[cfg(feature = "sql2")]
+pub(super) struct DeferredUnique {
+	/// The index entry of the record
+	key: Key,
+	/// The key which records the record holding the indexed value
+	claim: Key,
+	/// The range of the index entries sharing the indexed value
+	range: Range<Key>,
+	index: String,
+	value: String,
+	rid: Thing,
+}
+
 /// A set of undoable updates and requests against a dataset.
 #[allow(dead_code)]
 pub struct Transaction {
 	pub(super) inner: Inner,
 	pub(super) cache: Cache,
 	pub(super) cf: cf::Writer,
+	#[cfg(feature = "sql2")]
+	pub(super) uniques: Vec<DeferredUnique>,
 	pub(super) vso: Arc<Mutex<Oracle>>,
 	pub(super) clock: Arc<SizedClock>,
+	pub(super) cipher: Option<Arc<Cipher>>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -280,6 +308,12 @@ impl Transaction {
 	pub async fn commit(&mut self) -> Result<(), Error> {
 		#[cfg(debug_assertions)]
 		trace!("Commit");
+		// Check the deferred unique indexes
+		#[cfg(feature = "sql2")]
+		if let Err(e) = self.check_deferred_uniques().await {
+			self.cancel().await?;
+			return Err(e);
+		}
 		match self {
 			#[cfg(feature = "kv-dynamodb")]
 			Transaction {
@@ -329,6 +363,7 @@ impl Transaction {
 	{
 		#[cfg(debug_assertions)]
 		trace!("Del {:?}", key);
+		let key = self.encrypt_key(key);
 		match self {
 			#[cfg(feature = "kv-dynamodb")]
 			Transaction {
@@ -378,6 +413,7 @@ impl Transaction {
 	{
 		#[cfg(debug_assertions)]
 		trace!("Exi {:?}", key);
+		let key = self.encrypt_key(key);
 		match self {
 			#[cfg(feature = "kv-dynamodb")]
 			Transaction {
@@ -420,13 +456,20 @@ impl Transaction {
 	}
 
 	/// Fetch a key from the datastore.
-	#[allow(unused_variables)]
 	pub async fn get<K>(&mut self, key: K) -> Result<Option<Val>, Error>
 	where
 		K: Into<Key> + Debug,
 	{
 		#[cfg(debug_assertions)]
 		trace!("Get {:?}", key);
+		let key: Key = key.into();
+		let val = self._get(self.encrypt_key(key.clone())).await?;
+		self.decrypt_val(&key, val)
+	}
+
+	/// Fetch a key from the datastore, without decrypting its value.
+	#[allow(unused_variables)]
+	async fn _get(&mut self, key: Key) -> Result<Option<Val>, Error> {
 		match self {
 			#[cfg(feature = "kv-dynamodb")]
 			Transaction {
@@ -477,6 +520,9 @@ impl Transaction {
 	{
 		#[cfg(debug_assertions)]
 		trace!("Set {:?} => {:?}", key, val);
+		let key: Key = key.into();
+		let val = self.encrypt_val(&key, val)?;
+		let key = self.encrypt_key(key);
 		match self {
 			#[cfg(feature = "kv-dynamodb")]
 			Transaction {
@@ -530,6 +576,7 @@ impl Transaction {
 	{
 		#[cfg(debug_assertions)]
 		trace!("Get Timestamp {:?}", key);
+		let key = self.encrypt_key(key);
 		match self {
 			#[cfg(feature = "kv-dynamodb")]
 			Transaction {
@@ -609,63 +656,67 @@ impl Transaction {
 	{
 		#[cfg(debug_assertions)]
 		trace!("Set {:?} <ts> {:?} => {:?}", prefix, suffix, val);
-		match self {
+		let ts_key = self.encrypt_key(ts_key);
+		let prefix: Key = prefix.into();
+		let suffix: Key = suffix.into();
+		let key = match self {
 			#[cfg(feature = "kv-dynamodb")]
 			Transaction {
 				inner: Inner::DynamoDb(v),
 				..
-			} => {
-				let k = v.get_versionstamped_key(ts_key, prefix, suffix).await?;
-				v.set(k, val).await
-			}
+			} => v.get_versionstamped_key(ts_key, prefix, suffix).await?,
 			#[cfg(feature = "kv-mem")]
 			Transaction {
 				inner: Inner::Mem(v),
 				..
-			} => {
-				let k = v.get_versionstamped_key(ts_key, prefix, suffix).await?;
-				v.set(k, val)
-			}
+			} => v.get_versionstamped_key(ts_key, prefix, suffix).await?,
 			#[cfg(feature = "kv-rocksdb")]
 			Transaction {
 				inner: Inner::RocksDB(v),
 				..
-			} => {
-				let k = v.get_versionstamped_key(ts_key, prefix, suffix).await?;
-				v.set(k, val).await
-			}
+			} => v.get_versionstamped_key(ts_key, prefix, suffix).await?,
 			#[cfg(feature = "kv-indxdb")]
 			Transaction {
 				inner: Inner::IndxDB(v),
 				..
-			} => {
-				let k = v.get_versionstamped_key(ts_key, prefix, suffix).await?;
-				v.set(k, val).await
-			}
+			} => v.get_versionstamped_key(ts_key, prefix, suffix).await?,
 			#[cfg(feature = "kv-tikv")]
 			Transaction {
 				inner: Inner::TiKV(v),
 				..
-			} => {
-				let k = v.get_versionstamped_key(ts_key, prefix, suffix).await?;
-				v.set(k, val).await
-			}
+			} => v.get_versionstamped_key(ts_key, prefix, suffix).await?,
 			#[cfg(feature = "kv-fdb")]
 			Transaction {
 				inner: Inner::FoundationDB(v),
+				cipher,
 				..
-			} => v.set_versionstamped_key(prefix, suffix, val).await,
+			} => {
+				// The versionstamp is only written into the key when the
+				// transaction is committed, so the key can not be encrypted
+				if cipher.as_ref().is_some_and(|c| c.encrypts_keys()) {
+					return Err(Error::Ds(
+						"Changefeeds are not supported with key encryption on FoundationDB"
+							.to_owned(),
+					));
+				}
+				// The value is bound to the key without its versionstamp
+				let val = match cipher {
+					Some(cipher) => {
+						cipher.encrypt_val(&[&prefix[..], &suffix].concat(), val.into())?
+					}
+					None => val.into(),
+				};
+				return v.set_versionstamped_key(prefix, suffix, val).await;
+			}
 			#[cfg(feature = "kv-speedb")]
 			Transaction {
 				inner: Inner::SpeeDB(v),
 				..
-			} => {
-				let k = v.get_versionstamped_key(ts_key, prefix, suffix).await?;
-				v.set(k, val).await
-			}
+			} => v.get_versionstamped_key(ts_key, prefix, suffix).await?,
 			#[allow(unreachable_patterns)]
 			_ => unreachable!(),
-		}
+		};
+		self.set(key, val).await
 	}
 
 	/// Insert a key if it doesn't exist in the datastore.
@@ -675,6 +726,9 @@ impl Transaction {
 		K: Into<Key> + Debug,
 		V: Into<Val> + Debug,
 	{
+		let key: Key = key.into();
+		let val = self.encrypt_val(&key, val)?;
+		let key = self.encrypt_key(key);
 		match self {
 			#[cfg(feature = "kv-dynamodb")]
 			Transaction {
@@ -726,7 +780,8 @@ impl Transaction {
 	{
 		#[cfg(debug_assertions)]
 		trace!("Scan {:?} - {:?}", rng.start, rng.end);
-		match self {
+		let rng = self.encrypt_rng(rng);
+		let res = match self {
 			#[cfg(feature = "kv-dynamodb")]
 			Transaction {
 				inner: Inner::DynamoDb(v),
@@ -764,7 +819,8 @@ impl Transaction {
 			} => v.scan(rng, limit).await,
 			#[allow(unreachable_patterns)]
 			_ => unreachable!(),
-		}
+		}?;
+		self.decrypt_all(res)
 	}
 
 	/// Retrieve a specific range of keys from the datastore.
@@ -781,7 +837,7 @@ impl Transaction {
 	{
 		#[cfg(debug_assertions)]
 		trace!("Scan {:?} - {:?}", page.range.start, page.range.end);
-		let range = page.range.clone();
+		let range = self.encrypt_rng(page.range.clone());
 		let res = match self {
 			#[cfg(feature = "kv-dynamodb")]
 			Transaction {
@@ -821,6 +877,7 @@ impl Transaction {
 			#[allow(unreachable_patterns)]
 			_ => Err(Error::MissingStorageEngine),
 		};
+		let res = res.and_then(|v| self.decrypt_all(v));
 		// Construct next page
 		res.map(|tup_vec: Vec<(Key, Val)>| {
 			if tup_vec.len() < batch_limit as usize {
@@ -854,6 +911,10 @@ impl Transaction {
 	{
 		#[cfg(debug_assertions)]
 		trace!("Putc {:?} if {:?} => {:?}", key, chk, val);
+		let key: Key = key.into();
+		let chk = self.encrypt_chk(&key, chk).await?;
+		let val = self.encrypt_val(&key, val)?;
+		let key = self.encrypt_key(key);
 		match self {
 			#[cfg(feature = "kv-dynamodb")]
 			Transaction {
@@ -904,6 +965,9 @@ impl Transaction {
 	{
 		#[cfg(debug_assertions)]
 		trace!("Delc {:?} if {:?}", key, chk);
+		let key: Key = key.into();
+		let chk = self.encrypt_chk(&key, chk).await?;
+		let key = self.encrypt_key(key);
 		match self {
 			#[cfg(feature = "kv-dynamodb")]
 			Transaction {
@@ -992,15 +1056,18 @@ impl Transaction {
 	{
 		#[cfg(debug_assertions)]
 		trace!("Delr {:?}..{:?} (limit: {limit})", rng.start, rng.end);
+		// The keys of an encrypted datastore are deleted one by one
 		match self {
 			#[cfg(feature = "kv-tikv")]
 			Transaction {
 				inner: Inner::TiKV(v),
+				cipher: None,
 				..
 			} => v.delr(rng, limit).await,
 			#[cfg(feature = "kv-fdb")]
 			Transaction {
 				inner: Inner::FoundationDB(v),
+				cipher: None,
 				..
 			} => v.delr(rng).await,
 			#[allow(unreachable_patterns)]
@@ -1240,6 +1307,8 @@ impl Transaction {
 		trace!("del_lv: ns={:?} db={:?} tb={:?} lv={:?}", ns, db, tb, lv);
 		let key = crate::key::table::lq::new(ns, db, tb, lv);
 		self.cache.del(&key.clone().into());
+		// Clear the cached table live queries
+		self.cache.del(&crate::key::table::lq::prefix(ns, db, tb));
 		self.del(key).await
 	}
 
@@ -1328,6 +1397,58 @@ impl Transaction {
 		self.putc(key, tb, chk).await
 	}
 
+	/// Register a live query which depends on records in other tables
+	#[cfg(feature = "sql2")]
+	pub async fn put_dblq(&mut self, ns: &str, db: &str, lq: Uuid, tb: &str) -> Result<(), Error> {
+		let key = crate::key::database::lq::new(ns, db, lq);
+		self.cache.del(&crate::key::database::lq::prefix(ns, db));
+		self.set(key, tb).await
+	}
+
+	/// Remove a live query which depends on records in other tables
+	#[cfg(feature = "sql2")]
+	pub async fn del_dblq(&mut self, ns: &str, db: &str, lq: Uuid) -> Result<(), Error> {
+		let key = crate::key::database::lq::new(ns, db, lq);
+		self.cache.del(&crate::key::database::lq::prefix(ns, db));
+		self.del(key).await
+	}
+
+	/// Retrieve the live queries, and their tables, which depend on records in other tables
+	#[cfg(feature = "sql2")]
+	pub async fn all_db_lives(
+		&mut self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[(String, LiveStatement)]>, Error> {
+		let key = crate::key::database::lq::prefix(ns, db);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Dls(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::database::lq::prefix(ns, db);
+			let end = crate::key::database::lq::suffix(ns, db);
+			let mut val = vec![];
+			for (k, v) in self.getr(beg..end, u32::MAX).await? {
+				let lq = crate::key::database::lq::Lq::decode(&k)?;
+				let tb = String::from_utf8_lossy(&v).into_owned();
+				match self.get_tb_live(ns, db, &tb, &lq.lq).await {
+					Ok(lv) => val.push((tb, lv)),
+					// The LIVE query has since been removed
+					Err(Error::LvNotFound {
+						..
+					}) => self.del_dblq(ns, db, lq.lq).await?,
+					Err(e) => return Err(e),
+				}
+			}
+			let val: Arc<[(String, LiveStatement)]> = val.into();
+			self.cache.set(key, Entry::Dls(Arc::clone(&val)));
+			val
+		})
+	}
+
 	/// Retrieve all ROOT users.
 	pub async fn all_root_users(&mut self) -> Result<Arc<[DefineUserStatement]>, Error> {
 		let beg = crate::key::root::us::prefix();
@@ -1337,6 +1458,26 @@ impl Transaction {
 		Ok(val)
 	}
 
+	/// Retrieve all ROOT roles.
+	#[cfg(feature = "sql2")]
+	pub async fn all_root_roles(&mut self) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		let beg = crate::key::root::rl::prefix();
+		let end = crate::key::root::rl::suffix();
+		let val = self.getr(beg..end, u32::MAX).await?;
+		let val = val.convert().into();
+		Ok(val)
+	}
+
+	/// Retrieve all ROOT API keys.
+	#[cfg(feature = "sql2")]
+	pub async fn all_root_keys(&mut self) -> Result<Arc<[DefineKeyStatement]>, Error> {
+		let beg = crate::key::root::ky::prefix();
+		let end = crate::key::root::ky::suffix();
+		let val = self.getr(beg..end, u32::MAX).await?;
+		let val = val.convert().into();
+		Ok(val)
+	}
+
 	/// Retrieve all namespace definitions in a datastore.
 	pub async fn all_ns(&mut self) -> Result<Arc<[DefineNamespaceStatement]>, Error> {
 		let key = crate::key::root::ns::prefix();
@@ -1375,6 +1516,46 @@ impl Transaction {
 		})
 	}
 
+	/// Retrieve all namespace role definitions for a specific namespace.
+	#[cfg(feature = "sql2")]
+	pub async fn all_ns_roles(&mut self, ns: &str) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		let key = crate::key::namespace::rl::prefix(ns);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Nrs(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::namespace::rl::prefix(ns);
+			let end = crate::key::namespace::rl::suffix(ns);
+			let val = self.getr(beg..end, u32::MAX).await?;
+			let val = val.convert().into();
+			self.cache.set(key, Entry::Nrs(Arc::clone(&val)));
+			val
+		})
+	}
+
+	/// Retrieve all namespace API key definitions for a specific namespace.
+	#[cfg(feature = "sql2")]
+	pub async fn all_ns_keys(&mut self, ns: &str) -> Result<Arc<[DefineKeyStatement]>, Error> {
+		let key = crate::key::namespace::ky::prefix(ns);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Nks(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::namespace::ky::prefix(ns);
+			let end = crate::key::namespace::ky::suffix(ns);
+			let val = self.getr(beg..end, u32::MAX).await?;
+			let val = val.convert().into();
+			self.cache.set(key, Entry::Nks(Arc::clone(&val)));
+			val
+		})
+	}
+
 	/// Retrieve all namespace token definitions for a specific namespace.
 	pub async fn all_ns_tokens(&mut self, ns: &str) -> Result<Arc<[DefineTokenStatement]>, Error> {
 		let key = crate::key::namespace::tk::prefix(ns);
@@ -1436,6 +1617,54 @@ impl Transaction {
 		})
 	}
 
+	/// Retrieve all database role definitions for a specific database.
+	#[cfg(feature = "sql2")]
+	pub async fn all_db_roles(
+		&mut self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		let key = crate::key::database::rl::prefix(ns, db);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Drs(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::database::rl::prefix(ns, db);
+			let end = crate::key::database::rl::suffix(ns, db);
+			let val = self.getr(beg..end, u32::MAX).await?;
+			let val = val.convert().into();
+			self.cache.set(key, Entry::Drs(Arc::clone(&val)));
+			val
+		})
+	}
+
+	/// Retrieve all database API key definitions for a specific database.
+	#[cfg(feature = "sql2")]
+	pub async fn all_db_keys(
+		&mut self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineKeyStatement]>, Error> {
+		let key = crate::key::database::ky::prefix(ns, db);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Dks(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::database::ky::prefix(ns, db);
+			let end = crate::key::database::ky::suffix(ns, db);
+			let val = self.getr(beg..end, u32::MAX).await?;
+			let val = val.convert().into();
+			self.cache.set(key, Entry::Dks(Arc::clone(&val)));
+			val
+		})
+	}
+
 	/// Retrieve all database token definitions for a specific database.
 	pub async fn all_db_tokens(
 		&mut self,
@@ -1621,6 +1850,81 @@ impl Transaction {
 		})
 	}
 
+	/// Retrieve all changefeed consumer definitions for a specific table.
+	#[cfg(feature = "sql2")]
+	pub async fn all_tb_consumers(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Arc<[DefineConsumerStatement]>, Error> {
+		let key = crate::key::table::cn::prefix(ns, db, tb);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Cns(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::table::cn::prefix(ns, db, tb);
+			let end = crate::key::table::cn::suffix(ns, db, tb);
+			let val = self.getr(beg..end, u32::MAX).await?;
+			let val = val.convert().into();
+			self.cache.set(key, Entry::Cns(Arc::clone(&val)));
+			val
+		})
+	}
+
+	/// Retrieve all webhook definitions for a specific table.
+	#[cfg(feature = "sql2")]
+	pub async fn all_tb_webhooks(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Arc<[DefineWebhookStatement]>, Error> {
+		let key = crate::key::table::wh::prefix(ns, db, tb);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Whs(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::table::wh::prefix(ns, db, tb);
+			let end = crate::key::table::wh::suffix(ns, db, tb);
+			let val = self.getr(beg..end, u32::MAX).await?;
+			let val = val.convert().into();
+			self.cache.set(key, Entry::Whs(Arc::clone(&val)));
+			val
+		})
+	}
+
+	/// Retrieve all policy definitions for a specific table.
+	#[cfg(feature = "sql2")]
+	pub async fn all_tb_policies(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Arc<[DefinePolicyStatement]>, Error> {
+		let key = crate::key::table::pl::prefix(ns, db, tb);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Pls(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::table::pl::prefix(ns, db, tb);
+			let end = crate::key::table::pl::suffix(ns, db, tb);
+			let val = self.getr(beg..end, u32::MAX).await?;
+			let val = val.convert().into();
+			self.cache.set(key, Entry::Pls(Arc::clone(&val)));
+			val
+		})
+	}
+
 	/// Retrieve all event definitions for a specific table.
 	pub async fn all_tb_events(
 		&mut self,
@@ -1773,6 +2077,26 @@ impl Transaction {
 		Ok(val.into())
 	}
 
+	/// Retrieve a specific root role definition.
+	#[cfg(feature = "sql2")]
+	pub async fn get_root_role(&mut self, role: &str) -> Result<DefineRoleStatement, Error> {
+		let key = crate::key::root::rl::new(role);
+		let val = self.get(key).await?.ok_or(Error::RoleRootNotFound {
+			value: role.to_owned(),
+		})?;
+		Ok(val.into())
+	}
+
+	/// Retrieve a specific root API key definition.
+	#[cfg(feature = "sql2")]
+	pub async fn get_root_key(&mut self, name: &str) -> Result<DefineKeyStatement, Error> {
+		let key = crate::key::root::ky::new(name);
+		let val = self.get(key).await?.ok_or(Error::ApiKeyRootNotFound {
+			value: name.to_owned(),
+		})?;
+		Ok(val.into())
+	}
+
 	/// Retrieve a specific namespace definition.
 	pub async fn get_ns(&mut self, ns: &str) -> Result<DefineNamespaceStatement, Error> {
 		let key = crate::key::root::ns::new(ns);
@@ -1796,6 +2120,32 @@ impl Transaction {
 		Ok(val.into())
 	}
 
+	/// Retrieve a specific role definition from a namespace.
+	#[cfg(feature = "sql2")]
+	pub async fn get_ns_role(
+		&mut self,
+		ns: &str,
+		role: &str,
+	) -> Result<DefineRoleStatement, Error> {
+		let key = crate::key::namespace::rl::new(ns, role);
+		let val = self.get(key).await?.ok_or(Error::RoleNsNotFound {
+			value: role.to_owned(),
+			ns: ns.to_owned(),
+		})?;
+		Ok(val.into())
+	}
+
+	/// Retrieve a specific API key definition from a namespace.
+	#[cfg(feature = "sql2")]
+	pub async fn get_ns_key(&mut self, ns: &str, name: &str) -> Result<DefineKeyStatement, Error> {
+		let key = crate::key::namespace::ky::new(ns, name);
+		let val = self.get(key).await?.ok_or(Error::ApiKeyNsNotFound {
+			value: name.to_owned(),
+			ns: ns.to_owned(),
+		})?;
+		Ok(val.into())
+	}
+
 	/// Retrieve a specific namespace token definition.
 	pub async fn get_ns_token(
 		&mut self,
@@ -1818,6 +2168,40 @@ impl Transaction {
 		Ok(val.into())
 	}
 
+	/// Retrieve a specific role definition from a database.
+	#[cfg(feature = "sql2")]
+	pub async fn get_db_role(
+		&mut self,
+		ns: &str,
+		db: &str,
+		role: &str,
+	) -> Result<DefineRoleStatement, Error> {
+		let key = crate::key::database::rl::new(ns, db, role);
+		let val = self.get(key).await?.ok_or(Error::RoleDbNotFound {
+			value: role.to_owned(),
+			ns: ns.to_owned(),
+			db: db.to_owned(),
+		})?;
+		Ok(val.into())
+	}
+
+	/// Retrieve a specific API key definition from a database.
+	#[cfg(feature = "sql2")]
+	pub async fn get_db_key(
+		&mut self,
+		ns: &str,
+		db: &str,
+		name: &str,
+	) -> Result<DefineKeyStatement, Error> {
+		let key = crate::key::database::ky::new(ns, db, name);
+		let val = self.get(key).await?.ok_or(Error::ApiKeyDbNotFound {
+			value: name.to_owned(),
+			ns: ns.to_owned(),
+			db: db.to_owned(),
+		})?;
+		Ok(val.into())
+	}
+
 	/// Retrieve a specific user definition from a database.
 	pub async fn get_db_user(
 		&mut self,
@@ -1935,6 +2319,86 @@ impl Transaction {
 		Ok(val.into())
 	}
 
+	/// Retrieve a specific changefeed consumer definition.
+	#[cfg(feature = "sql2")]
+	pub async fn get_tb_consumer(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		cn: &str,
+	) -> Result<DefineConsumerStatement, Error> {
+		let key = crate::key::table::cn::new(ns, db, tb, cn);
+		let val = self.get(key).await?.ok_or(Error::CnNotFound {
+			value: cn.to_owned(),
+		})?;
+		Ok(val.into())
+	}
+
+	/// Retrieve a specific webhook definition.
+	#[cfg(feature = "sql2")]
+	pub async fn get_tb_webhook(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		wh: &str,
+	) -> Result<DefineWebhookStatement, Error> {
+		let key = crate::key::table::wh::new(ns, db, tb, wh);
+		let val = self.get(key).await?.ok_or(Error::WhNotFound {
+			value: wh.to_owned(),
+		})?;
+		Ok(val.into())
+	}
+
+	/// Retrieve a specific policy definition.
+	#[cfg(feature = "sql2")]
+	pub async fn get_tb_policy(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		pl: &str,
+	) -> Result<DefinePolicyStatement, Error> {
+		let key = crate::key::table::pl::new(ns, db, tb, pl);
+		let val = self.get(key).await?.ok_or(Error::PlNotFound {
+			value: pl.to_owned(),
+		})?;
+		Ok(val.into())
+	}
+
+	/// Retrieve the last versionstamp committed by a changefeed consumer, if any.
+	#[cfg(feature = "sql2")]
+	pub async fn get_tb_consumer_offset(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		cn: &str,
+	) -> Result<Option<u64>, Error> {
+		let key = crate::key::table::co::new(ns, db, tb, cn);
+		Ok(match self.get(key).await? {
+			Some(v) => Some(u64::from_be_bytes(v.as_slice().try_into().map_err(|_| {
+				Error::Internal(format!("invalid offset for the consumer '{cn}'"))
+			})?)),
+			None => None,
+		})
+	}
+
+	/// Commit the last versionstamp read by a changefeed consumer.
+	#[cfg(feature = "sql2")]
+	pub async fn set_tb_consumer_offset(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		cn: &str,
+		vs: u64,
+	) -> Result<(), Error> {
+		let key = crate::key::table::co::new(ns, db, tb, cn);
+		self.set(key, vs.to_be_bytes().to_vec()).await
+	}
+
 	/// Retrieve a live query for a table.
 	pub async fn get_tb_live(
 		&mut self,
@@ -2361,6 +2825,21 @@ impl Transaction {
 			chn.send(bytes!("OPTION IMPORT;")).await?;
 			chn.send(bytes!("")).await?;
 		}
+		// Output ROLES
+		#[cfg(feature = "sql2")]
+		{
+			let drs = self.all_db_roles(ns, db).await?;
+			if !drs.is_empty() {
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("-- ROLES")).await?;
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("")).await?;
+				for rl in drs.iter() {
+					chn.send(bytes!(format!("{rl};"))).await?;
+				}
+				chn.send(bytes!("")).await?;
+			}
+		}
 		// Output USERS
 		{
 			let dus = self.all_db_users(ns, db).await?;
@@ -2375,6 +2854,21 @@ impl Transaction {
 				chn.send(bytes!("")).await?;
 			}
 		}
+		// Output KEYS
+		#[cfg(feature = "sql2")]
+		{
+			let dks = self.all_db_keys(ns, db).await?;
+			if !dks.is_empty() {
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("-- KEYS")).await?;
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("")).await?;
+				for ky in dks.iter() {
+					chn.send(bytes!(format!("{ky};"))).await?;
+				}
+				chn.send(bytes!("")).await?;
+			}
+		}
 		// Output TOKENS
 		{
 			let dts = self.all_db_tokens(ns, db).await?;
@@ -2492,6 +2986,17 @@ impl Transaction {
 						}
 						chn.send(bytes!("")).await?;
 					}
+					// Output POLICIES
+					#[cfg(feature = "sql2")]
+					{
+						let pls = self.all_tb_policies(ns, db, &tb.name).await?;
+						if !pls.is_empty() {
+							for pl in pls.iter() {
+								chn.send(bytes!(format!("{pl};"))).await?;
+							}
+							chn.send(bytes!("")).await?;
+						}
+					}
 				}
 				// Start transaction
 				chn.send(bytes!("-- ------------------------------")).await?;
@@ -2576,6 +3081,20 @@ impl Transaction {
 		self.cf.update(ns, db, tb, id.clone(), v)
 	}
 
+	// Records the change in the changefeed along with the value of the record before the change.
+	#[cfg(feature = "sql2")]
+	pub(crate) fn record_change_with_original(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		id: &Thing,
+		previous: Cow<'_, Value>,
+		current: Cow<'_, Value>,
+	) {
+		self.cf.update_with_original(ns, db, tb, id.clone(), previous, current)
+	}
+
 	// Records the table (re)definition in the changefeed if enabled.
 	pub(crate) fn record_table_change(
 		&mut self,
@@ -2743,6 +3262,64 @@ impl Transaction {
 		Ok(())
 	}
 
+	/// Registers an entry of a deferred unique index, which is checked when the transaction is committed.
+	#[cfg(feature = "sql2")]
+	pub(crate) fn defer_unique_check(
+		&mut self,
+		key: Key,
+		claim: Key,
+		range: Range<Key>,
+		index: &str,
+		value: String,
+		rid: &Thing,
+	) {
+		self.uniques.push(DeferredUnique {
+			key,
+			claim,
+			range,
+			index: index.to_owned(),
+			value,
+			rid: rid.to_owned(),
+		});
+	}
+
+	/// Checks that no other record shares a value registered on a deferred unique index.
+	///
+	/// The value is then claimed by writing the record to a key derived from the value,
+	/// which was read beforehand, so that concurrent transactions writing the same value
+	/// conflict, as the entries written by the other transaction are not visible here.
+	#[cfg(feature = "sql2")]
+	async fn check_deferred_uniques(&mut self) -> Result<(), Error> {
+		for check in std::mem::take(&mut self.uniques) {
+			// Skip the values which the record does not hold anymore
+			if !self.exi(check.key).await? {
+				continue;
+			}
+			for (_, val) in self.scan(check.range, 2).await? {
+				let rid: Thing = val.into();
+				if rid != check.rid {
+					return Err(Error::IndexExists {
+						thing: rid,
+						index: check.index,
+						value: check.value,
+					});
+				}
+			}
+			match self.get(check.claim.clone()).await?.map(Thing::from) {
+				Some(rid) if rid != check.rid => {
+					return Err(Error::IndexExists {
+						thing: rid,
+						index: check.index,
+						value: check.value,
+					});
+				}
+				Some(_) => {}
+				None => self.set(check.claim, &check.rid).await?,
+			}
+		}
+		Ok(())
+	}
+
 	// set_timestamp_for_versionstamp correlates the given timestamp with the current versionstamp.
 	// This allows get_versionstamp_from_timestamp to obtain the versionstamp from the timestamp later.
 	pub(crate) async fn set_timestamp_for_versionstamp(
@@ -2804,6 +3381,78 @@ impl Transaction {
 	// Private methods
 	// --------------------------------------------------
 
+	/// Encrypts a key, if the keys of the datastore are encrypted
+	fn encrypt_key<K>(&self, key: K) -> Key
+	where
+		K: Into<Key>,
+	{
+		match &self.cipher {
+			Some(cipher) => cipher.encrypt_key(key.into()),
+			None => key.into(),
+		}
+	}
+
+	/// Encrypts the bounds of a range of keys, if the keys of the datastore are encrypted
+	fn encrypt_rng<K>(&self, rng: Range<K>) -> Range<Key>
+	where
+		K: Into<Key>,
+	{
+		self.encrypt_key(rng.start)..self.encrypt_key(rng.end)
+	}
+
+	/// Encrypts the value of a key, if the datastore is encrypted
+	fn encrypt_val<V>(&self, key: &[u8], val: V) -> Result<Val, Error>
+	where
+		V: Into<Val>,
+	{
+		match &self.cipher {
+			Some(cipher) => cipher.encrypt_val(key, val.into()),
+			None => Ok(val.into()),
+		}
+	}
+
+	/// Replaces the expected value of a conditional write with the stored value,
+	/// when the stored value decrypts to the expected value, as the same value is
+	/// encrypted differently each time that it is stored.
+	async fn encrypt_chk<V>(&mut self, key: &Key, chk: Option<V>) -> Result<Option<Val>, Error>
+	where
+		V: Into<Val>,
+	{
+		let chk = chk.map(Into::into);
+		if let (Some(cipher), Some(val)) = (self.cipher.clone(), &chk) {
+			if let Some(cur) = self._get(cipher.encrypt_key(key.clone())).await? {
+				if cipher.decrypt_val(key, cur.clone())? == *val {
+					return Ok(Some(cur));
+				}
+			}
+		}
+		// Otherwise the storage engine reports that the condition is not met
+		Ok(chk)
+	}
+
+	/// Decrypts the value of a key, if the datastore is encrypted
+	fn decrypt_val(&self, key: &[u8], val: Option<Val>) -> Result<Option<Val>, Error> {
+		match (&self.cipher, val) {
+			(Some(cipher), Some(val)) => cipher.decrypt_val(key, val).map(Some),
+			(_, val) => Ok(val),
+		}
+	}
+
+	/// Decrypts the keys and values fetched from the datastore, if the datastore is encrypted
+	fn decrypt_all(&self, res: Vec<(Key, Val)>) -> Result<Vec<(Key, Val)>, Error> {
+		match &self.cipher {
+			Some(cipher) => res
+				.into_iter()
+				.map(|(k, v)| {
+					let k = cipher.decrypt_key(k)?;
+					let v = cipher.decrypt_val(&k, v)?;
+					Ok((k, v))
+				})
+				.collect(),
+			None => Ok(res),
+		}
+	}
+
 	#[allow(unused_variables)]
 	fn check_level(&mut self, check: Check) {
 		#![allow(unused_variables)]
@@ -0,0 +1,141 @@
+//! Delivers the payloads enqueued by `DEFINE WEBHOOK` to their HTTP endpoints.
+//!
+//! Payloads are written to the queue in the same transaction as the change which
+//! triggered them, and are delivered by the datastore on each tick. A delivery is
+//! attempted at least once, so endpoints should use the delivery id to discard
+//! duplicates. Failed deliveries are retried with an exponential backoff, and are
+//! moved to the dead-letter queue of the webhook once all retries are exhausted.
+use crate::dbs::Capabilities;
+use crate::err::Error;
+use crate::sql::statements::DefineWebhookStatement;
+use crate::sql::{Object, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The longest time, in seconds, to wait before retrying a failed delivery
+const MAX_BACKOFF: u64 = 3600;
+
+/// A webhook payload which is waiting to be delivered
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[revisioned(revision = 1)]
+pub(crate) struct Delivery {
+	pub(crate) ns: String,
+	pub(crate) db: String,
+	pub(crate) tb: String,
+	pub(crate) wh: String,
+	pub(crate) payload: Value,
+	/// The number of failed attempts to deliver the payload
+	pub(crate) attempts: u32,
+	/// The error returned by the last failed attempt
+	pub(crate) error: Option<String>,
+}
+
+impl Delivery {
+	/// Describes a dead-lettered delivery, as returned by `INFO FOR WEBHOOK`
+	pub(crate) fn to_value(&self, id: Uuid) -> Value {
+		let mut res = Object::default();
+		res.insert("id".to_owned(), Value::Uuid(id.into()));
+		res.insert("payload".to_owned(), self.payload.clone());
+		res.insert("attempts".to_owned(), Value::from(self.attempts));
+		res.insert("error".to_owned(), self.error.clone().map(Value::from).unwrap_or_default());
+		Value::from(res)
+	}
+}
+
+/// Returns the number of seconds to wait before the next attempt
+pub(crate) fn backoff(attempts: u32) -> u64 {
+	2u64.saturating_pow(attempts).min(MAX_BACKOFF)
+}
+
+/// Signs a payload with the secret of a webhook, so that the endpoint can verify its origin
+#[cfg(any(test, feature = "http"))]
+pub(crate) fn sign(secret: &str, body: &[u8]) -> String {
+	use hmac::{Hmac, Mac};
+	use sha2::Sha256;
+	let mut mac =
+		Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take a key of any size");
+	mac.update(body);
+	format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(not(feature = "http"))]
+pub(crate) async fn deliver(
+	_: &Capabilities,
+	_: &DefineWebhookStatement,
+	_: Uuid,
+	_: &Value,
+) -> Result<(), Error> {
+	Err(Error::HttpDisabled)
+}
+
+/// Sends a payload to the endpoint of a webhook
+#[cfg(feature = "http")]
+pub(crate) async fn deliver(
+	caps: &Capabilities,
+	wh: &DefineWebhookStatement,
+	id: Uuid,
+	payload: &Value,
+) -> Result<(), Error> {
+	use crate::dbs::capabilities::NetTarget;
+	use reqwest::header::CONTENT_TYPE;
+	use reqwest::Client;
+	use url::Url;
+	// Check if the URL is valid and allowed
+	let url = Url::parse(&wh.url).map_err(|_| Error::InvalidUrl(wh.url.to_string()))?;
+	match url.host() {
+		Some(host)
+			if caps.allows_network_target(&NetTarget::Host(
+				host.to_owned(),
+				url.port_or_known_default(),
+			)) => {}
+		_ => return Err(Error::NetTargetNotAllowed(url.to_string())),
+	}
+	// Encode the payload
+	let body = payload.clone().into_json().to_string();
+	// Start a new POST request
+	let cli = Client::builder().build()?;
+	let mut req = cli
+		.post(url)
+		.header(CONTENT_TYPE, "application/json")
+		.header("X-Surreal-Webhook", wh.name.as_str())
+		.header("X-Surreal-Delivery", id.to_string());
+	// Add the User-Agent header
+	if cfg!(not(target_arch = "wasm32")) {
+		req = req.header("User-Agent", "SurrealDB");
+	}
+	// Add the payload signature
+	if let Some(secret) = &wh.secret {
+		req = req.header("X-Surreal-Signature", sign(secret, body.as_bytes()));
+	}
+	// Send the request and wait
+	#[cfg(not(target_arch = "wasm32"))]
+	let req = req.timeout(std::time::Duration::from_secs(*crate::cnf::WEBHOOK_TIMEOUT));
+	let res = req.body(body).send().await?;
+	// Check the response status
+	match res.status() {
+		s if s.is_success() => Ok(()),
+		s => Err(Error::Http(s.to_string())),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn backoff_is_capped() {
+		assert_eq!(backoff(0), 1);
+		assert_eq!(backoff(3), 8);
+		assert_eq!(backoff(12), MAX_BACKOFF);
+		assert_eq!(backoff(u32::MAX), MAX_BACKOFF);
+	}
+
+	#[test]
+	fn sign_payload() {
+		// Test vector from RFC 4231
+		let res = sign("Jefe", b"what do ya want for nothing?");
+		assert_eq!(res, "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+	}
+}
@@ -4,7 +4,7 @@ use std::fmt;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub enum Algorithm {
 	EdDSA,
 	Es256,
@@ -20,6 +20,8 @@ pub enum Algorithm {
 	Rs384,
 	Rs512,
 	Jwks, // Not an argorithm.
+	#[revision(start = 2)]
+	Oidc, // Not an algorithm.
 }
 
 impl Default for Algorithm {
@@ -45,6 +47,7 @@ impl fmt::Display for Algorithm {
 			Self::Rs384 => "RS384",
 			Self::Rs512 => "RS512",
 			Self::Jwks => "JWKS", // Not an algorithm.
+			Self::Oidc => "OIDC", // Not an algorithm.
 		})
 	}
 }
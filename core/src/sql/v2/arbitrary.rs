@@ -32,6 +32,9 @@ impl<'a> Arbitrary<'a> for ChangeFeed {
 	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
 		Ok(Self {
 			expiry: time::Duration::new(u64::arbitrary(u)?, u32::arbitrary(u)?),
+			store_original: bool::arbitrary(u)?,
+			max_changes: Option::<u64>::arbitrary(u)?,
+			max_bytes: Option::<u64>::arbitrary(u)?,
 		})
 	}
 }
@@ -6,14 +6,32 @@ use std::str;
 use std::time;
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 pub struct ChangeFeed {
 	pub expiry: time::Duration,
+	/// Whether the previous value of a record is stored alongside the new one
+	#[revision(start = 2)]
+	pub store_original: bool,
+	/// The maximum number of change sets which are retained for each table
+	#[revision(start = 3)]
+	pub max_changes: Option<u64>,
+	/// The maximum number of bytes which the change sets of each table can use
+	#[revision(start = 3)]
+	pub max_bytes: Option<u64>,
 }
 
 impl Display for ChangeFeed {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		write!(f, "CHANGEFEED {}", Duration(self.expiry))?;
+		if let Some(v) = self.max_changes {
+			write!(f, " LIMIT {v} CHANGES")?;
+		}
+		if let Some(v) = self.max_bytes {
+			write!(f, " LIMIT {v} BYTES")?;
+		}
+		if self.store_original {
+			write!(f, " INCLUDE ORIGINAL")?;
+		}
 		Ok(())
 	}
 }
@@ -22,6 +40,9 @@ impl Default for ChangeFeed {
 	fn default() -> Self {
 		Self {
 			expiry: time::Duration::from_secs(0),
+			store_original: false,
+			max_changes: None,
+			max_bytes: None,
 		}
 	}
 }
@@ -1,4 +1,6 @@
+use crate::sql::fmt::Fmt;
 use crate::sql::language::Language;
+use crate::sql::{Object, Strand, Table};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -6,7 +8,7 @@ use std::fmt::Display;
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub enum Filter {
 	Ascii,
 	EdgeNgram(u16, u16),
@@ -14,6 +16,15 @@ pub enum Filter {
 	Ngram(u16, u16),
 	Snowball(Language),
 	Uppercase,
+	/// Removes the terms contained in a language list or in a custom list
+	#[revision(start = 2)]
+	Stopwords(Stopwords),
+	/// Expands a term with its synonyms
+	#[revision(start = 2)]
+	Synonyms(Synonyms),
+	/// Replaces characters (or sequences of characters) within a term
+	#[revision(start = 2)]
+	Mapping(Object),
 }
 
 impl Display for Filter {
@@ -25,6 +36,51 @@ impl Display for Filter {
 			Self::Ngram(min, max) => write!(f, "NGRAM({},{})", min, max),
 			Self::Snowball(lang) => write!(f, "SNOWBALL({})", lang),
 			Self::Uppercase => f.write_str("UPPERCASE"),
+			Self::Stopwords(sw) => write!(f, "STOPWORDS({})", sw),
+			Self::Synonyms(sy) => write!(f, "SYNONYMS({})", sy),
+			Self::Mapping(mp) => write!(f, "MAPPING({})", mp),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[revisioned(revision = 1)]
+pub enum Stopwords {
+	/// The stopword list bundled for the given language
+	Language(Language),
+	/// A user provided list of stopwords
+	Custom(Vec<Strand>),
+}
+
+impl Display for Stopwords {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Language(lang) => Display::fmt(lang, f),
+			Self::Custom(list) => write!(f, "[{}]", Fmt::comma_separated(list)),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[revisioned(revision = 1)]
+pub enum Synonyms {
+	/// Synonyms are read from the records of a table.
+	/// The record ID is the term in lowercase, and the `synonyms` field contains the synonyms.
+	/// The table is read when a term is analyzed, so after it is changed, the analyzer
+	/// should be defined again, which rebuilds the indexes using it.
+	Table(Table),
+	/// Synonyms are given inline: each key is a term, matched regardless of case,
+	/// and each value is a synonym or an array of synonyms.
+	Map(Object),
+}
+
+impl Display for Synonyms {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Table(tb) => Display::fmt(tb, f),
+			Self::Map(map) => Display::fmt(map, f),
 		}
 	}
 }
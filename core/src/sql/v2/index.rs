@@ -13,7 +13,7 @@ use std::fmt::{Display, Formatter};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub enum Index {
 	/// (Basic) non unique
 	#[default]
@@ -24,6 +24,9 @@ pub enum Index {
 	Search(SearchParams),
 	/// M-Tree index for distance based metrics
 	MTree(MTreeParams),
+	/// Unique index, checked when the transaction is committed
+	#[revision(start = 2)]
+	UniqDeferred,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
@@ -49,7 +52,7 @@ pub struct SearchParams {
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 pub struct MTreeParams {
 	pub dimension: u16,
 	pub distance: Distance,
@@ -60,6 +63,8 @@ pub struct MTreeParams {
 	pub doc_ids_cache: u32,
 	#[revision(start = 2)]
 	pub mtree_cache: u32,
+	#[revision(start = 3)]
+	pub quantization: Option<Quantization>,
 }
 
 #[derive(Clone, Default, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
@@ -109,7 +114,7 @@ impl Display for Distance {
 
 #[derive(Clone, Copy, Default, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub enum VectorType {
 	#[default]
 	F64,
@@ -117,6 +122,12 @@ pub enum VectorType {
 	I64,
 	I32,
 	I16,
+	/// Half precision floats
+	#[revision(start = 2)]
+	F16,
+	/// One bit per component, set when the component is positive
+	#[revision(start = 2)]
+	Binary,
 }
 
 impl Display for VectorType {
@@ -127,6 +138,24 @@ impl Display for VectorType {
 			Self::I64 => f.write_str("I64"),
 			Self::I32 => f.write_str("I32"),
 			Self::I16 => f.write_str("I16"),
+			Self::F16 => f.write_str("F16"),
+			Self::Binary => f.write_str("BINARY"),
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[revisioned(revision = 1)]
+pub enum Quantization {
+	/// Each component is mapped from the [-1, 1] range to a byte
+	Scalar,
+}
+
+impl Display for Quantization {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Scalar => f.write_str("SCALAR"),
 		}
 	}
 }
@@ -136,6 +165,7 @@ impl Display for Index {
 		match self {
 			Self::Idx => Ok(()),
 			Self::Uniq => f.write_str("UNIQUE"),
+			Self::UniqDeferred => f.write_str("UNIQUE DEFERRED"),
 			Self::Search(p) => {
 				write!(
 					f,
@@ -161,7 +191,11 @@ impl Display for Index {
 					f,
 					"MTREE DIMENSION {} DIST {} TYPE {} CAPACITY {} DOC_IDS_ORDER {} DOC_IDS_CACHE {} MTREE_CACHE {}",
 					p.dimension, p.distance, p.vector_type, p.capacity, p.doc_ids_order, p.doc_ids_cache, p.mtree_cache
-				)
+				)?;
+				if let Some(q) = p.quantization {
+					write!(f, " QUANTIZE {q}")?;
+				}
+				Ok(())
 			}
 		}
 	}
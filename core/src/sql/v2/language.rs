@@ -48,6 +48,30 @@ impl Language {
 			Self::Turkish => "TURKISH",
 		}
 	}
+
+	/// Returns the bundled stopword list for this language, if there is one
+	pub fn stopwords(&self) -> Option<&'static [&'static str]> {
+		let code = match self {
+			Self::Arabic => "ar",
+			Self::Danish => "da",
+			Self::Dutch => "nl",
+			Self::English => "en",
+			Self::French => "fr",
+			Self::German => "de",
+			Self::Greek => "el",
+			Self::Hungarian => "hu",
+			Self::Italian => "it",
+			Self::Norwegian => "no",
+			Self::Portuguese => "pt",
+			Self::Romanian => "ro",
+			Self::Russian => "ru",
+			Self::Spanish => "es",
+			Self::Swedish => "sv",
+			Self::Turkish => "tr",
+			Self::Tamil => return None,
+		};
+		Some(stop_words::get(code))
+	}
 }
 
 impl Display for Language {
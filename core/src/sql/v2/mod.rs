@@ -117,6 +117,7 @@ pub use self::output::Output;
 pub use self::param::Param;
 pub use self::part::Part;
 pub use self::permission::Permission;
+pub use self::permission::PermissionKind;
 pub use self::permission::Permissions;
 pub use self::query::Query;
 pub use self::range::Range;
@@ -5,11 +5,12 @@ use crate::err::Error;
 use crate::sql::{
 	fmt::{Fmt, Pretty},
 	statements::{
-		AnalyzeStatement, BeginStatement, BreakStatement, CancelStatement, CommitStatement,
-		ContinueStatement, CreateStatement, DefineStatement, DeleteStatement, ForeachStatement,
-		IfelseStatement, InfoStatement, InsertStatement, KillStatement, LiveStatement,
-		OptionStatement, OutputStatement, RelateStatement, RemoveStatement, SelectStatement,
-		SetStatement, ShowStatement, SleepStatement, ThrowStatement, UpdateStatement, UseStatement,
+		AckStatement, AnalyzeStatement, BeginStatement, BreakStatement, CancelStatement,
+		CommitStatement, ContinueStatement, CreateStatement, DefineStatement, DeleteStatement,
+		ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
+		LiveStatement, OptionStatement, OutputStatement, ReencryptStatement, RelateStatement,
+		RemoveStatement, SelectStatement, SetStatement, ShowStatement, SleepStatement,
+		ThrowStatement, UpdateStatement, UseStatement,
 	},
 	value::Value,
 };
@@ -53,7 +54,7 @@ impl Display for Statements {
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 pub enum Statement {
 	Value(Value),
 	Analyze(AnalyzeStatement),
@@ -82,6 +83,10 @@ pub enum Statement {
 	Update(UpdateStatement),
 	Throw(ThrowStatement),
 	Use(UseStatement),
+	#[revision(start = 2)]
+	Ack(AckStatement),
+	#[revision(start = 3)]
+	Reencrypt(ReencryptStatement),
 }
 
 impl Statement {
@@ -101,6 +106,7 @@ impl Statement {
 	pub(crate) fn writeable(&self) -> bool {
 		match self {
 			Self::Value(v) => v.writeable(),
+			Self::Ack(_) => true,
 			Self::Analyze(_) => false,
 			Self::Break(_) => false,
 			Self::Continue(_) => false,
@@ -115,6 +121,7 @@ impl Statement {
 			Self::Live(_) => true,
 			Self::Output(v) => v.writeable(),
 			Self::Option(_) => false,
+			Self::Reencrypt(_) => true,
 			Self::Relate(v) => v.writeable(),
 			Self::Remove(_) => true,
 			Self::Select(v) => v.writeable(),
@@ -136,6 +143,7 @@ impl Statement {
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
 		match self {
+			Self::Ack(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Analyze(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Break(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Continue(v) => v.compute(ctx, opt, txn, doc).await,
@@ -149,6 +157,7 @@ impl Statement {
 			Self::Kill(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Live(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Output(v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Reencrypt(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Relate(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Remove(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Select(v) => v.compute(ctx, opt, txn, doc).await,
@@ -172,6 +181,7 @@ impl Display for Statement {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
 			Self::Value(v) => write!(Pretty::from(f), "{v}"),
+			Self::Ack(v) => write!(Pretty::from(f), "{v}"),
 			Self::Analyze(v) => write!(Pretty::from(f), "{v}"),
 			Self::Begin(v) => write!(Pretty::from(f), "{v}"),
 			Self::Break(v) => write!(Pretty::from(f), "{v}"),
@@ -189,6 +199,7 @@ impl Display for Statement {
 			Self::Live(v) => write!(Pretty::from(f), "{v}"),
 			Self::Option(v) => write!(Pretty::from(f), "{v}"),
 			Self::Output(v) => write!(Pretty::from(f), "{v}"),
+			Self::Reencrypt(v) => write!(Pretty::from(f), "{v}"),
 			Self::Relate(v) => write!(Pretty::from(f), "{v}"),
 			Self::Remove(v) => write!(Pretty::from(f), "{v}"),
 			Self::Select(v) => write!(Pretty::from(f), "{v}"),
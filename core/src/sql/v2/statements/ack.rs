@@ -0,0 +1,59 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::{Base, Ident, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// AckStatement commits the offset of a changefeed consumer, marking
+// all the changes up to and including the given versionstamp as read.
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[revisioned(revision = 1)]
+pub struct AckStatement {
+	pub name: Ident,
+	pub what: Ident,
+	pub versionstamp: u64,
+}
+
+impl AckStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Consumer, &Base::Db)?;
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Check the consumer exists
+		run.get_tb_consumer(opt.ns(), opt.db(), &self.what, &self.name).await?;
+		// Offsets only ever move forward
+		let offset = run.get_tb_consumer_offset(opt.ns(), opt.db(), &self.what, &self.name).await?;
+		if offset.map_or(true, |v| v < self.versionstamp) {
+			run.set_tb_consumer_offset(
+				opt.ns(),
+				opt.db(),
+				&self.what,
+				&self.name,
+				self.versionstamp,
+			)
+			.await?;
+		}
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl fmt::Display for AckStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ACK CONSUMER {} ON {} AT {}", self.name, self.what, self.versionstamp)
+	}
+}
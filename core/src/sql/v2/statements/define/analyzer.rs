@@ -3,7 +3,8 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
-use crate::sql::{filter::Filter, tokenizer::Tokenizer, Base, Ident, Strand, Value};
+use crate::sql::filter::{Filter, Stopwords, Synonyms};
+use crate::sql::{tokenizer::Tokenizer, Base, Ident, Index, Strand, Value};
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -24,13 +25,15 @@ pub struct DefineAnalyzerStatement {
 impl DefineAnalyzerStatement {
 	pub(crate) async fn compute(
 		&self,
-		_ctx: &Context<'_>,
+		ctx: &Context<'_>,
 		opt: &Options,
 		txn: &Transaction,
-		_doc: Option<&CursorDoc<'_>>,
+		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Analyzer, &Base::Db)?;
+		// Check the filters
+		self.check_filters()?;
 		// Claim transaction
 		let mut run = txn.lock().await;
 		// Clear the cache
@@ -40,11 +43,65 @@ impl DefineAnalyzerStatement {
 		run.add_ns(opt.ns(), opt.strict).await?;
 		run.add_db(opt.ns(), opt.db(), opt.strict).await?;
 		run.set(key, self).await?;
+		// Find the search indexes using this analyzer
+		let mut ixs = Vec::new();
+		for tb in run.all_tb(opt.ns(), opt.db()).await?.iter() {
+			for ix in run.all_tb_indexes(opt.ns(), opt.db(), &tb.name).await?.iter() {
+				if matches!(&ix.index, Index::Search(p) if p.az == self.name) {
+					ixs.push(ix.clone());
+				}
+			}
+		}
 		// Release the transaction
-		drop(run); // Do we really need this?
-		   // Ok all good
+		drop(run);
+		// Rebuild the indexes, as the terms they contain may
+		// have changed, along with a table of synonyms
+		for ix in ixs {
+			ix.compute(ctx, opt, txn, doc).await?;
+		}
+		// Ok all good
 		Ok(Value::None)
 	}
+
+	/// Checks that the filters can be built from their definition
+	fn check_filters(&self) -> Result<(), Error> {
+		let invalid = |message: String| Error::InvalidAnalyzer {
+			name: self.name.to_raw(),
+			message,
+		};
+		for f in self.filters.iter().flatten() {
+			match f {
+				Filter::Stopwords(Stopwords::Language(l)) if l.stopwords().is_none() => {
+					return Err(invalid(format!("There is no stopword list for {l}")));
+				}
+				Filter::Synonyms(Synonyms::Map(map)) => {
+					for (term, v) in map.iter() {
+						let valid = match v {
+							Value::Strand(_) => true,
+							Value::Array(a) => a.iter().all(|v| matches!(v, Value::Strand(_))),
+							_ => false,
+						};
+						if !valid {
+							return Err(invalid(format!(
+								"The synonyms of '{term}' should be a string or an array of strings"
+							)));
+						}
+					}
+				}
+				Filter::Mapping(map) => {
+					for (from, to) in map.iter() {
+						if from.is_empty() || !matches!(to, Value::Strand(_)) {
+							return Err(invalid(format!(
+								"The mapping of '{from}' should be a non empty key with a string value"
+							)));
+						}
+					}
+				}
+				_ => {}
+			}
+		}
+		Ok(())
+	}
 }
 
 impl Display for DefineAnalyzerStatement {
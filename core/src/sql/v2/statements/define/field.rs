@@ -3,6 +3,8 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
+use crate::sql::index::Index;
+use crate::sql::statements::DefineIndexStatement;
 use crate::sql::Part;
 use crate::sql::{
 	fmt::is_pretty, fmt::pretty_indent, Base, Ident, Idiom, Kind, Permissions, Strand, Value,
@@ -14,7 +16,7 @@ use std::fmt::{self, Display, Write};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 pub struct DefineFieldStatement {
 	pub name: Idiom,
 	pub what: Ident,
@@ -27,13 +29,17 @@ pub struct DefineFieldStatement {
 	pub default: Option<Value>,
 	pub permissions: Permissions,
 	pub comment: Option<Strand>,
+	#[revision(start = 3)]
+	pub encrypted: Option<Ident>,
+	#[revision(start = 3)]
+	pub deterministic: bool,
 }
 
 impl DefineFieldStatement {
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
-		_ctx: &Context<'_>,
+		ctx: &Context<'_>,
 		opt: &Options,
 		txn: &Transaction,
 		_doc: Option<&CursorDoc<'_>>,
@@ -44,6 +50,15 @@ impl DefineFieldStatement {
 		let mut run = txn.lock().await;
 		// Clear the cache
 		run.clear_cache();
+		// Check that the field can be encrypted
+		if let Some(key) = &self.encrypted {
+			// The key needs to exist before any values can be stored
+			ctx.get_keyring().check(key)?;
+			// Check that the existing indexes can index the field
+			for ix in run.all_tb_indexes(opt.ns(), opt.db(), &self.what).await?.iter() {
+				self.check_index(ix)?;
+			}
+		}
 		// Process the statement
 		let fd = self.name.to_string();
 		let key = crate::key::table::fd::new(opt.ns(), opt.db(), &self.what, &fd);
@@ -100,6 +115,21 @@ impl DefineFieldStatement {
 		// Ok all good
 		Ok(Value::None)
 	}
+
+	/// Checks that an index can be defined on this field. An encrypted field can
+	/// only be indexed by its exact value, and only when it is encrypted
+	/// deterministically, so that equal values can be found in the index.
+	pub(crate) fn check_index(&self, ix: &DefineIndexStatement) -> Result<(), Error> {
+		if self.encrypted.is_some() && ix.cols.contains(&self.name) {
+			let exact = matches!(ix.index, Index::Idx | Index::Uniq | Index::UniqDeferred);
+			if !exact || !self.deterministic {
+				return Err(Error::EncryptedFieldIndex {
+					value: self.name.to_string(),
+				});
+			}
+		}
+		Ok(())
+	}
 }
 
 impl Display for DefineFieldStatement {
@@ -123,6 +153,12 @@ impl Display for DefineFieldStatement {
 		if let Some(ref v) = self.assert {
 			write!(f, " ASSERT {v}")?
 		}
+		if let Some(ref v) = self.encrypted {
+			write!(f, " ENCRYPTED WITH KEY {v}")?;
+			if self.deterministic {
+				write!(f, " DETERMINISTIC")?
+			}
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
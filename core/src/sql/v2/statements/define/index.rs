@@ -3,7 +3,10 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
-use crate::sql::{statements::UpdateStatement, Base, Ident, Idioms, Index, Strand, Value, Values};
+use crate::idx::builder::BuildState;
+use crate::sql::{
+	statements::UpdateStatement, Base, Cond, Ident, Idioms, Index, Strand, Value, Values,
+};
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -11,13 +14,18 @@ use std::fmt::{self, Display};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 pub struct DefineIndexStatement {
 	pub name: Ident,
 	pub what: Ident,
 	pub cols: Idioms,
 	pub index: Index,
 	pub comment: Option<Strand>,
+	#[revision(start = 2)]
+	pub concurrently: bool,
+	/// Only the records matching this condition are indexed
+	#[revision(start = 3)]
+	pub cond: Option<Cond>,
 }
 
 impl DefineIndexStatement {
@@ -40,13 +48,26 @@ impl DefineIndexStatement {
 		run.add_ns(opt.ns(), opt.strict).await?;
 		run.add_db(opt.ns(), opt.db(), opt.strict).await?;
 		run.add_tb(opt.ns(), opt.db(), &self.what, opt.strict).await?;
+		// Check that the fields can be indexed
+		for fd in run.all_tb_fields(opt.ns(), opt.db(), &self.what).await?.iter() {
+			fd.check_index(self)?;
+		}
 		run.set(key, self).await?;
+		// Clear the index store cache
+		ctx.get_index_stores().index_defined(opt, self).await?;
 		// Remove the index data
 		let key = crate::key::index::all::new(opt.ns(), opt.db(), &self.what, &self.name);
 		run.delp(key, u32::MAX).await?;
 		// Clear the cache
 		let key = crate::key::table::ix::prefix(opt.ns(), opt.db(), &self.what);
 		run.clr(key).await?;
+		// Defer the build to the datastore
+		if self.concurrently {
+			BuildState::default().set(&mut run, opt.ns(), opt.db(), &self.what, &self.name).await?;
+			let key = crate::key::root::ib::new(opt.ns(), opt.db(), &self.what, &self.name);
+			run.set(key, vec![]).await?;
+			return Ok(Value::None);
+		}
 		// Release the transaction
 		drop(run);
 		// Force queries to run
@@ -74,9 +95,15 @@ impl Display for DefineIndexStatement {
 		if Index::Idx != self.index {
 			write!(f, " {}", self.index)?;
 		}
+		if let Some(ref v) = self.cond {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
+		if self.concurrently {
+			write!(f, " CONCURRENTLY")?
+		}
 		Ok(())
 	}
 }
@@ -0,0 +1,129 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{apikey, Action, Level, ResourceKind, Role};
+use crate::kvs::Key;
+use crate::sql::{escape::quote_str, fmt::Fmt, Base, Datetime, Duration, Ident, Strand, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[revisioned(revision = 1)]
+pub struct DefineKeyStatement {
+	pub name: Ident,
+	pub base: Base,
+	/// The hash of the secret, which is generated unless the key is imported
+	pub hash: String,
+	pub roles: Vec<Ident>,
+	/// How long the key can be used for, from the time at which it is defined
+	pub duration: Option<Duration>,
+	/// The time after which the key can no longer be used
+	pub expires: Option<Datetime>,
+	pub comment: Option<Strand>,
+}
+
+impl DefineKeyStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		// Check that the custom roles of the key are defined
+		let level = match self.base {
+			Base::Root => Level::Root,
+			Base::Ns => Level::Namespace(opt.ns().to_owned()),
+			Base::Db => Level::Database(opt.ns().to_owned(), opt.db().to_owned()),
+			// Other levels are not supported
+			_ => return Err(Error::InvalidLevel(self.base.to_string())),
+		};
+		let roles: Vec<Role> = self.roles.iter().map(Role::from).collect();
+		crate::iam::roles::check(&mut *txn.lock().await, &level, &roles).await?;
+		// A secret is generated, unless the key is imported with the hash of its secret
+		let (secret, hash) = match self.hash.is_empty() {
+			true => {
+				let secret = apikey::generate();
+				let hash = apikey::hash(&secret);
+				(Some(secret), hash)
+			}
+			false => (None, self.hash.clone()),
+		};
+		// The expiry is fixed at the time at which the key is defined
+		let ky = DefineKeyStatement {
+			hash,
+			duration: None,
+			expires: self.duration.map(|v| v + Datetime::default()).or(self.expires.clone()),
+			..self.clone()
+		};
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Clear the cache
+		run.clear_cache();
+		// Process the statement
+		let key: Key = match self.base {
+			Base::Root => crate::key::root::ky::new(&self.name).into(),
+			Base::Ns => {
+				run.add_ns(opt.ns(), opt.strict).await?;
+				crate::key::namespace::ky::new(opt.ns(), &self.name).into()
+			}
+			_ => {
+				run.add_ns(opt.ns(), opt.strict).await?;
+				run.add_db(opt.ns(), opt.db(), opt.strict).await?;
+				crate::key::database::ky::new(opt.ns(), opt.db(), &self.name).into()
+			}
+		};
+		// Redefining a key with a new secret revokes the previous secret
+		if let Some(old) = run.get(key.clone()).await? {
+			let old = DefineKeyStatement::from(old);
+			if old.hash != ky.hash {
+				apikey::release(&mut run, &old.hash, &level, &self.name).await?;
+			}
+		}
+		// Index the key by the hash of its secret
+		apikey::claim(&mut run, &ky.hash, &level, &self.name).await?;
+		run.set(key, ky).await?;
+		// The secret is only ever returned here
+		Ok(secret.map(Value::from).unwrap_or_default())
+	}
+}
+
+impl Display for DefineKeyStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE KEY {} ON {}", self.name, self.base)?;
+		if !self.hash.is_empty() {
+			write!(f, " HASH {}", quote_str(&self.hash))?;
+		}
+		write!(
+			f,
+			" ROLES {}",
+			Fmt::comma_separated(
+				&self
+					.roles
+					.iter()
+					.map(|r| match Role::from(r) {
+						// Custom role names are case sensitive
+						Role::Custom(_) => r.to_string(),
+						_ => r.to_string().to_uppercase(),
+					})
+					.collect::<Vec<String>>()
+			)
+		)?;
+		if let Some(ref v) = self.duration {
+			write!(f, " EXPIRES {v}")?
+		} else if let Some(ref v) = self.expires {
+			write!(f, " EXPIRES d{v}")?
+		}
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
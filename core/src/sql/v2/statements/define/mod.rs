@@ -1,30 +1,40 @@
 mod analyzer;
+mod consumer;
 mod database;
 mod event;
 mod field;
 mod function;
 mod index;
+mod key;
 mod model;
 mod namespace;
 mod param;
+mod policy;
+mod role;
 mod scope;
 mod table;
 mod token;
 mod user;
+mod webhook;
 
 pub use analyzer::DefineAnalyzerStatement;
+pub use consumer::DefineConsumerStatement;
 pub use database::DefineDatabaseStatement;
 pub use event::DefineEventStatement;
 pub use field::DefineFieldStatement;
 pub use function::DefineFunctionStatement;
 pub use index::DefineIndexStatement;
+pub use key::DefineKeyStatement;
 pub use model::DefineModelStatement;
 pub use namespace::DefineNamespaceStatement;
 pub use param::DefineParamStatement;
+pub use policy::DefinePolicyStatement;
+pub use role::DefineRoleStatement;
 pub use scope::DefineScopeStatement;
 pub use table::DefineTableStatement;
 pub use token::DefineTokenStatement;
 pub use user::DefineUserStatement;
+pub use webhook::DefineWebhookStatement;
 
 use crate::ctx::Context;
 use crate::dbs::Options;
@@ -39,7 +49,7 @@ use std::fmt::{self, Display};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 6)]
 pub enum DefineStatement {
 	Namespace(DefineNamespaceStatement),
 	Database(DefineDatabaseStatement),
@@ -54,6 +64,16 @@ pub enum DefineStatement {
 	Index(DefineIndexStatement),
 	User(DefineUserStatement),
 	Model(DefineModelStatement),
+	#[revision(start = 2)]
+	Consumer(DefineConsumerStatement),
+	#[revision(start = 3)]
+	Webhook(DefineWebhookStatement),
+	#[revision(start = 4)]
+	Role(DefineRoleStatement),
+	#[revision(start = 5)]
+	Policy(DefinePolicyStatement),
+	#[revision(start = 6)]
+	Key(DefineKeyStatement),
 }
 
 impl DefineStatement {
@@ -83,6 +103,11 @@ impl DefineStatement {
 			Self::Analyzer(ref v) => v.compute(ctx, opt, txn, doc).await,
 			Self::User(ref v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Model(ref v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Consumer(ref v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Webhook(ref v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Role(ref v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Policy(ref v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Key(ref v) => v.compute(ctx, opt, txn, doc).await,
 		}
 	}
 }
@@ -103,6 +128,11 @@ impl Display for DefineStatement {
 			Self::Index(v) => Display::fmt(v, f),
 			Self::Analyzer(v) => Display::fmt(v, f),
 			Self::Model(v) => Display::fmt(v, f),
+			Self::Consumer(v) => Display::fmt(v, f),
+			Self::Webhook(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
+			Self::Policy(v) => Display::fmt(v, f),
+			Self::Key(v) => Display::fmt(v, f),
 		}
 	}
 }
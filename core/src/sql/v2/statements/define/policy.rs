@@ -0,0 +1,78 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::{Base, Ident, PermissionKind, Strand, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[revisioned(revision = 1)]
+pub struct DefinePolicyStatement {
+	pub name: Ident,
+	pub what: Ident,
+	pub kind: Vec<PermissionKind>,
+	pub when: Value,
+	pub comment: Option<Strand>,
+}
+
+impl DefinePolicyStatement {
+	/// The types of statement which a policy applies to by default
+	pub(crate) const DEFAULT_KIND: [PermissionKind; 4] = [
+		PermissionKind::Select,
+		PermissionKind::Create,
+		PermissionKind::Update,
+		PermissionKind::Delete,
+	];
+	/// Check if this policy applies to a type of statement
+	pub(crate) fn applies(&self, kind: PermissionKind) -> bool {
+		self.kind.contains(&kind)
+	}
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Policy, &Base::Db)?;
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Clear the cache
+		run.clear_cache();
+		// Process the statement
+		let key = crate::key::table::pl::new(opt.ns(), opt.db(), &self.what, &self.name);
+		run.add_ns(opt.ns(), opt.strict).await?;
+		run.add_db(opt.ns(), opt.db(), opt.strict).await?;
+		run.add_tb(opt.ns(), opt.db(), &self.what, opt.strict).await?;
+		run.set(key, self).await?;
+		// Clear the cache
+		let key = crate::key::table::pl::prefix(opt.ns(), opt.db(), &self.what);
+		run.clr(key).await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for DefinePolicyStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE POLICY {} ON {} FOR ", self.name, self.what)?;
+		for (i, k) in self.kind.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			write!(f, "{k}")?;
+		}
+		write!(f, " WHEN {}", self.when)?;
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
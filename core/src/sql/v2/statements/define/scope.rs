@@ -13,7 +13,7 @@ use std::fmt::{self, Display};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub struct DefineScopeStatement {
 	pub name: Ident,
 	pub code: String,
@@ -21,6 +21,9 @@ pub struct DefineScopeStatement {
 	pub signup: Option<Value>,
 	pub signin: Option<Value>,
 	pub comment: Option<Strand>,
+	/// Selects the record of a client certificate, from its `$subject` and `$san`
+	#[revision(start = 2)]
+	pub certificate: Option<Value>,
 }
 
 impl DefineScopeStatement {
@@ -66,6 +69,9 @@ impl Display for DefineScopeStatement {
 		if let Some(ref v) = self.signin {
 			write!(f, " SIGNIN {v}")?
 		}
+		if let Some(ref v) = self.certificate {
+			write!(f, " CERTIFICATE {v}")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -3,7 +3,7 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
-use crate::sql::{escape::quote_str, Algorithm, Base, Ident, Strand, Value};
+use crate::sql::{escape::quote_str, Algorithm, Base, Ident, Object, Strand, Value};
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -11,13 +11,22 @@ use std::fmt::{self, Display};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub struct DefineTokenStatement {
 	pub name: Ident,
 	pub base: Base,
 	pub kind: Algorithm,
 	pub code: String,
 	pub comment: Option<Strand>,
+	#[revision(start = 2)]
+	pub audience: Option<Strand>,
+	#[revision(start = 2)]
+	pub claims: Option<Object>,
+}
+
+impl DefineTokenStatement {
+	/// The claims which can be mapped from the tokens of an OpenID Connect provider
+	pub const CLAIMS: [&'static str; 5] = ["ns", "db", "sc", "id", "roles"];
 }
 
 impl DefineTokenStatement {
@@ -79,14 +88,17 @@ impl DefineTokenStatement {
 
 impl Display for DefineTokenStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(
-			f,
-			"DEFINE TOKEN {} ON {} TYPE {} VALUE {}",
-			self.name,
-			self.base,
-			self.kind,
-			quote_str(&self.code)
-		)?;
+		write!(f, "DEFINE TOKEN {} ON {} TYPE {}", self.name, self.base, self.kind)?;
+		match self.kind {
+			Algorithm::Oidc => write!(f, " ISSUER {}", quote_str(&self.code))?,
+			_ => write!(f, " VALUE {}", quote_str(&self.code))?,
+		}
+		if let Some(ref v) = self.audience {
+			write!(f, " AUDIENCE {v}")?
+		}
+		if let Some(ref v) = self.claims {
+			write!(f, " CLAIMS {v}")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -2,8 +2,9 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::iam::{Action, ResourceKind};
-use crate::sql::{escape::quote_str, fmt::Fmt, Base, Ident, Strand, Value};
+use crate::iam::password::PasswordStrength;
+use crate::iam::{Action, Level, ResourceKind, Role};
+use crate::sql::{escape::quote_str, fmt::Fmt, Base, Datetime, Ident, Strand, Value};
 use argon2::{
 	password_hash::{PasswordHasher, SaltString},
 	Argon2,
@@ -16,7 +17,7 @@ use std::fmt::{self, Display};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 pub struct DefineUserStatement {
 	pub name: Ident,
 	pub base: Base,
@@ -24,6 +25,16 @@ pub struct DefineUserStatement {
 	pub code: String,
 	pub roles: Vec<Ident>,
 	pub comment: Option<Strand>,
+	/// The strength of the password, when it was specified with PASSWORD
+	#[revision(start = 2)]
+	#[serde(skip)]
+	pub strength: Option<PasswordStrength>,
+	/// The time at which the password was set
+	#[revision(start = 2)]
+	pub changed: Option<Datetime>,
+	/// The subject or subject alternative name of a client certificate which identifies the user
+	#[revision(start = 3)]
+	pub certificate: Option<Strand>,
 }
 
 impl From<(Base, &str, &str)> for DefineUserStatement {
@@ -42,6 +53,9 @@ impl From<(Base, &str, &str)> for DefineUserStatement {
 				.collect::<String>(),
 			roles: vec!["owner".into()],
 			comment: None,
+			strength: None,
+			changed: None,
+			certificate: None,
 		}
 	}
 }
@@ -65,7 +79,8 @@ impl DefineUserStatement {
 		self.hash = Argon2::default()
 			.hash_password(password.as_bytes(), &SaltString::generate(&mut OsRng))
 			.unwrap()
-			.to_string()
+			.to_string();
+		self.strength = Some(password.into());
 	}
 
 	pub(crate) fn set_passhash(&mut self, passhash: String) {
@@ -75,13 +90,25 @@ impl DefineUserStatement {
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
-		_ctx: &Context<'_>,
+		ctx: &Context<'_>,
 		opt: &Options,
 		txn: &Transaction,
 		_doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		// Check that the custom roles of the user are defined
+		self.check_roles(opt, txn).await?;
+		// Check that the password is complex enough
+		if let Some(strength) = &self.strength {
+			ctx.get_password_policy().check(strength)?;
+		}
+		// The strength of the password is not stored
+		let us = DefineUserStatement {
+			strength: None,
+			changed: self.changed(opt, txn).await?,
+			..self.clone()
+		};
 
 		match self.base {
 			Base::Root => {
@@ -91,7 +118,7 @@ impl DefineUserStatement {
 				run.clear_cache();
 				// Process the statement
 				let key = crate::key::root::us::new(&self.name);
-				run.set(key, self).await?;
+				run.set(key, us).await?;
 				// Ok all good
 				Ok(Value::None)
 			}
@@ -103,7 +130,7 @@ impl DefineUserStatement {
 				// Process the statement
 				let key = crate::key::namespace::us::new(opt.ns(), &self.name);
 				run.add_ns(opt.ns(), opt.strict).await?;
-				run.set(key, self).await?;
+				run.set(key, us).await?;
 				// Ok all good
 				Ok(Value::None)
 			}
@@ -116,7 +143,7 @@ impl DefineUserStatement {
 				let key = crate::key::database::us::new(opt.ns(), opt.db(), &self.name);
 				run.add_ns(opt.ns(), opt.strict).await?;
 				run.add_db(opt.ns(), opt.db(), opt.strict).await?;
-				run.set(key, self).await?;
+				run.set(key, us).await?;
 				// Ok all good
 				Ok(Value::None)
 			}
@@ -126,6 +153,38 @@ impl DefineUserStatement {
 	}
 }
 
+impl DefineUserStatement {
+	/// Returns the time at which the password of the user was changed, which is
+	/// kept when the user is redefined with the same password hash
+	async fn changed(&self, opt: &Options, txn: &Transaction) -> Result<Option<Datetime>, Error> {
+		let mut run = txn.lock().await;
+		let res = match self.base {
+			Base::Root => run.get(crate::key::root::us::new(&self.name)).await?,
+			Base::Ns => run.get(crate::key::namespace::us::new(opt.ns(), &self.name)).await?,
+			Base::Db => {
+				run.get(crate::key::database::us::new(opt.ns(), opt.db(), &self.name)).await?
+			}
+			_ => return Err(Error::InvalidLevel(self.base.to_string())),
+		};
+		match res.map(DefineUserStatement::from) {
+			Some(us) if us.hash == self.hash => Ok(us.changed),
+			_ => Ok(Some(Datetime::default())),
+		}
+	}
+
+	/// Checks that the custom roles are defined on the level of the user, or above it
+	async fn check_roles(&self, opt: &Options, txn: &Transaction) -> Result<(), Error> {
+		let level = match self.base {
+			Base::Root => Level::Root,
+			Base::Ns => Level::Namespace(opt.ns().to_owned()),
+			Base::Db => Level::Database(opt.ns().to_owned(), opt.db().to_owned()),
+			_ => return Err(Error::InvalidLevel(self.base.to_string())),
+		};
+		let roles: Vec<Role> = self.roles.iter().map(Role::from).collect();
+		crate::iam::roles::check(&mut *txn.lock().await, &level, &roles).await
+	}
+}
+
 impl Display for DefineUserStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(
@@ -135,9 +194,20 @@ impl Display for DefineUserStatement {
 			self.base,
 			quote_str(&self.hash),
 			Fmt::comma_separated(
-				&self.roles.iter().map(|r| r.to_string().to_uppercase()).collect::<Vec<String>>()
+				&self
+					.roles
+					.iter()
+					.map(|r| match Role::from(r) {
+						// Custom role names are case sensitive
+						Role::Custom(_) => r.to_string(),
+						_ => r.to_string().to_uppercase(),
+					})
+					.collect::<Vec<String>>()
 			)
 		)?;
+		if let Some(ref v) = self.certificate {
+			write!(f, " CERTIFICATE {v}")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -0,0 +1,75 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::{Base, Ident, Strand, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[revisioned(revision = 1)]
+pub struct DefineWebhookStatement {
+	pub name: Ident,
+	pub what: Ident,
+	pub when: Value,
+	pub url: Strand,
+	pub secret: Option<Strand>,
+	pub retry: u32,
+	pub comment: Option<Strand>,
+}
+
+impl DefineWebhookStatement {
+	/// The number of times a delivery is retried by default
+	pub(crate) const DEFAULT_RETRY: u32 = 5;
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Webhook, &Base::Db)?;
+		// Check that the URL is valid
+		url::Url::parse(&self.url).map_err(|_| Error::InvalidUrl(self.url.to_string()))?;
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Clear the cache
+		run.clear_cache();
+		// Process the statement
+		let key = crate::key::table::wh::new(opt.ns(), opt.db(), &self.what, &self.name);
+		run.add_ns(opt.ns(), opt.strict).await?;
+		run.add_db(opt.ns(), opt.db(), opt.strict).await?;
+		run.add_tb(opt.ns(), opt.db(), &self.what, opt.strict).await?;
+		run.set(key, self).await?;
+		// Clear the cache
+		let key = crate::key::table::wh::prefix(opt.ns(), opt.db(), &self.what);
+		run.clr(key).await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for DefineWebhookStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"DEFINE WEBHOOK {} ON {} WHEN {} URL {}",
+			self.name, self.what, self.when, self.url
+		)?;
+		// Never display the secret which signs the payloads
+		if self.secret.is_some() {
+			write!(f, " SECRET '[REDACTED]'")?
+		}
+		write!(f, " RETRY {}", self.retry)?;
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
@@ -2,8 +2,11 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::iam::apikey;
 use crate::iam::Action;
 use crate::iam::ResourceKind;
+use crate::idx::builder::BuildState;
+use crate::kvs::webhook::Delivery;
 use crate::sql::{Base, Ident, Object, Value};
 use derive::Store;
 use revision::revisioned;
@@ -12,7 +15,7 @@ use std::fmt;
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 4)]
 pub enum InfoStatement {
 	Root,
 	Ns,
@@ -20,6 +23,12 @@ pub enum InfoStatement {
 	Sc(Ident),
 	Tb(Ident),
 	User(Ident, Option<Base>),
+	#[revision(start = 2)]
+	Index(Ident, Ident),
+	#[revision(start = 3)]
+	Webhook(Ident, Ident),
+	#[revision(start = 4)]
+	Key(Ident, Option<Base>),
 }
 
 impl InfoStatement {
@@ -52,6 +61,22 @@ impl InfoStatement {
 					tmp.insert(v.name.to_string(), v.to_string().into());
 				}
 				res.insert("users".to_owned(), tmp.into());
+				// Process the roles, if any are defined
+				let mut tmp = Object::default();
+				for v in run.all_root_roles().await?.iter() {
+					tmp.insert(v.name.to_string(), v.to_string().into());
+				}
+				if !tmp.is_empty() {
+					res.insert("roles".to_owned(), tmp.into());
+				}
+				// Process the API keys, if any are defined
+				let mut tmp = Object::default();
+				for v in run.all_root_keys().await?.iter() {
+					tmp.insert(v.name.to_string(), v.to_string().into());
+				}
+				if !tmp.is_empty() {
+					res.insert("keys".to_owned(), tmp.into());
+				}
 				// Ok all good
 				Value::from(res).ok()
 			}
@@ -74,6 +99,22 @@ impl InfoStatement {
 					tmp.insert(v.name.to_string(), v.to_string().into());
 				}
 				res.insert("users".to_owned(), tmp.into());
+				// Process the roles, if any are defined
+				let mut tmp = Object::default();
+				for v in run.all_ns_roles(opt.ns()).await?.iter() {
+					tmp.insert(v.name.to_string(), v.to_string().into());
+				}
+				if !tmp.is_empty() {
+					res.insert("roles".to_owned(), tmp.into());
+				}
+				// Process the API keys, if any are defined
+				let mut tmp = Object::default();
+				for v in run.all_ns_keys(opt.ns()).await?.iter() {
+					tmp.insert(v.name.to_string(), v.to_string().into());
+				}
+				if !tmp.is_empty() {
+					res.insert("keys".to_owned(), tmp.into());
+				}
 				// Process the tokens
 				let mut tmp = Object::default();
 				for v in run.all_ns_tokens(opt.ns()).await?.iter() {
@@ -96,6 +137,22 @@ impl InfoStatement {
 					tmp.insert(v.name.to_string(), v.to_string().into());
 				}
 				res.insert("users".to_owned(), tmp.into());
+				// Process the roles, if any are defined
+				let mut tmp = Object::default();
+				for v in run.all_db_roles(opt.ns(), opt.db()).await?.iter() {
+					tmp.insert(v.name.to_string(), v.to_string().into());
+				}
+				if !tmp.is_empty() {
+					res.insert("roles".to_owned(), tmp.into());
+				}
+				// Process the API keys, if any are defined
+				let mut tmp = Object::default();
+				for v in run.all_db_keys(opt.ns(), opt.db()).await?.iter() {
+					tmp.insert(v.name.to_string(), v.to_string().into());
+				}
+				if !tmp.is_empty() {
+					res.insert("keys".to_owned(), tmp.into());
+				}
 				// Process the tokens
 				let mut tmp = Object::default();
 				for v in run.all_db_tokens(opt.ns(), opt.db()).await?.iter() {
@@ -138,6 +195,28 @@ impl InfoStatement {
 					tmp.insert(v.name.to_string(), v.to_string().into());
 				}
 				res.insert("analyzers".to_owned(), tmp.into());
+				// Process the changefeed storage use, if changefeeds are enabled
+				let dbcf = match run.get_db(opt.ns(), opt.db()).await {
+					Ok(db) => db.changefeed,
+					Err(Error::DbNotFound {
+						..
+					}) => None,
+					Err(e) => return Err(e),
+				};
+				let tbs = run.all_tb(opt.ns(), opt.db()).await?;
+				if dbcf.is_some() || tbs.iter().any(|tb| tb.changefeed.is_some()) {
+					let usage = crate::cf::reader::usage(&mut run, opt.ns(), opt.db()).await?;
+					let mut tmp = Object::default();
+					for tb in tbs.iter().filter(|tb| dbcf.is_some() || tb.changefeed.is_some()) {
+						let (changes, bytes) =
+							usage.get(tb.name.as_str()).copied().unwrap_or_default();
+						let mut cf = Object::default();
+						cf.insert("changes".to_owned(), changes.into());
+						cf.insert("bytes".to_owned(), bytes.into());
+						tmp.insert(tb.name.to_string(), cf.into());
+					}
+					res.insert("changefeeds".to_owned(), tmp.into());
+				}
 				// Ok all good
 				Value::from(res).ok()
 			}
@@ -194,6 +273,22 @@ impl InfoStatement {
 					tmp.insert(v.id.to_raw(), v.to_string().into());
 				}
 				res.insert("lives".to_owned(), tmp.into());
+				// Process the webhooks, if any are defined
+				let mut tmp = Object::default();
+				for v in run.all_tb_webhooks(opt.ns(), opt.db(), tb).await?.iter() {
+					tmp.insert(v.name.to_string(), v.to_string().into());
+				}
+				if !tmp.is_empty() {
+					res.insert("webhooks".to_owned(), tmp.into());
+				}
+				// Process the policies, if any are defined
+				let mut tmp = Object::default();
+				for v in run.all_tb_policies(opt.ns(), opt.db(), tb).await?.iter() {
+					tmp.insert(v.name.to_string(), v.to_string().into());
+				}
+				if !tmp.is_empty() {
+					res.insert("policies".to_owned(), tmp.into());
+				}
 				// Ok all good
 				Value::from(res).ok()
 			}
@@ -214,6 +309,74 @@ impl InfoStatement {
 				// Ok all good
 				Value::from(res.to_string()).ok()
 			}
+			InfoStatement::Index(index, table) => {
+				// Allowed to run?
+				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
+				// Claim transaction
+				let mut run = txn.lock().await;
+				// Check the index exists
+				run.get_and_cache_tb_index(opt.ns(), opt.db(), table, index).await?;
+				// Create the result set
+				let mut res = Object::default();
+				// Process the build state
+				let building =
+					match BuildState::get(&mut run, opt.ns(), opt.db(), table, index).await? {
+						Some(state) => state.to_value(),
+						// The index was not built concurrently
+						None => Value::from(map! {
+							"status".to_string() => Value::from("ready"),
+						}),
+					};
+				res.insert("building".to_owned(), building);
+				// Ok all good
+				Value::from(res).ok()
+			}
+			InfoStatement::Webhook(webhook, table) => {
+				// Allowed to run?
+				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
+				// Claim transaction
+				let mut run = txn.lock().await;
+				// Check the webhook exists
+				run.get_tb_webhook(opt.ns(), opt.db(), table, webhook).await?;
+				// Create the result set
+				let mut res = Object::default();
+				// Process the dead-lettered deliveries
+				let beg = crate::key::table::wd::prefix(opt.ns(), opt.db(), table, webhook);
+				let end = crate::key::table::wd::suffix(opt.ns(), opt.db(), table, webhook);
+				let mut tmp = Vec::new();
+				for (k, v) in run.getr(beg..end, u32::MAX).await? {
+					let key = crate::key::table::wd::Wd::decode(&k)?;
+					tmp.push(Delivery::from(v).to_value(key.id));
+				}
+				res.insert("deadletters".to_owned(), tmp.into());
+				// Ok all good
+				Value::from(res).ok()
+			}
+			InfoStatement::Key(key, base) => {
+				let base = base.clone().unwrap_or(opt.selected_base()?);
+				// Allowed to run?
+				opt.is_allowed(Action::View, ResourceKind::Actor, &base)?;
+				// Claim transaction
+				let mut run = txn.lock().await;
+				// Process the key
+				let ky = match base {
+					Base::Root => run.get_root_key(key).await?,
+					Base::Ns => run.get_ns_key(opt.ns(), key).await?,
+					Base::Db => run.get_db_key(opt.ns(), opt.db(), key).await?,
+					_ => return Err(Error::InvalidLevel(base.to_string())),
+				};
+				// Create the result set
+				let mut res = Object::default();
+				res.insert("definition".to_owned(), ky.to_string().into());
+				res.insert(
+					"expires".to_owned(),
+					ky.expires.clone().map_or(Value::None, Value::from),
+				);
+				let used = apikey::used(&mut run, &ky.hash).await?;
+				res.insert("used".to_owned(), used.map_or(Value::None, Value::from));
+				// Ok all good
+				Value::from(res).ok()
+			}
 		}
 	}
 }
@@ -230,6 +393,12 @@ impl fmt::Display for InfoStatement {
 				Some(ref b) => write!(f, "INFO FOR USER {u} ON {b}"),
 				None => write!(f, "INFO FOR USER {u}"),
 			},
+			Self::Index(ref i, ref t) => write!(f, "INFO FOR INDEX {i} ON {t}"),
+			Self::Webhook(ref w, ref t) => write!(f, "INFO FOR WEBHOOK {w} ON {t}"),
+			Self::Key(ref k, ref b) => match b {
+				Some(ref b) => write!(f, "INFO FOR KEY {k} ON {b}"),
+				None => write!(f, "INFO FOR KEY {k}"),
+			},
 		}
 	}
 }
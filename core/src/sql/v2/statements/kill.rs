@@ -71,6 +71,8 @@ impl KillStatement {
 					// Delete the table live query
 					let key = crate::key::table::lq::new(opt.ns(), opt.db(), tb, live_query_id.0);
 					run.del(key).await?;
+					// Delete the database live query
+					run.del_dblq(opt.ns(), opt.db(), live_query_id.0).await?;
 				}
 				_ => {
 					return Err(Error::KillStatement {
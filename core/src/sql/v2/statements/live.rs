@@ -11,7 +11,7 @@ use std::fmt;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 pub struct LiveStatement {
 	pub id: Uuid,
 	pub node: Uuid,
@@ -19,6 +19,10 @@ pub struct LiveStatement {
 	pub what: Value,
 	pub cond: Option<Cond>,
 	pub fetch: Option<Fetchs>,
+	// Whether notifications should also include the
+	// record content from before it was altered.
+	#[revision(start = 3)]
+	pub before: bool,
 	// When a live query is marked for archiving, this will
 	// be set to the node ID that archived the query. This
 	// is an internal property, set by the database runtime.
@@ -104,6 +108,10 @@ impl LiveStatement {
 				stm.node = nid.into();
 				// Insert the node live query
 				run.putc_ndlq(nid, id, opt.ns(), opt.db(), tb.as_str(), None).await?;
+				// Fetched records can be changed from other tables
+				if stm.fetch.is_some() {
+					run.put_dblq(opt.ns(), opt.db(), id, tb.as_str()).await?;
+				}
 				// Insert the table live query
 				run.putc_tblq(opt.ns(), opt.db(), &tb, stm, None).await?;
 			}
@@ -132,6 +140,9 @@ impl fmt::Display for LiveStatement {
 		if let Some(ref v) = self.fetch {
 			write!(f, " {v}")?
 		}
+		if self.before {
+			write!(f, " WITH BEFORE")?
+		}
 		Ok(())
 	}
 }
@@ -1,3 +1,4 @@
+pub(crate) mod ack;
 pub(crate) mod analyze;
 pub(crate) mod begin;
 pub(crate) mod r#break;
@@ -15,6 +16,7 @@ pub(crate) mod kill;
 pub(crate) mod live;
 pub(crate) mod option;
 pub(crate) mod output;
+pub(crate) mod reencrypt;
 pub(crate) mod relate;
 pub(crate) mod remove;
 pub(crate) mod select;
@@ -25,6 +27,7 @@ pub(crate) mod throw;
 pub(crate) mod update;
 pub(crate) mod r#use;
 
+pub use self::ack::AckStatement;
 pub use self::analyze::AnalyzeStatement;
 pub use self::begin::BeginStatement;
 pub use self::cancel::CancelStatement;
@@ -42,24 +45,27 @@ pub use self::output::OutputStatement;
 pub use self::r#break::BreakStatement;
 pub use self::r#continue::ContinueStatement;
 pub use self::r#use::UseStatement;
+pub use self::reencrypt::ReencryptStatement;
 pub use self::relate::RelateStatement;
 pub use self::select::SelectStatement;
 pub use self::set::SetStatement;
-pub use self::show::ShowStatement;
+pub use self::show::{ShowSince, ShowStatement};
 pub use self::sleep::SleepStatement;
 pub use self::throw::ThrowStatement;
 pub use self::update::UpdateStatement;
 
 pub use self::define::{
-	DefineAnalyzerStatement, DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
-	DefineFunctionStatement, DefineIndexStatement, DefineModelStatement, DefineNamespaceStatement,
-	DefineParamStatement, DefineScopeStatement, DefineStatement, DefineTableStatement,
-	DefineTokenStatement, DefineUserStatement,
+	DefineAnalyzerStatement, DefineConsumerStatement, DefineDatabaseStatement,
+	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
+	DefineKeyStatement, DefineModelStatement, DefineNamespaceStatement, DefineParamStatement,
+	DefinePolicyStatement, DefineRoleStatement, DefineScopeStatement, DefineStatement,
+	DefineTableStatement, DefineTokenStatement, DefineUserStatement, DefineWebhookStatement,
 };
 
 pub use self::remove::{
-	RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
-	RemoveFunctionStatement, RemoveIndexStatement, RemoveModelStatement, RemoveNamespaceStatement,
-	RemoveParamStatement, RemoveScopeStatement, RemoveStatement, RemoveTableStatement,
-	RemoveTokenStatement, RemoveUserStatement,
+	RemoveAnalyzerStatement, RemoveConsumerStatement, RemoveDatabaseStatement,
+	RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement,
+	RemoveKeyStatement, RemoveModelStatement, RemoveNamespaceStatement, RemoveParamStatement,
+	RemovePolicyStatement, RemoveRoleStatement, RemoveScopeStatement, RemoveStatement,
+	RemoveTableStatement, RemoveTokenStatement, RemoveUserStatement, RemoveWebhookStatement,
 };
@@ -0,0 +1,76 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::{statements::UpdateStatement, Base, Ident, Output, Value, Values};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// ReencryptStatement encrypts the encrypted fields of every record in a table
+// with the latest version of their keys, after the keys have been rotated.
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[revisioned(revision = 1)]
+pub struct ReencryptStatement {
+	pub what: Ident,
+}
+
+impl ReencryptStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Check the table exists
+		run.get_tb(opt.ns(), opt.db(), &self.what).await?;
+		// Check that the keys of the encrypted fields exist
+		let fds = run.all_tb_fields(opt.ns(), opt.db(), &self.what).await?;
+		let fds: Vec<_> = fds.iter().filter(|fd| fd.encrypted.is_some()).collect();
+		for key in fds.iter().filter_map(|fd| fd.encrypted.as_ref()) {
+			ctx.get_keyring().check(key)?;
+		}
+		// Remove the data of the indexes on encrypted fields, which is
+		// rebuilt with the values encrypted with the latest keys
+		for ix in run.all_tb_indexes(opt.ns(), opt.db(), &self.what).await?.iter() {
+			if fds.iter().any(|fd| ix.cols.contains(&fd.name)) {
+				let key = crate::key::index::all::new(opt.ns(), opt.db(), &self.what, &ix.name);
+				run.delp(key, u32::MAX).await?;
+			}
+		}
+		// Release the transaction
+		drop(run);
+		// Force queries to run
+		let opt = &opt.new_with_force(true);
+		// Don't process field queries
+		let opt = &opt.new_with_fields(false);
+		// Don't process event queries
+		let opt = &opt.new_with_events(false);
+		// Don't process table queries
+		let opt = &opt.new_with_tables(false);
+		// Store every record again
+		let stm = UpdateStatement {
+			what: Values(vec![Value::Table(self.what.clone().into())]),
+			output: Some(Output::None),
+			..UpdateStatement::default()
+		};
+		stm.compute(ctx, opt, txn, doc).await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl fmt::Display for ReencryptStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "REENCRYPT TABLE {}", self.what)
+	}
+}
@@ -0,0 +1,63 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::err::Error;
+use crate::iam::{apikey, Action, Level, ResourceKind};
+use crate::sql::{Base, Ident, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[revisioned(revision = 1)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RemoveKeyStatement {
+	pub name: Ident,
+	pub base: Base,
+}
+
+impl RemoveKeyStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Clear the cache
+		run.clear_cache();
+		// Get the definition
+		let (ky, level) = match self.base {
+			Base::Root => (run.get_root_key(&self.name).await?, Level::Root),
+			Base::Ns => {
+				let ky = run.get_ns_key(opt.ns(), &self.name).await?;
+				(ky, Level::Namespace(opt.ns().to_owned()))
+			}
+			Base::Db => {
+				let ky = run.get_db_key(opt.ns(), opt.db(), &self.name).await?;
+				(ky, Level::Database(opt.ns().to_owned(), opt.db().to_owned()))
+			}
+			_ => return Err(Error::InvalidLevel(self.base.to_string())),
+		};
+		// Revoke the secret of the key
+		apikey::release(&mut run, &ky.hash, &level, &self.name).await?;
+		// Delete the definition
+		match self.base {
+			Base::Root => run.del(crate::key::root::ky::new(&self.name)).await?,
+			Base::Ns => run.del(crate::key::namespace::ky::new(opt.ns(), &self.name)).await?,
+			_ => run.del(crate::key::database::ky::new(opt.ns(), opt.db(), &self.name)).await?,
+		}
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for RemoveKeyStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "REMOVE KEY {} ON {}", self.name, self.base)
+	}
+}
@@ -1,30 +1,40 @@
 mod analyzer;
+mod consumer;
 mod database;
 mod event;
 mod field;
 mod function;
 mod index;
+mod key;
 mod model;
 mod namespace;
 mod param;
+mod policy;
+mod role;
 mod scope;
 mod table;
 mod token;
 mod user;
+mod webhook;
 
 pub use analyzer::RemoveAnalyzerStatement;
+pub use consumer::RemoveConsumerStatement;
 pub use database::RemoveDatabaseStatement;
 pub use event::RemoveEventStatement;
 pub use field::RemoveFieldStatement;
 pub use function::RemoveFunctionStatement;
 pub use index::RemoveIndexStatement;
+pub use key::RemoveKeyStatement;
 pub use model::RemoveModelStatement;
 pub use namespace::RemoveNamespaceStatement;
 pub use param::RemoveParamStatement;
+pub use policy::RemovePolicyStatement;
+pub use role::RemoveRoleStatement;
 pub use scope::RemoveScopeStatement;
 pub use table::RemoveTableStatement;
 pub use token::RemoveTokenStatement;
 pub use user::RemoveUserStatement;
+pub use webhook::RemoveWebhookStatement;
 
 use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
@@ -37,7 +47,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 6)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum RemoveStatement {
 	Namespace(RemoveNamespaceStatement),
@@ -53,6 +63,16 @@ pub enum RemoveStatement {
 	Index(RemoveIndexStatement),
 	User(RemoveUserStatement),
 	Model(RemoveModelStatement),
+	#[revision(start = 2)]
+	Consumer(RemoveConsumerStatement),
+	#[revision(start = 3)]
+	Webhook(RemoveWebhookStatement),
+	#[revision(start = 4)]
+	Role(RemoveRoleStatement),
+	#[revision(start = 5)]
+	Policy(RemovePolicyStatement),
+	#[revision(start = 6)]
+	Key(RemoveKeyStatement),
 }
 
 impl RemoveStatement {
@@ -82,6 +102,11 @@ impl RemoveStatement {
 			Self::Analyzer(ref v) => v.compute(ctx, opt, txn).await,
 			Self::User(ref v) => v.compute(ctx, opt, txn).await,
 			Self::Model(ref v) => v.compute(ctx, opt, txn).await,
+			Self::Consumer(ref v) => v.compute(ctx, opt, txn).await,
+			Self::Webhook(ref v) => v.compute(ctx, opt, txn).await,
+			Self::Role(ref v) => v.compute(ctx, opt, txn).await,
+			Self::Policy(ref v) => v.compute(ctx, opt, txn).await,
+			Self::Key(ref v) => v.compute(ctx, opt, txn).await,
 		}
 	}
 }
@@ -102,6 +127,11 @@ impl Display for RemoveStatement {
 			Self::Analyzer(v) => Display::fmt(v, f),
 			Self::User(v) => Display::fmt(v, f),
 			Self::Model(v) => Display::fmt(v, f),
+			Self::Consumer(v) => Display::fmt(v, f),
+			Self::Webhook(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
+			Self::Policy(v) => Display::fmt(v, f),
+			Self::Key(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -3,7 +3,7 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
-use crate::sql::{Base, Datetime, Table, Value};
+use crate::sql::{Base, Datetime, Ident, Table, Value};
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -11,10 +11,13 @@ use std::fmt;
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub enum ShowSince {
 	Timestamp(Datetime),
 	Versionstamp(u64),
+	/// Reads the changes following the last offset committed by this consumer
+	#[revision(start = 2)]
+	Consumer(Ident),
 }
 
 // ShowStatement is used to show changes in a table or database via
@@ -75,6 +78,7 @@ impl fmt::Display for ShowStatement {
 		match self.since {
 			ShowSince::Timestamp(ref v) => write!(f, " SINCE {}", v)?,
 			ShowSince::Versionstamp(ref v) => write!(f, " SINCE {}", v)?,
+			ShowSince::Consumer(ref v) => write!(f, " CONSUMER {}", v)?,
 		}
 		if let Some(ref v) = self.limit {
 			write!(f, " LIMIT {}", v)?
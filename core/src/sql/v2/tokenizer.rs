@@ -5,12 +5,15 @@ use std::fmt::Display;
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub enum Tokenizer {
 	Blank,
 	Camel,
 	Class,
 	Punct,
+	/// Splits on Unicode word boundaries (UAX #29)
+	#[revision(start = 2)]
+	Unicode,
 }
 
 impl Display for Tokenizer {
@@ -20,6 +23,7 @@ impl Display for Tokenizer {
 			Self::Camel => "CAMEL",
 			Self::Class => "CLASS",
 			Self::Punct => "PUNCT",
+			Self::Unicode => "UNICODE",
 		})
 	}
 }
@@ -41,6 +41,8 @@ impl ser::Serializer for Serializer {
 			"Rs256" => Ok(Algorithm::Rs256),
 			"Rs384" => Ok(Algorithm::Rs384),
 			"Rs512" => Ok(Algorithm::Rs512),
+			"Jwks" => Ok(Algorithm::Jwks),
+			"Oidc" => Ok(Algorithm::Oidc),
 			variant => Err(Error::custom(format!("unknown variant `{name}::{variant}`"))),
 		}
 	}
@@ -38,6 +38,9 @@ impl ser::Serializer for Serializer {
 #[derive(Default)]
 pub struct SerializeChangeFeed {
 	expiry: Duration,
+	store_original: bool,
+	max_changes: Option<u64>,
+	max_bytes: Option<u64>,
 }
 
 impl serde::ser::SerializeStruct for SerializeChangeFeed {
@@ -52,6 +55,15 @@ impl serde::ser::SerializeStruct for SerializeChangeFeed {
 			"expiry" => {
 				self.expiry = value.serialize(ser::duration::Serializer.wrap())?;
 			}
+			"store_original" => {
+				self.store_original = value.serialize(ser::primitive::bool::Serializer.wrap())?;
+			}
+			"max_changes" => {
+				self.max_changes = value.serialize(ser::primitive::u64::opt::Serializer.wrap())?;
+			}
+			"max_bytes" => {
+				self.max_bytes = value.serialize(ser::primitive::u64::opt::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!("unexpected field `ChangeFeed::{key}`")));
 			}
@@ -62,6 +74,9 @@ impl serde::ser::SerializeStruct for SerializeChangeFeed {
 	fn end(self) -> Result<Self::Ok, Error> {
 		Ok(ChangeFeed {
 			expiry: self.expiry,
+			store_original: self.store_original,
+			max_changes: self.max_changes,
+			max_bytes: self.max_bytes,
 		})
 	}
 }
@@ -76,4 +91,25 @@ mod tests {
 		let value: ChangeFeed = stmt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(value, stmt);
 	}
+
+	#[test]
+	fn store_original() {
+		let stmt = ChangeFeed {
+			store_original: true,
+			..Default::default()
+		};
+		let value: ChangeFeed = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn retention() {
+		let stmt = ChangeFeed {
+			max_changes: Some(100),
+			max_bytes: Some(1024),
+			..Default::default()
+		};
+		let value: ChangeFeed = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
 }
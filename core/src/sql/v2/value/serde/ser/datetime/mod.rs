@@ -7,6 +7,8 @@ use serde::ser::Impossible;
 use serde::Serialize;
 use std::fmt::Display;
 
+pub(super) mod opt;
+
 pub(super) struct Serializer;
 
 impl ser::Serializer for Serializer {
@@ -0,0 +1,55 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Datetime;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Option<Datetime>;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Option<Datetime>, Error>;
+	type SerializeTuple = Impossible<Option<Datetime>, Error>;
+	type SerializeTupleStruct = Impossible<Option<Datetime>, Error>;
+	type SerializeTupleVariant = Impossible<Option<Datetime>, Error>;
+	type SerializeMap = Impossible<Option<Datetime>, Error>;
+	type SerializeStruct = Impossible<Option<Datetime>, Error>;
+	type SerializeStructVariant = Impossible<Option<Datetime>, Error>;
+
+	const EXPECTED: &'static str = "an `Option<Datetime>`";
+
+	#[inline]
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	#[inline]
+	fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(Some(Datetime(value.serialize(ser::datetime::Serializer.wrap())?)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+
+	#[test]
+	fn none() {
+		let option: Option<Datetime> = None;
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some() {
+		let option = Some(Datetime::default());
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+}
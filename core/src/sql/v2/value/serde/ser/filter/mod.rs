@@ -3,6 +3,7 @@ pub(super) mod vec;
 use crate::err::Error;
 use crate::sql::filter::Filter;
 use crate::sql::value::serde::ser;
+use crate::sql::Object;
 use ser::Serializer as _;
 use serde::ser::Error as _;
 use serde::ser::Impossible;
@@ -52,6 +53,13 @@ impl ser::Serializer for Serializer {
 	{
 		match variant {
 			"Snowball" => Ok(Filter::Snowball(value.serialize(ser::language::Serializer.wrap())?)),
+			"Stopwords" => {
+				Ok(Filter::Stopwords(value.serialize(ser::stopwords::Serializer.wrap())?))
+			}
+			"Synonyms" => Ok(Filter::Synonyms(value.serialize(ser::synonyms::Serializer.wrap())?)),
+			"Mapping" => {
+				Ok(Filter::Mapping(Object(value.serialize(ser::value::map::Serializer.wrap())?)))
+			}
 			variant => {
 				Err(Error::custom(format!("unexpected newtype variant `{name}::{variant}`")))
 			}
@@ -129,7 +137,9 @@ impl serde::ser::SerializeTupleVariant for SerializeFilter {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::sql::filter::Stopwords;
 	use crate::sql::language::Language;
+	use crate::sql::Value;
 
 	#[test]
 	fn ascii() {
@@ -151,4 +161,20 @@ mod tests {
 		let serialized = filter.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(filter, serialized);
 	}
+
+	#[test]
+	fn stopwords() {
+		let filter = Filter::Stopwords(Stopwords::Language(Language::French));
+		let serialized = filter.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(filter, serialized);
+	}
+
+	#[test]
+	fn mapping() {
+		let filter = Filter::Mapping(Object::from(map! {
+			"ß".to_string() => Value::from("ss"),
+		}));
+		let serialized = filter.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(filter, serialized);
+	}
 }
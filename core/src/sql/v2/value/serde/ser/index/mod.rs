@@ -34,6 +34,7 @@ impl ser::Serializer for Serializer {
 		match variant {
 			"Idx" => Ok(Index::Idx),
 			"Uniq" => Ok(Index::Uniq),
+			"UniqDeferred" => Ok(Index::UniqDeferred),
 			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
 		}
 	}
@@ -80,6 +81,13 @@ mod tests {
 		assert_eq!(idx, serialized);
 	}
 
+	#[test]
+	fn uniq_deferred() {
+		let idx = Index::UniqDeferred;
+		let serialized = idx.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(idx, serialized);
+	}
+
 	#[test]
 	fn search() {
 		let idx = Index::Search(SearchParams {
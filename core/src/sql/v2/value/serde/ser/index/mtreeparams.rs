@@ -1,5 +1,5 @@
 use crate::err::Error;
-use crate::sql::index::{Distance, MTreeParams, VectorType};
+use crate::sql::index::{Distance, MTreeParams, Quantization, VectorType};
 use crate::sql::value::serde::ser;
 use ser::Serializer as _;
 use serde::ser::Error as _;
@@ -53,6 +53,7 @@ pub(super) struct SerializeMTree {
 	doc_ids_order: u32,
 	doc_ids_cache: u32,
 	mtree_cache: u32,
+	quantization: Option<Quantization>,
 }
 impl serde::ser::SerializeStruct for SerializeMTree {
 	type Ok = MTreeParams;
@@ -84,6 +85,9 @@ impl serde::ser::SerializeStruct for SerializeMTree {
 			"mtree_cache" => {
 				self.mtree_cache = value.serialize(ser::primitive::u32::Serializer.wrap())?;
 			}
+			"quantization" => {
+				self.quantization = value.serialize(ser::quantization::opt::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!("unexpected field `MTreeParams {{ {key} }}`")));
 			}
@@ -100,6 +104,7 @@ impl serde::ser::SerializeStruct for SerializeMTree {
 			doc_ids_order: self.doc_ids_order,
 			doc_ids_cache: self.doc_ids_cache,
 			mtree_cache: self.mtree_cache,
+			quantization: self.quantization,
 		})
 	}
 }
@@ -114,6 +119,7 @@ fn mtree_params() {
 		doc_ids_order: 3,
 		doc_ids_cache: 4,
 		mtree_cache: 5,
+		quantization: Some(Quantization::Scalar),
 	};
 	let serialized = params.serialize(Serializer.wrap()).unwrap();
 	assert_eq!(params, serialized);
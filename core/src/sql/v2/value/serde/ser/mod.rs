@@ -39,14 +39,17 @@ mod part;
 mod permission;
 mod permissions;
 mod primitive;
+mod quantization;
 mod range;
 mod scoring;
 mod split;
 mod start;
 mod statement;
+mod stopwords;
 mod strand;
 mod string;
 mod subquery;
+mod synonyms;
 mod table;
 mod thing;
 mod timeout;
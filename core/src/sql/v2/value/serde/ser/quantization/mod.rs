@@ -0,0 +1,50 @@
+pub(super) mod opt;
+
+use crate::err::Error;
+use crate::sql::index::Quantization;
+use crate::sql::value::serde::ser;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Quantization;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Quantization, Error>;
+	type SerializeTuple = Impossible<Quantization, Error>;
+	type SerializeTupleStruct = Impossible<Quantization, Error>;
+	type SerializeTupleVariant = Impossible<Quantization, Error>;
+	type SerializeMap = Impossible<Quantization, Error>;
+	type SerializeStruct = Impossible<Quantization, Error>;
+	type SerializeStructVariant = Impossible<Quantization, Error>;
+	const EXPECTED: &'static str = "an enum `Quantization`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Scalar" => Ok(Quantization::Scalar),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn scalar() {
+		let q = Quantization::Scalar;
+		let serialized = q.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(q, serialized);
+	}
+}
@@ -50,6 +50,8 @@ pub struct SerializeDefineFieldStatement {
 	default: Option<Value>,
 	permissions: Permissions,
 	comment: Option<Strand>,
+	encrypted: Option<Ident>,
+	deterministic: bool,
 }
 
 impl serde::ser::SerializeStruct for SerializeDefineFieldStatement {
@@ -91,6 +93,12 @@ impl serde::ser::SerializeStruct for SerializeDefineFieldStatement {
 			"comment" => {
 				self.comment = value.serialize(ser::strand::opt::Serializer.wrap())?;
 			}
+			"encrypted" => {
+				self.encrypted = value.serialize(ser::string::opt::Serializer.wrap())?.map(Ident);
+			}
+			"deterministic" => {
+				self.deterministic = value.serialize(ser::primitive::bool::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!(
 					"unexpected field `DefineFieldStatement::{key}`"
@@ -112,6 +120,8 @@ impl serde::ser::SerializeStruct for SerializeDefineFieldStatement {
 			default: self.default,
 			permissions: self.permissions,
 			comment: self.comment,
+			encrypted: self.encrypted,
+			deterministic: self.deterministic,
 		})
 	}
 }
@@ -2,6 +2,7 @@ use crate::err::Error;
 use crate::sql::index::Index;
 use crate::sql::statements::DefineIndexStatement;
 use crate::sql::value::serde::ser;
+use crate::sql::Cond;
 use crate::sql::Ident;
 use crate::sql::Idioms;
 use crate::sql::Strand;
@@ -43,6 +44,8 @@ pub struct SerializeDefineIndexStatement {
 	cols: Idioms,
 	index: Index,
 	comment: Option<Strand>,
+	concurrently: bool,
+	cond: Option<Cond>,
 }
 
 impl serde::ser::SerializeStruct for SerializeDefineIndexStatement {
@@ -69,6 +72,12 @@ impl serde::ser::SerializeStruct for SerializeDefineIndexStatement {
 			"comment" => {
 				self.comment = value.serialize(ser::strand::opt::Serializer.wrap())?;
 			}
+			"concurrently" => {
+				self.concurrently = value.serialize(ser::primitive::bool::Serializer.wrap())?;
+			}
+			"cond" => {
+				self.cond = value.serialize(ser::cond::opt::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!(
 					"unexpected field `DefineIndexStatement::{key}`"
@@ -85,6 +94,8 @@ impl serde::ser::SerializeStruct for SerializeDefineIndexStatement {
 			cols: self.cols,
 			index: self.index,
 			comment: self.comment,
+			concurrently: self.concurrently,
+			cond: self.cond,
 		})
 	}
 }
@@ -99,4 +110,24 @@ mod tests {
 		let value: DefineIndexStatement = stmt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(value, stmt);
 	}
+
+	#[test]
+	fn concurrently() {
+		let stmt = DefineIndexStatement {
+			concurrently: true,
+			..Default::default()
+		};
+		let value: DefineIndexStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_cond() {
+		let stmt = DefineIndexStatement {
+			cond: Some(Default::default()),
+			..Default::default()
+		};
+		let value: DefineIndexStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
 }
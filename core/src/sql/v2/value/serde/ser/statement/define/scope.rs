@@ -44,6 +44,7 @@ pub struct SerializeDefineScopeStatement {
 	signup: Option<Value>,
 	signin: Option<Value>,
 	comment: Option<Strand>,
+	certificate: Option<Value>,
 }
 
 impl serde::ser::SerializeStruct for SerializeDefineScopeStatement {
@@ -74,6 +75,9 @@ impl serde::ser::SerializeStruct for SerializeDefineScopeStatement {
 			"comment" => {
 				self.comment = value.serialize(ser::strand::opt::Serializer.wrap())?;
 			}
+			"certificate" => {
+				self.certificate = value.serialize(ser::value::opt::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!(
 					"unexpected field `DefineScopeStatement::{key}`"
@@ -91,6 +95,7 @@ impl serde::ser::SerializeStruct for SerializeDefineScopeStatement {
 			signup: self.signup,
 			signin: self.signin,
 			comment: self.comment,
+			certificate: self.certificate,
 		})
 	}
 }
@@ -4,7 +4,9 @@ use crate::sql::value::serde::ser;
 use crate::sql::Algorithm;
 use crate::sql::Base;
 use crate::sql::Ident;
+use crate::sql::Object;
 use crate::sql::Strand;
+use crate::sql::Value;
 use ser::Serializer as _;
 use serde::ser::Error as _;
 use serde::ser::Impossible;
@@ -43,6 +45,8 @@ pub struct SerializeDefineTokenStatement {
 	kind: Algorithm,
 	code: String,
 	comment: Option<Strand>,
+	audience: Option<Strand>,
+	claims: Option<Object>,
 }
 
 impl serde::ser::SerializeStruct for SerializeDefineTokenStatement {
@@ -69,6 +73,18 @@ impl serde::ser::SerializeStruct for SerializeDefineTokenStatement {
 			"comment" => {
 				self.comment = value.serialize(ser::strand::opt::Serializer.wrap())?;
 			}
+			"audience" => {
+				self.audience = value.serialize(ser::strand::opt::Serializer.wrap())?;
+			}
+			"claims" => {
+				self.claims = match value.serialize(ser::value::opt::Serializer.wrap())? {
+					Some(Value::Object(v)) => Some(v),
+					Some(v) => {
+						return Err(Error::custom(format!("expected an object, found `{v}`")));
+					}
+					None => None,
+				};
+			}
 			key => {
 				return Err(Error::custom(format!(
 					"unexpected field `DefineTokenStatement::{key}`"
@@ -85,6 +101,8 @@ impl serde::ser::SerializeStruct for SerializeDefineTokenStatement {
 			kind: self.kind,
 			code: self.code,
 			comment: self.comment,
+			audience: self.audience,
+			claims: self.claims,
 		})
 	}
 }
@@ -99,4 +117,16 @@ mod tests {
 		let value: DefineTokenStatement = stmt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(value, stmt);
 	}
+
+	#[test]
+	fn with_claims() {
+		let stmt = DefineTokenStatement {
+			kind: Algorithm::Oidc,
+			audience: Some("surrealdb".into()),
+			claims: Some(Object::from(map! { "id".to_string() => Value::from("sub") })),
+			..Default::default()
+		};
+		let value: DefineTokenStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
 }
@@ -2,6 +2,7 @@ use crate::err::Error;
 use crate::sql::statements::DefineUserStatement;
 use crate::sql::value::serde::ser;
 use crate::sql::Base;
+use crate::sql::Datetime;
 use crate::sql::Ident;
 use crate::sql::Strand;
 use ser::Serializer as _;
@@ -43,6 +44,8 @@ pub struct SerializeDefineUserStatement {
 	code: String,
 	roles: Vec<Ident>,
 	comment: Option<Strand>,
+	changed: Option<Datetime>,
+	certificate: Option<Strand>,
 }
 
 impl serde::ser::SerializeStruct for SerializeDefineUserStatement {
@@ -72,6 +75,12 @@ impl serde::ser::SerializeStruct for SerializeDefineUserStatement {
 			"comment" => {
 				self.comment = value.serialize(ser::strand::opt::Serializer.wrap())?;
 			}
+			"changed" => {
+				self.changed = value.serialize(ser::datetime::opt::Serializer.wrap())?;
+			}
+			"certificate" => {
+				self.certificate = value.serialize(ser::strand::opt::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!(
 					"unexpected field `DefineUserStatement::{key}`"
@@ -89,6 +98,9 @@ impl serde::ser::SerializeStruct for SerializeDefineUserStatement {
 			code: self.code,
 			roles: self.roles,
 			comment: self.comment,
+			changed: self.changed,
+			certificate: self.certificate,
+			..Default::default()
 		})
 	}
 }
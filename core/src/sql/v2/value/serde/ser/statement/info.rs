@@ -68,7 +68,10 @@ impl ser::Serializer for Serializer {
 		_len: usize,
 	) -> Result<Self::SerializeTupleVariant, Self::Error> {
 		match variant {
-			"User" => Ok(SerializeInfoStatement::default()),
+			"User" | "Index" | "Webhook" | "Key" => Ok(SerializeInfoStatement {
+				variant,
+				..Default::default()
+			}),
 			variant => Err(Error::custom(format!("unexpected tuple variant `{name}::{variant}`"))),
 		}
 	}
@@ -76,8 +79,11 @@ impl ser::Serializer for Serializer {
 
 #[derive(Default)]
 pub(super) struct SerializeInfoStatement {
+	variant: &'static str,
 	index: usize,
-	tuple: (Option<Ident>, Option<Base>),
+	ident: Option<Ident>,
+	base: Option<Base>,
+	table: Option<Ident>,
 }
 
 impl serde::ser::SerializeTupleVariant for SerializeInfoStatement {
@@ -88,16 +94,19 @@ impl serde::ser::SerializeTupleVariant for SerializeInfoStatement {
 	where
 		T: Serialize + ?Sized,
 	{
-		match self.index {
-			0 => {
-				self.tuple.0 = Some(Ident(value.serialize(ser::string::Serializer.wrap())?));
+		match (self.variant, self.index) {
+			(_, 0) => {
+				self.ident = Some(Ident(value.serialize(ser::string::Serializer.wrap())?));
 			}
-			1 => {
-				self.tuple.1 = value.serialize(ser::base::opt::Serializer.wrap())?;
+			("User" | "Key", 1) => {
+				self.base = value.serialize(ser::base::opt::Serializer.wrap())?;
 			}
-			index => {
+			("Index" | "Webhook", 1) => {
+				self.table = Some(Ident(value.serialize(ser::string::Serializer.wrap())?));
+			}
+			(variant, index) => {
 				return Err(Error::custom(format!(
-					"unexpected `InfoStatement::User` index `{index}`"
+					"unexpected `InfoStatement::{variant}` index `{index}`"
 				)));
 			}
 		}
@@ -106,9 +115,14 @@ impl serde::ser::SerializeTupleVariant for SerializeInfoStatement {
 	}
 
 	fn end(self) -> Result<Self::Ok, Self::Error> {
-		match self.tuple.0 {
-			Some(ident) => Ok(InfoStatement::User(ident, self.tuple.1)),
-			None => Err(Error::custom("`InfoStatement::User` missing required value(s)")),
+		match (self.variant, self.ident, self.table) {
+			("User", Some(ident), _) => Ok(InfoStatement::User(ident, self.base)),
+			("Index", Some(index), Some(table)) => Ok(InfoStatement::Index(index, table)),
+			("Webhook", Some(webhook), Some(table)) => Ok(InfoStatement::Webhook(webhook, table)),
+			("Key", Some(key), _) => Ok(InfoStatement::Key(key, self.base)),
+			(variant, ..) => {
+				Err(Error::custom(format!("`InfoStatement::{variant}` missing required value(s)")))
+			}
 		}
 	}
 }
@@ -158,4 +172,25 @@ mod tests {
 		let serialized = stmt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(stmt, serialized);
 	}
+
+	#[test]
+	fn index() {
+		let stmt = InfoStatement::Index(Default::default(), Default::default());
+		let serialized = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(stmt, serialized);
+	}
+
+	#[test]
+	fn webhook() {
+		let stmt = InfoStatement::Webhook(Default::default(), Default::default());
+		let serialized = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(stmt, serialized);
+	}
+
+	#[test]
+	fn key() {
+		let stmt = InfoStatement::Key(Default::default(), Default::default());
+		let serialized = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(stmt, serialized);
+	}
 }
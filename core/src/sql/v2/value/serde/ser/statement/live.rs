@@ -46,6 +46,7 @@ pub struct SerializeLiveStatement {
 	what: Value,
 	cond: Option<Cond>,
 	fetch: Option<Fetchs>,
+	before: bool,
 	archived: Option<Uuid>,
 	session: Option<Value>,
 	auth: Option<Auth>,
@@ -78,6 +79,9 @@ impl serde::ser::SerializeStruct for SerializeLiveStatement {
 			"fetch" => {
 				self.fetch = value.serialize(ser::fetchs::opt::Serializer.wrap())?;
 			}
+			"before" => {
+				self.before = value.serialize(ser::primitive::bool::Serializer.wrap())?;
+			}
 			"archived" => {
 				self.archived = value.serialize(ser::uuid::opt::Serializer.wrap())?.map(Uuid);
 			}
@@ -102,6 +106,7 @@ impl serde::ser::SerializeStruct for SerializeLiveStatement {
 			what: self.what,
 			cond: self.cond,
 			fetch: self.fetch,
+			before: self.before,
 			archived: self.archived,
 			session: None,
 			auth: None,
@@ -119,4 +124,14 @@ mod tests {
 		let value: LiveStatement = stmt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(value, stmt);
 	}
+
+	#[test]
+	fn with_before() {
+		let stmt = LiveStatement {
+			before: true,
+			..Default::default()
+		};
+		let value: LiveStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
 }
@@ -2,6 +2,7 @@ use crate::err::Error;
 use crate::sql::datetime::Datetime;
 use crate::sql::statements::show::ShowSince;
 use crate::sql::value::serde::ser;
+use crate::sql::Ident;
 use serde::ser::Error as _;
 use serde::ser::Impossible;
 use serde::ser::Serialize;
@@ -40,6 +41,9 @@ impl ser::Serializer for Serializer {
 			"Versionstamp" => Ok(ShowSince::Versionstamp(
 				value.serialize(ser::primitive::u64::Serializer.wrap())?,
 			)),
+			"Consumer" => {
+				Ok(ShowSince::Consumer(Ident(value.serialize(ser::string::Serializer.wrap())?)))
+			}
 			variant => {
 				Err(Error::custom(format!("unexpected newtype variant `{name}::{variant}`")))
 			}
@@ -65,4 +69,11 @@ mod tests {
 		let serialized = stmt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(stmt, serialized);
 	}
+
+	#[test]
+	fn consumer() {
+		let stmt = ShowSince::Consumer(Ident::from("reader"));
+		let serialized = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(stmt, serialized);
+	}
 }
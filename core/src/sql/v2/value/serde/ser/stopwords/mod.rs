@@ -0,0 +1,73 @@
+use crate::err::Error;
+use crate::sql::filter::Stopwords;
+use crate::sql::value::serde::ser;
+use crate::sql::Strand;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Stopwords;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Stopwords, Error>;
+	type SerializeTuple = Impossible<Stopwords, Error>;
+	type SerializeTupleStruct = Impossible<Stopwords, Error>;
+	type SerializeTupleVariant = Impossible<Stopwords, Error>;
+	type SerializeMap = Impossible<Stopwords, Error>;
+	type SerializeStruct = Impossible<Stopwords, Error>;
+	type SerializeStructVariant = Impossible<Stopwords, Error>;
+
+	const EXPECTED: &'static str = "an enum `Stopwords`";
+
+	#[inline]
+	fn serialize_newtype_variant<T>(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+		value: &T,
+	) -> Result<Self::Ok, Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		match variant {
+			"Language" => {
+				Ok(Stopwords::Language(value.serialize(ser::language::Serializer.wrap())?))
+			}
+			"Custom" => Ok(Stopwords::Custom(
+				value
+					.serialize(ser::string::vec::Serializer.wrap())?
+					.into_iter()
+					.map(Strand)
+					.collect(),
+			)),
+			variant => {
+				Err(Error::custom(format!("unexpected newtype variant `{name}::{variant}`")))
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::language::Language;
+	use ser::Serializer as _;
+
+	#[test]
+	fn language() {
+		let stopwords = Stopwords::Language(Language::English);
+		let serialized = stopwords.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(stopwords, serialized);
+	}
+
+	#[test]
+	fn custom() {
+		let stopwords = Stopwords::Custom(vec![Strand::from("a"), Strand::from("the")]);
+		let serialized = stopwords.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(stopwords, serialized);
+	}
+}
@@ -0,0 +1,69 @@
+use crate::err::Error;
+use crate::sql::filter::Synonyms;
+use crate::sql::value::serde::ser;
+use crate::sql::{Object, Table};
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Synonyms;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Synonyms, Error>;
+	type SerializeTuple = Impossible<Synonyms, Error>;
+	type SerializeTupleStruct = Impossible<Synonyms, Error>;
+	type SerializeTupleVariant = Impossible<Synonyms, Error>;
+	type SerializeMap = Impossible<Synonyms, Error>;
+	type SerializeStruct = Impossible<Synonyms, Error>;
+	type SerializeStructVariant = Impossible<Synonyms, Error>;
+
+	const EXPECTED: &'static str = "an enum `Synonyms`";
+
+	#[inline]
+	fn serialize_newtype_variant<T>(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+		value: &T,
+	) -> Result<Self::Ok, Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		match variant {
+			"Table" => Ok(Synonyms::Table(Table(value.serialize(ser::string::Serializer.wrap())?))),
+			"Map" => {
+				Ok(Synonyms::Map(Object(value.serialize(ser::value::map::Serializer.wrap())?)))
+			}
+			variant => {
+				Err(Error::custom(format!("unexpected newtype variant `{name}::{variant}`")))
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::Value;
+	use ser::Serializer as _;
+
+	#[test]
+	fn table() {
+		let synonyms = Synonyms::Table(Table::from("synonym"));
+		let serialized = synonyms.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(synonyms, serialized);
+	}
+
+	#[test]
+	fn map() {
+		let synonyms = Synonyms::Map(Object::from(map! {
+			"car".to_string() => Value::from(vec!["auto", "automobile"]),
+		}));
+		let serialized = synonyms.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(synonyms, serialized);
+	}
+}
@@ -34,6 +34,7 @@ impl ser::Serializer for Serializer {
 			"Camel" => Ok(Tokenizer::Camel),
 			"Class" => Ok(Tokenizer::Class),
 			"Punct" => Ok(Tokenizer::Punct),
+			"Unicode" => Ok(Tokenizer::Unicode),
 			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
 		}
 	}
@@ -72,4 +73,11 @@ mod tests {
 		let serialized = tokenizer.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(tokenizer, serialized);
 	}
+
+	#[test]
+	fn unicode() {
+		let tokenizer = Tokenizer::Unicode;
+		let serialized = tokenizer.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(tokenizer, serialized);
+	}
 }
@@ -32,6 +32,8 @@ impl ser::Serializer for Serializer {
 			"I64" => Ok(VectorType::I64),
 			"I32" => Ok(VectorType::I32),
 			"I16" => Ok(VectorType::I16),
+			"F16" => Ok(VectorType::F16),
+			"Binary" => Ok(VectorType::Binary),
 			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
 		}
 	}
@@ -76,4 +78,18 @@ mod tests {
 		let serialized = vt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(vt, serialized);
 	}
+
+	#[test]
+	fn vector_type_f16() {
+		let vt = VectorType::F16;
+		let serialized = vt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(vt, serialized);
+	}
+
+	#[test]
+	fn vector_type_binary() {
+		let vt = VectorType::Binary;
+		let serialized = vt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(vt, serialized);
+	}
 }
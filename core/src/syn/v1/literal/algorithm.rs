@@ -19,5 +19,7 @@ pub fn algorithm(i: &str) -> IResult<&str, Algorithm> {
 		value(Algorithm::Rs512, tag("RS512")),
 		#[cfg(feature = "sql2")]
 		value(Algorithm::Jwks, tag("JWKS")), // Not an algorithm.
+		#[cfg(feature = "sql2")]
+		value(Algorithm::Oidc, tag("OIDC")), // Not an algorithm.
 	))(i)
 }
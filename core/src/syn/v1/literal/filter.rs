@@ -1,9 +1,19 @@
+#[cfg(feature = "sql2")]
+use super::super::{
+	common::{closebracket, openbracket},
+	literal::{strand, table},
+	value::object,
+};
 use super::super::{
 	common::{closeparentheses, commas, openparentheses},
 	literal::language::language,
 	IResult,
 };
 use crate::sql::filter::Filter;
+#[cfg(feature = "sql2")]
+use crate::sql::filter::{Stopwords, Synonyms};
+#[cfg(feature = "sql2")]
+use nom::combinator::map;
 use nom::{
 	branch::alt, bytes::complete::tag_no_case, character::complete::u16, combinator::cut,
 	multi::separated_list1,
@@ -58,8 +68,65 @@ fn uppercase(i: &str) -> IResult<&str, Filter> {
 	Ok((i, Filter::Uppercase))
 }
 
+#[cfg(feature = "sql2")]
+fn stopwords(i: &str) -> IResult<&str, Filter> {
+	let (i, _) = tag_no_case("STOPWORDS")(i)?;
+	cut(|i| {
+		let (i, _) = openparentheses(i)?;
+		let (i, stopwords) = alt((
+			map(language, Stopwords::Language),
+			map(
+				|i| {
+					let (i, _) = openbracket(i)?;
+					let (i, v) = separated_list1(commas, strand)(i)?;
+					let (i, _) = closebracket(i)?;
+					Ok((i, v))
+				},
+				Stopwords::Custom,
+			),
+		))(i)?;
+		let (i, _) = closeparentheses(i)?;
+		Ok((i, Filter::Stopwords(stopwords)))
+	})(i)
+}
+
+#[cfg(feature = "sql2")]
+fn synonyms(i: &str) -> IResult<&str, Filter> {
+	let (i, _) = tag_no_case("SYNONYMS")(i)?;
+	cut(|i| {
+		let (i, _) = openparentheses(i)?;
+		let (i, synonyms) = alt((map(object, Synonyms::Map), map(table, Synonyms::Table)))(i)?;
+		let (i, _) = closeparentheses(i)?;
+		Ok((i, Filter::Synonyms(synonyms)))
+	})(i)
+}
+
+#[cfg(feature = "sql2")]
+fn mapping(i: &str) -> IResult<&str, Filter> {
+	let (i, _) = tag_no_case("MAPPING")(i)?;
+	cut(|i| {
+		let (i, _) = openparentheses(i)?;
+		let (i, mapping) = object(i)?;
+		let (i, _) = closeparentheses(i)?;
+		Ok((i, Filter::Mapping(mapping)))
+	})(i)
+}
+
 fn filter(i: &str) -> IResult<&str, Filter> {
-	alt((ascii, edgengram, lowercase, ngram, snowball, uppercase))(i)
+	alt((
+		ascii,
+		edgengram,
+		lowercase,
+		ngram,
+		snowball,
+		uppercase,
+		#[cfg(feature = "sql2")]
+		stopwords,
+		#[cfg(feature = "sql2")]
+		synonyms,
+		#[cfg(feature = "sql2")]
+		mapping,
+	))(i)
 }
 
 pub fn filters(i: &str) -> IResult<&str, Vec<Filter>> {
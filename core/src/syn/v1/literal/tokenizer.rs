@@ -8,6 +8,8 @@ pub fn tokenizer(i: &str) -> IResult<&str, Tokenizer> {
 		value(Tokenizer::Camel, tag_no_case("CAMEL")),
 		value(Tokenizer::Class, tag_no_case("CLASS")),
 		value(Tokenizer::Punct, tag_no_case("PUNCT")),
+		#[cfg(feature = "sql2")]
+		value(Tokenizer::Unicode, tag_no_case("UNICODE")),
 	))(i)?;
 	Ok((i, t))
 }
@@ -3,11 +3,15 @@ use super::super::{
 	literal::{ident, scoring},
 	IResult,
 };
+#[cfg(feature = "sql2")]
+use crate::sql::index::Quantization;
 use crate::sql::{
 	index::{Distance, MTreeParams, SearchParams, VectorType},
 	Ident, Index,
 };
 
+#[cfg(feature = "sql2")]
+use nom::sequence::tuple;
 use nom::{
 	branch::alt,
 	bytes::complete::{tag, tag_no_case},
@@ -21,6 +25,10 @@ pub fn index(i: &str) -> IResult<&str, Index> {
 
 pub fn unique(i: &str) -> IResult<&str, Index> {
 	let (i, _) = tag_no_case("UNIQUE")(i)?;
+	#[cfg(feature = "sql2")]
+	if let (i, Some(_)) = opt(tuple((shouldbespace, tag_no_case("DEFERRED"))))(i)? {
+		return Ok((i, Index::UniqDeferred));
+	}
 	Ok((i, Index::Uniq))
 }
 
@@ -119,6 +127,8 @@ pub fn mtree_distance(i: &str) -> IResult<&str, Distance> {
 	alt((
 		map(tag_no_case("EUCLIDEAN"), |_| Distance::Euclidean),
 		map(tag_no_case("MANHATTAN"), |_| Distance::Manhattan),
+		#[cfg(feature = "sql2")]
+		map(tag_no_case("HAMMING"), |_| Distance::Hamming),
 		minkowski,
 	))(i)
 }
@@ -140,9 +150,21 @@ pub fn vector_type(i: &str) -> IResult<&str, VectorType> {
 		map(tag_no_case("I64"), |_| VectorType::I64),
 		map(tag_no_case("I32"), |_| VectorType::I32),
 		map(tag_no_case("I16"), |_| VectorType::I16),
+		#[cfg(feature = "sql2")]
+		map(tag_no_case("F16"), |_| VectorType::F16),
+		#[cfg(feature = "sql2")]
+		map(tag_no_case("BINARY"), |_| VectorType::Binary),
 	))(i)
 }
 
+#[cfg(feature = "sql2")]
+pub fn quantization(i: &str) -> IResult<&str, Quantization> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("QUANTIZE")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	map(tag_no_case("SCALAR"), |_| Quantization::Scalar)(i)
+}
+
 pub fn dimension(i: &str) -> IResult<&str, u16> {
 	let (i, _) = mightbespace(i)?;
 	let (i, _) = tag_no_case("DIMENSION")(i)?;
@@ -174,6 +196,8 @@ pub fn mtree(i: &str) -> IResult<&str, Index> {
 		let (i, doc_ids_order) = opt(doc_ids_order)(i)?;
 		let (i, doc_ids_cache) = opt(doc_ids_cache)(i)?;
 		let (i, mtree_cache) = opt(mtree_cache)(i)?;
+		#[cfg(feature = "sql2")]
+		let (i, quantization) = opt(quantization)(i)?;
 		Ok((
 			i,
 			Index::MTree(MTreeParams {
@@ -184,6 +208,8 @@ pub fn mtree(i: &str) -> IResult<&str, Index> {
 				doc_ids_order: doc_ids_order.unwrap_or(100),
 				doc_ids_cache: doc_ids_cache.unwrap_or(100),
 				mtree_cache: mtree_cache.unwrap_or(100),
+				#[cfg(feature = "sql2")]
+				quantization,
 			}),
 		))
 	})(i)
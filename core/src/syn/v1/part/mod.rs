@@ -67,14 +67,44 @@ pub fn changefeed(i: &str) -> IResult<&str, ChangeFeed> {
 	let (i, _) = tag_no_case("CHANGEFEED")(i)?;
 	let (i, _) = shouldbespace(i)?;
 	let (i, v) = cut(duration)(i)?;
+	#[cfg(feature = "sql2")]
+	let (i, max_changes) = opt(changefeed_limit("CHANGES"))(i)?;
+	#[cfg(feature = "sql2")]
+	let (i, max_bytes) = opt(changefeed_limit("BYTES"))(i)?;
+	#[cfg(feature = "sql2")]
+	let (i, store_original) = opt(tuple((
+		shouldbespace,
+		tag_no_case("INCLUDE"),
+		shouldbespace,
+		tag_no_case("ORIGINAL"),
+	)))(i)?;
 	Ok((
 		i,
 		ChangeFeed {
 			expiry: v.0,
+			#[cfg(feature = "sql2")]
+			store_original: store_original.is_some(),
+			#[cfg(feature = "sql2")]
+			max_changes,
+			#[cfg(feature = "sql2")]
+			max_bytes,
 		},
 	))
 }
 
+#[cfg(feature = "sql2")]
+fn changefeed_limit(unit: &'static str) -> impl Fn(&str) -> IResult<&str, u64> {
+	move |i| {
+		let (i, _) = shouldbespace(i)?;
+		let (i, _) = tag_no_case("LIMIT")(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, v) = cut(super::common::take_u64)(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, _) = tag_no_case(unit)(i)?;
+		Ok((i, v))
+	}
+}
+
 pub fn cond(i: &str) -> IResult<&str, Cond> {
 	let (i, _) = tag_no_case("WHERE")(i)?;
 	let (i, _) = shouldbespace(i)?;
@@ -256,7 +286,49 @@ mod tests {
 		assert_eq!(
 			out,
 			ChangeFeed {
-				expiry: time::Duration::from_secs(3600)
+				expiry: time::Duration::from_secs(3600),
+				#[cfg(feature = "sql2")]
+				store_original: false,
+				#[cfg(feature = "sql2")]
+				max_changes: None,
+				#[cfg(feature = "sql2")]
+				max_bytes: None,
+			}
+		);
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn changefeed_include_original() {
+		let sql = "CHANGEFEED 1h INCLUDE ORIGINAL";
+		let res = changefeed(sql);
+		let out = res.unwrap().1;
+		assert_eq!("CHANGEFEED 1h INCLUDE ORIGINAL", format!("{}", out));
+		assert_eq!(
+			out,
+			ChangeFeed {
+				expiry: time::Duration::from_secs(3600),
+				store_original: true,
+				max_changes: None,
+				max_bytes: None,
+			}
+		);
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn changefeed_limits() {
+		let sql = "CHANGEFEED 1h LIMIT 100 CHANGES LIMIT 1048576 BYTES INCLUDE ORIGINAL";
+		let res = changefeed(sql);
+		let out = res.unwrap().1;
+		assert_eq!(sql, format!("{}", out));
+		assert_eq!(
+			out,
+			ChangeFeed {
+				expiry: time::Duration::from_secs(3600),
+				store_original: true,
+				max_changes: Some(100),
+				max_bytes: Some(1048576),
 			}
 		);
 	}
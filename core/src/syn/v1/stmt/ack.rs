@@ -0,0 +1,49 @@
+use super::super::{comment::shouldbespace, error::expect_tag_no_case, literal::ident, IResult};
+use crate::sql::statements::AckStatement;
+use nom::{
+	bytes::complete::tag_no_case,
+	character::complete::u64,
+	combinator::{cut, opt},
+	sequence::tuple,
+};
+
+pub fn ack(i: &str) -> IResult<&str, AckStatement> {
+	let (i, _) = tag_no_case("ACK")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("CONSUMER")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	cut(|i| {
+		let (i, name) = ident(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, _) = expect_tag_no_case("ON")(i)?;
+		let (i, _) = opt(tuple((shouldbespace, tag_no_case("TABLE"))))(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, what) = ident(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, _) = expect_tag_no_case("AT")(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, versionstamp) = u64(i)?;
+		Ok((
+			i,
+			AckStatement {
+				name,
+				what,
+				versionstamp,
+			},
+		))
+	})(i)
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+
+	#[test]
+	fn ack_consumer() {
+		let sql = "ACK CONSUMER reader ON TABLE person AT 65536";
+		let res = ack(sql);
+		let out = res.unwrap().1;
+		assert_eq!("ACK CONSUMER reader ON person AT 65536", format!("{}", out));
+	}
+}
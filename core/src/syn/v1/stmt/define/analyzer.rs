@@ -96,3 +96,21 @@ fn analyzer_tokenizers(i: &str) -> IResult<&str, DefineAnalyzerOption> {
 	let (i, v) = cut(tokenizers)(i)?;
 	Ok((i, DefineAnalyzerOption::Tokenizers(v)))
 }
+
+#[cfg(all(test, feature = "sql2"))]
+mod tests {
+
+	use super::*;
+
+	#[test]
+	fn check_define_analyzer_filters() {
+		let sql = "ANALYZER ana TOKENIZERS unicode,camel FILTERS lowercase,stopwords(en),stopwords(['a', \"the\"]),synonyms(syn),synonyms({ car: ['auto'] }),mapping({ 'ß': 'ss' })";
+		let (_, az) = analyzer(sql).unwrap();
+		assert_eq!(
+			az.to_string(),
+			"DEFINE ANALYZER ana TOKENIZERS UNICODE,CAMEL FILTERS LOWERCASE,STOPWORDS(ENGLISH),STOPWORDS(['a', 'the']),SYNONYMS(syn),SYNONYMS({ car: ['auto'] }),MAPPING({ \"ß\": 'ss' })"
+		);
+		let (_, res) = analyzer(&az.to_string()[7..]).unwrap();
+		assert_eq!(az, res);
+	}
+}
@@ -0,0 +1,68 @@
+use super::super::super::{
+	comment::shouldbespace,
+	ending,
+	error::{expect_tag_no_case, expected},
+	literal::{ident, strand},
+	IResult,
+};
+use crate::sql::statements::DefineConsumerStatement;
+use nom::{
+	bytes::complete::tag_no_case,
+	combinator::{cut, opt},
+	sequence::tuple,
+};
+
+pub fn consumer(i: &str) -> IResult<&str, DefineConsumerStatement> {
+	let (i, _) = tag_no_case("CONSUMER")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, (name, what, comment)) = cut(|i| {
+		let (i, name) = ident(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, _) = expect_tag_no_case("ON")(i)?;
+		let (i, _) = opt(tuple((shouldbespace, tag_no_case("TABLE"))))(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, what) = ident(i)?;
+		let (i, comment) = opt(consumer_comment)(i)?;
+		let (i, _) = expected("COMMENT", ending::query)(i)?;
+		Ok((i, (name, what, comment)))
+	})(i)?;
+	// Return the statement
+	Ok((
+		i,
+		DefineConsumerStatement {
+			name,
+			what,
+			comment,
+		},
+	))
+}
+
+fn consumer_comment(i: &str) -> IResult<&str, crate::sql::Strand> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("COMMENT")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	cut(strand)(i)
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::sql::Ident;
+
+	#[test]
+	fn define_consumer() {
+		let sql = "CONSUMER reader ON TABLE person COMMENT 'Replication'";
+		let res = consumer(sql);
+		let out = res.unwrap().1;
+		assert_eq!(
+			out,
+			DefineConsumerStatement {
+				name: Ident("reader".to_string()),
+				what: Ident("person".to_string()),
+				comment: Some("Replication".into()),
+			}
+		);
+		assert_eq!("DEFINE CONSUMER reader ON person COMMENT 'Replication'", format!("{}", out));
+	}
+}
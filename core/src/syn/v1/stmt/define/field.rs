@@ -9,6 +9,8 @@ use super::super::super::{
 	value::value,
 	IResult,
 };
+#[cfg(feature = "sql2")]
+use crate::sql::Ident;
 use crate::sql::{statements::DefineFieldStatement, Kind, Permission, Permissions, Strand, Value};
 use nom::{
 	branch::alt,
@@ -30,7 +32,7 @@ pub fn field(i: &str) -> IResult<&str, DefineFieldStatement> {
 		let (i, what) = ident(i)?;
 		let (i, opts) = many0(field_opts)(i)?;
 		#[cfg(feature = "sql2")]
-		let one_of = "one of FLEX(IBLE), TYPE, READONLY, VALUE, ASSERT, DEFAULT, or COMMENT";
+		let one_of = "one of FLEX(IBLE), TYPE, READONLY, VALUE, ASSERT, DEFAULT, ENCRYPTED, or COMMENT";
 		#[cfg(not(feature = "sql2"))]
 		let one_of = "one of FLEX(IBLE), TYPE, VALUE, ASSERT, DEFAULT, or COMMENT";
 		let (i, _) = expected(one_of, cut(ending::query))(i)?;
@@ -70,6 +72,11 @@ pub fn field(i: &str) -> IResult<&str, DefineFieldStatement> {
 			DefineFieldOption::Permissions(v) => {
 				res.permissions = v;
 			}
+			#[cfg(feature = "sql2")]
+			DefineFieldOption::Encrypted(v, d) => {
+				res.encrypted = Some(v);
+				res.deterministic = d;
+			}
 		}
 	}
 	// Return the statement
@@ -86,6 +93,8 @@ enum DefineFieldOption {
 	Default(Value),
 	Comment(Strand),
 	Permissions(Permissions),
+	#[cfg(feature = "sql2")]
+	Encrypted(Ident, bool),
 }
 
 fn field_opts(i: &str) -> IResult<&str, DefineFieldOption> {
@@ -99,6 +108,8 @@ fn field_opts(i: &str) -> IResult<&str, DefineFieldOption> {
 		field_default,
 		field_comment,
 		field_permissions,
+		#[cfg(feature = "sql2")]
+		field_encrypted,
 	))(i)
 }
 
@@ -161,6 +172,20 @@ fn field_permissions(i: &str) -> IResult<&str, DefineFieldOption> {
 	Ok((i, DefineFieldOption::Permissions(v)))
 }
 
+#[cfg(feature = "sql2")]
+fn field_encrypted(i: &str) -> IResult<&str, DefineFieldOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("ENCRYPTED")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = expect_tag_no_case("WITH")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = expect_tag_no_case("KEY")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(ident)(i)?;
+	let (i, d) = opt(tuple((shouldbespace, tag_no_case("DETERMINISTIC"))))(i)?;
+	Ok((i, DefineFieldOption::Encrypted(v, d.is_some())))
+}
+
 #[cfg(test)]
 mod test {
 	use super::field;
@@ -176,4 +201,13 @@ mod test {
 	fn define_field_record_type_permissions() {
 		assert_parsable("FIELD attributes[*] ON listing TYPE record PERMISSIONS FULL")
 	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn define_field_encrypted() {
+		assert_parsable("FIELD ssn ON person TYPE string ENCRYPTED WITH KEY pii PERMISSIONS FULL");
+		assert_parsable(
+			"FIELD email ON person ENCRYPTED WITH KEY pii DETERMINISTIC PERMISSIONS FULL",
+		);
+	}
 }
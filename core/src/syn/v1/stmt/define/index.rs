@@ -7,6 +7,10 @@ use super::super::super::{
 	part::index,
 	IResult,
 };
+#[cfg(feature = "sql2")]
+use super::super::super::{common::commas, part::cond, value::path_like};
+#[cfg(feature = "sql2")]
+use crate::sql::{Cond, Idiom, Part, Value};
 use crate::{
 	sql::{statements::DefineIndexStatement, Idioms, Index, Strand},
 	syn::v1::ParseError,
@@ -19,6 +23,8 @@ use nom::{
 	sequence::tuple,
 	Err,
 };
+#[cfg(feature = "sql2")]
+use nom::{combinator::map, multi::separated_list1};
 
 pub fn index(i: &str) -> IResult<&str, DefineIndexStatement> {
 	let (i, _) = tag_no_case("INDEX")(i)?;
@@ -52,6 +58,14 @@ pub fn index(i: &str) -> IResult<&str, DefineIndexStatement> {
 			DefineIndexOption::Comment(v) => {
 				res.comment = Some(v);
 			}
+			#[cfg(feature = "sql2")]
+			DefineIndexOption::Concurrently => {
+				res.concurrently = true;
+			}
+			#[cfg(feature = "sql2")]
+			DefineIndexOption::Cond(v) => {
+				res.cond = Some(v);
+			}
 		}
 	}
 	// Check necessary options
@@ -70,10 +84,22 @@ enum DefineIndexOption {
 	Index(Index),
 	Columns(Idioms),
 	Comment(Strand),
+	#[cfg(feature = "sql2")]
+	Concurrently,
+	#[cfg(feature = "sql2")]
+	Cond(Cond),
 }
 
 fn index_opts(i: &str) -> IResult<&str, DefineIndexOption> {
-	alt((index_kind, index_columns, index_comment))(i)
+	alt((
+		index_kind,
+		index_columns,
+		index_comment,
+		#[cfg(feature = "sql2")]
+		index_concurrently,
+		#[cfg(feature = "sql2")]
+		index_cond,
+	))(i)
 }
 
 fn index_kind(i: &str) -> IResult<&str, DefineIndexOption> {
@@ -86,10 +112,22 @@ fn index_columns(i: &str) -> IResult<&str, DefineIndexOption> {
 	let (i, _) = shouldbespace(i)?;
 	let (i, _) = alt((tag_no_case("COLUMNS"), tag_no_case("FIELDS")))(i)?;
 	let (i, _) = shouldbespace(i)?;
+	#[cfg(not(feature = "sql2"))]
 	let (i, v) = idiom::locals(i)?;
+	#[cfg(feature = "sql2")]
+	let (i, v) = map(separated_list1(commas, alt((index_expression, idiom::local))), Idioms)(i)?;
 	Ok((i, DefineIndexOption::Columns(v)))
 }
 
+/// An expression index column, stored as an idiom starting with the function call
+#[cfg(feature = "sql2")]
+fn index_expression(i: &str) -> IResult<&str, Idiom> {
+	match path_like(i)? {
+		(i, v @ Value::Function(_)) => Ok((i, Idiom(vec![Part::Start(v)]))),
+		_ => Err(Err::Error(ParseError::Base(i))),
+	}
+}
+
 fn index_comment(i: &str) -> IResult<&str, DefineIndexOption> {
 	let (i, _) = shouldbespace(i)?;
 	let (i, _) = tag_no_case("COMMENT")(i)?;
@@ -98,10 +136,26 @@ fn index_comment(i: &str) -> IResult<&str, DefineIndexOption> {
 	Ok((i, DefineIndexOption::Comment(v)))
 }
 
+#[cfg(feature = "sql2")]
+fn index_cond(i: &str) -> IResult<&str, DefineIndexOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cond(i)?;
+	Ok((i, DefineIndexOption::Cond(v)))
+}
+
+#[cfg(feature = "sql2")]
+fn index_concurrently(i: &str) -> IResult<&str, DefineIndexOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("CONCURRENTLY")(i)?;
+	Ok((i, DefineIndexOption::Concurrently))
+}
+
 #[cfg(test)]
 mod tests {
 
 	use super::*;
+	#[cfg(feature = "sql2")]
+	use crate::sql::index::Quantization;
 	use crate::sql::index::{Distance, MTreeParams, SearchParams, VectorType};
 	use crate::sql::Ident;
 	use crate::sql::Idiom;
@@ -122,6 +176,10 @@ mod tests {
 				cols: Idioms(vec![Idiom(vec![Part::Field(Ident("my_col".to_string()))])]),
 				index: Index::Idx,
 				comment: None,
+				#[cfg(feature = "sql2")]
+				concurrently: false,
+				#[cfg(feature = "sql2")]
+				cond: None,
 			}
 		);
 		assert_eq!(idx.to_string(), "DEFINE INDEX my_index ON my_table FIELDS my_col");
@@ -139,6 +197,10 @@ mod tests {
 				cols: Idioms(vec![Idiom(vec![Part::Field(Ident("my_col".to_string()))])]),
 				index: Index::Uniq,
 				comment: None,
+				#[cfg(feature = "sql2")]
+				concurrently: false,
+				#[cfg(feature = "sql2")]
+				cond: None,
 			}
 		);
 		assert_eq!(idx.to_string(), "DEFINE INDEX my_index ON my_table FIELDS my_col UNIQUE");
@@ -173,6 +235,10 @@ mod tests {
 					terms_cache: 400,
 				}),
 				comment: None,
+				#[cfg(feature = "sql2")]
+				concurrently: false,
+				#[cfg(feature = "sql2")]
+				cond: None,
 			}
 		);
 		assert_eq!(idx.to_string(), "DEFINE INDEX my_index ON my_table FIELDS my_col SEARCH ANALYZER my_analyzer BM25(1.2,0.75) \
@@ -204,6 +270,10 @@ mod tests {
 					terms_cache: 100,
 				}),
 				comment: None,
+				#[cfg(feature = "sql2")]
+				concurrently: false,
+				#[cfg(feature = "sql2")]
+				cond: None,
 			}
 		);
 		assert_eq!(
@@ -230,8 +300,14 @@ mod tests {
 					doc_ids_order: 100,
 					doc_ids_cache: 100,
 					mtree_cache: 100,
+					#[cfg(feature = "sql2")]
+					quantization: None,
 				}),
 				comment: None,
+				#[cfg(feature = "sql2")]
+				concurrently: false,
+				#[cfg(feature = "sql2")]
+				cond: None,
 			}
 		);
 		assert_eq!(
@@ -240,6 +316,114 @@ mod tests {
 		);
 	}
 
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn check_create_index_concurrently() {
+		let sql = "INDEX my_index ON TABLE my_table COLUMNS my_col UNIQUE CONCURRENTLY";
+		let (_, idx) = index(sql).unwrap();
+		assert_eq!(
+			idx,
+			DefineIndexStatement {
+				name: Ident("my_index".to_string()),
+				what: Ident("my_table".to_string()),
+				cols: Idioms(vec![Idiom(vec![Part::Field(Ident("my_col".to_string()))])]),
+				index: Index::Uniq,
+				comment: None,
+				concurrently: true,
+				cond: None,
+			}
+		);
+		assert_eq!(
+			idx.to_string(),
+			"DEFINE INDEX my_index ON my_table FIELDS my_col UNIQUE CONCURRENTLY"
+		);
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn check_create_unique_deferred_index() {
+		let sql = "INDEX my_index ON TABLE my_table COLUMNS my_col UNIQUE DEFERRED";
+		let (_, idx) = index(sql).unwrap();
+		assert_eq!(
+			idx,
+			DefineIndexStatement {
+				name: Ident("my_index".to_string()),
+				what: Ident("my_table".to_string()),
+				cols: Idioms(vec![Idiom(vec![Part::Field(Ident("my_col".to_string()))])]),
+				index: Index::UniqDeferred,
+				comment: None,
+				concurrently: false,
+				cond: None,
+			}
+		);
+		assert_eq!(
+			idx.to_string(),
+			"DEFINE INDEX my_index ON my_table FIELDS my_col UNIQUE DEFERRED"
+		);
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn check_create_partial_index() {
+		let sql = "INDEX my_index ON TABLE my_table COLUMNS my_col WHERE deleted = false";
+		let (_, idx) = index(sql).unwrap();
+		assert!(idx.cond.is_some());
+		assert_eq!(
+			idx.to_string(),
+			"DEFINE INDEX my_index ON my_table FIELDS my_col WHERE deleted = false"
+		);
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn check_create_expression_index() {
+		let sql =
+			"INDEX my_index ON TABLE my_table COLUMNS string::lowercase(email), my_col UNIQUE";
+		let (_, idx) = index(sql).unwrap();
+		assert!(matches!(idx.cols[0].first(), Some(Part::Start(Value::Function(_)))));
+		assert_eq!(idx.cols[1], Idiom(vec![Part::Field(Ident("my_col".to_string()))]));
+		assert_eq!(
+			idx.to_string(),
+			"DEFINE INDEX my_index ON my_table FIELDS string::lowercase(email), my_col UNIQUE"
+		);
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn check_create_quantized_mtree_index() {
+		let sql = "INDEX my_index ON TABLE my_table COLUMNS my_col MTREE DIMENSION 4 TYPE F16 QUANTIZE SCALAR";
+		let (_, idx) = index(sql).unwrap();
+		assert_eq!(
+			idx.index,
+			Index::MTree(MTreeParams {
+				dimension: 4,
+				vector_type: VectorType::F16,
+				distance: Distance::Euclidean,
+				capacity: 40,
+				doc_ids_order: 100,
+				doc_ids_cache: 100,
+				mtree_cache: 100,
+				quantization: Some(Quantization::Scalar),
+			})
+		);
+		assert_eq!(
+			idx.to_string(),
+			"DEFINE INDEX my_index ON my_table FIELDS my_col MTREE DIMENSION 4 DIST EUCLIDEAN TYPE F16 CAPACITY 40 DOC_IDS_ORDER 100 DOC_IDS_CACHE 100 MTREE_CACHE 100 QUANTIZE SCALAR"
+		);
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn check_create_binary_mtree_index() {
+		let sql =
+			"INDEX my_index ON TABLE my_table COLUMNS my_col MTREE DIMENSION 8 DIST HAMMING TYPE BINARY";
+		let (_, idx) = index(sql).unwrap();
+		assert_eq!(
+			idx.to_string(),
+			"DEFINE INDEX my_index ON my_table FIELDS my_col MTREE DIMENSION 8 DIST HAMMING TYPE BINARY CAPACITY 40 DOC_IDS_ORDER 100 DOC_IDS_CACHE 100 MTREE_CACHE 100"
+		);
+	}
+
 	#[test]
 	fn define_index_without_columns_clause() {
 		let sql = "INDEX test ON test";
@@ -0,0 +1,151 @@
+use super::super::super::{
+	comment::shouldbespace,
+	common::commas,
+	ending,
+	error::{expect_tag_no_case, expected},
+	literal::{datetime, duration, ident, strand, strand::strand_raw},
+	part::base,
+	IResult,
+};
+use crate::sql::{statements::DefineKeyStatement, Datetime, Duration, Ident, Strand};
+use nom::{
+	branch::alt,
+	bytes::complete::tag_no_case,
+	combinator::{cut, map},
+	multi::{many0, separated_list1},
+};
+
+pub fn key(i: &str) -> IResult<&str, DefineKeyStatement> {
+	let (i, _) = tag_no_case("KEY")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, (name, base, opts)) = cut(|i| {
+		let (i, name) = ident(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, _) = expect_tag_no_case("ON")(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, base) = base(i)?;
+		let (i, opts) = many0(key_opts)(i)?;
+		let (i, _) = expected("HASH, ROLES, EXPIRES, or COMMENT", ending::query)(i)?;
+		Ok((i, (name, base, opts)))
+	})(i)?;
+	// Create the base statement
+	let mut res = DefineKeyStatement {
+		name,
+		base,
+		roles: vec!["Viewer".into()], // New keys get the viewer role by default
+		..Default::default()
+	};
+	// Assign any defined options
+	for opt in opts {
+		match opt {
+			DefineKeyOption::Hash(v) => {
+				res.hash = v;
+			}
+			DefineKeyOption::Roles(v) => {
+				res.roles = v;
+			}
+			DefineKeyOption::Duration(v) => {
+				res.duration = Some(v);
+				res.expires = None;
+			}
+			DefineKeyOption::Expires(v) => {
+				res.duration = None;
+				res.expires = Some(v);
+			}
+			DefineKeyOption::Comment(v) => {
+				res.comment = Some(v);
+			}
+		}
+	}
+	// Return the statement
+	Ok((i, res))
+}
+
+enum DefineKeyOption {
+	Hash(String),
+	Roles(Vec<Ident>),
+	Duration(Duration),
+	Expires(Datetime),
+	Comment(Strand),
+}
+
+fn key_opts(i: &str) -> IResult<&str, DefineKeyOption> {
+	alt((key_hash, key_roles, key_expires, key_comment))(i)
+}
+
+fn key_hash(i: &str) -> IResult<&str, DefineKeyOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("HASH")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(strand_raw)(i)?;
+	Ok((i, DefineKeyOption::Hash(v)))
+}
+
+fn key_roles(i: &str) -> IResult<&str, DefineKeyOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("ROLES")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(separated_list1(commas, ident))(i)?;
+	Ok((i, DefineKeyOption::Roles(v)))
+}
+
+fn key_expires(i: &str) -> IResult<&str, DefineKeyOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("EXPIRES")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(alt((
+		map(duration, DefineKeyOption::Duration),
+		map(datetime, DefineKeyOption::Expires),
+	)))(i)?;
+	Ok((i, v))
+}
+
+fn key_comment(i: &str) -> IResult<&str, DefineKeyOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("COMMENT")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(strand)(i)?;
+	Ok((i, DefineKeyOption::Comment(v)))
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::sql::Base;
+
+	#[test]
+	fn define_key() {
+		let sql = "KEY deploy ON DATABASE ROLES EDITOR, ops EXPIRES 30d COMMENT 'ci'";
+		let res = key(sql);
+		let out = res.unwrap().1;
+		assert_eq!(
+			out,
+			DefineKeyStatement {
+				name: Ident::from("deploy"),
+				base: Base::Db,
+				roles: vec![Ident::from("EDITOR"), Ident::from("ops")],
+				duration: Some(Duration::from_days(30)),
+				comment: Some("ci".into()),
+				..Default::default()
+			}
+		);
+		assert_eq!(
+			"DEFINE KEY deploy ON DATABASE ROLES EDITOR, ops EXPIRES 4w2d COMMENT 'ci'",
+			format!("{}", out)
+		);
+	}
+
+	#[test]
+	fn define_key_with_hash() {
+		let sql = "KEY deploy ON NAMESPACE HASH 'abc' EXPIRES d'2030-01-01T00:00:00Z'";
+		let res = key(sql);
+		let out = res.unwrap().1;
+		assert_eq!(out.hash, "abc");
+		assert_eq!(out.roles, vec![Ident::from("Viewer")]);
+		assert_eq!(
+			"DEFINE KEY deploy ON NAMESPACE HASH 'abc' ROLES VIEWER EXPIRES d'2030-01-01T00:00:00Z'",
+			format!("{}", out)
+		);
+	}
+}
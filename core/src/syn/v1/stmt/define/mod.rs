@@ -3,17 +3,27 @@ use crate::sql::statements::DefineStatement;
 use nom::{branch::alt, bytes::complete::tag_no_case, combinator::map};
 
 mod analyzer;
+#[cfg(feature = "sql2")]
+mod consumer;
 mod database;
 mod event;
 mod field;
 mod function;
 mod index;
+#[cfg(feature = "sql2")]
+mod key;
 mod namespace;
 mod param;
+#[cfg(feature = "sql2")]
+mod policy;
+#[cfg(feature = "sql2")]
+mod role;
 mod scope;
 mod table;
 mod token;
 mod user;
+#[cfg(feature = "sql2")]
+mod webhook;
 
 pub fn define(i: &str) -> IResult<&str, DefineStatement> {
 	let (i, _) = tag_no_case("DEFINE")(i)?;
@@ -31,5 +41,15 @@ pub fn define(i: &str) -> IResult<&str, DefineStatement> {
 		map(field::field, DefineStatement::Field),
 		map(index::index, DefineStatement::Index),
 		map(analyzer::analyzer, DefineStatement::Analyzer),
+		#[cfg(feature = "sql2")]
+		map(consumer::consumer, DefineStatement::Consumer),
+		#[cfg(feature = "sql2")]
+		map(webhook::webhook, DefineStatement::Webhook),
+		#[cfg(feature = "sql2")]
+		map(role::role, DefineStatement::Role),
+		#[cfg(feature = "sql2")]
+		map(policy::policy, DefineStatement::Policy),
+		#[cfg(feature = "sql2")]
+		map(key::key, DefineStatement::Key),
 	))(i)
 }
@@ -0,0 +1,138 @@
+use super::super::super::{
+	comment::shouldbespace,
+	common::commas,
+	ending,
+	error::{expect_tag_no_case, expected},
+	literal::{ident, strand},
+	value::value,
+	IResult,
+};
+use crate::sql::{statements::DefinePolicyStatement, PermissionKind, Strand, Value};
+use nom::{
+	branch::alt,
+	bytes::complete::tag_no_case,
+	combinator::{self, cut, opt},
+	multi::{many0, separated_list1},
+	sequence::tuple,
+};
+
+pub fn policy(i: &str) -> IResult<&str, DefinePolicyStatement> {
+	let (i, _) = tag_no_case("POLICY")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, (name, what, opts)) = cut(|i| {
+		let (i, name) = ident(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, _) = expect_tag_no_case("ON")(i)?;
+		let (i, _) = opt(tuple((shouldbespace, tag_no_case("TABLE"))))(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, what) = ident(i)?;
+		let (i, opts) = many0(policy_opts)(i)?;
+		let (i, _) = expected("FOR, WHEN, or COMMENT", ending::query)(i)?;
+		Ok((i, (name, what, opts)))
+	})(i)?;
+	// Create the base statement
+	let mut res = DefinePolicyStatement {
+		name,
+		what,
+		kind: DefinePolicyStatement::DEFAULT_KIND.to_vec(),
+		when: Value::Bool(true),
+		..Default::default()
+	};
+	// Assign any defined options
+	for opt in opts {
+		match opt {
+			DefinePolicyOption::For(v) => {
+				res.kind = v;
+			}
+			DefinePolicyOption::When(v) => {
+				res.when = v;
+			}
+			DefinePolicyOption::Comment(v) => {
+				res.comment = Some(v);
+			}
+		}
+	}
+	// Return the statement
+	Ok((i, res))
+}
+
+enum DefinePolicyOption {
+	For(Vec<PermissionKind>),
+	When(Value),
+	Comment(Strand),
+}
+
+fn policy_opts(i: &str) -> IResult<&str, DefinePolicyOption> {
+	alt((policy_for, policy_when, policy_comment))(i)
+}
+
+fn policy_for(i: &str) -> IResult<&str, DefinePolicyOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("FOR")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(separated_list1(
+		commas,
+		alt((
+			combinator::value(PermissionKind::Select, tag_no_case("SELECT")),
+			combinator::value(PermissionKind::Create, tag_no_case("CREATE")),
+			combinator::value(PermissionKind::Update, tag_no_case("UPDATE")),
+			combinator::value(PermissionKind::Delete, tag_no_case("DELETE")),
+		)),
+	))(i)?;
+	Ok((i, DefinePolicyOption::For(v)))
+}
+
+fn policy_when(i: &str) -> IResult<&str, DefinePolicyOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("WHEN")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(value)(i)?;
+	Ok((i, DefinePolicyOption::When(v)))
+}
+
+fn policy_comment(i: &str) -> IResult<&str, DefinePolicyOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("COMMENT")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(strand)(i)?;
+	Ok((i, DefinePolicyOption::Comment(v)))
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::sql::Ident;
+
+	#[test]
+	fn define_policy() {
+		let sql = "POLICY tenant ON TABLE ticket FOR select, update WHEN tenant = $token.tenant COMMENT 'support'";
+		let res = policy(sql);
+		let out = res.unwrap().1;
+		assert_eq!(
+			out,
+			DefinePolicyStatement {
+				name: Ident("tenant".to_string()),
+				what: Ident("ticket".to_string()),
+				kind: vec![PermissionKind::Select, PermissionKind::Update],
+				when: value("tenant = $token.tenant").unwrap().1,
+				comment: Some("support".into()),
+			}
+		);
+		assert_eq!(
+			"DEFINE POLICY tenant ON ticket FOR select, update WHEN tenant = $token.tenant COMMENT 'support'",
+			format!("{}", out)
+		);
+	}
+
+	#[test]
+	fn define_policy_defaults() {
+		let sql = "POLICY tenant ON ticket";
+		let res = policy(sql);
+		let out = res.unwrap().1;
+		assert_eq!(
+			"DEFINE POLICY tenant ON ticket FOR select, create, update, delete WHEN true",
+			format!("{}", out)
+		);
+	}
+}
@@ -0,0 +1,137 @@
+use super::super::super::{
+	comment::shouldbespace,
+	common::commas,
+	ending,
+	error::{expect_tag_no_case, expected},
+	literal::{ident, strand},
+	part::base,
+	IResult, ParseError,
+};
+use crate::iam::{Action, Privilege};
+use crate::sql::{statements::DefineRoleStatement, Strand};
+use nom::{
+	branch::alt,
+	bytes::complete::tag_no_case,
+	combinator::{cut, map},
+	multi::{many0, separated_list1},
+	Err,
+};
+
+pub fn role(i: &str) -> IResult<&str, DefineRoleStatement> {
+	let (i, _) = tag_no_case("ROLE")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, (name, base, opts)) = cut(|i| {
+		let (i, name) = ident(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, _) = expect_tag_no_case("ON")(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, base) = base(i)?;
+		let (i, opts) = many0(role_opts)(i)?;
+		let (i, _) = expected("GRANT, or COMMENT", ending::query)(i)?;
+		Ok((i, (name, base, opts)))
+	})(i)?;
+	// Create the base statement
+	let mut res = DefineRoleStatement {
+		name,
+		base,
+		..Default::default()
+	};
+	// Assign any defined options
+	for opt in opts {
+		match opt {
+			DefineRoleOption::Grant(v) => {
+				res.privileges.extend(v);
+			}
+			DefineRoleOption::Comment(v) => {
+				res.comment = Some(v);
+			}
+		}
+	}
+	// Return the statement
+	Ok((i, res))
+}
+
+enum DefineRoleOption {
+	Grant(Vec<Privilege>),
+	Comment(Strand),
+}
+
+fn role_opts(i: &str) -> IResult<&str, DefineRoleOption> {
+	alt((role_grant, role_comment))(i)
+}
+
+fn role_grant(i: &str) -> IResult<&str, DefineRoleOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("GRANT")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(separated_list1(commas, privilege))(i)?;
+	Ok((i, DefineRoleOption::Grant(v)))
+}
+
+fn privilege(i: &str) -> IResult<&str, Privilege> {
+	let (i, action) = alt((
+		map(tag_no_case("VIEW"), |_| Action::View),
+		map(tag_no_case("EDIT"), |_| Action::Edit),
+	))(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = expect_tag_no_case("ON")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = ident(i)?;
+	// Verify the type of resource is valid
+	let kind = Privilege::parse_kind(&v).map_err(|_| {
+		Err::Failure(ParseError::ExplainedExpected {
+			tried: i,
+			expected: "a type of resource",
+			explained: "Privileges can be granted on ANY, NAMESPACE, DATABASE, SCOPE, TABLE, RECORD, OPTION, FUNCTION, ANALYZER, PARAM, MODEL, EVENT, FIELD, INDEX, CONSUMER, WEBHOOK, ROLE, or USER.",
+		})
+	})?;
+	Ok((i, Privilege::new(action, kind)))
+}
+
+fn role_comment(i: &str) -> IResult<&str, DefineRoleOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("COMMENT")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(strand)(i)?;
+	Ok((i, DefineRoleOption::Comment(v)))
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::iam::ResourceKind;
+	use crate::sql::{Base, Ident};
+
+	#[test]
+	fn define_role() {
+		let sql =
+			"ROLE ops ON ROOT GRANT VIEW ON ANY, VIEW ON TABLE, EDIT ON INDEX COMMENT 'operations'";
+		let res = role(sql);
+		let out = res.unwrap().1;
+		assert_eq!(
+			out,
+			DefineRoleStatement {
+				name: Ident("ops".to_string()),
+				base: Base::Root,
+				privileges: vec![
+					Privilege::new(Action::View, ResourceKind::Any),
+					Privilege::new(Action::View, ResourceKind::Table),
+					Privilege::new(Action::Edit, ResourceKind::Index),
+				],
+				comment: Some("operations".into()),
+			}
+		);
+		assert_eq!(
+			"DEFINE ROLE ops ON ROOT GRANT VIEW ON ANY, VIEW ON TABLE, EDIT ON INDEX COMMENT 'operations'",
+			format!("{}", out)
+		);
+	}
+
+	#[test]
+	fn define_role_with_invalid_resource() {
+		let sql = "ROLE ops ON DATABASE GRANT VIEW ON everything";
+		let res = role(sql);
+		assert!(res.is_err())
+	}
+}
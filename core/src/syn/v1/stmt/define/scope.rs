@@ -36,6 +36,10 @@ pub fn scope(i: &str) -> IResult<&str, DefineScopeStatement> {
 			DefineScopeOption::Comment(v) => {
 				res.comment = Some(v);
 			}
+			#[cfg(feature = "sql2")]
+			DefineScopeOption::Certificate(v) => {
+				res.certificate = Some(v);
+			}
 		}
 	}
 	// Return the statement
@@ -47,9 +51,14 @@ enum DefineScopeOption {
 	Signup(Value),
 	Signin(Value),
 	Comment(Strand),
+	#[cfg(feature = "sql2")]
+	Certificate(Value),
 }
 
 fn scope_opts(i: &str) -> IResult<&str, DefineScopeOption> {
+	#[cfg(feature = "sql2")]
+	return alt((scope_session, scope_signup, scope_signin, scope_comment, scope_certificate))(i);
+	#[cfg(not(feature = "sql2"))]
 	alt((scope_session, scope_signup, scope_signin, scope_comment))(i)
 }
 
@@ -77,6 +86,15 @@ fn scope_signin(i: &str) -> IResult<&str, DefineScopeOption> {
 	Ok((i, DefineScopeOption::Signin(v)))
 }
 
+#[cfg(feature = "sql2")]
+fn scope_certificate(i: &str) -> IResult<&str, DefineScopeOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("CERTIFICATE")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(value)(i)?;
+	Ok((i, DefineScopeOption::Certificate(v)))
+}
+
 fn scope_comment(i: &str) -> IResult<&str, DefineScopeOption> {
 	let (i, _) = shouldbespace(i)?;
 	let (i, _) = tag_no_case("COMMENT")(i)?;
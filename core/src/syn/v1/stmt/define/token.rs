@@ -1,5 +1,7 @@
 #[cfg(all(not(feature = "jwks"), feature = "sql2"))]
 use super::super::super::error::ParseError::Expected;
+#[cfg(feature = "sql2")]
+use super::super::super::value::object;
 use super::super::super::{
 	comment::shouldbespace,
 	ending,
@@ -8,6 +10,8 @@ use super::super::super::{
 	part::base_or_scope,
 	IResult,
 };
+#[cfg(feature = "sql2")]
+use crate::sql::{Object, Value};
 use crate::{
 	sql::{statements::DefineTokenStatement, Algorithm, Strand},
 	syn::v1::ParseError,
@@ -39,7 +43,7 @@ pub fn token(i: &str) -> IResult<&str, DefineTokenStatement> {
 		match opt {
 			DefineTokenOption::Type(v) => {
 				#[cfg(all(not(feature = "jwks"), feature = "sql2"))]
-				if matches!(v, Algorithm::Jwks) {
+				if matches!(v, Algorithm::Jwks | Algorithm::Oidc) {
 					return Err(Err::Error(Expected {
 						tried: i,
 						expected: "the 'jwks' feature to be enabled",
@@ -53,9 +57,42 @@ pub fn token(i: &str) -> IResult<&str, DefineTokenStatement> {
 			DefineTokenOption::Comment(v) => {
 				res.comment = Some(v);
 			}
+			#[cfg(feature = "sql2")]
+			DefineTokenOption::Issuer(v) => {
+				res.code = v;
+			}
+			#[cfg(feature = "sql2")]
+			DefineTokenOption::Audience(v) => {
+				res.audience = Some(v);
+			}
+			#[cfg(feature = "sql2")]
+			DefineTokenOption::Claims(v) => {
+				// Only the claims of a session can be mapped, from the path of a claim
+				for (k, v) in v.iter() {
+					if !DefineTokenStatement::CLAIMS.contains(&k.as_str())
+						|| !matches!(v, Value::Strand(_))
+					{
+						return Err(Err::Failure(ParseError::ExplainedExpected {
+							tried: i,
+							expected: "a mapping of claims",
+							explained: "The CLAIMS clause maps the ns, db, sc, id, or roles claims to the path of a claim in the token.",
+						}));
+					}
+				}
+				res.claims = Some(v);
+			}
 		}
 	}
 	// Check necessary options
+	#[cfg(feature = "sql2")]
+	if res.kind == Algorithm::Oidc && res.code.is_empty() {
+		return Err(Err::Failure(ParseError::ExplainedExpected {
+			tried: i,
+			expected: "an ISSUER clause",
+			explained:
+				"A token from an OpenID Connect provider requires an ISSUER clause to be defined.",
+		}));
+	}
 	if res.code.is_empty() {
 		return Err(Err::Failure(ParseError::ExplainedExpected {
 			tried: i,
@@ -71,9 +108,25 @@ enum DefineTokenOption {
 	Type(Algorithm),
 	Value(String),
 	Comment(Strand),
+	#[cfg(feature = "sql2")]
+	Issuer(String),
+	#[cfg(feature = "sql2")]
+	Audience(Strand),
+	#[cfg(feature = "sql2")]
+	Claims(Object),
 }
 
 fn token_opts(i: &str) -> IResult<&str, DefineTokenOption> {
+	#[cfg(feature = "sql2")]
+	return alt((
+		token_type,
+		token_value,
+		token_comment,
+		token_issuer,
+		token_audience,
+		token_claims,
+	))(i);
+	#[cfg(not(feature = "sql2"))]
 	alt((token_type, token_value, token_comment))(i)
 }
 
@@ -101,6 +154,33 @@ fn token_comment(i: &str) -> IResult<&str, DefineTokenOption> {
 	Ok((i, DefineTokenOption::Comment(v)))
 }
 
+#[cfg(feature = "sql2")]
+fn token_issuer(i: &str) -> IResult<&str, DefineTokenOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("ISSUER")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(strand_raw)(i)?;
+	Ok((i, DefineTokenOption::Issuer(v)))
+}
+
+#[cfg(feature = "sql2")]
+fn token_audience(i: &str) -> IResult<&str, DefineTokenOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("AUDIENCE")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(strand)(i)?;
+	Ok((i, DefineTokenOption::Audience(v)))
+}
+
+#[cfg(feature = "sql2")]
+fn token_claims(i: &str) -> IResult<&str, DefineTokenOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("CLAIMS")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(object)(i)?;
+	Ok((i, DefineTokenOption::Claims(v)))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -113,4 +193,23 @@ mod tests {
 
 		assert_eq!(res.is_err(), true)
 	}
+
+	#[cfg(all(feature = "sql2", feature = "jwks"))]
+	#[test]
+	fn define_token_oidc() {
+		let sql = "TOKEN keycloak ON DATABASE TYPE OIDC ISSUER 'https://example.com/realms/test' AUDIENCE 'surrealdb' CLAIMS { id: 'surreal.record', roles: 'realm_access.roles' }";
+		let out = token(sql).unwrap().1;
+		assert_eq!(out.kind, Algorithm::Oidc);
+		assert_eq!(out.code, "https://example.com/realms/test");
+		assert_eq!(
+			out.to_string(),
+			"DEFINE TOKEN keycloak ON DATABASE TYPE OIDC ISSUER 'https://example.com/realms/test' AUDIENCE 'surrealdb' CLAIMS { id: 'surreal.record', roles: 'realm_access.roles' }"
+		);
+		// Only the claims of a session can be mapped
+		let sql = "TOKEN keycloak ON DATABASE TYPE OIDC ISSUER 'https://example.com' CLAIMS { user: 'sub' }";
+		assert!(token(sql).is_err());
+		// An issuer is required
+		let sql = "TOKEN keycloak ON DATABASE TYPE OIDC";
+		assert!(token(sql).is_err());
+	}
 }
@@ -1,3 +1,5 @@
+#[cfg(not(feature = "sql2"))]
+use super::super::super::ParseError;
 use super::super::super::{
 	comment::shouldbespace,
 	common::commas,
@@ -5,18 +7,18 @@ use super::super::super::{
 	error::{expect_tag_no_case, expected},
 	literal::{ident, strand, strand::strand_raw},
 	part::base,
-	IResult, ParseError,
-};
-use crate::{
-	iam::Role,
-	sql::{statements::DefineUserStatement, Ident, Strand},
+	IResult,
 };
+#[cfg(not(feature = "sql2"))]
+use crate::iam::Role;
+use crate::sql::{statements::DefineUserStatement, Ident, Strand};
+#[cfg(not(feature = "sql2"))]
+use nom::Err;
 use nom::{
 	branch::alt,
 	bytes::complete::tag_no_case,
 	combinator::cut,
 	multi::{many0, separated_list1},
-	Err,
 };
 
 pub fn user(i: &str) -> IResult<&str, DefineUserStatement> {
@@ -53,6 +55,10 @@ pub fn user(i: &str) -> IResult<&str, DefineUserStatement> {
 			DefineUserOption::Comment(v) => {
 				res.comment = Some(v);
 			}
+			#[cfg(feature = "sql2")]
+			DefineUserOption::Certificate(v) => {
+				res.certificate = Some(v);
+			}
 		}
 	}
 	// Return the statement
@@ -64,9 +70,14 @@ enum DefineUserOption {
 	Passhash(String),
 	Roles(Vec<Ident>),
 	Comment(Strand),
+	#[cfg(feature = "sql2")]
+	Certificate(Strand),
 }
 
 fn user_opts(i: &str) -> IResult<&str, Vec<DefineUserOption>> {
+	#[cfg(feature = "sql2")]
+	return many0(alt((user_pass, user_hash, user_roles, user_comment, user_certificate)))(i);
+	#[cfg(not(feature = "sql2"))]
 	many0(alt((user_pass, user_hash, user_roles, user_comment)))(i)
 }
 
@@ -94,13 +105,23 @@ fn user_comment(i: &str) -> IResult<&str, DefineUserOption> {
 	Ok((i, DefineUserOption::Comment(v)))
 }
 
+#[cfg(feature = "sql2")]
+fn user_certificate(i: &str) -> IResult<&str, DefineUserOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("CERTIFICATE")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(strand)(i)?;
+	Ok((i, DefineUserOption::Certificate(v)))
+}
+
 fn user_roles(i: &str) -> IResult<&str, DefineUserOption> {
 	let (i, _) = shouldbespace(i)?;
 	let (i, _) = tag_no_case("ROLES")(i)?;
 	let (i, _) = shouldbespace(i)?;
 	let (i, roles) = separated_list1(commas, |i| {
 		let (i, v) = cut(ident)(i)?;
-		// Verify the role is valid
+		// Verify the role is valid, as custom roles can only be defined with the newer syntax
+		#[cfg(not(feature = "sql2"))]
 		v.as_str().parse::<Role>().map_err(|_| Err::Failure(ParseError::Role(i, v.to_string())))?;
 
 		Ok((i, v))
@@ -0,0 +1,161 @@
+use super::super::super::{
+	comment::shouldbespace,
+	common::take_u32_len,
+	ending,
+	error::{expect_tag_no_case, expected},
+	literal::{ident, strand},
+	value::value,
+	IResult, ParseError,
+};
+use crate::sql::{statements::DefineWebhookStatement, Strand, Value};
+use nom::{
+	branch::alt,
+	bytes::complete::tag_no_case,
+	combinator::{cut, opt},
+	multi::many0,
+	sequence::tuple,
+	Err,
+};
+
+pub fn webhook(i: &str) -> IResult<&str, DefineWebhookStatement> {
+	let (i, _) = tag_no_case("WEBHOOK")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, (name, what, opts)) = cut(|i| {
+		let (i, name) = ident(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, _) = expect_tag_no_case("ON")(i)?;
+		let (i, _) = opt(tuple((shouldbespace, tag_no_case("TABLE"))))(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, what) = ident(i)?;
+		let (i, opts) = many0(webhook_opts)(i)?;
+		let (i, _) = expected("WHEN, URL, SECRET, RETRY, or COMMENT", ending::query)(i)?;
+		Ok((i, (name, what, opts)))
+	})(i)?;
+	// Create the base statement
+	let mut res = DefineWebhookStatement {
+		name,
+		what,
+		when: Value::Bool(true),
+		retry: DefineWebhookStatement::DEFAULT_RETRY,
+		..Default::default()
+	};
+	// Assign any defined options
+	for opt in opts {
+		match opt {
+			DefineWebhookOption::When(v) => {
+				res.when = v;
+			}
+			DefineWebhookOption::Url(v) => {
+				res.url = v;
+			}
+			DefineWebhookOption::Secret(v) => {
+				res.secret = Some(v);
+			}
+			DefineWebhookOption::Retry(v) => {
+				res.retry = v;
+			}
+			DefineWebhookOption::Comment(v) => {
+				res.comment = Some(v);
+			}
+		}
+	}
+	// Check necessary options
+	if res.url.is_empty() {
+		return Err(Err::Failure(ParseError::ExplainedExpected {
+			tried: i,
+			expected: "a URL clause",
+			explained: "A webhook requires a URL clause to be defined.",
+		}));
+	}
+	// Return the statement
+	Ok((i, res))
+}
+
+enum DefineWebhookOption {
+	When(Value),
+	Url(Strand),
+	Secret(Strand),
+	Retry(u32),
+	Comment(Strand),
+}
+
+fn webhook_opts(i: &str) -> IResult<&str, DefineWebhookOption> {
+	alt((webhook_when, webhook_url, webhook_secret, webhook_retry, webhook_comment))(i)
+}
+
+fn webhook_when(i: &str) -> IResult<&str, DefineWebhookOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("WHEN")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(value)(i)?;
+	Ok((i, DefineWebhookOption::When(v)))
+}
+
+fn webhook_url(i: &str) -> IResult<&str, DefineWebhookOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("URL")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(strand)(i)?;
+	Ok((i, DefineWebhookOption::Url(v)))
+}
+
+fn webhook_secret(i: &str) -> IResult<&str, DefineWebhookOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("SECRET")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(strand)(i)?;
+	Ok((i, DefineWebhookOption::Secret(v)))
+}
+
+fn webhook_retry(i: &str) -> IResult<&str, DefineWebhookOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("RETRY")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, (v, _)) = cut(take_u32_len)(i)?;
+	Ok((i, DefineWebhookOption::Retry(v)))
+}
+
+fn webhook_comment(i: &str) -> IResult<&str, DefineWebhookOption> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("COMMENT")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, v) = cut(strand)(i)?;
+	Ok((i, DefineWebhookOption::Comment(v)))
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::sql::Ident;
+
+	#[test]
+	fn define_webhook() {
+		let sql = "WEBHOOK notify ON TABLE person WHEN $event = 'CREATE' URL 'https://example.com/hook' SECRET 'shh' RETRY 3";
+		let res = webhook(sql);
+		let out = res.unwrap().1;
+		assert_eq!(
+			out,
+			DefineWebhookStatement {
+				name: Ident("notify".to_string()),
+				what: Ident("person".to_string()),
+				when: value("$event = 'CREATE'").unwrap().1,
+				url: "https://example.com/hook".into(),
+				secret: Some("shh".into()),
+				retry: 3,
+				comment: None,
+			}
+		);
+		assert_eq!(
+			"DEFINE WEBHOOK notify ON person WHEN $event = 'CREATE' URL 'https://example.com/hook' SECRET '[REDACTED]' RETRY 3",
+			format!("{}", out)
+		);
+	}
+
+	#[test]
+	fn define_webhook_without_url_clause() {
+		let sql = "WEBHOOK notify ON person";
+		let res = webhook(sql);
+		assert!(res.is_err())
+	}
+}
@@ -3,6 +3,8 @@ use super::super::{
 	IResult,
 };
 use crate::sql::statements::InfoStatement;
+#[cfg(feature = "sql2")]
+use nom::sequence::tuple;
 use nom::{
 	branch::alt,
 	bytes::complete::tag_no_case,
@@ -14,9 +16,26 @@ pub fn info(i: &str) -> IResult<&str, InfoStatement> {
 	let (i, _) = shouldbespace(i)?;
 	let (i, _) = tag_no_case("FOR")(i)?;
 	let (i, _) = cut(shouldbespace)(i)?;
+	#[cfg(feature = "sql2")]
+	let one_of = "ROOT, NAMESPACE, DATABASE, SCOPE, TABLE, USER, INDEX, WEBHOOK or KEY";
+	#[cfg(not(feature = "sql2"))]
+	let one_of = "ROOT, NAMESPACE, DATABASE, SCOPE, TABLE or USER";
 	expected(
-		"ROOT, NAMESPACE, DATABASE, SCOPE, TABLE or USER",
-		cut(alt((root, ns, db, sc, tb, user))),
+		one_of,
+		cut(alt((
+			root,
+			ns,
+			db,
+			sc,
+			tb,
+			user,
+			#[cfg(feature = "sql2")]
+			index,
+			#[cfg(feature = "sql2")]
+			webhook,
+			#[cfg(feature = "sql2")]
+			key,
+		))),
 	)(i)
 }
 
@@ -69,6 +88,56 @@ fn user(i: &str) -> IResult<&str, InfoStatement> {
 	})(i)
 }
 
+#[cfg(feature = "sql2")]
+fn index(i: &str) -> IResult<&str, InfoStatement> {
+	let (i, _) = tag_no_case("INDEX")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	cut(|i| {
+		let (i, index) = ident(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, _) = tag_no_case("ON")(i)?;
+		let (i, _) = opt(tuple((shouldbespace, tag_no_case("TABLE"))))(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, table) = ident(i)?;
+		Ok((i, InfoStatement::Index(index, table)))
+	})(i)
+}
+
+#[cfg(feature = "sql2")]
+fn webhook(i: &str) -> IResult<&str, InfoStatement> {
+	let (i, _) = tag_no_case("WEBHOOK")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	cut(|i| {
+		let (i, webhook) = ident(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, _) = tag_no_case("ON")(i)?;
+		let (i, _) = opt(tuple((shouldbespace, tag_no_case("TABLE"))))(i)?;
+		let (i, _) = shouldbespace(i)?;
+		let (i, table) = ident(i)?;
+		Ok((i, InfoStatement::Webhook(webhook, table)))
+	})(i)
+}
+
+#[cfg(feature = "sql2")]
+fn key(i: &str) -> IResult<&str, InfoStatement> {
+	let (i, _) = tag_no_case("KEY")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	cut(|i| {
+		let (i, key) = ident(i)?;
+		let (i, base) = opt(|i| {
+			let (i, _) = shouldbespace(i)?;
+			let (i, _) = tag_no_case("ON")(i)?;
+			cut(|i| {
+				let (i, _) = shouldbespace(i)?;
+				let (i, base) =
+					base(i).explain("scopes are not allowed here", tag_no_case("SCOPE"))?;
+				Ok((i, base))
+			})(i)
+		})(i)?;
+		Ok((i, InfoStatement::Key(key, base)))
+	})(i)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -146,4 +215,34 @@ mod tests {
 		assert_eq!(out, InfoStatement::User(Ident::from("test"), None));
 		assert_eq!("INFO FOR USER test", format!("{}", out));
 	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn info_query_index() {
+		let sql = "INFO FOR INDEX idx ON TABLE test";
+		let res = info(sql);
+		let out = res.unwrap().1;
+		assert_eq!(out, InfoStatement::Index(Ident::from("idx"), Ident::from("test")));
+		assert_eq!("INFO FOR INDEX idx ON test", format!("{}", out));
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn info_query_webhook() {
+		let sql = "INFO FOR WEBHOOK notify ON TABLE test";
+		let res = info(sql);
+		let out = res.unwrap().1;
+		assert_eq!(out, InfoStatement::Webhook(Ident::from("notify"), Ident::from("test")));
+		assert_eq!("INFO FOR WEBHOOK notify ON test", format!("{}", out));
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn info_query_key() {
+		let sql = "INFO FOR KEY deploy ON DATABASE";
+		let res = info(sql);
+		let out = res.unwrap().1;
+		assert_eq!(out, InfoStatement::Key(Ident::from("deploy"), Some(Base::Db)));
+		assert_eq!("INFO FOR KEY deploy ON DATABASE", format!("{}", out));
+	}
 }
@@ -26,6 +26,23 @@ pub fn live(i: &str) -> IResult<&str, LiveStatement> {
 		let (i, what) = alt((into(param), into(table)))(i)?;
 		let (i, cond) = opt(preceded(shouldbespace, cond))(i)?;
 		let (i, fetch) = opt(preceded(shouldbespace, fetch))(i)?;
-		Ok((i, LiveStatement::from_source_parts(expr, what, cond, fetch)))
+		#[cfg(feature = "sql2")]
+		let (i, before) = opt(with_before)(i)?;
+		#[cfg_attr(not(feature = "sql2"), allow(unused_mut))]
+		let mut stm = LiveStatement::from_source_parts(expr, what, cond, fetch);
+		#[cfg(feature = "sql2")]
+		{
+			stm.before = before.is_some();
+		}
+		Ok((i, stm))
 	})(i)
 }
+
+#[cfg(feature = "sql2")]
+fn with_before(i: &str) -> IResult<&str, ()> {
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("WITH")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = tag_no_case("BEFORE")(i)?;
+	Ok((i, ()))
+}
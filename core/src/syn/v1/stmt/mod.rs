@@ -7,6 +7,8 @@ use nom::{
 	sequence::delimited,
 };
 
+#[cfg(feature = "sql2")]
+mod ack;
 mod analyze;
 mod begin;
 mod cancel;
@@ -23,6 +25,8 @@ mod kill;
 mod live;
 mod option;
 mod output;
+#[cfg(feature = "sql2")]
+mod reencrypt;
 mod relate;
 mod remove;
 mod select;
@@ -33,6 +37,8 @@ mod throw;
 mod update;
 mod r#use;
 
+#[cfg(feature = "sql2")]
+pub use ack::ack;
 pub use analyze::analyze;
 pub use begin::begin;
 pub use cancel::cancel;
@@ -50,6 +56,8 @@ pub use live::live;
 pub use option::option;
 pub use output::output;
 pub use r#use::r#use;
+#[cfg(feature = "sql2")]
+pub use reencrypt::reencrypt;
 pub use relate::relate;
 pub use remove::remove;
 pub use select::select;
@@ -70,6 +78,8 @@ pub fn statement(i: &str) -> IResult<&str, Statement> {
 		mightbespace,
 		alt((
 			alt((
+				#[cfg(feature = "sql2")]
+				map(ack, Statement::Ack),
 				map(analyze, Statement::Analyze),
 				map(begin, Statement::Begin),
 				map(r#break, Statement::Break),
@@ -89,6 +99,8 @@ pub fn statement(i: &str) -> IResult<&str, Statement> {
 				map(live, Statement::Live),
 				map(option, Statement::Option),
 				map(output, Statement::Output),
+				#[cfg(feature = "sql2")]
+				map(reencrypt, Statement::Reencrypt),
 				map(relate, Statement::Relate),
 				map(remove, Statement::Remove),
 				map(select, Statement::Select),
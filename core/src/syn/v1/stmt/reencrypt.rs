@@ -0,0 +1,36 @@
+use super::super::{comment::shouldbespace, literal::ident, IResult};
+use crate::sql::statements::ReencryptStatement;
+use nom::{
+	bytes::complete::tag_no_case,
+	combinator::{cut, opt},
+	sequence::tuple,
+};
+
+pub fn reencrypt(i: &str) -> IResult<&str, ReencryptStatement> {
+	let (i, _) = tag_no_case("REENCRYPT")(i)?;
+	let (i, _) = opt(tuple((shouldbespace, tag_no_case("TABLE"))))(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, what) = cut(ident)(i)?;
+	Ok((
+		i,
+		ReencryptStatement {
+			what,
+		},
+	))
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+
+	#[test]
+	fn reencrypt_table() {
+		let sql = "REENCRYPT TABLE person";
+		let res = reencrypt(sql);
+		let out = res.unwrap().1;
+		assert_eq!("REENCRYPT TABLE person", format!("{}", out));
+		let res = reencrypt("REENCRYPT person");
+		assert_eq!(res.unwrap().1, out);
+	}
+}
@@ -12,6 +12,11 @@ use crate::sql::statements::{
 	RemoveScopeStatement, RemoveStatement, RemoveTableStatement, RemoveTokenStatement,
 	RemoveUserStatement,
 };
+#[cfg(feature = "sql2")]
+use crate::sql::statements::{
+	RemoveConsumerStatement, RemoveKeyStatement, RemovePolicyStatement, RemoveRoleStatement,
+	RemoveWebhookStatement,
+};
 use nom::{
 	branch::alt,
 	bytes::complete::{tag, tag_no_case},
@@ -36,6 +41,16 @@ pub fn remove(i: &str) -> IResult<&str, RemoveStatement> {
 		map(index, RemoveStatement::Index),
 		map(analyzer, RemoveStatement::Analyzer),
 		map(user, RemoveStatement::User),
+		#[cfg(feature = "sql2")]
+		map(consumer, RemoveStatement::Consumer),
+		#[cfg(feature = "sql2")]
+		map(webhook, RemoveStatement::Webhook),
+		#[cfg(feature = "sql2")]
+		map(role, RemoveStatement::Role),
+		#[cfg(feature = "sql2")]
+		map(policy, RemoveStatement::Policy),
+		#[cfg(feature = "sql2")]
+		map(key, RemoveStatement::Key),
 	))(i)
 }
 
@@ -63,6 +78,63 @@ pub fn database(i: &str) -> IResult<&str, RemoveDatabaseStatement> {
 	))
 }
 
+#[cfg(feature = "sql2")]
+pub fn consumer(i: &str) -> IResult<&str, RemoveConsumerStatement> {
+	let (i, _) = tag_no_case("CONSUMER")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, name) = cut(ident)(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = expect_tag_no_case("ON")(i)?;
+	let (i, _) = opt(tuple((shouldbespace, tag_no_case("TABLE"))))(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, what) = cut(ident)(i)?;
+	Ok((
+		i,
+		RemoveConsumerStatement {
+			name,
+			what,
+		},
+	))
+}
+
+#[cfg(feature = "sql2")]
+pub fn webhook(i: &str) -> IResult<&str, RemoveWebhookStatement> {
+	let (i, _) = tag_no_case("WEBHOOK")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, name) = cut(ident)(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = expect_tag_no_case("ON")(i)?;
+	let (i, _) = opt(tuple((shouldbespace, tag_no_case("TABLE"))))(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, what) = cut(ident)(i)?;
+	Ok((
+		i,
+		RemoveWebhookStatement {
+			name,
+			what,
+		},
+	))
+}
+
+#[cfg(feature = "sql2")]
+pub fn policy(i: &str) -> IResult<&str, RemovePolicyStatement> {
+	let (i, _) = tag_no_case("POLICY")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, name) = cut(ident)(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = expect_tag_no_case("ON")(i)?;
+	let (i, _) = opt(tuple((shouldbespace, tag_no_case("TABLE"))))(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, what) = cut(ident)(i)?;
+	Ok((
+		i,
+		RemovePolicyStatement {
+			name,
+			what,
+		},
+	))
+}
+
 pub fn event(i: &str) -> IResult<&str, RemoveEventStatement> {
 	let (i, _) = tag_no_case("EVENT")(i)?;
 	let (i, _) = shouldbespace(i)?;
@@ -137,6 +209,24 @@ pub fn index(i: &str) -> IResult<&str, RemoveIndexStatement> {
 	))
 }
 
+#[cfg(feature = "sql2")]
+pub fn key(i: &str) -> IResult<&str, RemoveKeyStatement> {
+	let (i, _) = tag_no_case("KEY")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, name) = cut(ident)(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = expect_tag_no_case("ON")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, base) = cut(base)(i)?;
+	Ok((
+		i,
+		RemoveKeyStatement {
+			name,
+			base,
+		},
+	))
+}
+
 pub fn namespace(i: &str) -> IResult<&str, RemoveNamespaceStatement> {
 	let (i, _) = alt((tag_no_case("NS"), tag_no_case("NAMESPACE")))(i)?;
 	let (i, _) = shouldbespace(i)?;
@@ -211,6 +301,24 @@ pub fn token(i: &str) -> IResult<&str, RemoveTokenStatement> {
 	))
 }
 
+#[cfg(feature = "sql2")]
+pub fn role(i: &str) -> IResult<&str, RemoveRoleStatement> {
+	let (i, _) = tag_no_case("ROLE")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, name) = cut(ident)(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, _) = expect_tag_no_case("ON")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	let (i, base) = cut(base)(i)?;
+	Ok((
+		i,
+		RemoveRoleStatement {
+			name,
+			base,
+		},
+	))
+}
+
 pub fn user(i: &str) -> IResult<&str, RemoveUserStatement> {
 	let (i, _) = tag_no_case("USER")(i)?;
 	let (i, _) = shouldbespace(i)?;
@@ -251,6 +359,51 @@ mod tests {
 		assert_eq!("REMOVE FUNCTION fn::foo::bar::baz::bac", format!("{}", out))
 	}
 
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn remove_consumer() {
+		let sql = "REMOVE CONSUMER reader ON TABLE person";
+		let res = remove(sql);
+		let out = res.unwrap().1;
+		assert_eq!("REMOVE CONSUMER reader ON person", format!("{}", out))
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn remove_webhook() {
+		let sql = "REMOVE WEBHOOK notify ON TABLE person";
+		let res = remove(sql);
+		let out = res.unwrap().1;
+		assert_eq!("REMOVE WEBHOOK notify ON person", format!("{}", out))
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn remove_role() {
+		let sql = "REMOVE ROLE ops ON DATABASE";
+		let res = remove(sql);
+		let out = res.unwrap().1;
+		assert_eq!("REMOVE ROLE ops ON DATABASE", format!("{}", out))
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn remove_key() {
+		let sql = "REMOVE KEY deploy ON NAMESPACE";
+		let res = remove(sql);
+		let out = res.unwrap().1;
+		assert_eq!("REMOVE KEY deploy ON NAMESPACE", format!("{}", out))
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn remove_policy() {
+		let sql = "REMOVE POLICY tenant ON TABLE ticket";
+		let res = remove(sql);
+		let out = res.unwrap().1;
+		assert_eq!("REMOVE POLICY tenant ON ticket", format!("{}", out))
+	}
+
 	#[test]
 	fn remove_table() {
 		let sql = "REMOVE TABLE test";
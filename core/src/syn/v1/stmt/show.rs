@@ -1,3 +1,5 @@
+#[cfg(feature = "sql2")]
+use super::super::literal::ident;
 use super::super::{
 	comment::shouldbespace,
 	error::{expect_tag_no_case, expected},
@@ -34,6 +36,13 @@ pub fn since(i: &str) -> IResult<&str, ShowSince> {
 	cut(alt((map(u64, ShowSince::Versionstamp), map(datetime, ShowSince::Timestamp))))(i)
 }
 
+#[cfg(feature = "sql2")]
+pub fn consumer(i: &str) -> IResult<&str, ShowSince> {
+	let (i, _) = tag_no_case("CONSUMER")(i)?;
+	let (i, _) = shouldbespace(i)?;
+	cut(map(ident, ShowSince::Consumer))(i)
+}
+
 pub fn limit(i: &str) -> IResult<&str, u32> {
 	let (i, _) = tag_no_case("LIMIT")(i)?;
 	let (i, _) = shouldbespace(i)?;
@@ -49,6 +58,12 @@ pub fn show(i: &str) -> IResult<&str, ShowStatement> {
 		let (i, _) = tag_no_case("FOR")(i)?;
 		let (i, _) = shouldbespace(i)?;
 		let (i, table) = table_or_database(i)?;
+		#[cfg(feature = "sql2")]
+		let (i, since) = match table {
+			Some(_) => alt((preceded(shouldbespace, consumer), preceded(shouldbespace, since)))(i)?,
+			None => preceded(shouldbespace, since)(i)?,
+		};
+		#[cfg(not(feature = "sql2"))]
 		let (i, since) = preceded(shouldbespace, since)(i)?;
 		let (i, limit) = opt(preceded(shouldbespace, limit))(i)?;
 		Ok((
@@ -117,6 +132,22 @@ mod tests {
 		assert_eq!(sql, format!("{}", out))
 	}
 
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn show_table_changes_consumer_limit() {
+		let sql = "SHOW CHANGES FOR TABLE person CONSUMER reader LIMIT 10";
+		let res = show(sql);
+		let out = res.unwrap().1;
+		assert_eq!(sql, format!("{}", out))
+	}
+
+	#[test]
+	#[cfg(feature = "sql2")]
+	fn show_database_changes_consumer() {
+		let sql = "SHOW CHANGES FOR DATABASE CONSUMER reader";
+		show(sql).unwrap_err();
+	}
+
 	#[test]
 	fn show_database_changes() {
 		let sql = "SHOW CHANGES FOR DATABASE";
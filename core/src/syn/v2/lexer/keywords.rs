@@ -8,6 +8,7 @@ use unicase::UniCase;
 /// A map for mapping keyword strings to a tokenkind,
 pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, Option<TokenKind>> = phf_map! {
 	// Keywords
+	UniCase::ascii("ACK") => Some(TokenKind::Keyword(Keyword::Ack)),
 	UniCase::ascii("AFTER") => Some(TokenKind::Keyword(Keyword::After)),
 	UniCase::ascii("ALL") => Some(TokenKind::Keyword(Keyword::All)),
 	UniCase::ascii("ANALYZE") => Some(TokenKind::Keyword(Keyword::Analyze)),
@@ -32,12 +33,15 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, Option<TokenKind>> =
 	UniCase::ascii("CLASS") => Some(TokenKind::Keyword(Keyword::Class)),
 	UniCase::ascii("COMMENT") => Some(TokenKind::Keyword(Keyword::Comment)),
 	UniCase::ascii("COMMIT") => Some(TokenKind::Keyword(Keyword::Commit)),
+	UniCase::ascii("CONCURRENTLY") => Some(TokenKind::Keyword(Keyword::Concurrently)),
+	UniCase::ascii("CONSUMER") => Some(TokenKind::Keyword(Keyword::Consumer)),
 	UniCase::ascii("CONTENT") => Some(TokenKind::Keyword(Keyword::Content)),
 	UniCase::ascii("CONTINUE") => Some(TokenKind::Keyword(Keyword::Continue)),
 	UniCase::ascii("CREATE") => Some(TokenKind::Keyword(Keyword::Create)),
 	UniCase::ascii("DATABASE") => Some(TokenKind::Keyword(Keyword::Database)),
 	UniCase::ascii("DB") => Some(TokenKind::Keyword(Keyword::Database)),
 	UniCase::ascii("DEFAULT") => Some(TokenKind::Keyword(Keyword::Default)),
+	UniCase::ascii("DEFERRED") => Some(TokenKind::Keyword(Keyword::Deferred)),
 	UniCase::ascii("DEFINE") => Some(TokenKind::Keyword(Keyword::Define)),
 	UniCase::ascii("DELETE") => Some(TokenKind::Keyword(Keyword::Delete)),
 	UniCase::ascii("DESCENDING") => Some(TokenKind::Keyword(Keyword::Descending)),
@@ -58,6 +62,22 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, Option<TokenKind>> =
 	UniCase::ascii("END") => Some(TokenKind::Keyword(Keyword::End)),
 	UniCase::ascii("EXISTS") => Some(TokenKind::Keyword(Keyword::Exists)),
 	UniCase::ascii("EXPLAIN") => Some(TokenKind::Keyword(Keyword::Explain)),
+	UniCase::ascii("RETRY") => Some(TokenKind::Keyword(Keyword::Retry)),
+	UniCase::ascii("SECRET") => Some(TokenKind::Keyword(Keyword::Secret)),
+	UniCase::ascii("URL") => Some(TokenKind::Keyword(Keyword::Url)),
+	UniCase::ascii("WEBHOOK") => Some(TokenKind::Keyword(Keyword::Webhook)),
+	UniCase::ascii("GRANT") => Some(TokenKind::Keyword(Keyword::Grant)),
+	UniCase::ascii("ROLE") => Some(TokenKind::Keyword(Keyword::Role)),
+	UniCase::ascii("POLICY") => Some(TokenKind::Keyword(Keyword::Policy)),
+	UniCase::ascii("ISSUER") => Some(TokenKind::Keyword(Keyword::Issuer)),
+	UniCase::ascii("AUDIENCE") => Some(TokenKind::Keyword(Keyword::Audience)),
+	UniCase::ascii("CLAIMS") => Some(TokenKind::Keyword(Keyword::Claims)),
+	UniCase::ascii("EXPIRES") => Some(TokenKind::Keyword(Keyword::Expires)),
+	UniCase::ascii("HASH") => Some(TokenKind::Keyword(Keyword::Hash)),
+	UniCase::ascii("CERTIFICATE") => Some(TokenKind::Keyword(Keyword::Certificate)),
+	UniCase::ascii("ENCRYPTED") => Some(TokenKind::Keyword(Keyword::Encrypted)),
+	UniCase::ascii("DETERMINISTIC") => Some(TokenKind::Keyword(Keyword::Deterministic)),
+	UniCase::ascii("REENCRYPT") => Some(TokenKind::Keyword(Keyword::Reencrypt)),
 	UniCase::ascii("false") => Some(TokenKind::Keyword(Keyword::False)),
 	UniCase::ascii("FETCH") => Some(TokenKind::Keyword(Keyword::Fetch)),
 	UniCase::ascii("FIELD") => Some(TokenKind::Keyword(Keyword::Field)),
@@ -74,6 +94,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, Option<TokenKind>> =
 	UniCase::ascii("GROUP") => Some(TokenKind::Keyword(Keyword::Group)),
 	UniCase::ascii("HIGHLIGHTS") => Some(TokenKind::Keyword(Keyword::Highlights)),
 	UniCase::ascii("IGNORE") => Some(TokenKind::Keyword(Keyword::Ignore)),
+	UniCase::ascii("INCLUDE") => Some(TokenKind::Keyword(Keyword::Include)),
 	UniCase::ascii("INDEX") => Some(TokenKind::Keyword(Keyword::Index)),
 	UniCase::ascii("INFO") => Some(TokenKind::Keyword(Keyword::Info)),
 	UniCase::ascii("INSERT") => Some(TokenKind::Keyword(Keyword::Insert)),
@@ -87,6 +108,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, Option<TokenKind>> =
 	UniCase::ascii("LIMIT") => Some(TokenKind::Keyword(Keyword::Limit)),
 	UniCase::ascii("LIVE") => Some(TokenKind::Keyword(Keyword::Live)),
 	UniCase::ascii("LOWERCASE") => Some(TokenKind::Keyword(Keyword::Lowercase)),
+	UniCase::ascii("MAPPING") => Some(TokenKind::Keyword(Keyword::Mapping)),
 	UniCase::ascii("MERGE") => Some(TokenKind::Keyword(Keyword::Merge)),
 	UniCase::ascii("MODEL") => Some(TokenKind::Keyword(Keyword::Model)),
 	UniCase::ascii("MTREE") => Some(TokenKind::Keyword(Keyword::MTree)),
@@ -104,6 +126,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, Option<TokenKind>> =
 	UniCase::ascii("ONLY") => Some(TokenKind::Keyword(Keyword::Only)),
 	UniCase::ascii("OPTION") => Some(TokenKind::Keyword(Keyword::Option)),
 	UniCase::ascii("ORDER") => Some(TokenKind::Keyword(Keyword::Order)),
+	UniCase::ascii("ORIGINAL") => Some(TokenKind::Keyword(Keyword::Original)),
 	UniCase::ascii("PARALLEL") => Some(TokenKind::Keyword(Keyword::Parallel)),
 	UniCase::ascii("PARAM") => Some(TokenKind::Keyword(Keyword::Param)),
 	UniCase::ascii("PASSHASH") => Some(TokenKind::Keyword(Keyword::Passhash)),
@@ -113,6 +136,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, Option<TokenKind>> =
 	UniCase::ascii("POSTINGS_CACHE") => Some(TokenKind::Keyword(Keyword::PostingsCache)),
 	UniCase::ascii("POSTINGS_ORDER") => Some(TokenKind::Keyword(Keyword::PostingsOrder)),
 	UniCase::ascii("PUNCT") => Some(TokenKind::Keyword(Keyword::Punct)),
+	UniCase::ascii("QUANTIZE") => Some(TokenKind::Keyword(Keyword::Quantize)),
 	UniCase::ascii("READONLY") => Some(TokenKind::Keyword(Keyword::Readonly)),
 	UniCase::ascii("RELATE") => Some(TokenKind::Keyword(Keyword::Relate)),
 	UniCase::ascii("REMOVE") => Some(TokenKind::Keyword(Keyword::Remove)),
@@ -138,6 +162,8 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, Option<TokenKind>> =
 	UniCase::ascii("SNOWBALL") => Some(TokenKind::Keyword(Keyword::Snowball)),
 	UniCase::ascii("SPLIT") => Some(TokenKind::Keyword(Keyword::Split)),
 	UniCase::ascii("START") => Some(TokenKind::Keyword(Keyword::Start)),
+	UniCase::ascii("STOPWORDS") => Some(TokenKind::Keyword(Keyword::Stopwords)),
+	UniCase::ascii("SYNONYMS") => Some(TokenKind::Keyword(Keyword::Synonyms)),
 	UniCase::ascii("TABLE") => Some(TokenKind::Keyword(Keyword::Table)),
 	UniCase::ascii("TB") => Some(TokenKind::Keyword(Keyword::Table)),
 	UniCase::ascii("TERMS_CACHE") => Some(TokenKind::Keyword(Keyword::TermsCache)),
@@ -150,6 +176,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, Option<TokenKind>> =
 	UniCase::ascii("TRANSACTION") => Some(TokenKind::Keyword(Keyword::Transaction)),
 	UniCase::ascii("true") => Some(TokenKind::Keyword(Keyword::True)),
 	UniCase::ascii("TYPE") => Some(TokenKind::Keyword(Keyword::Type)),
+	UniCase::ascii("UNICODE") => Some(TokenKind::Keyword(Keyword::Unicode)),
 	UniCase::ascii("UNIQUE") => Some(TokenKind::Keyword(Keyword::Unique)),
 	UniCase::ascii("UNSET") => Some(TokenKind::Keyword(Keyword::Unset)),
 	UniCase::ascii("UPDATE") => Some(TokenKind::Keyword(Keyword::Update)),
@@ -278,6 +305,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, Option<TokenKind>> =
 	UniCase::ascii("RS384") => Some(TokenKind::Algorithm(Algorithm::Rs384)),
 	UniCase::ascii("RS512") => Some(TokenKind::Algorithm(Algorithm::Rs512)),
 	UniCase::ascii("JWKS") => jwks_token_kind(), // Necessary because `phf_map!` doesn't support `cfg` attributes
+	UniCase::ascii("OIDC") => oidc_token_kind(),
 
 	// Distance
 	UniCase::ascii("EUCLIDEAN") => Some(TokenKind::Distance(DistanceKind::Euclidean)),
@@ -293,3 +321,11 @@ const fn jwks_token_kind() -> Option<TokenKind> {
 	let token = None;
 	token
 }
+
+const fn oidc_token_kind() -> Option<TokenKind> {
+	#[cfg(feature = "jwks")]
+	let token = Some(TokenKind::Algorithm(Algorithm::Oidc));
+	#[cfg(not(feature = "jwks"))]
+	let token = None;
+	token
+}
@@ -1,3 +1,14 @@
+#[cfg(feature = "sql2")]
+use crate::iam::{Action, Privilege};
+#[cfg(feature = "sql2")]
+use crate::sql::filter::{Stopwords, Synonyms};
+#[cfg(feature = "sql2")]
+use crate::sql::statements::{
+	DefineConsumerStatement, DefineKeyStatement, DefinePolicyStatement, DefineRoleStatement,
+	DefineWebhookStatement,
+};
+#[cfg(feature = "sql2")]
+use crate::sql::{Algorithm, Part, PermissionKind, Value};
 use crate::{
 	sql::{
 		filter::Filter,
@@ -9,7 +20,7 @@ use crate::{
 			DefineTableStatement, DefineTokenStatement, DefineUserStatement,
 		},
 		tokenizer::Tokenizer,
-		Ident, Idioms, Index, Param, Permissions, Scoring, Strand, Values,
+		Ident, Idiom, Idioms, Index, Param, Permissions, Scoring, Strand, Values,
 	},
 	syn::v2::{
 		parser::{
@@ -35,6 +46,16 @@ impl Parser<'_> {
 			t!("FIELD") => self.parse_define_field().map(DefineStatement::Field),
 			t!("INDEX") => self.parse_define_index().map(DefineStatement::Index),
 			t!("ANALYZER") => self.parse_define_analyzer().map(DefineStatement::Analyzer),
+			#[cfg(feature = "sql2")]
+			t!("CONSUMER") => self.parse_define_consumer().map(DefineStatement::Consumer),
+			#[cfg(feature = "sql2")]
+			t!("WEBHOOK") => self.parse_define_webhook().map(DefineStatement::Webhook),
+			#[cfg(feature = "sql2")]
+			t!("ROLE") => self.parse_define_role().map(DefineStatement::Role),
+			#[cfg(feature = "sql2")]
+			t!("POLICY") => self.parse_define_policy().map(DefineStatement::Policy),
+			#[cfg(feature = "sql2")]
+			t!("KEY") => self.parse_define_key().map(DefineStatement::Key),
 			x => unexpected!(self, x, "a define statement keyword"),
 		}
 	}
@@ -154,6 +175,11 @@ impl Parser<'_> {
 						res.roles.push(self.next_token_value()?);
 					}
 				}
+				#[cfg(feature = "sql2")]
+				t!("CERTIFICATE") => {
+					self.pop_peek();
+					res.certificate = Some(self.next_token_value()?);
+				}
 				_ => break,
 			}
 		}
@@ -191,9 +217,39 @@ impl Parser<'_> {
 						x => unexpected!(self, x, "a token algorithm"),
 					}
 				}
+				#[cfg(feature = "sql2")]
+				t!("ISSUER") => {
+					self.pop_peek();
+					res.code = self.next_token_value::<Strand>()?.0;
+				}
+				#[cfg(feature = "sql2")]
+				t!("AUDIENCE") => {
+					self.pop_peek();
+					res.audience = Some(self.next_token_value()?);
+				}
+				#[cfg(feature = "sql2")]
+				t!("CLAIMS") => {
+					self.pop_peek();
+					let start = expected!(self, t!("{")).span;
+					let claims = self.parse_object(start)?;
+					// Only the claims of a session can be mapped, from the path of a claim
+					for (k, v) in claims.iter() {
+						if !DefineTokenStatement::CLAIMS.contains(&k.as_str())
+							|| !matches!(v, Value::Strand(_))
+						{
+							unexpected!(self, t!("}"), "a mapping of the ns, db, sc, id, or roles claims to the path of a claim");
+						}
+					}
+					res.claims = Some(claims);
+				}
 				_ => break,
 			}
 		}
+		// Check necessary options
+		#[cfg(feature = "sql2")]
+		if res.kind == Algorithm::Oidc && res.code.is_empty() {
+			unexpected!(self, self.peek_kind(), "an ISSUER clause");
+		}
 
 		Ok(res)
 	}
@@ -224,6 +280,11 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.signin = Some(self.parse_value()?);
 				}
+				#[cfg(feature = "sql2")]
+				t!("CERTIFICATE") => {
+					self.pop_peek();
+					res.certificate = Some(self.parse_value()?);
+				}
 				_ => break,
 			}
 		}
@@ -349,6 +410,214 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	#[cfg(feature = "sql2")]
+	pub fn parse_define_consumer(&mut self) -> ParseResult<DefineConsumerStatement> {
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+		let comment = self.eat(t!("COMMENT")).then(|| self.next_token_value()).transpose()?;
+		Ok(DefineConsumerStatement {
+			name,
+			what,
+			comment,
+		})
+	}
+
+	#[cfg(feature = "sql2")]
+	pub fn parse_define_webhook(&mut self) -> ParseResult<DefineWebhookStatement> {
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+
+		let mut res = DefineWebhookStatement {
+			name,
+			what,
+			when: Value::Bool(true),
+			retry: DefineWebhookStatement::DEFAULT_RETRY,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("WHEN") => {
+					self.pop_peek();
+					res.when = self.parse_value()?;
+				}
+				t!("URL") => {
+					self.pop_peek();
+					res.url = self.next_token_value()?;
+				}
+				t!("SECRET") => {
+					self.pop_peek();
+					res.secret = Some(self.next_token_value()?);
+				}
+				t!("RETRY") => {
+					self.pop_peek();
+					res.retry = self.next_token_value()?;
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+		// Check necessary options
+		if res.url.is_empty() {
+			unexpected!(self, self.peek_kind(), "a URL clause");
+		}
+		Ok(res)
+	}
+
+	#[cfg(feature = "sql2")]
+	pub fn parse_define_policy(&mut self) -> ParseResult<DefinePolicyStatement> {
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+
+		let mut res = DefinePolicyStatement {
+			name,
+			what,
+			kind: DefinePolicyStatement::DEFAULT_KIND.to_vec(),
+			when: Value::Bool(true),
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("FOR") => {
+					self.pop_peek();
+					res.kind.clear();
+					loop {
+						let kind = match self.next().kind {
+							t!("SELECT") => PermissionKind::Select,
+							t!("CREATE") => PermissionKind::Create,
+							t!("UPDATE") => PermissionKind::Update,
+							t!("DELETE") => PermissionKind::Delete,
+							x => unexpected!(self, x, "'SELECT', 'CREATE', 'UPDATE' or 'DELETE'"),
+						};
+						res.kind.push(kind);
+						if !self.eat(t!(",")) {
+							break;
+						}
+					}
+				}
+				t!("WHEN") => {
+					self.pop_peek();
+					res.when = self.parse_value()?;
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
+	#[cfg(feature = "sql2")]
+	pub fn parse_define_role(&mut self) -> ParseResult<DefineRoleStatement> {
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		let base = self.parse_base(false)?;
+
+		let mut res = DefineRoleStatement {
+			name,
+			base,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("GRANT") => {
+					self.pop_peek();
+					res.privileges.push(self.parse_privilege()?);
+					while self.eat(t!(",")) {
+						res.privileges.push(self.parse_privilege()?);
+					}
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
+	#[cfg(feature = "sql2")]
+	pub fn parse_define_key(&mut self) -> ParseResult<DefineKeyStatement> {
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		let base = self.parse_base(false)?;
+
+		let mut res = DefineKeyStatement {
+			name,
+			base,
+			roles: vec!["Viewer".into()], // New keys get the viewer role by default
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("HASH") => {
+					self.pop_peek();
+					res.hash = self.next_token_value::<Strand>()?.0;
+				}
+				t!("ROLES") => {
+					self.pop_peek();
+					res.roles = vec![self.next_token_value()?];
+					while self.eat(t!(",")) {
+						res.roles.push(self.next_token_value()?);
+					}
+				}
+				t!("EXPIRES") => {
+					self.pop_peek();
+					let next = self.next();
+					match next.kind {
+						TokenKind::Duration => {
+							res.duration = Some(self.token_value(next)?);
+							res.expires = None;
+						}
+						TokenKind::DateTime => {
+							res.duration = None;
+							res.expires = Some(self.token_value(next)?);
+						}
+						x => unexpected!(self, x, "a duration or a date-time"),
+					}
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
+	#[cfg(feature = "sql2")]
+	fn parse_privilege(&mut self) -> ParseResult<Privilege> {
+		let token = self.peek();
+		let Ok(action) = self.next_token_value::<Ident>()?.parse::<Action>() else {
+			unexpected!(self, token.kind, "VIEW, or EDIT")
+		};
+		expected!(self, t!("ON"));
+		let token = self.peek();
+		let Ok(kind) = Privilege::parse_kind(&self.next_token_value::<Ident>()?) else {
+			unexpected!(self, token.kind, "a type of resource")
+		};
+		Ok(Privilege::new(action, kind))
+	}
+
 	pub fn parse_define_field(&mut self) -> ParseResult<DefineFieldStatement> {
 		let name = self.parse_local_idiom()?;
 		expected!(self, t!("ON"));
@@ -397,6 +666,14 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.comment = Some(self.next_token_value()?);
 				}
+				#[cfg(feature = "sql2")]
+				t!("ENCRYPTED") => {
+					self.pop_peek();
+					expected!(self, t!("WITH"));
+					expected!(self, t!("KEY"));
+					res.encrypted = Some(self.next_token_value()?);
+					res.deterministic = self.eat(t!("DETERMINISTIC"));
+				}
 				_ => break,
 			}
 		}
@@ -421,14 +698,22 @@ impl Parser<'_> {
 				// COLUMS and FIELDS are the same tokenkind
 				t!("FIELDS") => {
 					self.pop_peek();
-					res.cols = Idioms(vec![self.parse_local_idiom()?]);
+					res.cols = Idioms(vec![self.parse_index_column()?]);
 					while self.eat(t!(",")) {
-						res.cols.0.push(self.parse_local_idiom()?);
+						res.cols.0.push(self.parse_index_column()?);
 					}
 				}
+				#[cfg(feature = "sql2")]
+				t!("WHERE") => {
+					res.cond = self.try_parse_condition()?;
+				}
 				t!("UNIQUE") => {
 					self.pop_peek();
 					res.index = Index::Uniq;
+					#[cfg(feature = "sql2")]
+					if self.eat(t!("DEFERRED")) {
+						res.index = Index::UniqDeferred;
+					}
 				}
 				t!("SEARCH") => {
 					self.pop_peek();
@@ -517,6 +802,11 @@ impl Parser<'_> {
 					expected!(self, t!("DIMENSION"));
 					let dimension = self.next_token_value()?;
 					let distance = self.try_parse_distance()?.unwrap_or(Distance::Euclidean);
+					let vector_type = self
+						.eat(t!("TYPE"))
+						.then(|| self.parse_vector_type())
+						.transpose()?
+						.unwrap_or(VectorType::F64);
 					let capacity = self
 						.eat(t!("CAPACITY"))
 						.then(|| self.next_token_value())
@@ -541,6 +831,9 @@ impl Parser<'_> {
 						.transpose()?
 						.unwrap_or(100);
 
+					#[cfg(feature = "sql2")]
+					let quantization = self.eat(t!("QUANTIZE")).then(|| self.parse_quantization()).transpose()?;
+
 					res.index = Index::MTree(crate::sql::index::MTreeParams {
 						dimension,
 						distance,
@@ -548,13 +841,20 @@ impl Parser<'_> {
 						doc_ids_order,
 						doc_ids_cache,
 						mtree_cache,
-						vector_type: VectorType::F64,
+						vector_type,
+						#[cfg(feature = "sql2")]
+						quantization,
 					})
 				}
 				t!("COMMENT") => {
 					self.pop_peek();
 					res.comment = Some(self.next_token_value()?);
 				}
+				#[cfg(feature = "sql2")]
+				t!("CONCURRENTLY") => {
+					self.pop_peek();
+					res.concurrently = true;
+				}
 				_ => break,
 			}
 		}
@@ -562,6 +862,19 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses an index column, which is a local idiom or the expression of an expression index.
+	fn parse_index_column(&mut self) -> ParseResult<Idiom> {
+		#[cfg(feature = "sql2")]
+		if self.peek_kind() == t!("fn") || matches!(self.peek_token_at(1).kind, t!("::") | t!("("))
+		{
+			return match self.parse_idiom_expression()? {
+				Value::Idiom(i) => Ok(i),
+				v => Ok(Idiom(vec![Part::Start(v)])),
+			};
+		}
+		self.parse_local_idiom()
+	}
+
 	pub fn parse_define_analyzer(&mut self) -> ParseResult<DefineAnalyzerStatement> {
 		let name = self.next_token_value()?;
 		let mut res = DefineAnalyzerStatement {
@@ -610,6 +923,46 @@ impl Parser<'_> {
 								self.expect_closing_delimiter(t!(")"), open_span)?;
 								filters.push(Filter::Snowball(language))
 							}
+							#[cfg(feature = "sql2")]
+							t!("STOPWORDS") => {
+								let open_span = expected!(self, t!("(")).span;
+								let stopwords = if self.peek_kind() == t!("[") {
+									let start = self.pop_peek().span;
+									let mut words = Vec::new();
+									loop {
+										words.push(self.next_token_value()?);
+										if !self.eat(t!(",")) {
+											self.expect_closing_delimiter(t!("]"), start)?;
+											break;
+										}
+									}
+									Stopwords::Custom(words)
+								} else {
+									Stopwords::Language(self.next_token_value()?)
+								};
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Stopwords(stopwords))
+							}
+							#[cfg(feature = "sql2")]
+							t!("SYNONYMS") => {
+								let open_span = expected!(self, t!("(")).span;
+								let synonyms = if self.peek_kind() == t!("{") {
+									let start = self.pop_peek().span;
+									Synonyms::Map(self.parse_object(start)?)
+								} else {
+									Synonyms::Table(self.next_token_value()?)
+								};
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Synonyms(synonyms))
+							}
+							#[cfg(feature = "sql2")]
+							t!("MAPPING") => {
+								let open_span = expected!(self, t!("(")).span;
+								let start = expected!(self, t!("{")).span;
+								let mapping = self.parse_object(start)?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Mapping(mapping))
+							}
 							x => unexpected!(self, x, "a filter"),
 						}
 						if !self.eat(t!(",")) {
@@ -628,6 +981,8 @@ impl Parser<'_> {
 							t!("CAMEL") => Tokenizer::Camel,
 							t!("CLASS") => Tokenizer::Class,
 							t!("PUNCT") => Tokenizer::Punct,
+							#[cfg(feature = "sql2")]
+							t!("UNICODE") => Tokenizer::Unicode,
 							x => unexpected!(self, x, "a tokenizer"),
 						};
 						tokenizers.push(tokenizer);
@@ -1,6 +1,8 @@
 use crate::sql::block::Entry;
 use crate::sql::statements::show::{ShowSince, ShowStatement};
 use crate::sql::statements::sleep::SleepStatement;
+#[cfg(feature = "sql2")]
+use crate::sql::statements::{AckStatement, ReencryptStatement};
 use crate::sql::statements::{
 	KillStatement, LiveStatement, OptionStatement, SetStatement, ThrowStatement,
 };
@@ -55,6 +57,11 @@ impl Parser<'_> {
 	pub(super) fn parse_stmt(&mut self) -> ParseResult<Statement> {
 		let token = self.peek();
 		match token.kind {
+			#[cfg(feature = "sql2")]
+			t!("ACK") => {
+				self.pop_peek();
+				self.parse_ack_stmt().map(Statement::Ack)
+			}
 			t!("ANALYZE") => {
 				self.pop_peek();
 				self.parse_analyze().map(Statement::Analyze)
@@ -123,6 +130,11 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_return_stmt().map(Statement::Output)
 			}
+			#[cfg(feature = "sql2")]
+			t!("REENCRYPT") => {
+				self.pop_peek();
+				self.parse_reencrypt_stmt().map(Statement::Reencrypt)
+			}
 			t!("RELATE") => {
 				self.pop_peek();
 				self.parse_relate_stmt().map(Statement::Relate)
@@ -390,6 +402,28 @@ impl Parser<'_> {
 				let base = self.eat(t!("ON")).then(|| self.parse_base(false)).transpose()?;
 				InfoStatement::User(ident, base)
 			}
+			#[cfg(feature = "sql2")]
+			t!("INDEX") => {
+				let index = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				self.eat(t!("TABLE"));
+				let table = self.next_token_value()?;
+				InfoStatement::Index(index, table)
+			}
+			#[cfg(feature = "sql2")]
+			t!("WEBHOOK") => {
+				let webhook = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				self.eat(t!("TABLE"));
+				let table = self.next_token_value()?;
+				InfoStatement::Webhook(webhook, table)
+			}
+			#[cfg(feature = "sql2")]
+			t!("KEY") => {
+				let ident = self.next_token_value()?;
+				let base = self.eat(t!("ON")).then(|| self.parse_base(false)).transpose()?;
+				InfoStatement::Key(ident, base)
+			}
 			x => unexpected!(self, x, "an info target"),
 		};
 		Ok(stmt)
@@ -436,7 +470,14 @@ impl Parser<'_> {
 		let cond = self.try_parse_condition()?;
 		let fetch = self.try_parse_fetch()?;
 
-		Ok(LiveStatement::from_source_parts(expr, what, cond, fetch))
+		#[cfg_attr(not(feature = "sql2"), allow(unused_mut))]
+		let mut stm = LiveStatement::from_source_parts(expr, what, cond, fetch);
+		#[cfg(feature = "sql2")]
+		if self.eat(t!("WITH")) {
+			expected!(self, t!("BEFORE"));
+			stm.before = true;
+		}
+		Ok(stm)
 	}
 
 	/// Parsers a OPTION statement.
@@ -509,6 +550,17 @@ impl Parser<'_> {
 			x => unexpected!(self, x, "`TABLE` or `DATABASE`"),
 		};
 
+		#[cfg(feature = "sql2")]
+		if table.is_some() && self.eat(t!("CONSUMER")) {
+			let since = ShowSince::Consumer(self.next_token_value()?);
+			let limit = self.eat(t!("LIMIT")).then(|| self.next_token_value()).transpose()?;
+			return Ok(ShowStatement {
+				table,
+				since,
+				limit,
+			});
+		}
+
 		expected!(self, t!("SINCE"));
 
 		let next = self.next();
@@ -527,6 +579,39 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parsers an ACK statement
+	///
+	/// # Parser State
+	/// Expects `ACK` to already be consumed.
+	#[cfg(feature = "sql2")]
+	pub(crate) fn parse_ack_stmt(&mut self) -> ParseResult<AckStatement> {
+		expected!(self, t!("CONSUMER"));
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+		expected!(self, t!("AT"));
+		let versionstamp = self.next_token_value()?;
+		Ok(AckStatement {
+			name,
+			what,
+			versionstamp,
+		})
+	}
+
+	/// Parsers a REENCRYPT statement
+	///
+	/// # Parser State
+	/// Expects `REENCRYPT` to already be consumed.
+	#[cfg(feature = "sql2")]
+	pub(crate) fn parse_reencrypt_stmt(&mut self) -> ParseResult<ReencryptStatement> {
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+		Ok(ReencryptStatement {
+			what,
+		})
+	}
+
 	/// Parsers a SLEEP statement
 	///
 	/// # Parser State
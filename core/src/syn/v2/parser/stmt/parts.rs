@@ -1,9 +1,13 @@
 //! Contains parsing code for smaller common parts of statements.
 
+#[cfg(feature = "sql2")]
+use crate::sql::index::Quantization;
 use crate::{
 	sql::{
-		changefeed::ChangeFeed, index::Distance, Base, Cond, Data, Duration, Fetch, Fetchs, Group,
-		Groups, Ident, Operator, Output, Permission, Permissions, Tables, Timeout, View,
+		changefeed::ChangeFeed,
+		index::{Distance, VectorType},
+		Base, Cond, Data, Duration, Fetch, Fetchs, Group, Groups, Ident, Operator, Output,
+		Permission, Permissions, Tables, Timeout, View,
 	},
 	syn::v2::{
 		parser::{
@@ -275,8 +279,32 @@ impl Parser<'_> {
 	/// Expects the parser to have already eating the `CHANGEFEED` keyword
 	pub fn parse_changefeed(&mut self) -> ParseResult<ChangeFeed> {
 		let expiry = self.next_token_value::<Duration>()?.0;
+		#[cfg(feature = "sql2")]
+		let (mut max_changes, mut max_bytes) = (None, None);
+		#[cfg(feature = "sql2")]
+		while self.eat(t!("LIMIT")) {
+			let limit = self.next_token_value()?;
+			match self.next().kind {
+				t!("CHANGES") => max_changes = Some(limit),
+				t!("BYTES") => max_bytes = Some(limit),
+				x => unexpected!(self, x, "CHANGES or BYTES"),
+			}
+		}
+		#[cfg(feature = "sql2")]
+		let store_original = if self.eat(t!("INCLUDE")) {
+			expected!(self, t!("ORIGINAL"));
+			true
+		} else {
+			false
+		};
 		Ok(ChangeFeed {
 			expiry,
+			#[cfg(feature = "sql2")]
+			store_original,
+			#[cfg(feature = "sql2")]
+			max_changes,
+			#[cfg(feature = "sql2")]
+			max_bytes,
 		})
 	}
 
@@ -321,6 +349,34 @@ impl Parser<'_> {
 		Ok(dist)
 	}
 
+	pub fn parse_vector_type(&mut self) -> ParseResult<VectorType> {
+		let token = self.next();
+		let ident: Ident = self.token_value(token)?;
+		let vector_type = match ident.to_ascii_uppercase().as_str() {
+			"F64" => VectorType::F64,
+			"F32" => VectorType::F32,
+			"I64" => VectorType::I64,
+			"I32" => VectorType::I32,
+			"I16" => VectorType::I16,
+			#[cfg(feature = "sql2")]
+			"F16" => VectorType::F16,
+			#[cfg(feature = "sql2")]
+			"BINARY" => VectorType::Binary,
+			_ => unexpected!(self, token.kind, "a vector type"),
+		};
+		Ok(vector_type)
+	}
+
+	#[cfg(feature = "sql2")]
+	pub fn parse_quantization(&mut self) -> ParseResult<Quantization> {
+		let token = self.next();
+		let ident: Ident = self.token_value(token)?;
+		match ident.to_ascii_uppercase().as_str() {
+			"SCALAR" => Ok(Quantization::Scalar),
+			_ => unexpected!(self, token.kind, "a quantization method"),
+		}
+	}
+
 	pub fn try_parse_distance(&mut self) -> ParseResult<Option<Distance>> {
 		if !self.eat(t!("DISTANCE")) {
 			return Ok(None);
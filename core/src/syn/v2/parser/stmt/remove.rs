@@ -79,6 +79,59 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			#[cfg(feature = "sql2")]
+			t!("CONSUMER") => {
+				let name = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				self.eat(t!("TABLE"));
+				let table = self.next_token_value()?;
+				RemoveStatement::Consumer(crate::sql::statements::RemoveConsumerStatement {
+					name,
+					what: table,
+				})
+			}
+			#[cfg(feature = "sql2")]
+			t!("WEBHOOK") => {
+				let name = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				self.eat(t!("TABLE"));
+				let table = self.next_token_value()?;
+				RemoveStatement::Webhook(crate::sql::statements::RemoveWebhookStatement {
+					name,
+					what: table,
+				})
+			}
+			#[cfg(feature = "sql2")]
+			t!("ROLE") => {
+				let name = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				let base = self.parse_base(false)?;
+				RemoveStatement::Role(crate::sql::statements::RemoveRoleStatement {
+					name,
+					base,
+				})
+			}
+			#[cfg(feature = "sql2")]
+			t!("KEY") => {
+				let name = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				let base = self.parse_base(false)?;
+				RemoveStatement::Key(crate::sql::statements::RemoveKeyStatement {
+					name,
+					base,
+				})
+			}
+			#[cfg(feature = "sql2")]
+			t!("POLICY") => {
+				let name = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				self.eat(t!("TABLE"));
+				let table = self.next_token_value()?;
+				RemoveStatement::Policy(crate::sql::statements::RemovePolicyStatement {
+					name,
+					what: table,
+				})
+			}
 			t!("EVENT") => {
 				let name = self.next_token_value()?;
 				expected!(self, t!("ON"));
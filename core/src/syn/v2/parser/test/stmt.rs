@@ -1,3 +1,5 @@
+#[cfg(feature = "sql2")]
+use crate::sql::index::Quantization;
 use crate::{
 	sql::{
 		block::Entry,
@@ -136,6 +138,296 @@ fn parse_define_namespace() {
 	)
 }
 
+#[test]
+#[cfg(feature = "sql2")]
+fn parse_define_table_changefeed_include_original() {
+	let res = test_parse!(parse_stmt, "DEFINE TABLE a CHANGEFEED 1h INCLUDE ORIGINAL").unwrap();
+	let Statement::Define(DefineStatement::Table(res)) = res else {
+		panic!()
+	};
+	assert_eq!(
+		res.changefeed,
+		Some(ChangeFeed {
+			expiry: std::time::Duration::from_secs(3600),
+			store_original: true,
+			max_changes: None,
+			max_bytes: None,
+		})
+	);
+}
+
+#[test]
+#[cfg(feature = "sql2")]
+fn parse_define_database_changefeed_limits() {
+	let res = test_parse!(
+		parse_stmt,
+		"DEFINE DATABASE a CHANGEFEED 1d LIMIT 1000 CHANGES LIMIT 1024 BYTES"
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Database(res)) = res else {
+		panic!()
+	};
+	assert_eq!(
+		res.changefeed,
+		Some(ChangeFeed {
+			expiry: std::time::Duration::from_secs(86400),
+			store_original: false,
+			max_changes: Some(1000),
+			max_bytes: Some(1024),
+		})
+	);
+}
+
+#[test]
+#[cfg(feature = "sql2")]
+fn parse_define_webhook() {
+	use crate::sql::statements::{DefineWebhookStatement, RemoveWebhookStatement};
+
+	let res = test_parse!(
+		parse_stmt,
+		"DEFINE WEBHOOK notify ON TABLE person WHEN $event = 'CREATE' URL 'https://example.com/hook' SECRET 'shh' RETRY 3 COMMENT 'test'"
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Webhook(DefineWebhookStatement {
+			name: Ident("notify".to_owned()),
+			what: Ident("person".to_owned()),
+			when: Value::Expression(Box::new(Expression::Binary {
+				l: Value::Param(Param(Ident("event".to_owned()))),
+				o: Operator::Equal,
+				r: Value::Strand(Strand("CREATE".to_owned())),
+			})),
+			url: Strand("https://example.com/hook".to_owned()),
+			secret: Some(Strand("shh".to_owned())),
+			retry: 3,
+			comment: Some(Strand("test".to_owned())),
+		}))
+	);
+
+	let res =
+		test_parse!(parse_stmt, "DEFINE WEBHOOK notify ON person URL 'https://example.com/hook'")
+			.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Webhook(DefineWebhookStatement {
+			name: Ident("notify".to_owned()),
+			what: Ident("person".to_owned()),
+			when: Value::Bool(true),
+			url: Strand("https://example.com/hook".to_owned()),
+			secret: None,
+			retry: DefineWebhookStatement::DEFAULT_RETRY,
+			comment: None,
+		}))
+	);
+
+	test_parse!(parse_stmt, "DEFINE WEBHOOK notify ON person").unwrap_err();
+
+	let res = test_parse!(parse_stmt, "INFO FOR WEBHOOK notify ON TABLE person").unwrap();
+	assert_eq!(
+		res,
+		Statement::Info(InfoStatement::Webhook(
+			Ident("notify".to_owned()),
+			Ident("person".to_owned())
+		))
+	);
+
+	let res = test_parse!(parse_stmt, "REMOVE WEBHOOK notify ON TABLE person").unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Webhook(RemoveWebhookStatement {
+			name: Ident("notify".to_owned()),
+			what: Ident("person".to_owned()),
+		}))
+	);
+}
+
+#[test]
+#[cfg(feature = "sql2")]
+fn parse_define_role() {
+	use crate::iam::{Action, Privilege, ResourceKind};
+	use crate::sql::statements::{DefineRoleStatement, RemoveRoleStatement};
+
+	let res = test_parse!(
+		parse_stmt,
+		"DEFINE ROLE ops ON ROOT GRANT VIEW ON ANY, view on table, EDIT ON INDEX COMMENT 'test'"
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Role(DefineRoleStatement {
+			name: Ident("ops".to_owned()),
+			base: Base::Root,
+			privileges: vec![
+				Privilege::new(Action::View, ResourceKind::Any),
+				Privilege::new(Action::View, ResourceKind::Table),
+				Privilege::new(Action::Edit, ResourceKind::Index),
+			],
+			comment: Some(Strand("test".to_owned())),
+		}))
+	);
+
+	test_parse!(parse_stmt, "DEFINE ROLE ops ON DATABASE GRANT READ ON TABLE").unwrap_err();
+	test_parse!(parse_stmt, "DEFINE ROLE ops ON DATABASE GRANT VIEW ON everything").unwrap_err();
+	test_parse!(parse_stmt, "DEFINE ROLE ops ON SCOPE test").unwrap_err();
+
+	let res = test_parse!(parse_stmt, "REMOVE ROLE ops ON NAMESPACE").unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Role(RemoveRoleStatement {
+			name: Ident("ops".to_owned()),
+			base: Base::Ns,
+		}))
+	);
+}
+
+#[test]
+#[cfg(feature = "sql2")]
+fn parse_define_key() {
+	use crate::sql::statements::{DefineKeyStatement, RemoveKeyStatement};
+	use crate::sql::Duration;
+
+	let res = test_parse!(
+		parse_stmt,
+		"DEFINE KEY deploy ON DATABASE ROLES EDITOR, ops EXPIRES 30d COMMENT 'test'"
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Key(DefineKeyStatement {
+			name: Ident("deploy".to_owned()),
+			base: Base::Db,
+			roles: vec![Ident("EDITOR".to_owned()), Ident("ops".to_owned())],
+			duration: Some(Duration::from_days(30)),
+			comment: Some(Strand("test".to_owned())),
+			..Default::default()
+		}))
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		"DEFINE KEY deploy ON ROOT HASH 'abc' EXPIRES d'2030-01-01T00:00:00Z'"
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Key(ky)) = res else {
+		panic!()
+	};
+	assert_eq!(ky.hash, "abc");
+	assert_eq!(ky.roles, vec![Ident("Viewer".to_owned())]);
+	assert!(ky.expires.is_some());
+
+	test_parse!(parse_stmt, "DEFINE KEY deploy ON DATABASE EXPIRES 'soon'").unwrap_err();
+	test_parse!(parse_stmt, "DEFINE KEY deploy ON SCOPE test").unwrap_err();
+
+	let res = test_parse!(parse_stmt, "REMOVE KEY deploy ON NAMESPACE").unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Key(RemoveKeyStatement {
+			name: Ident("deploy".to_owned()),
+			base: Base::Ns,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, "INFO FOR KEY deploy ON DATABASE").unwrap();
+	assert_eq!(
+		res,
+		Statement::Info(InfoStatement::Key(Ident("deploy".to_owned()), Some(Base::Db)))
+	);
+}
+
+#[test]
+#[cfg(feature = "sql2")]
+fn parse_define_policy() {
+	use crate::sql::statements::{DefinePolicyStatement, RemovePolicyStatement};
+	use crate::sql::PermissionKind;
+
+	let res = test_parse!(
+		parse_stmt,
+		"DEFINE POLICY tenant ON TABLE ticket FOR select, update WHEN true COMMENT 'test'"
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Policy(DefinePolicyStatement {
+			name: Ident("tenant".to_owned()),
+			what: Ident("ticket".to_owned()),
+			kind: vec![PermissionKind::Select, PermissionKind::Update],
+			when: Value::Bool(true),
+			comment: Some(Strand("test".to_owned())),
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, "DEFINE POLICY tenant ON ticket").unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Policy(DefinePolicyStatement {
+			name: Ident("tenant".to_owned()),
+			what: Ident("ticket".to_owned()),
+			kind: DefinePolicyStatement::DEFAULT_KIND.to_vec(),
+			when: Value::Bool(true),
+			comment: None,
+		}))
+	);
+
+	test_parse!(parse_stmt, "DEFINE POLICY tenant ON ticket FOR relate").unwrap_err();
+
+	let res = test_parse!(parse_stmt, "REMOVE POLICY tenant ON ticket").unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Policy(RemovePolicyStatement {
+			name: Ident("tenant".to_owned()),
+			what: Ident("ticket".to_owned()),
+		}))
+	);
+}
+
+#[test]
+#[cfg(feature = "sql2")]
+fn parse_changefeed_consumer() {
+	use crate::sql::statements::{AckStatement, DefineConsumerStatement, RemoveConsumerStatement};
+
+	let res =
+		test_parse!(parse_stmt, "DEFINE CONSUMER reader ON TABLE person COMMENT 'test'").unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Consumer(DefineConsumerStatement {
+			name: Ident("reader".to_owned()),
+			what: Ident("person".to_owned()),
+			comment: Some(Strand("test".to_owned())),
+		}))
+	);
+
+	let res =
+		test_parse!(parse_stmt, "SHOW CHANGES FOR TABLE person CONSUMER reader LIMIT 10").unwrap();
+	assert_eq!(
+		res,
+		Statement::Show(ShowStatement {
+			table: Some(Table("person".to_owned())),
+			since: ShowSince::Consumer(Ident("reader".to_owned())),
+			limit: Some(10),
+		})
+	);
+
+	let res = test_parse!(parse_stmt, "ACK CONSUMER reader ON person AT 65536").unwrap();
+	assert_eq!(
+		res,
+		Statement::Ack(AckStatement {
+			name: Ident("reader".to_owned()),
+			what: Ident("person".to_owned()),
+			versionstamp: 65536,
+		})
+	);
+
+	let res = test_parse!(parse_stmt, "REMOVE CONSUMER reader ON TABLE person").unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Consumer(RemoveConsumerStatement {
+			name: Ident("reader".to_owned()),
+			what: Ident("person".to_owned()),
+		}))
+	);
+}
+
 #[test]
 fn parse_define_database() {
 	let res = test_parse!(parse_stmt, "DEFINE DATABASE a COMMENT 'test' CHANGEFEED 10m").unwrap();
@@ -146,7 +438,13 @@ fn parse_define_database() {
 			name: Ident("a".to_string()),
 			comment: Some(Strand("test".to_string())),
 			changefeed: Some(ChangeFeed {
-				expiry: std::time::Duration::from_secs(60) * 10
+				expiry: std::time::Duration::from_secs(60) * 10,
+				#[cfg(feature = "sql2")]
+				store_original: false,
+				#[cfg(feature = "sql2")]
+				max_changes: None,
+				#[cfg(feature = "sql2")]
+				max_bytes: None,
 			})
 		}))
 	);
@@ -225,11 +523,47 @@ fn parse_define_token() {
 			base: Base::Sc(Ident("b".to_string())),
 			kind: Algorithm::EdDSA,
 			code: "foo".to_string(),
-			comment: Some(Strand("bar".to_string()))
+			comment: Some(Strand("bar".to_string())),
+			#[cfg(feature = "sql2")]
+			audience: None,
+			#[cfg(feature = "sql2")]
+			claims: None
 		}))
 	)
 }
 
+#[cfg(feature = "jwks")]
+#[test]
+fn parse_define_token_oidc() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE TOKEN a ON DATABASE TYPE OIDC ISSUER "https://example.com" AUDIENCE "surrealdb" CLAIMS { roles: "realm_access.roles" }"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Token(DefineTokenStatement {
+			name: Ident("a".to_string()),
+			base: Base::Db,
+			kind: Algorithm::Oidc,
+			code: "https://example.com".to_string(),
+			comment: None,
+			audience: Some(Strand("surrealdb".to_string())),
+			claims: Some(Object(
+				[("roles".to_string(), Value::from("realm_access.roles"))].into_iter().collect()
+			)),
+		}))
+	);
+	// Only the claims of a session can be mapped
+	test_parse!(
+		parse_stmt,
+		r#"DEFINE TOKEN a ON DATABASE TYPE OIDC ISSUER "https://example.com" CLAIMS { user: "sub" }"#
+	)
+	.unwrap_err();
+	// An issuer is required
+	test_parse!(parse_stmt, r#"DEFINE TOKEN a ON DATABASE TYPE OIDC"#).unwrap_err();
+}
+
 #[test]
 fn parse_define_scope() {
 	let res = test_parse!(
@@ -250,6 +584,32 @@ fn parse_define_scope() {
 	assert_eq!(stmt.signin, Some(Value::Bool(false)));
 }
 
+#[test]
+#[cfg(feature = "sql2")]
+fn parse_define_certificate() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE USER api ON DATABASE ROLES EDITOR CERTIFICATE "CN=api,O=mesh""#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::User(stmt)) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.certificate, Some(Strand("CN=api,O=mesh".to_string())));
+	assert!(stmt.hash.is_empty());
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE SCOPE a CERTIFICATE (SELECT * FROM service WHERE name = $subject)"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Scope(stmt)) = res else {
+		panic!()
+	};
+	assert!(matches!(stmt.certificate, Some(Value::Subquery(_))));
+	assert_eq!(stmt.signin, None);
+}
+
 #[test]
 fn parse_define_param() {
 	let res =
@@ -312,7 +672,13 @@ fn parse_define_table() {
 				delete: Permission::None,
 			},
 			changefeed: Some(ChangeFeed {
-				expiry: std::time::Duration::from_secs(1)
+				expiry: std::time::Duration::from_secs(1),
+				#[cfg(feature = "sql2")]
+				store_original: false,
+				#[cfg(feature = "sql2")]
+				max_changes: None,
+				#[cfg(feature = "sql2")]
+				max_bytes: None,
 			}),
 			comment: None,
 		}))
@@ -370,11 +736,56 @@ fn parse_define_field() {
 				create: Permission::Specific(Value::Bool(true)),
 				select: Permission::Full,
 			},
-			comment: None
+			comment: None,
+			#[cfg(feature = "sql2")]
+			encrypted: None,
+			#[cfg(feature = "sql2")]
+			deterministic: false,
 		}))
 	)
 }
 
+#[test]
+#[cfg(feature = "sql2")]
+fn parse_define_field_encrypted() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE FIELD ssn ON person TYPE string ENCRYPTED WITH KEY pii COMMENT "PII""#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Field(stmt)) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.encrypted, Some(Ident("pii".to_owned())));
+	assert!(!stmt.deterministic);
+	assert_eq!(stmt.comment, Some(Strand("PII".to_owned())));
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE FIELD email ON person ENCRYPTED WITH KEY pii DETERMINISTIC"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Field(stmt)) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.encrypted, Some(Ident("pii".to_owned())));
+	assert!(stmt.deterministic);
+}
+
+#[test]
+#[cfg(feature = "sql2")]
+fn parse_reencrypt() {
+	use crate::sql::statements::ReencryptStatement;
+	let res = test_parse!(parse_stmt, r#"REENCRYPT TABLE person"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Reencrypt(ReencryptStatement {
+			what: Ident("person".to_owned()),
+		})
+	);
+	assert_eq!(test_parse!(parse_stmt, r#"REENCRYPT person"#).unwrap(), res);
+}
+
 #[test]
 fn parse_define_index() {
 	let res = test_parse!(
@@ -417,7 +828,11 @@ fn parse_define_index() {
 				postings_cache: 7,
 				terms_cache: 8,
 			}),
-			comment: None
+			comment: None,
+			#[cfg(feature = "sql2")]
+			concurrently: false,
+			#[cfg(feature = "sql2")]
+			cond: None,
 		}))
 	);
 
@@ -431,7 +846,11 @@ fn parse_define_index() {
 			what: Ident("table".to_owned()),
 			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))]),]),
 			index: Index::Uniq,
-			comment: None
+			comment: None,
+			#[cfg(feature = "sql2")]
+			concurrently: false,
+			#[cfg(feature = "sql2")]
+			cond: None,
 		}))
 	);
 
@@ -452,10 +871,96 @@ fn parse_define_index() {
 				doc_ids_cache: 8,
 				mtree_cache: 9,
 				vector_type: VectorType::F64,
+				#[cfg(feature = "sql2")]
+				quantization: None,
 			}),
-			comment: None
+			comment: None,
+			#[cfg(feature = "sql2")]
+			concurrently: false,
+			#[cfg(feature = "sql2")]
+			cond: None,
 		}))
 	);
+
+	#[cfg(feature = "sql2")]
+	{
+		let res = test_parse!(
+			parse_stmt,
+			r#"DEFINE INDEX index ON TABLE table FIELDS a UNIQUE CONCURRENTLY"#
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			Statement::Define(DefineStatement::Index(DefineIndexStatement {
+				name: Ident("index".to_owned()),
+				what: Ident("table".to_owned()),
+				cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))]),]),
+				index: Index::Uniq,
+				comment: None,
+				concurrently: true,
+				cond: None,
+			}))
+		);
+
+		let res = test_parse!(
+			parse_stmt,
+			r#"DEFINE INDEX index ON TABLE table FIELDS a UNIQUE DEFERRED"#
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			Statement::Define(DefineStatement::Index(DefineIndexStatement {
+				name: Ident("index".to_owned()),
+				what: Ident("table".to_owned()),
+				cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))]),]),
+				index: Index::UniqDeferred,
+				comment: None,
+				concurrently: false,
+				cond: None,
+			}))
+		);
+
+		let res = test_parse!(
+			parse_stmt,
+			r#"DEFINE INDEX index ON TABLE table FIELDS string::lowercase(a), b WHERE deleted = false"#
+		)
+		.unwrap();
+		let Statement::Define(DefineStatement::Index(res)) = res else {
+			panic!()
+		};
+		assert!(matches!(res.cols[0].first(), Some(Part::Start(Value::Function(_)))));
+		assert_eq!(res.cols[1], Idiom(vec![Part::Field(Ident("b".to_owned()))]));
+		assert_eq!(
+			res.cond,
+			Some(Cond(Value::Expression(Box::new(Expression::Binary {
+				l: Value::Idiom(Idiom(vec![Part::Field(Ident("deleted".to_owned()))])),
+				o: Operator::Equal,
+				r: Value::Bool(false),
+			}))))
+		);
+
+		let res = test_parse!(
+			parse_stmt,
+			r#"DEFINE INDEX index ON TABLE table FIELDS a MTREE DIMENSION 4 TYPE F16 QUANTIZE SCALAR"#
+		)
+		.unwrap();
+		let Statement::Define(DefineStatement::Index(res)) = res else {
+			panic!()
+		};
+		assert_eq!(
+			res.index,
+			Index::MTree(MTreeParams {
+				dimension: 4,
+				distance: Distance::Euclidean,
+				capacity: 40,
+				doc_ids_order: 100,
+				doc_ids_cache: 100,
+				mtree_cache: 100,
+				vector_type: VectorType::F16,
+				quantization: Some(Quantization::Scalar),
+			})
+		);
+	}
 }
 
 #[test]
@@ -490,6 +995,38 @@ fn parse_define_analyzer() {
 	)
 }
 
+#[cfg(feature = "sql2")]
+#[test]
+fn parse_define_analyzer_filters() {
+	use crate::sql::filter::{Stopwords, Synonyms};
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE ANALYZER ana TOKENIZERS UNICODE FILTERS STOPWORDS(ENGLISH), STOPWORDS(['a', 'b']), SYNONYMS(syn), SYNONYMS({ car: 'auto' }), MAPPING({ 'ß': 'ss' })"#
+	).unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Analyzer(DefineAnalyzerStatement {
+			name: Ident("ana".to_owned()),
+			tokenizers: Some(vec![Tokenizer::Unicode]),
+			filters: Some(vec![
+				Filter::Stopwords(Stopwords::Language(Language::English)),
+				Filter::Stopwords(Stopwords::Custom(vec![Strand::from("a"), Strand::from("b")])),
+				Filter::Synonyms(Synonyms::Table(Table::from("syn"))),
+				Filter::Synonyms(Synonyms::Map(Object(
+					[("car".to_string(), Value::from("auto"))].into_iter().collect()
+				))),
+				Filter::Mapping(Object(
+					[("ß".to_string(), Value::from("ss"))].into_iter().collect()
+				)),
+			]),
+			comment: None,
+			function: None,
+		})),
+	)
+}
+
 #[test]
 fn parse_delete() {
 	let res = test_parse!(
@@ -651,6 +1188,18 @@ fn parse_info() {
 
 	let res = test_parse!(parse_stmt, "INFO FOR USER user ON namespace").unwrap();
 	assert_eq!(res, Statement::Info(InfoStatement::User(Ident("user".to_owned()), Some(Base::Ns))));
+
+	#[cfg(feature = "sql2")]
+	{
+		let res = test_parse!(parse_stmt, "INFO FOR INDEX index ON TABLE table").unwrap();
+		assert_eq!(
+			res,
+			Statement::Info(InfoStatement::Index(
+				Ident("index".to_owned()),
+				Ident("table".to_owned())
+			))
+		);
+	}
 }
 
 #[test]
@@ -980,6 +1529,26 @@ fn parse_live() {
 	)
 }
 
+#[cfg(feature = "sql2")]
+#[test]
+fn parse_live_with_before() {
+	let res =
+		test_parse!(parse_stmt, r#"LIVE SELECT * FROM person WHERE age > 18 WITH BEFORE"#).unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.what, Value::Table(Table("person".to_owned())));
+	assert!(stmt.cond.is_some());
+	assert!(stmt.before);
+	assert_eq!(stmt.to_string(), "LIVE SELECT * FROM person WHERE age > 18 WITH BEFORE");
+
+	let res = test_parse!(parse_stmt, r#"LIVE SELECT DIFF FROM person"#).unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert!(!stmt.before);
+}
+
 #[test]
 fn parse_option() {
 	let res = test_parse!(parse_stmt, r#"OPTION value = true"#).unwrap();
@@ -163,6 +163,9 @@ fn statements() -> Vec<Statement> {
 			comment: Some(Strand("test".to_string())),
 			changefeed: Some(ChangeFeed {
 				expiry: std::time::Duration::from_secs(60) * 10,
+				store_original: false,
+				max_changes: None,
+				max_bytes: None,
 			}),
 		})),
 		Statement::Define(DefineStatement::Database(DefineDatabaseStatement {
@@ -190,6 +193,10 @@ fn statements() -> Vec<Statement> {
 			kind: Algorithm::EdDSA,
 			code: "foo".to_string(),
 			comment: Some(Strand("bar".to_string())),
+			#[cfg(feature = "sql2")]
+			audience: None,
+			#[cfg(feature = "sql2")]
+			claims: None,
 		})),
 		Statement::Define(DefineStatement::Param(DefineParamStatement {
 			name: Ident("a".to_string()),
@@ -235,6 +242,9 @@ fn statements() -> Vec<Statement> {
 			},
 			changefeed: Some(ChangeFeed {
 				expiry: std::time::Duration::from_secs(1),
+				store_original: false,
+				max_changes: None,
+				max_bytes: None,
 			}),
 			comment: None,
 		})),
@@ -269,6 +279,8 @@ fn statements() -> Vec<Statement> {
 				select: Permission::Full,
 			},
 			comment: None,
+			encrypted: None,
+			deterministic: false,
 		})),
 		Statement::Define(DefineStatement::Index(DefineIndexStatement {
 			name: Ident("index".to_owned()),
@@ -294,6 +306,8 @@ fn statements() -> Vec<Statement> {
 				terms_cache: 8,
 			}),
 			comment: None,
+			concurrently: false,
+			cond: None,
 		})),
 		Statement::Define(DefineStatement::Index(DefineIndexStatement {
 			name: Ident("index".to_owned()),
@@ -301,6 +315,8 @@ fn statements() -> Vec<Statement> {
 			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))])]),
 			index: Index::Uniq,
 			comment: None,
+			concurrently: false,
+			cond: None,
 		})),
 		Statement::Define(DefineStatement::Index(DefineIndexStatement {
 			name: Ident("index".to_owned()),
@@ -314,8 +330,11 @@ fn statements() -> Vec<Statement> {
 				doc_ids_cache: 8,
 				mtree_cache: 9,
 				vector_type: VectorType::F64,
+				quantization: None,
 			}),
 			comment: None,
+			concurrently: false,
+			cond: None,
 		})),
 		Statement::Define(DefineStatement::Analyzer(DefineAnalyzerStatement {
 			name: Ident("ana".to_owned()),
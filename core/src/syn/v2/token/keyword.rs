@@ -24,6 +24,7 @@ macro_rules! keyword {
 }
 
 keyword! {
+	Ack => "ACK",
 	After => "AFTER",
 	All => "ALL",
 	Analyze => "ANALYZE",
@@ -47,11 +48,14 @@ keyword! {
 	Class => "CLASS",
 	Comment => "COMMENT",
 	Commit => "COMMIT",
+	Concurrently => "CONCURRENTLY",
+	Consumer => "CONSUMER",
 	Content => "CONTENT",
 	Continue => "CONTINUE",
 	Create => "CREATE",
 	Database => "DATABASE",
 	Default => "DEFAULT",
+	Deferred => "DEFERRED",
 	Define => "DEFINE",
 	Delete => "DELETE",
 	Descending => "DESCENDING",
@@ -70,6 +74,22 @@ keyword! {
 	End => "END",
 	Exists => "EXISTS",
 	Explain => "EXPLAIN",
+	Retry => "RETRY",
+	Secret => "SECRET",
+	Url => "URL",
+	Webhook => "WEBHOOK",
+	Grant => "GRANT",
+	Role => "ROLE",
+	Policy => "POLICY",
+	Issuer => "ISSUER",
+	Audience => "AUDIENCE",
+	Claims => "CLAIMS",
+	Expires => "EXPIRES",
+	Hash => "HASH",
+	Certificate => "CERTIFICATE",
+	Encrypted => "ENCRYPTED",
+	Deterministic => "DETERMINISTIC",
+	Reencrypt => "REENCRYPT",
 	False => "false",
 	Fetch => "FETCH",
 	Field => "FIELD",
@@ -83,6 +103,7 @@ keyword! {
 	Group => "GROUP",
 	Highlights => "HIGHLIGHTS",
 	Ignore => "IGNORE",
+	Include => "INCLUDE",
 	Index => "INDEX",
 	Info => "INFO",
 	Insert => "INSERT",
@@ -96,6 +117,7 @@ keyword! {
 	Limit => "LIMIT",
 	Live => "LIVE",
 	Lowercase => "LOWERCASE",
+	Mapping => "MAPPING",
 	Merge => "MERGE",
 	Model => "MODEL",
 	MTree => "MTREE",
@@ -112,6 +134,7 @@ keyword! {
 	Only => "ONLY",
 	Option => "OPTION",
 	Order => "ORDER",
+	Original => "ORIGINAL",
 	Parallel => "PARALLEL",
 	Param => "PARAM",
 	Passhash => "PASSHASH",
@@ -121,6 +144,7 @@ keyword! {
 	PostingsCache => "POSTINGS_CACHE",
 	PostingsOrder => "POSTINGS_ORDER",
 	Punct => "PUNCT",
+	Quantize => "QUANTIZE",
 	Readonly => "READONLY",
 	Relate => "RELATE",
 	Remove => "REMOVE",
@@ -143,6 +167,8 @@ keyword! {
 	Snowball => "SNOWBALL",
 	Split => "SPLIT",
 	Start => "START",
+	Stopwords => "STOPWORDS",
+	Synonyms => "SYNONYMS",
 	Table => "TABLE",
 	TermsCache => "TERMS_CACHE",
 	TermsOrder => "TERMS_ORDER",
@@ -154,6 +180,7 @@ keyword! {
 	Transaction => "TRANSACTION",
 	True => "true",
 	Type => "TYPE",
+	Unicode => "UNICODE",
 	Unique => "UNIQUE",
 	Unset => "UNSET",
 	Update => "UPDATE",
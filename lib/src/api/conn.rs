@@ -5,6 +5,7 @@ use crate::api::opt::Endpoint;
 use crate::api::ExtraFeatures;
 use crate::api::Result;
 use crate::api::Surreal;
+use crate::dbs::ChangeNotification;
 use crate::dbs::Notification;
 use crate::sql::from_value;
 use crate::sql::Query;
@@ -54,8 +55,12 @@ impl Drop for Router {
 pub enum Method {
 	/// Sends an authentication token to the server
 	Authenticate,
+	/// Runs several requests, optionally within a single transaction
+	Batch,
 	/// Performs a merge update operation
 	Merge,
+	/// Subscribes to the changefeed of a table or database
+	Changefeed,
 	/// Creates a record in a table
 	Create,
 	/// Deletes a record from a table
@@ -76,8 +81,20 @@ pub enum Method {
 	Live,
 	/// Performs a patch update operation
 	Patch,
+	/// Pulls the changes made to a database since a versionstamp
+	Pull,
+	/// Pushes a batch of changes made by a replica of a database
+	Push,
 	/// Sends a raw query to the database
 	Query,
+	/// Exchanges a refresh token for new authentication tokens
+	Refresh,
+	/// Relates records with a graph edge
+	Relate,
+	/// Revokes the sessions of a user
+	Revoke,
+	/// Runs a function
+	Run,
 	/// Selects a record or records from a table
 	Select,
 	/// Sets a parameter on the connection
@@ -124,6 +141,7 @@ pub struct Param {
 	pub(crate) file: Option<PathBuf>,
 	pub(crate) bytes_sender: Option<channel::Sender<Result<Vec<u8>>>>,
 	pub(crate) notification_sender: Option<channel::Sender<Notification>>,
+	pub(crate) changes_sender: Option<channel::Sender<ChangeNotification>>,
 	pub(crate) ml_config: Option<MlConfig>,
 }
 
@@ -162,6 +180,13 @@ impl Param {
 			..Default::default()
 		}
 	}
+
+	pub(crate) fn changes_sender(send: channel::Sender<ChangeNotification>) -> Self {
+		Self {
+			changes_sender: Some(send),
+			..Default::default()
+		}
+	}
 }
 
 /// Connection trait implemented by supported protocols
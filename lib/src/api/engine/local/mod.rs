@@ -31,10 +31,14 @@ use crate::api::conn::Method;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::api::conn::MlConfig;
 use crate::api::conn::Param;
+use crate::api::engine::batch_query;
+use crate::api::engine::batch_results;
 use crate::api::engine::create_statement;
 use crate::api::engine::delete_statement;
 use crate::api::engine::merge_statement;
 use crate::api::engine::patch_statement;
+use crate::api::engine::relate_statement;
+use crate::api::engine::run_statement;
 use crate::api::engine::select_statement;
 use crate::api::engine::update_statement;
 #[cfg(not(target_arch = "wasm32"))]
@@ -55,6 +59,7 @@ use crate::iam::Action;
 #[cfg(any(feature = "ml", feature = "ml2"))]
 #[cfg(not(target_arch = "wasm32"))]
 use crate::iam::ResourceKind;
+use crate::kvs::sync::Push;
 use crate::kvs::Datastore;
 #[cfg(any(feature = "ml", feature = "ml2"))]
 #[cfg(not(target_arch = "wasm32"))]
@@ -582,9 +587,26 @@ async fn router(
 				[Value::Object(credentials)] => mem::take(credentials),
 				_ => unreachable!(),
 			};
-			let response = crate::iam::signin::signin(kvs, session, credentials).await?;
+			let response =
+				crate::iam::signin::signin_with_refresh(kvs, session, credentials).await?;
 			Ok(DbResponse::Other(response.into()))
 		}
+		Method::Refresh => {
+			let token = match &mut params[..] {
+				[Value::Strand(Strand(token))] => mem::take(token),
+				_ => unreachable!(),
+			};
+			let response = crate::iam::refresh::refresh(kvs, session, &token).await?;
+			Ok(DbResponse::Other(response.into()))
+		}
+		Method::Revoke => {
+			let subject = match &mut params[..] {
+				[Value::Object(subject)] => Some(mem::take(subject)),
+				_ => None,
+			};
+			crate::iam::refresh::revoke(kvs, session, subject).await?;
+			Ok(DbResponse::Other(Value::None))
+		}
 		Method::Authenticate => {
 			let token = match &mut params[..] {
 				[Value::Strand(Strand(token))] => mem::take(token),
@@ -639,6 +661,26 @@ async fn router(
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
+		Method::Relate => {
+			let statement = relate_statement(&mut params);
+			let query = Query(Statements(vec![Statement::Relate(statement)]));
+			let response = kvs.process(query, &*session, Some(vars.clone())).await?;
+			let value = take(false, response).await?;
+			Ok(DbResponse::Other(value))
+		}
+		Method::Run => {
+			let function = run_statement(&mut params)?;
+			let query = Query(Statements(vec![Statement::Value(function)]));
+			let response = kvs.process(query, &*session, Some(vars.clone())).await?;
+			let value = take(false, response).await?;
+			Ok(DbResponse::Other(value))
+		}
+		Method::Batch => {
+			let (calls, query) = batch_query(&mut params)?;
+			let response = kvs.process(query, &*session, Some(vars.clone())).await?;
+			let value = batch_results(calls, process(response));
+			Ok(DbResponse::Other(value))
+		}
 		Method::Query => {
 			let response = match param.query {
 				Some((query, mut bindings)) => {
@@ -839,5 +881,27 @@ async fn router(
 			let value = kill_live_query(kvs, id, session, vars.clone()).await?;
 			Ok(DbResponse::Other(value))
 		}
+		Method::Pull => {
+			let (since, limit) = match &mut params[..] {
+				[since, limit] => (mem::take(since), mem::take(limit)),
+				_ => unreachable!(),
+			};
+			let limit = match limit {
+				Value::None => None,
+				limit => Some(u32::try_from(limit)?),
+			};
+			let changes = kvs.sync_pull(session, u64::try_from(since)?, limit).await?;
+			Ok(DbResponse::Other(changes.into()))
+		}
+		Method::Push => {
+			let push = match &mut params[..] {
+				[push] => Push::try_from(mem::take(push))?,
+				_ => unreachable!(),
+			};
+			let conflicts = kvs.sync_push(session, push).await?;
+			let conflicts: Vec<Value> = conflicts.into_iter().map(Value::from).collect();
+			Ok(DbResponse::Other(conflicts.into()))
+		}
+		Method::Changefeed => Err(Error::ChangefeedsNotSupported.into()),
 	}
 }
@@ -11,6 +11,7 @@ use crate::api::ExtraFeatures;
 use crate::api::OnceLockExt;
 use crate::api::Result;
 use crate::api::Surreal;
+use crate::dbs::Action;
 use crate::dbs::Session;
 use crate::engine::IntervalStream;
 use crate::iam::Level;
@@ -187,6 +188,7 @@ pub(crate) fn router(
 				}
 				Either::Right(notification) => {
 					let id = notification.id;
+					let killed = notification.action == Action::Killed;
 					if let Some(sender) = live_queries.get(&id) {
 						if sender.send(notification).await.is_err() {
 							live_queries.remove(&id);
@@ -197,6 +199,10 @@ pub(crate) fn router(
 							}
 						}
 					}
+					// Killed live queries will not receive any further notifications
+					if killed {
+						live_queries.remove(&id);
+					}
 				}
 			}
 		}
@@ -221,6 +227,16 @@ fn run_maintenance(kvs: Arc<Datastore>, tick_interval: Duration, stop_signal: Re
 		let mut stream = streams.merge();
 
 		while let Some(Some(_)) = stream.next().await {
+			// Deliver webhooks in the background, so that slow endpoints don't delay the tick
+			#[cfg(feature = "sql2")]
+			{
+				let kvs = kvs.clone();
+				tokio::spawn(async move {
+					if let Err(error) = kvs.deliver_webhooks().await {
+						error!("Error delivering webhooks: {error}");
+					}
+				});
+			}
 			match kvs.tick().await {
 				Ok(()) => trace!("Node agent tick ran successfully"),
 				Err(error) => error!("Error running node agent tick: {error}"),
@@ -14,15 +14,28 @@ pub mod local;
 #[cfg(any(feature = "protocol-http", feature = "protocol-ws"))]
 pub mod remote;
 
+use crate::api::err::Error;
+use crate::api::Response as QueryResponse;
+use crate::api::Result;
+use crate::sql::statements::BeginStatement;
+use crate::sql::statements::CommitStatement;
 use crate::sql::statements::CreateStatement;
 use crate::sql::statements::DeleteStatement;
+use crate::sql::statements::InsertStatement;
+use crate::sql::statements::RelateStatement;
 use crate::sql::statements::SelectStatement;
+use crate::sql::statements::SetStatement;
 use crate::sql::statements::UpdateStatement;
 use crate::sql::Array;
 use crate::sql::Data;
 use crate::sql::Field;
 use crate::sql::Fields;
+use crate::sql::Function;
+use crate::sql::Model;
 use crate::sql::Output;
+use crate::sql::Query;
+use crate::sql::Statement;
+use crate::sql::Statements;
 use crate::sql::Value;
 use crate::sql::Values;
 use futures::Stream;
@@ -69,6 +82,24 @@ fn create_statement(params: &mut [Value]) -> CreateStatement {
 	}
 }
 
+#[allow(dead_code)] // used by the the embedded database and `http`
+fn insert_statement(params: &mut [Value]) -> (bool, InsertStatement) {
+	let (what, data) = match params {
+		[what] => (mem::take(what), Value::None),
+		[what, data] => (mem::take(what), mem::take(data)),
+		_ => unreachable!(),
+	};
+	(
+		what.is_thing(),
+		InsertStatement {
+			into: what.could_be_table(),
+			data: Data::SingleExpression(data),
+			output: Some(Output::After),
+			..Default::default()
+		},
+	)
+}
+
 #[allow(dead_code)] // used by the the embedded database and `http`
 fn update_statement(params: &mut [Value]) -> (bool, UpdateStatement) {
 	let (one, what, data) = split_params(params);
@@ -149,6 +180,300 @@ fn delete_statement(params: &mut [Value]) -> (bool, DeleteStatement) {
 	)
 }
 
+#[allow(dead_code)] // used by the the embedded database and `http`
+fn relate_statement(params: &mut [Value]) -> RelateStatement {
+	let (from, kind, with, data) = match params {
+		[from, kind, with] => (mem::take(from), mem::take(kind), mem::take(with), Value::None),
+		[from, kind, with, data] => {
+			(mem::take(from), mem::take(kind), mem::take(with), mem::take(data))
+		}
+		_ => unreachable!(),
+	};
+	let data = match data {
+		Value::None | Value::Null => None,
+		value => Some(Data::ContentExpression(value)),
+	};
+	RelateStatement {
+		only: from.is_thing() && with.is_thing(),
+		kind: kind.could_be_table(),
+		from,
+		with,
+		data,
+		output: Some(Output::After),
+		..Default::default()
+	}
+}
+
+#[allow(dead_code)] // used by the the embedded database and `http`
+fn run_statement(params: &mut [Value]) -> Result<Value> {
+	let (name, version, args) = match params {
+		[Value::Strand(name)] => (mem::take(&mut name.0), Value::None, Value::None),
+		[Value::Strand(name), version] => (mem::take(&mut name.0), mem::take(version), Value::None),
+		[Value::Strand(name), version, args] => {
+			(mem::take(&mut name.0), mem::take(version), mem::take(args))
+		}
+		_ => return Err(Error::InvalidParams("expected the name of a function".to_owned()).into()),
+	};
+	let args = match args {
+		Value::None | Value::Null => Vec::new(),
+		Value::Array(Array(args)) => args,
+		_ => return Err(Error::InvalidParams("expected an array of arguments".to_owned()).into()),
+	};
+	let function = match (name.strip_prefix("fn::"), name.strip_prefix("ml::"), version) {
+		(Some(name), _, Value::None | Value::Null) => {
+			Value::Function(Box::new(Function::Custom(name.to_owned(), args)))
+		}
+		(_, Some(name), Value::Strand(version)) => Value::Model(Box::new(Model {
+			name: name.to_owned(),
+			version: version.0,
+			args,
+		})),
+		(None, None, Value::None | Value::Null) => {
+			Value::Function(Box::new(Function::Normal(name, args)))
+		}
+		_ => {
+			return Err(Error::InvalidParams(
+				"only machine learning models have a version".to_owned(),
+			)
+			.into())
+		}
+	};
+	Ok(function)
+}
+
+/// How the result of a call in a batch is taken from the responses to its statements
+#[allow(dead_code)] // used by the the embedded database and `http`
+enum BatchResult {
+	/// A data method, and whether it returns a single record
+	Method(bool),
+	/// A query, with the number of statements which set its parameters, and of its own statements
+	Query(usize, usize),
+}
+
+/// A call in a batch, as it is run within the query of the batch
+#[allow(dead_code)] // used by the the embedded database and `http`
+struct BatchCall {
+	/// The id of the call, which is returned with its result
+	id: Option<Value>,
+	/// How the result of the call is taken from the responses
+	result: BatchResult,
+}
+
+/// Converts the calls in a batch into a query, along with how the result of each call is taken
+///
+/// The calls accept the same methods and params as the `batch` RPC method of the server.
+#[allow(dead_code)] // used by the the embedded database and `http`
+fn batch_query(params: &mut [Value]) -> Result<(Vec<BatchCall>, Query)> {
+	let (calls, transaction) = match params {
+		[Value::Array(calls), transaction] => (mem::take(calls), transaction.is_true()),
+		_ => unreachable!(),
+	};
+	let mut out = Vec::with_capacity(calls.len());
+	let mut statements = Vec::with_capacity(calls.len() + 2);
+	if transaction {
+		statements.push(Statement::Begin(BeginStatement));
+	}
+	for call in calls {
+		let (id, method, params) = match call {
+			Value::Object(mut call) => {
+				(call.remove("id"), call.remove("method"), call.remove("params"))
+			}
+			call => {
+				return Err(Error::InvalidParams(format!("expected a call, found {call}")).into())
+			}
+		};
+		let (method, mut params) = match (method, params) {
+			(Some(Value::Strand(method)), Some(Value::Array(Array(params)))) => (method.0, params),
+			(Some(Value::Strand(method)), None) => (method.0, Vec::new()),
+			_ => {
+				return Err(
+					Error::InvalidParams("expected a method and its params".to_owned()).into()
+				)
+			}
+		};
+		let (result, statement) = match (method.as_str(), params.len()) {
+			("select", 1) => {
+				let (one, statement) = select_statement(&mut params);
+				(BatchResult::Method(one), Statement::Select(statement))
+			}
+			("insert", 1 | 2) => {
+				let (one, statement) = insert_statement(&mut params);
+				(BatchResult::Method(one), Statement::Insert(statement))
+			}
+			("create", 1 | 2) => {
+				let one = params[0].is_thing();
+				(BatchResult::Method(one), Statement::Create(create_statement(&mut params)))
+			}
+			("update", 1 | 2) => {
+				let (one, statement) = update_statement(&mut params);
+				(BatchResult::Method(one), Statement::Update(statement))
+			}
+			("merge", 1 | 2) => {
+				let (one, statement) = merge_statement(&mut params);
+				(BatchResult::Method(one), Statement::Update(statement))
+			}
+			("patch", 1..=3) => {
+				// The third param returns the diff of the patch
+				let diff = params.get(2).is_some_and(Value::is_true);
+				params.truncate(2);
+				let (one, mut statement) = patch_statement(&mut params);
+				if diff {
+					statement.output = Some(Output::Diff);
+				}
+				(BatchResult::Method(one), Statement::Update(statement))
+			}
+			("delete", 1) => {
+				let (one, statement) = delete_statement(&mut params);
+				(BatchResult::Method(one), Statement::Delete(statement))
+			}
+			("relate", 3 | 4) => {
+				(BatchResult::Method(false), Statement::Relate(relate_statement(&mut params)))
+			}
+			("run", 1..=3) => {
+				(BatchResult::Method(false), Statement::Value(run_statement(&mut params)?))
+			}
+			// A query could end the transaction of the batch
+			("query", 1 | 2) if !transaction => {
+				let (vars, query) = query_statements(&mut params)?;
+				let result = BatchResult::Query(vars.len(), query.len());
+				statements.extend(vars);
+				statements.extend(query);
+				out.push(BatchCall {
+					id,
+					result,
+				});
+				continue;
+			}
+			("query", _) if transaction => {
+				return Err(Error::InvalidParams(
+					"the `query` method can not be called within a transaction".to_owned(),
+				)
+				.into())
+			}
+			(method, _) => {
+				return Err(Error::InvalidParams(format!(
+					"the `{method}` method can not be called in a batch with these params"
+				))
+				.into())
+			}
+		};
+		out.push(BatchCall {
+			id,
+			result,
+		});
+		statements.push(statement);
+	}
+	if transaction {
+		statements.push(Statement::Commit(CommitStatement));
+	}
+	Ok((out, Query(Statements(statements))))
+}
+
+/// Converts the params of a `query` call into the statements which set its parameters, and its own statements
+#[allow(dead_code)] // used by the the embedded database and `http`
+fn query_statements(params: &mut [Value]) -> Result<(Vec<Statement>, Vec<Statement>)> {
+	let (query, vars) = match params {
+		[query] => (mem::take(query), Value::None),
+		[query, vars] => (mem::take(query), mem::take(vars)),
+		_ => unreachable!(),
+	};
+	let query = match query {
+		Value::Strand(query) => crate::syn::parse(&query)?.0 .0,
+		Value::Query(query) => query.0 .0,
+		_ => return Err(Error::InvalidParams("expected a query".to_owned()).into()),
+	};
+	let vars = match vars {
+		Value::None | Value::Null => Vec::new(),
+		Value::Object(vars) => vars
+			.0
+			.into_iter()
+			.map(|(name, what)| {
+				Statement::Set(SetStatement {
+					name,
+					what,
+				})
+			})
+			.collect(),
+		_ => return Err(Error::InvalidParams("expected an object of params".to_owned()).into()),
+	};
+	Ok((vars, query))
+}
+
+/// Converts the responses to the calls in a batch into their results
+#[allow(dead_code)] // used by the the embedded database and `http`
+fn batch_results(calls: Vec<BatchCall>, mut response: QueryResponse) -> Value {
+	let mut results = Vec::with_capacity(calls.len());
+	let mut index = 0;
+	for BatchCall {
+		id,
+		result,
+	} in calls
+	{
+		let mut result = match result {
+			BatchResult::Method(one) => {
+				let result = match response.results.swap_remove(&index) {
+					Some((_, Ok(value))) if one => Ok(value.first()),
+					Some((_, result)) => result,
+					None => {
+						Err(Error::InternalError(format!("missing the result of call {index}"))
+							.into())
+					}
+				};
+				index += 1;
+				match result {
+					Ok(value) => crate::map! {
+						"result".to_owned() => value,
+					},
+					Err(error) => crate::map! {
+						"error".to_owned() => Value::from(crate::map! {
+							"message".to_owned() => Value::from(error.to_string()),
+						}),
+					},
+				}
+			}
+			// A query returns the responses to each of its statements
+			BatchResult::Query(vars, statements) => {
+				index += vars;
+				let mut responses = Vec::with_capacity(statements);
+				for _ in 0..statements {
+					let (stats, result) = match response.results.swap_remove(&index) {
+						Some((stats, result)) => (stats.execution_time, result),
+						None => (
+							None,
+							Err(Error::InternalError(format!(
+								"missing the result of query {index}"
+							))
+							.into()),
+						),
+					};
+					index += 1;
+					let time = format!("{:?}", stats.unwrap_or_default());
+					responses.push(Value::from(match result {
+						Ok(value) => crate::map! {
+							"status".to_owned() => Value::from("OK"),
+							"time".to_owned() => Value::from(time),
+							"result".to_owned() => value,
+						},
+						Err(error) => crate::map! {
+							"status".to_owned() => Value::from("ERR"),
+							"time".to_owned() => Value::from(time),
+							"result".to_owned() => Value::from(error.to_string()),
+						},
+					}));
+				}
+				crate::map! {
+					"result".to_owned() => Value::from(responses),
+				}
+			}
+		};
+		if let Some(id) = id {
+			result.insert("id".to_owned(), id);
+		}
+		results.push(Value::from(result));
+	}
+	Value::from(results)
+}
+
 struct IntervalStream {
 	inner: Interval,
 }
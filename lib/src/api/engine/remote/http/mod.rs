@@ -11,11 +11,15 @@ use crate::api::conn::Method;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::api::conn::MlConfig;
 use crate::api::conn::Param;
+use crate::api::engine::batch_query;
+use crate::api::engine::batch_results;
 use crate::api::engine::create_statement;
 use crate::api::engine::delete_statement;
 use crate::api::engine::merge_statement;
 use crate::api::engine::patch_statement;
+use crate::api::engine::relate_statement;
 use crate::api::engine::remote::duration_from_str;
+use crate::api::engine::run_statement;
 use crate::api::engine::select_statement;
 use crate::api::engine::update_statement;
 use crate::api::err::Error;
@@ -29,6 +33,7 @@ use crate::headers::AUTH_DB;
 use crate::headers::AUTH_NS;
 use crate::headers::DB_LEGACY;
 use crate::headers::NS_LEGACY;
+use crate::iam::refresh::Tokens;
 use crate::method::Stats;
 use crate::opt::IntoEndpoint;
 use crate::sql::from_value;
@@ -60,6 +65,7 @@ use tokio_util::compat::FuturesAsyncReadCompatExt;
 use url::Url;
 
 const SQL_PATH: &str = "sql";
+const SYNC_PATH: &str = "sync";
 
 /// The HTTP scheme used to connect to `http://` endpoints
 #[derive(Debug)]
@@ -174,6 +180,15 @@ struct AuthResponse {
 	code: u16,
 	details: String,
 	token: Option<String>,
+	refresh: Option<String>,
+}
+
+/// Returns the access token from the response to a signin or refresh request
+fn access_token(value: &Value) -> String {
+	match value {
+		Value::Object(v) => v.get("token").map(Value::to_raw_string).unwrap_or_default(),
+		v => v.to_raw_string(),
+	}
 }
 
 async fn submit_auth(request: RequestBuilder) -> Result<Value> {
@@ -184,7 +199,11 @@ async fn submit_auth(request: RequestBuilder) -> Result<Value> {
 			binary: bytes.to_vec(),
 			error,
 		})?;
-	Ok(response.token.into())
+	Ok(Tokens {
+		token: response.token,
+		refresh: response.refresh,
+	}
+	.into())
 }
 
 async fn query(request: RequestBuilder) -> Result<QueryResponse> {
@@ -332,6 +351,16 @@ async fn import(request: RequestBuilder, path: PathBuf) -> Result<Value> {
 	Ok(Value::None)
 }
 
+async fn sync(request: RequestBuilder) -> Result<Value> {
+	let response = request.send().await?.error_for_status()?;
+	let bytes = response.bytes().await?;
+	let value = deserialize(&bytes).map_err(|error| Error::ResponseFromBinary {
+		binary: bytes.to_vec(),
+		error,
+	})?;
+	Ok(value)
+}
+
 async fn version(request: RequestBuilder) -> Result<Value> {
 	let response = request.send().await?.error_for_status()?;
 	let version = response.text().await?;
@@ -423,12 +452,36 @@ async fn router(
 					});
 				} else {
 					*auth = Some(Auth::Bearer {
-						token: value.to_raw_string(),
+						token: access_token(&value),
 					});
 				}
 			}
 			Ok(DbResponse::Other(value))
 		}
+		Method::Refresh => {
+			let path = base_url.join(Method::Refresh.as_str())?;
+			let token = match &mut params[..] {
+				[Value::Strand(Strand(token))] => mem::take(token),
+				_ => unreachable!(),
+			};
+			let body = serde_json::json!({ "refresh": token });
+			let request = client.post(path).headers(headers.clone()).body(body.to_string());
+			let value = submit_auth(request).await?;
+			*auth = Some(Auth::Bearer {
+				token: access_token(&value),
+			});
+			Ok(DbResponse::Other(value))
+		}
+		Method::Revoke => {
+			let path = base_url.join(Method::Revoke.as_str())?;
+			let mut request = client.post(path).headers(headers.clone()).auth(auth);
+			if let [subject] = &params[..] {
+				request = request.body(subject.clone().into_json().to_string());
+			}
+			let response = request.send().await?;
+			response.error_for_status()?;
+			Ok(DbResponse::Other(Value::None))
+		}
 		Method::Signup => {
 			let path = base_url.join(Method::Signup.as_str())?;
 			let credentials = match &mut params[..] {
@@ -505,6 +558,30 @@ async fn router(
 			let value = take(one, request).await?;
 			Ok(DbResponse::Other(value))
 		}
+		Method::Relate => {
+			let path = base_url.join(SQL_PATH)?;
+			let statement = relate_statement(&mut params);
+			let request =
+				client.post(path).headers(headers.clone()).auth(auth).body(statement.to_string());
+			let value = take(false, request).await?;
+			Ok(DbResponse::Other(value))
+		}
+		Method::Run => {
+			let path = base_url.join(SQL_PATH)?;
+			let function = run_statement(&mut params)?;
+			let request =
+				client.post(path).headers(headers.clone()).auth(auth).body(function.to_string());
+			let value = take(false, request).await?;
+			Ok(DbResponse::Other(value))
+		}
+		Method::Batch => {
+			let path = base_url.join(SQL_PATH)?;
+			let (calls, statements) = batch_query(&mut params)?;
+			let request =
+				client.post(path).headers(headers.clone()).auth(auth).body(statements.to_string());
+			let value = batch_results(calls, query(request).await?);
+			Ok(DbResponse::Other(value))
+		}
 		Method::Query => {
 			let path = base_url.join(SQL_PATH)?;
 			let mut request = client.post(path).headers(headers.clone()).query(&vars).auth(auth);
@@ -619,5 +696,30 @@ async fn router(
 			let value = take(true, request).await?;
 			Ok(DbResponse::Other(value))
 		}
+		Method::Pull => {
+			let path = base_url.join(SYNC_PATH)?;
+			let (since, limit) = match &params[..] {
+				[since, limit] => (since.to_string(), limit),
+				_ => unreachable!(),
+			};
+			let mut request =
+				client.get(path).headers(headers.clone()).auth(auth).query(&[("since", since)]);
+			if !limit.is_none() {
+				request = request.query(&[("limit", limit.to_string())]);
+			}
+			let value = sync(request).await?;
+			Ok(DbResponse::Other(value))
+		}
+		Method::Push => {
+			let path = base_url.join(SYNC_PATH)?;
+			let push = match &params[..] {
+				[push] => push.to_string(),
+				_ => unreachable!(),
+			};
+			let request = client.post(path).headers(headers.clone()).auth(auth).body(push);
+			let value = sync(request).await?;
+			Ok(DbResponse::Other(value))
+		}
+		Method::Changefeed => Err(Error::ChangefeedsNotSupported.into()),
 	}
 }
@@ -14,6 +14,7 @@ use crate::api::method::query::QueryResult;
 use crate::api::Connect;
 use crate::api::Result;
 use crate::api::Surreal;
+use crate::dbs::ChangeNotification;
 use crate::dbs::Notification;
 use crate::dbs::QueryMethodResponse;
 use crate::dbs::Status;
@@ -25,6 +26,7 @@ use revision::revisioned;
 use revision::Revisioned;
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::io::Read;
 use std::marker::PhantomData;
 use std::time::Duration;
@@ -84,10 +86,13 @@ impl Surreal<Client> {
 }
 
 #[derive(Clone, Debug, Deserialize)]
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 pub(crate) struct Failure {
 	pub(crate) code: i64,
 	pub(crate) message: String,
+	#[revision(start = 2)]
+	#[serde(default)]
+	pub(crate) data: Option<Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -96,12 +101,28 @@ pub(crate) enum Data {
 	Other(Value),
 	Query(Vec<QueryMethodResponse>),
 	Live(Notification),
+	Changes(ChangeNotification),
 }
 
 type ServerResult = std::result::Result<Data, Failure>;
 
 impl From<Failure> for Error {
 	fn from(failure: Failure) -> Self {
+		// Unique index violations detail the record which holds the value
+		if let Some(Value::Object(mut data)) = failure.data {
+			if let (
+				Some(Value::Thing(thing)),
+				Some(Value::Strand(index)),
+				Some(Value::Strand(value)),
+			) = (data.remove("thing"), data.remove("index"), data.remove("value"))
+			{
+				return Self::IndexExists {
+					thing,
+					index: index.0,
+					value: value.0,
+				};
+			}
+		}
 		match failure.code {
 			-32600 => Self::InvalidRequest(failure.message),
 			-32602 => Self::InvalidParams(failure.message),
@@ -142,8 +163,8 @@ impl DbResponse {
 					..api::Response::new()
 				}))
 			}
-			// Live notifications don't call this method
-			Data::Live(..) => unreachable!(),
+			// Live and changefeed notifications don't call this method
+			Data::Live(..) | Data::Changes(..) => unreachable!(),
 		}
 	}
 }
@@ -164,6 +185,16 @@ fn serialize(value: &Value, revisioned: bool) -> Result<Vec<u8>> {
 	crate::sql::serde::serialize(value).map_err(|error| crate::Error::Db(error.into()))
 }
 
+// Serialises a request whose response is not routed back to a caller
+fn unrouted_request(method: Method, params: Vec<Value>, revisioned: bool) -> Result<Vec<u8>> {
+	let mut request = BTreeMap::new();
+	request.insert("method".to_owned(), method.as_str().into());
+	request.insert("params".to_owned(), params.into());
+	let payload = Value::from(request);
+	trace!("Request {payload}");
+	serialize(&payload, revisioned)
+}
+
 fn deserialize<A, T>(bytes: &mut A, revisioned: bool) -> Result<T>
 where
 	A: Read,
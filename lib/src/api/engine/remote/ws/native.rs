@@ -1,5 +1,5 @@
 use super::PATH;
-use super::{deserialize, serialize};
+use super::{deserialize, serialize, unrouted_request};
 use crate::api::conn::Connection;
 use crate::api::conn::DbResponse;
 use crate::api::conn::Method;
@@ -18,6 +18,7 @@ use crate::api::ExtraFeatures;
 use crate::api::OnceLockExt;
 use crate::api::Result;
 use crate::api::Surreal;
+use crate::dbs::Action;
 use crate::engine::remote::ws::Data;
 use crate::engine::IntervalStream;
 use crate::sql::Strand;
@@ -198,6 +199,7 @@ pub(crate) fn router(
 		let mut var_stash = IndexMap::new();
 		let mut vars = IndexMap::new();
 		let mut replay = IndexMap::new();
+		let mut changefeeds = HashMap::new();
 
 		'router: loop {
 			let (socket_sink, socket_stream) = socket.split();
@@ -268,6 +270,15 @@ pub(crate) fn router(
 								Method::Kill => {
 									if let [Value::Uuid(id)] = &params[..1] {
 										live_queries.remove(id);
+										changefeeds.remove(id);
+									}
+								}
+								Method::Changefeed => {
+									if let Some(sender) = param.changes_sender {
+										if let [what, since, Value::Uuid(id)] = &params[..] {
+											changefeeds
+												.insert(*id, (what.clone(), since.clone(), sender));
+										}
 									}
 								}
 								_ => {}
@@ -365,6 +376,8 @@ pub(crate) fn router(
 													None => match response.result {
 														Ok(Data::Live(notification)) => {
 															let live_query_id = notification.id;
+															let killed = notification.action
+																== Action::Killed;
 															// Check if this live query is registered
 															if let Some(sender) =
 																live_queries.get(&live_query_id)
@@ -411,6 +424,46 @@ pub(crate) fn router(
 																	}
 																}
 															}
+															// Killed live queries will not receive any further notifications
+															if killed {
+																live_queries.remove(&live_query_id);
+															}
+														}
+														Ok(Data::Changes(changes)) => {
+															let changefeed_id = changes.id;
+															// Check if this changefeed is registered
+															if let Some((_, since, sender)) =
+																changefeeds.get_mut(&changefeed_id)
+															{
+																// Resume after these changes when reconnecting
+																*since = Value::from(
+																	changes.versionstamp + 1,
+																);
+																// Send the changes back to the caller or stop the changefeed if the receiver is already dropped
+																if sender
+																	.send(changes)
+																	.await
+																	.is_err()
+																{
+																	changefeeds
+																		.remove(&changefeed_id);
+																	let kill = unrouted_request(
+																		Method::Kill,
+																		vec![Value::from(
+																			changefeed_id,
+																		)],
+																		endpoint.supports_revision,
+																	)
+																	.unwrap();
+																	if let Err(error) = socket_sink
+																		.send(Message::Binary(kill))
+																		.await
+																	{
+																		trace!("failed to send kill query to the server; {error:?}");
+																		break;
+																	}
+																}
+															}
 														}
 														Ok(..) => { /* Ignored responses like pings */
 														}
@@ -520,6 +573,20 @@ pub(crate) fn router(
 								continue 'reconnect;
 							}
 						}
+						for (id, (what, since, _)) in &changefeeds {
+							let params = vec![what.clone(), since.clone(), Value::from(*id)];
+							let payload = unrouted_request(
+								Method::Changefeed,
+								params,
+								endpoint.supports_revision,
+							)
+							.unwrap();
+							if let Err(error) = socket.send(Message::Binary(payload)).await {
+								trace!("{error}");
+								time::sleep(time::Duration::from_secs(1)).await;
+								continue 'reconnect;
+							}
+						}
 						trace!("Reconnected successfully");
 						break;
 					}
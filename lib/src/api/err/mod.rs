@@ -171,6 +171,14 @@ pub enum Error {
 	#[error("The protocol or storage engine does not support live queries on this architecture")]
 	LiveQueriesNotSupported,
 
+	/// The protocol or storage engine being used does not support streaming changefeeds
+	#[error("The protocol or storage engine does not support streaming changefeeds")]
+	ChangefeedsNotSupported,
+
+	/// The live query was ended by the server
+	#[error("The live query was killed: {0}")]
+	LiveQueryKilled(String),
+
 	/// Tried to use a range query on an object
 	#[error("Live queries on objects not supported: {0}")]
 	LiveOnObject(Object),
@@ -194,6 +202,14 @@ pub enum Error {
 	/// Called `Response::take` or `Response::stream` on a query response more than once
 	#[error("Tried to take a query response that has already been taken")]
 	ResponseAlreadyTaken,
+
+	/// A unique index already contains the value, which is held by another record
+	#[error("Database index `{index}` already contains {value}, with record `{thing}`")]
+	IndexExists {
+		thing: Thing,
+		index: String,
+		value: String,
+	},
 }
 
 #[cfg(feature = "protocol-http")]
@@ -10,5 +10,6 @@ pub static DB: HeaderName = HeaderName::from_static("surreal-db");
 pub static DB_LEGACY: HeaderName = HeaderName::from_static("db");
 pub static AUTH_NS: HeaderName = HeaderName::from_static("surreal-auth-ns");
 pub static AUTH_DB: HeaderName = HeaderName::from_static("surreal-auth-db");
+pub static AUTH_SC: HeaderName = HeaderName::from_static("surreal-auth-sc");
 pub static VERSION: HeaderName = HeaderName::from_static("surreal-version");
 pub static VERSION_LEGACY: HeaderName = HeaderName::from_static("version");
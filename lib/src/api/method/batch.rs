@@ -0,0 +1,107 @@
+use crate::api::conn::Method;
+use crate::api::conn::Param;
+use crate::api::err::Error;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::sql::to_value;
+use crate::sql::Array;
+use crate::sql::Object;
+use crate::sql::Value;
+use crate::Surreal;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::future::Future;
+use std::future::IntoFuture;
+use std::pin::Pin;
+
+/// A batch future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Batch<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) calls: Result<Vec<Value>>,
+	pub(super) transaction: bool,
+}
+
+impl<'r, C> Batch<'r, C>
+where
+	C: Connection,
+{
+	/// Adds a call to an RPC method, such as `create`, `relate` or `query`, to the batch
+	///
+	/// A tuple or a `Vec` is passed as several parameters, and any other value as a single one.
+	pub fn call<P>(mut self, method: impl Into<String>, params: P) -> Self
+	where
+		P: Serialize,
+	{
+		let call = to_value(params).map(|params| {
+			let params = match params {
+				Value::None | Value::Null => Value::Array(Array::new()),
+				Value::Array(params) => Value::Array(params),
+				param => Value::Array(Array(vec![param])),
+			};
+			Value::Object(Object::from(crate::map! {
+				"method".to_owned() => Value::from(method.into()),
+				"params".to_owned() => params,
+			}))
+		});
+		self.calls = self.calls.and_then(|mut calls| {
+			calls.push(call?);
+			Ok(calls)
+		});
+		self
+	}
+
+	/// Runs the calls within a single transaction, so that either all or none of them are applied
+	///
+	/// Only the methods which read or write data, and `run`, can be called within a transaction.
+	pub fn transaction(mut self) -> Self {
+		self.transaction = true;
+		self
+	}
+
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Batch<'static, C> {
+		Batch {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Batch<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Vec<Result<Value>>>;
+	type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + Sync + 'r>>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			let mut conn = Client::new(Method::Batch);
+			let params = vec![self.calls?.into(), self.transaction.into()];
+			match conn.execute_value(router, Param::new(params)).await? {
+				Value::Array(results) => Ok(results.into_iter().map(result).collect()),
+				v => Err(Error::InternalError(format!("expected the results, found {v}")).into()),
+			}
+		})
+	}
+}
+
+/// Converts the response to a call in the batch into its result
+fn result(value: Value) -> Result<Value> {
+	match value {
+		Value::Object(mut v) => match (v.remove("result"), v.remove("error")) {
+			// A result of NONE may be left out
+			(result, None) => Ok(result.unwrap_or_default()),
+			(_, Some(Value::Object(mut error))) => {
+				let message = error.remove("message").unwrap_or_default().as_raw_string();
+				Err(Error::Query(message).into())
+			}
+			(_, Some(error)) => Err(Error::Query(error.as_raw_string()).into()),
+		},
+		v => Err(Error::InternalError(format!("expected a result, found {v}")).into()),
+	}
+}
@@ -0,0 +1,140 @@
+use crate::api::conn::Method;
+use crate::api::conn::Param;
+use crate::api::method::live;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::dbs;
+use crate::engine::any::Any;
+use crate::method::OnceLockExt;
+use crate::sql::Table;
+use crate::sql::Uuid;
+use crate::sql::Value;
+use crate::Surreal;
+use channel::Receiver;
+use futures::StreamExt;
+use std::borrow::Cow;
+use std::future::Future;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+// The number of change sets which are buffered before the server waits for the stream to be polled
+const BUFFER_SIZE: usize = 100;
+
+/// A changefeed subscription future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Changes<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) table: Option<String>,
+	pub(super) since: u128,
+}
+
+impl<'r, C> Changes<'r, C>
+where
+	C: Connection,
+{
+	/// Only streams the changes made to a specific table
+	pub fn table(mut self, table: impl Into<String>) -> Self {
+		self.table = Some(table.into());
+		self
+	}
+
+	/// Streams the changes committed at or after a specific versionstamp
+	pub fn since(mut self, versionstamp: u128) -> Self {
+		self.since = versionstamp;
+		self
+	}
+
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Changes<'static, C> {
+		Changes {
+			client: Cow::Owned(self.client.into_owned()),
+			table: self.table,
+			since: self.since,
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Changes<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<ChangeStream>;
+	type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + Sync + 'r>>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			// The id is chosen here, so that the subscription can be resumed after reconnecting
+			let id = Uuid::new_v4();
+			let what = match self.table {
+				Some(table) => Table(table).into(),
+				None => Value::None,
+			};
+			let (tx, rx) = channel::bounded(BUFFER_SIZE);
+			let mut param = Param::changes_sender(tx);
+			param.other = vec![what, Value::from(self.since), id.into()];
+			let mut conn = Client::new(Method::Changefeed);
+			conn.execute_value(router, param).await?;
+			Ok(ChangeStream {
+				id,
+				rx: Some(rx),
+				client: Surreal {
+					router: self.client.router.clone(),
+					engine: PhantomData,
+				},
+			})
+		})
+	}
+}
+
+/// A set of changes which were committed at the same versionstamp
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ChangeSet {
+	/// The versionstamp at which the changes were committed
+	pub versionstamp: u128,
+	/// The changes, in the same format as returned by `SHOW CHANGES`
+	pub changes: Value,
+}
+
+/// A stream of the changes committed to a changefeed
+#[derive(Debug)]
+#[must_use = "streams do nothing unless you poll them"]
+pub struct ChangeStream {
+	pub(crate) client: Surreal<Any>,
+	pub(crate) id: Uuid,
+	pub(crate) rx: Option<Receiver<dbs::ChangeNotification>>,
+}
+
+impl futures::Stream for ChangeStream {
+	type Item = ChangeSet;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let Some(ref mut rx) = self.as_mut().rx else {
+			return Poll::Ready(None);
+		};
+		match rx.poll_next_unpin(cx) {
+			Poll::Ready(Some(notification)) => Poll::Ready(Some(ChangeSet {
+				versionstamp: notification.versionstamp,
+				changes: notification.changes,
+			})),
+			Poll::Ready(None) => Poll::Ready(None),
+			Poll::Pending => Poll::Pending,
+		}
+	}
+}
+
+impl Drop for ChangeStream {
+	/// Close the changefeed stream
+	///
+	/// This stops the changefeed subscription on the server.
+	fn drop(&mut self) {
+		if self.rx.is_some() {
+			live::kill(&self.client, self.id.into());
+		}
+	}
+}
@@ -286,7 +286,12 @@ where
 		notification => Poll::Ready(Some(Notification {
 			query_id: notification.id.0,
 			action: notification.action.into(),
+			record: record(notification.record),
 			data: notification.result,
+			before: match notification.before {
+				Value::None => None,
+				before => Some(before),
+			},
 		}))
 	}
 }
@@ -294,18 +299,38 @@ where
 macro_rules! poll_next_and_convert {
 	() => {
 		poll_next! {
-			notification => match from_value(notification.result) {
-				Ok(data) => Poll::Ready(Some(Ok(Notification {
-					data,
-					query_id: notification.id.0,
-					action: notification.action.into(),
-				}))),
-				Err(error) => Poll::Ready(Some(Err(error.into()))),
-			}
+			notification => Poll::Ready(Some(convert(notification)))
 		}
 	};
 }
 
+fn record(value: Value) -> Option<Thing> {
+	match value {
+		Value::Thing(thing) => Some(thing),
+		_ => None,
+	}
+}
+
+fn convert<R>(notification: dbs::Notification) -> Result<Notification<R>>
+where
+	R: DeserializeOwned,
+{
+	if notification.action == dbs::Action::Killed {
+		return Err(Error::LiveQueryKilled(notification.result.as_raw_string()).into());
+	}
+	let before = match notification.before {
+		Value::None => None,
+		before => Some(from_value(before)?),
+	};
+	Ok(Notification {
+		query_id: notification.id.0,
+		action: notification.action.into(),
+		record: record(notification.record),
+		data: from_value(notification.result)?,
+		before,
+	})
+}
+
 impl<C, R> futures::Stream for Stream<'_, C, Option<R>>
 where
 	C: Connection,
@@ -4,8 +4,10 @@ pub(crate) mod live;
 pub(crate) mod query;
 
 mod authenticate;
+mod batch;
 mod begin;
 mod cancel;
+mod changes;
 mod commit;
 mod content;
 mod create;
@@ -16,6 +18,12 @@ mod import;
 mod invalidate;
 mod merge;
 mod patch;
+mod pull;
+mod push;
+mod refresh;
+mod relate;
+mod revoke;
+mod run;
 mod select;
 mod set;
 mod signin;
@@ -30,12 +38,16 @@ mod version;
 mod tests;
 
 pub use authenticate::Authenticate;
+pub use batch::Batch;
 #[doc(hidden)] // Not supported yet
 pub use begin::Begin;
 #[doc(hidden)] // Not supported yet
 pub use begin::Transaction;
 #[doc(hidden)] // Not supported yet
 pub use cancel::Cancel;
+pub use changes::ChangeSet;
+pub use changes::ChangeStream;
+pub use changes::Changes;
 #[doc(hidden)] // Not supported yet
 pub use commit::Commit;
 pub use content::Content;
@@ -49,8 +61,14 @@ pub use invalidate::Invalidate;
 pub use live::Stream;
 pub use merge::Merge;
 pub use patch::Patch;
+pub use pull::Pull;
+pub use push::Push;
 pub use query::Query;
 pub use query::QueryStream;
+pub use refresh::Refresh;
+pub use relate::Relate;
+pub use revoke::Revoke;
+pub use run::Run;
 pub use select::Select;
 pub use set::Set;
 pub use signin::Signin;
@@ -66,13 +84,18 @@ use crate::api::opt;
 use crate::api::opt::auth;
 use crate::api::opt::auth::Credentials;
 use crate::api::opt::auth::Jwt;
+use crate::api::opt::auth::RefreshToken;
 use crate::api::opt::IntoEndpoint;
 use crate::api::Connect;
 use crate::api::Connection;
 use crate::api::OnceLockExt;
 use crate::api::Surreal;
+use crate::kvs::sync;
+use crate::kvs::sync::Change;
 use crate::opt::IntoExportDestination;
 use crate::sql::to_value;
+use crate::sql::Table;
+use crate::sql::Thing;
 use crate::sql::Value;
 use serde::Serialize;
 use std::borrow::Cow;
@@ -105,6 +128,8 @@ impl Method {
 	pub(crate) fn as_str(&self) -> &str {
 		match self {
 			Method::Authenticate => "authenticate",
+			Method::Batch => "batch",
+			Method::Changefeed => "changefeed",
 			Method::Create => "create",
 			Method::Delete => "delete",
 			Method::Export => "export",
@@ -115,7 +140,13 @@ impl Method {
 			Method::Live => "live",
 			Method::Merge => "merge",
 			Method::Patch => "patch",
+			Method::Pull => "pull",
+			Method::Push => "push",
 			Method::Query => "query",
+			Method::Refresh => "refresh",
+			Method::Relate => "relate",
+			Method::Revoke => "revoke",
+			Method::Run => "run",
 			Method::Select => "select",
 			Method::Set => "set",
 			Method::Signin => "signin",
@@ -586,6 +617,8 @@ where
 
 	/// Authenticates the current connection with a JWT token
 	///
+	/// The secret of an API key, which is returned by `DEFINE KEY`, can be used in place of a token.
+	///
 	/// # Examples
 	///
 	/// ```no_run
@@ -604,6 +637,49 @@ where
 		}
 	}
 
+	/// Exchanges a refresh token for a new access token and a new refresh token,
+	/// and authenticates the current connection with them
+	///
+	/// Each refresh token can only be used once.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// # let token = String::new();
+	/// let tokens = db.refresh(token).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn refresh(&self, token: impl Into<RefreshToken>) -> Refresh<C> {
+		Refresh {
+			client: Cow::Borrowed(self),
+			token: token.into(),
+		}
+	}
+
+	/// Revokes every access and refresh token which was issued to the user of
+	/// the current connection, so that they need to signin again
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// db.revoke().await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn revoke(&self) -> Revoke<C> {
+		Revoke {
+			client: Cow::Borrowed(self),
+			subject: Ok(None),
+		}
+	}
+
 	/// Runs a set of SurrealQL statements against the database
 	///
 	/// # Examples
@@ -940,6 +1016,242 @@ where
 		}
 	}
 
+	/// Relates two records with a graph edge
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use serde::Deserialize;
+	/// use serde_json::json;
+	///
+	/// #[derive(Deserialize)]
+	/// struct Knows {
+	///     since: u32,
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Relate two records with a `knows` edge
+	/// let edge: Option<Knows> = db
+	///     .relate(("person", "tobie"), "knows", ("person", "jaime"))
+	///     .content(json!({ "since": 2015 }))
+	///     .await?;
+	///
+	/// // Traverse the edges of a record
+	/// let mut response = db.query("SELECT VALUE ->knows->person FROM ONLY person:tobie").await?;
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn relate<R>(
+		&self,
+		from: impl Into<Thing>,
+		kind: impl Into<String>,
+		with: impl Into<Thing>,
+	) -> Relate<C, R> {
+		Relate {
+			client: Cow::Borrowed(self),
+			from: from.into(),
+			kind: Table(kind.into()),
+			with: with.into(),
+			content: Ok(Value::None),
+			response_type: PhantomData,
+		}
+	}
+
+	/// Runs a function defined with `DEFINE FUNCTION`, a builtin function, or a machine learning model
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Run a function defined with `DEFINE FUNCTION fn::greet`
+	/// let greeting: String = db.run("fn::greet").args("Tobie").await?;
+	///
+	/// // Run a builtin function with several arguments
+	/// let distance: f64 = db.run("geo::distance").args(((0.0, 0.0), (1.0, 1.0))).await?;
+	///
+	/// // Run a machine learning model
+	/// let prediction: f64 = db.run("ml::house_price").version("1.0.0").args(1000).await?;
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn run<R>(&self, function: impl Into<String>) -> Run<C, R> {
+		Run {
+			client: Cow::Borrowed(self),
+			function: function.into(),
+			version: None,
+			args: Ok(Value::None),
+			response_type: PhantomData,
+		}
+	}
+
+	/// Runs several RPC calls in a single request, optionally within a single transaction
+	///
+	/// The result of each call is returned in order. Without a transaction, a call which
+	/// fails does not stop the others from running.
+	///
+	/// # Support
+	///
+	/// The embedded database and the HTTP protocol only support the methods which read or
+	/// write data, and `run`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use serde_json::json;
+	/// use surrealdb::sql::Thing;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Create two records and relate them, all or nothing
+	/// let tobie = Thing::from(("person", "tobie"));
+	/// let jaime = Thing::from(("person", "jaime"));
+	/// let results = db
+	///     .batch()
+	///     .call("create", (&tobie, json!({ "name": "Tobie" })))
+	///     .call("create", (&jaime, json!({ "name": "Jaime" })))
+	///     .call("relate", (&tobie, "knows", &jaime))
+	///     .transaction()
+	///     .await?;
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn batch(&self) -> Batch<C> {
+		Batch {
+			client: Cow::Borrowed(self),
+			calls: Ok(Vec::new()),
+			transaction: false,
+		}
+	}
+
+	/// Streams the changes committed to the changefeed of the database, or of a single table
+	///
+	/// The changes are sent as they are committed, starting from the versionstamp given to
+	/// `since`. If the connection to the server drops, the stream resumes right after the
+	/// last changes it received once the client has reconnected.
+	///
+	/// # Support
+	///
+	/// Currently only supported by the WebSocket protocol.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # use futures::StreamExt;
+	/// #
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("ws://localhost:8000").await?;
+	/// #
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Stream all of the changes made to a table
+	/// let mut stream = db.changes().table("person").since(0).await?;
+	///
+	/// while let Some(change) = stream.next().await {
+	///     // Process the changes, keeping track of `change.versionstamp`
+	/// }
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn changes(&self) -> Changes<C> {
+		Changes {
+			client: Cow::Borrowed(self),
+			table: None,
+			since: 0,
+		}
+	}
+
+	/// Pulls the changes made to the records of the database since a versionstamp
+	///
+	/// The changes are read from the changefeeds of the tables, so only tables with a
+	/// changefeed are replicated. Keep track of `versionstamp` in the returned changes,
+	/// which is where the next pull starts from.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// # let local = surrealdb::engine::any::connect("mem://").await?;
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// local.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Pull the changes made on the server since the last sync
+	/// let changes = db.pull(0).await?;
+	///
+	/// // Apply them to a local replica, without pushing them back later
+	/// local.push(changes.changes).replicate().await?;
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn pull(&self, since: u64) -> Pull<C> {
+		Pull {
+			client: Cow::Borrowed(self),
+			since,
+			limit: None,
+		}
+	}
+
+	/// Pushes a batch of changes made to the records of a replica of the database
+	///
+	/// A change conflicts when the record was changed after the versionstamp given to
+	/// `since`. By default the change which was written last is kept, and a `DEFINE FUNCTION`
+	/// can resolve the conflict instead. Changes without a time are taken to be written when
+	/// they are pushed. The records with conflicting changes are returned.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// # let local = surrealdb::engine::any::connect("mem://").await?;
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	/// local.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Push the changes made while offline, resolving conflicts with a function
+	/// let changes = local.pull(0).await?;
+	/// let conflicts = db.push(changes).since(0).resolve("fn::merge").await?;
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn push(&self, changes: impl Into<Vec<Change>>) -> Push<C> {
+		Push {
+			client: Cow::Borrowed(self),
+			push: sync::Push {
+				changes: changes.into(),
+				..Default::default()
+			},
+		}
+	}
+
 	/// Returns the version of the server
 	///
 	/// # Examples
@@ -0,0 +1,63 @@
+use crate::api::conn::Method;
+use crate::api::conn::Param;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::kvs::sync::Changes;
+use crate::method::OnceLockExt;
+use crate::sql::Value;
+use crate::Surreal;
+use std::borrow::Cow;
+use std::future::Future;
+use std::future::IntoFuture;
+use std::pin::Pin;
+
+/// A pull future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Pull<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) since: u64,
+	pub(super) limit: Option<u32>,
+}
+
+impl<'r, C> Pull<'r, C>
+where
+	C: Connection,
+{
+	/// Pulls at most this number of changes to the tables of the database
+	pub fn limit(mut self, limit: u32) -> Self {
+		self.limit = Some(limit);
+		self
+	}
+
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Pull<'static, C> {
+		Pull {
+			client: Cow::Owned(self.client.into_owned()),
+			since: self.since,
+			limit: self.limit,
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Pull<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Changes>;
+	type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + Sync + 'r>>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			let limit = match self.limit {
+				Some(limit) => Value::from(limit),
+				None => Value::None,
+			};
+			let mut conn = Client::new(Method::Pull);
+			let value =
+				conn.execute_value(router, Param::new(vec![self.since.into(), limit])).await?;
+			Ok(Changes::try_from(value)?)
+		})
+	}
+}
@@ -0,0 +1,91 @@
+use crate::api::conn::Method;
+use crate::api::conn::Param;
+use crate::api::err::Error;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::kvs::sync;
+use crate::kvs::sync::Resolution;
+use crate::method::OnceLockExt;
+use crate::sql::Thing;
+use crate::sql::Value;
+use crate::Surreal;
+use std::borrow::Cow;
+use std::future::Future;
+use std::future::IntoFuture;
+use std::pin::Pin;
+
+/// A push future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Push<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) push: sync::Push,
+}
+
+impl<'r, C> Push<'r, C>
+where
+	C: Connection,
+{
+	/// Detects the records which were changed after the versionstamp which was last pulled
+	pub fn since(mut self, versionstamp: u64) -> Self {
+		self.push.since = Some(versionstamp);
+		self
+	}
+
+	/// Resolves conflicting changes with a `DEFINE FUNCTION`, instead of keeping the last written change
+	///
+	/// The function is called with the current and the pushed record, and the record is
+	/// replaced with its result, or deleted when it returns `NONE`.
+	pub fn resolve(mut self, function: impl Into<String>) -> Self {
+		let function = function.into();
+		let name = function.trim_start_matches("fn::").to_owned();
+		self.push.resolution = Resolution::Function(name);
+		self
+	}
+
+	/// Applies changes pulled from another database, without recording them in the changefeed
+	///
+	/// This is used to apply the changes pulled from the server to a local replica, so
+	/// that they are not pushed back to the server. Only owners can replicate changes.
+	pub fn replicate(mut self) -> Self {
+		self.push.replicate = true;
+		self
+	}
+
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Push<'static, C> {
+		Push {
+			client: Cow::Owned(self.client.into_owned()),
+			push: self.push,
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Push<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Vec<Thing>>;
+	type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + Sync + 'r>>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			let mut conn = Client::new(Method::Push);
+			let value = conn.execute_value(router, Param::new(vec![self.push.into()])).await?;
+			match value {
+				Value::Array(conflicts) => conflicts
+					.into_iter()
+					.map(|v| match v {
+						Value::Thing(v) => Ok(v),
+						v => {
+							Err(Error::InternalError(format!("expected a record id, found {v}"))
+								.into())
+						}
+					})
+					.collect(),
+				v => Err(Error::InternalError(format!("expected the conflicts, found {v}")).into()),
+			}
+		})
+	}
+}
@@ -0,0 +1,49 @@
+use crate::api::conn::Method;
+use crate::api::conn::Param;
+use crate::api::method::OnceLockExt;
+use crate::api::opt::auth::RefreshToken;
+use crate::api::opt::auth::Tokens;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::Surreal;
+use std::borrow::Cow;
+use std::future::Future;
+use std::future::IntoFuture;
+use std::pin::Pin;
+
+/// A session refresh future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Refresh<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) token: RefreshToken,
+}
+
+impl<C> Refresh<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Refresh<'static, C> {
+		Refresh {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Refresh<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Tokens>;
+	type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + Sync + 'r>>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			let mut conn = Client::new(Method::Refresh);
+			conn.execute(router, Param::new(vec![self.token.0.into()])).await
+		})
+	}
+}
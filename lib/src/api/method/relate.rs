@@ -0,0 +1,69 @@
+use crate::api::conn::Method;
+use crate::api::conn::Param;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::sql::to_value;
+use crate::sql::Table;
+use crate::sql::Thing;
+use crate::sql::Value;
+use crate::Surreal;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::future::Future;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+/// A relate future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Relate<'r, C: Connection, R> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) from: Thing,
+	pub(super) kind: Table,
+	pub(super) with: Thing,
+	pub(super) content: Result<Value>,
+	pub(super) response_type: PhantomData<R>,
+}
+
+impl<'r, C, R> Relate<'r, C, R>
+where
+	C: Connection,
+{
+	/// Sets the contents of the edge
+	pub fn content<D>(mut self, data: D) -> Self
+	where
+		D: Serialize,
+	{
+		self.content = to_value(data).map_err(Into::into);
+		self
+	}
+
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Relate<'static, C, R> {
+		Relate {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client, R> IntoFuture for Relate<'r, Client, R>
+where
+	Client: Connection,
+	R: DeserializeOwned,
+{
+	type Output = Result<R>;
+	type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + Sync + 'r>>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			let mut conn = Client::new(Method::Relate);
+			let params = vec![self.from.into(), self.kind.into(), self.with.into(), self.content?];
+			conn.execute(router, Param::new(params)).await
+		})
+	}
+}
@@ -0,0 +1,80 @@
+use crate::api::conn::Method;
+use crate::api::conn::Param;
+use crate::api::method::OnceLockExt;
+use crate::api::opt::auth::Subject;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::sql::to_value;
+use crate::sql::Value;
+use crate::Surreal;
+use std::borrow::Cow;
+use std::future::Future;
+use std::future::IntoFuture;
+use std::pin::Pin;
+
+/// A session revocation future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Revoke<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) subject: Result<Option<Value>>,
+}
+
+impl<C> Revoke<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Revoke<'static, C> {
+		Revoke {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+
+	/// Revokes the sessions of another user, or of every user of a scope or level
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::opt::auth::Subject;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// // Revoke the sessions of every user of a scope
+	/// db.revoke()
+	///     .subject(Subject {
+	///         namespace: Some("namespace"),
+	///         database: Some("database"),
+	///         scope: Some("user"),
+	///         ..Default::default()
+	///     })
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn subject(self, subject: Subject<'_>) -> Self {
+		Self {
+			subject: to_value(subject).map(Some).map_err(Into::into),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Revoke<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + Sync + 'r>>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			let mut conn = Client::new(Method::Revoke);
+			let params = self.subject?.into_iter().collect();
+			conn.execute_unit(router, Param::new(params)).await
+		})
+	}
+}
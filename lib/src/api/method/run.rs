@@ -0,0 +1,84 @@
+use crate::api::conn::Method;
+use crate::api::conn::Param;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::sql::to_value;
+use crate::sql::Array;
+use crate::sql::Value;
+use crate::Surreal;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::future::Future;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+/// A run future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Run<'r, C: Connection, R> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) function: String,
+	pub(super) version: Option<String>,
+	pub(super) args: Result<Value>,
+	pub(super) response_type: PhantomData<R>,
+}
+
+impl<'r, C, R> Run<'r, C, R>
+where
+	C: Connection,
+{
+	/// Sets the arguments of the function
+	///
+	/// A tuple or a `Vec` is passed as several arguments, and any other value as a single one.
+	pub fn args<A>(mut self, args: A) -> Self
+	where
+		A: Serialize,
+	{
+		self.args = to_value(args).map_err(Into::into);
+		self
+	}
+
+	/// Sets the version of a machine learning model
+	pub fn version(mut self, version: impl Into<String>) -> Self {
+		self.version = Some(version.into());
+		self
+	}
+
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Run<'static, C, R> {
+		Run {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client, R> IntoFuture for Run<'r, Client, R>
+where
+	Client: Connection,
+	R: DeserializeOwned,
+{
+	type Output = Result<R>;
+	type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + Sync + 'r>>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			let args = match self.args? {
+				Value::None | Value::Null => Value::Array(Array::new()),
+				Value::Array(args) => Value::Array(args),
+				arg => Value::Array(Array(vec![arg])),
+			};
+			let version = match self.version {
+				Some(version) => version.into(),
+				None => Value::None,
+			};
+			let mut conn = Client::new(Method::Run);
+			let params = vec![self.function.into(), version, args];
+			conn.execute(router, Param::new(params)).await
+		})
+	}
+}
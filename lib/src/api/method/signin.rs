@@ -1,5 +1,6 @@
 use crate::api::conn::Method;
 use crate::api::conn::Param;
+use crate::api::opt::auth::Tokens;
 use crate::api::Connection;
 use crate::api::Result;
 use crate::method::OnceLockExt;
@@ -21,7 +22,7 @@ pub struct Signin<'r, C: Connection, R> {
 	pub(super) response_type: PhantomData<R>,
 }
 
-impl<C, R> Signin<'_, C, R>
+impl<'r, C, R> Signin<'r, C, R>
 where
 	C: Connection,
 {
@@ -32,6 +33,42 @@ where
 			..self
 		}
 	}
+
+	/// Also requests a refresh token, with which to obtain a new access token once it expires
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::opt::auth::Root;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let tokens = db
+	///     .signin(Root {
+	///         username: "root",
+	///         password: "root",
+	///     })
+	///     .with_refresh()
+	///     .await?;
+	///
+	/// // Later, once the access token has expired
+	/// let tokens = db.refresh(tokens.refresh).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_refresh(self) -> Signin<'r, C, Tokens> {
+		Signin {
+			client: self.client,
+			credentials: self.credentials.map(|mut v| {
+				if let Value::Object(v) = &mut v {
+					v.insert("refresh".to_owned(), Value::Bool(true));
+				}
+				v
+			}),
+			response_type: PhantomData,
+		}
+	}
 }
 
 impl<'r, Client, R> IntoFuture for Signin<'r, Client, R>
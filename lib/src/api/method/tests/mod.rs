@@ -6,16 +6,20 @@ mod server;
 mod types;
 
 use crate::api::method::tests::types::AuthParams;
+use crate::api::method::ChangeStream;
 use crate::api::opt::auth::Database;
 use crate::api::opt::auth::Jwt;
 use crate::api::opt::auth::Namespace;
 use crate::api::opt::auth::Root;
 use crate::api::opt::auth::Scope;
+use crate::api::opt::auth::Subject;
+use crate::api::opt::auth::Tokens;
 use crate::api::opt::PatchOp;
 use crate::api::Response as QueryResponse;
 use crate::api::Surreal;
 use crate::sql::statements::BeginStatement;
 use crate::sql::statements::CommitStatement;
+use crate::sql::Thing;
 use once_cell::sync::Lazy;
 use protocol::Client;
 use protocol::Test;
@@ -89,6 +93,22 @@ async fn api() {
 	// authenticate
 	let _: () = DB.authenticate(Jwt(String::new())).await.unwrap();
 
+	// refresh
+	let _: Tokens = DB.refresh("refresh").await.unwrap();
+
+	// revoke
+	let _: () = DB.revoke().await.unwrap();
+	let _: () = DB
+		.revoke()
+		.subject(Subject {
+			namespace: Some("test-ns"),
+			database: Some("test-db"),
+			scope: Some("scope"),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+
 	// query
 	let _: QueryResponse = DB.query("SELECT * FROM user").await.unwrap();
 	let _: QueryResponse =
@@ -154,12 +174,35 @@ async fn api() {
 	let _: Option<User> = DB.delete((USER, "john")).await.unwrap();
 	let _: Vec<User> = DB.delete(USER).range("jane".."john").await.unwrap();
 
+	// relate
+	let _: Option<User> = DB.relate((USER, "john"), "knows", (USER, "jane")).await.unwrap();
+	let _: Option<User> =
+		DB.relate((USER, "john"), "knows", (USER, "jane")).content(User::default()).await.unwrap();
+
+	// run
+	let _: Option<String> = DB.run("fn::greet").await.unwrap();
+	let _: Option<String> = DB.run("fn::greet").args(("john", 30)).await.unwrap();
+	let _: Option<f64> = DB.run("ml::price").version("1.0.0").args(1000).await.unwrap();
+
+	// batch
+	let results = DB
+		.batch()
+		.call("create", (Thing::from((USER, "john")), User::default()))
+		.call("select", Thing::from((USER, "john")))
+		.transaction()
+		.await
+		.unwrap();
+	assert_eq!(results.len(), 2);
+
 	// export
 	let _: () = DB.export("backup.sql").await.unwrap();
 
 	// import
 	let _: () = DB.import("backup.sql").await.unwrap();
 
+	// changes
+	let _: ChangeStream = DB.changes().table(USER).since(0).await.unwrap();
+
 	// version
 	let _: Version = DB.version().await.unwrap();
 }
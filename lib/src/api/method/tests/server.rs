@@ -36,6 +36,18 @@ pub(super) fn mock(route_rx: Receiver<Option<Route>>) {
 					)),
 					_ => unreachable!(),
 				},
+				Method::Changefeed => match &params[..] {
+					[_, _, id] => Ok(DbResponse::Other(id.clone())),
+					_ => unreachable!(),
+				},
+				Method::Pull => match &params[..] {
+					[_, _] => Ok(DbResponse::Other(crate::kvs::sync::Changes::default().into())),
+					_ => unreachable!(),
+				},
+				Method::Push => match &params[..] {
+					[_] => Ok(DbResponse::Other(Value::Array(Array::new()))),
+					_ => unreachable!(),
+				},
 				Method::Version => match &params[..] {
 					[] => Ok(DbResponse::Other("1.0.0".into())),
 					_ => unreachable!(),
@@ -44,6 +56,16 @@ pub(super) fn mock(route_rx: Receiver<Option<Route>>) {
 					[_] | [_, _] => Ok(DbResponse::Other(Value::None)),
 					_ => unreachable!(),
 				},
+				Method::Revoke => match &params[..] {
+					[] | [_] => Ok(DbResponse::Other(Value::None)),
+					_ => unreachable!(),
+				},
+				Method::Refresh => match &params[..] {
+					[_] => Ok(DbResponse::Other(
+						crate::sql::json(r#"{ "token": "jwt", "refresh": "refresh" }"#).unwrap(),
+					)),
+					_ => unreachable!(),
+				},
 				Method::Signup | Method::Signin => match &mut params[..] {
 					[_] => Ok(DbResponse::Other("jwt".to_owned().into())),
 					_ => unreachable!(),
@@ -78,6 +100,28 @@ pub(super) fn mock(route_rx: Receiver<Option<Route>>) {
 					}
 					_ => unreachable!(),
 				},
+				Method::Relate => match &params[..] {
+					[Value::Thing(..), Value::Table(..), Value::Thing(..), _] => {
+						Ok(DbResponse::Other(to_value(User::default()).unwrap()))
+					}
+					_ => unreachable!(),
+				},
+				Method::Run => match &params[..] {
+					[Value::Strand(..), _, Value::Array(..)] => Ok(DbResponse::Other(Value::None)),
+					_ => unreachable!(),
+				},
+				Method::Batch => match &params[..] {
+					[Value::Array(calls), _] => Ok(DbResponse::Other(
+						calls
+							.iter()
+							.map(|_| {
+								Value::from(crate::map! { "result".to_owned() => Value::None })
+							})
+							.collect::<Vec<_>>()
+							.into(),
+					)),
+					_ => unreachable!(),
+				},
 				Method::Export | Method::Import => match param.file {
 					Some(_) => Ok(DbResponse::Other(Value::None)),
 					_ => unreachable!(),
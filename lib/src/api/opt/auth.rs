@@ -140,6 +140,81 @@ impl fmt::Debug for Jwt {
 	}
 }
 
+/// A refresh token, which can be exchanged once for a new access token
+///
+/// Like [`Jwt`], the debug implementation of this struct does not print the token.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RefreshToken(pub(crate) String);
+
+impl RefreshToken {
+	/// Returns the underlying token string.
+	///
+	/// ⚠️: It is important to note that the token should be handled securely and protected from unauthorized access.
+	pub fn as_insecure_token(&self) -> &str {
+		&self.0
+	}
+
+	/// Returns the underlying token string.
+	///
+	/// ⚠️: It is important to note that the token should be handled securely and protected from unauthorized access.
+	pub fn into_insecure_token(self) -> String {
+		self.0
+	}
+}
+
+impl From<String> for RefreshToken {
+	fn from(token: String) -> Self {
+		RefreshToken(token)
+	}
+}
+
+impl<'a> From<&'a String> for RefreshToken {
+	fn from(token: &'a String) -> Self {
+		RefreshToken(token.to_owned())
+	}
+}
+
+impl<'a> From<&'a str> for RefreshToken {
+	fn from(token: &'a str) -> Self {
+		RefreshToken(token.to_owned())
+	}
+}
+
+impl fmt::Debug for RefreshToken {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "RefreshToken(REDACTED)")
+	}
+}
+
+/// The tokens returned when signing in with a refresh token, or when refreshing a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tokens {
+	/// The access token
+	pub token: Jwt,
+	/// The refresh token with which to obtain the next access token
+	pub refresh: RefreshToken,
+}
+
+/// The user, or the set of users, whose sessions to revoke
+///
+/// Without a user, the sessions of every user of the scope, database, namespace
+/// or root level are revoked.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Subject<'a> {
+	/// The namespace of the users
+	#[serde(rename = "ns", skip_serializing_if = "Option::is_none")]
+	pub namespace: Option<&'a str>,
+	/// The database of the users
+	#[serde(rename = "db", skip_serializing_if = "Option::is_none")]
+	pub database: Option<&'a str>,
+	/// The scope of the users
+	#[serde(rename = "sc", skip_serializing_if = "Option::is_none")]
+	pub scope: Option<&'a str>,
+	/// The name of the system user, or the record id of the scope user
+	#[serde(rename = "id", skip_serializing_if = "Option::is_none")]
+	pub user: Option<&'a str>,
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -172,6 +172,8 @@ pub enum Action {
 	Create,
 	Update,
 	Delete,
+	/// The live query was ended by the server
+	Killed,
 }
 
 impl From<dbs::Action> for Action {
@@ -180,6 +182,7 @@ impl From<dbs::Action> for Action {
 			dbs::Action::Create => Self::Create,
 			dbs::Action::Update => Self::Update,
 			dbs::Action::Delete => Self::Delete,
+			dbs::Action::Killed => Self::Killed,
 		}
 	}
 }
@@ -188,13 +191,21 @@ impl From<dbs::Action> for Action {
 ///
 /// Live queries return a stream of notifications. The notification contains an `action` that triggered the change in the database record and `data` itself.
 /// For deletions the data is the record before it was deleted. For everything else, it's the newly created record or updated record depending on whether
-/// the action is create or update.
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+/// the action is create or update. When the live query was created `WITH BEFORE`, `before` contains the record before it was
+/// updated or deleted.
+///
+/// When the server ends a live query, a final notification is sent with a `Killed` action. Untyped streams receive the reason
+/// as `data`, while typed streams return an [`Api::LiveQueryKilled`](crate::error::Api::LiveQueryKilled) error.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[non_exhaustive]
 pub struct Notification<R> {
 	pub query_id: Uuid,
 	pub action: Action,
+	/// The id of the record which was changed
+	pub record: Option<sql::Thing>,
 	pub data: R,
+	/// The record before it was changed, if requested
+	pub before: Option<R>,
 }
 
 /// An error originating from the SurrealDB client library
@@ -97,6 +97,8 @@ mod api_integration {
 
 		include!("api/mod.rs");
 		include!("api/live.rs");
+		include!("api/unique.rs");
+		include!("api/changes.rs");
 	}
 
 	#[cfg(feature = "protocol-http")]
@@ -221,6 +223,7 @@ mod api_integration {
 
 		include!("api/mod.rs");
 		include!("api/live.rs");
+		include!("api/unique.rs");
 		include!("api/backup.rs");
 	}
 
@@ -256,6 +259,7 @@ mod api_integration {
 
 		include!("api/mod.rs");
 		include!("api/live.rs");
+		include!("api/unique.rs");
 		include!("api/backup.rs");
 	}
 
@@ -291,6 +295,7 @@ mod api_integration {
 
 		include!("api/mod.rs");
 		include!("api/live.rs");
+		include!("api/unique.rs");
 		include!("api/backup.rs");
 	}
 
@@ -326,6 +331,7 @@ mod api_integration {
 
 		include!("api/mod.rs");
 		include!("api/live.rs");
+		include!("api/unique.rs");
 		include!("api/backup.rs");
 	}
 
@@ -359,6 +365,7 @@ mod api_integration {
 
 		include!("api/mod.rs");
 		include!("api/live.rs");
+		include!("api/unique.rs");
 		include!("api/backup.rs");
 	}
 
@@ -389,6 +396,7 @@ mod api_integration {
 
 		include!("api/mod.rs");
 		include!("api/live.rs");
+		include!("api/unique.rs");
 		include!("api/backup.rs");
 	}
 
@@ -0,0 +1,41 @@
+// Tests for streaming changefeeds
+// Supported by the WS protocol
+
+#[test_log::test(tokio::test)]
+async fn changes_stream_table() {
+	let (permit, db) = new_db().await;
+
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+
+	let table = Ulid::new().to_string();
+	db.query(format!("DEFINE TABLE `{table}` CHANGEFEED 1h")).await.unwrap().check().unwrap();
+
+	// Start streaming the changes
+	let mut changes = db.changes().table(&table).since(0).await.unwrap();
+
+	// The table definition is recorded first
+	let set = changes.next().await.unwrap();
+	assert!(set.changes.to_string().contains("define_table"), "{set:?}");
+
+	// Create a record
+	let created: Vec<RecordId> = db.create(&table).await.unwrap();
+	// Pull the changes
+	let set = changes.next().await.unwrap();
+	let expected = format!("[{{ update: {{ id: {} }} }}]", created[0].id);
+	assert_eq!(set.changes, surrealdb::sql::value(&expected).unwrap());
+	let versionstamp = set.versionstamp;
+
+	// Create another record
+	let created: Vec<RecordId> = db.create(&table).await.unwrap();
+	// The changes are streamed in order
+	let set = changes.next().await.unwrap();
+	assert!(set.versionstamp > versionstamp);
+	let expected = format!("[{{ update: {{ id: {} }} }}]", created[0].id);
+	assert_eq!(set.changes, surrealdb::sql::value(&expected).unwrap());
+
+	// Streaming again from a later versionstamp skips earlier changes
+	let mut later = db.changes().table(&table).since(versionstamp + 1).await.unwrap();
+	assert_eq!(later.next().await.unwrap(), set);
+
+	drop(permit);
+}
@@ -307,6 +307,35 @@ async fn authenticate() {
 	db.authenticate(token).await.unwrap();
 }
 
+#[test_log::test(tokio::test)]
+async fn refresh_and_revoke() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	let user = Ulid::new().to_string();
+	let pass = "password123";
+	let sql = format!("DEFINE USER `{user}` ON NAMESPACE PASSWORD '{pass}'");
+	let response = db.query(sql).await.unwrap();
+	drop(permit);
+	response.check().unwrap();
+	let tokens = db
+		.signin(Namespace {
+			namespace: NS,
+			username: &user,
+			password: pass,
+		})
+		.with_refresh()
+		.await
+		.unwrap();
+	// Each refresh token can only be used once
+	let refreshed = db.refresh(tokens.refresh.clone()).await.unwrap();
+	db.refresh(tokens.refresh).await.unwrap_err();
+	db.authenticate(refreshed.token.clone()).await.unwrap();
+	// Revoking the sessions of the user rejects all of the tokens issued so far
+	db.revoke().await.unwrap();
+	db.authenticate(refreshed.token).await.unwrap_err();
+	db.refresh(refreshed.refresh).await.unwrap_err();
+}
+
 #[test_log::test(tokio::test)]
 async fn query() {
 	let (permit, db) = new_db().await;
@@ -873,6 +902,106 @@ async fn delete_record_range() {
 	);
 }
 
+#[test_log::test(tokio::test)]
+async fn relate_records() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let _: Option<RecordId> = db.create(("user", "john")).await.unwrap();
+	let _: Option<RecordId> = db.create(("user", "jane")).await.unwrap();
+	let edge: Option<RecordName> = db
+		.relate(("user", "john"), "knows", ("user", "jane"))
+		.content(json!({ "name": "friend" }))
+		.await
+		.unwrap();
+	assert_eq!(edge.unwrap().name, "friend");
+	let sql = "SELECT VALUE ->knows->user FROM ONLY user:john";
+	let users: Vec<Thing> = db.query(sql).await.unwrap().take(0).unwrap();
+	assert_eq!(users, vec![thing("user:jane").unwrap()]);
+}
+
+#[test_log::test(tokio::test)]
+async fn run_function() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let sql = "DEFINE FUNCTION fn::greet($name: string, $end: string) { RETURN 'Hello ' + $name + $end; }";
+	db.query(sql).await.unwrap().check().unwrap();
+	let greeting: String = db.run("fn::greet").args(("John", "!")).await.unwrap();
+	assert_eq!(greeting, "Hello John!");
+	let name: String = db.run("string::uppercase").args("john").await.unwrap();
+	assert_eq!(name, "JOHN");
+	// The arguments are checked against the function signature
+	db.run::<String>("fn::greet").args(("John", 1)).await.unwrap_err();
+	// Only machine learning models have a version
+	db.run::<String>("fn::greet").version("1.0.0").await.unwrap_err();
+}
+
+#[test_log::test(tokio::test)]
+async fn batch_calls() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let john = thing("user:john").unwrap();
+	let jane = thing("user:jane").unwrap();
+	// Calls which fail do not stop the others
+	let results = db
+		.batch()
+		.call("create", (&john, json!({ "name": "John" })))
+		.call("create", &john)
+		.call("run", ("string::uppercase", Value::None, ["john"]))
+		.await
+		.unwrap();
+	assert_eq!(results.len(), 3);
+	assert!(results[0].is_ok());
+	assert!(results[1].is_err());
+	assert_eq!(results[2].as_ref().unwrap(), &Value::from("JOHN"));
+	// Queries, and the other data methods, can be called outside of a transaction
+	let patch = json!([{ "op": "add", "path": "/age", "value": 30 }]);
+	let results = db
+		.batch()
+		.call("patch", (&john, patch, true))
+		.call("insert", ("user", json!({ "name": "Jim" })))
+		.call("query", ("SELECT VALUE age FROM ONLY type::thing('user', $id)", json!({ "id": "john" })))
+		.await
+		.unwrap();
+	let diff = surrealdb::sql::value("[{ op: 'add', path: '/age', value: 30 }]").unwrap();
+	assert_eq!(results[0].as_ref().unwrap(), &diff);
+	assert!(results[1].is_ok());
+	let Value::Array(responses) = results[2].as_ref().unwrap() else {
+		panic!("expected the responses of the query, found {:?}", results[2]);
+	};
+	assert_eq!(responses.len(), 1);
+	assert_eq!(responses[0].pick(&["result".into()]), Value::from(30));
+	let results = db.batch().call("query", "RETURN 1").transaction().await;
+	assert!(results.is_err());
+	// A transaction is cancelled when a call fails
+	let results = db
+		.batch()
+		.call("create", (&jane, json!({ "name": "Jane" })))
+		.call("create", &john)
+		.transaction()
+		.await
+		.unwrap();
+	assert!(results.iter().all(Result::is_err));
+	let user: Option<RecordName> = db.select(("user", "jane")).await.unwrap();
+	assert!(user.is_none());
+	// The calls in a transaction are applied together
+	let results = db
+		.batch()
+		.call("create", (&jane, json!({ "name": "Jane" })))
+		.call("relate", (&john, "knows", &jane))
+		.call("select", &jane)
+		.transaction()
+		.await
+		.unwrap();
+	let results: Vec<Value> = results.into_iter().map(Result::unwrap).collect();
+	assert_eq!(results[2], results[0]);
+	let sql = "SELECT VALUE ->knows->user FROM ONLY user:john";
+	let users: Vec<Thing> = db.query(sql).await.unwrap().take(0).unwrap();
+	assert_eq!(users, vec![jane]);
+}
+
 #[test_log::test(tokio::test)]
 async fn changefeed() {
 	let (permit, db) = new_db().await;
@@ -1047,6 +1176,33 @@ async fn changefeed() {
 	);
 }
 
+#[test_log::test(tokio::test)]
+async fn sync_pull_and_push() {
+	use surrealdb::kvs::sync::Change;
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let sql = "
+		DEFINE TABLE user CHANGEFEED 1h;
+		CREATE user:amos SET name = 'Amos';
+	";
+	db.query(sql).await.unwrap().check().unwrap();
+	let changes = db.pull(0).await.unwrap();
+	assert_eq!(changes.changes.len(), 1);
+	// The record is changed on the server, while a replica is offline
+	db.query("UPDATE user:amos SET name = 'Amos Burton'").await.unwrap().check().unwrap();
+	let record = surrealdb::sql::value("{ id: user:amos, name: 'AMOS' }").unwrap();
+	let change = Change::Update(thing("user:amos").unwrap(), record, None);
+	// The replica pushes its change, which is the last write
+	let conflicts = db.push(vec![change]).since(changes.versionstamp).await.unwrap();
+	assert_eq!(conflicts, vec![thing("user:amos").unwrap()]);
+	let sql = "SELECT VALUE name FROM ONLY user:amos";
+	let name: Option<String> = db.query(sql).await.unwrap().take(0).unwrap();
+	assert_eq!(name.as_deref(), Some("AMOS"));
+	let changes = db.pull(changes.versionstamp).limit(10).await.unwrap();
+	assert_eq!(changes.changes.len(), 2);
+}
+
 #[test_log::test(tokio::test)]
 async fn version() {
 	let (permit, db) = new_db().await;
@@ -0,0 +1,33 @@
+// Tests for the details of unique index violations
+// Supported by the storage engines and the WS protocol
+
+#[test_log::test(tokio::test)]
+async fn unique_index_violation() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let sql = "DEFINE INDEX email ON user FIELDS email UNIQUE";
+	db.query(sql).await.unwrap().check().unwrap();
+	let _: Option<RecordId> =
+		db.create(("user", "john")).content(json!({ "email": "john@example.com" })).await.unwrap();
+	let res: surrealdb::Result<Option<RecordId>> =
+		db.create(("user", "jane")).content(json!({ "email": "john@example.com" })).await;
+	// The error details the record which holds the value
+	match res.unwrap_err() {
+		surrealdb::Error::Db(surrealdb::error::Db::IndexExists {
+			thing: record,
+			index,
+			value,
+		})
+		| surrealdb::Error::Api(surrealdb::error::Api::IndexExists {
+			thing: record,
+			index,
+			value,
+		}) => {
+			assert_eq!(record, thing("user:john").unwrap());
+			assert_eq!(index, "email");
+			assert_eq!(value, "'john@example.com'");
+		}
+		error => panic!("unexpected error: {error}"),
+	}
+}
@@ -0,0 +1,155 @@
+#![cfg(feature = "sql2")]
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::iam::verify::token;
+use surrealdb::sql::Value;
+
+#[tokio::test]
+async fn define_key_statement() -> Result<(), Error> {
+	let sql = "
+		DEFINE KEY deploy ON DATABASE ROLES EDITOR COMMENT 'ci';
+		DEFINE KEY other ON DATABASE ROLES missing;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	// The secret of the key is only returned when it is defined
+	let tmp = res.remove(0).result?;
+	let Value::Strand(key) = tmp else {
+		panic!("expected the secret of the key, got {tmp}");
+	};
+	assert!(key.starts_with("sk-"));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "IAM error: Invalid role 'missing'"
+	));
+	// Only the hash of the secret is stored
+	let sql = "INFO FOR DB; INFO FOR KEY deploy ON DATABASE";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?.pick(&["keys".into(), "deploy".into()]);
+	let Value::Strand(def) = tmp else {
+		panic!("expected the definition of the key, got {tmp}");
+	};
+	assert!(def.starts_with("DEFINE KEY deploy ON DATABASE HASH '"));
+	assert!(def.ends_with("' ROLES EDITOR COMMENT 'ci'"));
+	assert!(!def.contains(key.as_str()));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp.pick(&["expires".into()]), Value::None);
+	assert_eq!(tmp.pick(&["used".into()]), Value::None);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn authenticate_with_key() -> Result<(), Error> {
+	let sql = "
+		DEFINE KEY reader ON DATABASE;
+		DEFINE KEY expired ON DATABASE ROLES EDITOR EXPIRES d'2020-01-01T00:00:00Z';
+		DEFINE TABLE person;
+		CREATE person:tobie;
+	";
+	let dbs = new_ds().await?.with_auth_enabled(true);
+	let owner = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &owner, None).await?;
+	assert_eq!(res.len(), 4);
+	let reader = res.remove(0).result?.as_raw_string();
+	let expired = res.remove(0).result?.as_raw_string();
+	// A key grants its roles on the level on which it is defined
+	let mut ses = Session::default();
+	token(&dbs, &mut ses, &reader).await?;
+	assert_eq!(ses.ns.as_deref(), Some("test"));
+	assert_eq!(ses.db.as_deref(), Some("test"));
+	assert_eq!(ses.au.id(), "reader");
+	let sql = "SELECT * FROM person; CREATE person:jaime";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.remove(0).result?, Value::parse("[{ id: person:tobie }]"));
+	// A viewer can not create records
+	let _ = res.remove(0).result;
+	let sql = "SELECT * FROM person";
+	let tmp = dbs.execute(sql, &owner, None).await?.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[{ id: person:tobie }]"));
+	// The time at which the key was used is recorded
+	let sql = "INFO FOR KEY reader ON DATABASE";
+	let tmp = dbs.execute(sql, &owner, None).await?.remove(0).result?;
+	assert!(matches!(tmp.pick(&["used".into()]), Value::Datetime(_)));
+	// A key can not be used once it has expired
+	let mut ses = Session::default();
+	let res = token(&dbs, &mut ses, &expired).await;
+	assert!(matches!(res, Err(Error::InvalidAuth)));
+	// A key can not be guessed
+	let res = token(&dbs, &mut ses, "sk-0000000000000000000000000000000000000000").await;
+	assert!(matches!(res, Err(Error::InvalidAuth)));
+	// Redefining a key replaces its secret
+	let sql = "DEFINE KEY reader ON DATABASE";
+	let renewed = dbs.execute(sql, &owner, None).await?.remove(0).result?.as_raw_string();
+	let res = token(&dbs, &mut ses, &reader).await;
+	assert!(matches!(res, Err(Error::InvalidAuth)));
+	token(&dbs, &mut ses, &renewed).await?;
+	// Removing a key revokes it
+	let sql = "REMOVE KEY reader ON DATABASE";
+	dbs.execute(sql, &owner, None).await?.remove(0).result?;
+	let res = token(&dbs, &mut ses, &renewed).await;
+	assert!(matches!(res, Err(Error::InvalidAuth)));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn export_and_import_key() -> Result<(), Error> {
+	let sql = "DEFINE KEY deploy ON DATABASE ROLES EDITOR EXPIRES 30d";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let key = dbs.execute(sql, &ses, None).await?.remove(0).result?.as_raw_string();
+	// The hash of the secret is exported, along with the time at which the key expires
+	let sql = "INFO FOR DB";
+	let tmp = dbs.execute(sql, &ses, None).await?.remove(0).result?;
+	let def = tmp.pick(&["keys".into(), "deploy".into()]).as_raw_string();
+	assert!(def.contains(" EXPIRES d'"));
+	// The secret can be used once the key is imported into another datastore
+	let other = new_ds().await?;
+	let tmp = other.execute(&def, &ses, None).await?.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	let mut auth = Session::default();
+	token(&other, &mut auth, &key).await?;
+	assert_eq!(auth.au.id(), "deploy");
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_key_with_hash_of_another_key() -> Result<(), Error> {
+	let sql = "DEFINE KEY deploy ON ROOT ROLES OWNER";
+	let dbs = new_ds().await?.with_auth_enabled(true);
+	let root = Session::owner();
+	let key = dbs.execute(sql, &root, None).await?.remove(0).result?.as_raw_string();
+	let sql = "INFO FOR ROOT";
+	let tmp = dbs.execute(sql, &root, None).await?.remove(0).result?;
+	let def = tmp.pick(&["keys".into(), "deploy".into()]).as_raw_string();
+	let hash = def.split('\'').nth(1).unwrap();
+	// A key can not be imported with the hash of a key on another level
+	let sql = format!("DEFINE KEY deploy ON DATABASE HASH '{hash}'");
+	let owner = Session::owner().with_ns("test").with_db("test");
+	let tmp = dbs.execute(&sql, &owner, None).await?.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "The hash of the API key 'deploy' is already used by another key"
+	));
+	let sql = "REMOVE KEY deploy ON DATABASE";
+	let tmp = dbs.execute(sql, &owner, None).await?.remove(0).result;
+	assert!(tmp.is_err());
+	// The other key can still be used
+	let mut ses = Session::default();
+	token(&dbs, &mut ses, &key).await?;
+	assert_eq!(ses.au.id(), "deploy");
+	assert!(ses.au.is_root());
+	//
+	Ok(())
+}
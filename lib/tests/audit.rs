@@ -0,0 +1,112 @@
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::iam::signin::signin;
+use surrealdb::iam::verify::token;
+use surrealdb::kvs::audit::{self, AuditClass, AuditLog};
+use surrealdb::sql::Value;
+use temp_dir::TempDir;
+
+fn vars(user: &str, pass: &str) -> surrealdb::sql::Object {
+	let vars =
+		Value::parse(&format!("{{ ns: 'test', db: 'test', user: '{user}', pass: '{pass}' }}"));
+	let Value::Object(vars) = vars else {
+		unreachable!()
+	};
+	vars
+}
+
+fn records(path: &std::path::Path) -> Vec<serde_json::Value> {
+	let text = std::fs::read_to_string(path).unwrap();
+	text.lines().map(|l| serde_json::from_str(l).unwrap()).collect()
+}
+
+#[tokio::test]
+async fn audit_log_records_events() -> Result<(), Error> {
+	let dir = TempDir::new().unwrap();
+	let path = dir.child("audit.log");
+	let log = AuditLog::open(&path, AuditClass::ALL)?;
+	let dbs = new_ds().await?.with_auth_enabled(true).with_audit_log(log);
+	// Define some resources
+	let sql = "
+		DEFINE TABLE person;
+		DEFINE USER viewer ON DATABASE PASSWORD 'secret' ROLES VIEWER;
+		SELECT * FROM person;
+	";
+	let mut ses = Session::owner().with_ns("test").with_db("test");
+	ses.ip = Some("10.0.0.1".into());
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	for r in res.drain(..) {
+		r.result?;
+	}
+	// Sign in with the right and the wrong password
+	let mut ses = Session::default();
+	signin(&dbs, &mut ses, vars("viewer", "secret")).await?;
+	assert!(signin(&dbs, &mut Session::default(), vars("viewer", "wrong")).await.is_err());
+	// Authenticate with an invalid token
+	assert!(token(&dbs, &mut Session::default(), "invalid").await.is_err());
+	// Define a resource without the right role
+	let res = dbs.execute("DEFINE TABLE other", &ses, None).await?.remove(0).result;
+	assert!(res.is_err());
+	//
+	assert_eq!(audit::verify(&path)?, 6);
+	let recs = records(&path);
+	//
+	assert_eq!(recs[0]["class"], "ddl");
+	assert_eq!(recs[0]["outcome"], "success");
+	assert_eq!(recs[0]["detail"], "DEFINE TABLE person SCHEMALESS PERMISSIONS NONE");
+	assert_eq!(recs[0]["ip"], "10.0.0.1");
+	//
+	assert_eq!(recs[1]["class"], "user");
+	assert!(!recs[1]["detail"].as_str().unwrap().contains("$argon2"));
+	assert!(recs[1]["detail"].as_str().unwrap().contains("[REDACTED]"));
+	//
+	assert_eq!(recs[2]["class"], "signin");
+	assert_eq!(recs[2]["outcome"], "success");
+	assert_eq!(recs[2]["actor"], "viewer");
+	assert_eq!(recs[2]["level"], "/ns:test/db:test/");
+	//
+	assert_eq!(recs[3]["class"], "signin");
+	assert_eq!(recs[3]["outcome"], "failure");
+	assert_eq!(recs[3]["actor"], "viewer");
+	//
+	assert_eq!(recs[4]["class"], "token");
+	assert_eq!(recs[4]["outcome"], "failure");
+	//
+	assert_eq!(recs[5]["class"], "ddl");
+	assert_eq!(recs[5]["outcome"], "failure");
+	assert_eq!(recs[5]["actor"], "viewer");
+	assert_eq!(recs[5]["roles"], serde_json::json!(["Viewer"]));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn audit_log_records_configured_classes() -> Result<(), Error> {
+	let dir = TempDir::new().unwrap();
+	let path = dir.child("audit.log");
+	let log = AuditLog::open(&path, [AuditClass::User])?;
+	let dbs = new_ds().await?.with_audit_log(log);
+	let sql = "
+		DEFINE TABLE person;
+		DEFINE USER editor ON DATABASE PASSWORD 'secret' ROLES EDITOR;
+		REMOVE USER editor ON DATABASE;
+	";
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	for r in res.drain(..) {
+		r.result?;
+	}
+	//
+	assert_eq!(audit::verify(&path)?, 2);
+	let recs = records(&path);
+	assert!(recs[0]["detail"].as_str().unwrap().starts_with("DEFINE USER editor"));
+	assert_eq!(recs[1]["detail"], "REMOVE USER editor ON DATABASE");
+	//
+	Ok(())
+}
@@ -9,6 +9,7 @@ mod parse;
 
 use helpers::new_ds;
 use serial_test::serial;
+use surrealdb::dbs::Action;
 use surrealdb::err::Error;
 use surrealdb::kvs::LockType::Optimistic;
 use surrealdb::kvs::Transaction;
@@ -157,6 +158,42 @@ async fn bootstrap_removes_unreachable_table_live_queries() -> Result<(), Error>
 	Ok(())
 }
 
+#[tokio::test]
+#[serial]
+async fn bootstrap_notifies_killed_live_queries() -> Result<(), Error> {
+	// Create the datastore
+	let dbs = new_ds().await.unwrap().with_notifications();
+
+	// Introduce a live query on a node which has stopped sending heartbeats
+	let mut tx = dbs.transaction(Write, Optimistic).await.unwrap();
+	let dead_data = a_valid_notification(
+		&mut tx,
+		ValidNotificationState {
+			timestamp: Some(0),
+			node_id: None,
+			live_query_id: None,
+			notification_id: None,
+			namespace: "testns".to_string(),
+			database: "testdb".to_string(),
+			table: "testtb".to_string(),
+		},
+	)
+	.await
+	.unwrap();
+	tx.commit().await.unwrap();
+
+	// Bootstrap
+	dbs.bootstrap().await.unwrap();
+
+	// Verify that the client is told the live query has ended
+	let notifications = dbs.notifications().expect("expected notifications");
+	let notification = notifications.try_recv().unwrap();
+	assert_eq!(notification.id, dead_data.live_query_id.unwrap());
+	assert_eq!(notification.action, Action::Killed);
+	assert!(notifications.try_recv().is_err());
+	Ok(())
+}
+
 #[tokio::test]
 #[serial]
 async fn bootstrap_removes_unreachable_live_query_notifications() -> Result<(), Error> {
@@ -0,0 +1,92 @@
+#![cfg(feature = "sql2")]
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::iam::certificate::Certificate;
+use surrealdb::iam::verify::certificate;
+use surrealdb::sql::Value;
+
+fn cert(subject: &str, san: &[&str]) -> Certificate {
+	Certificate {
+		subject: subject.to_owned(),
+		san: san.iter().map(|v| v.to_string()).collect(),
+	}
+}
+
+#[tokio::test]
+async fn define_user_certificate() -> Result<(), Error> {
+	let sql = "
+		DEFINE USER api ON DATABASE ROLES EDITOR CERTIFICATE 'CN=api, O=mesh';
+		DEFINE USER web ON NAMESPACE CERTIFICATE 'spiffe://mesh/ns/prod/sa/web';
+		INFO FOR DB;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	res.remove(0).result?;
+	res.remove(0).result?;
+	let tmp = res.remove(0).result?.pick(&["users".into(), "api".into()]);
+	let Value::Strand(def) = tmp else {
+		panic!("expected the definition of the user, got {tmp}");
+	};
+	assert!(def.ends_with(" ROLES EDITOR CERTIFICATE 'CN=api, O=mesh'"));
+	// A certificate identifies a user by its subject
+	let mut ses = Session::default();
+	certificate(&dbs, &mut ses, &cert("CN=api,O=mesh", &[]), Some("test"), Some("test"), None)
+		.await?;
+	assert_eq!(ses.au.id(), "api");
+	assert!(ses.au.is_db());
+	// A certificate identifies a user by a subject alternative name
+	let mut ses = Session::default();
+	let web = cert("CN=web", &["web.prod", "spiffe://mesh/ns/prod/sa/web"]);
+	certificate(&dbs, &mut ses, &web, Some("test"), None, None).await?;
+	assert_eq!(ses.au.id(), "web");
+	assert!(ses.au.is_ns());
+	// A certificate only identifies users on the level on which they are defined
+	let mut ses = Session::default();
+	let res = certificate(&dbs, &mut ses, &web, Some("test"), Some("test"), None).await;
+	assert!(matches!(res, Err(Error::InvalidAuth)));
+	let res = certificate(&dbs, &mut ses, &web, None, None, None).await;
+	assert!(matches!(res, Err(Error::InvalidAuth)));
+	assert!(ses.au.is_anon());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_scope_certificate() -> Result<(), Error> {
+	let sql = "
+		DEFINE SCOPE mesh CERTIFICATE (SELECT * FROM service WHERE spiffe IN $san);
+		DEFINE SCOPE user SIGNIN (SELECT * FROM user);
+		CREATE service:api SET spiffe = 'spiffe://mesh/ns/prod/sa/api';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	res.remove(0).result?;
+	res.remove(0).result?;
+	res.remove(0).result?;
+	// The scope selects the record of the certificate
+	let mut ses = Session::default();
+	let api = cert("CN=api", &["spiffe://mesh/ns/prod/sa/api"]);
+	certificate(&dbs, &mut ses, &api, Some("test"), Some("test"), Some("mesh")).await?;
+	assert_eq!(ses.sc.as_deref(), Some("mesh"));
+	assert_eq!(ses.sd, Some(Value::parse("service:api")));
+	assert_eq!(ses.au.id(), "service:api");
+	// A certificate without a record can not sign in
+	let mut ses = Session::default();
+	let web = cert("CN=web", &["spiffe://mesh/ns/prod/sa/web"]);
+	let res = certificate(&dbs, &mut ses, &web, Some("test"), Some("test"), Some("mesh")).await;
+	assert!(matches!(res, Err(Error::NoRecordFound)));
+	// A scope without a CERTIFICATE clause does not allow client certificates
+	let res = certificate(&dbs, &mut ses, &api, Some("test"), Some("test"), Some("user")).await;
+	assert!(matches!(res, Err(Error::InvalidAuth)));
+	assert!(ses.au.is_anon());
+	//
+	Ok(())
+}
@@ -5,6 +5,8 @@ mod helpers;
 use helpers::new_ds;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
+#[cfg(feature = "sql2")]
+use surrealdb::sql::Part;
 use surrealdb::sql::Value;
 
 #[tokio::test]
@@ -514,3 +516,379 @@ async fn changefeed_with_ts() -> Result<(), Error> {
 	assert_eq!(array.len(), 0);
 	Ok(())
 }
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn table_change_feeds_include_original() -> Result<(), Error> {
+	let sql = "
+        DEFINE TABLE person CHANGEFEED 1h INCLUDE ORIGINAL;
+		CREATE person:test SET name = 'Tobie';
+		UPDATE person:test SET name = 'Jaime';
+		DELETE person:test;
+        SHOW CHANGES FOR TABLE person SINCE 0;
+		INFO FOR DB;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	dbs.tick_at(0).await?;
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	for _ in 0..4 {
+		let _ = res.remove(0).result?;
+	}
+	// SHOW CHANGES
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				versionstamp: 65536,
+				changes: [
+					{
+						define_table: {
+							name: 'person'
+						}
+					}
+				]
+			},
+			{
+				versionstamp: 131072,
+				changes: [
+					{
+						update: {
+							id: person:test,
+							name: 'Tobie'
+						},
+						original: NONE
+					}
+				]
+			},
+			{
+				versionstamp: 196608,
+				changes: [
+					{
+						update: {
+							id: person:test,
+							name: 'Jaime'
+						},
+						original: {
+							id: person:test,
+							name: 'Tobie'
+						}
+					}
+				]
+			},
+			{
+				versionstamp: 262144,
+				changes: [
+					{
+						delete: {
+							id: person:test
+						},
+						original: {
+							id: person:test,
+							name: 'Jaime'
+						}
+					}
+				]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// INFO FOR DB
+	let tmp = res.remove(0).result?;
+	let tables = tmp.pick(&[surrealdb::sql::Part::from("tables")]);
+	assert_eq!(
+		tables,
+		Value::parse("{ person: 'DEFINE TABLE person SCHEMALESS CHANGEFEED 1h INCLUDE ORIGINAL PERMISSIONS NONE' }")
+	);
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn table_change_feeds_consumer() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	dbs.tick_at(0).await?;
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h;
+		DEFINE CONSUMER reader ON TABLE person;
+		CREATE person:1;
+		CREATE person:2;
+		SHOW CHANGES FOR TABLE person CONSUMER reader LIMIT 2;
+		ACK CONSUMER reader ON person AT 131072;
+		SHOW CHANGES FOR TABLE person CONSUMER reader;
+		ACK CONSUMER reader ON person AT 65536;
+		SHOW CHANGES FOR TABLE person CONSUMER reader;
+		ACK CONSUMER writer ON person AT 65536;
+		SHOW CHANGES FOR TABLE person CONSUMER writer;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	dbs.tick_at(1).await?;
+	assert_eq!(res.len(), 11);
+	//
+	for _ in 0..4 {
+		let _ = res.remove(0).result?;
+	}
+	// Nothing has been acknowledged yet
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				versionstamp: 65536,
+				changes: [{ define_table: { name: 'person' } }]
+			},
+			{
+				versionstamp: 131072,
+				changes: [{ update: { id: person:1 } }]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	// Only the changes after the committed offset are read
+	let after = Value::parse(
+		"[
+			{
+				versionstamp: 196608,
+				changes: [{ update: { id: person:2 } }]
+			}
+		]",
+	);
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, after);
+	// The offset can not be moved backwards
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, after);
+	// Unknown consumers are rejected
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(e) if e.to_string() == "The consumer 'writer' does not exist"));
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(e) if e.to_string() == "The consumer 'writer' does not exist"));
+	// The changes not yet read by the consumer are kept after they expire
+	dbs.tick_at(3601).await?;
+	let sql = "SHOW CHANGES FOR TABLE person SINCE 0";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, after);
+	// Once the consumer is removed, they are garbage collected
+	let sql = "REMOVE CONSUMER reader ON person";
+	dbs.execute(sql, &ses, None).await?.remove(0).result?;
+	dbs.tick_at(3602).await?;
+	let sql = "SHOW CHANGES FOR TABLE person SINCE 0";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn table_change_feeds_limits() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	dbs.tick_at(0).await?;
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h LIMIT 2 CHANGES;
+		DEFINE TABLE thing CHANGEFEED 1h LIMIT 1 BYTES;
+		CREATE person:1;
+		CREATE person:2;
+		CREATE person:3;
+		CREATE thing:1;
+		INFO FOR DB;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	for _ in 0..6 {
+		let _ = res.remove(0).result?;
+	}
+	// The changes are retained until they are garbage collected
+	let tmp = res.remove(0).result?;
+	let changes = |v: &Value, tb: &str| {
+		v.pick(&[Part::from("changefeeds"), Part::from(tb), Part::from("changes")])
+	};
+	assert_eq!(changes(&tmp, "person"), Value::from(4));
+	assert_eq!(changes(&tmp, "thing"), Value::from(2));
+	dbs.tick_at(1).await?;
+	// Only the newest changes are kept
+	let sql = "
+		SHOW CHANGES FOR TABLE person SINCE 0;
+		SHOW CHANGES FOR TABLE thing SINCE 0;
+		INFO FOR DB;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				versionstamp: 262144,
+				changes: [
+					{
+						update: {
+							id: person:2
+						}
+					}
+				]
+			},
+			{
+				versionstamp: 327680,
+				changes: [
+					{
+						update: {
+							id: person:3
+						}
+					}
+				]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	let tmp = res.remove(0).result?;
+	assert_eq!(changes(&tmp, "person"), Value::from(2));
+	assert_eq!(changes(&tmp, "thing"), Value::from(0));
+	let bytes = tmp.pick(&[Part::from("changefeeds"), Part::from("thing"), Part::from("bytes")]);
+	assert_eq!(bytes, Value::from(0));
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn table_change_feeds_usage_of_large_changefeeds() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	dbs.tick_at(0).await?;
+	// Each statement, including the table definition, records a separate change set
+	let sql = "DEFINE TABLE person CHANGEFEED 1h;".to_owned() + &"CREATE person;".repeat(2500);
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	assert_eq!(res.len(), 2501);
+	for _ in 0..2501 {
+		let _ = res.remove(0).result?;
+	}
+	// The change sets are counted across several batches
+	let res = &mut dbs.execute("INFO FOR DB", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let changes =
+		tmp.pick(&[Part::from("changefeeds"), Part::from("person"), Part::from("changes")]);
+	assert_eq!(changes, Value::from(2501));
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn table_change_feeds_limits_consumer() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	dbs.tick_at(0).await?;
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h LIMIT 1 CHANGES;
+		DEFINE CONSUMER reader ON TABLE person;
+		CREATE person:1;
+		CREATE person:2;
+		CREATE person:3;
+		ACK CONSUMER reader ON person AT 131072;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	for _ in 0..6 {
+		let _ = res.remove(0).result?;
+	}
+	dbs.tick_at(1).await?;
+	// The changes not yet read by the consumer are kept
+	let sql = "SHOW CHANGES FOR TABLE person SINCE 0";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				versionstamp: 196608,
+				changes: [{ update: { id: person:2 } }]
+			},
+			{
+				versionstamp: 262144,
+				changes: [{ update: { id: person:3 } }]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// Once they are read, the limit is enforced
+	let sql = "ACK CONSUMER reader ON person AT 262144";
+	dbs.execute(sql, &ses, None).await?.remove(0).result?;
+	dbs.tick_at(2).await?;
+	let sql = "SHOW CHANGES FOR TABLE person SINCE 0";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				versionstamp: 262144,
+				changes: [{ update: { id: person:3 } }]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn database_change_feeds_defaults() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	dbs.tick_at(0).await?;
+	let sql = "
+		DEFINE DATABASE test CHANGEFEED 1h LIMIT 1 CHANGES INCLUDE ORIGINAL;
+		DEFINE TABLE person;
+		DEFINE TABLE thing CHANGEFEED 1h;
+		CREATE person:test SET name = 'Tobie';
+		UPDATE person:test SET name = 'Jaime';
+		CREATE thing:1;
+		CREATE thing:2;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	for _ in 0..7 {
+		let _ = res.remove(0).result?;
+	}
+	dbs.tick_at(1).await?;
+	let sql = "
+		SHOW CHANGES FOR TABLE person SINCE 0;
+		INFO FOR DB;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	// The table inherits the database changefeed
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				versionstamp: 196608,
+				changes: [
+					{
+						update: {
+							id: person:test,
+							name: 'Jaime'
+						},
+						original: {
+							id: person:test,
+							name: 'Tobie'
+						}
+					}
+				]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// The table which defines its own changefeed keeps all changes
+	let tmp = res.remove(0).result?;
+	let changes =
+		|tb: &str| tmp.pick(&[Part::from("changefeeds"), Part::from(tb), Part::from("changes")]);
+	assert_eq!(changes("person"), Value::from(1));
+	assert_eq!(changes("thing"), Value::from(3));
+	Ok(())
+}
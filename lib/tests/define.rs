@@ -1071,6 +1071,427 @@ async fn define_statement_index_multiple_unique_existing() -> Result<(), Error>
 	Ok(())
 }
 
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn define_statement_index_concurrently() -> Result<(), Error> {
+	let sql = "
+		CREATE user:1 SET email = 'info@surrealdb.com';
+		CREATE user:2 SET email = 'test@surrealdb.com';
+		DEFINE INDEX test ON user FIELDS email CONCURRENTLY;
+		CREATE user:3 SET email = 'test@surrealdb.com';
+		INFO FOR INDEX test ON user;
+		SELECT * FROM user WHERE email = 'test@surrealdb.com' EXPLAIN;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	for _ in 0..4 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ building: { status: 'building', count: 0 } }");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				detail: {
+					table: 'user',
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					reason: 'NO INDEX FOUND'
+				},
+				operation: 'Fallback'
+			}
+		]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	// Build the index in the background
+	dbs.tick().await?;
+	//
+	let sql = "
+		INFO FOR INDEX test ON user;
+		SELECT id FROM user WHERE email = 'test@surrealdb.com' EXPLAIN;
+		SELECT id FROM user WHERE email = 'test@surrealdb.com';
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ building: { status: 'ready', count: 3 } }");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'test',
+						operator: '=',
+						value: 'test@surrealdb.com'
+					},
+					table: 'user',
+				},
+				operation: 'Iterate Index'
+			}
+		]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: user:2 }, { id: user:3 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn define_statement_index_concurrently_over_several_ticks() -> Result<(), Error> {
+	let sql = "
+		CREATE |user:1..3000| SET email = 'test@surrealdb.com';
+		DEFINE INDEX test ON user FIELDS email CONCURRENTLY;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	// Only a limited number of batches are indexed on each tick
+	dbs.tick_at(1).await?;
+	let res = &mut dbs.execute("INFO FOR INDEX test ON user", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ building: { status: 'building', count: 2500 } }");
+	assert_eq!(tmp, val);
+	// The build continues on the next tick
+	dbs.tick_at(2).await?;
+	let res = &mut dbs.execute("INFO FOR INDEX test ON user", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ building: { status: 'ready', count: 3000 } }");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn define_statement_index_concurrently_unique_existing() -> Result<(), Error> {
+	let sql = "
+		CREATE user:1 SET email = 'info@surrealdb.com';
+		CREATE user:2 SET email = 'test@surrealdb.com';
+		CREATE user:3 SET email = 'test@surrealdb.com';
+		DEFINE INDEX test ON user FIELDS email UNIQUE CONCURRENTLY;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	for _ in 0..4 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	// Build the index in the background
+	dbs.tick().await?;
+	//
+	let sql = "
+		INFO FOR INDEX test ON user;
+		SELECT * FROM user WHERE email = 'test@surrealdb.com' EXPLAIN;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			building: {
+				status: 'failed',
+				count: 0,
+				error: 'Database index `test` already contains \\'test@surrealdb.com\\', with record `user:2`'
+			}
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				detail: {
+					table: 'user',
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					reason: 'NO INDEX FOUND'
+				},
+				operation: 'Fallback'
+			}
+		]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn define_statement_index_unique_deferred_swap() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX test ON user FIELDS email UNIQUE DEFERRED;
+		CREATE user:1 SET email = 'info@surrealdb.com';
+		CREATE user:2 SET email = 'test@surrealdb.com';
+		BEGIN;
+		UPDATE user:1 SET email = 'test@surrealdb.com';
+		UPDATE user:2 SET email = 'info@surrealdb.com';
+		COMMIT;
+		SELECT id FROM user WHERE email = 'test@surrealdb.com';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	for _ in 0..5 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: user:1 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn define_statement_index_unique_deferred_duplicate() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX test ON user FIELDS email UNIQUE DEFERRED;
+		CREATE user:1 SET email = 'info@surrealdb.com';
+		BEGIN;
+		CREATE user:2 SET email = 'info@surrealdb.com';
+		COMMIT;
+		SELECT id FROM user WHERE email = 'info@surrealdb.com';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "The query was not executed due to a failed transaction. Database index `test` already contains 'info@surrealdb.com', with record `user:1`"
+	));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: user:1 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn define_statement_index_unique_deferred_released() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX test ON user FIELDS email UNIQUE DEFERRED;
+		CREATE user:1 SET email = 'info@surrealdb.com';
+		BEGIN;
+		UPDATE user:1 SET email = 'test@surrealdb.com';
+		CREATE user:2 SET email = 'test@surrealdb.com';
+		UPDATE user:1 SET email = 'tobie@surrealdb.com';
+		COMMIT;
+		DELETE user:2;
+		CREATE user:3 SET email = 'test@surrealdb.com';
+		CREATE user:4 SET email = 'tobie@surrealdb.com';
+		SELECT id, email FROM user;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 9);
+	//
+	for _ in 0..7 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Database index `test` already contains 'tobie@surrealdb.com', with record `user:1`"
+	));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: user:1, email: 'tobie@surrealdb.com' },
+			{ id: user:3, email: 'test@surrealdb.com' }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn define_statement_index_partial() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX test ON user FIELDS email UNIQUE WHERE deleted = false;
+		CREATE user:1 SET email = 'info@surrealdb.com', deleted = true;
+		CREATE user:2 SET email = 'info@surrealdb.com', deleted = false;
+		CREATE user:3 SET email = 'info@surrealdb.com', deleted = false;
+		SELECT id FROM user WHERE deleted = false AND email = 'info@surrealdb.com' EXPLAIN;
+		SELECT id FROM user WHERE deleted = false AND email = 'info@surrealdb.com';
+		SELECT id FROM user WHERE email = 'info@surrealdb.com' EXPLAIN;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	for _ in 0..3 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Database index `test` already contains 'info@surrealdb.com', with record `user:2`"
+	));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'test',
+						operator: '=',
+						value: 'info@surrealdb.com'
+					},
+					table: 'user',
+				},
+				operation: 'Iterate Index'
+			}
+		]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: user:2 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				detail: {
+					table: 'user',
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					reason: 'NO INDEX FOUND'
+				},
+				operation: 'Fallback'
+			}
+		]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn define_statement_index_expression() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX test ON user FIELDS string::lowercase(email) UNIQUE;
+		CREATE user:1 SET email = 'Info@SurrealDB.com';
+		CREATE user:2 SET email = 'info@surrealdb.com';
+		SELECT id FROM user WHERE string::lowercase(email) = 'info@surrealdb.com' EXPLAIN;
+		SELECT id FROM user WHERE string::lowercase(email) = 'info@surrealdb.com';
+		INFO FOR TABLE user;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Database index `test` already contains 'info@surrealdb.com', with record `user:1`"
+	));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'test',
+						operator: '=',
+						value: 'info@surrealdb.com'
+					},
+					table: 'user',
+				},
+				operation: 'Iterate Index'
+			}
+		]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: user:1 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			events: {},
+			fields: {},
+			tables: {},
+			indexes: { test: 'DEFINE INDEX test ON user FIELDS string::lowercase(email) UNIQUE' },
+			lives: {},
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_index_single_unique_embedded_multiple() -> Result<(), Error> {
 	let sql = "
@@ -2118,3 +2539,70 @@ async fn define_statement_table_permissions() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn define_statement_analyzer_invalid_filters() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE ANALYZER tamil TOKENIZERS blank FILTERS stopwords(tamil);
+		DEFINE ANALYZER mapping TOKENIZERS blank FILTERS mapping({ a: 1 });
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "The analyzer 'tamil' is not valid: There is no stopword list for TAMIL"
+	));
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "The analyzer 'mapping' is not valid: The mapping of 'a' should be a non empty key with a string value"
+	));
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_table_permissions_kill_live_queries() -> Result<(), Error> {
+	use surrealdb::dbs::{Action, Notification};
+	use surrealdb::sql::Thing;
+	let dbs = new_ds().await?.with_notifications();
+	let owner = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("DEFINE TABLE person PERMISSIONS FULL", &owner, None).await?;
+	res.remove(0).result?;
+	// Start a live query as a scope user
+	let ses = Session::for_scope("test", "test", "test", Thing::from(("user", "test")).into())
+		.with_rt(true);
+	let res = &mut dbs.execute("LIVE SELECT * FROM person", &ses, None).await?;
+	let Value::Uuid(live_id) = res.remove(0).result? else {
+		panic!("expected uuid");
+	};
+	// The scope user is allowed to see this record
+	let res = &mut dbs.execute("CREATE person:one", &owner, None).await?;
+	res.remove(0).result?;
+	let notifications = dbs.notifications().expect("expected notifications");
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(notification.id, live_id);
+	assert_eq!(notification.action, Action::Create);
+	// Revoke the permissions for the table
+	let res = &mut dbs.execute("DEFINE TABLE person PERMISSIONS NONE", &owner, None).await?;
+	res.remove(0).result?;
+	let res = &mut dbs.execute("CREATE person:two", &owner, None).await?;
+	res.remove(0).result?;
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(
+		notification,
+		Notification::killed(live_id, "The permissions for this live query have been revoked")
+	);
+	// The live query no longer exists
+	let res = &mut dbs.execute("CREATE person:three", &owner, None).await?;
+	res.remove(0).result?;
+	assert!(notifications.try_recv().is_err());
+	let res = &mut dbs.execute("INFO FOR TABLE person", &owner, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp.pick(&Idiom::from(vec![Part::from("lives")])), Value::parse("{}"));
+	Ok(())
+}
@@ -416,12 +416,14 @@ async fn delete_filtered_live_notification() -> Result<(), Error> {
 		Notification {
 			id: live_id,
 			action: Action::Delete,
+			record: Value::parse("person:test_true"),
 			result: Value::parse(
 				"{
 					id: person:test_true,
 					condition: true,
 				}"
 			),
+			before: Value::None,
 		}
 	);
 	Ok(())
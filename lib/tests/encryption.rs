@@ -0,0 +1,337 @@
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::kvs::cipher::Cipher;
+#[cfg(feature = "sql2")]
+use surrealdb::kvs::keyring::Keyring;
+#[cfg(feature = "sql2")]
+use surrealdb::sql::Bytes;
+use surrealdb::sql::Value;
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn field_encryption() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD ssn ON person TYPE string ENCRYPTED WITH KEY pii;
+		CREATE person:tobie SET name = 'Tobie', ssn = '123-45-6789';
+		SELECT name, ssn FROM person:tobie;
+		UPDATE person:tobie SET name = 'Tobie Morgan';
+		SELECT name, ssn FROM person;
+		REMOVE FIELD ssn ON person;
+		SELECT VALUE ssn FROM ONLY person:tobie;
+	";
+	let dbs = new_ds().await?.with_keyring(Keyring::new().with_key("pii", 1, [1; 32]));
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	res.remove(0).result?;
+	res.remove(0).result?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ name: 'Tobie', ssn: '123-45-6789' }]");
+	assert_eq!(tmp, val);
+	res.remove(0).result?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ name: 'Tobie Morgan', ssn: '123-45-6789' }]");
+	assert_eq!(tmp, val);
+	res.remove(0).result?;
+	// Without the field definition, the stored value is encrypted
+	let tmp = res.remove(0).result?;
+	assert!(tmp.is_bytes(), "expected the encrypted value, got {tmp}");
+	//
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn field_encryption_requires_key() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD ssn ON person ENCRYPTED WITH KEY pii;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::EncryptionKeyNotFound { .. })));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn field_encryption_condition() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD ssn ON person ENCRYPTED WITH KEY pii;
+		DEFINE FIELD email ON person ENCRYPTED WITH KEY pii DETERMINISTIC;
+		DEFINE INDEX email ON person FIELDS email UNIQUE;
+		CREATE person:tobie SET ssn = '123-45-6789', email = 'tobie@surrealdb.com';
+		CREATE person:jaime SET ssn = '987-65-4321', email = 'jaime@surrealdb.com';
+		SELECT * FROM person WHERE ssn = '123-45-6789';
+		SELECT VALUE id FROM person WHERE email = 'tobie@surrealdb.com';
+		SELECT VALUE id FROM person WHERE email = 'tobie@surrealdb.com' EXPLAIN;
+		SELECT * FROM person WHERE email > 'a';
+		CREATE person:other SET email = 'tobie@surrealdb.com';
+		DEFINE INDEX ssn ON person FIELDS ssn;
+		DEFINE INDEX search ON person FIELDS email SEARCH ANALYZER simple BM25;
+	";
+	let dbs = new_ds().await?.with_keyring(Keyring::new().with_key("pii", 1, [1; 32]));
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 12);
+	//
+	for _ in 0..5 {
+		res.remove(0).result?;
+	}
+	// Randomized encryption can not be queried
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::EncryptedFieldCondition { .. })));
+	// Deterministic encryption can be found with an index
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[person:tobie]"));
+	let tmp = res.remove(0).result?;
+	let tmp = tmp.pick(&["0".into(), "operation".into()]);
+	assert_eq!(tmp, Value::from("Iterate Index"));
+	// Deterministic encryption can only be compared for equality
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::EncryptedFieldCondition { .. })));
+	// Unique indexes work on the encrypted values
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::IndexExists { .. })));
+	// Only deterministic encryption can be indexed, by the exact value
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::EncryptedFieldIndex { .. })));
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::EncryptedFieldIndex { .. })));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn field_encryption_bound_to_record() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD ssn ON person ENCRYPTED WITH KEY pii;
+		CREATE person:tobie SET ssn = '123-45-6789';
+		CREATE person:jaime SET ssn = <bytes> 'senc00000000000000000000000000000000';
+		SELECT VALUE ssn FROM ONLY person:jaime;
+		REMOVE FIELD ssn ON person;
+		SELECT VALUE ssn FROM ONLY person:tobie;
+		DEFINE FIELD ssn ON person ENCRYPTED WITH KEY pii;
+	";
+	let dbs = new_ds().await?.with_keyring(Keyring::new().with_key("pii", 1, [1; 32]));
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	res.remove(0).result?;
+	res.remove(0).result?;
+	res.remove(0).result?;
+	// Bytes which look like an encrypted value are encrypted too
+	let tmp = res.remove(0).result?;
+	let val = Bytes::from(b"senc00000000000000000000000000000000".to_vec());
+	assert_eq!(tmp, Value::Bytes(val));
+	res.remove(0).result?;
+	let enc = res.remove(0).result?;
+	assert!(enc.is_bytes(), "expected the encrypted value, got {enc}");
+	res.remove(0).result?;
+	// An encrypted value can not be copied into another record
+	let sql = "
+		UPDATE person:jaime SET ssn = $enc;
+		SELECT VALUE ssn FROM ONLY person:jaime;
+		UPDATE person:tobie SET ssn = $enc;
+		SELECT VALUE ssn FROM ONLY person:tobie;
+	";
+	let vars = [("enc".to_string(), enc.clone())].into();
+	let res = &mut dbs.execute(sql, &ses, Some(vars)).await?;
+	assert_eq!(res.len(), 4);
+	res.remove(0).result?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, enc);
+	// But it can be stored again in its own record, such as when it is imported
+	res.remove(0).result?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from("123-45-6789"));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn field_encryption_key_rotation() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD email ON person ENCRYPTED WITH KEY pii DETERMINISTIC;
+		DEFINE INDEX email ON person FIELDS email UNIQUE;
+		CREATE person:tobie SET email = 'tobie@surrealdb.com';
+	";
+	let old = Keyring::new().with_key("pii", 1, [1; 32]);
+	let dbs = new_ds().await?.with_keyring(old);
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	for _ in 0..3 {
+		res.remove(0).result?;
+	}
+	// Rotate the key, keeping the old version to decrypt existing values
+	let dbs =
+		dbs.with_keyring(Keyring::new().with_key("pii", 1, [1; 32]).with_key("pii", 2, [2; 32]));
+	let sql = "
+		SELECT VALUE email FROM person:tobie;
+		SELECT VALUE id FROM person WHERE email = 'tobie@surrealdb.com';
+		REENCRYPT TABLE person;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("['tobie@surrealdb.com']"));
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[person:tobie]"));
+	res.remove(0).result?;
+	// Once every record is re-encrypted, the old version can be removed
+	let dbs = dbs.with_keyring(Keyring::new().with_key("pii", 2, [2; 32]));
+	let sql = "
+		SELECT VALUE email FROM person:tobie;
+		SELECT VALUE id FROM person WHERE email = 'tobie@surrealdb.com';
+		CREATE person:other SET email = 'tobie@surrealdb.com';
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("['tobie@surrealdb.com']"));
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[person:tobie]"));
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::IndexExists { .. })));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn datastore_encryption() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h;
+		DEFINE INDEX age ON person FIELDS age;
+		CREATE person:1 SET name = 'Tobie', age = 34;
+		CREATE person:2 SET name = 'Jaime', age = 28;
+		CREATE person:3 SET name = 'Mary', age = 41;
+		SELECT VALUE name FROM person:1..3;
+		SELECT VALUE name FROM person WHERE age > 30 ORDER BY name;
+		UPDATE person:2 SET age += 1 RETURN VALUE age;
+		DELETE person:3;
+		SELECT VALUE name FROM person;
+		SHOW CHANGES FOR TABLE person SINCE 0;
+	";
+	let cipher = Cipher::new([1; 32]).with_key_encryption(true);
+	let dbs = new_ds().await?.with_encryption(cipher);
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 11);
+	//
+	for _ in 0..5 {
+		res.remove(0).result?;
+	}
+	// Range scans work over the encrypted keys
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("['Tobie', 'Jaime']"));
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("['Mary', 'Tobie']"));
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[29]"));
+	res.remove(0).result?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("['Tobie', 'Jaime']"));
+	// Changefeeds are stored with versionstamped keys
+	let tmp = res.remove(0).result?;
+	let Value::Array(tmp) = tmp else {
+		panic!("expected the changes, got {tmp}");
+	};
+	assert_eq!(tmp.len(), 6, "unexpected changes {tmp}");
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn datastore_encryption_copy() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX name ON person FIELDS name UNIQUE;
+		CREATE person:tobie SET name = 'Tobie';
+		CREATE person:jaime SET name = 'Jaime';
+	";
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let dbs = new_ds().await?;
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	for _ in 0..3 {
+		res.remove(0).result?;
+	}
+	// Copy the datastore into an encrypted datastore
+	let cipher = Cipher::new([1; 32]).with_key_encryption(true);
+	let enc = new_ds().await?.with_encryption(cipher);
+	assert!(dbs.copy_into(&enc).await? > 0);
+	// Copy the encrypted datastore into a datastore with a different key
+	let cipher = Cipher::new([2; 32]);
+	let new = new_ds().await?.with_encryption(cipher);
+	assert!(enc.copy_into(&new).await? > 0);
+	let sql = "
+		SELECT VALUE name FROM person;
+		SELECT VALUE id FROM person WHERE name = 'Jaime';
+		CREATE person:other SET name = 'Tobie';
+	";
+	for dbs in [enc, new] {
+		let res = &mut dbs.execute(sql, &ses, None).await?;
+		assert_eq!(res.len(), 3);
+		let tmp = res.remove(0).result?;
+		assert_eq!(tmp, Value::parse("['Jaime', 'Tobie']"));
+		let tmp = res.remove(0).result?;
+		assert_eq!(tmp, Value::parse("[person:jaime]"));
+		let tmp = res.remove(0).result;
+		assert!(matches!(tmp, Err(Error::IndexExists { .. })));
+	}
+	//
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn field_encryption_in_changefeeds_and_webhooks() -> Result<(), Error> {
+	use surrealdb::kvs::{LockType::*, TransactionType::*};
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h INCLUDE ORIGINAL;
+		DEFINE FIELD ssn ON person ENCRYPTED WITH KEY pii;
+		DEFINE WEBHOOK notify ON person URL 'http://localhost/hook';
+		CREATE person:tobie SET ssn = '123-45-6789';
+		UPDATE person:tobie SET ssn = '987-65-4321';
+	";
+	let dbs = new_ds().await?.with_keyring(Keyring::new().with_key("pii", 1, [1; 32]));
+	let ses = Session::owner().with_ns("test").with_db("test");
+	for res in dbs.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	// Only the encrypted values are stored in the changefeed and in the webhook queue
+	let contains = |v: &[u8], s: &[u8]| v.windows(s.len()).any(|w| w == s);
+	let mut tx = dbs.transaction(Read, Optimistic).await?;
+	let beg = surrealdb::key::change::prefix("test", "test");
+	let end = surrealdb::key::change::suffix("test", "test");
+	let changes = tx.getr(beg..end, 1000).await?;
+	let beg = surrealdb::key::root::wq::prefix();
+	let end = surrealdb::key::root::wq::suffix(u64::MAX);
+	let queue = tx.getr(beg..end, 1000).await?;
+	tx.cancel().await?;
+	let changes: Vec<_> = changes.into_iter().filter(|(_, v)| contains(v, b"tobie")).collect();
+	assert_eq!(changes.len(), 2);
+	assert_eq!(queue.len(), 2);
+	for (_, v) in changes.iter().chain(queue.iter()) {
+		assert!(contains(v, b"senc"), "expected the encrypted value in {v:?}");
+		assert!(!contains(v, b"123-45-6789"));
+		assert!(!contains(v, b"987-65-4321"));
+	}
+	// The changes are read with the encrypted values
+	let res = &mut dbs.execute("SHOW CHANGES FOR TABLE person SINCE 0", &ses, None).await?;
+	let tmp = res.remove(0).result?.to_string();
+	assert!(!tmp.contains("123-45-6789") && !tmp.contains("987-65-4321"), "{tmp}");
+	//
+	Ok(())
+}
@@ -279,3 +279,88 @@ async fn create_relate_select() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[cfg(feature = "sql2")]
+#[tokio::test]
+async fn fetch_live_notification_on_fetched_change() -> Result<(), Error> {
+	use surrealdb::dbs::Action;
+	let dbs = new_ds().await?.with_notifications();
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let sql = "
+		DEFINE INDEX author ON post FIELDS author;
+		CREATE user:tobie SET name = 'Tobie';
+		CREATE post:one SET author = user:tobie;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	res.remove(0).result?;
+	res.remove(0).result?;
+	res.remove(0).result?;
+	// Start a live query which fetches a linked record
+	let res = &mut dbs.execute("LIVE SELECT * FROM post FETCH author", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	let Value::Uuid(live_id) = res.remove(0).result? else {
+		panic!("expected uuid");
+	};
+	// Change the fetched record
+	let res = &mut dbs.execute("UPDATE user:tobie SET name = 'Jaime'", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	res.remove(0).result?;
+	// Validate notification
+	let notifications = dbs.notifications().expect("expected notifications");
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(notification.id, live_id);
+	assert_eq!(notification.action, Action::Update);
+	assert_eq!(notification.record, Value::parse("post:one"));
+	assert_eq!(
+		notification.result,
+		Value::parse("{ id: post:one, author: { id: user:tobie, name: 'Jaime' } }")
+	);
+	// Direct changes are also fetched
+	let res = &mut dbs.execute("UPDATE post:one SET title = 'Hello'", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	res.remove(0).result?;
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(
+		notification.result,
+		Value::parse("{ id: post:one, author: { id: user:tobie, name: 'Jaime' }, title: 'Hello' }")
+	);
+	// The live query no longer fires once killed
+	let vars = [("id".to_string(), Value::Uuid(live_id))].into();
+	let res = &mut dbs.execute("KILL $id", &ses, Some(vars)).await?;
+	assert_eq!(res.len(), 1);
+	res.remove(0).result?;
+	let res = &mut dbs.execute("UPDATE user:tobie SET name = 'Tobie'", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	res.remove(0).result?;
+	tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+	assert!(notifications.try_recv().is_err());
+	//
+	Ok(())
+}
+
+#[cfg(feature = "sql2")]
+#[tokio::test]
+async fn fetch_live_notification_requires_index() -> Result<(), Error> {
+	let dbs = new_ds().await?.with_notifications();
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let sql = "
+		DEFINE FIELD editor ON post TYPE option<record<admin>>;
+		DEFINE INDEX editor ON post FIELDS editor;
+		CREATE user:tobie SET name = 'Tobie';
+		CREATE post:one SET author = user:tobie;
+		LIVE SELECT * FROM post FETCH author, editor;
+	";
+	for res in dbs.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	// Records fetched through a field without an index, or through a field
+	// which can not link to the record, do not notify the live query
+	let res = &mut dbs.execute("UPDATE user:tobie SET name = 'Jaime'", &ses, None).await?;
+	res.remove(0).result?;
+	tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+	let notifications = dbs.notifications().expect("expected notifications");
+	assert!(notifications.try_recv().is_err());
+	//
+	Ok(())
+}
@@ -0,0 +1,159 @@
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use std::time::Duration;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::iam::signin::signin;
+use surrealdb::iam::throttle::LoginPolicy;
+use surrealdb::iam::verify::basic_legacy;
+use surrealdb::sql::Value;
+
+async fn signin_as(dbs: &surrealdb::kvs::Datastore, vars: &str) -> Result<Session, Error> {
+	let mut ses = Session::default();
+	let Value::Object(vars) = Value::parse(vars) else {
+		unreachable!()
+	};
+	signin(dbs, &mut ses, vars).await?;
+	Ok(ses)
+}
+
+#[tokio::test]
+async fn system_users_are_locked_out() -> Result<(), Error> {
+	let dbs = new_ds().await?.with_auth_enabled(true).with_login_policy(LoginPolicy {
+		max_failures: 2,
+		lockout: Duration::from_secs(60),
+		..Default::default()
+	});
+	let sql = "
+		DEFINE USER one ON DATABASE PASSWORD 'pass' ROLES VIEWER;
+		DEFINE USER two ON DATABASE PASSWORD 'pass' ROLES VIEWER;
+	";
+	let ses = Session::owner().with_ns("test").with_db("test");
+	for r in dbs.execute(sql, &ses, None).await? {
+		r.result?;
+	}
+	let good = "{ ns: 'test', db: 'test', user: 'one', pass: 'pass' }";
+	let bad = "{ ns: 'test', db: 'test', user: 'one', pass: 'wrong' }";
+	// A successful signin resets the failed attempts
+	assert!(matches!(signin_as(&dbs, bad).await, Err(Error::InvalidAuth)));
+	signin_as(&dbs, good).await?;
+	assert!(matches!(signin_as(&dbs, bad).await, Err(Error::InvalidAuth)));
+	assert!(matches!(signin_as(&dbs, bad).await, Err(Error::InvalidAuth)));
+	// The user is now locked out, even with the right password
+	assert!(matches!(signin_as(&dbs, good).await, Err(Error::SigninLocked)));
+	// Other users can still signin
+	signin_as(&dbs, "{ ns: 'test', db: 'test', user: 'two', pass: 'pass' }").await?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn root_users_are_locked_out_in_any_namespace() -> Result<(), Error> {
+	let dbs = new_ds().await?.with_auth_enabled(true).with_login_policy(LoginPolicy {
+		max_failures: 2,
+		lockout: Duration::from_secs(60),
+		..Default::default()
+	});
+	let sql = "DEFINE USER root ON ROOT PASSWORD 'pass' ROLES OWNER";
+	dbs.execute(sql, &Session::owner(), None).await?.remove(0).result?;
+	// Each attempt uses another namespace and database
+	for i in 0..2 {
+		let mut ses = Session::default().with_ns(&format!("ns{i}")).with_db(&format!("db{i}"));
+		let res = basic_legacy(&dbs, &mut ses, "root", "wrong").await;
+		assert!(matches!(res, Err(Error::InvalidAuth)));
+	}
+	// The root user is locked out, whichever namespace is used
+	let mut ses = Session::default().with_ns("other").with_db("other");
+	let res = basic_legacy(&dbs, &mut ses, "root", "pass").await;
+	assert!(matches!(res, Err(Error::InvalidAuth)));
+	assert!(!ses.au.is_root());
+	let mut ses = Session::default();
+	let res = basic_legacy(&dbs, &mut ses, "root", "pass").await;
+	assert!(matches!(res, Err(Error::SigninLocked)));
+	Ok(())
+}
+
+#[tokio::test]
+async fn scope_users_are_throttled() -> Result<(), Error> {
+	let dbs = new_ds().await?.with_auth_enabled(true).with_login_policy(LoginPolicy {
+		max_attempts: 2,
+		..Default::default()
+	});
+	let sql = "
+		DEFINE SCOPE account SIGNIN (SELECT * FROM user WHERE email = $email AND crypto::argon2::compare(pass, $pass));
+		CREATE user:one SET email = 'one@example.com', pass = crypto::argon2::generate('pass');
+	";
+	let ses = Session::owner().with_ns("test").with_db("test");
+	for r in dbs.execute(sql, &ses, None).await? {
+		r.result?;
+	}
+	let one = "{ ns: 'test', db: 'test', sc: 'account', email: 'one@example.com', pass: 'pass' }";
+	signin_as(&dbs, one).await?;
+	assert!(signin_as(&dbs, &one.replace("'pass' }", "'wrong' }")).await.is_err());
+	assert!(matches!(signin_as(&dbs, one).await, Err(Error::SigninThrottled)));
+	// Other signin variables do not identify the user
+	let other = one.replace(" }", ", device: 'other' }");
+	assert!(matches!(signin_as(&dbs, &other).await, Err(Error::SigninThrottled)));
+	// Other users of the scope are identified by their own signin variables
+	let two = "{ ns: 'test', db: 'test', sc: 'account', email: 'two@example.com', pass: 'pass' }";
+	assert!(!matches!(signin_as(&dbs, two).await, Err(Error::SigninThrottled)));
+	Ok(())
+}
+
+#[cfg(feature = "sql2")]
+#[tokio::test]
+async fn passwords_follow_the_password_policy() -> Result<(), Error> {
+	use surrealdb::iam::password::PasswordPolicy;
+	let dbs = new_ds().await?.with_auth_enabled(true).with_password_policy(PasswordPolicy {
+		min_length: 10,
+		min_classes: 3,
+		expiry: Some(Duration::from_secs(1)),
+	});
+	let sql = "
+		DEFINE USER one ON DATABASE PASSWORD 'Sh0rt!' ROLES VIEWER;
+		DEFINE USER one ON DATABASE PASSWORD 'lowercaseonly' ROLES VIEWER;
+		DEFINE USER one ON DATABASE PASSWORD 'L0ng-enough' ROLES VIEWER;
+		DEFINE USER two ON DATABASE PASSHASH '$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaA' ROLES VIEWER;
+	";
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp,
+		Err(Error::PasswordTooShort {
+			min: 10
+		})
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp,
+		Err(Error::PasswordTooSimple {
+			min: 3
+		})
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	// Password hashes can not be checked
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	// The password can be used until it expires
+	let one = "{ ns: 'test', db: 'test', user: 'one', pass: 'L0ng-enough' }";
+	signin_as(&dbs, one).await?;
+	tokio::time::sleep(Duration::from_millis(2100)).await;
+	assert!(matches!(signin_as(&dbs, one).await, Err(Error::PasswordExpired)));
+	// Importing the same password hash does not reset the expiry
+	let tmp = dbs.execute("INFO FOR DB", &ses, None).await?.remove(0).result?;
+	let sql = tmp.pick(&["users".into(), "one".into()]).as_raw_string();
+	dbs.execute(&sql, &ses, None).await?.remove(0).result?;
+	assert!(matches!(signin_as(&dbs, one).await, Err(Error::PasswordExpired)));
+	// Setting the password again resets the expiry
+	let sql = "DEFINE USER one ON DATABASE PASSWORD 'An0ther-one' ROLES VIEWER";
+	dbs.execute(sql, &ses, None).await?.remove(0).result?;
+	signin_as(&dbs, "{ ns: 'test', db: 'test', user: 'one', pass: 'An0ther-one' }").await?;
+	Ok(())
+}
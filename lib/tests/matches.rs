@@ -450,3 +450,73 @@ async fn select_where_matches_without_complex_query() -> Result<(), Error> {
 	assert_eq!(format!("{:#}", tmp), format!("{:#}", val_docs));
 	Ok(())
 }
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn select_where_matches_using_synonyms_and_stopwords() -> Result<(), Error> {
+	let sql = r"
+		CREATE synonym:laptop SET synonyms = ['notebook'];
+		CREATE synonym:notebook SET synonyms = ['laptop'];
+		CREATE ticket:1 SET subject = 'The notebook does not boot';
+		CREATE ticket:2 SET subject = 'Printer out of paper';
+		DEFINE ANALYZER support TOKENIZERS unicode FILTERS lowercase,stopwords(english),synonyms(synonym);
+		DEFINE INDEX ticket_subject ON ticket FIELDS subject SEARCH ANALYZER support BM25;
+		SELECT id FROM ticket WHERE subject @@ 'laptop';
+		SELECT id FROM ticket WHERE subject @@ 'the';
+		RETURN search::analyze('support', 'The Laptop is broken');
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 9);
+	//
+	for _ in 0..6 {
+		let _ = res.remove(0).result?;
+	}
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: ticket:1 }]");
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['laptop', 'notebook', 'broken']");
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn select_where_matches_after_changing_synonyms() -> Result<(), Error> {
+	let sql = r"
+		CREATE ticket:1 SET subject = 'The Printer is out of paper';
+		DEFINE ANALYZER support TOKENIZERS unicode FILTERS synonyms(synonym),lowercase;
+		DEFINE INDEX ticket_subject ON ticket FIELDS subject SEARCH ANALYZER support BM25;
+		CREATE synonym:printer SET synonyms = ['copier'];
+		SELECT id FROM ticket WHERE subject @@ 'copier';
+		DEFINE ANALYZER support TOKENIZERS unicode FILTERS synonyms(synonym),lowercase;
+		SELECT id FROM ticket WHERE subject @@ 'copier';
+		RETURN search::analyze('support', 'PRINTER');
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	for _ in 0..4 {
+		let _ = res.remove(0).result?;
+	}
+	// The index is stale until the analyzer is defined again
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	let _ = res.remove(0).result?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: ticket:1 }]");
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	// Synonyms are matched regardless of case
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['printer', 'copier']");
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	Ok(())
+}
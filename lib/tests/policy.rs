@@ -0,0 +1,131 @@
+#![cfg(feature = "sql2")]
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::iam::signin::signin;
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::{Thing, Value};
+
+#[tokio::test]
+async fn define_policy_statement() -> Result<(), Error> {
+	let sql = "
+		DEFINE POLICY tenant ON TABLE ticket FOR select, update WHEN tenant = $token.ID COMMENT 'support';
+		INFO FOR TABLE ticket;
+		REMOVE POLICY tenant ON TABLE ticket;
+		INFO FOR TABLE ticket;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			events: {},
+			fields: {},
+			indexes: {},
+			lives: {},
+			policies: { tenant: \"DEFINE POLICY tenant ON ticket FOR select, update WHEN tenant = $token.ID COMMENT 'support'\" },
+			tables: {},
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			events: {},
+			fields: {},
+			indexes: {},
+			lives: {},
+			tables: {},
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+async fn signin_as(dbs: &Datastore, user: &str) -> Result<Session, Error> {
+	let mut ses = Session::default();
+	let vars = Value::parse(&format!("{{ ns: 'test', db: 'test', user: '{user}', pass: 'pass' }}"));
+	let Value::Object(vars) = vars else {
+		unreachable!()
+	};
+	signin(dbs, &mut ses, vars).await?;
+	Ok(ses)
+}
+
+#[tokio::test]
+async fn policies_apply_to_every_actor() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE ticket SCHEMALESS PERMISSIONS FOR select, update WHERE published = true FOR create, delete FULL;
+		DEFINE POLICY tenant ON ticket FOR select, update WHEN $session.ro CONTAINS 'Owner' OR tenant = $auth.tenant OR tenant = $token.ID;
+		DEFINE POLICY archive ON ticket FOR delete WHEN false;
+		DEFINE USER acme ON DATABASE PASSWORD 'pass' ROLES EDITOR;
+		CREATE account:one SET tenant = 'acme';
+		CREATE ticket:1 SET tenant = 'acme', published = true;
+		CREATE ticket:2 SET tenant = 'acme', published = false;
+		CREATE ticket:3 SET tenant = 'globex', published = true;
+	";
+	let dbs = new_ds().await?.with_auth_enabled(true);
+	let owner = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &owner, None).await?;
+	assert_eq!(res.len(), 8);
+	for r in res.drain(..) {
+		r.result?;
+	}
+	// Database users are filtered by policies, even though they bypass table permissions
+	let ses = signin_as(&dbs, "acme").await?;
+	let sql = "
+		SELECT VALUE id FROM ticket;
+		UPDATE ticket SET seen = true RETURN VALUE id;
+		DELETE ticket:1 RETURN BEFORE;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[ticket:1, ticket:2]"));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[ticket:1, ticket:2]"));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	// Record users are filtered by both table permissions and policies
+	let rid = Value::from(Thing::from(("account", "one")));
+	let ses = Session::for_scope("test", "test", "account", rid);
+	let sql = "SELECT VALUE id FROM ticket";
+	let tmp = dbs.execute(sql, &ses, None).await?.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[ticket:1]"));
+	// Policies can depend on the roles of the actor
+	let sql = "
+		SELECT VALUE id FROM ticket;
+		DELETE ticket:1 RETURN BEFORE;
+		SELECT VALUE id FROM ticket WHERE seen = true;
+	";
+	let res = &mut dbs.execute(sql, &owner, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[ticket:1, ticket:2, ticket:3]"));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[ticket:1, ticket:2]"));
+	//
+	Ok(())
+}
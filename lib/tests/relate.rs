@@ -105,3 +105,46 @@ async fn relate_and_overwrite() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[cfg(feature = "sql2")]
+#[tokio::test]
+async fn relate_live_notification_on_graph_traversal() -> Result<(), Error> {
+	use surrealdb::dbs::Action;
+	let dbs = new_ds().await?.with_notifications();
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let sql = "
+		CREATE person:tobie;
+		CREATE post:one SET title = 'Hello';
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	res.remove(0).result?;
+	res.remove(0).result?;
+	// Start a live query which traverses the graph
+	let res =
+		&mut dbs.execute("LIVE SELECT id, ->likes->post AS liked FROM person", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	let Value::Uuid(live_id) = res.remove(0).result? else {
+		panic!("expected uuid");
+	};
+	// Relate the record to another record
+	let res = &mut dbs.execute("RELATE person:tobie->likes->post:one", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	res.remove(0).result?;
+	// Validate notification
+	let notifications = dbs.notifications().expect("expected notifications");
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(notification.id, live_id);
+	assert_eq!(notification.action, Action::Update);
+	assert_eq!(notification.record, Value::parse("person:tobie"));
+	assert_eq!(notification.result, Value::parse("{ id: person:tobie, liked: [post:one] }"));
+	// Remove the edge
+	let res = &mut dbs.execute("DELETE likes", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	res.remove(0).result?;
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(notification.action, Action::Update);
+	assert_eq!(notification.result, Value::parse("{ id: person:tobie, liked: [] }"));
+	//
+	Ok(())
+}
@@ -0,0 +1,175 @@
+#![cfg(feature = "sql2")]
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::iam::signin::signin;
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::Value;
+
+#[tokio::test]
+async fn define_role_statement() -> Result<(), Error> {
+	let sql = "
+		DEFINE ROLE ops ON DATABASE GRANT VIEW ON ANY, VIEW ON TABLE COMMENT 'operations';
+		INFO FOR DB;
+		DEFINE ROLE owner ON DATABASE GRANT VIEW ON ANY;
+		DEFINE USER test ON DATABASE PASSWORD 'test' ROLES missing;
+		REMOVE ROLE ops ON DATABASE;
+		INFO FOR DB;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			analyzers: {},
+			functions: {},
+			models: {},
+			params: {},
+			roles: { ops: \"DEFINE ROLE ops ON DATABASE GRANT VIEW ON ANY, VIEW ON TABLE COMMENT 'operations'\" },
+			scopes: {},
+			tables: {},
+			tokens: {},
+			users: {},
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "The role 'owner' is a predefined role"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "IAM error: Invalid role 'missing'"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			analyzers: {},
+			functions: {},
+			models: {},
+			params: {},
+			scopes: {},
+			tables: {},
+			tokens: {},
+			users: {},
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+async fn signin_as(dbs: &Datastore, user: &str) -> Result<Session, Error> {
+	let mut ses = Session::default();
+	let vars = Value::parse(&format!("{{ ns: 'test', db: 'test', user: '{user}', pass: 'pass' }}"));
+	let Value::Object(vars) = vars else {
+		unreachable!()
+	};
+	signin(dbs, &mut ses, vars).await?;
+	Ok(ses)
+}
+
+#[tokio::test]
+async fn custom_role_privileges() -> Result<(), Error> {
+	let sql = "
+		DEFINE ROLE ops ON NAMESPACE GRANT VIEW ON ANY, VIEW ON TABLE;
+		DEFINE ROLE indexer ON DATABASE GRANT EDIT ON INDEX;
+		DEFINE ROLE reader ON DATABASE GRANT VIEW ON RECORD;
+		DEFINE USER ops ON DATABASE PASSWORD 'pass' ROLES ops;
+		DEFINE USER indexer ON DATABASE PASSWORD 'pass' ROLES indexer;
+		DEFINE USER reader ON DATABASE PASSWORD 'pass' ROLES reader;
+		DEFINE TABLE person CHANGEFEED 1h;
+		CREATE person:tobie SET name = 'Tobie';
+	";
+	let dbs = new_ds().await?.with_auth_enabled(true);
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	for r in res.drain(..) {
+		r.result?;
+	}
+	// A role can allow running INFO and SHOW CHANGES, without reading table data
+	let ses = signin_as(&dbs, "ops").await?;
+	let sql = "
+		INFO FOR DB;
+		SHOW CHANGES FOR TABLE person SINCE 0 LIMIT 10;
+		SELECT * FROM person;
+		DEFINE INDEX name ON person FIELDS name;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::IamError(_))));
+	// A role can allow defining indexes, without defining users
+	let ses = signin_as(&dbs, "indexer").await?;
+	let sql = "
+		DEFINE INDEX name ON person FIELDS name;
+		DEFINE USER other ON DATABASE PASSWORD 'pass';
+		INFO FOR DB;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::IamError(_))));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::IamError(_))));
+	// A role can allow reading table data, without editing it
+	let ses = signin_as(&dbs, "reader").await?;
+	let sql = "
+		SELECT * FROM person;
+		UPDATE person:tobie SET name = 'Jaime';
+		SELECT name FROM person;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[{ id: person:tobie, name: 'Tobie' }]"));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[{ name: 'Tobie' }]"));
+	// Changes to a role apply to existing sessions
+	let sql = "DEFINE ROLE reader ON DATABASE GRANT EDIT ON RECORD";
+	let owner = Session::owner().with_ns("test").with_db("test");
+	dbs.execute(sql, &owner, None).await?.remove(0).result?;
+	let sql = "UPDATE person:tobie SET name = 'Jaime' RETURN name";
+	let tmp = dbs.execute(sql, &ses, None).await?.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[{ name: 'Jaime' }]"));
+	//
+	Ok(())
+}
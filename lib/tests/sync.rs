@@ -0,0 +1,244 @@
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::kvs::sync::{Change, Changes, Push, Resolution};
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::{thing, Datetime, Value};
+
+async fn run(dbs: &Datastore, sql: &str) -> Result<Vec<Value>, Error> {
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let mut out = Vec::new();
+	for res in dbs.execute(sql, &ses, None).await? {
+		out.push(res.result?);
+	}
+	Ok(out)
+}
+
+#[tokio::test]
+async fn sync_pull_and_push() -> Result<(), Error> {
+	let ses = Session::owner().with_ns("test").with_db("test");
+	// The server and a replica, which both record their changes
+	let server = new_ds().await?;
+	run(&server, "DEFINE TABLE person CHANGEFEED 1h").await?;
+	run(&server, "CREATE person:1 SET name = 'Tobie'; CREATE person:2 SET name = 'Jaime'").await?;
+	let replica = new_ds().await?;
+	run(&replica, "DEFINE TABLE person CHANGEFEED 1h").await?;
+	// The replica pulls the changes from the server
+	let changes = server.sync_pull(&ses, 0, None).await?;
+	assert_eq!(changes.changes.len(), 2);
+	let remote = changes.versionstamp;
+	let push = Push {
+		changes: changes.changes,
+		replicate: true,
+		..Default::default()
+	};
+	assert!(replica.sync_push(&ses, push).await?.is_empty());
+	let tmp = run(&replica, "SELECT VALUE name FROM person").await?.remove(0);
+	assert_eq!(tmp, Value::parse("['Tobie', 'Jaime']"));
+	// Replicated changes are not recorded, so they are not pushed back
+	let changes = replica.sync_pull(&ses, 0, None).await?;
+	assert!(changes.changes.is_empty());
+	let local = changes.versionstamp;
+	// Both the server and, later, the replica change a record
+	run(&server, "UPDATE person:1 SET name = 'Tobie Morgan'").await?;
+	server.tick_at(100).await?;
+	run(&replica, "UPDATE person:1 SET name = 'Tobias'; CREATE person:3 SET name = 'Mary'").await?;
+	replica.tick_at(200).await?;
+	// The replica pushes its changes, which are kept as they were written last
+	let changes = replica.sync_pull(&ses, local, None).await?;
+	assert_eq!(changes.changes.len(), 2);
+	let push = Push {
+		since: Some(remote),
+		changes: changes.changes,
+		..Default::default()
+	};
+	let conflicts = server.sync_push(&ses, push).await?;
+	assert_eq!(conflicts, vec![thing("person:1")?]);
+	let tmp = run(&server, "SELECT VALUE name FROM person").await?.remove(0);
+	assert_eq!(tmp, Value::parse("['Tobias', 'Jaime', 'Mary']"));
+	// Pushing changes which were already applied does nothing
+	let changes = server.sync_pull(&ses, remote, None).await?;
+	assert_eq!(changes.changes.len(), 3);
+	let remote = changes.versionstamp;
+	let push = Push {
+		changes: changes.changes,
+		replicate: true,
+		..Default::default()
+	};
+	assert!(replica.sync_push(&ses, push).await?.is_empty());
+	let changes = server.sync_pull(&ses, remote, None).await?;
+	assert!(changes.changes.is_empty());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn sync_keeps_last_written_change() -> Result<(), Error> {
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let server = new_ds().await?;
+	server.tick_at(100).await?;
+	run(&server, "DEFINE TABLE person CHANGEFEED 1h; CREATE person:1, person:2").await?;
+	let remote = server.sync_pull(&ses, 0, None).await?.versionstamp;
+	// The records are changed on the server between two ticks
+	server.tick_at(200).await?;
+	run(&server, "UPDATE person:1 SET name = 'Tobie'; UPDATE person:2 SET name = 'Jaime'").await?;
+	server.tick_at(300).await?;
+	// The pulled changes carry the time of the tick after them
+	let changes = server.sync_pull(&ses, remote, None).await?;
+	let at = |secs: i64| Datetime::from(chrono::DateTime::from_timestamp(secs, 0).unwrap());
+	assert_eq!(changes.changes[0].written(), Some(&at(300)));
+	// A replica pushes a change written before, and a change written after, the server changes
+	let push = Push {
+		since: Some(remote),
+		changes: vec![
+			Change::Update(
+				thing("person:1")?,
+				Value::parse("{ id: person:1, name: 'Tobias' }"),
+				Some(at(250)),
+			),
+			Change::Update(
+				thing("person:2")?,
+				Value::parse("{ id: person:2, name: 'Jamie' }"),
+				Some(at(350)),
+			),
+		],
+		..Default::default()
+	};
+	let conflicts = server.sync_push(&ses, push).await?;
+	assert_eq!(conflicts, vec![thing("person:1")?, thing("person:2")?]);
+	let tmp = run(&server, "SELECT VALUE name FROM person").await?.remove(0);
+	assert_eq!(tmp, Value::parse("['Tobie', 'Jamie']"));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn sync_pull_with_a_small_limit() -> Result<(), Error> {
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let server = new_ds().await?;
+	run(&server, "DEFINE TABLE a CHANGEFEED 1h; DEFINE TABLE b CHANGEFEED 1h").await?;
+	run(&server, "BEGIN; CREATE a:1; CREATE b:1; COMMIT; CREATE a:2; CREATE b:2").await?;
+	// All of the changes are pulled, in batches of whole change sets
+	let mut since = 0;
+	let mut ids = Vec::new();
+	loop {
+		let changes = server.sync_pull(&ses, since, Some(1)).await?;
+		if changes.versionstamp == since {
+			break;
+		}
+		ids.extend(changes.changes.iter().map(|c| c.id().to_string()));
+		since = changes.versionstamp;
+	}
+	assert_eq!(ids, vec!["a:1", "b:1", "a:2", "b:2"]);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn sync_resolve_with_function() -> Result<(), Error> {
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let server = new_ds().await?;
+	run(
+		&server,
+		"
+		DEFINE TABLE person CHANGEFEED 1h;
+		DEFINE FUNCTION fn::resolve($current: any, $pushed: any) {
+			RETURN IF $pushed.name = 'Deleted' THEN NONE
+			ELSE { id: $current.id, name: $current.name + ' / ' + $pushed.name } END;
+		};
+		CREATE person:1 SET name = 'Tobie';
+		CREATE person:2 SET name = 'Jaime';
+		",
+	)
+	.await?;
+	let remote = server.sync_pull(&ses, 0, None).await?.versionstamp;
+	run(&server, "UPDATE person:1 SET name = 'Tobie Morgan'; UPDATE person:2 SET age = 28").await?;
+	// A replica pushes changes to the same records
+	let push = Push {
+		since: Some(remote),
+		changes: vec![
+			Change::Update(
+				thing("person:1")?,
+				Value::parse("{ id: person:1, name: 'Tobias' }"),
+				None,
+			),
+			Change::Update(
+				thing("person:2")?,
+				Value::parse("{ id: person:2, name: 'Deleted' }"),
+				None,
+			),
+			Change::Update(
+				thing("person:3")?,
+				Value::parse("{ id: person:3, name: 'Mary' }"),
+				None,
+			),
+		],
+		resolution: Resolution::Function("resolve".to_string()),
+		..Default::default()
+	};
+	// The changes can be sent as a value
+	let push = Push::try_from(Value::from(push))?;
+	let conflicts = server.sync_push(&ses, push).await?;
+	assert_eq!(conflicts, vec![thing("person:1")?, thing("person:2")?]);
+	let tmp = run(&server, "SELECT VALUE name FROM person").await?.remove(0);
+	assert_eq!(tmp, Value::parse("['Tobie Morgan / Tobias', 'Mary']"));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn sync_replicate_requires_owner() -> Result<(), Error> {
+	let server = new_ds().await?.with_auth_enabled(true);
+	run(&server, "DEFINE TABLE person CHANGEFEED 1h").await?;
+	let push = |replicate| Push {
+		changes: vec![Change::Update(
+			thing("person:1").unwrap(),
+			Value::parse("{ id: person:1, name: 'Tobie' }"),
+			None,
+		)],
+		replicate,
+		..Default::default()
+	};
+	// Editors can not replicate changes, but can push changes which are recorded
+	let ses = Session::editor().with_ns("test").with_db("test");
+	let tmp = server.sync_push(&ses, push(true)).await;
+	assert!(matches!(tmp, Err(Error::IamError(_))), "unexpected result {tmp:?}");
+	assert!(server.sync_push(&ses, push(false)).await?.is_empty());
+	let changes = server.sync_pull(&ses, 0, None).await?;
+	assert_eq!(changes.changes.len(), 1);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn sync_changes_as_value() -> Result<(), Error> {
+	let val = Value::parse(
+		"{
+			versionstamp: 3,
+			changes: [
+				{ update: { id: 'person:1', name: 'Tobie' }, at: '2024-01-01T00:00:00Z' },
+				{ delete: { id: person:2 } },
+			],
+		}",
+	);
+	let changes = Changes::try_from(val)?;
+	assert_eq!(changes.versionstamp, 3);
+	assert_eq!(
+		changes.changes,
+		vec![
+			Change::Update(
+				thing("person:1")?,
+				Value::parse("{ id: person:1, name: 'Tobie' }"),
+				Some(Datetime::try_from("2024-01-01T00:00:00Z").unwrap()),
+			),
+			Change::Delete(thing("person:2")?, None),
+		]
+	);
+	let tmp = Changes::try_from(Value::parse("{ versionstamp: 3, changes: [{ insert: {} }] }"));
+	assert!(matches!(tmp, Err(Error::InvalidChanges(_))));
+	//
+	Ok(())
+}
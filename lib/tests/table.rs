@@ -106,3 +106,52 @@ async fn define_foreign_table() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn define_foreign_table_live_notification() -> Result<(), Error> {
+	use surrealdb::dbs::{Action, Notification};
+	let dbs = new_ds().await?.with_notifications();
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let sql = "
+		DEFINE TABLE person SCHEMALESS;
+		DEFINE TABLE person_by_age AS SELECT count(), age FROM person GROUP BY age;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	res.remove(0).result?;
+	res.remove(0).result?;
+	// Start a live query on the foreign table
+	let res = &mut dbs.execute("LIVE SELECT * FROM person_by_age", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	let Value::Uuid(live_id) = res.remove(0).result? else {
+		panic!("expected uuid");
+	};
+	// Create a record in the source table
+	let res = &mut dbs.execute("CREATE person:one SET age = 20", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	res.remove(0).result?;
+	// Validate notification
+	let notifications = dbs.notifications().expect("expected notifications");
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(notification.id, live_id);
+	assert_eq!(notification.action, Action::Create);
+	assert_eq!(notification.record, Value::parse("person_by_age:[20]"));
+	assert_eq!(notification.result, Value::parse("{ id: person_by_age:[20], age: 20, count: 1 }"));
+	// Update the aggregate with another source record
+	let res = &mut dbs.execute("CREATE person:two SET age = 20", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	res.remove(0).result?;
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(
+		notification,
+		Notification {
+			id: live_id,
+			action: Action::Update,
+			record: Value::parse("person_by_age:[20]"),
+			result: Value::parse("{ id: person_by_age:[20], age: 20, count: 2 }"),
+			before: Value::None,
+		}
+	);
+	//
+	Ok(())
+}
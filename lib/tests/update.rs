@@ -681,3 +681,46 @@ async fn check_permissions_auth_disabled() {
 		);
 	}
 }
+
+#[cfg(feature = "sql2")]
+#[tokio::test]
+async fn update_live_notification_with_before() -> Result<(), Error> {
+	use surrealdb::dbs::{Action, Notification};
+	let dbs = new_ds().await?.with_notifications();
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let res = &mut dbs.execute("CREATE person:test SET name = 'Tobie'", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	res.remove(0).result?;
+	// Start a live query which includes the previous record content
+	let res = &mut dbs.execute("LIVE SELECT * FROM person WITH BEFORE", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	let Value::Uuid(live_id) = res.remove(0).result? else {
+		panic!("expected uuid");
+	};
+	// Update the record
+	let res = &mut dbs.execute("UPDATE person:test SET name = 'Jaime'", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	res.remove(0).result?;
+	// Validate notification
+	let notifications = dbs.notifications().expect("expected notifications");
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(
+		notification,
+		Notification {
+			id: live_id,
+			action: Action::Update,
+			record: Value::parse("person:test"),
+			result: Value::parse("{ id: person:test, name: 'Jaime' }"),
+			before: Value::parse("{ id: person:test, name: 'Tobie' }"),
+		}
+	);
+	// Newly created records have no previous content
+	let res = &mut dbs.execute("CREATE person:other SET name = 'Tobie'", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	res.remove(0).result?;
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(notification.action, Action::Create);
+	assert_eq!(notification.record, Value::parse("person:other"));
+	assert_eq!(notification.before, Value::None);
+	Ok(())
+}
@@ -203,3 +203,152 @@ async fn select_where_brut_force_knn() -> Result<(), Error> {
 	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
 	Ok(())
 }
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn select_where_mtree_knn_f16() -> Result<(), Error> {
+	let sql = r"
+		CREATE pts:1 SET point = [1,2,3,4];
+		CREATE pts:2 SET point = [4,5,6,7];
+		CREATE pts:3 SET point = [8,9,10,11];
+		DEFINE INDEX mt_pts ON pts FIELDS point MTREE DIMENSION 4 TYPE F16;
+		LET $pt = [2,3,4,5];
+		SELECT id, vector::distance::euclidean(point, $pt) AS dist FROM pts WHERE point knn<2> $pt;
+		INFO FOR TABLE pts;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	for _ in 0..5 {
+		let _ = res.remove(0).result?;
+	}
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: pts:1,
+				dist: 2f
+			},
+			{
+				id: pts:2,
+				dist: 4f
+			}
+		]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			events: {},
+			fields: {},
+			indexes: {
+				mt_pts: 'DEFINE INDEX mt_pts ON pts FIELDS point MTREE DIMENSION 4 DIST EUCLIDEAN TYPE F16 CAPACITY 40 DOC_IDS_ORDER 100 DOC_IDS_CACHE 100 MTREE_CACHE 100'
+			},
+			lives: {},
+			tables: {}
+		}",
+	);
+	assert_eq!(tmp, val);
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn select_where_mtree_knn_binary() -> Result<(), Error> {
+	let sql = r"
+		CREATE pts:1 SET point = [1,0,1,0,1,0,1,0,1];
+		CREATE pts:2 SET point = [1,1,1,1,0,0,0,0,1];
+		CREATE pts:3 SET point = [0,1,0,1,0,1,0,1,0];
+		DEFINE INDEX mt_pts ON pts FIELDS point MTREE DIMENSION 9 DIST HAMMING TYPE BINARY;
+		LET $pt = [1,0,1,0,1,0,1,1,1];
+		SELECT id, vector::distance::hamming(point, $pt) AS dist FROM pts WHERE point knn<2,HAMMING> $pt;
+		SELECT id FROM pts WHERE point knn<2,HAMMING> $pt EXPLAIN;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	for _ in 0..5 {
+		let _ = res.remove(0).result?;
+	}
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: pts:1,
+				dist: 1
+			},
+			{
+				id: pts:2,
+				dist: 5
+			}
+		]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'mt_pts',
+						operator: '<2>',
+						value: [1,0,1,0,1,0,1,1,1]
+					},
+					table: 'pts',
+				},
+				operation: 'Iterate Index'
+			}
+		]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	Ok(())
+}
+
+#[tokio::test]
+#[cfg(feature = "sql2")]
+async fn select_where_mtree_knn_quantized() -> Result<(), Error> {
+	let sql = r"
+		DEFINE INDEX mt_pts ON pts FIELDS point MTREE DIMENSION 2 QUANTIZE SCALAR;
+		CREATE pts:1 SET point = [0.5, 0.5];
+		CREATE pts:2 SET point = [0.501, 0.5];
+		CREATE pts:3 SET point = [0.503, 0.5];
+		CREATE pts:4 SET point = [-0.5, -0.5];
+		LET $pt = [0.5021, 0.5];
+		SELECT id FROM pts WHERE point knn<2> $pt;
+		INFO FOR TABLE pts;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	for _ in 0..6 {
+		let _ = res.remove(0).result?;
+	}
+	// The quantized vectors of the first three points are equal,
+	// so the nearest ones are found by re-ranking the stored vectors.
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: pts:3 }, { id: pts:2 }]");
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			events: {},
+			fields: {},
+			indexes: {
+				mt_pts: 'DEFINE INDEX mt_pts ON pts FIELDS point MTREE DIMENSION 2 DIST EUCLIDEAN TYPE F64 CAPACITY 40 DOC_IDS_ORDER 100 DOC_IDS_CACHE 100 MTREE_CACHE 100 QUANTIZE SCALAR'
+			},
+			lives: {},
+			tables: {}
+		}",
+	);
+	assert_eq!(tmp, val);
+	Ok(())
+}
@@ -0,0 +1,180 @@
+#![cfg(feature = "sql2")]
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::sql::{Part, Value};
+
+#[tokio::test]
+async fn define_webhook_statement() -> Result<(), Error> {
+	let sql = "
+		DEFINE WEBHOOK notify ON person WHEN $event = 'CREATE' URL 'http://localhost/hook' SECRET 'shh';
+		INFO FOR TABLE person;
+		INFO FOR WEBHOOK notify ON person;
+		REMOVE WEBHOOK notify ON person;
+		INFO FOR TABLE person;
+		DEFINE WEBHOOK invalid ON person URL 'not a url';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			events: {},
+			fields: {},
+			tables: {},
+			indexes: {},
+			lives: {},
+			webhooks: { notify: \"DEFINE WEBHOOK notify ON person WHEN $event = 'CREATE' URL 'http://localhost/hook' SECRET '[REDACTED]' RETRY 5\" },
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ deadletters: [] }");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			events: {},
+			fields: {},
+			tables: {},
+			indexes: {},
+			lives: {},
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::InvalidUrl(_))));
+	//
+	Ok(())
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+async fn webhook_delivers_signed_payload() -> Result<(), Error> {
+	use wiremock::{
+		matchers::{header, method, path},
+		Mock, ResponseTemplate,
+	};
+
+	let server = wiremock::MockServer::start().await;
+	Mock::given(method("POST"))
+		.and(path("/hook"))
+		.and(header("x-surreal-webhook", "notify"))
+		.respond_with(ResponseTemplate::new(200))
+		.expect(1)
+		.mount(&server)
+		.await;
+
+	let sql = format!(
+		"
+		DEFINE WEBHOOK notify ON person WHEN $event = 'CREATE' URL '{}/hook' SECRET 'shh';
+		CREATE person:tobie SET name = 'Tobie';
+		UPDATE person:tobie SET name = 'Jaime';
+	",
+		server.uri()
+	);
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	for _ in 0..3 {
+		res.remove(0).result?;
+	}
+	// Nothing is sent until the payloads are processed
+	assert!(server.received_requests().await.unwrap().is_empty());
+	// The datastore tick does not deliver the payloads
+	dbs.tick_at(1).await?;
+	assert!(server.received_requests().await.unwrap().is_empty());
+	dbs.process_webhooks(1).await?;
+	// Only the CREATE event matches the webhook condition
+	server.verify().await;
+	let req = &server.received_requests().await.unwrap()[0];
+	let body: serde_json::Value = serde_json::from_slice(&req.body).unwrap();
+	assert_eq!(body["webhook"], "notify");
+	assert_eq!(body["table"], "person");
+	assert_eq!(body["event"], "CREATE");
+	assert_eq!(body["id"], "person:tobie");
+	assert_eq!(body["after"]["name"], "Tobie");
+	// The payload is signed with the webhook secret
+	let signature = "sha256=5feba1e60c5b1518224bd98e94cfcd41f7925618241939166cf25e23cae7b7b8";
+	assert_eq!(req.headers.get(&"x-surreal-signature".into()).unwrap().as_str(), signature);
+	// The payload is not delivered again
+	dbs.process_webhooks(2).await?;
+	assert_eq!(server.received_requests().await.unwrap().len(), 1);
+	//
+	Ok(())
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+async fn webhook_retries_and_dead_letters_failed_deliveries() -> Result<(), Error> {
+	use wiremock::{
+		matchers::{method, path},
+		Mock, ResponseTemplate,
+	};
+
+	let server = wiremock::MockServer::start().await;
+	Mock::given(method("POST"))
+		.and(path("/hook"))
+		.respond_with(ResponseTemplate::new(500))
+		.mount(&server)
+		.await;
+
+	let sql = format!(
+		"
+		DEFINE WEBHOOK notify ON person URL '{}/hook' RETRY 1;
+		CREATE person:tobie;
+	",
+		server.uri()
+	);
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	res.remove(0).result?;
+	res.remove(0).result?;
+	// The first attempt fails, and is retried after a backoff
+	dbs.process_webhooks(10).await?;
+	assert_eq!(server.received_requests().await.unwrap().len(), 1);
+	dbs.process_webhooks(11).await?;
+	assert_eq!(server.received_requests().await.unwrap().len(), 1);
+	// The retry fails, and the delivery is dead-lettered
+	dbs.process_webhooks(12).await?;
+	assert_eq!(server.received_requests().await.unwrap().len(), 2);
+	dbs.process_webhooks(100).await?;
+	assert_eq!(server.received_requests().await.unwrap().len(), 2);
+	//
+	let res = &mut dbs.execute("INFO FOR WEBHOOK notify ON person", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let deadletters = tmp.pick(&[Part::from("deadletters")]);
+	let Value::Array(deadletters) = deadletters else {
+		panic!("expected an array");
+	};
+	assert_eq!(deadletters.len(), 1);
+	let dead = &deadletters[0];
+	assert_eq!(dead.pick(&[Part::from("attempts")]), Value::from(2));
+	assert_eq!(
+		dead.pick(&[Part::from("error")]),
+		Value::from(
+			"There was an error processing a remote HTTP request: 500 Internal Server Error"
+		)
+	);
+	assert_eq!(dead.pick(&[Part::from("payload"), Part::from("id")]), Value::parse("person:tobie"));
+	//
+	Ok(())
+}
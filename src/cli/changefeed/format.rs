@@ -0,0 +1,163 @@
+use crate::cnf::PKG_VERSION;
+use crate::err::Error;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{json, Value as Json};
+use std::time::{SystemTime, UNIX_EPOCH};
+use surrealdb::sql::Value;
+
+/// The format in which change sets are written
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+	/// One JSON object per change set, as returned by `SHOW CHANGES`
+	#[default]
+	Ndjson,
+	/// One Debezium change event envelope per modified record
+	Debezium,
+}
+
+/// A single encoded message, written as one line to files, or as one record to Kafka
+#[derive(Debug, PartialEq, Eq)]
+pub struct Message {
+	pub key: Option<Vec<u8>>,
+	pub value: Vec<u8>,
+}
+
+#[derive(Serialize)]
+struct ChangeSet<'a> {
+	versionstamp: u128,
+	changes: &'a Json,
+}
+
+/// Encodes the changes committed at a versionstamp into messages
+pub fn encode(
+	format: Format,
+	ns: &str,
+	db: &str,
+	versionstamp: u128,
+	changes: Value,
+) -> Result<Vec<Message>, Error> {
+	match format {
+		Format::Ndjson => {
+			let changes = changes.into_json();
+			let value = serde_json::to_vec(&ChangeSet {
+				versionstamp,
+				changes: &changes,
+			})?;
+			Ok(vec![Message {
+				key: None,
+				value,
+			}])
+		}
+		Format::Debezium => {
+			let Value::Array(changes) = changes else {
+				return Ok(vec![]);
+			};
+			let ts_ms =
+				SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+			let mut messages = Vec::with_capacity(changes.len());
+			for change in changes {
+				let Value::Object(mut change) = change else {
+					continue;
+				};
+				let original = change.remove("original");
+				let (op, before, after) = if let Some(after) = change.remove("update") {
+					// Only changefeeds which include original values can tell creates from updates
+					match original {
+						Some(before) if before.is_none_or_null() => ("c", Value::Null, after),
+						Some(before) => ("u", before, after),
+						None => ("u", Value::Null, after),
+					}
+				} else if let Some(deleted) = change.remove("delete") {
+					("d", original.unwrap_or(deleted), Value::Null)
+				} else {
+					// Table definitions are schema changes, which have no record envelope
+					continue;
+				};
+				// Fetch the id of the modified record
+				let id = match (&before, &after) {
+					(_, Value::Object(v)) | (Value::Object(v), _) => v.get("id").cloned(),
+					_ => None,
+				};
+				let Some(Value::Thing(id)) = id else {
+					continue;
+				};
+				let envelope = json!({
+					"before": before.into_json(),
+					"after": after.into_json(),
+					"source": {
+						"version": *PKG_VERSION,
+						"connector": "surrealdb",
+						"ns": ns,
+						"db": db,
+						"table": id.tb,
+						"versionstamp": versionstamp.to_string(),
+					},
+					"op": op,
+					"ts_ms": ts_ms as u64,
+				});
+				messages.push(Message {
+					key: Some(serde_json::to_vec(&json!({ "id": id.to_raw() }))?),
+					value: serde_json::to_vec(&envelope)?,
+				});
+			}
+			Ok(messages)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(json: &[u8]) -> Json {
+		serde_json::from_slice(json).unwrap()
+	}
+
+	#[test]
+	fn encode_ndjson() {
+		let changes =
+			surrealdb::sql::value("[{ update: { id: person:one, name: 'one' } }]").unwrap();
+		let res = encode(Format::Ndjson, "test", "test", 65536, changes).unwrap();
+		assert_eq!(res.len(), 1);
+		assert_eq!(res[0].key, None);
+		assert_eq!(
+			parse(&res[0].value),
+			json!({ "versionstamp": 65536, "changes": [{ "update": { "id": "person:one", "name": "one" } }] })
+		);
+	}
+
+	#[test]
+	fn encode_debezium() {
+		let changes = surrealdb::sql::value(
+			"[
+				{ define_table: { name: 'person' } },
+				{ update: { id: person:one, name: 'one' }, original: NONE },
+				{ update: { id: person:one, name: 'two' }, original: { id: person:one, name: 'one' } },
+				{ update: { id: person:two } },
+				{ delete: { id: person:one }, original: { id: person:one, name: 'two' } },
+			]",
+		)
+		.unwrap();
+		let res = encode(Format::Debezium, "test", "test", 65536, changes).unwrap();
+		assert_eq!(res.len(), 4);
+		// Every record is keyed by its id
+		let keys: Vec<Json> = res.iter().map(|m| parse(m.key.as_ref().unwrap())).collect();
+		assert_eq!(keys[0], json!({ "id": "person:one" }));
+		assert_eq!(keys[2], json!({ "id": "person:two" }));
+		let events: Vec<Json> = res.iter().map(|m| parse(&m.value)).collect();
+		assert_eq!(events[0]["op"], "c");
+		assert_eq!(events[0]["before"], Json::Null);
+		assert_eq!(events[0]["after"]["name"], "one");
+		assert_eq!(events[0]["source"]["table"], "person");
+		assert_eq!(events[0]["source"]["versionstamp"], "65536");
+		assert_eq!(events[1]["op"], "u");
+		assert_eq!(events[1]["before"]["name"], "one");
+		assert_eq!(events[1]["after"]["name"], "two");
+		assert_eq!(events[2]["op"], "u");
+		assert_eq!(events[2]["before"], Json::Null);
+		assert_eq!(events[3]["op"], "d");
+		assert_eq!(events[3]["before"]["name"], "two");
+		assert_eq!(events[3]["after"], Json::Null);
+	}
+}
@@ -0,0 +1,287 @@
+//! A minimal producer for brokers which speak the Kafka wire protocol.
+//!
+//! Messages are sent with the version 3 Produce API as a single uncompressed
+//! record batch per change set, and are acknowledged by all in-sync replicas.
+//! The producer does not discover partition leaders, so the broker it connects
+//! to must lead the target partition, as is the case for single-node brokers.
+use super::format::Message;
+use crate::err::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const API_PRODUCE: i16 = 0;
+const API_VERSION: i16 = 3;
+const CLIENT_ID: &str = "surreal";
+// Wait for all in-sync replicas to acknowledge the batch
+const ACKS_ALL: i16 = -1;
+const TIMEOUT_MS: i32 = 30_000;
+
+pub struct Producer {
+	stream: TcpStream,
+	topic: String,
+	partition: i32,
+	correlation: i32,
+}
+
+impl Producer {
+	/// Connects to a broker
+	pub async fn connect(broker: &str, topic: String, partition: i32) -> Result<Self, Error> {
+		let stream = TcpStream::connect(broker).await?;
+		stream.set_nodelay(true)?;
+		Ok(Self {
+			stream,
+			topic,
+			partition,
+			correlation: 0,
+		})
+	}
+
+	/// Produces a batch of messages, and waits for the broker to acknowledge them
+	pub async fn produce(&mut self, messages: &[Message]) -> Result<(), Error> {
+		if messages.is_empty() {
+			return Ok(());
+		}
+		self.correlation = self.correlation.wrapping_add(1);
+		let req = produce_request(self.correlation, &self.topic, self.partition, messages);
+		self.stream.write_all(&req).await?;
+		// Read the response
+		let len = self.stream.read_i32().await?;
+		let mut res = vec![0; len.max(0) as usize];
+		self.stream.read_exact(&mut res).await?;
+		match produce_error(&res, self.correlation) {
+			Some(0) => Ok(()),
+			Some(code) => Err(Error::Kafka(format!("the broker returned error code {code}"))),
+			None => Err(Error::Kafka("the broker returned an invalid response".to_string())),
+		}
+	}
+}
+
+/// Encodes a Produce request, prefixed with its length
+fn produce_request(correlation: i32, topic: &str, partition: i32, messages: &[Message]) -> Vec<u8> {
+	let batch = record_batch(messages);
+	let mut req = Vec::with_capacity(batch.len() + 64);
+	// Request header
+	req.extend_from_slice(&API_PRODUCE.to_be_bytes());
+	req.extend_from_slice(&API_VERSION.to_be_bytes());
+	req.extend_from_slice(&correlation.to_be_bytes());
+	put_string(&mut req, CLIENT_ID);
+	// A null transactional id
+	req.extend_from_slice(&(-1i16).to_be_bytes());
+	req.extend_from_slice(&ACKS_ALL.to_be_bytes());
+	req.extend_from_slice(&TIMEOUT_MS.to_be_bytes());
+	// A single topic, with a single partition
+	req.extend_from_slice(&1i32.to_be_bytes());
+	put_string(&mut req, topic);
+	req.extend_from_slice(&1i32.to_be_bytes());
+	req.extend_from_slice(&partition.to_be_bytes());
+	req.extend_from_slice(&(batch.len() as i32).to_be_bytes());
+	req.extend_from_slice(&batch);
+	// Prefix the request with its length
+	let mut out = Vec::with_capacity(req.len() + 4);
+	out.extend_from_slice(&(req.len() as i32).to_be_bytes());
+	out.extend_from_slice(&req);
+	out
+}
+
+/// Encodes messages as a version 2 record batch
+fn record_batch(messages: &[Message]) -> Vec<u8> {
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as i64;
+	let mut records = Vec::new();
+	for (i, msg) in messages.iter().enumerate() {
+		let mut rec = Vec::with_capacity(msg.value.len() + 16);
+		// Attributes, and timestamp delta
+		rec.push(0);
+		put_varint(&mut rec, 0);
+		put_varint(&mut rec, i as i64);
+		match &msg.key {
+			Some(key) => {
+				put_varint(&mut rec, key.len() as i64);
+				rec.extend_from_slice(key);
+			}
+			None => put_varint(&mut rec, -1),
+		}
+		put_varint(&mut rec, msg.value.len() as i64);
+		rec.extend_from_slice(&msg.value);
+		// No headers
+		put_varint(&mut rec, 0);
+		put_varint(&mut records, rec.len() as i64);
+		records.extend_from_slice(&rec);
+	}
+	// The part of the batch which is covered by the checksum
+	let mut body = Vec::with_capacity(records.len() + 40);
+	body.extend_from_slice(&0i16.to_be_bytes());
+	body.extend_from_slice(&(messages.len() as i32 - 1).to_be_bytes());
+	body.extend_from_slice(&now.to_be_bytes());
+	body.extend_from_slice(&now.to_be_bytes());
+	// No producer id, epoch, or base sequence
+	body.extend_from_slice(&(-1i64).to_be_bytes());
+	body.extend_from_slice(&(-1i16).to_be_bytes());
+	body.extend_from_slice(&(-1i32).to_be_bytes());
+	body.extend_from_slice(&(messages.len() as i32).to_be_bytes());
+	body.extend_from_slice(&records);
+	// The batch header
+	let mut batch = Vec::with_capacity(body.len() + 21);
+	batch.extend_from_slice(&0i64.to_be_bytes());
+	batch.extend_from_slice(&((body.len() + 9) as i32).to_be_bytes());
+	batch.extend_from_slice(&(-1i32).to_be_bytes());
+	batch.push(2);
+	batch.extend_from_slice(&crc32c(&body).to_be_bytes());
+	batch.extend_from_slice(&body);
+	batch
+}
+
+/// Returns the error code of the first partition in a Produce response
+fn produce_error(res: &[u8], correlation: i32) -> Option<i16> {
+	let mut pos = 0;
+	let mut take = |n: usize| {
+		let v = res.get(pos..pos + n);
+		pos += n;
+		v
+	};
+	if i32::from_be_bytes(take(4)?.try_into().ok()?) != correlation {
+		return None;
+	}
+	// The number of topics, and the topic name
+	take(4)?;
+	let len = i16::from_be_bytes(take(2)?.try_into().ok()?);
+	take(len.max(0) as usize)?;
+	// The number of partitions, the partition, and its error code
+	take(4)?;
+	take(4)?;
+	Some(i16::from_be_bytes(take(2)?.try_into().ok()?))
+}
+
+fn put_string(buf: &mut Vec<u8>, s: &str) {
+	buf.extend_from_slice(&(s.len() as i16).to_be_bytes());
+	buf.extend_from_slice(s.as_bytes());
+}
+
+/// Writes a zigzag encoded variable length integer
+fn put_varint(buf: &mut Vec<u8>, v: i64) {
+	let mut v = ((v << 1) ^ (v >> 63)) as u64;
+	while v >= 0x80 {
+		buf.push(v as u8 | 0x80);
+		v >>= 7;
+	}
+	buf.push(v as u8);
+}
+
+/// The lookup table for the Castagnoli CRC used by record batches
+const CRC32C: [u32; 256] = {
+	let mut table = [0u32; 256];
+	let mut i = 0;
+	while i < 256 {
+		let mut crc = i as u32;
+		let mut j = 0;
+		while j < 8 {
+			crc = if crc & 1 == 1 {
+				(crc >> 1) ^ 0x82F6_3B78
+			} else {
+				crc >> 1
+			};
+			j += 1;
+		}
+		table[i] = crc;
+		i += 1;
+	}
+	table
+};
+
+fn crc32c(data: &[u8]) -> u32 {
+	!data.iter().fold(!0u32, |crc, b| CRC32C[((crc ^ *b as u32) & 0xFF) as usize] ^ (crc >> 8))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tokio::net::TcpListener;
+
+	#[test]
+	fn checksum() {
+		assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+	}
+
+	#[test]
+	fn varint() {
+		let enc = |v| {
+			let mut buf = vec![];
+			put_varint(&mut buf, v);
+			buf
+		};
+		assert_eq!(enc(0), vec![0x00]);
+		assert_eq!(enc(-1), vec![0x01]);
+		assert_eq!(enc(1), vec![0x02]);
+		assert_eq!(enc(300), vec![0xD8, 0x04]);
+	}
+
+	// Accepts a single request, and replies with the given error code
+	async fn broker(code: i16) -> (String, tokio::task::JoinHandle<Vec<u8>>) {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let addr = listener.local_addr().unwrap().to_string();
+		let handle = tokio::spawn(async move {
+			let (mut sock, _) = listener.accept().await.unwrap();
+			let len = sock.read_i32().await.unwrap();
+			let mut req = vec![0; len as usize];
+			sock.read_exact(&mut req).await.unwrap();
+			let mut res = vec![];
+			res.extend_from_slice(&req[4..8]);
+			res.extend_from_slice(&1i32.to_be_bytes());
+			put_string(&mut res, "changes");
+			res.extend_from_slice(&1i32.to_be_bytes());
+			res.extend_from_slice(&0i32.to_be_bytes());
+			res.extend_from_slice(&code.to_be_bytes());
+			res.extend_from_slice(&0i64.to_be_bytes());
+			res.extend_from_slice(&(-1i64).to_be_bytes());
+			res.extend_from_slice(&0i32.to_be_bytes());
+			sock.write_i32(res.len() as i32).await.unwrap();
+			sock.write_all(&res).await.unwrap();
+			req
+		});
+		(addr, handle)
+	}
+
+	#[tokio::test]
+	async fn produce() {
+		let (addr, handle) = broker(0).await;
+		let mut producer = Producer::connect(&addr, "changes".to_string(), 0).await.unwrap();
+		let messages = vec![
+			Message {
+				key: Some(b"one".to_vec()),
+				value: b"first".to_vec(),
+			},
+			Message {
+				key: None,
+				value: b"second".to_vec(),
+			},
+		];
+		producer.produce(&messages).await.unwrap();
+		let req = handle.await.unwrap();
+		// The request header
+		assert_eq!(&req[0..2], &API_PRODUCE.to_be_bytes());
+		assert_eq!(&req[2..4], &API_VERSION.to_be_bytes());
+		assert_eq!(&req[4..8], &1i32.to_be_bytes());
+		// The record batch is at the end of the request
+		let batch = record_batch(&messages);
+		let (head, tail) = req.split_at(req.len() - batch.len());
+		assert!(head.windows(7).any(|w| w == b"changes"));
+		// The timestamps may differ, but the records are the same
+		assert_eq!(tail[16], 2);
+		assert_eq!(&tail[tail.len() - 20..], &batch[batch.len() - 20..]);
+		// The checksum covers the rest of the batch
+		let crc = u32::from_be_bytes(tail[17..21].try_into().unwrap());
+		assert_eq!(crc, crc32c(&tail[21..]));
+	}
+
+	#[tokio::test]
+	async fn produce_error_code() {
+		let (addr, _handle) = broker(3).await;
+		let mut producer = Producer::connect(&addr, "changes".to_string(), 0).await.unwrap();
+		let messages = vec![Message {
+			key: None,
+			value: b"first".to_vec(),
+		}];
+		let err = producer.produce(&messages).await.unwrap_err();
+		assert!(matches!(err, Error::Kafka(_)), "{err}");
+	}
+}
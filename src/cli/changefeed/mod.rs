@@ -0,0 +1,20 @@
+mod format;
+mod kafka;
+mod sink;
+mod tail;
+
+use self::tail::TailCommandArguments;
+use crate::err::Error;
+use clap::Subcommand;
+
+#[derive(Debug, Subcommand)]
+pub enum ChangefeedCommand {
+	#[command(about = "Stream the changefeed of an existing database to stdout, files or Kafka")]
+	Tail(TailCommandArguments),
+}
+
+pub async fn init(command: ChangefeedCommand) -> Result<(), Error> {
+	match command {
+		ChangefeedCommand::Tail(args) => tail::init(args).await,
+	}
+}
@@ -0,0 +1,140 @@
+use super::format::Message;
+use super::kafka::Producer;
+use crate::err::Error;
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, File};
+use tokio::io::{self, AsyncWriteExt, Stdout};
+
+/// The destination of the exported change sets
+pub enum Sink {
+	/// Writes one message per line to standard output
+	Stdout(Stdout),
+	/// Writes one message per line to files which are rolled once they reach a size
+	Files(RollingFiles),
+	/// Produces one record per message to a Kafka topic
+	Kafka(Producer),
+}
+
+impl Sink {
+	/// Writes the messages of a change set, returning once they are durably stored
+	pub async fn write(&mut self, versionstamp: u128, messages: &[Message]) -> Result<(), Error> {
+		match self {
+			Sink::Stdout(stdout) => {
+				for msg in messages {
+					stdout.write_all(&msg.value).await?;
+					stdout.write_all(b"\n").await?;
+				}
+				stdout.flush().await?;
+			}
+			Sink::Files(files) => files.write(versionstamp, messages).await?,
+			Sink::Kafka(producer) => producer.produce(messages).await?,
+		}
+		Ok(())
+	}
+}
+
+/// Writes change sets to files in a directory
+///
+/// Each file is named after the first versionstamp it contains, so that the
+/// files sort in the order in which the changes were committed. Change sets
+/// are never split across files.
+pub struct RollingFiles {
+	dir: PathBuf,
+	max_size: u64,
+	current: Option<(File, u64)>,
+}
+
+impl RollingFiles {
+	pub async fn new(dir: PathBuf, max_size: u64) -> Result<Self, Error> {
+		fs::create_dir_all(&dir).await?;
+		Ok(Self {
+			dir,
+			max_size,
+			current: None,
+		})
+	}
+
+	async fn write(&mut self, versionstamp: u128, messages: &[Message]) -> Result<(), Error> {
+		// Start a new file if needed
+		let (file, size) = match &mut self.current {
+			Some(current) => current,
+			None => {
+				let path = self.dir.join(format!("changes-{versionstamp:039}.ndjson"));
+				let file = File::options().create(true).append(true).open(path).await?;
+				let size = file.metadata().await?.len();
+				self.current.insert((file, size))
+			}
+		};
+		for msg in messages {
+			file.write_all(&msg.value).await?;
+			file.write_all(b"\n").await?;
+			*size += msg.value.len() as u64 + 1;
+		}
+		file.sync_data().await?;
+		// Roll the file once it is full
+		if *size >= self.max_size {
+			self.current = None;
+		}
+		Ok(())
+	}
+}
+
+/// Returns the last versionstamp which was stored in a checkpoint file
+pub async fn read_checkpoint(path: &Path) -> Result<Option<u128>, Error> {
+	match fs::read_to_string(path).await {
+		Ok(v) => match v.trim().parse() {
+			Ok(v) => Ok(Some(v)),
+			Err(_) => Err(Error::Other(format!("Invalid checkpoint file: {}", path.display()))),
+		},
+		Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// Atomically stores the last versionstamp which was exported
+pub async fn write_checkpoint(path: &Path, versionstamp: u128) -> Result<(), Error> {
+	let tmp = path.with_extension("tmp");
+	fs::write(&tmp, format!("{versionstamp}\n")).await?;
+	fs::rename(&tmp, path).await?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn message(value: &str) -> Message {
+		Message {
+			key: None,
+			value: value.as_bytes().to_vec(),
+		}
+	}
+
+	#[tokio::test]
+	async fn rolling_files() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut files = RollingFiles::new(dir.path().to_path_buf(), 10).await.unwrap();
+		files.write(1, &[message("one")]).await.unwrap();
+		files.write(2, &[message("two"), message("three")]).await.unwrap();
+		files.write(3, &[message("four")]).await.unwrap();
+		let mut names: Vec<String> = std::fs::read_dir(dir.path())
+			.unwrap()
+			.map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+			.collect();
+		names.sort();
+		assert_eq!(names.len(), 2);
+		assert_eq!(names[0], format!("changes-{:039}.ndjson", 1));
+		assert_eq!(names[1], format!("changes-{:039}.ndjson", 3));
+		let first = std::fs::read_to_string(dir.path().join(&names[0])).unwrap();
+		assert_eq!(first, "one\ntwo\nthree\n");
+	}
+
+	#[tokio::test]
+	async fn checkpoint() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("checkpoint");
+		assert_eq!(read_checkpoint(&path).await.unwrap(), None);
+		write_checkpoint(&path, 65536).await.unwrap();
+		assert_eq!(read_checkpoint(&path).await.unwrap(), Some(65536));
+	}
+}
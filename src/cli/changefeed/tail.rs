@@ -0,0 +1,174 @@
+use super::format::{self, Format};
+use super::kafka::Producer;
+use super::sink::{self, RollingFiles, Sink};
+use crate::cli::abstraction::auth::{CredentialsBuilder, CredentialsLevel};
+use crate::cli::abstraction::{
+	AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::Args;
+use futures_util::StreamExt;
+use std::path::PathBuf;
+use surrealdb::engine::any::{connect, IntoEndpoint};
+use tokio::io;
+
+#[derive(Args, Debug)]
+pub struct OutputArguments {
+	#[arg(help = "The format of the exported changes")]
+	#[arg(long = "format", value_enum, default_value_t = Format::Ndjson)]
+	format: Format,
+	#[arg(help = "Directory in which to write rolling files, instead of writing to stdout")]
+	#[arg(long = "output", conflicts_with = "kafka_broker")]
+	output: Option<PathBuf>,
+	#[arg(help = "The size in bytes after which a new file is started")]
+	#[arg(long = "max-file-size", default_value_t = 64 * 1024 * 1024, requires = "output")]
+	max_file_size: u64,
+	#[arg(
+		help = "File in which to store the last exported versionstamp, to resume from on restart"
+	)]
+	#[arg(long = "checkpoint")]
+	checkpoint: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct KafkaArguments {
+	#[arg(
+		help = "Address of a Kafka-compatible broker to produce the changes to, instead of writing to stdout\nThe broker must lead the selected partition"
+	)]
+	#[arg(long = "kafka-broker", requires = "kafka_topic")]
+	kafka_broker: Option<String>,
+	#[arg(help = "The topic to produce the changes to")]
+	#[arg(long = "kafka-topic", requires = "kafka_broker")]
+	kafka_topic: Option<String>,
+	#[arg(help = "The partition to produce the changes to")]
+	#[arg(long = "kafka-partition", default_value_t = 0)]
+	kafka_partition: i32,
+}
+
+#[derive(Args, Debug)]
+pub struct TailCommandArguments {
+	#[arg(help = "Only export the changes made to a specific table")]
+	#[arg(long = "table", visible_alias = "tb")]
+	table: Option<String>,
+	#[arg(help = "Export the changes committed at or after this versionstamp")]
+	#[arg(long = "since")]
+	since: Option<u128>,
+
+	#[command(flatten)]
+	out: OutputArguments,
+	#[command(flatten)]
+	kafka: KafkaArguments,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+pub async fn init(
+	TailCommandArguments {
+		table,
+		since,
+		out: OutputArguments {
+			format,
+			output,
+			max_file_size,
+			checkpoint,
+		},
+		kafka: KafkaArguments {
+			kafka_broker,
+			kafka_topic,
+			kafka_partition,
+		},
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		auth: AuthArguments {
+			username,
+			password,
+			auth_level,
+		},
+		sel: DatabaseSelectionArguments {
+			namespace,
+			database,
+		},
+	}: TailCommandArguments,
+) -> Result<(), Error> {
+	// Initialize opentelemetry and logging
+	crate::telemetry::builder().with_log_level("error").init();
+
+	// Resume right after the last exported changes, unless told otherwise
+	let since = match (since, &checkpoint) {
+		(Some(since), _) => since,
+		(None, Some(path)) => sink::read_checkpoint(path).await?.map(|v| v + 1).unwrap_or(0),
+		(None, None) => 0,
+	};
+
+	// Open the destination before connecting, so that misconfigurations fail early
+	let mut sink = match (output, kafka_broker, kafka_topic) {
+		(Some(dir), _, _) => Sink::Files(RollingFiles::new(dir, max_file_size).await?),
+		(None, Some(broker), Some(topic)) => {
+			debug!("Connecting to the Kafka broker at {broker}");
+			Sink::Kafka(Producer::connect(&broker, topic, kafka_partition).await?)
+		}
+		_ => Sink::Stdout(io::stdout()),
+	};
+
+	// If username and password are specified, and we are connecting to a remote SurrealDB server, then we need to authenticate.
+	// If we are connecting directly to a datastore (i.e. file://local.db or tikv://...), then we don't need to authenticate because we use an embedded (local) SurrealDB instance with auth disabled.
+	let client = if username.is_some()
+		&& password.is_some()
+		&& !endpoint.clone().into_endpoint()?.parse_kind()?.is_local()
+	{
+		debug!("Connecting to the database engine with authentication");
+		let creds = CredentialsBuilder::default()
+			.with_username(username.as_deref())
+			.with_password(password.as_deref())
+			.with_namespace(namespace.as_str())
+			.with_database(database.as_str());
+
+		let client = connect(endpoint).await?;
+
+		debug!("Signing in to the database engine at '{:?}' level", auth_level);
+		match auth_level {
+			CredentialsLevel::Root => client.signin(creds.root()?).await?,
+			CredentialsLevel::Namespace => client.signin(creds.namespace()?).await?,
+			CredentialsLevel::Database => client.signin(creds.database()?).await?,
+		};
+
+		client
+	} else {
+		debug!("Connecting to the database engine without authentication");
+		connect(endpoint).await?
+	};
+
+	// Use the specified namespace / database
+	client.use_ns(&namespace).use_db(&database).await?;
+	// Start streaming the changefeed
+	debug!("Streaming changes from versionstamp {since}");
+	let mut changes = client.changes().since(since);
+	if let Some(table) = table {
+		changes = changes.table(table);
+	}
+	let mut stream = changes.await?;
+	loop {
+		let set = tokio::select! {
+			set = stream.next() => match set {
+				Some(set) => set,
+				None => break,
+			},
+			_ = tokio::signal::ctrl_c() => break,
+		};
+		let messages =
+			format::encode(format, &namespace, &database, set.versionstamp, set.changes)?;
+		sink.write(set.versionstamp, &messages).await?;
+		// Only record the versionstamp once the changes are stored
+		if let Some(path) = &checkpoint {
+			sink::write_checkpoint(path, set.versionstamp).await?;
+		}
+	}
+	info!("The changefeed stream was closed");
+	// Everything OK
+	Ok(())
+}
@@ -15,5 +15,6 @@ pub struct Config {
 	pub pass: Option<String>,
 	pub crt: Option<PathBuf>,
 	pub key: Option<PathBuf>,
+	pub client_ca: Option<PathBuf>,
 	pub tick_interval: Duration,
 }
@@ -0,0 +1,77 @@
+use crate::err::Error;
+use clap::Args;
+use std::path::PathBuf;
+use surrealdb::engine::any::IntoEndpoint;
+use surrealdb::kvs::cipher::Cipher;
+use surrealdb::kvs::Datastore;
+use surrealdb::opt::Endpoint;
+
+#[derive(Args, Debug)]
+pub struct EncryptCommandArguments {
+	#[arg(help = "Path to the existing datastore which is copied")]
+	#[arg(value_parser = super::validator::path_valid)]
+	from: String,
+	#[arg(help = "Path to the new datastore which is encrypted")]
+	#[arg(value_parser = super::validator::path_valid)]
+	into: String,
+	#[arg(
+		help = "The path of a file with the base64 encoded 256-bit key which encrypts the datastore"
+	)]
+	#[arg(env = "SURREAL_ENCRYPTION_KEY_FILE", long = "encryption-key-file")]
+	#[arg(value_parser = super::validator::file_exists)]
+	encryption_key_file: PathBuf,
+	#[arg(help = "Whether to encrypt the keys of the datastore, as well as the values")]
+	#[arg(env = "SURREAL_ENCRYPT_KEYS", long = "encrypt-keys")]
+	#[arg(default_value_t = false)]
+	encrypt_keys: bool,
+	#[arg(
+		help = "The path of a file with the base64 encoded 256-bit key which the existing datastore is encrypted with"
+	)]
+	#[arg(env = "SURREAL_FROM_ENCRYPTION_KEY_FILE", long = "from-encryption-key-file")]
+	#[arg(value_parser = super::validator::file_exists)]
+	from_encryption_key_file: Option<PathBuf>,
+	#[arg(
+		help = "Whether the keys of the existing datastore are encrypted, as well as the values"
+	)]
+	#[arg(env = "SURREAL_FROM_ENCRYPT_KEYS", long = "from-encrypt-keys")]
+	#[arg(requires = "from_encryption_key_file")]
+	#[arg(default_value_t = false)]
+	from_encrypt_keys: bool,
+}
+
+pub async fn init(
+	EncryptCommandArguments {
+		from,
+		into,
+		encryption_key_file,
+		encrypt_keys,
+		from_encryption_key_file,
+		from_encrypt_keys,
+	}: EncryptCommandArguments,
+) -> Result<(), Error> {
+	// Initialize opentelemetry and logging
+	crate::telemetry::builder().with_log_level("info").init();
+	// Load the key which encrypts the new datastore
+	let cipher = Cipher::open(encryption_key_file)?.with_key_encryption(encrypt_keys);
+	// Open both of the datastores, decrypting the existing datastore if it is encrypted
+	let from = Datastore::new(&path(from.into_endpoint()?)).await?;
+	let from = match from_encryption_key_file {
+		Some(file) => {
+			from.with_encryption(Cipher::open(file)?.with_key_encryption(from_encrypt_keys))
+		}
+		None => from,
+	};
+	let into = Datastore::new(&path(into.into_endpoint()?)).await?.with_encryption(cipher);
+	// Copy every key into the encrypted datastore
+	let count = from.copy_into(&into).await?;
+	info!("Copied {count} keys into the encrypted datastore");
+	Ok(())
+}
+
+/// Gets the path which the datastore of an endpoint is opened with
+fn path(endpoint: Endpoint) -> String {
+	match endpoint.path.is_empty() {
+		true => endpoint.url.to_string(),
+		false => endpoint.path,
+	}
+}
@@ -1,6 +1,8 @@
 pub(crate) mod abstraction;
 mod backup;
+mod changefeed;
 mod config;
+mod encrypt;
 mod export;
 mod import;
 mod isready;
@@ -15,8 +17,10 @@ mod version;
 use crate::cnf::LOGO;
 use crate::env::RELEASE;
 use backup::BackupCommandArguments;
+use changefeed::ChangefeedCommand;
 use clap::{Parser, Subcommand};
 pub use config::CF;
+use encrypt::EncryptCommandArguments;
 use export::ExportCommandArguments;
 use import::ImportCommandArguments;
 use isready::IsReadyCommandArguments;
@@ -69,6 +73,8 @@ enum Commands {
 	Sql(SqlCommandArguments),
 	#[command(subcommand, about = "Manage SurrealML models within an existing database")]
 	Ml(MlCommand),
+	#[command(subcommand, about = "Export the changefeeds of an existing database")]
+	Changefeed(ChangefeedCommand),
 	#[command(
 		about = "Check if the SurrealDB server is ready to accept connections",
 		visible_alias = "isready"
@@ -76,6 +82,8 @@ enum Commands {
 	IsReady(IsReadyCommandArguments),
 	#[command(about = "Validate SurrealQL query files")]
 	Validate(ValidateCommandArguments),
+	#[command(about = "Copy an existing datastore into a new encrypted datastore")]
+	Encrypt(EncryptCommandArguments),
 }
 
 pub async fn init() -> ExitCode {
@@ -89,8 +97,10 @@ pub async fn init() -> ExitCode {
 		Commands::Upgrade(args) => upgrade::init(args).await,
 		Commands::Sql(args) => sql::init(args).await,
 		Commands::Ml(args) => ml::init(args).await,
+		Commands::Changefeed(args) => changefeed::init(args).await,
 		Commands::IsReady(args) => isready::init(args).await,
 		Commands::Validate(args) => validate::init(args).await,
+		Commands::Encrypt(args) => encrypt::init(args).await,
 	};
 	if let Err(e) = output {
 		error!("{}", e);
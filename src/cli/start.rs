@@ -128,6 +128,9 @@ struct StartCommandWebTlsOptions {
 	#[arg(help = "Path to the private key file for encrypted client connections")]
 	#[arg(env = "SURREAL_WEB_KEY", long = "web-key", value_parser = super::validator::file_exists)]
 	web_key: Option<PathBuf>,
+	#[arg(help = "Path to the CA file used to verify the certificates of clients")]
+	#[arg(env = "SURREAL_CLIENT_CA", long = "client-ca", value_parser = super::validator::file_exists)]
+	client_ca: Option<PathBuf>,
 }
 
 pub async fn init(
@@ -173,6 +176,7 @@ pub async fn init(
 		tick_interval,
 		crt: web.as_ref().and_then(|x| x.web_crt.clone()),
 		key: web.as_ref().and_then(|x| x.web_key.clone()),
+		client_ca: web.as_ref().and_then(|x| x.client_ca.clone()),
 	});
 	// This is the cancellation token propagated down to
 	// all the async functions that needs to be stopped gracefully.
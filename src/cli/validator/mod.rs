@@ -6,6 +6,7 @@ use std::{
 };
 
 use surrealdb::dbs::capabilities::{FuncTarget, NetTarget, Targets};
+use surrealdb::kvs::audit::AuditClass;
 
 pub(crate) mod parser;
 
@@ -103,6 +104,20 @@ pub(crate) fn func_targets(value: &str) -> Result<Targets<FuncTarget>, String> {
 	Ok(Targets::Some(result))
 }
 
+pub(crate) fn audit_classes(value: &str) -> Result<HashSet<AuditClass>, String> {
+	if ["*", ""].contains(&value) {
+		return Ok(AuditClass::ALL.into_iter().collect());
+	}
+
+	let mut result = HashSet::new();
+
+	for class in value.split(',').filter(|s| !s.is_empty()) {
+		result.insert(AuditClass::from_str(class.trim()).map_err(|e| e.to_string())?);
+	}
+
+	Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -46,6 +46,16 @@ pub static WEBSOCKET_MAX_CONCURRENT_REQUESTS: Lazy<usize> = Lazy::new(|| {
 		.unwrap_or(24)
 });
 
+/// Specifies the frequency with which changefeed subscriptions check for new changes
+pub const WEBSOCKET_CHANGEFEED_FREQUENCY: Duration = Duration::from_millis(250);
+
+/// How many changefeed entries are read at once for each subscription (defaults to 100)
+pub static WEBSOCKET_CHANGEFEED_BATCH_SIZE: Lazy<u32> = Lazy::new(|| {
+	option_env!("SURREAL_WEBSOCKET_CHANGEFEED_BATCH_SIZE")
+		.and_then(|s| s.parse::<u32>().ok())
+		.unwrap_or(100)
+});
+
 /// What is the runtime thread memory stack size (defaults to 10MiB)
 pub static RUNTIME_STACK_SIZE: Lazy<usize> = Lazy::new(|| {
 	// Stack frames are generally larger in debug mode.
@@ -1,9 +1,16 @@
 use crate::cli::CF;
 use crate::err::Error;
 use clap::Args;
+use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::OnceLock;
 use std::time::Duration;
 use surrealdb::dbs::capabilities::{Capabilities, FuncTarget, NetTarget, Targets};
+use surrealdb::iam::password::PasswordPolicy;
+use surrealdb::iam::throttle::LoginPolicy;
+use surrealdb::kvs::audit::{AuditClass, AuditLog};
+use surrealdb::kvs::cipher::Cipher;
+use surrealdb::kvs::keyring::Keyring;
 use surrealdb::kvs::Datastore;
 
 pub static DB: OnceLock<Datastore> = OnceLock::new();
@@ -34,6 +41,87 @@ pub struct StartCommandDbsOptions {
 	#[arg(env = "SURREAL_AUTH_LEVEL_ENABLED", long = "auth-level-enabled")]
 	#[arg(default_value_t = false)]
 	auth_level_enabled: bool,
+	#[arg(
+		help = "The number of signin attempts which a user can make within the login window, or 0 for no limit",
+		help_heading = "Authentication"
+	)]
+	#[arg(env = "SURREAL_LOGIN_MAX_ATTEMPTS", long = "login-max-attempts")]
+	#[arg(default_value_t = 0)]
+	login_max_attempts: u32,
+	#[arg(
+		help = "The period of time over which the signin attempts of a user are counted",
+		help_heading = "Authentication"
+	)]
+	#[arg(env = "SURREAL_LOGIN_WINDOW", long = "login-window")]
+	#[arg(default_value = "1m")]
+	#[arg(value_parser = super::cli::validator::duration)]
+	login_window: Duration,
+	#[arg(
+		help = "The number of consecutive failed signin attempts after which a user is locked out, or 0 for never",
+		help_heading = "Authentication"
+	)]
+	#[arg(env = "SURREAL_LOGIN_MAX_FAILURES", long = "login-max-failures")]
+	#[arg(default_value_t = 0)]
+	login_max_failures: u32,
+	#[arg(
+		help = "The period of time for which a user is locked out",
+		help_heading = "Authentication"
+	)]
+	#[arg(env = "SURREAL_LOGIN_LOCKOUT", long = "login-lockout")]
+	#[arg(default_value = "15m")]
+	#[arg(value_parser = super::cli::validator::duration)]
+	login_lockout: Duration,
+	#[arg(
+		help = "The minimum number of characters in the password of a system user",
+		help_heading = "Authentication"
+	)]
+	#[arg(env = "SURREAL_PASSWORD_MIN_LENGTH", long = "password-min-length")]
+	#[arg(default_value_t = 0)]
+	password_min_length: u32,
+	#[arg(
+		help = "The minimum number of classes of characters (lowercase, uppercase, digits and symbols) in the password of a system user",
+		help_heading = "Authentication"
+	)]
+	#[arg(env = "SURREAL_PASSWORD_MIN_CLASSES", long = "password-min-classes")]
+	#[arg(default_value_t = 0, value_parser = clap::value_parser!(u8).range(0..=4))]
+	password_min_classes: u8,
+	#[arg(
+		help = "The period of time after which the password of a system user needs to be changed",
+		help_heading = "Authentication"
+	)]
+	#[arg(env = "SURREAL_PASSWORD_EXPIRY", long = "password-expiry")]
+	#[arg(value_parser = super::cli::validator::duration)]
+	password_expiry: Option<Duration>,
+	#[arg(help = "The path of a file to record audited events to", help_heading = "Audit")]
+	#[arg(env = "SURREAL_AUDIT_LOG", long = "audit-log")]
+	audit_log: Option<PathBuf>,
+	#[arg(
+		help = "The classes of events to record in the audit log (signin, signup, token, ddl, user)",
+		help_heading = "Audit"
+	)]
+	#[arg(env = "SURREAL_AUDIT_EVENTS", long = "audit-events", requires = "audit_log")]
+	#[arg(default_value = "*")]
+	#[arg(value_parser = super::cli::validator::audit_classes)]
+	audit_events: HashSet<AuditClass>,
+	#[arg(
+		help = "The path of a JSON keyfile with the keys which encrypt the values of encrypted fields",
+		help_heading = "Encryption"
+	)]
+	#[arg(env = "SURREAL_KEYFILE", long = "keyfile")]
+	keyfile: Option<PathBuf>,
+	#[arg(
+		help = "The path of a file with the base64 encoded 256-bit key which encrypts the datastore",
+		help_heading = "Encryption"
+	)]
+	#[arg(env = "SURREAL_ENCRYPTION_KEY_FILE", long = "encryption-key-file")]
+	encryption_key_file: Option<PathBuf>,
+	#[arg(
+		help = "Whether to encrypt the keys of the datastore, as well as the values",
+		help_heading = "Encryption"
+	)]
+	#[arg(env = "SURREAL_ENCRYPT_KEYS", long = "encrypt-keys", requires = "encryption_key_file")]
+	#[arg(default_value_t = false)]
+	encrypt_keys: bool,
 	#[command(flatten)]
 	#[command(next_help_heading = "Capabilities")]
 	caps: DbsCapabilities,
@@ -213,6 +301,18 @@ pub async fn init(
 		auth_enabled,
 		// TODO(gguillemas): Remove this field once the legacy authentication is deprecated in v2.0.0
 		auth_level_enabled,
+		login_max_attempts,
+		login_window,
+		login_max_failures,
+		login_lockout,
+		password_min_length,
+		password_min_classes,
+		password_expiry,
+		audit_log,
+		audit_events,
+		keyfile,
+		encryption_key_file,
+		encrypt_keys,
 		caps,
 	}: StartCommandDbsOptions,
 ) -> Result<(), Error> {
@@ -243,6 +343,21 @@ pub async fn init(
 	let caps = caps.into();
 	debug!("Server capabilities: {caps}");
 
+	let login = LoginPolicy {
+		max_attempts: login_max_attempts,
+		window: login_window,
+		max_failures: login_max_failures,
+		lockout: login_lockout,
+	};
+	debug!("Login policy: {login:?}");
+
+	let password = PasswordPolicy {
+		min_length: password_min_length,
+		min_classes: password_min_classes,
+		expiry: password_expiry,
+	};
+	debug!("Password policy: {password:?}");
+
 	// Parse and setup the desired kv datastore
 	let dbs = Datastore::new(&opt.path)
 		.await?
@@ -252,7 +367,36 @@ pub async fn init(
 		.with_transaction_timeout(transaction_timeout)
 		.with_auth_enabled(auth_enabled)
 		.with_auth_level_enabled(auth_level_enabled)
-		.with_capabilities(caps);
+		.with_capabilities(caps)
+		.with_login_policy(login)
+		.with_password_policy(password);
+
+	// Open the audit log
+	let dbs = match audit_log {
+		Some(path) => {
+			info!("Recording audited events to {}", path.display());
+			dbs.with_audit_log(AuditLog::open(path, audit_events)?)
+		}
+		None => dbs,
+	};
+
+	// Load the keys of encrypted fields
+	let dbs = match keyfile {
+		Some(path) => {
+			info!("Loading the keys of encrypted fields from {}", path.display());
+			dbs.with_keyring(Keyring::open(path)?)
+		}
+		None => dbs,
+	};
+
+	// Load the key which encrypts the datastore
+	let dbs = match encryption_key_file {
+		Some(path) => {
+			info!("Encrypting the datastore with the key from {}", path.display());
+			dbs.with_encryption(Cipher::open(path)?.with_key_encryption(encrypt_keys))
+		}
+		None => dbs,
+	};
 
 	dbs.bootstrap().await?;
 
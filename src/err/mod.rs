@@ -64,6 +64,9 @@ pub enum Error {
 	#[error("There was an error with auth: {0}")]
 	Auth(#[from] SurrealAuthError),
 
+	#[error("There was an error with the Kafka broker: {0}")]
+	Kafka(String),
+
 	#[error("There was an error with the node agent")]
 	NodeAgent,
 
@@ -9,6 +9,10 @@ use axum::{
 use futures_util::future::BoxFuture;
 use http::{request::Parts, StatusCode};
 use hyper::{Request, Response};
+#[cfg(feature = "sql2")]
+use std::sync::Arc;
+#[cfg(feature = "sql2")]
+use surrealdb::iam::{certificate::Certificate, verify::certificate};
 use surrealdb::{
 	dbs::Session,
 	iam::verify::{basic, basic_legacy, token},
@@ -17,6 +21,8 @@ use tower_http::auth::AsyncAuthorizeRequest;
 
 use crate::{dbs::DB, err::Error};
 
+#[cfg(feature = "sql2")]
+use super::headers::SurrealAuthScope;
 use super::{
 	client_ip::ExtractClientIP,
 	headers::{
@@ -29,7 +35,8 @@ use super::{
 
 ///
 /// SurrealAuth is a tower layer that implements the AsyncAuthorizeRequest trait.
-/// It is used to authorize requests to SurrealDB using Basic or Token authentication.
+/// It is used to authorize requests to SurrealDB using Basic, Token, or client certificate
+/// authentication.
 ///
 /// It has to be used in conjunction with the tower_http::auth::RequireAuthorizationLayer layer:
 ///
@@ -124,6 +131,14 @@ async fn check_auth(parts: &mut Parts) -> Result<Session, Error> {
 	let auth_db = parse_typed_header::<SurrealAuthDatabase>(
 		parts.extract::<TypedHeader<SurrealAuthDatabase>>().await,
 	)?;
+	#[cfg(feature = "sql2")]
+	let auth_sc = parse_typed_header::<SurrealAuthScope>(
+		parts.extract::<TypedHeader<SurrealAuthScope>>().await,
+	)?;
+
+	// Extract the verified client certificate of a mutual TLS connection
+	#[cfg(feature = "sql2")]
+	let cert = parts.extensions.get::<Option<Arc<Certificate>>>().cloned().flatten();
 
 	let Extension(state) = parts.extract::<Extension<AppState>>().await.map_err(|err| {
 		tracing::error!("Error extracting the app state: {:?}", err);
@@ -138,7 +153,8 @@ async fn check_auth(parts: &mut Parts) -> Result<Session, Error> {
 	let mut session = Session { ip, or, id, ns, db, ..Default::default() };
 
 	// If Basic authentication data was supplied
-	if let Ok(au) = parts.extract::<TypedHeader<Authorization<Basic>>>().await {
+	let basic_auth = parts.extract::<TypedHeader<Authorization<Basic>>>().await;
+	if let Ok(au) = &basic_auth {
 		if kvs.is_auth_level_enabled() {
 			basic(
 				kvs,
@@ -155,9 +171,33 @@ async fn check_auth(parts: &mut Parts) -> Result<Session, Error> {
 	};
 
 	// If Token authentication data was supplied
-	if let Ok(au) = parts.extract::<TypedHeader<Authorization<Bearer>>>().await {
+	let bearer_auth = parts.extract::<TypedHeader<Authorization<Bearer>>>().await;
+	if let Ok(au) = &bearer_auth {
 		token(kvs, &mut session, au.token()).await?;
 	};
 
+	// If only a client certificate was supplied
+	#[cfg(feature = "sql2")]
+	if let (Err(_), Err(_), Some(cert)) = (&basic_auth, &bearer_auth, &cert) {
+		// A certificate which does not identify a user or record is not an error,
+		// as the client can still authenticate with other credentials
+		let res = certificate(
+			kvs,
+			&mut session,
+			cert,
+			auth_ns.as_deref(),
+			auth_db.as_deref(),
+			auth_sc.as_deref(),
+		)
+		.await;
+		if let Err(err) = res {
+			tracing::debug!(
+				"The client certificate '{}' was not authenticated: {}",
+				cert.subject,
+				err
+			);
+		}
+	};
+
 	Ok(session)
 }
@@ -0,0 +1,52 @@
+use axum::headers;
+use axum::headers::Header;
+use http::HeaderName;
+use http::HeaderValue;
+use surrealdb::headers::AUTH_SC;
+
+/// Typed header implementation for the `surreal-auth-sc` header.
+/// It's used to specify the scope to use for the client certificate authentication.
+pub struct SurrealAuthScope(String);
+
+impl Header for SurrealAuthScope {
+	fn name() -> &'static HeaderName {
+		&AUTH_SC
+	}
+
+	fn decode<'i, I>(values: &mut I) -> Result<Self, headers::Error>
+	where
+		I: Iterator<Item = &'i HeaderValue>,
+	{
+		let value = values.next().ok_or_else(headers::Error::invalid)?;
+		let value = value.to_str().map_err(|_| headers::Error::invalid())?.to_string();
+
+		Ok(SurrealAuthScope(value))
+	}
+
+	fn encode<E>(&self, values: &mut E)
+	where
+		E: Extend<HeaderValue>,
+	{
+		values.extend(std::iter::once(self.into()));
+	}
+}
+
+impl std::ops::Deref for SurrealAuthScope {
+	type Target = String;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl From<SurrealAuthScope> for HeaderValue {
+	fn from(value: SurrealAuthScope) -> Self {
+		HeaderValue::from(&value)
+	}
+}
+
+impl From<&SurrealAuthScope> for HeaderValue {
+	fn from(value: &SurrealAuthScope) -> Self {
+		HeaderValue::from_str(value.0.as_str()).unwrap()
+	}
+}
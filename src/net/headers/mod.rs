@@ -14,6 +14,8 @@ use tower_http::set_header::SetResponseHeaderLayer;
 mod accept;
 mod auth_db;
 mod auth_ns;
+#[cfg(feature = "sql2")]
+mod auth_sc;
 mod db;
 mod id;
 mod ns;
@@ -21,6 +23,8 @@ mod ns;
 pub use accept::Accept;
 pub use auth_db::SurrealAuthDatabase;
 pub use auth_ns::SurrealAuthNamespace;
+#[cfg(feature = "sql2")]
+pub use auth_sc::SurrealAuthScope;
 pub use db::{SurrealDatabase, SurrealDatabaseLegacy};
 pub use id::{SurrealId, SurrealIdLegacy};
 pub use ns::{SurrealNamespace, SurrealNamespaceLegacy};
@@ -8,12 +8,14 @@ mod input;
 mod key;
 mod output;
 mod params;
+mod refresh;
 mod rpc;
 mod signals;
 mod signin;
 mod signup;
 mod sql;
 mod sync;
+mod tls;
 mod tracer;
 mod version;
 
@@ -163,6 +165,7 @@ pub async fn init(ct: CancellationToken) -> Result<(), Error> {
 		.merge(sync::router())
 		.merge(sql::router())
 		.merge(signin::router())
+		.merge(refresh::router())
 		.merge(signup::router())
 		.merge(key::router());
 
@@ -177,8 +180,22 @@ pub async fn init(ct: CancellationToken) -> Result<(), Error> {
 	let shutdown_handler = graceful_shutdown(ct.clone(), handle.clone());
 	// Spawn a task to handle notifications
 	tokio::spawn(async move { notifications(ct.clone()).await });
+	// If a client CA is specified then setup mutual TLS
+	if let (Some(cert), Some(key), Some(ca)) = (&opt.crt, &opt.key, &opt.client_ca) {
+		// Configure certificate, private key, and client CA used by https
+		let tls = tls::config(cert, key, ca).await?;
+		// Setup the Axum server with TLS, verifying the certificates of clients
+		let server = axum_server::bind(opt.bind).acceptor(tls::ClientCertAcceptor::new(tls));
+		// Log the server startup to the CLI
+		info!(target: LOG, "Started web server on {} requiring client certificates", &opt.bind);
+		// Start the server and listen for connections
+		server
+			.handle(handle)
+			.serve(axum_app.into_make_service_with_connect_info::<SocketAddr>())
+			.await?;
+	}
 	// If a certificate and key are specified then setup TLS
-	if let (Some(cert), Some(key)) = (&opt.crt, &opt.key) {
+	else if let (Some(cert), Some(key)) = (&opt.crt, &opt.key) {
 		// Configure certificate and private key used by https
 		let tls = RustlsConfig::from_pem_file(cert, key).await.unwrap();
 		// Setup the Axum server with TLS
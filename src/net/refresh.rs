@@ -0,0 +1,129 @@
+use crate::dbs::DB;
+use crate::err::Error;
+use crate::net::input::bytes_to_utf8;
+use crate::net::output;
+use axum::extract::DefaultBodyLimit;
+use axum::response::IntoResponse;
+use axum::routing::options;
+use axum::{Extension, Router, TypedHeader};
+use bytes::Bytes;
+use http_body::Body as HttpBody;
+use serde::Serialize;
+use surrealdb::dbs::Session;
+use surrealdb::sql::Value;
+use tower_http::limit::RequestBodyLimitLayer;
+
+use super::headers::Accept;
+
+const MAX: usize = 1024; // 1 KiB
+
+#[derive(Serialize)]
+struct Success {
+	code: u16,
+	details: String,
+	token: Option<String>,
+	refresh: Option<String>,
+}
+
+impl Success {
+	fn new(details: &str, token: Option<String>, refresh: Option<String>) -> Success {
+		Success {
+			token,
+			refresh,
+			code: 200,
+			details: String::from(details),
+		}
+	}
+}
+
+pub(super) fn router<S, B>() -> Router<S, B>
+where
+	B: HttpBody + Send + 'static,
+	B::Data: Send,
+	B::Error: std::error::Error + Send + Sync + 'static,
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new()
+		.route("/refresh", options(|| async {}).post(refresh))
+		.route("/revoke", options(|| async {}).post(revoke))
+		.route_layer(DefaultBodyLimit::disable())
+		.layer(RequestBodyLimitLayer::new(MAX))
+}
+
+async fn refresh(
+	Extension(mut session): Extension<Session>,
+	accept: Option<TypedHeader<Accept>>,
+	body: Bytes,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+	// Get a database reference
+	let kvs = DB.get().unwrap();
+	// Convert the HTTP body into text
+	let data = bytes_to_utf8(&body)?;
+	// Parse the provided data as JSON
+	let token = match surrealdb::sql::json(data) {
+		Ok(Value::Object(mut vars)) => match vars.remove("refresh") {
+			Some(Value::Strand(v)) => v.0,
+			_ => return Err(Error::Request),
+		},
+		// The provided value was not an object
+		_ => return Err(Error::Request),
+	};
+	match surrealdb::iam::refresh::refresh(kvs, &mut session, &token).await.map_err(Error::from) {
+		// Authentication was successful
+		Ok(v) => {
+			let res = Success::new("Authentication succeeded", v.token, v.refresh);
+			match accept.as_deref() {
+				// Simple serialization
+				Some(Accept::ApplicationJson) => Ok(output::json(&res)),
+				Some(Accept::ApplicationCbor) => Ok(output::cbor(&res)),
+				Some(Accept::ApplicationPack) => Ok(output::pack(&res)),
+				// Internal serialization
+				Some(Accept::Surrealdb) => Ok(output::full(&res)),
+				// Return nothing
+				None => Ok(output::none()),
+				// An incorrect content-type was requested
+				_ => Err(Error::InvalidType),
+			}
+		}
+		// There was an error with authentication
+		Err(err) => Err(err),
+	}
+}
+
+async fn revoke(
+	Extension(session): Extension<Session>,
+	accept: Option<TypedHeader<Accept>>,
+	body: Bytes,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+	// Get a database reference
+	let kvs = DB.get().unwrap();
+	// Without a body, the sessions of the authenticated user are revoked
+	let subject = match body.is_empty() {
+		true => None,
+		false => match surrealdb::sql::json(bytes_to_utf8(&body)?) {
+			Ok(Value::Object(vars)) => Some(vars),
+			// The provided value was not an object
+			_ => return Err(Error::Request),
+		},
+	};
+	match surrealdb::iam::refresh::revoke(kvs, &session, subject).await.map_err(Error::from) {
+		// The sessions were revoked
+		Ok(()) => {
+			let res = Success::new("Revocation succeeded", None, None);
+			match accept.as_deref() {
+				// Simple serialization
+				Some(Accept::ApplicationJson) => Ok(output::json(&res)),
+				Some(Accept::ApplicationCbor) => Ok(output::cbor(&res)),
+				Some(Accept::ApplicationPack) => Ok(output::pack(&res)),
+				// Internal serialization
+				Some(Accept::Surrealdb) => Ok(output::full(&res)),
+				// Return nothing
+				None => Ok(output::none()),
+				// An incorrect content-type was requested
+				_ => Err(Error::InvalidType),
+			}
+		}
+		// There was an error with the revocation
+		Err(err) => Err(err),
+	}
+}
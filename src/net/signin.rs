@@ -12,6 +12,7 @@ use bytes::Bytes;
 use http_body::Body as HttpBody;
 use serde::Serialize;
 use surrealdb::dbs::Session;
+use surrealdb::iam::refresh::Tokens;
 use surrealdb::sql::Value;
 use tower_http::limit::RequestBodyLimitLayer;
 
@@ -24,12 +25,14 @@ struct Success {
 	code: u16,
 	details: String,
 	token: Option<String>,
+	refresh: Option<String>,
 }
 
 impl Success {
-	fn new(token: Option<String>) -> Success {
+	fn new(tokens: Tokens) -> Success {
 		Success {
-			token,
+			token: tokens.token,
+			refresh: tokens.refresh,
 			code: 200,
 			details: String::from("Authentication succeeded"),
 		}
@@ -62,7 +65,9 @@ async fn handler(
 	match surrealdb::sql::json(data) {
 		// The provided value was an object
 		Ok(Value::Object(vars)) => {
-			match surrealdb::iam::signin::signin(kvs, &mut session, vars).await.map_err(Error::from)
+			match surrealdb::iam::signin::signin_with_refresh(kvs, &mut session, vars)
+				.await
+				.map_err(Error::from)
 			{
 				// Authentication was successful
 				Ok(v) => match accept.as_deref() {
@@ -71,7 +76,7 @@ async fn handler(
 					Some(Accept::ApplicationCbor) => Ok(output::cbor(&Success::new(v))),
 					Some(Accept::ApplicationPack) => Ok(output::pack(&Success::new(v))),
 					// Text serialization
-					Some(Accept::TextPlain) => Ok(output::text(v.unwrap_or_default())),
+					Some(Accept::TextPlain) => Ok(output::text(v.token.unwrap_or_default())),
 					// Internal serialization
 					Some(Accept::Surrealdb) => Ok(output::full(&Success::new(v))),
 					// Return nothing
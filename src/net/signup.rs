@@ -22,12 +22,14 @@ struct Success {
 	code: u16,
 	details: String,
 	token: Option<String>,
+	refresh: Option<String>,
 }
 
 impl Success {
 	fn new(token: Option<String>) -> Success {
 		Success {
 			token,
+			refresh: None,
 			code: 200,
 			details: String::from("Authentication succeeded"),
 		}
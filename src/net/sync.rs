@@ -1,20 +1,101 @@
+use crate::dbs::DB;
+use crate::err::Error;
+use crate::net::input::bytes_to_utf8;
+use crate::net::output;
+use axum::extract::DefaultBodyLimit;
 use axum::response::IntoResponse;
-use axum::routing::get;
-use axum::Router;
+use axum::routing::options;
+use axum::{Extension, Router, TypedHeader};
+use axum_extra::extract::Query;
+use bytes::Bytes;
 use http_body::Body as HttpBody;
+use serde::Deserialize;
+use surrealdb::dbs::Session;
+use surrealdb::kvs::sync::Push;
+use surrealdb::sql::Value;
+use tower_http::limit::RequestBodyLimitLayer;
+
+use super::headers::Accept;
+
+const MAX: usize = 1024 * 1024 * 10; // 10 MiB
+
+#[derive(Default, Deserialize, Debug, Clone)]
+struct PullOptions {
+	pub since: Option<u64>,
+	pub limit: Option<u32>,
+}
 
 pub(super) fn router<S, B>() -> Router<S, B>
 where
 	B: HttpBody + Send + 'static,
+	B::Data: Send,
+	B::Error: std::error::Error + Send + Sync + 'static,
 	S: Clone + Send + Sync + 'static,
 {
-	Router::new().route("/sync", get(save).post(load))
+	Router::new()
+		.route("/sync", options(|| async {}).get(pull).post(push))
+		.route_layer(DefaultBodyLimit::disable())
+		.layer(RequestBodyLimitLayer::new(MAX))
 }
 
-async fn load() -> impl IntoResponse {
-	"Load"
+async fn pull(
+	Extension(session): Extension<Session>,
+	accept: Option<TypedHeader<Accept>>,
+	Query(query): Query<PullOptions>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+	// Get the datastore reference
+	let db = DB.get().unwrap();
+	// Read the changes since the versionstamp
+	match db.sync_pull(&session, query.since.unwrap_or(0), query.limit).await {
+		Ok(res) => {
+			let res = Value::from(res);
+			match accept.as_deref() {
+				// Simple serialization
+				Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
+				Some(Accept::ApplicationCbor) => Ok(output::cbor(&output::simplify(res))),
+				Some(Accept::ApplicationPack) => Ok(output::pack(&output::simplify(res))),
+				// Internal serialization
+				Some(Accept::Surrealdb) => Ok(output::full(&res)),
+				// An incorrect content-type was requested
+				_ => Err(Error::InvalidType),
+			}
+		}
+		// There was an error when reading the changes
+		Err(err) => Err(Error::from(err)),
+	}
 }
 
-async fn save() -> impl IntoResponse {
-	"Save"
+async fn push(
+	Extension(session): Extension<Session>,
+	accept: Option<TypedHeader<Accept>>,
+	body: Bytes,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+	// Get the datastore reference
+	let db = DB.get().unwrap();
+	// Convert the HTTP request body
+	let data = bytes_to_utf8(&body)?;
+	// Parse the request body as the changes to push
+	let push = match surrealdb::sql::value(data) {
+		Ok(data) => Push::try_from(data).map_err(Error::from)?,
+		Err(_) => return Err(Error::Request),
+	};
+	// Apply the changes
+	match db.sync_push(&session, push).await {
+		Ok(res) => {
+			// Return the records with conflicting changes
+			let res = Value::from(res.into_iter().map(Value::from).collect::<Vec<_>>());
+			match accept.as_deref() {
+				// Simple serialization
+				Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
+				Some(Accept::ApplicationCbor) => Ok(output::cbor(&output::simplify(res))),
+				Some(Accept::ApplicationPack) => Ok(output::pack(&output::simplify(res))),
+				// Internal serialization
+				Some(Accept::Surrealdb) => Ok(output::full(&res)),
+				// An incorrect content-type was requested
+				_ => Err(Error::InvalidType),
+			}
+		}
+		// There was an error when applying the changes
+		Err(err) => Err(Error::from(err)),
+	}
 }
@@ -0,0 +1,192 @@
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
+use futures::future::BoxFuture;
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{Certificate, PrivateKey, RootCertStore, ServerConfig};
+use rustls_pemfile::Item;
+use std::io::{self, ErrorKind};
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::Arc;
+use surrealdb::iam::certificate::Certificate as ClientCertificate;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tower_http::add_extension::AddExtension;
+use x509_parser::extensions::GeneralName;
+use x509_parser::objects::{oid2abbrev, oid_registry};
+use x509_parser::prelude::{FromDer, X509Certificate, X509Name};
+
+/// Configures TLS, requiring clients to present a certificate which is signed by the CA
+pub(super) async fn config(crt: &Path, key: &Path, ca: &Path) -> io::Result<RustlsConfig> {
+	let crt = rustls_pemfile::certs(&mut tokio::fs::read(crt).await?.as_ref())?;
+	let key = match rustls_pemfile::read_one(&mut tokio::fs::read(key).await?.as_ref())? {
+		Some(Item::RSAKey(key)) | Some(Item::PKCS8Key(key)) | Some(Item::ECKey(key)) => key,
+		_ => {
+			return Err(io::Error::new(ErrorKind::InvalidData, "private key format not supported"))
+		}
+	};
+	let mut roots = RootCertStore::empty();
+	for ca in rustls_pemfile::certs(&mut tokio::fs::read(ca).await?.as_ref())? {
+		roots.add(&Certificate(ca)).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+	}
+	let mut config = ServerConfig::builder()
+		.with_safe_defaults()
+		.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+		.with_single_cert(crt.into_iter().map(Certificate).collect(), PrivateKey(key))
+		.map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+	config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+	Ok(RustlsConfig::from_config(Arc::new(config)))
+}
+
+///
+/// ClientCertAcceptor completes the TLS handshake of a connection, and adds the
+/// identities of the verified client certificate to each request of the connection.
+///
+#[derive(Clone)]
+pub(super) struct ClientCertAcceptor {
+	inner: RustlsAcceptor,
+}
+
+impl ClientCertAcceptor {
+	pub(super) fn new(config: RustlsConfig) -> Self {
+		Self {
+			inner: RustlsAcceptor::new(config),
+		}
+	}
+}
+
+impl<I, S> Accept<I, S> for ClientCertAcceptor
+where
+	I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+	S: Send + 'static,
+{
+	type Stream = TlsStream<I>;
+	type Service = AddExtension<S, Option<Arc<ClientCertificate>>>;
+	type Future = BoxFuture<'static, io::Result<(Self::Stream, Self::Service)>>;
+
+	fn accept(&self, stream: I, service: S) -> Self::Future {
+		let acceptor = self.inner.clone();
+		Box::pin(async move {
+			let (stream, service) = Accept::<I, S>::accept(&acceptor, stream, service).await?;
+			let cert = stream
+				.get_ref()
+				.1
+				.peer_certificates()
+				.and_then(|v| v.first())
+				.and_then(|v| identities(&v.0))
+				.map(Arc::new);
+			Ok((stream, AddExtension::new(service, cert)))
+		})
+	}
+}
+
+/// Reads the subject and the subject alternative names of a certificate
+fn identities(der: &[u8]) -> Option<ClientCertificate> {
+	let (_, cert) = X509Certificate::from_der(der).ok()?;
+	let san = match cert.subject_alternative_name() {
+		Ok(Some(ext)) => ext.value.general_names.iter().filter_map(general_name).collect(),
+		_ => Vec::new(),
+	};
+	Some(ClientCertificate {
+		subject: distinguished_name(cert.subject())?,
+		san,
+	})
+}
+
+/// Formats a distinguished name as a string, as described in RFC 4514
+///
+/// Returns `None` if the value of any attribute can not be decoded as a string,
+/// so that a certificate is never identified by a partial subject.
+fn distinguished_name(name: &X509Name) -> Option<String> {
+	let rdns: Vec<_> = name.iter().collect();
+	let rdns = rdns
+		.iter()
+		.rev()
+		.map(|rdn| {
+			let attrs = rdn
+				.iter()
+				.map(|v| {
+					let key = match oid2abbrev(v.attr_type(), oid_registry()) {
+						Ok(key) => key.to_owned(),
+						Err(_) => v.attr_type().to_id_string(),
+					};
+					Some(format!("{key}={}", escape(v.as_str().ok()?)))
+				})
+				.collect::<Option<Vec<_>>>()?;
+			Some(attrs.join("+"))
+		})
+		.collect::<Option<Vec<_>>>()?;
+	Some(rdns.join(","))
+}
+
+/// Escapes the value of an attribute, as described in RFC 4514
+fn escape(value: &str) -> String {
+	let last = value.chars().count().saturating_sub(1);
+	let mut out = String::with_capacity(value.len());
+	for (i, c) in value.chars().enumerate() {
+		match c {
+			'"' | '+' | ',' | ';' | '<' | '>' | '\\' => out.push('\\'),
+			'#' if i == 0 => out.push('\\'),
+			' ' if i == 0 || i == last => out.push('\\'),
+			'\0' => {
+				out.push_str("\\00");
+				continue;
+			}
+			_ => {}
+		}
+		out.push(c);
+	}
+	out
+}
+
+/// Formats a subject alternative name as a string
+fn general_name(name: &GeneralName) -> Option<String> {
+	match name {
+		GeneralName::DNSName(v) | GeneralName::URI(v) | GeneralName::RFC822Name(v) => {
+			Some(v.to_string())
+		}
+		GeneralName::IPAddress(v) => match v.len() {
+			4 => Some(IpAddr::from(<[u8; 4]>::try_from(*v).ok()?).to_string()),
+			16 => Some(IpAddr::from(<[u8; 16]>::try_from(*v).ok()?).to_string()),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn certificate_identities() {
+		let mut params = rcgen::CertificateParams::new(vec!["api.prod".to_owned()]);
+		params.distinguished_name = rcgen::DistinguishedName::new();
+		params.distinguished_name.push(rcgen::DnType::OrganizationName, "mesh");
+		params.distinguished_name.push(rcgen::DnType::CommonName, "api");
+		params.subject_alt_names.push(rcgen::SanType::URI("spiffe://mesh/ns/prod/sa/api".into()));
+		params.subject_alt_names.push(rcgen::SanType::IpAddress("10.0.0.1".parse().unwrap()));
+		let cert = rcgen::Certificate::from_params(params).unwrap();
+		let res = identities(&cert.serialize_der().unwrap()).unwrap();
+		assert_eq!(res.subject, "CN=api,O=mesh");
+		assert_eq!(res.san, vec!["api.prod", "spiffe://mesh/ns/prod/sa/api", "10.0.0.1"]);
+	}
+
+	#[test]
+	fn certificate_identities_escaped() {
+		let mut params = rcgen::CertificateParams::new(vec![]);
+		params.distinguished_name = rcgen::DistinguishedName::new();
+		params.distinguished_name.push(rcgen::DnType::CommonName, " api,O=mesh#");
+		let cert = rcgen::Certificate::from_params(params).unwrap();
+		let res = identities(&cert.serialize_der().unwrap()).unwrap();
+		assert_eq!(res.subject, "CN=\\ api\\,O=mesh#");
+	}
+
+	#[test]
+	fn escape_values() {
+		assert_eq!(escape("api"), "api");
+		assert_eq!(escape("#a b "), "\\#a b\\ ");
+		assert_eq!(escape("a+b;c<d>e\"f\\g"), "a\\+b\\;c\\<d\\>e\\\"f\\\\g");
+		assert_eq!(escape("a\0b"), "a\\00b");
+	}
+}
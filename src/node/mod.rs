@@ -21,6 +21,13 @@ pub fn init(ct: CancellationToken) -> JoinHandle<()> {
 
 	tokio::spawn(async move {
 		loop {
+			// Deliver webhooks in the background, so that slow endpoints don't delay the tick
+			#[cfg(feature = "sql2")]
+			tokio::spawn(async move {
+				if let Err(e) = dbs.deliver_webhooks().await {
+					error!("Error delivering webhooks: {}", e);
+				}
+			});
 			if let Err(e) = dbs.tick().await {
 				error!("Error running node agent tick: {}", e);
 			}
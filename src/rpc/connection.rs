@@ -1,30 +1,50 @@
 use crate::cnf::PKG_NAME;
 use crate::cnf::PKG_VERSION;
-use crate::cnf::{WEBSOCKET_MAX_CONCURRENT_REQUESTS, WEBSOCKET_PING_FREQUENCY};
+use crate::cnf::{
+	WEBSOCKET_CHANGEFEED_BATCH_SIZE, WEBSOCKET_CHANGEFEED_FREQUENCY,
+	WEBSOCKET_MAX_CONCURRENT_REQUESTS, WEBSOCKET_PING_FREQUENCY,
+};
 use crate::dbs::DB;
 use crate::err::Error;
 use crate::rpc::args::Take;
 use crate::rpc::failure::Failure;
 use crate::rpc::format::Format;
-use crate::rpc::response::{failure, Data, IntoRpcResponse};
+use crate::rpc::operation::Operation;
+use crate::rpc::request::Request;
+use crate::rpc::response::{failure, success, Data, IntoRpcResponse};
 use crate::rpc::{CONN_CLOSED_ERR, LIVE_QUERIES, WEBSOCKETS};
 use crate::telemetry;
+use crate::telemetry::metrics::ws::NotificationContext;
 use crate::telemetry::metrics::ws::RequestContext;
 use crate::telemetry::traces::rpc::span_for_request;
 use axum::extract::ws::{Message, WebSocket};
+use futures_util::future::BoxFuture;
 use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
 use opentelemetry::trace::FutureExt;
 use opentelemetry::Context as TelemetryContext;
 use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::sync::Arc;
 use surrealdb::channel::{self, Receiver, Sender};
+use surrealdb::dbs::ChangeNotification;
 use surrealdb::dbs::QueryType;
 use surrealdb::dbs::Response;
 use surrealdb::dbs::Session;
+use surrealdb::error::Db as SurrealDbError;
+use surrealdb::kvs::sync::Push;
+use surrealdb::sql::statements::BeginStatement;
+use surrealdb::sql::statements::CommitStatement;
+use surrealdb::sql::statements::SetStatement;
+use surrealdb::sql::statements::ShowSince;
+use surrealdb::sql::statements::ShowStatement;
 use surrealdb::sql::Array;
 use surrealdb::sql::Object;
+use surrealdb::sql::Query;
+use surrealdb::sql::Statement;
+use surrealdb::sql::Statements;
 use surrealdb::sql::Strand;
+use surrealdb::sql::Table;
 use surrealdb::sql::Value;
 use tokio::sync::{RwLock, Semaphore};
 use tokio::task::JoinSet;
@@ -41,6 +61,7 @@ pub struct Connection {
 	pub(crate) limiter: Arc<Semaphore>,
 	pub(crate) canceller: CancellationToken,
 	pub(crate) channels: (Sender<Message>, Receiver<Message>),
+	pub(crate) changefeeds: HashMap<Uuid, CancellationToken>,
 }
 
 impl Connection {
@@ -57,6 +78,7 @@ impl Connection {
 			limiter: Arc::new(Semaphore::new(*WEBSOCKET_MAX_CONCURRENT_REQUESTS)),
 			canceller: CancellationToken::new(),
 			channels: channel::bounded(*WEBSOCKET_MAX_CONCURRENT_REQUESTS),
+			changefeeds: HashMap::new(),
 		}))
 	}
 
@@ -372,6 +394,23 @@ impl Connection {
 				}
 				_ => Err(Failure::INVALID_PARAMS),
 			},
+			// Exchange a refresh token for new authentication tokens
+			"refresh" => match params.needs_one() {
+				Ok(Value::Strand(v)) => {
+					rpc.write().await.refresh(v).await.map(Into::into).map_err(Into::into)
+				}
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			// Revoke the sessions of a user, or of every user of a scope
+			"revoke" => match params.len() {
+				0 => rpc.read().await.revoke(None).await.map(Into::into).map_err(Into::into),
+				_ => match params.needs_one() {
+					Ok(Value::Object(v)) => {
+						rpc.read().await.revoke(Some(v)).await.map(Into::into).map_err(Into::into)
+					}
+					_ => Err(Failure::INVALID_PARAMS),
+				},
+			},
 			// Invalidate the current authentication session
 			"invalidate" => match params.len() {
 				0 => rpc.write().await.invalidate().await.map(Into::into).map_err(Into::into),
@@ -386,7 +425,24 @@ impl Connection {
 			},
 			// Kill a live query using a query id
 			"kill" => match params.needs_one() {
-				Ok(v) => rpc.read().await.kill(v).await.map(Into::into).map_err(Into::into),
+				Ok(v) => rpc.write().await.kill(v).await.map(Into::into).map_err(Into::into),
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			// Subscribe to the changefeed of a table or database
+			"changefeed" => match params.needs_one_two_or_three() {
+				Ok((v, s, i)) if v.is_none_or_null() || v.is_table() || v.is_strand() => {
+					rpc.write().await.changefeed(v, s, i).await.map(Into::into).map_err(Into::into)
+				}
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			// Pull the changes made to the database since a versionstamp
+			"pull" => match params.needs_one_or_two() {
+				Ok((v, l)) => rpc.read().await.pull(v, l).await.map(Into::into).map_err(Into::into),
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			// Push a batch of changes made by a replica of the database
+			"push" => match params.needs_one() {
+				Ok(v) => rpc.read().await.push(v).await.map(Into::into).map_err(Into::into),
 				_ => Err(Failure::INVALID_PARAMS),
 			},
 			// Setup a live query on a specific table
@@ -413,51 +469,18 @@ impl Connection {
 				}
 				_ => Err(Failure::INVALID_PARAMS),
 			},
-			// Select a value or values from the database
-			"select" => match params.needs_one() {
-				Ok(v) => rpc.read().await.select(v).await.map(Into::into).map_err(Into::into),
-				_ => Err(Failure::INVALID_PARAMS),
+			// Select, insert, create, update, merge, patch, delete or relate records, or run a function
+			method if Operation::is_method(method) => match Operation::new(method, params) {
+				Ok(op) => rpc.read().await.operate(op).await.map(Into::into).map_err(Into::into),
+				Err(err) => Err(err),
 			},
-			// Insert a value or values in the database
-			"insert" => match params.needs_one_or_two() {
-				Ok((v, o)) => {
-					rpc.read().await.insert(v, o).await.map(Into::into).map_err(Into::into)
+			// Run several requests, optionally within a single transaction
+			"batch" => match params.needs_one_or_two() {
+				Ok((Value::Array(v), t)) if t.is_none_or_null() || t.is_bool() => {
+					Connection::batch(rpc, v, t.is_true()).await.map(Into::into)
 				}
 				_ => Err(Failure::INVALID_PARAMS),
 			},
-			// Create a value or values in the database
-			"create" => match params.needs_one_or_two() {
-				Ok((v, o)) => {
-					rpc.read().await.create(v, o).await.map(Into::into).map_err(Into::into)
-				}
-				_ => Err(Failure::INVALID_PARAMS),
-			},
-			// Update a value or values in the database using `CONTENT`
-			"update" => match params.needs_one_or_two() {
-				Ok((v, o)) => {
-					rpc.read().await.update(v, o).await.map(Into::into).map_err(Into::into)
-				}
-				_ => Err(Failure::INVALID_PARAMS),
-			},
-			// Update a value or values in the database using `MERGE`
-			"merge" => match params.needs_one_or_two() {
-				Ok((v, o)) => {
-					rpc.read().await.merge(v, o).await.map(Into::into).map_err(Into::into)
-				}
-				_ => Err(Failure::INVALID_PARAMS),
-			},
-			// Update a value or values in the database using `PATCH`
-			"patch" => match params.needs_one_two_or_three() {
-				Ok((v, o, d)) => {
-					rpc.read().await.patch(v, o, d).await.map(Into::into).map_err(Into::into)
-				}
-				_ => Err(Failure::INVALID_PARAMS),
-			},
-			// Delete a value or values from the database
-			"delete" => match params.needs_one() {
-				Ok(v) => rpc.read().await.delete(v).await.map(Into::into).map_err(Into::into),
-				_ => Err(Failure::INVALID_PARAMS),
-			},
 			// Get the current server version
 			"version" => match params.len() {
 				0 => Ok(format!("{PKG_NAME}-{}", *PKG_VERSION).into()),
@@ -501,12 +524,26 @@ impl Connection {
 
 	async fn signin(&mut self, vars: Object) -> Result<Value, Error> {
 		let kvs = DB.get().unwrap();
-		surrealdb::iam::signin::signin(kvs, &mut self.session, vars)
+		surrealdb::iam::signin::signin_with_refresh(kvs, &mut self.session, vars)
 			.await
 			.map(Into::into)
 			.map_err(Into::into)
 	}
 
+	async fn refresh(&mut self, token: Strand) -> Result<Value, Error> {
+		let kvs = DB.get().unwrap();
+		surrealdb::iam::refresh::refresh(kvs, &mut self.session, &token.0)
+			.await
+			.map(Into::into)
+			.map_err(Into::into)
+	}
+
+	async fn revoke(&self, subject: Option<Object>) -> Result<Value, Error> {
+		let kvs = DB.get().unwrap();
+		surrealdb::iam::refresh::revoke(kvs, &self.session, subject).await?;
+		Ok(Value::None)
+	}
+
 	async fn invalidate(&mut self) -> Result<Value, Error> {
 		surrealdb::iam::clear::clear(&mut self.session)?;
 		Ok(Value::None)
@@ -566,7 +603,12 @@ impl Connection {
 	// Methods for live queries
 	// ------------------------------
 
-	async fn kill(&self, id: Value) -> Result<Value, Error> {
+	async fn kill(&mut self, id: Value) -> Result<Value, Error> {
+		// Check if this is a changefeed subscription
+		if let Some(canceller) = subscription_id(&id).and_then(|id| self.changefeeds.remove(&id)) {
+			canceller.cancel();
+			return Ok(Value::None);
+		}
 		// Specify the SQL query string
 		let sql = "KILL $id";
 		// Specify the query parameters
@@ -606,149 +648,159 @@ impl Connection {
 	}
 
 	// ------------------------------
-	// Methods for selecting
+	// Methods for changefeeds
 	// ------------------------------
 
-	async fn select(&self, what: Value) -> Result<Value, Error> {
-		// Return a single result?
-		let one = what.is_thing();
-		// Get a database reference
-		let kvs = DB.get().unwrap();
-		// Specify the SQL query string
-		let sql = "SELECT * FROM $what";
-		// Specify the query parameters
-		let var = Some(map! {
-			String::from("what") => what.could_be_table(),
-			=> &self.vars
-		});
-		// Execute the query on the database
-		let mut res = kvs.execute(sql, &self.session, var).await?;
-		// Extract the first query result
-		let res = match one {
-			true => res.remove(0).result?.first(),
-			false => res.remove(0).result?,
+	async fn changefeed(&mut self, what: Value, since: Value, id: Value) -> Result<Value, Error> {
+		// Subscribe to a specific table, or to the whole database
+		let table = match what.could_be_table() {
+			Value::Table(v) => Some(v),
+			_ => None,
 		};
-		// Return the result to the client
-		Ok(res)
+		// Send all changes committed at or after this versionstamp
+		let since = match since {
+			Value::None | Value::Null => 0,
+			v => u128::try_from(v)?,
+		};
+		// Clients can specify the subscription id, so that they can resume it when reconnecting
+		let id = match id {
+			Value::None | Value::Null => Uuid::new_v4(),
+			v => subscription_id(&v).ok_or(Error::Request)?,
+		};
+		// Check that the changefeed can be read
+		changes(&self.session, &table, since).await?;
+		// Replace any existing subscription with the same id
+		if let Some(canceller) = self.changefeeds.remove(&id) {
+			canceller.cancel();
+		}
+		// The subscription is stopped when the WebSocket closes
+		let canceller = self.canceller.child_token();
+		self.changefeeds.insert(id, canceller.clone());
+		trace!("Registered changefeed subscription {} on websocket {}", id, self.id);
+		// Stream the changes to the client in the background
+		tokio::spawn(Self::stream_changes(
+			id,
+			self.session.clone(),
+			table,
+			since,
+			self.format,
+			self.channels.0.clone(),
+			canceller,
+		));
+		// Return the subscription id
+		Ok(Value::from(id))
 	}
 
-	// ------------------------------
-	// Methods for inserting
-	// ------------------------------
-
-	async fn insert(&self, what: Value, data: Value) -> Result<Value, Error> {
-		// Return a single result?
-		let one = what.is_thing();
-		// Get a database reference
-		let kvs = DB.get().unwrap();
-		// Specify the SQL query string
-		let sql = "INSERT INTO $what $data RETURN AFTER";
-		// Specify the query parameters
-		let var = Some(map! {
-			String::from("what") => what.could_be_table(),
-			String::from("data") => data,
-			=> &self.vars
-		});
-		// Execute the query on the database
-		let mut res = kvs.execute(sql, &self.session, var).await?;
-		// Extract the first query result
-		let res = match one {
-			true => res.remove(0).result?.first(),
-			false => res.remove(0).result?,
-		};
-		// Return the result to the client
-		Ok(res)
+	/// Send the changes of a changefeed subscription to the client as they are committed
+	async fn stream_changes(
+		id: Uuid,
+		session: Session,
+		table: Option<Table>,
+		mut since: u128,
+		fmt: Format,
+		chn: Sender<Message>,
+		canceller: CancellationToken,
+	) {
+		// Create the interval ticker
+		let mut interval = tokio::time::interval(WEBSOCKET_CHANGEFEED_FREQUENCY);
+		interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+		// Add metrics
+		let not_ctx = NotificationContext::default().with_live_id(id.to_string());
+		let cx = Arc::new(TelemetryContext::new().with_value(not_ctx));
+		// Loop, and check for new changes
+		loop {
+			tokio::select! {
+				//
+				biased;
+				// Check if this has been cancelled
+				_ = canceller.cancelled() => break,
+				// Check for new changes regularly
+				_ = interval.tick() => {
+					// Read all of the changes committed since the last check
+					while !canceller.is_cancelled() {
+						let mut sets = match changes(&session, &table, since).await {
+							Ok(v) => v,
+							Err(err) => {
+								// Let the client know the subscription has failed
+								failure(None, Failure::from(err)).send(cx.clone(), fmt, &chn).await;
+								return;
+							}
+						};
+						// The last change set might have been cut short by the
+						// batch limit, so it is read again with the next batch
+						if sets.len() > 1 {
+							sets.pop();
+						}
+						// Wait for more changes once we have caught up
+						if sets.is_empty() {
+							break;
+						}
+						// Send the changes to the client, waiting
+						// whenever the client is not keeping up
+						for (versionstamp, changes) in sets {
+							since = versionstamp + 1;
+							let notification = ChangeNotification {
+								id: id.into(),
+								versionstamp,
+								changes,
+							};
+							success(None, notification).send(cx.clone(), fmt, &chn).await;
+						}
+					}
+				},
+			}
+		}
+		trace!("Stopped changefeed subscription {}", id);
 	}
 
 	// ------------------------------
-	// Methods for creating
+	// Methods for replication
 	// ------------------------------
 
-	async fn create(&self, what: Value, data: Value) -> Result<Value, Error> {
-		// Return a single result?
-		let one = what.is_thing();
+	async fn pull(&self, since: Value, limit: Value) -> Result<Value, Error> {
 		// Get a database reference
 		let kvs = DB.get().unwrap();
-		// Specify the SQL query string
-		let sql = if data.is_none_or_null() {
-			"CREATE $what RETURN AFTER"
-		} else {
-			"CREATE $what CONTENT $data RETURN AFTER"
+		// Read the changes made since this versionstamp
+		let since = match since {
+			Value::None | Value::Null => 0,
+			v => u64::try_from(v)?,
 		};
-		// Specify the query parameters
-		let var = Some(map! {
-			String::from("what") => what.could_be_table(),
-			String::from("data") => data,
-			=> &self.vars
-		});
-		// Execute the query on the database
-		let mut res = kvs.execute(sql, &self.session, var).await?;
-		// Extract the first query result
-		let res = match one {
-			true => res.remove(0).result?.first(),
-			false => res.remove(0).result?,
+		// Read at most this number of changes
+		let limit = match limit {
+			Value::None | Value::Null => None,
+			v => Some(u32::try_from(v)?),
 		};
+		// Read the changes from the database
+		let res = kvs.sync_pull(&self.session, since, limit).await?;
 		// Return the result to the client
-		Ok(res)
+		Ok(res.into())
 	}
 
-	// ------------------------------
-	// Methods for updating
-	// ------------------------------
-
-	async fn update(&self, what: Value, data: Value) -> Result<Value, Error> {
-		// Return a single result?
-		let one = what.is_thing();
+	async fn push(&self, push: Value) -> Result<Value, Error> {
 		// Get a database reference
 		let kvs = DB.get().unwrap();
-		// Specify the SQL query string
-		let sql = if data.is_none_or_null() {
-			"UPDATE $what RETURN AFTER"
-		} else {
-			"UPDATE $what CONTENT $data RETURN AFTER"
-		};
-		// Specify the query parameters
-		let var = Some(map! {
-			String::from("what") => what.could_be_table(),
-			String::from("data") => data,
-			=> &self.vars
-		});
-		// Execute the query on the database
-		let mut res = kvs.execute(sql, &self.session, var).await?;
-		// Extract the first query result
-		let res = match one {
-			true => res.remove(0).result?.first(),
-			false => res.remove(0).result?,
-		};
-		// Return the result to the client
-		Ok(res)
+		// Parse the changes to push
+		let push = Push::try_from(push)?;
+		// Apply the changes to the database
+		let res = kvs.sync_push(&self.session, push).await?;
+		// Return the records with conflicting changes
+		Ok(res.into_iter().map(Value::from).collect::<Vec<_>>().into())
 	}
 
 	// ------------------------------
-	// Methods for merging
+	// Methods for data operations
 	// ------------------------------
 
-	async fn merge(&self, what: Value, data: Value) -> Result<Value, Error> {
-		// Return a single result?
-		let one = what.is_thing();
+	async fn operate(&self, op: Operation) -> Result<Value, Error> {
 		// Get a database reference
 		let kvs = DB.get().unwrap();
-		// Specify the SQL query string
-		let sql = if data.is_none_or_null() {
-			"UPDATE $what RETURN AFTER"
-		} else {
-			"UPDATE $what MERGE $data RETURN AFTER"
-		};
 		// Specify the query parameters
-		let var = Some(map! {
-			String::from("what") => what.could_be_table(),
-			String::from("data") => data,
-			=> &self.vars
-		});
+		let mut var = self.vars.clone();
+		var.extend(op.vars);
 		// Execute the query on the database
-		let mut res = kvs.execute(sql, &self.session, var).await?;
+		let mut res = kvs.process(op.query, &self.session, Some(var)).await?;
 		// Extract the first query result
-		let res = match one {
+		let res = match op.one {
 			true => res.remove(0).result?.first(),
 			false => res.remove(0).result?,
 		};
@@ -756,62 +808,97 @@ impl Connection {
 		Ok(res)
 	}
 
-	// ------------------------------
-	// Methods for patching
-	// ------------------------------
-
-	async fn patch(&self, what: Value, data: Value, diff: Value) -> Result<Value, Error> {
-		// Return a single result?
-		let one = what.is_thing();
+	async fn transaction(&self, ops: Vec<Operation>) -> Result<Vec<Result<Value, Failure>>, Error> {
 		// Get a database reference
 		let kvs = DB.get().unwrap();
-		// Specify the SQL query string
-		let sql = match diff.is_true() {
-			true => "UPDATE $what PATCH $data RETURN DIFF",
-			false => "UPDATE $what PATCH $data RETURN AFTER",
-		};
-		// Specify the query parameters
-		let var = Some(map! {
-			String::from("what") => what.could_be_table(),
-			String::from("data") => data,
-			=> &self.vars
-		});
+		// The parameters of each operation are set with LET statements, so that
+		// they do not clash with the parameters of the other operations
+		let mut stms = vec![Statement::Begin(BeginStatement)];
+		let mut out = Vec::with_capacity(ops.len());
+		for op in ops {
+			out.push((op.one, op.vars.len()));
+			for (name, what) in op.vars {
+				stms.push(Statement::Set(SetStatement {
+					name,
+					what,
+				}));
+			}
+			stms.extend(op.query.0 .0);
+		}
+		stms.push(Statement::Commit(CommitStatement));
 		// Execute the query on the database
-		let mut res = kvs.execute(sql, &self.session, var).await?;
-		// Extract the first query result
-		let res = match one {
-			true => res.remove(0).result?.first(),
-			false => res.remove(0).result?,
-		};
-		// Return the result to the client
+		let query = Query(Statements(stms));
+		let mut res = kvs.process(query, &self.session, Some(self.vars.clone())).await?.into_iter();
+		// Extract the result of each operation, skipping the LET statements
+		let res = out
+			.into_iter()
+			.map(|(one, lets)| {
+				let res = match res.nth(lets) {
+					Some(v) => v.result,
+					None => Err(SurrealDbError::QueryNotExecuted),
+				};
+				let res = res.map_err(|e| Failure::from(Error::from(e)));
+				match one {
+					true => res.map(|v| v.first()),
+					false => res,
+				}
+			})
+			.collect();
+		// Return the results to the client
 		Ok(res)
 	}
 
 	// ------------------------------
-	// Methods for deleting
+	// Methods for batches
 	// ------------------------------
 
-	async fn delete(&self, what: Value) -> Result<Value, Error> {
-		// Return a single result?
-		let one = what.is_thing();
-		// Get a database reference
-		let kvs = DB.get().unwrap();
-		// Specify the SQL query string
-		let sql = "DELETE $what RETURN BEFORE";
-		// Specify the query parameters
-		let var = Some(map! {
-			String::from("what") => what.could_be_table(),
-			=> &self.vars
-		});
-		// Execute the query on the database
-		let mut res = kvs.execute(sql, &self.session, var).await?;
-		// Extract the first query result
-		let res = match one {
-			true => res.remove(0).result?.first(),
-			false => res.remove(0).result?,
-		};
-		// Return the result to the client
-		Ok(res)
+	// Requests in a batch are processed recursively, so the future is boxed
+	fn batch(
+		rpc: Arc<RwLock<Connection>>,
+		calls: Array,
+		transaction: bool,
+	) -> BoxFuture<'static, Result<Value, Failure>> {
+		Box::pin(async move {
+			// Parse the requests in the batch
+			let calls = calls.into_iter().map(Request::try_from);
+			// Run the requests one after the other
+			if !transaction {
+				let mut out = Vec::new();
+				for req in calls {
+					let res = match req {
+						Ok(req) if req.method == "batch" => {
+							failure(req.id, Failure::custom("Batches can not be nested"))
+						}
+						Ok(req) => {
+							Connection::process_message(rpc.clone(), &req.method, req.params)
+								.await
+								.into_response(req.id)
+						}
+						Err(err) => failure(None, err),
+					};
+					out.push(res.into_value());
+				}
+				return Ok(out.into());
+			}
+			// Only data methods can run within a transaction
+			let mut ids = Vec::new();
+			let mut ops = Vec::new();
+			for req in calls {
+				let req = req?;
+				if !Operation::is_method(&req.method) {
+					return Err(Failure::custom(format!(
+						"The method '{}' can not run within a transaction",
+						req.method
+					)));
+				}
+				ops.push(Operation::new(&req.method, req.params)?);
+				ids.push(req.id);
+			}
+			// Run the data methods within a single transaction
+			let res = rpc.read().await.transaction(ops).await?;
+			let out = ids.into_iter().zip(res).map(|(id, res)| res.into_response(id).into_value());
+			Ok(out.collect::<Vec<_>>().into())
+		})
 	}
 
 	// ------------------------------
@@ -881,3 +968,45 @@ impl Connection {
 		}
 	}
 }
+
+/// Reads the change sets which were committed at or after a versionstamp
+async fn changes(
+	session: &Session,
+	table: &Option<Table>,
+	since: u128,
+) -> Result<Vec<(u128, Value)>, Error> {
+	// Get a database reference
+	let kvs = DB.get().unwrap();
+	// SHOW CHANGES starts from the higher 64 bits of a versionstamp,
+	// so any change sets before the requested versionstamp are skipped
+	let stm = ShowStatement {
+		table: table.clone(),
+		since: ShowSince::Versionstamp((since >> 16) as u64),
+		limit: Some(*WEBSOCKET_CHANGEFEED_BATCH_SIZE),
+	};
+	// Execute the query on the database
+	let query = Query(Statements(vec![Statement::Show(stm)]));
+	let mut res = kvs.process(query, session, None).await?;
+	// Extract the change sets
+	let mut out = Vec::new();
+	if let Value::Array(sets) = res.remove(0).result? {
+		for set in sets {
+			if let Value::Object(mut set) = set {
+				let versionstamp = u128::try_from(set.remove("versionstamp").unwrap_or_default())?;
+				if versionstamp >= since {
+					out.push((versionstamp, set.remove("changes").unwrap_or_default()));
+				}
+			}
+		}
+	}
+	Ok(out)
+}
+
+/// Parses the id of a changefeed subscription
+fn subscription_id(id: &Value) -> Option<Uuid> {
+	match id {
+		Value::Uuid(v) => Some(v.0),
+		Value::Strand(v) => Uuid::parse_str(v.as_str()).ok(),
+		_ => None,
+	}
+}
@@ -3,12 +3,16 @@ use revision::revisioned;
 use revision::Revisioned;
 use serde::Serialize;
 use std::borrow::Cow;
+use surrealdb::error::Db as SurrealDbError;
 use surrealdb::sql::Value;
+use surrealdb::Error as SurrealError;
 
 #[derive(Clone, Debug, Serialize)]
 pub struct Failure {
 	pub(crate) code: i64,
 	pub(crate) message: Cow<'static, str>,
+	/// The details of the error, which clients can process
+	pub(crate) data: Option<Value>,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -18,16 +22,34 @@ struct Inner {
 	message: String,
 }
 
+#[derive(Clone, Debug, Serialize)]
+#[revisioned(revision = 2)]
+struct InnerWithData {
+	code: i64,
+	message: String,
+	#[revision(start = 2)]
+	data: Option<Value>,
+}
+
 impl Revisioned for Failure {
 	fn serialize_revisioned<W: std::io::Write>(
 		&self,
 		writer: &mut W,
 	) -> Result<(), revision::Error> {
-		let inner = Inner {
-			code: self.code,
-			message: self.message.as_ref().to_owned(),
-		};
-		inner.serialize_revisioned(writer)
+		// Errors without details are sent as before, so older clients can read them
+		match &self.data {
+			None => Inner {
+				code: self.code,
+				message: self.message.as_ref().to_owned(),
+			}
+			.serialize_revisioned(writer),
+			Some(data) => InnerWithData {
+				code: self.code,
+				message: self.message.as_ref().to_owned(),
+				data: Some(data.clone()),
+			}
+			.serialize_revisioned(writer),
+		}
 	}
 
 	fn deserialize_revisioned<R: std::io::Read>(_reader: &mut R) -> Result<Self, revision::Error> {
@@ -35,7 +57,7 @@ impl Revisioned for Failure {
 	}
 
 	fn revision() -> u16 {
-		1
+		2
 	}
 }
 
@@ -47,17 +69,35 @@ impl From<&str> for Failure {
 
 impl From<Error> for Failure {
 	fn from(err: Error) -> Self {
-		Failure::custom(err.to_string())
+		match err {
+			// Unique index violations detail the record which holds the value
+			Error::Db(SurrealError::Db(SurrealDbError::IndexExists {
+				ref thing,
+				ref index,
+				ref value,
+			})) => Failure {
+				data: Some(Value::from(map! {
+					String::from("thing") => Value::from(thing.clone()),
+					String::from("index") => Value::from(index.clone()),
+					String::from("value") => Value::from(value.clone()),
+				})),
+				..Failure::custom(err.to_string())
+			},
+			err => Failure::custom(err.to_string()),
+		}
 	}
 }
 
 impl From<Failure> for Value {
 	fn from(err: Failure) -> Self {
-		map! {
+		let mut res = map! {
 			String::from("code") => Value::from(err.code),
 			String::from("message") => Value::from(err.message.to_string()),
+		};
+		if let Some(data) = err.data {
+			res.insert(String::from("data"), data);
 		}
-		.into()
+		res.into()
 	}
 }
 
@@ -66,26 +106,31 @@ impl Failure {
 	pub const PARSE_ERROR: Failure = Failure {
 		code: -32700,
 		message: Cow::Borrowed("Parse error"),
+		data: None,
 	};
 
 	pub const INVALID_REQUEST: Failure = Failure {
 		code: -32600,
 		message: Cow::Borrowed("Invalid Request"),
+		data: None,
 	};
 
 	pub const METHOD_NOT_FOUND: Failure = Failure {
 		code: -32601,
 		message: Cow::Borrowed("Method not found"),
+		data: None,
 	};
 
 	pub const INVALID_PARAMS: Failure = Failure {
 		code: -32602,
 		message: Cow::Borrowed("Invalid params"),
+		data: None,
 	};
 
 	pub const INTERNAL_ERROR: Failure = Failure {
 		code: -32603,
 		message: Cow::Borrowed("Internal error"),
+		data: None,
 	};
 
 	pub fn custom<S>(message: S) -> Failure
@@ -95,6 +140,7 @@ impl Failure {
 		Failure {
 			code: -32000,
 			message: message.into(),
+			data: None,
 		}
 	}
 }
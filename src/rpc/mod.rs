@@ -2,6 +2,7 @@ pub mod args;
 pub mod connection;
 pub mod failure;
 pub mod format;
+pub mod operation;
 pub mod request;
 pub mod response;
 
@@ -14,6 +15,7 @@ use opentelemetry::Context as TelemetryContext;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use surrealdb::dbs::Action;
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
@@ -44,6 +46,8 @@ pub(crate) async fn notifications(canceller: CancellationToken) {
 				_ = canceller.cancelled() => break,
 				// Receive a notification on the channel
 				Ok(notification) = channel.recv() => {
+					let live_id = notification.id.0;
+					let killed = notification.action == Action::Killed;
 					// Find which WebSocket the notification belongs to
 					if let Some(id) = LIVE_QUERIES.read().await.get(&notification.id) {
 						// Check to see if the WebSocket exists
@@ -63,6 +67,10 @@ pub(crate) async fn notifications(canceller: CancellationToken) {
 							message.send(cx, format, &sender).await
 						}
 					}
+					// Killed live queries will not receive any further notifications
+					if killed {
+						LIVE_QUERIES.write().await.remove(&live_id);
+					}
 				},
 			}
 		}
@@ -0,0 +1,268 @@
+use crate::err::Error;
+use crate::rpc::args::Take;
+use crate::rpc::failure::Failure;
+use std::collections::BTreeMap;
+use surrealdb::sql::statements::RelateStatement;
+use surrealdb::sql::Array;
+use surrealdb::sql::Data;
+use surrealdb::sql::Function;
+use surrealdb::sql::Model;
+use surrealdb::sql::Output;
+use surrealdb::sql::Query;
+use surrealdb::sql::Statement;
+use surrealdb::sql::Statements;
+use surrealdb::sql::Value;
+
+/// A data method, as the query which runs it on the database
+pub struct Operation {
+	/// Whether the method returns a single record
+	pub one: bool,
+	/// The query which runs the method
+	pub query: Query,
+	/// The parameters of the query
+	pub vars: BTreeMap<String, Value>,
+}
+
+impl Operation {
+	/// Check if this is the name of a data method
+	pub fn is_method(method: &str) -> bool {
+		matches!(
+			method,
+			"select"
+				| "insert" | "create"
+				| "update" | "merge"
+				| "patch" | "delete"
+				| "relate" | "run"
+		)
+	}
+
+	/// Prepare a data method with its parameters
+	pub fn new(method: &str, params: Array) -> Result<Operation, Failure> {
+		match method {
+			// Select a value or values from the database
+			"select" => match params.needs_one() {
+				Ok(v) => Operation::select(v),
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			// Insert a value or values in the database
+			"insert" => match params.needs_one_or_two() {
+				Ok((v, o)) => Operation::insert(v, o),
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			// Create a value or values in the database
+			"create" => match params.needs_one_or_two() {
+				Ok((v, o)) => Operation::create(v, o),
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			// Update a value or values in the database using `CONTENT`
+			"update" => match params.needs_one_or_two() {
+				Ok((v, o)) => Operation::update(v, o),
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			// Update a value or values in the database using `MERGE`
+			"merge" => match params.needs_one_or_two() {
+				Ok((v, o)) => Operation::merge(v, o),
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			// Update a value or values in the database using `PATCH`
+			"patch" => match params.needs_one_two_or_three() {
+				Ok((v, o, d)) => Operation::patch(v, o, d),
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			// Delete a value or values from the database
+			"delete" => match params.needs_one() {
+				Ok(v) => Operation::delete(v),
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			// Relate records with a graph edge
+			"relate" => match params.needs_three_or_four() {
+				Ok((f, k, w, d)) if k.is_table() || k.is_thing() || k.is_strand() => {
+					Ok(Operation::relate(f, k, w, d))
+				}
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			// Run a user-defined, machine learning, or builtin function
+			"run" => match params.needs_one_two_or_three() {
+				Ok((Value::Strand(n), v, a))
+					if (v.is_none_or_null() || v.is_strand())
+						&& (a.is_none_or_null() || a.is_array()) =>
+				{
+					Operation::run(n.to_raw(), v, a)
+				}
+				_ => Err(Failure::INVALID_PARAMS),
+			},
+			_ => Err(Failure::METHOD_NOT_FOUND),
+		}
+	}
+
+	fn select(what: Value) -> Result<Operation, Failure> {
+		Ok(Operation {
+			// Return a single result?
+			one: what.is_thing(),
+			// Specify the SQL query string
+			query: parse("SELECT * FROM $what")?,
+			// Specify the query parameters
+			vars: map! {
+				String::from("what") => what.could_be_table(),
+			},
+		})
+	}
+
+	fn insert(what: Value, data: Value) -> Result<Operation, Failure> {
+		Ok(Operation {
+			// Return a single result?
+			one: what.is_thing(),
+			// Specify the SQL query string
+			query: parse("INSERT INTO $what $data RETURN AFTER")?,
+			// Specify the query parameters
+			vars: map! {
+				String::from("what") => what.could_be_table(),
+				String::from("data") => data,
+			},
+		})
+	}
+
+	fn create(what: Value, data: Value) -> Result<Operation, Failure> {
+		// Specify the SQL query string
+		let sql = if data.is_none_or_null() {
+			"CREATE $what RETURN AFTER"
+		} else {
+			"CREATE $what CONTENT $data RETURN AFTER"
+		};
+		Ok(Operation {
+			// Return a single result?
+			one: what.is_thing(),
+			query: parse(sql)?,
+			// Specify the query parameters
+			vars: map! {
+				String::from("what") => what.could_be_table(),
+				String::from("data") => data,
+			},
+		})
+	}
+
+	fn update(what: Value, data: Value) -> Result<Operation, Failure> {
+		// Specify the SQL query string
+		let sql = if data.is_none_or_null() {
+			"UPDATE $what RETURN AFTER"
+		} else {
+			"UPDATE $what CONTENT $data RETURN AFTER"
+		};
+		Ok(Operation {
+			// Return a single result?
+			one: what.is_thing(),
+			query: parse(sql)?,
+			// Specify the query parameters
+			vars: map! {
+				String::from("what") => what.could_be_table(),
+				String::from("data") => data,
+			},
+		})
+	}
+
+	fn merge(what: Value, data: Value) -> Result<Operation, Failure> {
+		// Specify the SQL query string
+		let sql = if data.is_none_or_null() {
+			"UPDATE $what RETURN AFTER"
+		} else {
+			"UPDATE $what MERGE $data RETURN AFTER"
+		};
+		Ok(Operation {
+			// Return a single result?
+			one: what.is_thing(),
+			query: parse(sql)?,
+			// Specify the query parameters
+			vars: map! {
+				String::from("what") => what.could_be_table(),
+				String::from("data") => data,
+			},
+		})
+	}
+
+	fn patch(what: Value, data: Value, diff: Value) -> Result<Operation, Failure> {
+		// Specify the SQL query string
+		let sql = match diff.is_true() {
+			true => "UPDATE $what PATCH $data RETURN DIFF",
+			false => "UPDATE $what PATCH $data RETURN AFTER",
+		};
+		Ok(Operation {
+			// Return a single result?
+			one: what.is_thing(),
+			query: parse(sql)?,
+			// Specify the query parameters
+			vars: map! {
+				String::from("what") => what.could_be_table(),
+				String::from("data") => data,
+			},
+		})
+	}
+
+	fn delete(what: Value) -> Result<Operation, Failure> {
+		Ok(Operation {
+			// Return a single result?
+			one: what.is_thing(),
+			// Specify the SQL query string
+			query: parse("DELETE $what RETURN BEFORE")?,
+			// Specify the query parameters
+			vars: map! {
+				String::from("what") => what.could_be_table(),
+			},
+		})
+	}
+
+	fn relate(from: Value, kind: Value, with: Value, data: Value) -> Operation {
+		// The edge table can not be a parameter, so the statement is built directly
+		let stm = RelateStatement {
+			// Return a single result?
+			only: from.is_thing() && with.is_thing(),
+			kind: kind.could_be_table(),
+			from,
+			with,
+			data: match data {
+				Value::None | Value::Null => None,
+				data => Some(Data::ContentExpression(data)),
+			},
+			output: Some(Output::After),
+			..Default::default()
+		};
+		Operation {
+			one: false,
+			query: Query(Statements(vec![Statement::Relate(stm)])),
+			vars: BTreeMap::new(),
+		}
+	}
+
+	fn run(name: String, version: Value, args: Value) -> Result<Operation, Failure> {
+		// Specify the function arguments
+		let args = match args {
+			Value::Array(Array(args)) => args,
+			_ => Vec::new(),
+		};
+		// Specify the function to call
+		let fnc = match (name.strip_prefix("fn::"), name.strip_prefix("ml::"), version) {
+			(Some(name), _, Value::None | Value::Null) => {
+				Value::Function(Box::new(Function::Custom(name.to_owned(), args)))
+			}
+			(_, Some(name), Value::Strand(version)) => Value::Model(Box::new(Model {
+				name: name.to_owned(),
+				version: version.to_raw(),
+				args,
+			})),
+			(None, None, Value::None | Value::Null) => {
+				Value::Function(Box::new(Function::Normal(name, args)))
+			}
+			// Only machine learning models have a version
+			_ => return Err(Failure::INVALID_PARAMS),
+		};
+		Ok(Operation {
+			one: false,
+			query: Query(Statements(vec![Statement::Value(fnc)])),
+			vars: BTreeMap::new(),
+		})
+	}
+}
+
+/// Parses the query which runs a data method
+fn parse(sql: &str) -> Result<Query, Failure> {
+	surrealdb::sql::parse(sql).map_err(|e| Error::from(e).into())
+}
@@ -9,6 +9,7 @@ use serde_json::Value as Json;
 use std::sync::Arc;
 use surrealdb::channel::Sender;
 use surrealdb::dbs;
+use surrealdb::dbs::ChangeNotification;
 use surrealdb::dbs::Notification;
 use surrealdb::sql;
 use surrealdb::sql::Value;
@@ -26,6 +27,8 @@ pub enum Data {
 	Query(Vec<dbs::Response>),
 	/// Live queries return a notification
 	Live(Notification),
+	/// Changefeed subscriptions return the committed changes
+	Changes(ChangeNotification),
 	// Add new variants here
 }
 
@@ -47,6 +50,12 @@ impl From<Notification> for Data {
 	}
 }
 
+impl From<ChangeNotification> for Data {
+	fn from(n: ChangeNotification) -> Self {
+		Data::Changes(n)
+	}
+}
+
 impl From<Vec<dbs::Response>> for Data {
 	fn from(v: Vec<dbs::Response>) -> Self {
 		Data::Query(v)
@@ -58,6 +67,13 @@ impl From<Data> for Value {
 		match val {
 			Data::Query(v) => sql::to_value(v).unwrap(),
 			Data::Live(v) => sql::to_value(v).unwrap(),
+			// Versionstamps are returned as integers, as with SHOW CHANGES
+			Data::Changes(v) => map! {
+				"id" => Value::from(v.id),
+				"versionstamp" => Value::from(v.versionstamp),
+				"changes" => v.changes,
+			}
+			.into(),
 			Data::Other(v) => v,
 		}
 	}
@@ -254,6 +254,76 @@ mod cli_integration {
 		assert!(output.contains("Started web server"), "couldn't start web server: {output}");
 	}
 
+	#[test(tokio::test)]
+	async fn changefeed_tail() {
+		let (addr, server) = common::start_server_with_defaults().await.unwrap();
+		let creds = format!("--user {USER} --pass {PASS}");
+		let ns = Ulid::new();
+		let db = Ulid::new();
+		let dir = common::tmp_file("changes");
+		let checkpoint = common::tmp_file("checkpoint");
+		let sql_args = format!("sql --conn http://{addr} {creds} --ns {ns} --db {db} --multi");
+		let tail_args = format!(
+			"changefeed tail --conn ws://{addr} {creds} --ns {ns} --db {db} --table thing --output {dir} --checkpoint {checkpoint}"
+		);
+		// Reads all of the exported files, in order
+		let read = || {
+			let mut files: Vec<_> = fs::read_dir(&dir)
+				.map(|d| d.map(|e| e.unwrap().path()).collect())
+				.unwrap_or_default();
+			files.sort();
+			files.iter().map(|f| fs::read_to_string(f).unwrap()).collect::<String>()
+		};
+
+		info!("* Create a record in a table with a changefeed");
+		{
+			let input = "DEFINE TABLE thing CHANGEFEED 1h; CREATE thing:one;\n";
+			common::run(&sql_args).input(input).output().expect("failed to create a record");
+		}
+
+		info!("* Export the changes to files");
+		{
+			let tail = common::run(&tail_args);
+			for _ in 0..20 {
+				if read().contains("thing:one") {
+					break;
+				}
+				sleep(ONE_SEC).await;
+			}
+			tail.finish();
+			let output = read();
+			assert_eq!(output.lines().count(), 2, "unexpected changes: {output}");
+			assert!(output.contains("define_table"), "unexpected changes: {output}");
+			assert!(output.contains("\"update\":{\"id\":\"thing:one\"}"), "{output}");
+		}
+
+		info!("* Resume the export from the checkpoint");
+		{
+			let stored: u128 = fs::read_to_string(&checkpoint).unwrap().trim().parse().unwrap();
+			let last: serde_json::Value =
+				serde_json::from_str(read().lines().last().unwrap()).unwrap();
+			assert_eq!(last["versionstamp"], json!(stored as u64));
+
+			let input = "CREATE thing:two;\n";
+			common::run(&sql_args).input(input).output().expect("failed to create a record");
+
+			let tail = common::run(&tail_args);
+			for _ in 0..20 {
+				if read().contains("thing:two") {
+					break;
+				}
+				sleep(ONE_SEC).await;
+			}
+			tail.finish();
+			let output = read();
+			// The changes which were already exported are not exported again
+			assert_eq!(output.lines().count(), 3, "unexpected changes: {output}");
+			assert_eq!(output.matches("thing:one").count(), 1, "unexpected changes: {output}");
+			assert!(output.contains("\"update\":{\"id\":\"thing:two\"}"), "{output}");
+		}
+		server.finish()
+	}
+
 	#[test(tokio::test)]
 	async fn with_root_auth() {
 		// Commands with credentials when auth is enabled, should succeed
@@ -241,6 +241,40 @@ async fn authenticate() -> Result<(), Box<dyn std::error::Error>> {
 	Ok(())
 }
 
+#[test(tokio::test)]
+async fn refresh_and_revoke() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection, and request a refresh token
+	let res = socket.send_request("signin", json!([{ "user": USER, "pass": PASS, "refresh": true }])).await?;
+	let refresh = res["result"]["refresh"].as_str().unwrap().to_owned();
+	// Disconnect the connection
+	socket.close().await?;
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Send REFRESH command
+	let res = socket.send_request("refresh", json!([refresh])).await?;
+	let token = res["result"]["token"].as_str().unwrap().to_owned();
+	assert_ne!(res["result"]["refresh"].as_str(), Some(refresh.as_str()), "result: {:?}", res);
+	// Verify we have an authenticated session
+	let res = socket.send_message_query("DEFINE NAMESPACE test").await?;
+	assert_eq!(res[0]["status"], "OK", "result: {:?}", res);
+	// Verify the refresh token can only be used once
+	let res = socket.send_request("refresh", json!([refresh])).await?;
+	assert!(res["error"].is_object(), "result: {:?}", res);
+	// Send REVOKE command
+	let res = socket.send_request("revoke", json!([])).await?;
+	assert!(res["error"].is_null(), "result: {:?}", res);
+	// Verify the access token was revoked
+	let res = socket.send_request("authenticate", json!([token])).await?;
+	assert!(res["error"].is_object(), "result: {:?}", res);
+	// Test passed
+	server.finish();
+	Ok(())
+}
+
 #[test(tokio::test)]
 async fn letset() -> Result<(), Box<dyn std::error::Error>> {
 	// Setup database server
@@ -585,6 +619,171 @@ async fn query() -> Result<(), Box<dyn std::error::Error>> {
 	Ok(())
 }
 
+#[test(tokio::test)]
+async fn create_unique_violation() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await?;
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await?;
+	// Define a unique index, and a record holding a value
+	socket
+		.send_message_query(
+			"DEFINE INDEX email ON person FIELDS email UNIQUE; CREATE person:tobie SET email = 'tobie@surrealdb.com'",
+		)
+		.await?;
+	// Send CREATE command with the same value
+	let res = socket
+		.send_request("create", json!(["person:jaime", { "email": "tobie@surrealdb.com" }]))
+		.await?;
+	assert!(res["error"].is_object(), "result: {:?}", res);
+	// The error details the record which holds the value
+	let data = &res["error"]["data"];
+	assert_eq!(data["thing"], "person:tobie", "result: {:?}", res);
+	assert_eq!(data["index"], "email", "result: {:?}", res);
+	assert_eq!(data["value"], "'tobie@surrealdb.com'", "result: {:?}", res);
+	// Test passed
+	server.finish();
+	Ok(())
+}
+
+#[test(tokio::test)]
+async fn relate() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await?;
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await?;
+	// Create the test records
+	socket.send_message_query("CREATE person:tobie; CREATE person:jaime").await?;
+	// Send RELATE command
+	let res = socket
+		.send_request("relate", json!(["person:tobie", "knows", "person:jaime", { "since": 2015 }]))
+		.await?;
+	assert!(res.is_object(), "result: {:?}", res);
+	assert!(res["result"].is_object(), "result: {:?}", res);
+	let res = res["result"].as_object().unwrap();
+	assert_eq!(res["in"], "person:tobie", "result: {:?}", res);
+	assert_eq!(res["out"], "person:jaime", "result: {:?}", res);
+	assert_eq!(res["since"], 2015, "result: {:?}", res);
+	// Verify the edge can be traversed
+	let res = socket.send_message_query("SELECT VALUE ->knows->person FROM person:tobie").await?;
+	assert_eq!(res[0]["result"], json!([["person:jaime"]]), "result: {:?}", res);
+	// Test passed
+	server.finish();
+	Ok(())
+}
+
+#[test(tokio::test)]
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await?;
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await?;
+	// Define a test function
+	socket.send_message_query("DEFINE FUNCTION fn::greet($name: string) { RETURN 'Hello ' + $name; }").await?;
+	// Send RUN command for a custom function
+	let res = socket.send_request("run", json!(["fn::greet", null, ["Tobie"]])).await?;
+	assert_eq!(res["result"], "Hello Tobie", "result: {:?}", res);
+	// Send RUN command for a builtin function
+	let res = socket.send_request("run", json!(["string::uppercase", null, ["tobie"]])).await?;
+	assert_eq!(res["result"], "TOBIE", "result: {:?}", res);
+	// Send RUN command with arguments of the wrong type
+	let res = socket.send_request("run", json!(["fn::greet", null, [1]])).await?;
+	assert!(res["error"].is_object(), "result: {:?}", res);
+	// Test passed
+	server.finish();
+	Ok(())
+}
+
+#[test(tokio::test)]
+async fn batch() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await?;
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await?;
+	// Send BATCH command
+	let res = socket
+		.send_request(
+			"batch",
+			json!([[
+				{ "id": 1, "method": "create", "params": ["tester:one", { "value": 1 }] },
+				{ "id": 2, "method": "create", "params": ["tester:one"] },
+				{ "id": 3, "method": "query", "params": ["SELECT VALUE value FROM tester"] },
+			]]),
+		)
+		.await?;
+	assert!(res["result"].is_array(), "result: {:?}", res);
+	let res = res["result"].as_array().unwrap();
+	assert_eq!(res.len(), 3, "result: {:?}", res);
+	assert_eq!(res[0]["id"], 1, "result: {:?}", res);
+	assert_eq!(res[0]["result"]["value"], 1, "result: {:?}", res);
+	assert_eq!(res[1]["id"], 2, "result: {:?}", res);
+	assert!(res[1]["error"].is_object(), "result: {:?}", res);
+	assert_eq!(res[2]["result"][0]["result"], json!([1]), "result: {:?}", res);
+	// Send BATCH command within a transaction which fails
+	let res = socket
+		.send_request(
+			"batch",
+			json!([
+				[
+					{ "method": "create", "params": ["tester:two"] },
+					{ "method": "create", "params": ["tester:one"] },
+				],
+				true
+			]),
+		)
+		.await?;
+	assert!(res["result"].is_array(), "result: {:?}", res);
+	let res = res["result"].as_array().unwrap();
+	assert_eq!(res.len(), 2, "result: {:?}", res);
+	assert!(res.iter().all(|v| v["error"].is_object()), "result: {:?}", res);
+	// Verify the transaction was cancelled
+	let res = socket.send_message_query("SELECT VALUE id FROM tester").await?;
+	assert_eq!(res[0]["result"], json!(["tester:one"]), "result: {:?}", res);
+	// Send BATCH command within a transaction
+	let res = socket
+		.send_request(
+			"batch",
+			json!([
+				[
+					{ "method": "create", "params": ["tester:two", { "value": 2 }] },
+					{ "method": "relate", "params": ["tester:one", "next", "tester:two"] },
+					{ "method": "select", "params": ["tester:two"] },
+				],
+				true
+			]),
+		)
+		.await?;
+	let res = res["result"].as_array().unwrap();
+	assert_eq!(res.len(), 3, "result: {:?}", res);
+	assert_eq!(res[0]["result"]["value"], 2, "result: {:?}", res);
+	assert_eq!(res[1]["result"]["out"], "tester:two", "result: {:?}", res);
+	assert_eq!(res[2]["result"]["value"], 2, "result: {:?}", res);
+	// Only data methods can run within a transaction
+	let res = socket
+		.send_request("batch", json!([[{ "method": "query", "params": ["INFO FOR DB"] }], true]))
+		.await?;
+	assert!(res["error"].is_object(), "result: {:?}", res);
+	// Test passed
+	server.finish();
+	Ok(())
+}
+
 #[test(tokio::test)]
 async fn version() -> Result<(), Box<dyn std::error::Error>> {
 	// Setup database server
@@ -810,6 +1009,64 @@ async fn kill() -> Result<(), Box<dyn std::error::Error>> {
 	Ok(())
 }
 
+#[test(tokio::test)]
+async fn changefeed() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await?;
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await?;
+	// Define a table with a changefeed
+	let res = socket.send_request("query", json!(["DEFINE TABLE tester CHANGEFEED 1h"])).await?;
+	assert!(res.is_object(), "result: {:?}", res);
+	assert!(res["result"].is_array(), "result: {:?}", res);
+	// Send CHANGEFEED command
+	let res = socket.send_request("changefeed", json!(["tester", 0])).await?;
+	assert!(res.is_object(), "result: {:?}", res);
+	assert!(res["result"].is_string(), "result: {:?}", res);
+	let feed = res["result"].as_str().unwrap().to_owned();
+	// Create a new test record
+	let res = socket.send_request("query", json!(["CREATE tester:one SET name = 'one'"])).await?;
+	assert!(res.is_object(), "result: {:?}", res);
+	assert!(res["result"].is_array(), "result: {:?}", res);
+	// Wait for the table definition and the record creation to arrive
+	let msgs = socket.receive_all_other_messages(2, Duration::from_secs(2)).await?;
+	assert!(msgs.iter().all(|v| v["error"].is_null()), "Unexpected error received: {:?}", msgs);
+	assert!(msgs.iter().all(|v| v["result"]["id"] == feed.as_str()), "result: {:?}", msgs);
+	let res = msgs[0]["result"]["changes"].as_array().unwrap();
+	assert!(res[0]["define_table"].is_object(), "result: {:?}", res);
+	let res = msgs[1]["result"]["changes"].as_array().unwrap();
+	assert_eq!(res[0]["update"]["id"], "tester:one", "result: {:?}", res);
+	let vs = msgs[1]["result"]["versionstamp"].as_u64().unwrap();
+	// Send KILL command
+	let res = socket.send_request("kill", json!([feed])).await?;
+	assert!(res.is_object(), "result: {:?}", res);
+	assert!(res["result"].is_null(), "result: {:?}", res);
+	// Create a new test record
+	let res = socket.send_request("query", json!(["CREATE tester:two SET name = 'two'"])).await?;
+	assert!(res.is_object(), "result: {:?}", res);
+	assert!(res["result"].is_array(), "result: {:?}", res);
+	// The subscription should not send any more changes
+	let res = socket.receive_all_other_messages(1, Duration::from_secs(1)).await;
+	assert!(res.is_err(), "Unexpected message received: {:?}", res);
+	// Resume the subscription right after the last received changes
+	let res = socket.send_request("changefeed", json!(["tester", vs + 1, feed])).await?;
+	assert!(res.is_object(), "result: {:?}", res);
+	assert_eq!(res["result"], feed.as_str(), "result: {:?}", res);
+	// Only the changes which were not received yet should arrive
+	let msgs = socket.receive_all_other_messages(1, Duration::from_secs(2)).await?;
+	assert_eq!(msgs[0]["result"]["id"], feed.as_str(), "result: {:?}", msgs);
+	let res = msgs[0]["result"]["changes"].as_array().unwrap();
+	assert_eq!(res.len(), 1, "result: {:?}", res);
+	assert_eq!(res[0]["update"]["id"], "tester:two", "result: {:?}", res);
+	// Test passed
+	server.finish();
+	Ok(())
+}
+
 #[test(tokio::test)]
 async fn live_second_connection() -> Result<(), Box<dyn std::error::Error>> {
 	// Setup database server
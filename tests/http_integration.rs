@@ -713,6 +713,65 @@ mod http_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn refresh_endpoint() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Signin and request a refresh token
+		let refresh = {
+			let req_body = json!({ "user": USER, "pass": PASS, "refresh": true }).to_string();
+			let res = client.post(format!("http://{addr}/signin")).body(req_body).send().await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			body["refresh"].as_str().unwrap().to_owned()
+		};
+
+		// Exchange the refresh token for new tokens
+		let req_body = json!({ "refresh": refresh }).to_string();
+		let token = {
+			let res =
+				client.post(format!("http://{addr}/refresh")).body(req_body.clone()).send().await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			assert_ne!(body["refresh"].as_str(), Some(refresh.as_str()), "body: {}", body);
+			body["token"].as_str().unwrap().to_owned()
+		};
+
+		// The refresh token can only be used once
+		{
+			let res = client.post(format!("http://{addr}/refresh")).body(req_body).send().await?;
+			assert_eq!(res.status(), 401, "body: {}", res.text().await?);
+		}
+
+		// Revoke the sessions of the user
+		{
+			let res =
+				client.post(format!("http://{addr}/revoke")).bearer_auth(&token).send().await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+
+		// The access token is rejected
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.bearer_auth(&token)
+				.body("INFO FOR ROOT")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 401, "body: {}", res.text().await?);
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn rpc_endpoint() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_with_defaults().await.unwrap();